@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// A custom tool compiled into the viewer from another crate: press Y, then
+// click meshes to drop markers on the hit points. Run with
+// `cargo run --example marker_tool -- some_mesh.obj`.
+
+use bevy::prelude::*;
+use bevy_inspector_egui::egui;
+use cgar_viewer::extension::{ToolPick, ViewerTool, ViewerToolsAppExt};
+
+#[derive(Default)]
+struct MarkerTool {
+    markers: Vec<Vec3>,
+    radius: f32,
+}
+
+impl ViewerTool for MarkerTool {
+    fn label(&self) -> &str {
+        "Marker"
+    }
+
+    fn hint(&self) -> &str {
+        "Click a mesh to drop a marker"
+    }
+
+    fn shortcut(&self) -> Option<(KeyCode, &str)> {
+        Some((KeyCode::KeyY, "Y"))
+    }
+
+    fn on_pick(&mut self, pick: ToolPick, _world: &mut World) {
+        if let Some(point) = pick.world_position {
+            info!("Marker {} at {}", self.markers.len(), point);
+            self.markers.push(point);
+        }
+    }
+
+    fn draw_gizmos(&self, gizmos: &mut Gizmos) {
+        for &point in &self.markers {
+            gizmos.sphere(
+                Isometry3d::from_translation(point),
+                self.radius,
+                Color::srgb(1.0, 0.3, 0.6),
+            );
+        }
+    }
+
+    fn options_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Slider::new(&mut self.radius, 0.005..=0.2).text("Size"));
+        if ui.button("Clear").clicked() {
+            self.markers.clear();
+        }
+    }
+}
+
+fn main() {
+    cgar_viewer::build_app()
+        .add_viewer_tool(MarkerTool {
+            radius: 0.02,
+            ..default()
+        })
+        .run();
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::color::{Color, Mix, Srgba};
+use serde::{Deserialize, Serialize};
+
+// Viridis, sampled at even steps (sRGB)
+const VIRIDIS: [[f32; 3]; 6] = [
+    [0.267, 0.005, 0.329],
+    [0.255, 0.267, 0.529],
+    [0.164, 0.471, 0.558],
+    [0.134, 0.659, 0.518],
+    [0.478, 0.821, 0.318],
+    [0.993, 0.906, 0.144],
+];
+
+// Blue through green to red for `t` in [0, 1]; values outside are clamped
+pub fn heat(t: f32) -> Color {
+    Color::hsl(240.0 * (1.0 - t.clamp(0.0, 1.0)), 0.9, 0.5)
+}
+
+// Color used for missing or non-finite scalar values
+pub const NO_DATA_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+// Further scientific maps, sampled at even steps (sRGB)
+const MAGMA: [[f32; 3]; 9] = [
+    [0.001, 0.000, 0.014],
+    [0.113, 0.065, 0.277],
+    [0.317, 0.072, 0.485],
+    [0.513, 0.139, 0.507],
+    [0.716, 0.215, 0.475],
+    [0.904, 0.320, 0.388],
+    [0.987, 0.536, 0.382],
+    [0.996, 0.761, 0.524],
+    [0.987, 0.991, 0.750],
+];
+const INFERNO: [[f32; 3]; 9] = [
+    [0.001, 0.000, 0.014],
+    [0.106, 0.047, 0.270],
+    [0.258, 0.039, 0.406],
+    [0.416, 0.090, 0.433],
+    [0.578, 0.148, 0.404],
+    [0.736, 0.216, 0.330],
+    [0.865, 0.317, 0.226],
+    [0.961, 0.489, 0.084],
+    [0.988, 0.998, 0.645],
+];
+const PLASMA: [[f32; 3]; 9] = [
+    [0.050, 0.030, 0.528],
+    [0.254, 0.014, 0.615],
+    [0.417, 0.001, 0.658],
+    [0.562, 0.052, 0.642],
+    [0.798, 0.280, 0.470],
+    [0.881, 0.393, 0.383],
+    [0.949, 0.518, 0.296],
+    [0.988, 0.652, 0.211],
+    [0.940, 0.975, 0.131],
+];
+const CIVIDIS: [[f32; 3]; 7] = [
+    [0.000, 0.135, 0.304],
+    [0.212, 0.259, 0.440],
+    [0.363, 0.373, 0.439],
+    [0.487, 0.486, 0.471],
+    [0.623, 0.605, 0.465],
+    [0.778, 0.733, 0.404],
+    [0.995, 0.906, 0.144],
+];
+const COOLWARM: [[f32; 3]; 3] = [[0.23, 0.3, 0.75], [0.87, 0.87, 0.87], [0.7, 0.02, 0.15]];
+const GRAYSCALE: [[f32; 3]; 2] = [[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RampStop {
+    pub position: f32,
+    // sRGB
+    pub color: [f32; 3],
+}
+
+// Piecewise-linear color ramp over [0, 1], blended in sRGB like the
+// bundled maps
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ColorRamp {
+    pub name: String,
+    // Sorted by position
+    pub stops: Vec<RampStop>,
+}
+
+impl ColorRamp {
+    fn from_samples(name: &str, samples: &[[f32; 3]]) -> Self {
+        let last = (samples.len() - 1).max(1) as f32;
+        Self {
+            name: name.to_string(),
+            stops: samples
+                .iter()
+                .enumerate()
+                .map(|(i, &color)| RampStop {
+                    position: i as f32 / last,
+                    color,
+                })
+                .collect(),
+        }
+    }
+
+    // Color for `t` in [0, 1]; values outside are clamped
+    pub fn sample(&self, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let color = |stop: &RampStop| Srgba::rgb(stop.color[0], stop.color[1], stop.color[2]);
+        let Some(first) = self.stops.first() else {
+            return NO_DATA_COLOR;
+        };
+        let upper = self.stops.partition_point(|stop| stop.position < t);
+        if upper == 0 {
+            return color(first).into();
+        }
+        let Some(b) = self.stops.get(upper) else {
+            return color(&self.stops[upper - 1]).into();
+        };
+        let a = &self.stops[upper - 1];
+        let span = (b.position - a.position).max(f32::EPSILON);
+        color(a).mix(&color(b), (t - a.position) / span).into()
+    }
+
+    pub fn sort(&mut self) {
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+    }
+}
+
+pub fn bundled_ramps() -> Vec<ColorRamp> {
+    vec![
+        ColorRamp::from_samples("Viridis", &VIRIDIS),
+        ColorRamp::from_samples("Magma", &MAGMA),
+        ColorRamp::from_samples("Inferno", &INFERNO),
+        ColorRamp::from_samples("Plasma", &PLASMA),
+        ColorRamp::from_samples("Cividis", &CIVIDIS),
+        ColorRamp::from_samples("Cool-warm", &COOLWARM),
+        ColorRamp::from_samples("Grayscale", &GRAYSCALE),
+    ]
+}
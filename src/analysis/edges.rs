@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::mesh::topology::MeshTopology;
+
+pub struct EdgeLengthReport {
+    // Undirected (min, max) edges with their lengths, shortest first
+    pub edges: Vec<((usize, usize), f64)>,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    // Edge counts over `bin_count` equal slices of [min, max]
+    pub bins: Vec<usize>,
+}
+
+impl EdgeLengthReport {
+    pub fn bin_width(&self) -> f64 {
+        (self.max - self.min) / self.bins.len().max(1) as f64
+    }
+
+    // Bin holding `length`; the maximum falls in the last bin
+    pub fn bin_of(&self, length: f64) -> usize {
+        let width = self.bin_width();
+        if width <= 0.0 {
+            return 0;
+        }
+        (((length - self.min) / width) as usize).min(self.bins.len().saturating_sub(1))
+    }
+
+    // Edges whose length lies in [lo, hi]
+    pub fn edges_between(&self, lo: f64, hi: f64) -> &[((usize, usize), f64)] {
+        let start = self.edges.partition_point(|(_, length)| *length < lo);
+        let end = self.edges.partition_point(|(_, length)| *length <= hi);
+        &self.edges[start..end.max(start)]
+    }
+}
+
+pub fn edge_length_stats(topology: &MeshTopology, bin_count: usize) -> EdgeLengthReport {
+    let mut edges: Vec<((usize, usize), f64)> = topology
+        .edge_faces
+        .keys()
+        .map(|&edge| (edge, topology.edge_length(edge)))
+        .collect();
+    edges.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let mut report = EdgeLengthReport {
+        min: edges.first().map_or(0.0, |(_, length)| *length),
+        max: edges.last().map_or(0.0, |(_, length)| *length),
+        mean: edges.iter().map(|(_, length)| length).sum::<f64>() / edges.len().max(1) as f64,
+        median: match edges.len() {
+            0 => 0.0,
+            n if n % 2 == 1 => edges[n / 2].1,
+            n => 0.5 * (edges[n / 2 - 1].1 + edges[n / 2].1),
+        },
+        bins: vec![0; bin_count.max(1)],
+        edges,
+    };
+    for i in 0..report.edges.len() {
+        let bin = report.bin_of(report.edges[i].1);
+        report.bins[bin] += 1;
+    }
+    report
+}
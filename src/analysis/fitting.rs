@@ -0,0 +1,316 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::{DVec2, DVec3};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FittedPrimitive {
+    Plane {
+        point: DVec3,
+        normal: DVec3,
+        // Half extent of the fitted points within the plane, for display
+        half_size: f64,
+    },
+    Sphere {
+        center: DVec3,
+        radius: f64,
+    },
+    Cylinder {
+        center: DVec3,
+        axis: DVec3,
+        radius: f64,
+        half_length: f64,
+    },
+}
+
+impl FittedPrimitive {
+    pub fn distance(&self, p: DVec3) -> f64 {
+        match *self {
+            FittedPrimitive::Plane { point, normal, .. } => (p - point).dot(normal),
+            FittedPrimitive::Sphere { center, radius } => p.distance(center) - radius,
+            FittedPrimitive::Cylinder {
+                center,
+                axis,
+                radius,
+                ..
+            } => {
+                let d = p - center;
+                (d - axis * d.dot(axis)).length() - radius
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FitReport {
+    pub primitive: FittedPrimitive,
+    pub rms: f64,
+    pub max_deviation: f64,
+    pub point_count: usize,
+}
+
+pub fn report(primitive: FittedPrimitive, points: &[DVec3]) -> FitReport {
+    let mut sum_sq = 0.0;
+    let mut max_deviation: f64 = 0.0;
+    for &p in points {
+        let d = primitive.distance(p);
+        sum_sq += d * d;
+        max_deviation = max_deviation.max(d.abs());
+    }
+    FitReport {
+        primitive,
+        rms: (sum_sq / points.len().max(1) as f64).sqrt(),
+        max_deviation,
+        point_count: points.len(),
+    }
+}
+
+pub fn centroid(points: &[DVec3]) -> DVec3 {
+    points.iter().copied().sum::<DVec3>() / points.len().max(1) as f64
+}
+
+// Symmetric eigen decomposition by cyclic Jacobi rotations. Returns the
+// eigenvalues in ascending order with the eigenvectors as matching columns.
+pub fn jacobi_eigen<const N: usize>(mut a: [[f64; N]; N]) -> ([f64; N], [[f64; N]; N]) {
+    let mut v = [[0.0; N]; N];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for _ in 0..64 {
+        let mut off = 0.0;
+        for p in 0..N {
+            for q in (p + 1)..N {
+                off += a[p][q] * a[p][q];
+            }
+        }
+        if off < 1e-30 {
+            break;
+        }
+        for p in 0..N {
+            for q in (p + 1)..N {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                for k in 0..N {
+                    let (apk, aqk) = (a[p][k], a[q][k]);
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order = [0; N];
+    for (i, slot) in order.iter_mut().enumerate() {
+        *slot = i;
+    }
+    order.sort_by(|&i, &j| a[i][i].total_cmp(&a[j][j]));
+    let values = order.map(|i| a[i][i]);
+    let mut vectors = [[0.0; N]; N];
+    for (column, &i) in order.iter().enumerate() {
+        for (dst, src) in vectors.iter_mut().zip(&v) {
+            dst[column] = src[i];
+        }
+    }
+    (values, vectors)
+}
+
+// 3x3 specialization returning unit eigenvectors, smallest eigenvalue first
+pub fn symmetric_eigen(a: [[f64; 3]; 3]) -> ([f64; 3], [DVec3; 3]) {
+    let (values, v) = jacobi_eigen(a);
+    let vectors = [0, 1, 2].map(|i| DVec3::new(v[0][i], v[1][i], v[2][i]).normalize_or_zero());
+    (values, vectors)
+}
+
+pub fn covariance(vectors: impl Iterator<Item = DVec3>) -> [[f64; 3]; 3] {
+    let mut m = [[0.0; 3]; 3];
+    for d in vectors {
+        let d = d.to_array();
+        for (row, di) in m.iter_mut().zip(d) {
+            for (cell, dj) in row.iter_mut().zip(d) {
+                *cell += di * dj;
+            }
+        }
+    }
+    m
+}
+
+// Adds one least-squares row to the normal equations A^T A x = A^T b
+fn accumulate_normal_equations<const N: usize>(
+    ata: &mut [[f64; N]; N],
+    atb: &mut [f64; N],
+    row: [f64; N],
+    rhs: f64,
+) {
+    for ((ata_row, atb_i), ri) in ata.iter_mut().zip(atb.iter_mut()).zip(row) {
+        for (cell, rj) in ata_row.iter_mut().zip(row) {
+            *cell += ri * rj;
+        }
+        *atb_i += ri * rhs;
+    }
+}
+
+// Gaussian elimination with partial pivoting; `None` when singular
+pub fn solve_linear<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+    for col in 0..N {
+        let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in (col + 1)..N {
+            let factor = a[row][col] / a[col][col];
+            for k in col..N {
+                a[row][k] -= factor * a[col][k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let tail: f64 = ((row + 1)..N).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+// Two orthonormal vectors spanning the plane perpendicular to `n`
+pub fn plane_basis(n: DVec3) -> (DVec3, DVec3) {
+    let helper = if n.x.abs() < 0.9 { DVec3::X } else { DVec3::Y };
+    let u = n.cross(helper).normalize();
+    (u, n.cross(u))
+}
+
+pub fn fit_plane(points: &[DVec3]) -> Option<FittedPrimitive> {
+    if points.len() < 3 {
+        return None;
+    }
+    let c = centroid(points);
+    let (_, vectors) = symmetric_eigen(covariance(points.iter().map(|&p| p - c)));
+    let normal = vectors[0];
+    let half_size = points
+        .iter()
+        .map(|&p| {
+            let d = p - c;
+            (d - normal * d.dot(normal)).length()
+        })
+        .fold(0.0, f64::max);
+    Some(FittedPrimitive::Plane {
+        point: c,
+        normal,
+        half_size,
+    })
+}
+
+// Algebraic sphere fit: |p|^2 + D x + E y + F z + G = 0
+pub fn fit_sphere(points: &[DVec3]) -> Option<FittedPrimitive> {
+    if points.len() < 4 {
+        return None;
+    }
+    // Center the data for conditioning
+    let c = centroid(points);
+    let mut ata = [[0.0; 4]; 4];
+    let mut atb = [0.0; 4];
+    for &p in points {
+        let d = p - c;
+        accumulate_normal_equations(
+            &mut ata,
+            &mut atb,
+            [d.x, d.y, d.z, 1.0],
+            -d.length_squared(),
+        );
+    }
+    let [dx, dy, dz, g] = solve_linear(ata, atb)?;
+    let center = DVec3::new(-dx, -dy, -dz) * 0.5;
+    let radius_sq = center.length_squared() - g;
+    if radius_sq <= 0.0 {
+        return None;
+    }
+    Some(FittedPrimitive::Sphere {
+        center: c + center,
+        radius: radius_sq.sqrt(),
+    })
+}
+
+// Circle fit in 2D (Kasa): x^2 + y^2 + D x + E y + F = 0
+fn fit_circle(points: &[DVec2]) -> Option<(DVec2, f64)> {
+    let mut ata = [[0.0; 3]; 3];
+    let mut atb = [0.0; 3];
+    for &p in points {
+        accumulate_normal_equations(&mut ata, &mut atb, [p.x, p.y, 1.0], -p.length_squared());
+    }
+    let [d, e, f] = solve_linear(ata, atb)?;
+    let center = DVec2::new(-d, -e) * 0.5;
+    let radius_sq = center.length_squared() - f;
+    (radius_sq > 0.0).then(|| (center, radius_sq.sqrt()))
+}
+
+// Cylinder fit: the axis is the direction most perpendicular to the surface
+// normals, then a circle is fitted to the points projected along it.
+pub fn fit_cylinder(points: &[DVec3], normals: &[DVec3]) -> Option<FittedPrimitive> {
+    if points.len() < 5 || normals.len() < 2 {
+        return None;
+    }
+    let (_, vectors) = symmetric_eigen(covariance(normals.iter().copied()));
+    let axis = vectors[0];
+    let (u, v) = plane_basis(axis);
+
+    let c = centroid(points);
+    let projected: Vec<DVec2> = points
+        .iter()
+        .map(|&p| {
+            let d = p - c;
+            DVec2::new(d.dot(u), d.dot(v))
+        })
+        .collect();
+    let (center_2d, radius) = fit_circle(&projected)?;
+    let base = c + u * center_2d.x + v * center_2d.y;
+
+    let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &p in points {
+        let t = (p - base).dot(axis);
+        lo = lo.min(t);
+        hi = hi.max(t);
+    }
+    Some(FittedPrimitive::Cylinder {
+        center: base + axis * (0.5 * (lo + hi)),
+        axis,
+        radius,
+        half_length: 0.5 * (hi - lo),
+    })
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::DVec3;
+
+use crate::analysis::fitting::plane_basis;
+use crate::mesh::topology::MeshTopology;
+
+pub struct FlatnessReport {
+    // Signed distance of every vertex to the plane, along its normal; NaN
+    // for vertices no face uses
+    pub vertex_distance: Vec<f64>,
+    // Mean of the corner distances, per cgar face id
+    pub face_distance: Vec<f64>,
+    pub min: f64,
+    pub max: f64,
+    // Largest in-plane offset of a vertex from the plane point along either
+    // plane axis, for drawing the plane
+    pub half_extent: f64,
+}
+
+impl FlatnessReport {
+    // Width of the band between the two planes that enclose the surface
+    pub fn flatness(&self) -> f64 {
+        self.max - self.min
+    }
+}
+
+// Plane through three points, or `None` if they're collinear
+pub fn plane_from_points([a, b, c]: [DVec3; 3]) -> Option<(DVec3, DVec3)> {
+    let normal = (b - a).cross(c - a).try_normalize()?;
+    Some(((a + b + c) / 3.0, normal))
+}
+
+pub fn plane_deviation(topology: &MeshTopology, point: DVec3, normal: DVec3) -> FlatnessReport {
+    let mut vertex_distance = vec![f64::NAN; topology.positions.len()];
+    let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (u, w) = plane_basis(normal);
+    let mut half_extent = 0.0f64;
+    for v in topology.used_vertices() {
+        let offset = topology.positions[v] - point;
+        let distance = offset.dot(normal);
+        half_extent = half_extent
+            .max(offset.dot(u).abs())
+            .max(offset.dot(w).abs());
+        vertex_distance[v] = distance;
+        min = min.min(distance);
+        max = max.max(distance);
+    }
+    let mut face_distance = vec![f64::NAN; topology.triangles.len()];
+    for (f, tri) in topology.live_faces() {
+        face_distance[f] = tri.iter().map(|&v| vertex_distance[v]).sum::<f64>() / 3.0;
+    }
+    if min > max {
+        (min, max) = (0.0, 0.0);
+    }
+    FlatnessReport {
+        vertex_distance,
+        face_distance,
+        min,
+        max,
+        half_extent,
+    }
+}
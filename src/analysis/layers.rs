@@ -0,0 +1,426 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::borrow::Cow;
+
+use bevy::color::Color;
+use bevy::ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    system::{Commands, EntityCommand, Query, Res},
+    world::{EntityWorldMut, Ref},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::overlay::{FaceColorOverlay, overlay_color, scalar_overlay};
+use crate::analysis::ramps::Colormaps;
+use crate::analysis::scaling::{ScalarScaling, ramp_positions, ramp_ticks};
+use crate::camera::components::CgarMeshData;
+use crate::selection::components::SelectionSet;
+
+// Layer color for faces an analysis has nothing to say about; they show the
+// layers below, or the mesh's own material
+pub const NO_LAYER_DATA: [f32; 4] = [1.0, 1.0, 1.0, 0.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisLayerKind {
+    Attribute,
+    Segmentation,
+    Asymmetry,
+    Overhang,
+    Thickness,
+    Flatness,
+}
+
+impl AnalysisLayerKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            AnalysisLayerKind::Attribute => "Attribute heatmap",
+            AnalysisLayerKind::Segmentation => "Segmentation",
+            AnalysisLayerKind::Asymmetry => "Asymmetry",
+            AnalysisLayerKind::Overhang => "Overhang",
+            AnalysisLayerKind::Thickness => "Wall thickness",
+            AnalysisLayerKind::Flatness => "Flatness",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayerMode {
+    // Only the topmost visible layer is drawn
+    #[default]
+    Top,
+    // Visible layers are composited bottom to top by their opacity
+    Blend,
+}
+
+// Per-face scalars, colored through the active ramp when composited so
+// that switching ramps recolors every field
+#[derive(Debug, Clone)]
+pub struct ScalarField {
+    pub values: Vec<f64>,
+    pub min: f64,
+    pub max: f64,
+    // Centered on zero; drawn with the diverging ramp
+    pub diverging: bool,
+    // Drawn for values below `min` instead of the low end of the ramp
+    pub below: Option<Color>,
+    // Whether the range may be rescaled by the active scaling mode; off
+    // for ranges that carry meaning, like a thickness threshold
+    pub auto_range: bool,
+    // Per-vertex values the face values were averaged from, when the field
+    // lives on vertices; lets readouts interpolate across a triangle
+    pub vertex_values: Option<Vec<f64>>,
+}
+
+impl ScalarField {
+    pub fn new(values: Vec<f64>, min: f64, max: f64) -> Self {
+        Self {
+            values,
+            min,
+            max,
+            diverging: false,
+            below: None,
+            auto_range: true,
+            vertex_values: None,
+        }
+    }
+
+    // Value at a point of face `face` with barycentric `weights` over `tri`
+    pub fn value_at(&self, face: usize, tri: [usize; 3], weights: [f64; 3]) -> Option<f64> {
+        let value = match &self.vertex_values {
+            Some(vertex_values) => tri
+                .iter()
+                .zip(weights)
+                .map(|(&v, w)| vertex_values.get(v).map(|value| value * w))
+                .sum::<Option<f64>>()?,
+            None => *self.values.get(face)?,
+        };
+        Some(value).filter(|value| value.is_finite())
+    }
+
+    fn scaling(&self, colormaps: &Colormaps) -> ScalarScaling {
+        if self.auto_range {
+            colormaps.scaling
+        } else {
+            ScalarScaling::Linear
+        }
+    }
+
+    pub fn overlay(&self, colormaps: &Colormaps) -> FaceColorOverlay {
+        let positions = ramp_positions(
+            &self.values,
+            (self.min, self.max),
+            self.diverging,
+            self.scaling(colormaps),
+            colormaps.percentiles,
+        );
+        let mut overlay = scalar_overlay(&positions, colormaps.ramp(self.diverging));
+        if let Some(below) = self.below {
+            let below = overlay_color(below);
+            for (color, value) in overlay.colors.iter_mut().zip(&self.values) {
+                if *value < self.min {
+                    *color = below;
+                }
+            }
+        }
+        overlay
+    }
+
+    // Values at the start, middle and end of the ramp
+    pub fn ticks(&self, colormaps: &Colormaps) -> [f64; 3] {
+        ramp_ticks(
+            &self.values,
+            (self.min, self.max),
+            self.diverging,
+            self.scaling(colormaps),
+            colormaps.percentiles,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum LayerData {
+    // Per-face colors indexed by cgar face id; alpha scales the opacity
+    Colors(Vec<[f32; 4]>),
+    Scalars(ScalarField),
+}
+
+impl LayerData {
+    fn face_count(&self) -> usize {
+        match self {
+            LayerData::Colors(colors) => colors.len(),
+            LayerData::Scalars(field) => field.values.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnalysisLayer {
+    pub kind: AnalysisLayerKind,
+    pub data: LayerData,
+    pub visible: bool,
+    pub opacity: f32,
+}
+
+impl AnalysisLayer {
+    pub fn is_stale(&self, face_count: usize) -> bool {
+        self.data.face_count() != face_count
+    }
+}
+
+// Per-face analysis results shown over a mesh. The stack is composited into
+// its `FaceColorOverlay`, so the mesh's material stays untouched and comes
+// back once no layer is visible.
+#[derive(Component, Debug, Default)]
+pub struct AnalysisLayers {
+    // Bottom to top
+    pub layers: Vec<AnalysisLayer>,
+    pub mode: LayerMode,
+}
+
+impl AnalysisLayers {
+    // Replaces the layer of this kind in place, or pushes it on top
+    pub fn set(&mut self, kind: AnalysisLayerKind, data: LayerData) {
+        match self.layers.iter_mut().find(|layer| layer.kind == kind) {
+            Some(layer) => {
+                layer.data = data;
+                layer.visible = true;
+            }
+            None => self.layers.push(AnalysisLayer {
+                kind,
+                data,
+                visible: true,
+                opacity: 1.0,
+            }),
+        }
+    }
+
+    pub fn remove(&mut self, kind: AnalysisLayerKind) {
+        self.layers.retain(|layer| layer.kind != kind);
+    }
+
+    // Topmost visible scalar layer, the one the legend and readouts describe
+    pub fn top_scalar(&self) -> Option<(AnalysisLayerKind, &ScalarField)> {
+        let mut visible = self.layers.iter().rev().filter(|layer| layer.visible);
+        let shown: Vec<&AnalysisLayer> = match self.mode {
+            LayerMode::Top => visible.next().into_iter().collect(),
+            LayerMode::Blend => visible.collect(),
+        };
+        shown.into_iter().find_map(|layer| match &layer.data {
+            LayerData::Scalars(field) => Some((layer.kind, field)),
+            LayerData::Colors(_) => None,
+        })
+    }
+
+    // Blends the visible layers over a white base. Layers computed for a
+    // different face count are stale after an edit and left out.
+    pub fn composite(&self, face_count: usize, colormaps: &Colormaps) -> Option<FaceColorOverlay> {
+        let mut shown: Vec<&AnalysisLayer> = self
+            .layers
+            .iter()
+            .filter(|layer| layer.visible && !layer.is_stale(face_count))
+            .collect();
+        if self.mode == LayerMode::Top {
+            shown = shown.split_off(shown.len().saturating_sub(1));
+        }
+        if shown.is_empty() {
+            return None;
+        }
+        let layer_colors: Vec<(Cow<[[f32; 4]]>, f32)> = shown
+            .iter()
+            .map(|layer| {
+                let colors = match &layer.data {
+                    LayerData::Colors(colors) => Cow::Borrowed(colors.as_slice()),
+                    LayerData::Scalars(field) => Cow::Owned(field.overlay(colormaps).colors),
+                };
+                (colors, layer.opacity)
+            })
+            .collect();
+        let colors = (0..face_count)
+            .map(|face| {
+                let mut color = [1.0; 4];
+                for (colors, opacity) in &layer_colors {
+                    let over = colors[face];
+                    let t = (opacity * over[3]).clamp(0.0, 1.0);
+                    for (c, o) in color.iter_mut().zip(over).take(3) {
+                        *c += (o - *c) * t;
+                    }
+                }
+                color
+            })
+            .collect();
+        Some(FaceColorOverlay { colors })
+    }
+}
+
+fn set_layer_data(kind: AnalysisLayerKind, data: LayerData) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| match entity.get_mut::<AnalysisLayers>() {
+        Some(mut layers) => layers.set(kind, data),
+        None => {
+            let mut layers = AnalysisLayers::default();
+            layers.set(kind, data);
+            entity.insert(layers);
+        }
+    }
+}
+
+// Shows `overlay` as the mesh's layer of this kind, for `EntityCommands::queue`
+pub fn set_layer(kind: AnalysisLayerKind, overlay: FaceColorOverlay) -> impl EntityCommand {
+    set_layer_data(kind, LayerData::Colors(overlay.colors))
+}
+
+// Shows a scalar field as the mesh's layer of this kind, colored by the
+// active ramp
+pub fn set_scalar_layer(kind: AnalysisLayerKind, field: ScalarField) -> impl EntityCommand {
+    set_layer_data(kind, LayerData::Scalars(field))
+}
+
+pub fn clear_layer(kind: AnalysisLayerKind) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        if let Some(mut layers) = entity.get_mut::<AnalysisLayers>() {
+            layers.remove(kind);
+        }
+    }
+}
+
+// Recomposites stacks that changed, and those showing scalar fields when
+// the ramps change
+pub fn compose_analysis_layers(
+    mut commands: Commands,
+    colormaps: Res<Colormaps>,
+    query: Query<(Entity, Ref<AnalysisLayers>, &CgarMeshData)>,
+) {
+    for (entity, layers, cgar_data) in &query {
+        let recolor = colormaps.is_changed()
+            && layers
+                .layers
+                .iter()
+                .any(|layer| matches!(layer.data, LayerData::Scalars(_)));
+        if !layers.is_changed() && !recolor {
+            continue;
+        }
+        match layers.composite(cgar_data.0.faces.len(), &colormaps) {
+            Some(overlay) => {
+                commands.entity(entity).insert(overlay);
+            }
+            None => {
+                commands.entity(entity).remove::<FaceColorOverlay>();
+            }
+        }
+    }
+}
+
+enum LayerAction {
+    Mode(LayerMode),
+    Visible(usize, bool),
+    Opacity(usize, f32),
+    Raise(usize),
+    Lower(usize),
+    Remove(usize),
+    Clear,
+}
+
+pub fn analysis_layers_panel(
+    mut contexts: EguiContexts,
+    selection: Res<SelectionSet>,
+    mut query: Query<(Entity, &mut AnalysisLayers, &CgarMeshData)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let target = selection
+        .mesh
+        .filter(|entity| query.contains(*entity))
+        .or_else(|| query.iter().next().map(|(entity, ..)| entity));
+
+    egui::Window::new("Analysis Layers")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let Some((_, mut layers, cgar_data)) = target.and_then(|e| query.get_mut(e).ok())
+            else {
+                ui.label("Run an analysis to add a layer");
+                return;
+            };
+            let face_count = cgar_data.0.faces.len();
+            let mut action = None;
+            ui.horizontal(|ui| {
+                let mut mode = layers.mode;
+                ui.radio_value(&mut mode, LayerMode::Top, "Top layer");
+                ui.radio_value(&mut mode, LayerMode::Blend, "Blend");
+                if mode != layers.mode {
+                    action = Some(LayerAction::Mode(mode));
+                }
+            });
+            ui.separator();
+            // Top of the stack first
+            let count = layers.layers.len();
+            for (i, layer) in layers.layers.iter().enumerate().rev() {
+                ui.horizontal(|ui| {
+                    let mut visible = layer.visible;
+                    if ui.checkbox(&mut visible, layer.kind.label()).changed() {
+                        action = Some(LayerAction::Visible(i, visible));
+                    }
+                    if layer.is_stale(face_count) {
+                        ui.label("(stale)")
+                            .on_hover_text("The mesh changed since this analysis ran");
+                    }
+                    let mut opacity = layer.opacity;
+                    if ui
+                        .add(egui::Slider::new(&mut opacity, 0.0..=1.0).text("Opacity"))
+                        .changed()
+                    {
+                        action = Some(LayerAction::Opacity(i, opacity));
+                    }
+                    if ui
+                        .add_enabled(i + 1 < count, egui::Button::new("⏶"))
+                        .clicked()
+                    {
+                        action = Some(LayerAction::Raise(i));
+                    }
+                    if ui.add_enabled(i > 0, egui::Button::new("⏷")).clicked() {
+                        action = Some(LayerAction::Lower(i));
+                    }
+                    if ui.small_button("✖").clicked() {
+                        action = Some(LayerAction::Remove(i));
+                    }
+                });
+            }
+            if count > 0 && ui.button("Remove all").clicked() {
+                action = Some(LayerAction::Clear);
+            }
+
+            match action {
+                Some(LayerAction::Mode(mode)) => layers.mode = mode,
+                Some(LayerAction::Visible(i, visible)) => layers.layers[i].visible = visible,
+                Some(LayerAction::Opacity(i, opacity)) => layers.layers[i].opacity = opacity,
+                Some(LayerAction::Raise(i)) => layers.layers.swap(i, i + 1),
+                Some(LayerAction::Lower(i)) => layers.layers.swap(i, i - 1),
+                Some(LayerAction::Remove(i)) => {
+                    layers.layers.remove(i);
+                }
+                Some(LayerAction::Clear) => layers.layers.clear(),
+                None => {}
+            }
+        });
+}
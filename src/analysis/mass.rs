@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::{DMat3, DVec3};
+
+use crate::analysis::fitting::symmetric_eigen;
+use crate::mesh::topology::MeshTopology;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MassProperties {
+    pub volume: f64,
+    pub mass: f64,
+    pub center: DVec3,
+    // Inertia tensor about the center of mass
+    pub inertia: DMat3,
+    // Principal moments, smallest first, and their right-handed unit axes
+    pub principal_moments: [f64; 3],
+    pub principal_axes: [DVec3; 3],
+    // Faces were wound inward, so signs were flipped to get a positive volume
+    pub inverted: bool,
+}
+
+// Closed 2-manifold: every edge is shared by exactly two faces
+pub fn is_watertight(topology: &MeshTopology) -> bool {
+    !topology.edge_faces.is_empty() && topology.edge_faces.values().all(|faces| faces.len() == 2)
+}
+
+// Volume integrals over the signed tetrahedra each face forms with the origin,
+// for a solid of uniform `density`. Only meaningful for watertight meshes.
+pub fn mass_properties(topology: &MeshTopology, density: f64) -> Option<MassProperties> {
+    let mut volume = 0.0;
+    let mut first_moment = DVec3::ZERO;
+    // Integral of p p^T over the solid
+    let mut second_moment = DMat3::ZERO;
+    for (_, tri) in topology.live_faces() {
+        let [a, b, c] = topology.corners(tri);
+        let v = a.dot(b.cross(c)) / 6.0;
+        let sum = a + b + c;
+        volume += v;
+        first_moment += sum * (v / 4.0);
+        let outer = |p: DVec3| DMat3::from_cols(p * p.x, p * p.y, p * p.z);
+        second_moment += (outer(a) + outer(b) + outer(c) + outer(sum)) * (v / 20.0);
+    }
+    if volume.abs() < f64::EPSILON {
+        return None;
+    }
+    let inverted = volume < 0.0;
+    if inverted {
+        volume = -volume;
+        first_moment = -first_moment;
+        second_moment = -second_moment;
+    }
+
+    let center = first_moment / volume;
+    let mass = density * volume;
+    let outer_center = DMat3::from_cols(center * center.x, center * center.y, center * center.z);
+    let covariance = (second_moment - outer_center * volume) * density;
+    let trace = covariance.x_axis.x + covariance.y_axis.y + covariance.z_axis.z;
+    let inertia = DMat3::from_diagonal(DVec3::splat(trace)) - covariance;
+
+    let (moments, mut axes) = symmetric_eigen(inertia.to_cols_array_2d());
+    axes[2] = axes[0].cross(axes[1]).normalize_or_zero();
+    Some(MassProperties {
+        volume,
+        mass,
+        center,
+        inertia,
+        principal_moments: moments,
+        principal_axes: axes,
+        inverted,
+    })
+}
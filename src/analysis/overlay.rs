@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    asset::Assets,
+    color::{Color, ColorToComponents, ColorToPacked, LinearRgba, Srgba},
+    ecs::{
+        component::Component,
+        query::{Changed, Or},
+        removal_detection::RemovedComponents,
+        system::{Query, ResMut},
+    },
+    render::mesh::{Mesh, Mesh3d},
+};
+use bevy_inspector_egui::egui;
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::analysis::colormap::{ColorRamp, NO_DATA_COLOR};
+use crate::camera::components::CgarMeshData;
+use crate::mesh::conversion::{cgar_to_bevy_mesh, cgar_to_bevy_mesh_unshared};
+use crate::mesh::features::{FeatureEdges, detect_feature_edges};
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+
+// Per-face colors drawn instead of the plain mesh, indexed by cgar face id
+#[derive(Component, Default, Debug, Clone)]
+pub struct FaceColorOverlay {
+    pub colors: Vec<[f32; 4]>,
+}
+
+// Builds the render mesh for a cgar mesh, honoring an active overlay,
+// splitting shading along tagged feature edges and sharp dihedral angles,
+// and using authored normals when they're switched on
+pub fn render_mesh(
+    cgar_mesh: &CgarMesh<CgarF64, 3>,
+    overlay: Option<&FaceColorOverlay>,
+    features: Option<&FeatureEdges>,
+    normals: Option<&ImportedNormals>,
+    settings: Option<&NormalSettings>,
+) -> Mesh {
+    let mut creases = features
+        .map(|features| features.edges.clone())
+        .unwrap_or_default();
+    if let Some(settings) = settings.filter(|settings| settings.split_creases) {
+        creases.extend(detect_feature_edges(
+            &MeshTopology::from_cgar(cgar_mesh),
+            (settings.crease_angle_deg as f64).to_radians(),
+            false,
+        ));
+    }
+    let creases = Some(&creases).filter(|edges| !edges.is_empty());
+    let normals = normals.filter(|normals| normals.enabled);
+    let weighting = settings
+        .map(|settings| settings.weighting)
+        .unwrap_or_default();
+    match (overlay, creases, normals) {
+        (None, None, None) => cgar_to_bevy_mesh(cgar_mesh, weighting),
+        (overlay, creases, normals) => cgar_to_bevy_mesh_unshared(
+            cgar_mesh,
+            overlay.map_or(&[][..], |overlay| overlay.colors.as_slice()),
+            creases,
+            normals,
+            weighting,
+        ),
+    }
+}
+
+// Visually distinct color for a label, spreading hues by the golden angle
+pub fn label_color(label: usize) -> Color {
+    let hue = (label as f32 * 137.507_77) % 360.0;
+    Color::hsl(hue, 0.65, 0.55)
+}
+
+// Linear RGBA as stored in the mesh color attribute
+pub fn overlay_color(color: Color) -> [f32; 4] {
+    LinearRgba::from(color).to_f32_array()
+}
+
+pub fn egui_color(color: Color) -> egui::Color32 {
+    let [r, g, b, a] = Srgba::from(color).to_u8_array();
+    egui::Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+// Colors per-face ramp positions in [0, 1]; non-finite ones have no data
+pub fn scalar_overlay(positions: &[f64], ramp: &ColorRamp) -> FaceColorOverlay {
+    let colors = positions
+        .iter()
+        .map(|&t| {
+            if t.is_finite() {
+                overlay_color(ramp.sample(t as f32))
+            } else {
+                overlay_color(NO_DATA_COLOR)
+            }
+        })
+        .collect();
+    FaceColorOverlay { colors }
+}
+
+pub fn apply_face_overlays(
+    mut meshes: ResMut<Assets<Mesh>>,
+    changed: Query<
+        (
+            &Mesh3d,
+            &CgarMeshData,
+            Option<&FaceColorOverlay>,
+            Option<&FeatureEdges>,
+            Option<&ImportedNormals>,
+            Option<&NormalSettings>,
+        ),
+        Or<(
+            Changed<FaceColorOverlay>,
+            Changed<FeatureEdges>,
+            Changed<ImportedNormals>,
+            Changed<NormalSettings>,
+        )>,
+    >,
+    mut removed_overlays: RemovedComponents<FaceColorOverlay>,
+    mut removed_features: RemovedComponents<FeatureEdges>,
+    mut removed_normals: RemovedComponents<ImportedNormals>,
+    mesh_query: Query<(
+        &Mesh3d,
+        &CgarMeshData,
+        Option<&FaceColorOverlay>,
+        Option<&FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    for (mesh_handle, cgar_data, overlay, features, normals, settings) in &changed {
+        meshes.insert(
+            &mesh_handle.0,
+            render_mesh(&cgar_data.0, overlay, features, normals, settings),
+        );
+    }
+    for entity in removed_overlays
+        .read()
+        .chain(removed_features.read())
+        .chain(removed_normals.read())
+    {
+        if let Ok((mesh_handle, cgar_data, overlay, features, normals, settings)) =
+            mesh_query.get(entity)
+        {
+            meshes.insert(
+                &mesh_handle.0,
+                render_mesh(&cgar_data.0, overlay, features, normals, settings),
+            );
+        }
+    }
+}
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::DVec3;
+
+use crate::mesh::topology::MeshTopology;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildDirection {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl BuildDirection {
+    pub const ALL: [BuildDirection; 6] = [
+        BuildDirection::PosX,
+        BuildDirection::NegX,
+        BuildDirection::PosY,
+        BuildDirection::NegY,
+        BuildDirection::PosZ,
+        BuildDirection::NegZ,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BuildDirection::PosX => "+X",
+            BuildDirection::NegX => "-X",
+            BuildDirection::PosY => "+Y",
+            BuildDirection::NegY => "-Y",
+            BuildDirection::PosZ => "+Z",
+            BuildDirection::NegZ => "-Z",
+        }
+    }
+
+    pub fn vector(self) -> DVec3 {
+        match self {
+            BuildDirection::PosX => DVec3::X,
+            BuildDirection::NegX => DVec3::NEG_X,
+            BuildDirection::PosY => DVec3::Y,
+            BuildDirection::NegY => DVec3::NEG_Y,
+            BuildDirection::PosZ => DVec3::Z,
+            BuildDirection::NegZ => DVec3::NEG_Z,
+        }
+    }
+}
+
+pub struct OverhangReport {
+    // Per cgar face id: how far past the threshold an overhang face is, in [0, 1]
+    pub severity: Vec<Option<f64>>,
+    pub faces: Vec<usize>,
+    pub area: f64,
+    pub total_area: f64,
+}
+
+// Faces tilted further than `threshold` (radians, measured from the build
+// direction's vertical) while facing down need support. Faces lying flat on the
+// lowest layer rest on the bed and are skipped when `skip_bed` is set.
+pub fn overhang_analysis(
+    topology: &MeshTopology,
+    build_direction: DVec3,
+    threshold: f64,
+    skip_bed: bool,
+) -> OverhangReport {
+    let up = build_direction.normalize();
+    let mut report = OverhangReport {
+        severity: vec![None; topology.triangles.len()],
+        faces: Vec::new(),
+        area: 0.0,
+        total_area: 0.0,
+    };
+
+    let heights: Vec<f64> = topology
+        .used_vertices()
+        .map(|v| topology.positions[v].dot(up))
+        .collect();
+    let (bed, top) = heights
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &h| {
+            (lo.min(h), hi.max(h))
+        });
+    let bed_tolerance = (top - bed).abs() * 1e-6;
+
+    // A face needs support once its downward tilt exceeds `threshold` from vertical
+    let limit = threshold.sin();
+    for (f, tri) in topology.live_faces() {
+        let area = topology.face_area(f);
+        report.total_area += area;
+
+        let down = -topology.face_normal(f).dot(up);
+        if down <= limit {
+            continue;
+        }
+        if skip_bed
+            && tri
+                .iter()
+                .all(|&v| topology.positions[v].dot(up) - bed <= bed_tolerance)
+        {
+            continue;
+        }
+        // Tilt from vertical past the threshold, scaled so a flat ceiling is 1
+        let tilt = down.clamp(-1.0, 1.0).asin();
+        let span = std::f64::consts::FRAC_PI_2 - threshold;
+        report.severity[f] = Some(if span > 0.0 {
+            ((tilt - threshold) / span).clamp(0.0, 1.0)
+        } else {
+            1.0
+        });
+        report.faces.push(f);
+        report.area += area;
+    }
+    report
+}
@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::ecs::{
+    entity::Entity,
+    resource::Resource,
+    system::{Local, Query, Res, ResMut},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::colormap::{ColorRamp, RampStop, bundled_ramps};
+use crate::analysis::layers::AnalysisLayers;
+use crate::analysis::overlay::egui_color;
+use crate::analysis::scaling::ScalarScaling;
+use crate::probe::systems::ScalarProbe;
+use crate::selection::components::SelectionSet;
+
+// Steps the preview and legend bars are painted with
+const BAR_STEPS: usize = 64;
+
+// Color ramps for every scalar-field visualization: the bundled maps plus
+// the user's own, which are saved with the session
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Colormaps {
+    pub bundled: Vec<ColorRamp>,
+    pub custom: Vec<ColorRamp>,
+    // Names of the ramps for fields centered on zero and for all others
+    pub sequential: String,
+    pub diverging: String,
+    // How fields that allow it are spread over the ramp
+    pub scaling: ScalarScaling,
+    // Low and high percentiles for percentile clipping
+    pub percentiles: (f32, f32),
+}
+
+impl Default for Colormaps {
+    fn default() -> Self {
+        Self {
+            bundled: bundled_ramps(),
+            custom: Vec::new(),
+            sequential: "Viridis".to_string(),
+            diverging: "Cool-warm".to_string(),
+            scaling: ScalarScaling::Linear,
+            percentiles: (2.0, 98.0),
+        }
+    }
+}
+
+impl Colormaps {
+    pub fn all(&self) -> impl Iterator<Item = &ColorRamp> {
+        self.bundled.iter().chain(&self.custom)
+    }
+
+    // Falls back to the first bundled ramp when the named one was deleted
+    pub fn ramp(&self, diverging: bool) -> &ColorRamp {
+        let name = if diverging {
+            &self.diverging
+        } else {
+            &self.sequential
+        };
+        self.all()
+            .find(|ramp| &ramp.name == name)
+            .unwrap_or(&self.bundled[0])
+    }
+
+    fn unique_name(&self, base: &str) -> String {
+        (1..)
+            .map(|n| format!("{} {}", base, n))
+            .find(|name| self.all().all(|ramp| &ramp.name != name))
+            .unwrap_or_default()
+    }
+}
+
+// Paints the ramp left to right across the available width
+pub fn ramp_bar(ui: &mut egui::Ui, ramp: &ColorRamp, height: f32) -> egui::Response {
+    let size = egui::vec2(ui.available_width().max(120.0), height);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let step = rect.width() / BAR_STEPS as f32;
+    for i in 0..BAR_STEPS {
+        let t = (i as f32 + 0.5) / BAR_STEPS as f32;
+        let left = rect.left() + i as f32 * step;
+        painter.rect_filled(
+            egui::Rect::from_min_max(
+                egui::pos2(left, rect.top()),
+                egui::pos2(left + step + 0.5, rect.bottom()),
+            ),
+            0.0,
+            egui_color(ramp.sample(t)),
+        );
+    }
+    response
+}
+
+fn ramp_combo(ui: &mut egui::Ui, label: &str, selected: &mut String, colormaps: &Colormaps) {
+    egui::ComboBox::from_label(label)
+        .selected_text(selected.as_str())
+        .show_ui(ui, |ui| {
+            for ramp in colormaps.all() {
+                ui.selectable_value(selected, ramp.name.clone(), ramp.name.as_str());
+            }
+        });
+}
+
+pub fn colormap_panel(
+    mut contexts: EguiContexts,
+    mut colormaps: ResMut<Colormaps>,
+    mut editing: Local<Option<usize>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Colormaps")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut edited = colormaps.clone();
+            ramp_combo(ui, "Sequential", &mut edited.sequential, &colormaps);
+            ramp_bar(ui, edited.ramp(false), 12.0);
+            ramp_combo(ui, "Diverging", &mut edited.diverging, &colormaps);
+            ramp_bar(ui, edited.ramp(true), 12.0);
+            egui::ComboBox::from_label("Scaling")
+                .selected_text(edited.scaling.label())
+                .show_ui(ui, |ui| {
+                    for option in ScalarScaling::ALL {
+                        ui.selectable_value(&mut edited.scaling, option, option.label());
+                    }
+                });
+            if edited.scaling == ScalarScaling::Percentile {
+                let (low, high) = &mut edited.percentiles;
+                ui.add(egui::Slider::new(low, 0.0..=49.0).text("Low %"));
+                ui.add(egui::Slider::new(high, 51.0..=100.0).text("High %"));
+            }
+
+            ui.separator();
+            ui.label("Custom ramps");
+            for (i, ramp) in edited.custom.iter().enumerate() {
+                if ui
+                    .selectable_label(*editing == Some(i), ramp.name.as_str())
+                    .clicked()
+                {
+                    *editing = Some(i).filter(|&i| *editing != Some(i));
+                }
+            }
+            if ui.button("New ramp").clicked() {
+                let mut ramp = edited.ramp(false).clone();
+                ramp.name = edited.unique_name("Custom");
+                edited.custom.push(ramp);
+                *editing = Some(edited.custom.len() - 1);
+            }
+
+            let mut delete = false;
+            if let Some(ramp) = editing.and_then(|i| edited.custom.get_mut(i)) {
+                ui.separator();
+                let old_name = ramp.name.clone();
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut ramp.name);
+                });
+                let removable = ramp.stops.len() > 2;
+                let mut remove = None;
+                for (k, stop) in ramp.stops.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::Slider::new(&mut stop.position, 0.0..=1.0));
+                        ui.color_edit_button_rgb(&mut stop.color);
+                        if ui.add_enabled(removable, egui::Button::new("✖")).clicked() {
+                            remove = Some(k);
+                        }
+                    });
+                }
+                if let Some(k) = remove {
+                    ramp.stops.remove(k);
+                }
+                ramp.sort();
+                ramp_bar(ui, ramp, 16.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Add stop").clicked() {
+                        // Split the widest gap, keeping the ramp's look
+                        let (k, _) = ramp
+                            .stops
+                            .windows(2)
+                            .map(|pair| pair[1].position - pair[0].position)
+                            .enumerate()
+                            .fold(
+                                (0, f32::MIN),
+                                |best, (k, gap)| {
+                                    if gap > best.1 { (k, gap) } else { best }
+                                },
+                            );
+                        let position = ramp
+                            .stops
+                            .get(k..k + 2)
+                            .map_or(0.5, |pair| (pair[0].position + pair[1].position) * 0.5);
+                        let color = ramp.sample(position).to_srgba();
+                        ramp.stops.insert(
+                            (k + 1).min(ramp.stops.len()),
+                            RampStop {
+                                position,
+                                color: [color.red, color.green, color.blue],
+                            },
+                        );
+                    }
+                    delete = ui.button("Delete ramp").clicked();
+                });
+                if ramp.name != old_name {
+                    let new_name = ramp.name.clone();
+                    for name in [&mut edited.sequential, &mut edited.diverging] {
+                        if *name == old_name {
+                            *name = new_name.clone();
+                        }
+                    }
+                }
+            }
+            if let Some(i) = editing.filter(|_| delete) {
+                edited.custom.remove(i);
+                *editing = None;
+            }
+
+            if edited != *colormaps {
+                *colormaps = edited;
+            }
+        });
+}
+
+// Color scale of the topmost scalar layer shown on the selected mesh
+pub fn legend_panel(
+    mut contexts: EguiContexts,
+    colormaps: Res<Colormaps>,
+    selection: Res<SelectionSet>,
+    mut scalar_probe: ResMut<ScalarProbe>,
+    layer_query: Query<(Entity, &AnalysisLayers)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let Some((_, layers)) = selection
+        .mesh
+        .and_then(|entity| layer_query.get(entity).ok())
+        .or_else(|| layer_query.iter().next())
+    else {
+        return;
+    };
+    let Some((kind, field)) = layers.top_scalar() else {
+        return;
+    };
+
+    egui::Window::new("Legend").show(ctx, |ui| {
+        let ramp = colormaps.ramp(field.diverging);
+        ui.label(format!("{} ({})", kind.label(), ramp.name));
+        ramp_bar(ui, ramp, 16.0);
+        let [low, mid, high] = field.ticks(&colormaps);
+        ui.horizontal(|ui| {
+            ui.label(format!("{:.4}", low));
+            ui.separator();
+            ui.label(format!("{:.4}", mid));
+            ui.separator();
+            ui.label(format!("{:.4}", high));
+        });
+        if field.auto_range && colormaps.scaling != ScalarScaling::Linear {
+            ui.label(colormaps.scaling.label());
+        }
+        if let Some(below) = field.below {
+            ui.horizontal(|ui| {
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(16.0, 12.0), egui::Sense::hover());
+                ui.painter().rect_filled(rect, 0.0, egui_color(below));
+                ui.label(format!("Below {:.4}", field.min));
+            });
+        }
+        let mut enabled = scalar_probe.enabled;
+        ui.checkbox(&mut enabled, "Show value under cursor");
+        if enabled != scalar_probe.enabled {
+            scalar_probe.enabled = enabled;
+        }
+    });
+}
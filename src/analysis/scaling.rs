@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// How scalar fields are spread over a color ramp. Raw min/max scaling lets a
+// few outliers squeeze everything else into one color; clipping to
+// percentiles or equalizing the histogram keeps the bulk of the field
+// readable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScalarScaling {
+    #[default]
+    Linear,
+    // Clamp to the low and high percentiles of the field
+    Percentile,
+    // Spread by rank, so each part of the ramp covers as many faces
+    Equalize,
+}
+
+impl ScalarScaling {
+    pub const ALL: [ScalarScaling; 3] = [
+        ScalarScaling::Linear,
+        ScalarScaling::Percentile,
+        ScalarScaling::Equalize,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScalarScaling::Linear => "Linear min/max",
+            ScalarScaling::Percentile => "Percentile clip",
+            ScalarScaling::Equalize => "Histogram equalize",
+        }
+    }
+}
+
+// Finite values, sorted; diverging fields are ranked by magnitude so zero
+// stays in the middle of the ramp
+fn sorted_finite(values: &[f64], diverging: bool) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values
+        .iter()
+        .filter(|v| v.is_finite())
+        .map(|&v| if diverging { v.abs() } else { v })
+        .collect();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+// Linearly interpolated quantile of sorted values, `q` in [0, 1]
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let Some(&last) = sorted.last() else {
+        return 0.0;
+    };
+    let x = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let i = x.floor() as usize;
+    match sorted.get(i + 1) {
+        Some(&next) => sorted[i] + (next - sorted[i]) * (x - i as f64),
+        None => last,
+    }
+}
+
+// Fraction of sorted values below `v`, counting ties as half
+fn rank(sorted: &[f64], v: f64) -> f64 {
+    let below = sorted.partition_point(|&s| s < v);
+    let not_above = sorted.partition_point(|&s| s <= v);
+    let n = sorted.len().saturating_sub(1).max(1) as f64;
+    ((below + not_above.saturating_sub(1)) as f64 * 0.5 / n).clamp(0.0, 1.0)
+}
+
+// Low and high clip values; diverging fields clip symmetrically around zero
+fn clip_range(sorted: &[f64], percentiles: (f32, f32), diverging: bool) -> (f64, f64) {
+    if diverging {
+        let r = quantile(sorted, percentiles.1 as f64 / 100.0);
+        (-r, r)
+    } else {
+        (
+            quantile(sorted, percentiles.0 as f64 / 100.0),
+            quantile(sorted, percentiles.1 as f64 / 100.0),
+        )
+    }
+}
+
+// Ramp position in [0, 1] for each value, NaN for missing ones. `min` and
+// `max` are the field's own range, used by linear scaling.
+pub fn ramp_positions(
+    values: &[f64],
+    (min, max): (f64, f64),
+    diverging: bool,
+    scaling: ScalarScaling,
+    percentiles: (f32, f32),
+) -> Vec<f64> {
+    let linear =
+        |v: f64, lo: f64, hi: f64| ((v - lo) / (hi - lo).max(f64::EPSILON)).clamp(0.0, 1.0);
+    let sorted = match scaling {
+        ScalarScaling::Linear => Vec::new(),
+        ScalarScaling::Percentile | ScalarScaling::Equalize => sorted_finite(values, diverging),
+    };
+    let clip = clip_range(&sorted, percentiles, diverging);
+    values
+        .iter()
+        .map(|&v| {
+            if !v.is_finite() {
+                return f64::NAN;
+            }
+            match scaling {
+                ScalarScaling::Linear => linear(v, min, max),
+                ScalarScaling::Percentile => linear(v, clip.0, clip.1),
+                ScalarScaling::Equalize if diverging => {
+                    0.5 + 0.5 * v.signum() * rank(&sorted, v.abs())
+                }
+                ScalarScaling::Equalize => rank(&sorted, v),
+            }
+        })
+        .collect()
+}
+
+// Values at the start, middle and end of the ramp, for the legend
+pub fn ramp_ticks(
+    values: &[f64],
+    (min, max): (f64, f64),
+    diverging: bool,
+    scaling: ScalarScaling,
+    percentiles: (f32, f32),
+) -> [f64; 3] {
+    let sorted = sorted_finite(values, diverging);
+    let (lo, hi) = match scaling {
+        ScalarScaling::Linear => (min, max),
+        ScalarScaling::Percentile => clip_range(&sorted, percentiles, diverging),
+        ScalarScaling::Equalize if diverging => {
+            let r = sorted.last().copied().unwrap_or(0.0);
+            (-r, r)
+        }
+        ScalarScaling::Equalize => {
+            let mid = quantile(&sorted, 0.5);
+            return [quantile(&sorted, 0.0), mid, quantile(&sorted, 1.0)];
+        }
+    };
+    [lo, (lo + hi) * 0.5, hi]
+}
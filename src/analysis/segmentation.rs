@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::mesh::topology::MeshTopology;
+
+pub struct Segments {
+    // Patch label per cgar face id, `None` for removed faces
+    pub labels: Vec<Option<usize>>,
+    pub patches: Vec<Vec<usize>>,
+}
+
+// Partitions the surface into patches of edge-connected faces whose adjacent
+// normals differ by less than `max_angle` (radians). Patches smaller than
+// `min_faces` are merged into the neighbor they share most edges with.
+pub fn segment_by_normals(topology: &MeshTopology, max_angle: f64, min_faces: usize) -> Segments {
+    let cos_limit = max_angle.cos();
+    let mut labels: Vec<Option<usize>> = vec![None; topology.triangles.len()];
+    let mut patches: Vec<Vec<usize>> = Vec::new();
+
+    for (seed, _) in topology.live_faces() {
+        if labels[seed].is_some() {
+            continue;
+        }
+        let label = patches.len();
+        let mut faces = vec![seed];
+        labels[seed] = Some(label);
+        let mut queue = VecDeque::from([seed]);
+        while let Some(face) = queue.pop_front() {
+            let normal = topology.face_normal(face);
+            for neighbor in topology.face_neighbors(face) {
+                if labels[neighbor].is_none()
+                    && topology.face_normal(neighbor).dot(normal) >= cos_limit
+                {
+                    labels[neighbor] = Some(label);
+                    faces.push(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        patches.push(faces);
+    }
+
+    if min_faces > 1 {
+        let mut order: Vec<usize> = (0..patches.len()).collect();
+        order.sort_by_key(|&label| patches[label].len());
+        for label in order {
+            if patches[label].is_empty() || patches[label].len() >= min_faces {
+                continue;
+            }
+            let mut shared: BTreeMap<usize, usize> = BTreeMap::new();
+            for &face in &patches[label] {
+                for neighbor in topology.face_neighbors(face) {
+                    match labels[neighbor] {
+                        Some(other) if other != label => *shared.entry(other).or_default() += 1,
+                        _ => {}
+                    }
+                }
+            }
+            let Some((target, _)) = shared.into_iter().max_by_key(|&(_, count)| count) else {
+                continue;
+            };
+            let faces = std::mem::take(&mut patches[label]);
+            for &face in &faces {
+                labels[face] = Some(target);
+            }
+            patches[target].extend(faces);
+        }
+
+        // Drop emptied patches and renumber the rest
+        let mut remap = vec![None; patches.len()];
+        let mut compacted = Vec::new();
+        for (label, faces) in patches.into_iter().enumerate() {
+            if !faces.is_empty() {
+                remap[label] = Some(compacted.len());
+                compacted.push(faces);
+            }
+        }
+        for label in labels.iter_mut() {
+            *label = label.and_then(|l| remap[l]);
+        }
+        patches = compacted;
+    }
+
+    Segments { labels, patches }
+}
@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+
+use bevy::math::DVec3;
+
+use crate::mesh::topology::MeshTopology;
+
+// Curve skeleton as a graph of points inside the surface
+#[derive(Debug, Clone, Default)]
+pub struct Skeleton {
+    pub nodes: Vec<DVec3>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl Skeleton {
+    pub fn length(&self) -> f64 {
+        self.edges
+            .iter()
+            .map(|&(a, b)| self.nodes[a].distance(self.nodes[b]))
+            .sum()
+    }
+
+    fn degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.nodes.len()];
+        for &(a, b) in &self.edges {
+            degrees[a] += 1;
+            degrees[b] += 1;
+        }
+        degrees
+    }
+
+    // Nodes where three or more branches meet
+    pub fn junctions(&self) -> usize {
+        self.degrees().into_iter().filter(|&d| d > 2).count()
+    }
+
+    pub fn tips(&self) -> usize {
+        self.degrees().into_iter().filter(|&d| d == 1).count()
+    }
+}
+
+// Edge-path distance from `seeds` to every vertex reachable from them
+fn geodesic_distances(topology: &MeshTopology, seeds: &[usize]) -> Vec<f64> {
+    let mut distance = vec![f64::INFINITY; topology.positions.len()];
+    // Non-negative floats order the same as their bit patterns
+    let mut heap = BinaryHeap::new();
+    for &seed in seeds {
+        distance[seed] = 0.0;
+        heap.push(Reverse((0f64.to_bits(), seed)));
+    }
+    while let Some(Reverse((bits, v))) = heap.pop() {
+        let d = f64::from_bits(bits);
+        if d > distance[v] {
+            continue;
+        }
+        for &n in &topology.vertex_neighbors[v] {
+            let next = d + topology.positions[v].distance(topology.positions[n]);
+            if next < distance[n] {
+                distance[n] = next;
+                heap.push(Reverse((next.to_bits(), n)));
+            }
+        }
+    }
+    distance
+}
+
+fn find(parent: &mut [usize], mut v: usize) -> usize {
+    while parent[v] != v {
+        parent[v] = parent[parent[v]];
+        v = parent[v];
+    }
+    v
+}
+
+// Approximates the curve skeleton with a level-set (Reeb) graph of geodesic
+// distance from an extremity. Vertices are binned into `levels` bands; each
+// connected piece of a band becomes a node at its centroid, which sits near
+// the middle of tubular parts, and pieces joined by a mesh edge across
+// neighboring bands are linked. Each connected component of the mesh gets
+// its own graph.
+pub fn extract_skeleton(topology: &MeshTopology, levels: usize) -> Skeleton {
+    let levels = levels.max(1);
+    let vertex_count = topology.positions.len();
+    let mut level = vec![usize::MAX; vertex_count];
+
+    for start in topology.used_vertices() {
+        if level[start] != usize::MAX {
+            continue;
+        }
+        // The vertex farthest from anywhere in the component is an extremity
+        let from_start = geodesic_distances(topology, &[start]);
+        let component: Vec<usize> = topology
+            .used_vertices()
+            .filter(|&v| from_start[v].is_finite())
+            .collect();
+        let seed = component
+            .iter()
+            .copied()
+            .max_by(|&a, &b| from_start[a].total_cmp(&from_start[b]))
+            .unwrap_or(start);
+        let distance = geodesic_distances(topology, &[seed]);
+        let max = component.iter().map(|&v| distance[v]).fold(0.0, f64::max);
+        for &v in &component {
+            level[v] = if max > 0.0 {
+                ((distance[v] / max * levels as f64) as usize).min(levels - 1)
+            } else {
+                0
+            };
+        }
+    }
+
+    // Split each band into its connected pieces
+    let mut parent: Vec<usize> = (0..vertex_count).collect();
+    for &(a, b) in topology.edge_faces.keys() {
+        if level[a] == level[b] {
+            let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+            parent[ra] = rb;
+        }
+    }
+
+    let mut node_of_root: HashMap<usize, usize> = HashMap::new();
+    let mut sums: Vec<(DVec3, usize)> = Vec::new();
+    let mut node = vec![usize::MAX; vertex_count];
+    for v in topology.used_vertices() {
+        let root = find(&mut parent, v);
+        let id = *node_of_root.entry(root).or_insert_with(|| {
+            sums.push((DVec3::ZERO, 0));
+            sums.len() - 1
+        });
+        sums[id].0 += topology.positions[v];
+        sums[id].1 += 1;
+        node[v] = id;
+    }
+
+    let edges: BTreeSet<(usize, usize)> = topology
+        .edge_faces
+        .keys()
+        .filter(|&&(a, b)| level[a] != level[b])
+        .map(|&(a, b)| (node[a].min(node[b]), node[a].max(node[b])))
+        .collect();
+    Skeleton {
+        nodes: sums
+            .into_iter()
+            .map(|(sum, count)| sum / count as f64)
+            .collect(),
+        edges: edges.into_iter().collect(),
+    }
+}
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::math::DVec3;
+
+use crate::mesh::topology::MeshTopology;
+
+#[derive(Debug, Clone)]
+pub struct Contour {
+    pub points: Vec<DVec3>,
+    pub closed: bool,
+}
+
+impl Contour {
+    pub fn length(&self) -> f64 {
+        let open: f64 = self.points.windows(2).map(|w| w[0].distance(w[1])).sum();
+        match (self.closed, self.points.first(), self.points.last()) {
+            (true, Some(first), Some(last)) => open + last.distance(*first),
+            _ => open,
+        }
+    }
+}
+
+// Lowest and highest vertex along `up`
+pub fn height_range(topology: &MeshTopology, up: DVec3) -> Option<(f64, f64)> {
+    topology
+        .used_vertices()
+        .map(|v| topology.positions[v].dot(up))
+        .fold(None, |range, h| match range {
+            None => Some((h, h)),
+            Some((lo, hi)) => Some((f64::min(lo, h), f64::max(hi, h))),
+        })
+}
+
+// Intersects the mesh with the plane `dot(p, up) == height` and chains the
+// pieces into contours. Vertices lying exactly on the plane count as above
+// it, so every crossing is a proper edge crossing and the contour points are
+// identified by the mesh edge they sit on.
+pub fn slice_mesh(topology: &MeshTopology, up: DVec3, height: f64) -> Vec<Contour> {
+    let above = |v: usize| topology.positions[v].dot(up) >= height;
+    let crossing = |a: usize, b: usize| -> DVec3 {
+        let (pa, pb) = (topology.positions[a], topology.positions[b]);
+        let (ha, hb) = (pa.dot(up) - height, pb.dot(up) - height);
+        pa + (pb - pa) * (ha / (ha - hb))
+    };
+    let key = |a: usize, b: usize| (a.min(b), a.max(b));
+
+    // Each face crossing the plane contributes one segment between two edge crossings,
+    // directed by the face winding so neighboring segments chain head to tail
+    let mut next: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut points: HashMap<(usize, usize), DVec3> = HashMap::new();
+    for (_, tri) in topology.live_faces() {
+        let mut exits = None;
+        let mut enters = None;
+        for i in 0..3 {
+            let (a, b) = (tri[i], tri[(i + 1) % 3]);
+            match (above(a), above(b)) {
+                (true, false) => exits = Some(key(a, b)),
+                (false, true) => enters = Some(key(a, b)),
+                _ => {}
+            }
+            if above(a) != above(b) {
+                points.entry(key(a, b)).or_insert_with(|| crossing(a, b));
+            }
+        }
+        if let (Some(from), Some(to)) = (exits, enters) {
+            next.insert(from, to);
+        }
+    }
+
+    // Open chains start at crossings nothing leads into
+    let targets: HashSet<(usize, usize)> = next.values().copied().collect();
+    let mut starts: Vec<(usize, usize)> = next
+        .keys()
+        .filter(|k| !targets.contains(k))
+        .copied()
+        .collect();
+    starts.sort_unstable();
+    let mut remaining: Vec<(usize, usize)> = next.keys().copied().collect();
+    remaining.sort_unstable();
+
+    let mut contours = Vec::new();
+    let walk = |start: (usize, usize), next: &mut HashMap<(usize, usize), (usize, usize)>| {
+        let mut chain = vec![points[&start]];
+        let mut current = start;
+        while let Some(to) = next.remove(&current) {
+            if to == start {
+                return Contour {
+                    points: chain,
+                    closed: true,
+                };
+            }
+            chain.push(points[&to]);
+            current = to;
+        }
+        Contour {
+            points: chain,
+            closed: false,
+        }
+    };
+    for start in starts {
+        contours.push(walk(start, &mut next));
+    }
+    for start in remaining {
+        if next.contains_key(&start) {
+            contours.push(walk(start, &mut next));
+        }
+    }
+    contours
+}
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::DVec3;
+
+use crate::analysis::fitting::{centroid, covariance, symmetric_eigen};
+use crate::mesh::spatial::PointGrid;
+use crate::mesh::topology::MeshTopology;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SymmetryPlane {
+    pub point: DVec3,
+    pub normal: DVec3,
+}
+
+impl SymmetryPlane {
+    pub fn reflect(&self, p: DVec3) -> DVec3 {
+        p - self.normal * (2.0 * (p - self.point).dot(self.normal))
+    }
+}
+
+pub struct SymmetryReport {
+    pub plane: SymmetryPlane,
+    // Half extent of the mesh, for drawing the plane
+    pub half_size: f64,
+    pub rms: f64,
+    // Distance from each mirrored vertex to the closest original vertex,
+    // indexed by vertex id; NaN for vertices without faces
+    pub vertex_deviation: Vec<f64>,
+}
+
+impl SymmetryReport {
+    pub fn face_deviation(&self, topology: &MeshTopology) -> Vec<f64> {
+        topology
+            .triangles
+            .iter()
+            .map(|tri| match tri {
+                Some(tri) => tri.iter().map(|&v| self.vertex_deviation[v]).sum::<f64>() / 3.0,
+                None => f64::NAN,
+            })
+            .collect()
+    }
+}
+
+fn mirror_rms(plane: &SymmetryPlane, points: &[(usize, DVec3)], grid: &PointGrid) -> f64 {
+    let sum_sq: f64 = points
+        .iter()
+        .filter_map(|&(_, p)| grid.nearest(plane.reflect(p)))
+        .map(|(_, d)| d * d)
+        .sum();
+    (sum_sq / points.len().max(1) as f64).sqrt()
+}
+
+// Finds an approximate mirror plane: the best of the three principal planes
+// through the centroid, refined by re-fitting the bisector of matched
+// vertex pairs until the residual stops improving.
+pub fn detect_symmetry(topology: &MeshTopology) -> Option<SymmetryReport> {
+    let points: Vec<(usize, DVec3)> = topology
+        .used_vertices()
+        .map(|v| (v, topology.positions[v]))
+        .collect();
+    if points.len() < 4 {
+        return None;
+    }
+    let positions: Vec<DVec3> = points.iter().map(|&(_, p)| p).collect();
+    let center = centroid(&positions);
+    let grid = PointGrid::with_auto_cell(points.clone());
+
+    let (_, axes) = symmetric_eigen(covariance(positions.iter().map(|&p| p - center)));
+    let (mut plane, mut rms) = axes
+        .into_iter()
+        .map(|normal| {
+            let plane = SymmetryPlane {
+                point: center,
+                normal,
+            };
+            (plane, mirror_rms(&plane, &points, &grid))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    for _ in 0..10 {
+        let mut direction = DVec3::ZERO;
+        let mut midpoint_sum = DVec3::ZERO;
+        let mut matched = 0usize;
+        for &(_, p) in &points {
+            let Some((id, _)) = grid.nearest(plane.reflect(p)) else {
+                continue;
+            };
+            let q = topology.positions[id];
+            let d = p - q;
+            direction += if d.dot(plane.normal) < 0.0 { -d } else { d };
+            midpoint_sum += (p + q) * 0.5;
+            matched += 1;
+        }
+        let normal = direction.normalize_or_zero();
+        if matched == 0 || normal == DVec3::ZERO {
+            break;
+        }
+        let candidate = SymmetryPlane {
+            point: midpoint_sum / matched as f64,
+            normal,
+        };
+        let candidate_rms = mirror_rms(&candidate, &points, &grid);
+        if candidate_rms >= rms {
+            break;
+        }
+        plane = candidate;
+        rms = candidate_rms;
+    }
+
+    let mut vertex_deviation = vec![f64::NAN; topology.positions.len()];
+    for &(v, p) in &points {
+        if let Some((_, d)) = grid.nearest(plane.reflect(p)) {
+            vertex_deviation[v] = d;
+        }
+    }
+    let half_size = positions
+        .iter()
+        .map(|&p| p.distance(center))
+        .fold(0.0, f64::max);
+
+    Some(SymmetryReport {
+        plane,
+        half_size,
+        rms,
+        vertex_deviation,
+    })
+}
+
+// Reflected positions and triangles with reversed winding to keep normals outward
+pub fn mirror_geometry(
+    topology: &MeshTopology,
+    plane: &SymmetryPlane,
+) -> (Vec<DVec3>, Vec<[usize; 3]>) {
+    let positions = topology
+        .positions
+        .iter()
+        .map(|&p| plane.reflect(p))
+        .collect();
+    let triangles = topology
+        .live_faces()
+        .map(|(_, [a, b, c])| [a, c, b])
+        .collect();
+    (positions, triangles)
+}
@@ -0,0 +1,1693 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(feature = "native")]
+use std::path::PathBuf;
+
+#[cfg(feature = "native")]
+use bevy::ecs::event::EventWriter;
+use bevy::{
+    asset::Assets,
+    color::{Color, Mix, Srgba},
+    ecs::{
+        change_detection::{DetectChanges, DetectChangesMut, Ref},
+        entity::Entity,
+        event::EventReader,
+        query::With,
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+    },
+    gizmos::{config::GizmoConfigGroup, gizmos::Gizmos},
+    log::info,
+    math::{DVec3, Isometry3d, Mat3, Quat, Vec2, Vec3},
+    reflect::Reflect,
+    render::mesh::{Mesh, Mesh3d},
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::edges::{EdgeLengthReport, edge_length_stats};
+use crate::analysis::fitting::{
+    FitReport, FittedPrimitive, fit_cylinder, fit_plane, fit_sphere, plane_basis, report,
+};
+use crate::analysis::flatness::{FlatnessReport, plane_deviation, plane_from_points};
+use crate::analysis::layers::{
+    AnalysisLayerKind, NO_LAYER_DATA, ScalarField, clear_layer, set_layer, set_scalar_layer,
+};
+use crate::analysis::mass::{MassProperties, is_watertight, mass_properties};
+use crate::analysis::overlay::{
+    FaceColorOverlay, egui_color, label_color, overlay_color, render_mesh,
+};
+use crate::analysis::printing::{BuildDirection, OverhangReport, overhang_analysis};
+use crate::analysis::segmentation::{Segments, segment_by_normals};
+use crate::analysis::skeleton::{Skeleton, extract_skeleton};
+use crate::analysis::slicing::{Contour, height_range, slice_mesh};
+use crate::analysis::symmetry::{SymmetryReport, detect_symmetry, mirror_geometry};
+use crate::analysis::thickness::{ThicknessReport, wall_thickness};
+use crate::camera::components::CgarMeshData;
+use crate::mesh::attributes::{AttributeDomain, AttributeValues, store_attribute};
+use crate::mesh::conversion::{build_cgar_mesh, vertex_position};
+use crate::mesh::edge::MeshPicked;
+#[cfg(feature = "native")]
+use crate::mesh::export::{write_obj_faces, write_obj_lines};
+use crate::mesh::face_tree::{FaceSurface, FaceTreeCache};
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+#[cfg(feature = "native")]
+use crate::notifications::systems::Notify;
+use crate::selection::components::SelectionSet;
+
+// Last primitive fitted to the selection, drawn as a ghost over its mesh
+#[derive(Resource, Default)]
+pub struct PrimitiveFit {
+    pub result: Option<(Entity, FitReport)>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PrimitiveKind {
+    Plane,
+    Sphere,
+    Cylinder,
+}
+
+fn fit_selection(
+    kind: PrimitiveKind,
+    selection: &SelectionSet,
+    cgar_data: &CgarMeshData,
+) -> Result<FitReport, String> {
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    let vertices = selection.touched_vertices(&topology);
+    let points: Vec<DVec3> = vertices.iter().map(|&v| topology.positions[v]).collect();
+
+    let primitive = match kind {
+        PrimitiveKind::Plane => fit_plane(&points),
+        PrimitiveKind::Sphere => fit_sphere(&points),
+        PrimitiveKind::Cylinder => {
+            let normals: Vec<DVec3> = if selection.faces.is_empty() {
+                vertices
+                    .iter()
+                    .map(|&v| topology.vertex_normal(v))
+                    .collect()
+            } else {
+                selection
+                    .faces
+                    .iter()
+                    .filter(|&&f| topology.triangles.get(f).is_some_and(Option::is_some))
+                    .map(|&f| topology.face_normal(f))
+                    .collect()
+            };
+            fit_cylinder(&points, &normals)
+        }
+    };
+
+    primitive
+        .map(|primitive| report(primitive, &points))
+        .ok_or_else(|| format!("{:?} fit failed on {} points", kind, points.len()))
+}
+
+pub fn primitive_fit_panel(
+    mut contexts: EguiContexts,
+    selection: Res<SelectionSet>,
+    mut fit: ResMut<PrimitiveFit>,
+    mesh_query: Query<(Entity, &CgarMeshData)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Fit Primitive").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            for (label, kind) in [
+                ("Plane", PrimitiveKind::Plane),
+                ("Sphere", PrimitiveKind::Sphere),
+                ("Cylinder", PrimitiveKind::Cylinder),
+            ] {
+                if !ui.button(label).clicked() {
+                    continue;
+                }
+                let Some((entity, cgar_data)) = selection
+                    .mesh
+                    .and_then(|entity| mesh_query.get(entity).ok())
+                else {
+                    fit.status = "Select vertices or faces first".to_string();
+                    continue;
+                };
+                match fit_selection(kind, &selection, cgar_data) {
+                    Ok(report) => {
+                        fit.result = Some((entity, report));
+                        fit.status.clear();
+                    }
+                    Err(err) => fit.status = err,
+                }
+            }
+            if ui.button("Clear").clicked() {
+                fit.result = None;
+                fit.status.clear();
+            }
+        });
+
+        if let Some((_, report)) = &fit.result {
+            match report.primitive {
+                FittedPrimitive::Plane { point, normal, .. } => {
+                    ui.label(format!("Plane point: {:.6?}", point.to_array()));
+                    ui.label(format!("Plane normal: {:.6?}", normal.to_array()));
+                }
+                FittedPrimitive::Sphere { center, radius } => {
+                    ui.label(format!("Sphere center: {:.6?}", center.to_array()));
+                    ui.label(format!("Sphere radius: {:.6}", radius));
+                }
+                FittedPrimitive::Cylinder {
+                    center,
+                    axis,
+                    radius,
+                    half_length,
+                } => {
+                    ui.label(format!("Cylinder center: {:.6?}", center.to_array()));
+                    ui.label(format!("Cylinder axis: {:.6?}", axis.to_array()));
+                    ui.label(format!("Cylinder radius: {:.6}", radius));
+                    ui.label(format!("Cylinder length: {:.6}", 2.0 * half_length));
+                }
+            }
+            ui.label(format!(
+                "RMS deviation: {:.6} (max {:.6}, {} points)",
+                report.rms, report.max_deviation, report.point_count
+            ));
+        }
+        if !fit.status.is_empty() {
+            ui.label(&fit.status);
+        }
+    });
+}
+
+pub fn draw_fitted_primitive(
+    mut gizmos: Gizmos,
+    fit: Res<PrimitiveFit>,
+    mesh_query: Query<&GlobalTransform>,
+) {
+    let Some((entity, report)) = &fit.result else {
+        return;
+    };
+    let Ok(mesh_global) = mesh_query.get(*entity) else {
+        return;
+    };
+
+    let color = Color::srgba(0.3, 0.8, 1.0, 0.6);
+    let mesh_rotation = mesh_global.rotation();
+    // Gizmo sizes are taken in mesh-local units; non-uniform scales are not represented
+    let scale = mesh_global.scale().max_element();
+    let to_world = |p: DVec3| mesh_global.transform_point(p.as_vec3());
+    let orient = |n: DVec3| mesh_rotation * Quat::from_rotation_arc(Vec3::Z, n.as_vec3());
+
+    match report.primitive {
+        FittedPrimitive::Plane {
+            point,
+            normal,
+            half_size,
+        } => {
+            let size = Vec2::splat(2.0 * half_size as f32 * scale);
+            gizmos.rect(
+                Isometry3d::new(to_world(point), orient(normal)),
+                size,
+                color,
+            );
+            gizmos.arrow(
+                to_world(point),
+                to_world(point + normal * half_size * 0.5),
+                color,
+            );
+        }
+        FittedPrimitive::Sphere { center, radius } => {
+            gizmos.sphere(
+                Isometry3d::from_translation(to_world(center)),
+                radius as f32 * scale,
+                color,
+            );
+        }
+        FittedPrimitive::Cylinder {
+            center,
+            axis,
+            radius,
+            half_length,
+        } => {
+            let rotation = orient(axis);
+            let r = radius as f32 * scale;
+            let top = center + axis * half_length;
+            let bottom = center - axis * half_length;
+            gizmos.circle(Isometry3d::new(to_world(top), rotation), r, color);
+            gizmos.circle(Isometry3d::new(to_world(bottom), rotation), r, color);
+            let (u, v) = plane_basis(axis);
+            for side in [u, v, -u, -v] {
+                gizmos.line(
+                    to_world(top + side * radius),
+                    to_world(bottom + side * radius),
+                    color,
+                );
+            }
+        }
+    }
+}
+
+// Result of the last automatic segmentation
+#[derive(Resource, Default)]
+pub struct MeshSegmentation {
+    pub result: Option<(Entity, Segments)>,
+}
+
+pub struct SegmentationPanelState {
+    max_angle_deg: f32,
+    min_faces: usize,
+}
+
+impl Default for SegmentationPanelState {
+    fn default() -> Self {
+        Self {
+            max_angle_deg: 20.0,
+            min_faces: 8,
+        }
+    }
+}
+
+pub fn segmentation_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut segmentation: ResMut<MeshSegmentation>,
+    mut selection: ResMut<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData)>,
+    mut state: Local<SegmentationPanelState>,
+    #[cfg(feature = "native")] mut notices: EventWriter<Notify>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Segmentation").show(ctx, |ui| {
+        ui.add(egui::Slider::new(&mut state.max_angle_deg, 1.0..=90.0).text("Max angle (deg)"));
+        ui.add(egui::Slider::new(&mut state.min_faces, 1..=500).text("Min patch faces"));
+
+        ui.horizontal(|ui| {
+            if ui.button("Segment").clicked() {
+                let target = selection
+                    .mesh
+                    .and_then(|entity| mesh_query.get(entity).ok())
+                    .or_else(|| mesh_query.iter().next());
+                if let Some((entity, cgar_data)) = target {
+                    let topology = MeshTopology::from_cgar(&cgar_data.0);
+                    let segments = segment_by_normals(
+                        &topology,
+                        (state.max_angle_deg as f64).to_radians(),
+                        state.min_faces,
+                    );
+                    let colors = segments
+                        .labels
+                        .iter()
+                        .map(|label| label.map_or(NO_LAYER_DATA, |l| overlay_color(label_color(l))))
+                        .collect();
+                    commands.entity(entity).queue(set_layer(
+                        AnalysisLayerKind::Segmentation,
+                        FaceColorOverlay { colors },
+                    ));
+                    info!("Segmented mesh into {} patches", segments.patches.len());
+                    segmentation.result = Some((entity, segments));
+                }
+            }
+            if ui.button("Clear").clicked() {
+                if let Some((entity, _)) = segmentation.result.take() {
+                    commands
+                        .entity(entity)
+                        .queue(clear_layer(AnalysisLayerKind::Segmentation));
+                }
+            }
+        });
+
+        let Some((entity, segments)) = &segmentation.result else {
+            return;
+        };
+        let Ok((_, cgar_data)) = mesh_query.get(*entity) else {
+            return;
+        };
+        ui.separator();
+        egui::ScrollArea::vertical()
+            .max_height(240.0)
+            .show(ui, |ui| {
+                for (label, faces) in segments.patches.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            egui_color(label_color(label)),
+                            format!("Patch {}: {} faces", label, faces.len()),
+                        );
+                        if ui.small_button("Select").clicked() {
+                            selection.clear();
+                            selection.mesh = Some(*entity);
+                            selection.faces = faces.iter().copied().collect();
+                        }
+                        #[cfg(feature = "native")]
+                        if ui.small_button("Export").clicked() {
+                            let topology = MeshTopology::from_cgar(&cgar_data.0);
+                            let path = PathBuf::from(format!("segment_{}.obj", label));
+                            notices.write(match write_obj_faces(&path, &topology, faces) {
+                                Ok(()) => Notify::info(format!(
+                                    "Exported patch {} to {}",
+                                    label,
+                                    path.display()
+                                )),
+                                Err(err) => Notify::error(format!(
+                                    "Failed to export {}: {}",
+                                    path.display(),
+                                    err
+                                )),
+                            });
+                        }
+                    });
+                }
+            });
+    });
+}
+
+#[derive(Resource, Default)]
+pub struct SymmetryAnalysis {
+    pub result: Option<(Entity, SymmetryReport)>,
+    pub show_deviation: bool,
+    pub status: String,
+}
+
+fn asymmetry_field(report: &SymmetryReport, topology: &MeshTopology) -> ScalarField {
+    let deviation = report.face_deviation(topology);
+    let max = deviation
+        .iter()
+        .copied()
+        .filter(|d| d.is_finite())
+        .fold(0.0, f64::max);
+    ScalarField::new(deviation, 0.0, max)
+}
+
+pub fn symmetry_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut symmetry: ResMut<SymmetryAnalysis>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    selection: Res<SelectionSet>,
+    mut mesh_query: Query<(Entity, &Mesh3d, &mut CgarMeshData)>,
+    shading_query: Query<(Option<&FeatureEdges>, Option<&NormalSettings>)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Symmetry").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            if ui.button("Detect").clicked() {
+                let target = selection
+                    .mesh
+                    .and_then(|entity| mesh_query.get(entity).ok())
+                    .or_else(|| mesh_query.iter().next());
+                if let Some((entity, _, cgar_data)) = target {
+                    let topology = MeshTopology::from_cgar(&cgar_data.0);
+                    match detect_symmetry(&topology) {
+                        Some(report) => {
+                            if symmetry.show_deviation {
+                                commands.entity(entity).queue(set_scalar_layer(
+                                    AnalysisLayerKind::Asymmetry,
+                                    asymmetry_field(&report, &topology),
+                                ));
+                            }
+                            symmetry.status.clear();
+                            symmetry.result = Some((entity, report));
+                        }
+                        None => symmetry.status = "Not enough vertices".to_string(),
+                    }
+                }
+            }
+            if ui.button("Clear").clicked() {
+                if let Some((entity, _)) = symmetry.result.take() {
+                    commands
+                        .entity(entity)
+                        .queue(clear_layer(AnalysisLayerKind::Asymmetry));
+                }
+            }
+        });
+
+        let mut show_deviation = symmetry.show_deviation;
+        if ui
+            .checkbox(&mut show_deviation, "Color by asymmetry")
+            .changed()
+        {
+            symmetry.show_deviation = show_deviation;
+            if let Some((entity, report)) = &symmetry.result {
+                if show_deviation {
+                    if let Ok((_, _, cgar_data)) = mesh_query.get(*entity) {
+                        let topology = MeshTopology::from_cgar(&cgar_data.0);
+                        commands.entity(*entity).queue(set_scalar_layer(
+                            AnalysisLayerKind::Asymmetry,
+                            asymmetry_field(report, &topology),
+                        ));
+                    }
+                } else {
+                    commands
+                        .entity(*entity)
+                        .queue(clear_layer(AnalysisLayerKind::Asymmetry));
+                }
+            }
+        }
+
+        let mut mirror_target = None;
+        if let Some((entity, report)) = &symmetry.result {
+            let plane = report.plane;
+            ui.label(format!("Plane point: {:.6?}", plane.point.to_array()));
+            ui.label(format!("Plane normal: {:.6?}", plane.normal.to_array()));
+            ui.label(format!("Mirror RMS deviation: {:.6}", report.rms));
+            if ui.button("Mirror mesh across plane").clicked() {
+                mirror_target = Some((*entity, plane));
+            }
+        }
+        if let Some((entity, plane)) = mirror_target {
+            if let Ok((_, mesh_handle, mut cgar_data)) = mesh_query.get_mut(entity) {
+                let topology = MeshTopology::from_cgar(&cgar_data.0);
+                let (positions, triangles) = mirror_geometry(&topology, &plane);
+                cgar_data.0 = build_cgar_mesh(&positions, triangles);
+                // Mirroring keeps vertex ids, so feature tags still apply;
+                // authored normals would point the wrong way
+                let (features, settings) = shading_query.get(entity).unwrap_or_default();
+                meshes.insert(
+                    &mesh_handle.0,
+                    render_mesh(&cgar_data.0, None, features, None, settings),
+                );
+                commands
+                    .entity(entity)
+                    .remove::<ImportedNormals>()
+                    .queue(clear_layer(AnalysisLayerKind::Asymmetry));
+                symmetry.result = None;
+                info!("Mirrored mesh across symmetry plane");
+            }
+        }
+
+        if !symmetry.status.is_empty() {
+            ui.label(&symmetry.status);
+        }
+    });
+}
+
+pub fn draw_symmetry_plane(
+    mut gizmos: Gizmos,
+    symmetry: Res<SymmetryAnalysis>,
+    mesh_query: Query<&GlobalTransform>,
+) {
+    let Some((entity, report)) = &symmetry.result else {
+        return;
+    };
+    let Ok(mesh_global) = mesh_query.get(*entity) else {
+        return;
+    };
+    let plane = report.plane;
+    let rotation =
+        mesh_global.rotation() * Quat::from_rotation_arc(Vec3::Z, plane.normal.as_vec3());
+    let size = Vec2::splat(2.0 * report.half_size as f32 * mesh_global.scale().max_element());
+    gizmos.rect(
+        Isometry3d::new(mesh_global.transform_point(plane.point.as_vec3()), rotation),
+        size,
+        Color::srgba(1.0, 0.3, 0.8, 0.8),
+    );
+}
+
+#[derive(Resource)]
+pub struct OverhangAnalysis {
+    pub build_direction: BuildDirection,
+    pub threshold_deg: f32,
+    pub skip_bed: bool,
+    pub result: Option<(Entity, OverhangReport)>,
+}
+
+impl Default for OverhangAnalysis {
+    fn default() -> Self {
+        Self {
+            build_direction: BuildDirection::PosY,
+            threshold_deg: 45.0,
+            skip_bed: true,
+            result: None,
+        }
+    }
+}
+
+fn overhang_overlay(report: &OverhangReport) -> FaceColorOverlay {
+    let mild = Srgba::rgb(1.0, 0.75, 0.2);
+    let severe = Srgba::rgb(0.85, 0.05, 0.05);
+    let colors = report
+        .severity
+        .iter()
+        .map(|severity| match severity {
+            Some(s) => overlay_color(mild.mix(&severe, *s as f32).into()),
+            None => NO_LAYER_DATA,
+        })
+        .collect();
+    FaceColorOverlay { colors }
+}
+
+pub fn overhang_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut overhangs: ResMut<OverhangAnalysis>,
+    mut selection: ResMut<SelectionSet>,
+    mesh_query: Query<(Entity, &GlobalTransform, &CgarMeshData)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Overhangs").show(ctx, |ui| {
+        let mut direction = overhangs.build_direction;
+        egui::ComboBox::from_label("Build direction (world)")
+            .selected_text(direction.label())
+            .show_ui(ui, |ui| {
+                for option in BuildDirection::ALL {
+                    ui.selectable_value(&mut direction, option, option.label());
+                }
+            });
+        let mut threshold = overhangs.threshold_deg;
+        ui.add(egui::Slider::new(&mut threshold, 0.0..=89.0).text("Overhang threshold (deg)"));
+        let mut skip_bed = overhangs.skip_bed;
+        ui.checkbox(&mut skip_bed, "Ignore faces resting on the bed");
+        let settings_changed = direction != overhangs.build_direction
+            || threshold != overhangs.threshold_deg
+            || skip_bed != overhangs.skip_bed;
+        if settings_changed {
+            overhangs.build_direction = direction;
+            overhangs.threshold_deg = threshold;
+            overhangs.skip_bed = skip_bed;
+        }
+
+        let mut analyze = None;
+        ui.horizontal(|ui| {
+            if ui.button("Analyze").clicked() {
+                analyze = selection
+                    .mesh
+                    .and_then(|entity| mesh_query.get(entity).ok())
+                    .or_else(|| mesh_query.iter().next())
+                    .map(|(entity, ..)| entity);
+            }
+            if ui.button("Clear").clicked() {
+                if let Some((entity, _)) = overhangs.result.take() {
+                    commands
+                        .entity(entity)
+                        .queue(clear_layer(AnalysisLayerKind::Overhang));
+                }
+            }
+        });
+        // Settings edits re-run the analysis on the mesh already shown
+        if settings_changed && analyze.is_none() {
+            analyze = overhangs.result.as_ref().map(|(entity, _)| *entity);
+        }
+        if let Some((entity, global, cgar_data)) = analyze.and_then(|e| mesh_query.get(e).ok()) {
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            // The build direction is fixed in the world; analyze in the mesh's own frame
+            let up = global.rotation().inverse().as_dquat() * overhangs.build_direction.vector();
+            let report = overhang_analysis(
+                &topology,
+                up,
+                (overhangs.threshold_deg as f64).to_radians(),
+                overhangs.skip_bed,
+            );
+            commands.entity(entity).queue(set_layer(
+                AnalysisLayerKind::Overhang,
+                overhang_overlay(&report),
+            ));
+            overhangs.result = Some((entity, report));
+        }
+
+        let Some((entity, report)) = &overhangs.result else {
+            return;
+        };
+        ui.separator();
+        let percent = if report.total_area > 0.0 {
+            100.0 * report.area / report.total_area
+        } else {
+            0.0
+        };
+        ui.label(format!(
+            "Overhang area: {:.6} ({:.1}% of {:.6})",
+            report.area, percent, report.total_area
+        ));
+        ui.label(format!("Overhang faces: {}", report.faces.len()));
+        if ui.button("Select overhang faces").clicked() {
+            selection.clear();
+            selection.mesh = Some(*entity);
+            selection.faces = report.faces.iter().copied().collect();
+        }
+    });
+}
+
+#[derive(Resource, Default)]
+pub struct ThicknessAnalysis {
+    // Minimum printable wall; zero until first picked from the mesh size
+    pub min_thickness: f64,
+    pub result: Option<(Entity, ThicknessReport)>,
+}
+
+fn thickness_field(report: &ThicknessReport, threshold: f64) -> ScalarField {
+    // Color the acceptable walls over [threshold, 4 * threshold]; thin ones stand out in red
+    ScalarField {
+        below: Some(Color::srgb(0.9, 0.05, 0.05)),
+        auto_range: false,
+        ..ScalarField::new(report.thickness.clone(), threshold, 4.0 * threshold)
+    }
+}
+
+pub fn thickness_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut analysis: ResMut<ThicknessAnalysis>,
+    mut selection: ResMut<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData, Option<&FaceTreeCache>)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Wall Thickness").show(ctx, |ui| {
+        let target = selection
+            .mesh
+            .and_then(|entity| mesh_query.get(entity).ok())
+            .or_else(|| mesh_query.iter().next());
+
+        // Default to 1% of the mesh's bounding diagonal
+        let needs_default = analysis.min_thickness <= 0.0;
+        if let Some((_, cgar_data, _)) = target.filter(|_| needs_default) {
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            let (lo, hi) = topology.used_vertices().fold(
+                (DVec3::splat(f64::INFINITY), DVec3::splat(f64::NEG_INFINITY)),
+                |(lo, hi), v| (lo.min(topology.positions[v]), hi.max(topology.positions[v])),
+            );
+            analysis.min_thickness = ((hi - lo).length() * 0.01).max(f64::EPSILON);
+        }
+        let mut threshold = analysis.min_thickness;
+        let speed = threshold * 0.01;
+        ui.horizontal(|ui| {
+            ui.label("Minimum thickness");
+            ui.add(
+                egui::DragValue::new(&mut threshold)
+                    .speed(speed)
+                    .range(0.0..=f64::MAX),
+            );
+        });
+        let threshold_changed = threshold != analysis.min_thickness;
+        if threshold_changed {
+            analysis.min_thickness = threshold;
+        }
+
+        let mut analyze = None;
+        ui.horizontal(|ui| {
+            if ui.button("Analyze").clicked() {
+                analyze = target.map(|(entity, ..)| entity);
+            }
+            if ui.button("Clear").clicked() {
+                if let Some((entity, _)) = analysis.result.take() {
+                    commands
+                        .entity(entity)
+                        .queue(clear_layer(AnalysisLayerKind::Thickness));
+                }
+            }
+        });
+
+        if let Some((entity, cgar_data, cache)) = analyze.and_then(|e| mesh_query.get(e).ok()) {
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            let built;
+            let surface = match cache {
+                Some(cache) => &cache.0,
+                None => {
+                    built = FaceSurface::build(&cgar_data.0);
+                    &built
+                }
+            };
+            let report = wall_thickness(&topology, (&cgar_data.0, surface), analysis.min_thickness);
+            commands.entity(entity).queue(set_scalar_layer(
+                AnalysisLayerKind::Thickness,
+                thickness_field(&report, analysis.min_thickness),
+            ));
+            analysis.result = Some((entity, report));
+        } else if let Some((entity, report)) =
+            analysis.result.as_mut().filter(|_| threshold_changed)
+        {
+            // Re-flag the existing measurements against the new threshold
+            report.thin_faces.clear();
+            report.thin_area = 0.0;
+            if let Ok((_, cgar_data, _)) = mesh_query.get(*entity) {
+                let topology = MeshTopology::from_cgar(&cgar_data.0);
+                for (f, _) in topology.live_faces() {
+                    if report.thickness[f] < threshold {
+                        report.thin_faces.push(f);
+                        report.thin_area += topology.face_area(f);
+                    }
+                }
+            }
+            commands.entity(*entity).queue(set_scalar_layer(
+                AnalysisLayerKind::Thickness,
+                thickness_field(report, threshold),
+            ));
+        }
+
+        let Some((entity, report)) = &analysis.result else {
+            return;
+        };
+        ui.separator();
+        if report.min_thickness.is_finite() {
+            ui.label(format!("Thinnest wall: {:.6}", report.min_thickness));
+        } else {
+            ui.label("No opposite walls found (open surface?)");
+        }
+        ui.label(format!(
+            "Thin faces: {} (area {:.6})",
+            report.thin_faces.len(),
+            report.thin_area
+        ));
+        ui.horizontal(|ui| {
+            if ui.button("Select thin faces").clicked() {
+                selection.clear();
+                selection.mesh = Some(*entity);
+                selection.faces = report.thin_faces.iter().copied().collect();
+            }
+            if ui.button("Store as face attribute").clicked() {
+                store_attribute(
+                    &mut commands,
+                    *entity,
+                    AttributeDomain::Face,
+                    "thickness".to_string(),
+                    AttributeValues::Float(report.thickness.clone()),
+                );
+            }
+        });
+    });
+}
+
+#[derive(Resource)]
+pub struct SlicePreview {
+    pub enabled: bool,
+    pub mesh: Option<Entity>,
+    pub build_direction: BuildDirection,
+    // Zero until first derived from the mesh height
+    pub layer_height: f64,
+    pub layer: usize,
+    pub layer_count: usize,
+    // Contours of the current layer, in mesh-local space
+    pub contours: Vec<Contour>,
+    // Local-space build direction and layer height the contours were cut at
+    pub plane: Option<(DVec3, f64)>,
+}
+
+impl Default for SlicePreview {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mesh: None,
+            build_direction: BuildDirection::PosY,
+            layer_height: 0.0,
+            layer: 0,
+            layer_count: 0,
+            contours: Vec::new(),
+            plane: None,
+        }
+    }
+}
+
+// Re-slices whenever the settings, the mesh or its orientation change
+pub fn update_slice_preview(
+    mut preview: ResMut<SlicePreview>,
+    mesh_query: Query<(Ref<GlobalTransform>, Ref<CgarMeshData>)>,
+) {
+    if !preview.enabled {
+        return;
+    }
+    let Some((global, cgar_data)) = preview.mesh.and_then(|e| mesh_query.get(e).ok()) else {
+        return;
+    };
+    if !preview.is_changed() && !global.is_changed() && !cgar_data.is_changed() {
+        return;
+    }
+
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    let up =
+        (global.rotation().inverse().as_dquat() * preview.build_direction.vector()).normalize();
+    let Some((bottom, top)) = height_range(&topology, up) else {
+        preview.contours.clear();
+        preview.layer_count = 0;
+        return;
+    };
+    if preview.layer_height <= 0.0 {
+        preview.layer_height = ((top - bottom) / 100.0).max(f64::EPSILON);
+    }
+    let layer_count = (((top - bottom) / preview.layer_height).ceil() as usize).max(1);
+    let layer = preview.layer.min(layer_count - 1);
+    // Cut through the middle of each layer so the bed face itself is never sliced
+    let height = bottom + (layer as f64 + 0.5) * preview.layer_height;
+    let contours = slice_mesh(&topology, up, height);
+
+    let preview = preview.bypass_change_detection();
+    preview.layer_count = layer_count;
+    preview.layer = layer;
+    preview.contours = contours;
+    preview.plane = Some((up, height));
+}
+
+pub fn slice_preview_panel(
+    mut contexts: EguiContexts,
+    mut preview: ResMut<SlicePreview>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<Entity, With<CgarMeshData>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Slice Preview").show(ctx, |ui| {
+        let mut enabled = preview.enabled;
+        if ui.checkbox(&mut enabled, "Enabled").changed() {
+            preview.enabled = enabled;
+            if enabled {
+                preview.mesh = selection
+                    .mesh
+                    .filter(|e| mesh_query.contains(*e))
+                    .or_else(|| mesh_query.iter().next());
+            }
+        }
+        if !preview.enabled {
+            return;
+        }
+
+        let mut direction = preview.build_direction;
+        egui::ComboBox::from_label("Build direction (world)")
+            .selected_text(direction.label())
+            .show_ui(ui, |ui| {
+                for option in BuildDirection::ALL {
+                    ui.selectable_value(&mut direction, option, option.label());
+                }
+            });
+        if direction != preview.build_direction {
+            preview.build_direction = direction;
+        }
+
+        let mut layer_height = preview.layer_height;
+        let speed = layer_height * 0.01;
+        ui.horizontal(|ui| {
+            ui.label("Layer height");
+            ui.add(
+                egui::DragValue::new(&mut layer_height)
+                    .speed(speed)
+                    .range(f64::EPSILON..=f64::MAX),
+            );
+        });
+        if layer_height != preview.layer_height {
+            preview.layer_height = layer_height;
+        }
+
+        let last = preview.layer_count.saturating_sub(1);
+        let mut layer = preview.layer;
+        ui.horizontal(|ui| {
+            if ui.button("<").clicked() {
+                layer = layer.saturating_sub(1);
+            }
+            ui.add(egui::Slider::new(&mut layer, 0..=last).text("Layer"));
+            if ui.button(">").clicked() {
+                layer = (layer + 1).min(last);
+            }
+        });
+        if layer != preview.layer {
+            preview.layer = layer;
+        }
+
+        ui.separator();
+        let open = preview.contours.iter().filter(|c| !c.closed).count();
+        ui.label(format!(
+            "Layer {} of {}: {} contours, total length {:.6}",
+            preview.layer + 1,
+            preview.layer_count,
+            preview.contours.len(),
+            preview.contours.iter().map(Contour::length).sum::<f64>()
+        ));
+        if open > 0 {
+            ui.colored_label(
+                egui::Color32::from_rgb(230, 120, 40),
+                format!("{} open contours (mesh is not watertight here)", open),
+            );
+        }
+    });
+}
+
+pub fn draw_slice_preview(
+    mut gizmos: Gizmos,
+    preview: Res<SlicePreview>,
+    mesh_query: Query<&GlobalTransform>,
+) {
+    if !preview.enabled {
+        return;
+    }
+    let Some(mesh_global) = preview.mesh.and_then(|e| mesh_query.get(e).ok()) else {
+        return;
+    };
+    let world = |p: DVec3| mesh_global.transform_point(p.as_vec3());
+    for contour in &preview.contours {
+        let color = if contour.closed {
+            Color::srgb(0.2, 1.0, 0.3)
+        } else {
+            Color::srgb(1.0, 0.5, 0.1)
+        };
+        let mut points: Vec<Vec3> = contour.points.iter().map(|&p| world(p)).collect();
+        if let Some(&first) = points.first().filter(|_| contour.closed) {
+            points.push(first);
+        }
+        gizmos.linestrip(points, color);
+    }
+}
+
+#[derive(Resource)]
+pub struct MassAnalysis {
+    pub density: f64,
+    pub show_axes: bool,
+    pub result: Option<(Entity, MassProperties)>,
+    pub watertight: bool,
+    // Farthest vertex from the center of mass, used to size the axis gizmos
+    pub extent: f64,
+    pub status: String,
+}
+
+impl Default for MassAnalysis {
+    fn default() -> Self {
+        Self {
+            density: 1.0,
+            show_axes: true,
+            result: None,
+            watertight: true,
+            extent: 1.0,
+            status: String::new(),
+        }
+    }
+}
+
+pub fn mass_properties_panel(
+    mut contexts: EguiContexts,
+    mut analysis: ResMut<MassAnalysis>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData, &GlobalTransform)>,
+    mut transform_query: Query<&mut Transform, With<CgarMeshData>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Mass Properties").show(ctx, |ui| {
+        let mut density = analysis.density;
+        ui.horizontal(|ui| {
+            ui.label("Density");
+            ui.add(
+                egui::DragValue::new(&mut density)
+                    .speed(0.01)
+                    .range(0.0..=f64::MAX),
+            );
+        });
+        if density != analysis.density {
+            analysis.density = density;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Compute").clicked() {
+                let target = selection
+                    .mesh
+                    .and_then(|entity| mesh_query.get(entity).ok())
+                    .or_else(|| mesh_query.iter().next());
+                if let Some((entity, cgar_data, _)) = target {
+                    let topology = MeshTopology::from_cgar(&cgar_data.0);
+                    analysis.watertight = is_watertight(&topology);
+                    match mass_properties(&topology, analysis.density) {
+                        Some(props) => {
+                            analysis.extent = topology
+                                .used_vertices()
+                                .map(|v| topology.positions[v].distance(props.center))
+                                .fold(0.0, f64::max);
+                            analysis.result = Some((entity, props));
+                            analysis.status.clear();
+                        }
+                        None => {
+                            analysis.result = None;
+                            analysis.status = "Mesh encloses no volume".to_string();
+                        }
+                    }
+                }
+            }
+            let mut show_axes = analysis.show_axes;
+            if ui.checkbox(&mut show_axes, "Show principal axes").changed() {
+                analysis.show_axes = show_axes;
+            }
+        });
+
+        if !analysis.status.is_empty() {
+            ui.label(&analysis.status);
+        }
+        let Some((entity, props)) = analysis.result else {
+            return;
+        };
+        ui.separator();
+        if !analysis.watertight {
+            ui.colored_label(
+                egui::Color32::from_rgb(230, 120, 40),
+                "Mesh is not watertight; results are approximate",
+            );
+        }
+        if props.inverted {
+            ui.label("Faces are wound inward; volume sign was flipped");
+        }
+        ui.label(format!("Volume: {:.6}", props.volume));
+        ui.label(format!("Mass: {:.6}", props.mass));
+        ui.label(format!(
+            "Center of mass (local): {:.6?}",
+            props.center.to_array()
+        ));
+        ui.label("Inertia tensor (about center of mass):");
+        for row in props.inertia.transpose().to_cols_array_2d() {
+            ui.monospace(format!(
+                "{:>14.6} {:>14.6} {:>14.6}",
+                row[0], row[1], row[2]
+            ));
+        }
+        ui.label(format!(
+            "Principal moments: {:.6?}",
+            props.principal_moments
+        ));
+
+        if ui.button("Align to principal axes").clicked() {
+            if let (Ok((_, _, global)), Ok(mut transform)) =
+                (mesh_query.get(entity), transform_query.get_mut(entity))
+            {
+                // Rotate the principal frame onto world X/Y/Z and move the center of mass to the origin
+                let rotation = global.rotation();
+                let frame = Mat3::from_cols(
+                    rotation * props.principal_axes[0].as_vec3(),
+                    rotation * props.principal_axes[1].as_vec3(),
+                    rotation * props.principal_axes[2].as_vec3(),
+                );
+                let align = Quat::from_mat3(&frame.transpose()).normalize();
+                let center = global.transform_point(props.center.as_vec3());
+                let delta = Transform {
+                    translation: -(align * center),
+                    rotation: align,
+                    ..Transform::IDENTITY
+                };
+                *transform = delta.mul_transform(*transform);
+                info!("Aligned mesh to its principal axes");
+            }
+        }
+    });
+}
+
+pub fn draw_principal_axes(
+    mut gizmos: Gizmos,
+    analysis: Res<MassAnalysis>,
+    mesh_query: Query<&GlobalTransform>,
+) {
+    if !analysis.show_axes {
+        return;
+    }
+    let Some((entity, props)) = &analysis.result else {
+        return;
+    };
+    let Ok(mesh_global) = mesh_query.get(*entity) else {
+        return;
+    };
+    let center = mesh_global.transform_point(props.center.as_vec3());
+    let colors = [
+        Color::srgb(1.0, 0.2, 0.2),
+        Color::srgb(0.2, 1.0, 0.2),
+        Color::srgb(0.3, 0.4, 1.0),
+    ];
+    for (axis, color) in props.principal_axes.iter().zip(colors) {
+        let tip = mesh_global.transform_point((props.center + *axis * analysis.extent).as_vec3());
+        gizmos.arrow(center, tip, color);
+    }
+    gizmos.sphere(Isometry3d::from_translation(center), 0.02, Color::WHITE);
+}
+
+#[derive(Resource)]
+pub struct EdgeLengthAnalysis {
+    pub bin_count: usize,
+    pub result: Option<(Entity, EdgeLengthReport)>,
+    // Length range picked on the histogram; its edges are highlighted
+    pub brush: Option<(f64, f64)>,
+}
+
+impl Default for EdgeLengthAnalysis {
+    fn default() -> Self {
+        Self {
+            bin_count: 32,
+            result: None,
+            brush: None,
+        }
+    }
+}
+
+// Bars of `report.bins`; dragging across them picks a length range
+fn edge_length_histogram(
+    ui: &mut egui::Ui,
+    report: &EdgeLengthReport,
+    brush: &mut Option<(f64, f64)>,
+) {
+    let size = egui::vec2(ui.available_width().max(200.0), 100.0);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let bin_at = |x: f32| {
+        let t = ((x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+        ((t * report.bins.len() as f32) as usize).min(report.bins.len() - 1)
+    };
+    let bin_range = |bin: usize| {
+        let width = report.bin_width();
+        let lo = report.min + width * bin as f64;
+        let hi = if bin + 1 == report.bins.len() {
+            report.max
+        } else {
+            lo + width
+        };
+        (lo, hi)
+    };
+    if let Some(pos) = response.interact_pointer_pos() {
+        let press = response
+            .ctx
+            .input(|input| input.pointer.press_origin())
+            .unwrap_or(pos);
+        let (first, last) = (bin_at(press.x), bin_at(pos.x));
+        *brush = Some((bin_range(first.min(last)).0, bin_range(first.max(last)).1));
+    }
+
+    let tallest = report.bins.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let bar_width = rect.width() / report.bins.len() as f32;
+    for (bin, &count) in report.bins.iter().enumerate() {
+        let (lo, hi) = bin_range(bin);
+        let brushed = brush.is_some_and(|(from, to)| lo >= from && hi <= to);
+        let height = rect.height() * count as f32 / tallest;
+        let left = rect.left() + bar_width * bin as f32;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(left + 0.5, rect.bottom() - height),
+            egui::pos2(left + bar_width - 0.5, rect.bottom()),
+        );
+        let color = if brushed {
+            egui::Color32::from_rgb(255, 170, 50)
+        } else {
+            egui::Color32::from_rgb(90, 140, 200)
+        };
+        painter.rect_filled(bar, 0.0, color);
+    }
+    response.on_hover_text("Drag across bars to highlight those edges");
+}
+
+pub fn edge_length_panel(
+    mut contexts: EguiContexts,
+    mut analysis: ResMut<EdgeLengthAnalysis>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Edge Lengths")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut bin_count = analysis.bin_count;
+            ui.add(egui::Slider::new(&mut bin_count, 4..=128).text("Bins"));
+            let bins_changed = bin_count != analysis.bin_count;
+            if bins_changed {
+                analysis.bin_count = bin_count;
+            }
+
+            let mut analyze = None;
+            ui.horizontal(|ui| {
+                if ui.button("Analyze").clicked() {
+                    analyze = selection
+                        .mesh
+                        .and_then(|entity| mesh_query.get(entity).ok())
+                        .or_else(|| mesh_query.iter().next())
+                        .map(|(entity, _)| entity);
+                }
+                if ui.button("Clear").clicked() {
+                    analysis.result = None;
+                    analysis.brush = None;
+                }
+            });
+            if bins_changed && analyze.is_none() {
+                analyze = analysis.result.as_ref().map(|(entity, _)| *entity);
+            }
+            if let Some((entity, cgar_data)) = analyze.and_then(|e| mesh_query.get(e).ok()) {
+                let topology = MeshTopology::from_cgar(&cgar_data.0);
+                analysis.result = Some((entity, edge_length_stats(&topology, bin_count)));
+            }
+
+            let EdgeLengthAnalysis { result, brush, .. } = &mut *analysis;
+            let Some((_, report)) = result else {
+                return;
+            };
+            ui.separator();
+            if report.edges.is_empty() {
+                ui.label("The mesh has no edges");
+                return;
+            }
+            egui::Grid::new("edge_length_stats").show(ui, |ui| {
+                ui.label("Edges");
+                ui.label(report.edges.len().to_string());
+                ui.end_row();
+                for (label, value) in [
+                    ("Min", report.min),
+                    ("Max", report.max),
+                    ("Mean", report.mean),
+                    ("Median", report.median),
+                ] {
+                    ui.label(label);
+                    ui.label(format!("{:.6}", value));
+                    ui.end_row();
+                }
+            });
+            edge_length_histogram(ui, report, brush);
+            if let Some((lo, hi)) = *brush {
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{:.6} to {:.6}: {} edges",
+                        lo,
+                        hi,
+                        report.edges_between(lo, hi).len()
+                    ));
+                    if ui.button("Clear highlight").clicked() {
+                        *brush = None;
+                    }
+                });
+            }
+        });
+}
+
+pub fn draw_edge_length_brush(
+    mut gizmos: Gizmos,
+    analysis: Res<EdgeLengthAnalysis>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+) {
+    let (Some((entity, report)), Some((lo, hi))) = (&analysis.result, analysis.brush) else {
+        return;
+    };
+    let Ok((mesh_global, cgar_data)) = mesh_query.get(*entity) else {
+        return;
+    };
+    let color = Color::srgb(1.0, 0.65, 0.2);
+    for &((a, b), _) in report.edges_between(lo, hi) {
+        if a.max(b) >= cgar_data.0.vertices.len() {
+            continue;
+        }
+        gizmos.line(
+            mesh_global.transform_point(vertex_position(&cgar_data.0, a)),
+            mesh_global.transform_point(vertex_position(&cgar_data.0, b)),
+            color,
+        );
+    }
+}
+
+// Mesh-local plane that deviations are measured from
+#[derive(Debug, Clone, Copy)]
+pub struct ReferencePlane {
+    pub mesh: Entity,
+    pub point: DVec3,
+    pub normal: DVec3,
+}
+
+#[derive(Resource, Default)]
+pub struct FlatnessInspection {
+    // Clicks on a mesh are collected for a three-point plane
+    pub picking: bool,
+    pub picks: Vec<(Entity, DVec3)>,
+    pub plane: Option<ReferencePlane>,
+    pub report: Option<FlatnessReport>,
+    // Distance shown at full color; zero follows the largest deviation
+    pub color_range: f64,
+    // Set when the plane or coloring changed and the report must be redone
+    pub dirty: bool,
+    pub status: String,
+}
+
+fn flatness_field(report: &FlatnessReport, color_range: f64) -> ScalarField {
+    let range = if color_range > 0.0 {
+        color_range
+    } else {
+        report.min.abs().max(report.max.abs())
+    }
+    .max(f64::EPSILON);
+    // A range set by hand is kept as is
+    ScalarField {
+        diverging: true,
+        auto_range: color_range <= 0.0,
+        ..ScalarField::new(report.face_distance.clone(), -range, range)
+    }
+}
+
+pub fn record_flatness_picks(
+    mut inspection: ResMut<FlatnessInspection>,
+    mut picked: EventReader<MeshPicked>,
+    mesh_query: Query<&GlobalTransform, With<CgarMeshData>>,
+) {
+    for event in picked.read() {
+        if !inspection.picking || event.double_click {
+            continue;
+        }
+        let (Some(world), Ok(global)) = (event.world_position, mesh_query.get(event.entity)) else {
+            continue;
+        };
+        // All three points have to be on one mesh; a click elsewhere starts over
+        if inspection
+            .picks
+            .first()
+            .is_some_and(|(mesh, _)| *mesh != event.entity)
+        {
+            inspection.picks.clear();
+        }
+        let local = global.affine().inverse().transform_point3(world).as_dvec3();
+        inspection.picks.push((event.entity, local));
+        if inspection.picks.len() < 3 {
+            continue;
+        }
+        let points = [0, 1, 2].map(|i| inspection.picks[i].1);
+        inspection.picking = false;
+        inspection.picks.clear();
+        match plane_from_points(points) {
+            Some((point, normal)) => {
+                inspection.plane = Some(ReferencePlane {
+                    mesh: event.entity,
+                    point,
+                    normal,
+                });
+                inspection.dirty = true;
+                inspection.status.clear();
+            }
+            None => inspection.status = "The three points are collinear".to_string(),
+        }
+    }
+}
+
+// Re-measures after the plane changes or its mesh is edited
+pub fn update_flatness(
+    mut commands: Commands,
+    mut inspection: ResMut<FlatnessInspection>,
+    mesh_query: Query<Ref<CgarMeshData>>,
+) {
+    let Some(plane) = inspection.plane else {
+        return;
+    };
+    let Ok(cgar_data) = mesh_query.get(plane.mesh) else {
+        inspection.plane = None;
+        inspection.report = None;
+        return;
+    };
+    if !inspection.dirty && !cgar_data.is_changed() {
+        return;
+    }
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    let report = plane_deviation(&topology, plane.point, plane.normal);
+    commands.entity(plane.mesh).queue(set_scalar_layer(
+        AnalysisLayerKind::Flatness,
+        flatness_field(&report, inspection.color_range),
+    ));
+    inspection.report = Some(report);
+    inspection.dirty = false;
+}
+
+pub fn flatness_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut inspection: ResMut<FlatnessInspection>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Flatness")
+        .default_open(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Fit plane to selection").clicked() {
+                    let fitted = selection
+                        .mesh
+                        .and_then(|entity| mesh_query.get(entity).ok())
+                        .ok_or_else(|| "Select vertices or faces first".to_string())
+                        .and_then(|(entity, cgar_data)| {
+                            fit_selection(PrimitiveKind::Plane, &selection, cgar_data)
+                                .map(|report| (entity, report.primitive))
+                        });
+                    match fitted {
+                        Ok((mesh, FittedPrimitive::Plane { point, normal, .. })) => {
+                            inspection.plane = Some(ReferencePlane {
+                                mesh,
+                                point,
+                                normal,
+                            });
+                            inspection.dirty = true;
+                            inspection.status.clear();
+                        }
+                        Ok(_) => {}
+                        Err(err) => inspection.status = err,
+                    }
+                }
+                let picking = inspection.picking;
+                let label = if picking {
+                    "Cancel picking"
+                } else {
+                    "Pick 3 points"
+                };
+                if ui.selectable_label(picking, label).clicked() {
+                    inspection.picking = !picking;
+                    inspection.picks.clear();
+                }
+                if ui.button("Clear").clicked() {
+                    if let Some(plane) = inspection.plane.take() {
+                        commands
+                            .entity(plane.mesh)
+                            .queue(clear_layer(AnalysisLayerKind::Flatness));
+                    }
+                    inspection.report = None;
+                    inspection.status.clear();
+                }
+            });
+            if inspection.picking {
+                ui.label(format!(
+                    "Click point {} of 3 on the mesh",
+                    inspection.picks.len() + 1
+                ));
+            }
+            if !inspection.status.is_empty() {
+                ui.label(&inspection.status);
+            }
+
+            let (Some(plane), Some(report)) = (inspection.plane, &inspection.report) else {
+                return;
+            };
+            ui.separator();
+            ui.label(format!("Plane normal: {:.6?}", plane.normal.to_array()));
+            egui::Grid::new("flatness_report").show(ui, |ui| {
+                for (label, value) in [
+                    ("Lowest", report.min),
+                    ("Highest", report.max),
+                    ("Flatness", report.flatness()),
+                ] {
+                    ui.label(label);
+                    ui.label(format!("{:.6}", value));
+                    ui.end_row();
+                }
+            });
+            let mut color_range = inspection.color_range;
+            let speed = (report.flatness() * 0.01).max(f64::EPSILON);
+            ui.horizontal(|ui| {
+                ui.label("Color range (0 = auto)");
+                ui.add(
+                    egui::DragValue::new(&mut color_range)
+                        .speed(speed)
+                        .range(0.0..=f64::MAX),
+                );
+            });
+            if color_range != inspection.color_range {
+                inspection.color_range = color_range;
+                inspection.dirty = true;
+            }
+            if ui.button("Flip normal").clicked() {
+                inspection.plane = Some(ReferencePlane {
+                    normal: -plane.normal,
+                    ..plane
+                });
+                inspection.dirty = true;
+            }
+        });
+}
+
+pub fn draw_flatness_plane(
+    mut gizmos: Gizmos,
+    inspection: Res<FlatnessInspection>,
+    mesh_query: Query<&GlobalTransform, With<CgarMeshData>>,
+) {
+    let color = Color::srgb(0.9, 0.9, 0.3);
+    for (mesh, local) in &inspection.picks {
+        if let Ok(mesh_global) = mesh_query.get(*mesh) {
+            let at = mesh_global.transform_point(local.as_vec3());
+            gizmos.sphere(Isometry3d::from_translation(at), 0.01, color);
+        }
+    }
+    let Some(plane) = inspection.plane else {
+        return;
+    };
+    let Ok(mesh_global) = mesh_query.get(plane.mesh) else {
+        return;
+    };
+    let Some(report) = &inspection.report else {
+        return;
+    };
+    // A square spanning the mesh's footprint on the plane
+    let (u, v) = plane_basis(plane.normal);
+    let half = report.half_extent;
+    let corners = [
+        (-1.0, -1.0),
+        (1.0, -1.0),
+        (1.0, 1.0),
+        (-1.0, 1.0),
+        (-1.0, -1.0),
+    ]
+    .map(|(a, b)| mesh_global.transform_point((plane.point + (u * a + v * b) * half).as_vec3()));
+    gizmos.linestrip(corners, color);
+    let center = mesh_global.transform_point(plane.point.as_vec3());
+    let tip = mesh_global.transform_point((plane.point + plane.normal * half * 0.25).as_vec3());
+    gizmos.arrow(center, tip, color);
+}
+
+// Draws the skeleton through the surface that hides it
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct SkeletonGizmos;
+
+#[derive(Resource)]
+pub struct SkeletonAnalysis {
+    // Geodesic bands the mesh is cut into; more gives a finer graph
+    pub levels: usize,
+    // Skeleton in mesh-local space
+    pub result: Option<(Entity, Skeleton)>,
+}
+
+impl Default for SkeletonAnalysis {
+    fn default() -> Self {
+        Self {
+            levels: 32,
+            result: None,
+        }
+    }
+}
+
+pub fn skeleton_panel(
+    mut contexts: EguiContexts,
+    mut analysis: ResMut<SkeletonAnalysis>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData)>,
+    #[cfg(feature = "native")] mut notices: EventWriter<Notify>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Skeleton")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut levels = analysis.levels;
+            ui.add(egui::Slider::new(&mut levels, 4..=256).text("Levels"));
+            if levels != analysis.levels {
+                analysis.levels = levels;
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Extract").clicked() {
+                    let target = selection
+                        .mesh
+                        .and_then(|entity| mesh_query.get(entity).ok())
+                        .or_else(|| mesh_query.iter().next());
+                    if let Some((entity, cgar_data)) = target {
+                        let topology = MeshTopology::from_cgar(&cgar_data.0);
+                        let skeleton = extract_skeleton(&topology, levels);
+                        info!(
+                            "Extracted skeleton with {} nodes and {} edges",
+                            skeleton.nodes.len(),
+                            skeleton.edges.len()
+                        );
+                        analysis.result = Some((entity, skeleton));
+                    }
+                }
+                if ui.button("Clear").clicked() {
+                    analysis.result = None;
+                }
+            });
+
+            let Some((_, skeleton)) = &analysis.result else {
+                return;
+            };
+            ui.separator();
+            egui::Grid::new("skeleton_stats").show(ui, |ui| {
+                ui.label("Nodes");
+                ui.label(skeleton.nodes.len().to_string());
+                ui.end_row();
+                ui.label("Edges");
+                ui.label(skeleton.edges.len().to_string());
+                ui.end_row();
+                ui.label("Tips");
+                ui.label(skeleton.tips().to_string());
+                ui.end_row();
+                ui.label("Junctions");
+                ui.label(skeleton.junctions().to_string());
+                ui.end_row();
+                ui.label("Total length");
+                ui.label(format!("{:.6}", skeleton.length()));
+                ui.end_row();
+            });
+            #[cfg(feature = "native")]
+            if ui.button("Export OBJ lines").clicked() {
+                let path = PathBuf::from("skeleton.obj");
+                notices.write(
+                    match write_obj_lines(&path, &skeleton.nodes, &skeleton.edges) {
+                        Ok(()) => Notify::info(format!("Exported skeleton to {}", path.display())),
+                        Err(err) => {
+                            Notify::error(format!("Failed to export {}: {}", path.display(), err))
+                        }
+                    },
+                );
+            }
+        });
+}
+
+pub fn draw_skeleton(
+    mut gizmos: Gizmos<SkeletonGizmos>,
+    analysis: Res<SkeletonAnalysis>,
+    mesh_query: Query<&GlobalTransform>,
+) {
+    let Some((entity, skeleton)) = &analysis.result else {
+        return;
+    };
+    let Ok(mesh_global) = mesh_query.get(*entity) else {
+        return;
+    };
+    let world = |p: DVec3| mesh_global.transform_point(p.as_vec3());
+    let color = Color::srgb(1.0, 0.3, 0.8);
+    for &(a, b) in &skeleton.edges {
+        gizmos.line(world(skeleton.nodes[a]), world(skeleton.nodes[b]), color);
+    }
+}
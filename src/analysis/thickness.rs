@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::DVec3;
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::mesh::face_tree::FaceSurface;
+use crate::mesh::topology::MeshTopology;
+
+pub struct ThicknessReport {
+    // Per cgar face id; infinite where the inward ray never meets an opposite wall
+    pub thickness: Vec<f64>,
+    pub thin_faces: Vec<usize>,
+    pub thin_area: f64,
+    pub min_thickness: f64,
+}
+
+// Casts a ray inward from each face centroid along the reversed normal and
+// takes the distance to the first wall it meets. Faces closer than
+// `threshold` to their opposite wall are flagged as thin.
+pub fn wall_thickness(
+    topology: &MeshTopology,
+    (mesh, surface): (&CgarMesh<CgarF64, 3>, &FaceSurface),
+    threshold: f64,
+) -> ThicknessReport {
+    let mut report = ThicknessReport {
+        thickness: vec![f64::NAN; topology.triangles.len()],
+        thin_faces: Vec::new(),
+        thin_area: 0.0,
+        min_thickness: f64::INFINITY,
+    };
+    for (f, tri) in topology.live_faces() {
+        let [a, b, c] = topology.corners(tri);
+        let centroid = (a + b + c) / 3.0;
+        let inward = -topology.face_normal(f);
+        if inward == DVec3::ZERO {
+            continue;
+        }
+        let thickness = surface
+            .cast_ray(mesh, centroid, inward, None, |face| face != f)
+            .map_or(f64::INFINITY, |hit| hit.t);
+        report.thickness[f] = thickness;
+        report.min_thickness = report.min_thickness.min(thickness);
+        if thickness < threshold {
+            report.thin_faces.push(f);
+            report.thin_area += topology.face_area(f);
+        }
+    }
+    report
+}
@@ -0,0 +1,597 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::input::InputSystem;
+use bevy::pbr::wireframe::WireframePlugin;
+use bevy::picking::PickSet;
+use bevy::picking::prelude::*;
+use bevy::prelude::*;
+use bevy::sprite::Material2dPlugin;
+use bevy_inspector_egui::bevy_egui::{EguiGlobalSettings, EguiPlugin, EguiPrimaryContextPass};
+
+use crate::analysis::layers::{analysis_layers_panel, compose_analysis_layers};
+use crate::analysis::overlay::apply_face_overlays;
+use crate::analysis::ramps::{Colormaps, colormap_panel, legend_panel};
+use crate::analysis::systems::{
+    EdgeLengthAnalysis, FlatnessInspection, MassAnalysis, MeshSegmentation, OverhangAnalysis,
+    PrimitiveFit, SkeletonAnalysis, SkeletonGizmos, SlicePreview, SymmetryAnalysis,
+    ThicknessAnalysis, draw_edge_length_brush, draw_fitted_primitive, draw_flatness_plane,
+    draw_principal_axes, draw_skeleton, draw_slice_preview, draw_symmetry_plane, edge_length_panel,
+    flatness_panel, mass_properties_panel, overhang_panel, primitive_fit_panel,
+    record_flatness_picks, segmentation_panel, skeleton_panel, slice_preview_panel, symmetry_panel,
+    thickness_panel, update_flatness, update_slice_preview,
+};
+use crate::background::systems::{BackgroundOperation, poll_background_operation, progress_dialog};
+use crate::benchmark::systems::{RayBenchmark, benchmark_panel, run_ray_benchmark};
+use crate::blink::systems::{Blink, apply_blink, blink_panel, blink_shortcut};
+use crate::budget::memory::{check_memory_ceiling, measure_mesh_memory};
+use crate::budget::systems::{
+    MeshBudget, apply_display_decimation, budget_panel, check_mesh_budgets, limit_hover_picking,
+    swap_navigation_proxies,
+};
+use crate::camera::components::{OrbitSettings, SceneBounds};
+use crate::camera::systems::{
+    animate_orbit_focus, camera_controller, camera_panel, draw_orbit_pivot, fit_clipping_planes,
+    fit_orthographic_height, focus_on_double_click, pivot_on_selection, update_scene_bounds,
+};
+use crate::capture::systems::{MapExport, map_export_panel, run_map_export};
+use crate::command::batch::{BatchScript, run_batch_script};
+use crate::command::systems::{CommandLine, command_line_panel, run_commands};
+use crate::context_menu::systems::{
+    ContextAction, ContextMenu, apply_context_actions, context_menu_panel, open_context_menu,
+    unhide_meshes,
+};
+use crate::duplicate::systems::{
+    MeshDuplication, duplicate_meshes, duplicate_panel, duplicate_shortcut, sync_linked_meshes,
+};
+use crate::edit::systems::{
+    FeatureEdgeTool, VertexEdit, drag_vertex, draw_feature_edges, draw_vertex_edit,
+    feature_edges_panel, normals_panel, vertex_edit_panel,
+};
+use crate::edit::weld::{
+    VertexWeld, draw_vertex_weld, exit_weld, vertex_weld_panel, weld_on_pick, weld_undo_shortcut,
+};
+use crate::explode::systems::{ExplodedView, apply_explode, explode_panel, split_into_parts};
+use crate::extension::ViewerTools;
+use crate::flythrough::systems::{CameraPath, camera_path_panel, play_camera_path};
+use crate::import::archive::{ArchiveChooser, archive_chooser_panel};
+use crate::import::clipboard::{ClipboardGeometry, clipboard_panel};
+use crate::import::download::{
+    UrlDownloads, download_progress_panel, open_url_panel, run_downloads,
+};
+use crate::import::folder::{FolderImport, folder_import_panel, process_folder_import};
+use crate::import::systems::{
+    ImportJobs, ImportQueue, ImportSettings, import_panel, import_progress_panel,
+    install_file_sources, process_imports, queue_dropped_files,
+};
+use crate::input::systems::toggle_wireframe;
+use crate::inspector::systems::{AttributeInspector, attribute_inspector_panel};
+use crate::lighting::setup::{setup_camera_and_light, sync_camera_aspect};
+use crate::mesh::attributes::remap_mesh_attributes;
+use crate::mesh::collapse::CollapseOptions;
+use crate::mesh::constraints::{EditConstraints, edit_constraints_panel};
+use crate::mesh::edge::{
+    HighlightedEdges, MeshLongPressed, MeshPicked, PickSettings, PointerPresses, handle_mesh_click,
+};
+use crate::mesh::face_tree::refresh_face_tree_cache;
+use crate::mesh::highlight::{
+    HighlightAssets, HighlightStyle, HoveredEdge, highlight_style_panel, hover_edge_highlight,
+    update_edge_highlights,
+};
+use crate::mesh::setup::{load_cli_meshes, setup_cgar_mesh};
+use crate::morph::systems::{MorphView, morph_panel, update_morph};
+use crate::notifications::systems::{
+    NotificationLog, Notify, collect_notifications, notification_log_panel, notification_toasts,
+};
+use crate::outliner::export::{SceneExport, run_scene_export};
+use crate::outliner::systems::{Outliner, outliner_panel};
+use crate::perturb::systems::{Perturbation, perturbation_panel, run_perturbation};
+use crate::picklog::replay::{PickReplay, replay_picks};
+use crate::picklog::systems::{PickLog, log_picks, pick_log_panel};
+use crate::pointcloud::systems::{
+    PointCloudDisplay, PointSpriteMaterial, SurfaceReconstruction, point_cloud_panel,
+    poll_reconstruction, reconstruction_panel, render_point_clouds, setup_point_clouds,
+    setup_point_sprite_shader,
+};
+use crate::probe::systems::{
+    Probe, ScalarProbe, SpatialQueryTool, drag_query_shape, draw_probe, draw_spatial_query,
+    probe_panel, spatial_query_panel, toggle_probe, update_probe, update_scalar_probe,
+    update_spatial_query,
+};
+use crate::registration::systems::{
+    Registration, draw_registration_picks, record_registration_picks, registration_panel,
+};
+use crate::remote::systems::RemoteControlPlugin;
+use crate::repair::holes::{HoleNavigator, draw_hole_loops, hole_navigator_panel, navigate_holes};
+use crate::repair::systems::{
+    MergeByDistance, RepairWizard, draw_import_issues, draw_repair_preview, import_issues_panel,
+    merge_by_distance_panel, repair_panel,
+};
+use crate::sculpt::systems::{SculptBrush, draw_sculpt_brush, sculpt_panel, sculpt_stroke};
+use crate::selection::components::{
+    AreaSelect, FacePathPick, RegionGrowSeeded, RegionGrowSettings, SavedSelections, SelectionSet,
+};
+use crate::selection::systems::{
+    area_select, area_select_panel, draw_selection, exit_face_path, face_path_on_pick,
+    query_selection_panel, region_grow_panel, selection_sets_panel, sync_selection_batch,
+    toggle_region_grow, update_region_grow,
+};
+use crate::selftest::systems::{SelfTest, run_selftest};
+use crate::session::autosave::{
+    Autosave, autosave_panel, autosave_session, check_recovery, clear_autosave_on_exit,
+    restore_autosave,
+};
+use crate::session::presets::OperationPresets;
+use crate::session::systems::{load_session, save_session};
+use crate::silhouette::systems::{
+    SilhouetteSettings, draw_silhouettes, refresh_silhouette_cache, silhouette_panel,
+    toggle_silhouettes,
+};
+use crate::snapshot::systems::{
+    SnapshotCompare, draw_snapshot_diff, snapshot_panel, update_snapshot_diff,
+};
+use crate::stats::systems::{MeshFingerprints, drop_stale_fingerprints, mesh_stats_panel};
+use crate::stepper::systems::{
+    StepMode, collapse_queue_panel, draw_step_mode, run_step_mode, step_mode_panel,
+};
+use crate::stereo::systems::{
+    AnaglyphMaterial, StereoSettings, apply_stereo_mode, setup_stereo_shader, stereo_panel,
+    sync_stereo_eyes,
+};
+use crate::tools::systems::{
+    ActiveTool, Measurement, draw_custom_tool, draw_measurement, enter_vertex_move, exit_measure,
+    exit_vertex_move, measure_on_pick, run_custom_tools, status_bar, tool_shortcuts,
+    update_tool_cursor,
+};
+use crate::trajectory::systems::{
+    TrajectoryRecorder, draw_trajectory, play_trajectory, trajectory_panel,
+};
+use crate::utils::cli::CliOptions;
+use crate::wireframe::systems::{
+    EdgeLineMaterial, EdgeLines, edge_lines_panel, setup_edge_line_shader, sync_edge_lines,
+};
+use crate::workspace::systems::{
+    Workspaces, switch_workspace, tag_workspace_members, workspace_tabs,
+};
+
+// The whole viewer: window, built-in tools and panels, with options read from
+// the command line. Downstream crates register their own tools on it with
+// `ViewerToolsAppExt::add_viewer_tool` before running it.
+pub fn build_app() -> App {
+    let cli = CliOptions::from_args();
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            title: "CGAR Viewer".into(),
+            // Browser build draws into the page's canvas (see index.html)
+            #[cfg(target_arch = "wasm32")]
+            canvas: Some("#cgar-viewer".into()),
+            #[cfg(target_arch = "wasm32")]
+            fit_canvas_to_parent: true,
+            ..default()
+        }),
+        ..default()
+    }))
+    .insert_resource(RayBenchmark::from_options(&cli))
+    .insert_resource(SelfTest::from_options(&cli))
+    .insert_resource(BatchScript::from_options(&cli))
+    .insert_resource(ImportSettings::from_options(&cli))
+    .insert_resource(UrlDownloads::from_options(&cli))
+    .add_plugins(RemoteControlPlugin::from_options(&cli))
+    .insert_resource(cli)
+    .init_resource::<HighlightedEdges>()
+    .init_resource::<PointerPresses>()
+    .init_resource::<PickSettings>()
+    .init_resource::<CollapseOptions>()
+    .init_resource::<EditConstraints>()
+    .init_resource::<ContextMenu>()
+    .init_resource::<Measurement>()
+    .init_resource::<SculptBrush>()
+    .init_resource::<TrajectoryRecorder>()
+    .init_resource::<PickLog>()
+    .init_resource::<PickReplay>()
+    .init_resource::<EdgeLines>()
+    .init_resource::<StepMode>()
+    .init_resource::<BackgroundOperation>()
+    .init_resource::<Workspaces>()
+    .init_resource::<ViewerTools>()
+    .init_resource::<Perturbation>()
+    .init_resource::<SnapshotCompare>()
+    .init_resource::<Blink>()
+    .init_resource::<MorphView>()
+    .init_resource::<MeshFingerprints>()
+    .init_resource::<EdgeLengthAnalysis>()
+    .init_resource::<FlatnessInspection>()
+    .init_resource::<SilhouetteSettings>()
+    .init_resource::<ExplodedView>()
+    .init_resource::<SkeletonAnalysis>()
+    .init_resource::<MapExport>()
+    .init_resource::<CameraPath>()
+    .init_resource::<CommandLine>()
+    .init_resource::<SceneExport>()
+    .init_resource::<Autosave>()
+    .init_resource::<MeshBudget>()
+    .init_resource::<FolderImport>()
+    .init_resource::<Outliner>()
+    .init_resource::<MeshDuplication>()
+    .init_resource::<AreaSelect>()
+    .init_resource::<FacePathPick>()
+    .init_resource::<HighlightStyle>()
+    .init_resource::<HighlightAssets>()
+    .init_resource::<HoveredEdge>()
+    .init_resource::<Colormaps>()
+    .insert_gizmo_config(
+        SkeletonGizmos,
+        GizmoConfig {
+            depth_bias: -1.0,
+            ..default()
+        },
+    )
+    .init_resource::<SelectionSet>()
+    .init_resource::<SavedSelections>()
+    .init_resource::<OperationPresets>()
+    .init_resource::<RegionGrowSettings>()
+    .init_resource::<PrimitiveFit>()
+    .init_resource::<MeshSegmentation>()
+    .init_resource::<SymmetryAnalysis>()
+    .init_resource::<OverhangAnalysis>()
+    .init_resource::<ThicknessAnalysis>()
+    .init_resource::<SlicePreview>()
+    .init_resource::<MassAnalysis>()
+    .init_resource::<RepairWizard>()
+    .init_resource::<MergeByDistance>()
+    .init_resource::<VertexEdit>()
+    .init_resource::<VertexWeld>()
+    .init_resource::<HoleNavigator>()
+    .init_resource::<FeatureEdgeTool>()
+    .init_resource::<AttributeInspector>()
+    .init_resource::<OrbitSettings>()
+    .init_resource::<SceneBounds>()
+    .init_resource::<StereoSettings>()
+    .init_resource::<ImportQueue>()
+    .init_resource::<ImportJobs>()
+    .init_resource::<ArchiveChooser>()
+    .init_resource::<ClipboardGeometry>()
+    .init_resource::<NotificationLog>()
+    .init_resource::<Registration>()
+    .init_resource::<Probe>()
+    .init_resource::<ScalarProbe>()
+    .init_resource::<SpatialQueryTool>()
+    .init_resource::<PointCloudDisplay>()
+    .init_resource::<SurfaceReconstruction>()
+    .init_state::<ActiveTool>()
+    .add_event::<MeshPicked>()
+    .add_event::<MeshLongPressed>()
+    .add_event::<ContextAction>()
+    .add_event::<Notify>()
+    .add_event::<RegionGrowSeeded>()
+    .add_plugins((
+        MeshPickingPlugin, // built-in mesh picking
+        WireframePlugin::default(),
+        EguiPlugin::default(),
+        Material2dPlugin::<AnaglyphMaterial>::default(),
+        MaterialPlugin::<EdgeLineMaterial> {
+            prepass_enabled: false,
+            shadows_enabled: false,
+            ..default()
+        },
+        MaterialPlugin::<PointSpriteMaterial> {
+            prepass_enabled: false,
+            shadows_enabled: false,
+            ..default()
+        },
+    ))
+    // Keys typed into text fields (the command line) must not trigger shortcuts
+    .insert_resource(EguiGlobalSettings {
+        enable_absorb_bevy_input_system: true,
+        ..default()
+    })
+    .add_systems(
+        Startup,
+        (
+            setup_camera_and_light,
+            setup_cgar_mesh,
+            load_cli_meshes.after(setup_cgar_mesh),
+            setup_point_clouds,
+            load_session,
+            check_recovery,
+            setup_stereo_shader,
+            setup_edge_line_shader,
+            setup_point_sprite_shader,
+        ),
+    )
+    .add_systems(
+        Update,
+        (
+            toggle_wireframe,
+            camera_controller,
+            handle_mesh_click.after(refresh_face_tree_cache),
+            draw_selection,
+            save_session,
+            toggle_region_grow,
+            update_region_grow.after(handle_mesh_click),
+            draw_fitted_primitive,
+            apply_face_overlays,
+            draw_symmetry_plane,
+            record_registration_picks.after(handle_mesh_click),
+            draw_registration_picks,
+            refresh_face_tree_cache,
+            remap_mesh_attributes,
+            toggle_probe,
+            update_probe.after(refresh_face_tree_cache),
+            draw_probe.after(update_probe),
+            update_scalar_probe.after(refresh_face_tree_cache),
+        ),
+    )
+    .add_systems(
+        Update,
+        (
+            drag_query_shape,
+            update_spatial_query
+                .after(drag_query_shape)
+                .after(refresh_face_tree_cache),
+            draw_spatial_query.after(update_spatial_query),
+            run_ray_benchmark,
+            render_point_clouds,
+            poll_reconstruction,
+            update_slice_preview,
+            draw_slice_preview.after(update_slice_preview),
+            draw_principal_axes,
+            draw_repair_preview,
+            drag_vertex.before(camera_controller),
+            draw_vertex_edit.after(drag_vertex),
+            draw_feature_edges,
+            focus_on_double_click.after(handle_mesh_click),
+            pivot_on_selection.after(handle_mesh_click),
+            animate_orbit_focus
+                .after(focus_on_double_click)
+                .after(pivot_on_selection)
+                .after(camera_controller),
+            draw_orbit_pivot.after(animate_orbit_focus),
+            update_scene_bounds.before(camera_controller),
+            fit_clipping_planes
+                .after(update_scene_bounds)
+                .after(animate_orbit_focus),
+            fit_orthographic_height.after(update_scene_bounds),
+        ),
+    )
+    .add_systems(
+        Update,
+        (
+            apply_stereo_mode,
+            sync_stereo_eyes
+                .after(apply_stereo_mode)
+                .after(fit_clipping_planes),
+            queue_dropped_files,
+            process_imports.after(queue_dropped_files),
+            collect_notifications.after(process_imports),
+            draw_import_issues,
+            open_context_menu.after(handle_mesh_click),
+            apply_context_actions,
+            unhide_meshes,
+            draw_measurement,
+            tool_shortcuts,
+            update_tool_cursor.after(tool_shortcuts),
+            measure_on_pick
+                .after(handle_mesh_click)
+                .run_if(in_state(ActiveTool::Measure)),
+            sculpt_stroke.before(camera_controller),
+            run_perturbation,
+            run_selftest,
+            update_snapshot_diff,
+            draw_snapshot_diff,
+            draw_sculpt_brush.after(sculpt_stroke),
+        ),
+    )
+    .add_systems(
+        Update,
+        (
+            blink_shortcut,
+            apply_blink.after(blink_shortcut),
+            draw_edge_length_brush,
+            record_flatness_picks.after(handle_mesh_click),
+            update_flatness.after(record_flatness_picks),
+            draw_flatness_plane.after(update_flatness),
+            toggle_silhouettes,
+            refresh_silhouette_cache.after(toggle_silhouettes),
+            draw_silhouettes.after(refresh_silhouette_cache),
+            split_into_parts,
+            apply_explode.after(split_into_parts),
+            draw_skeleton,
+            run_map_export,
+            play_camera_path.after(camera_controller),
+            run_batch_script.before(run_commands),
+            run_commands,
+            autosave_session,
+            restore_autosave,
+        ),
+    )
+    .add_systems(
+        EguiPrimaryContextPass,
+        (
+            selection_sets_panel,
+            query_selection_panel,
+            region_grow_panel,
+            primitive_fit_panel,
+            segmentation_panel,
+            symmetry_panel,
+            overhang_panel,
+            thickness_panel,
+            slice_preview_panel,
+            mass_properties_panel,
+            repair_panel,
+            merge_by_distance_panel,
+            vertex_edit_panel,
+            feature_edges_panel,
+            registration_panel,
+            probe_panel,
+            spatial_query_panel,
+            benchmark_panel,
+            point_cloud_panel,
+            reconstruction_panel,
+        ),
+    )
+    .add_systems(
+        EguiPrimaryContextPass,
+        (
+            attribute_inspector_panel,
+            camera_panel,
+            stereo_panel,
+            import_panel,
+            notification_log_panel,
+            notification_toasts,
+            import_issues_panel,
+            normals_panel,
+            context_menu_panel,
+            sculpt_panel,
+            perturbation_panel,
+            snapshot_panel,
+            blink_panel,
+            edge_length_panel,
+            flatness_panel,
+            silhouette_panel,
+            explode_panel,
+            skeleton_panel,
+            map_export_panel,
+            status_bar,
+        ),
+    )
+    .add_systems(
+        EguiPrimaryContextPass,
+        (
+            camera_path_panel,
+            command_line_panel,
+            autosave_panel,
+            budget_panel,
+            folder_import_panel,
+            outliner_panel,
+            duplicate_panel,
+            area_select_panel,
+            highlight_style_panel,
+            analysis_layers_panel,
+            colormap_panel,
+            legend_panel,
+            trajectory_panel,
+            step_mode_panel,
+            collapse_queue_panel,
+            edit_constraints_panel,
+            progress_dialog,
+            workspace_tabs,
+            pick_log_panel,
+            edge_lines_panel,
+        ),
+    )
+    .add_systems(
+        EguiPrimaryContextPass,
+        (
+            import_progress_panel,
+            archive_chooser_panel,
+            open_url_panel,
+            download_progress_panel,
+            clipboard_panel,
+            vertex_weld_panel,
+            hole_navigator_panel,
+            morph_panel,
+            mesh_stats_panel,
+        ),
+    )
+    .add_systems(
+        Update,
+        (
+            check_mesh_budgets,
+            apply_display_decimation
+                .after(check_mesh_budgets)
+                .after(apply_face_overlays),
+            sync_selection_batch,
+            process_folder_import.after(queue_dropped_files),
+            duplicate_shortcut,
+            duplicate_meshes.after(duplicate_shortcut),
+            sync_linked_meshes,
+            area_select.before(camera_controller),
+            hover_edge_highlight,
+            update_edge_highlights
+                .after(handle_mesh_click)
+                .after(hover_edge_highlight),
+            compose_analysis_layers.before(apply_face_overlays),
+            play_trajectory,
+            draw_trajectory.after(play_trajectory),
+            run_step_mode,
+            draw_step_mode.after(run_step_mode),
+            poll_background_operation,
+            tag_workspace_members,
+            switch_workspace.after(tag_workspace_members),
+        ),
+    )
+    .add_systems(
+        PreUpdate,
+        limit_hover_picking
+            .after(InputSystem)
+            .before(PickSet::Backend),
+    )
+    .add_systems(Last, clear_autosave_on_exit)
+    .add_systems(OnEnter(ActiveTool::VertexMove), enter_vertex_move)
+    .add_systems(OnExit(ActiveTool::VertexMove), exit_vertex_move)
+    .add_systems(OnExit(ActiveTool::Measure), exit_measure)
+    .add_systems(OnExit(ActiveTool::Weld), exit_weld)
+    .add_systems(OnExit(ActiveTool::FacePath), exit_face_path)
+    .add_systems(
+        Update,
+        (
+            run_custom_tools.after(handle_mesh_click),
+            draw_custom_tool.after(run_custom_tools),
+            log_picks.after(handle_mesh_click),
+            replay_picks,
+            sync_edge_lines.after(apply_display_decimation),
+            swap_navigation_proxies
+                .after(camera_controller)
+                .after(apply_display_decimation),
+            measure_mesh_memory
+                .after(apply_display_decimation)
+                .after(swap_navigation_proxies),
+            check_memory_ceiling.after(measure_mesh_memory),
+            run_scene_export.after(run_commands),
+            run_downloads,
+            weld_on_pick
+                .after(handle_mesh_click)
+                .run_if(in_state(ActiveTool::Weld)),
+            weld_undo_shortcut.run_if(in_state(ActiveTool::Weld)),
+            draw_vertex_weld,
+            navigate_holes,
+            draw_hole_loops.after(navigate_holes),
+            face_path_on_pick
+                .after(handle_mesh_click)
+                .run_if(in_state(ActiveTool::FacePath)),
+            update_morph,
+            drop_stale_fingerprints.before(run_commands),
+        ),
+    )
+    .add_systems(
+        PostUpdate,
+        (
+            sync_camera_aspect, // updates aspect from viewport/window
+                                // handle_mesh_click,  // computes ray using correct projection + transforms
+        )
+            .chain()
+            .after(TransformSystem::TransformPropagate),
+    );
+    app
+}
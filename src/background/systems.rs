@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        change_detection::DetectChanges,
+        component::Tick,
+        entity::Entity,
+        event::EventWriter,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    log::info,
+    platform::time::Instant,
+    render::mesh::{Mesh, Mesh3d},
+    tasks::{Task, block_on, futures_lite::future},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::CgarMeshData;
+use crate::mesh::conversion::build_cgar_mesh;
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::notifications::systems::Notify;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+
+// Shared between the progress dialog and a background mesh operation, which
+// updates it after every step and checks `cancel` in between
+pub struct OperationProgress {
+    pub steps: AtomicUsize,
+    pub faces: AtomicUsize,
+    // Work done out of the expected total, in whatever unit the operation counts
+    pub done: AtomicUsize,
+    pub total: AtomicUsize,
+    // Bits of the latest error metric; NaN until one is reported
+    error: AtomicU64,
+    pub cancel: AtomicBool,
+}
+
+impl Default for OperationProgress {
+    fn default() -> Self {
+        Self {
+            steps: AtomicUsize::new(0),
+            faces: AtomicUsize::new(0),
+            done: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+            error: AtomicU64::new(f64::NAN.to_bits()),
+            cancel: AtomicBool::new(false),
+        }
+    }
+}
+
+impl OperationProgress {
+    pub fn fraction(&self) -> f32 {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            0.0
+        } else {
+            (self.done.load(Ordering::Relaxed) as f32 / total as f32).min(1.0)
+        }
+    }
+
+    pub fn error(&self) -> Option<f64> {
+        Some(f64::from_bits(self.error.load(Ordering::Relaxed))).filter(|e| !e.is_nan())
+    }
+
+    pub fn set_error(&self, error: f64) {
+        self.error.store(error.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+// How a background operation ended
+pub enum JobOutcome {
+    // The finished mesh and a summary for the notification
+    Done(TriangleSoup, String),
+    Cancelled,
+    Failed(String),
+}
+
+pub struct MeshJob {
+    pub mesh: Entity,
+    pub label: String,
+    pub progress: Arc<OperationProgress>,
+    task: Task<JobOutcome>,
+    started: Instant,
+    // When the mesh last changed before the job copied it
+    changed_at: Tick,
+}
+
+// At most one long-running operation at a time. It works on a copy of the
+// mesh, which only replaces the original once it finishes; cancelling or a
+// failure leaves the mesh as it was before the operation, and so does any
+// edit made to the mesh while the job ran.
+#[derive(Resource, Default)]
+pub struct BackgroundOperation {
+    pub job: Option<MeshJob>,
+}
+
+impl BackgroundOperation {
+    pub fn busy(&self) -> bool {
+        self.job.is_some()
+    }
+
+    // `changed_at` is the mesh's `last_changed` tick when the job copied it
+    pub fn spawn(
+        &mut self,
+        mesh: Entity,
+        changed_at: Tick,
+        label: impl Into<String>,
+        progress: Arc<OperationProgress>,
+        task: Task<JobOutcome>,
+    ) {
+        self.job = Some(MeshJob {
+            mesh,
+            label: label.into(),
+            progress,
+            task,
+            started: Instant::now(),
+            changed_at,
+        });
+    }
+}
+
+// Swaps the finished mesh in once the background task completes
+pub fn poll_background_operation(
+    mut commands: Commands,
+    mut operation: ResMut<BackgroundOperation>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut selection: ResMut<SelectionSet>,
+    mut notices: EventWriter<Notify>,
+    mut mesh_query: Query<(
+        &Mesh3d,
+        &mut CgarMeshData,
+        Option<&FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    let Some(job) = operation.job.as_mut() else {
+        return;
+    };
+    let Some(outcome) = block_on(future::poll_once(&mut job.task)) else {
+        return;
+    };
+    let job = operation.job.take().expect("job checked above");
+    let elapsed = job.started.elapsed().as_secs_f64();
+
+    let (soup, summary) = match outcome {
+        JobOutcome::Done(soup, summary) => (soup, summary),
+        JobOutcome::Cancelled => {
+            notices.write(Notify::info(format!(
+                "{} cancelled; the mesh was left as it was",
+                job.label
+            )));
+            return;
+        }
+        JobOutcome::Failed(err) => {
+            notices.write(Notify::warning(format!(
+                "{} failed, the mesh was left as it was: {}",
+                job.label, err
+            )));
+            return;
+        }
+    };
+    // The mesh was deleted while the job ran
+    let Ok((mesh_handle, mut cgar_data, features, normals, settings)) =
+        mesh_query.get_mut(job.mesh)
+    else {
+        return;
+    };
+    // The job worked on a copy; applying it now would throw away whatever
+    // was done to the mesh since
+    if cgar_data.last_changed() != job.changed_at {
+        notices.write(Notify::warning(format!(
+            "{} finished, but the mesh was edited while it ran; its result was discarded",
+            job.label
+        )));
+        return;
+    }
+    let message = format!("{}: {} in {:.1} s", job.label, summary, elapsed);
+    info!("{}", message);
+    notices.write(Notify::info(message));
+    cgar_data.0 = build_cgar_mesh(&soup.positions, soup.triangles.iter().copied());
+    meshes.insert(
+        &mesh_handle.0,
+        render_mesh(&cgar_data.0, None, features, normals, settings),
+    );
+    commands.entity(job.mesh).remove::<FaceColorOverlay>();
+    // Face ids no longer mean the same thing
+    if selection.mesh == Some(job.mesh) {
+        selection.clear();
+    }
+}
+
+pub fn progress_dialog(mut contexts: EguiContexts, operation: Res<BackgroundOperation>) {
+    let Some(job) = &operation.job else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Working")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            let progress = &job.progress;
+            ui.strong(&job.label);
+            ui.add(egui::ProgressBar::new(progress.fraction()).show_percentage());
+            egui::Grid::new("operation_progress")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Steps");
+                    ui.label(progress.steps.load(Ordering::Relaxed).to_string());
+                    ui.end_row();
+                    ui.label("Faces");
+                    ui.label(progress.faces.load(Ordering::Relaxed).to_string());
+                    ui.end_row();
+                    if let Some(error) = progress.error() {
+                        ui.label("Current error");
+                        ui.label(format!("{:.6}", error));
+                        ui.end_row();
+                    }
+                    ui.label("Elapsed");
+                    ui.label(format!("{:.1} s", job.started.elapsed().as_secs_f64()));
+                    ui.end_row();
+                });
+            if progress.cancelled() {
+                ui.label("Cancelling after the current step...");
+            } else if ui.button("Cancel").clicked() {
+                progress.cancel.store(true, Ordering::Relaxed);
+            }
+            // Keep repainting so the counters advance without input events
+            ui.ctx().request_repaint();
+        });
+}
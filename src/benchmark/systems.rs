@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    app::AppExit,
+    ecs::{
+        event::EventWriter,
+        name::Name,
+        query::With,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    log::{info, warn},
+    math::Vec2,
+    platform::time::Instant,
+    render::camera::Camera,
+    transform::components::GlobalTransform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use cgar::geometry::spatial_element::SpatialElement;
+use cgar::geometry::{Point3, Vector3};
+use cgar::mesh::basic_types::IntersectionResult;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::utils::cli::CliOptions;
+
+#[derive(Debug, Clone)]
+pub struct BenchmarkRow {
+    pub mesh: String,
+    pub faces: usize,
+    pub build_ms: f64,
+    pub rays: usize,
+    pub hits: usize,
+    pub query_ms: f64,
+}
+
+impl BenchmarkRow {
+    pub fn rays_per_second(&self) -> f64 {
+        if self.query_ms > 0.0 {
+            self.rays as f64 / (self.query_ms / 1000.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+// Ray-cast throughput harness: a grid of camera rays against every mesh's face tree
+#[derive(Resource)]
+pub struct RayBenchmark {
+    // Rays per side of the screen-space grid
+    pub grid: usize,
+    pub requested: bool,
+    pub exit_when_done: bool,
+    pub rows: Vec<BenchmarkRow>,
+}
+
+impl RayBenchmark {
+    pub fn from_options(options: &CliOptions) -> Self {
+        Self {
+            grid: options.bench_grid.unwrap_or(128),
+            requested: options.bench,
+            exit_when_done: options.bench,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "{:<24} {:>9} {:>10} {:>9} {:>9} {:>10} {:>12}\n",
+            "mesh", "faces", "build ms", "rays", "hits", "query ms", "rays/s"
+        );
+        for row in &self.rows {
+            out += &format!(
+                "{:<24} {:>9} {:>10.3} {:>9} {:>9} {:>10.3} {:>12.0}\n",
+                row.mesh,
+                row.faces,
+                row.build_ms,
+                row.rays,
+                row.hits,
+                row.query_ms,
+                row.rays_per_second()
+            );
+        }
+        out
+    }
+}
+
+pub fn run_ray_benchmark(
+    mut benchmark: ResMut<RayBenchmark>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData, Option<&Name>)>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if !benchmark.requested {
+        return;
+    }
+    let Ok((camera, camera_global)) = camera_query.single() else {
+        return;
+    };
+    // The viewport size is only known once the render target has been set up
+    let Some(viewport) = camera.logical_viewport_size() else {
+        return;
+    };
+    if mesh_query.is_empty() {
+        return;
+    }
+    benchmark.requested = false;
+
+    let grid = benchmark.grid.max(1);
+    let rays: Vec<_> = (0..grid * grid)
+        .filter_map(|i| {
+            let cell = Vec2::new((i % grid) as f32 + 0.5, (i / grid) as f32 + 0.5);
+            camera
+                .viewport_to_world(camera_global, cell / grid as f32 * viewport)
+                .ok()
+        })
+        .collect();
+
+    let tolerance = CgarF64::from(1e-9);
+    let mut rows = Vec::new();
+    for (mesh_global, cgar_data, name) in &mesh_query {
+        let cgar_mesh = &cgar_data.0;
+        let inv_affine = mesh_global.affine().inverse();
+        let local_rays: Vec<(Point3<CgarF64>, Vector3<CgarF64>)> = rays
+            .iter()
+            .map(|ray| {
+                let o = inv_affine.transform_point3(ray.origin);
+                let d = inv_affine
+                    .transform_vector3(ray.direction.as_vec3())
+                    .normalize();
+                (
+                    Point3::from_vals([o.x as f64, o.y as f64, o.z as f64]),
+                    Vector3::from_vals([d.x as f64, d.y as f64, d.z as f64]),
+                )
+            })
+            .collect();
+
+        let start = Instant::now();
+        let tree = cgar_mesh.build_face_tree();
+        let build_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let start = Instant::now();
+        let hits = local_rays
+            .iter()
+            .filter(|(origin, direction)| {
+                matches!(
+                    cgar_mesh.cast_ray(origin, direction, &tree, &Some(tolerance.clone())),
+                    IntersectionResult::Hit(..)
+                )
+            })
+            .count();
+        let query_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        rows.push(BenchmarkRow {
+            mesh: name.map_or_else(|| "unnamed".to_string(), |n| n.to_string()),
+            faces: cgar_mesh.faces.iter().filter(|f| !f.removed).count(),
+            build_ms,
+            rays: local_rays.len(),
+            hits,
+            query_ms,
+        });
+    }
+    benchmark.rows = rows;
+
+    let summary = benchmark.summary();
+    info!("Ray-cast benchmark ({0}x{0} grid):\n{1}", grid, summary);
+    if benchmark.exit_when_done {
+        println!("{}", summary);
+        exit.write(AppExit::Success);
+    }
+}
+
+pub fn benchmark_panel(mut contexts: EguiContexts, mut benchmark: ResMut<RayBenchmark>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Ray Benchmark")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut grid = benchmark.grid;
+            if ui
+                .add(egui::Slider::new(&mut grid, 8..=1024).text("Grid (rays per side)"))
+                .changed()
+            {
+                benchmark.grid = grid;
+            }
+            if ui.button("Run").clicked() {
+                if benchmark.requested {
+                    warn!("Benchmark already pending");
+                }
+                benchmark.requested = true;
+            }
+
+            if benchmark.rows.is_empty() {
+                return;
+            }
+            ui.separator();
+            egui::Grid::new("benchmark_rows")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in [
+                        "Mesh", "Faces", "Build ms", "Rays", "Hits", "Query ms", "Rays/s",
+                    ] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    for row in &benchmark.rows {
+                        ui.label(&row.mesh);
+                        ui.label(row.faces.to_string());
+                        ui.label(format!("{:.3}", row.build_ms));
+                        ui.label(row.rays.to_string());
+                        ui.label(row.hits.to_string());
+                        ui.label(format!("{:.3}", row.query_ms));
+                        ui.label(format!("{:.0}", row.rays_per_second()));
+                        ui.end_row();
+                    }
+                });
+            if ui.button("Copy summary").clicked() {
+                ui.ctx().copy_text(benchmark.summary());
+            }
+        });
+}
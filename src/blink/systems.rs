@@ -0,0 +1,284 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        change_detection::DetectChangesMut,
+        entity::Entity,
+        name::Name,
+        query::With,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    input::{ButtonInput, keyboard::KeyCode},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    render::{
+        mesh::{Mesh, Mesh3d},
+        view::Visibility,
+    },
+    time::Time,
+    transform::components::Transform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::CgarMeshData;
+use crate::mesh::conversion::{build_cgar_mesh, cgar_to_bevy_mesh};
+use crate::mesh::normals::NormalWeighting;
+use crate::repair::ops::TriangleSoup;
+use crate::snapshot::systems::SnapshotCompare;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlinkSource {
+    Mesh(Entity),
+    // The pinned snapshot, drawn in place of the mesh it was taken from
+    Snapshot,
+}
+
+// Swaps which of two meshes is drawn, leaving camera and lighting alone, so
+// small differences stand out. X swaps by hand; `auto` swaps on a timer.
+#[derive(Resource)]
+pub struct Blink {
+    pub a: Option<Entity>,
+    pub b: Option<BlinkSource>,
+    // Entities being swapped while running; B may be a stand-in for a snapshot
+    pub running: Option<(Entity, Entity)>,
+    pub ghost: Option<Entity>,
+    pub showing_b: bool,
+    pub auto: bool,
+    pub interval_secs: f32,
+    pub elapsed: f32,
+}
+
+impl Default for Blink {
+    fn default() -> Self {
+        Self {
+            a: None,
+            b: None,
+            running: None,
+            ghost: None,
+            showing_b: false,
+            auto: false,
+            interval_secs: 0.5,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl Blink {
+    fn stop(&mut self, commands: &mut Commands, visibilities: &mut Query<&mut Visibility>) {
+        if let Some((a, b)) = self.running.take() {
+            for entity in [a, b] {
+                if let Ok(mut visibility) = visibilities.get_mut(entity) {
+                    *visibility = Visibility::Inherited;
+                }
+            }
+        }
+        if let Some(ghost) = self.ghost.take() {
+            commands.entity(ghost).despawn();
+        }
+        self.showing_b = false;
+        self.elapsed = 0.0;
+    }
+}
+
+pub fn blink_shortcut(kb: Res<ButtonInput<KeyCode>>, mut blink: ResMut<Blink>) {
+    if blink.running.is_some() && kb.just_pressed(KeyCode::KeyX) {
+        blink.showing_b = !blink.showing_b;
+        blink.elapsed = 0.0;
+    }
+}
+
+pub fn apply_blink(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut blink: ResMut<Blink>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    let Some((a, b)) = blink.running else {
+        return;
+    };
+    if !visibilities.contains(a) || !visibilities.contains(b) {
+        blink.stop(&mut commands, &mut visibilities);
+        return;
+    }
+    if blink.auto {
+        blink.elapsed += time.delta_secs();
+        if blink.elapsed >= blink.interval_secs {
+            blink.elapsed = 0.0;
+            blink.showing_b = !blink.showing_b;
+        }
+    }
+    let (shown, hidden) = if blink.showing_b { (b, a) } else { (a, b) };
+    for (entity, wanted) in [(shown, Visibility::Inherited), (hidden, Visibility::Hidden)] {
+        if let Ok(mut visibility) = visibilities.get_mut(entity) {
+            visibility.set_if_neq(wanted);
+        }
+    }
+}
+
+pub fn blink_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut blink: ResMut<Blink>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    compare: Res<SnapshotCompare>,
+    mesh_query: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&Name>,
+            Option<&MeshMaterial3d<StandardMaterial>>,
+        ),
+        With<CgarMeshData>,
+    >,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let label = |entity: Entity| {
+        let name = mesh_query
+            .get(entity)
+            .ok()
+            .and_then(|(_, _, name, _)| name)
+            .map_or_else(|| "unnamed".to_string(), |name| name.to_string());
+        format!("Mesh: {} ({})", name, entity)
+    };
+
+    egui::Window::new("A/B Blink")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let running = blink.running.is_some();
+            ui.add_enabled_ui(!running, |ui| {
+                let mut a = blink.a.filter(|e| mesh_query.contains(*e));
+                egui::ComboBox::from_label("A")
+                    .selected_text(a.map_or_else(|| "None".to_string(), label))
+                    .show_ui(ui, |ui| {
+                        for (entity, ..) in &mesh_query {
+                            ui.selectable_value(&mut a, Some(entity), label(entity));
+                        }
+                    });
+                if a != blink.a {
+                    blink.a = a;
+                }
+
+                let mut b = blink.b;
+                let b_text = match b {
+                    Some(BlinkSource::Mesh(entity)) => label(entity),
+                    Some(BlinkSource::Snapshot) => "Pinned snapshot".to_string(),
+                    None => "None".to_string(),
+                };
+                egui::ComboBox::from_label("B")
+                    .selected_text(b_text)
+                    .show_ui(ui, |ui| {
+                        for (entity, ..) in &mesh_query {
+                            ui.selectable_value(
+                                &mut b,
+                                Some(BlinkSource::Mesh(entity)),
+                                label(entity),
+                            );
+                        }
+                        if compare.pinned.is_some() {
+                            ui.selectable_value(
+                                &mut b,
+                                Some(BlinkSource::Snapshot),
+                                "Pinned snapshot",
+                            );
+                        }
+                    });
+                if b != blink.b {
+                    blink.b = b;
+                }
+            });
+
+            let mut auto = blink.auto;
+            ui.checkbox(&mut auto, "Swap automatically");
+            if auto != blink.auto {
+                blink.auto = auto;
+            }
+            let mut interval = blink.interval_secs;
+            ui.add(egui::Slider::new(&mut interval, 0.1..=2.0).text("Interval (s)"));
+            if interval != blink.interval_secs {
+                blink.interval_secs = interval;
+            }
+
+            if running {
+                ui.label(format!(
+                    "Showing {}; press X to swap",
+                    if blink.showing_b { "B" } else { "A" }
+                ));
+                if ui.button("Stop").clicked() {
+                    blink.stop(&mut commands, &mut visibilities);
+                }
+                return;
+            }
+
+            let a = blink.a.filter(|e| mesh_query.contains(*e));
+            let can_start = match (a, blink.b) {
+                (Some(a), Some(BlinkSource::Mesh(b))) => a != b && mesh_query.contains(b),
+                (Some(_), Some(BlinkSource::Snapshot)) => compare.pinned.is_some(),
+                _ => false,
+            };
+            let start = ui
+                .add_enabled(can_start, egui::Button::new("Start"))
+                .clicked();
+            let Some(a) = a.filter(|_| start) else {
+                return;
+            };
+            let b = match blink.b {
+                Some(BlinkSource::Mesh(b)) => Some(b),
+                // A snapshot is stood in for by a copy drawn with A's
+                // transform and material
+                Some(BlinkSource::Snapshot) => {
+                    compare.pinned.as_ref().zip(mesh_query.get(a).ok()).map(
+                        |(snapshot, (_, transform, _, material))| {
+                            let soup = TriangleSoup::from_topology(&snapshot.topology);
+                            let cgar_mesh =
+                                build_cgar_mesh(&soup.positions, soup.triangles.iter().copied());
+                            let mut ghost = commands.spawn((
+                                Name::new(format!("{} (snapshot)", snapshot.name)),
+                                Mesh3d(meshes.add(cgar_to_bevy_mesh(
+                                    &cgar_mesh,
+                                    NormalWeighting::default(),
+                                ))),
+                                *transform,
+                                Visibility::Hidden,
+                            ));
+                            if let Some(material) = material {
+                                ghost.insert(material.clone());
+                            }
+                            let ghost = ghost.id();
+                            blink.ghost = Some(ghost);
+                            ghost
+                        },
+                    )
+                }
+                None => None,
+            };
+            if let Some(b) = b {
+                blink.running = Some((a, b));
+                blink.showing_b = false;
+                blink.elapsed = 0.0;
+            }
+        });
+}
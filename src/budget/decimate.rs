@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{BTreeSet, HashMap};
+
+use bevy::asset::RenderAssetUsages;
+use bevy::math::{DVec3, IVec3};
+use bevy::render::mesh::{Indices, Mesh, PrimitiveTopology};
+
+use crate::repair::ops::TriangleSoup;
+
+// Cell size refinements tried when searching for the target face count
+const CLUSTER_SEARCH_STEPS: usize = 12;
+
+// Merges every vertex inside a grid cell into the cell's average position and
+// drops triangles that collapse. Cheap and robust on any input, but the result
+// is only meant to be looked at: it can be non-manifold.
+fn cluster(soup: &TriangleSoup, origin: DVec3, cell: f64) -> TriangleSoup {
+    let mut cells: HashMap<IVec3, usize> = HashMap::new();
+    let mut sums: Vec<(DVec3, usize)> = Vec::new();
+    let remap: Vec<usize> = soup
+        .positions
+        .iter()
+        .map(|&p| {
+            let key = ((p - origin) / cell).floor().as_ivec3();
+            let id = *cells.entry(key).or_insert_with(|| {
+                sums.push((DVec3::ZERO, 0));
+                sums.len() - 1
+            });
+            sums[id].0 += p;
+            sums[id].1 += 1;
+            id
+        })
+        .collect();
+
+    let mut seen: BTreeSet<[usize; 3]> = BTreeSet::new();
+    let triangles = soup
+        .triangles
+        .iter()
+        .map(|tri| tri.map(|v| remap[v]))
+        .filter(|&[a, b, c]| a != b && b != c && c != a)
+        .filter(|tri| {
+            let mut key = *tri;
+            key.sort_unstable();
+            seen.insert(key)
+        })
+        .collect();
+    let positions = sums
+        .into_iter()
+        .map(|(sum, count)| sum / count.max(1) as f64)
+        .collect();
+    TriangleSoup {
+        positions,
+        triangles,
+    }
+    .compacted()
+    .0
+}
+
+// Vertex-clustering decimation down to roughly `target_faces`, bisecting the
+// cell size between "nothing merges" and "the whole box is one cell"
+pub fn decimate_for_display(soup: &TriangleSoup, target_faces: usize) -> TriangleSoup {
+    if soup.triangles.len() <= target_faces || soup.positions.is_empty() {
+        return soup.clone();
+    }
+    let (min, max) = soup.positions.iter().fold(
+        (DVec3::splat(f64::INFINITY), DVec3::splat(f64::NEG_INFINITY)),
+        |(min, max), &p| (min.min(p), max.max(p)),
+    );
+    let extent = (max - min).max_element().max(f64::EPSILON);
+    // Cell sizes are searched in log space; the finest starts well below any
+    // meaningful feature
+    let (mut fine, mut coarse) = (extent * 1e-6, extent);
+    let mut best = cluster(soup, min, coarse);
+    for _ in 0..CLUSTER_SEARCH_STEPS {
+        let cell = (fine * coarse).sqrt();
+        let result = cluster(soup, min, cell);
+        if result.triangles.len() > target_faces {
+            fine = cell;
+        } else {
+            coarse = cell;
+            best = result;
+        }
+    }
+    best
+}
+
+// Smooth-shaded render mesh for a triangle soup, with area-weighted normals
+pub fn soup_to_bevy_mesh(soup: &TriangleSoup) -> Mesh {
+    let mut normals = vec![DVec3::ZERO; soup.positions.len()];
+    for &[a, b, c] in &soup.triangles {
+        let [pa, pb, pc] = [a, b, c].map(|v| soup.positions[v]);
+        let n = (pb - pa).cross(pc - pa);
+        for v in [a, b, c] {
+            normals[v] += n;
+        }
+    }
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all());
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        soup.positions
+            .iter()
+            .map(|p| p.as_vec3().to_array())
+            .collect::<Vec<_>>(),
+    );
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        normals
+            .iter()
+            .map(|n| n.normalize_or(DVec3::Y).as_vec3().to_array())
+            .collect::<Vec<_>>(),
+    );
+    mesh.insert_indices(Indices::U32(
+        soup.triangles
+            .iter()
+            .flat_map(|tri| tri.map(|v| v as u32))
+            .collect(),
+    ));
+    mesh
+}
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        change_detection::DetectChangesMut,
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        hierarchy::ChildOf,
+        query::Without,
+        system::{Commands, Query, Res, ResMut},
+    },
+    render::mesh::{Indices, Mesh, Mesh3d},
+};
+
+use crate::budget::systems::MeshBudget;
+use crate::camera::components::CgarMeshData;
+use crate::mesh::face_tree::FaceTreeCache;
+use crate::notifications::systems::Notify;
+
+const MIB: usize = 1024 * 1024;
+
+// Approximate bytes a mesh holds: the cgar vertex, half-edge and face arrays,
+// the vertex and index buffers uploaded for it and its children (overlays,
+// proxies, highlights), and cgar's face tree used for picking
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MeshMemory {
+    pub cgar: usize,
+    pub gpu: usize,
+    pub face_tree: usize,
+}
+
+impl MeshMemory {
+    pub fn total(&self) -> usize {
+        self.cgar + self.gpu + self.face_tree
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1024 * MIB => format!("{:.2} GiB", b as f64 / (1024 * MIB) as f64),
+        b if b >= MIB => format!("{:.1} MiB", b as f64 / MIB as f64),
+        b => format!("{:.0} KiB", b as f64 / 1024.0),
+    }
+}
+
+fn cgar_bytes(cgar_data: &CgarMeshData) -> usize {
+    let m = &cgar_data.0;
+    size_of_val(m.vertices.as_slice())
+        + size_of_val(m.half_edges.as_slice())
+        + size_of_val(m.faces.as_slice())
+}
+
+// Meshes that only live in the render world are no longer in `Assets<Mesh>`
+// and count as zero
+fn gpu_bytes(meshes: &Assets<Mesh>, handle: &Mesh3d) -> usize {
+    meshes.get(&handle.0).map_or(0, |mesh| {
+        let vertices = mesh.count_vertices() * mesh.get_vertex_size() as usize;
+        let indices = match mesh.indices() {
+            Some(Indices::U16(indices)) => indices.len() * 2,
+            Some(Indices::U32(indices)) => indices.len() * 4,
+            None => 0,
+        };
+        vertices + indices
+    })
+}
+
+// Keeps every mesh's `MeshMemory` current, children's buffers included. The sizes are cheap to read, so
+// they are refreshed every frame rather than tracked through each edit.
+pub fn measure_mesh_memory(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    mut mesh_query: Query<(
+        Entity,
+        &CgarMeshData,
+        Option<&Mesh3d>,
+        Option<&FaceTreeCache>,
+        Option<&mut MeshMemory>,
+    )>,
+    child_query: Query<(&Mesh3d, &ChildOf), Without<CgarMeshData>>,
+) {
+    let mut child_bytes: HashMap<Entity, usize> = HashMap::new();
+    for (handle, parent) in &child_query {
+        *child_bytes.entry(parent.parent()).or_default() += gpu_bytes(&meshes, handle);
+    }
+    for (entity, cgar_data, handle, tree, memory) in &mut mesh_query {
+        let measured = MeshMemory {
+            cgar: cgar_bytes(cgar_data),
+            gpu: handle.map_or(0, |handle| gpu_bytes(&meshes, handle))
+                + child_bytes.get(&entity).copied().unwrap_or(0),
+            face_tree: tree.map_or(0, |tree| tree.0.memory_bytes()),
+        };
+        match memory {
+            Some(mut memory) => {
+                memory.set_if_neq(measured);
+            }
+            None => {
+                commands.entity(entity).insert(measured);
+            }
+        }
+    }
+}
+
+// Warns once when the meshes together come within 90% of the memory
+// ceiling, and again only after usage has dropped back below 80%
+pub fn check_memory_ceiling(
+    mut budget: ResMut<MeshBudget>,
+    mut notices: EventWriter<Notify>,
+    memory_query: Query<&MeshMemory>,
+) {
+    let total: usize = memory_query.iter().map(MeshMemory::total).sum();
+    let ceiling = budget.memory_ceiling_mib * MIB;
+    let budget = budget.bypass_change_detection();
+    if !budget.memory_warned && total * 10 >= ceiling * 9 {
+        budget.memory_warned = true;
+        notices.write(Notify::warning(format!(
+            "Meshes use about {} of the {} memory ceiling; close or decimate meshes before loading more",
+            format_bytes(total),
+            format_bytes(ceiling)
+        )));
+    } else if budget.memory_warned && total * 10 < ceiling * 8 {
+        budget.memory_warned = false;
+    }
+}
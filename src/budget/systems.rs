@@ -0,0 +1,526 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        change_detection::{DetectChanges, DetectChangesMut},
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        hierarchy::ChildOf,
+        name::Name,
+        query::{Changed, Has, Or, With},
+        removal_detection::RemovedComponents,
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+        world::Ref,
+    },
+    input::{ButtonInput, mouse::MouseButton, touch::Touches},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    picking::Pickable,
+    render::{
+        camera::Projection,
+        mesh::{Mesh, Mesh3d},
+        view::{RenderLayers, Visibility},
+    },
+    time::Time,
+    transform::components::Transform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::budget::decimate::{decimate_for_display, soup_to_bevy_mesh};
+use crate::budget::memory::{MeshMemory, format_bytes};
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::repair::ops::TriangleSoup;
+use crate::utils::constants::{
+    DEFAULT_DISPLAY_FACES, DEFAULT_FACE_BUDGET, DEFAULT_MEMORY_CEILING_MIB, DEFAULT_PROXY_FACES,
+    DEFAULT_VERTEX_BUDGET,
+};
+
+// Layer no camera renders; full meshes move there while their proxy is drawn
+const PROXY_HIDDEN_LAYER: usize = 29;
+// Seconds without camera motion before full meshes come back
+const PROXY_SETTLE_SECS: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshCounts {
+    pub faces: usize,
+    pub vertices: usize,
+}
+
+// Size limits past which a mesh is flagged and the viewer degrades instead of
+// grinding to a halt: hover picking is limited to clicks, selection highlights
+// are drawn as one batched mesh, and the mesh can be decimated for display
+#[derive(Resource)]
+pub struct MeshBudget {
+    pub max_faces: usize,
+    pub max_vertices: usize,
+    pub display_faces: usize,
+    // Decimate over-budget meshes for display as soon as they're flagged
+    pub auto_decimate: bool,
+    pub limit_hover_picking: bool,
+    pub batch_highlights: bool,
+    // Draw meshes with more than `proxy_faces` faces as a decimated proxy
+    // while the camera moves
+    pub proxy_while_navigating: bool,
+    pub proxy_faces: usize,
+    // Memory all meshes may use together before a warning, see `MeshMemory`
+    pub memory_ceiling_mib: usize,
+    pub memory_warned: bool,
+    // Meshes currently over budget
+    pub over: HashMap<Entity, MeshCounts>,
+}
+
+impl Default for MeshBudget {
+    fn default() -> Self {
+        Self {
+            max_faces: DEFAULT_FACE_BUDGET,
+            max_vertices: DEFAULT_VERTEX_BUDGET,
+            display_faces: DEFAULT_DISPLAY_FACES,
+            auto_decimate: false,
+            limit_hover_picking: true,
+            batch_highlights: true,
+            proxy_while_navigating: false,
+            proxy_faces: DEFAULT_PROXY_FACES,
+            memory_ceiling_mib: DEFAULT_MEMORY_CEILING_MIB,
+            memory_warned: false,
+            over: HashMap::new(),
+        }
+    }
+}
+
+impl MeshBudget {
+    pub fn exceeds(&self, counts: MeshCounts) -> bool {
+        counts.faces > self.max_faces || counts.vertices > self.max_vertices
+    }
+
+    pub fn hover_limited(&self, entity: Entity) -> bool {
+        self.limit_hover_picking && self.over.contains_key(&entity)
+    }
+
+    // Selection highlights go through the batched mesh while any mesh is over budget
+    pub fn batched(&self) -> bool {
+        self.batch_highlights && !self.over.is_empty()
+    }
+}
+
+// Draws a decimated copy of the mesh while the cgar mesh keeps full detail
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DisplayDecimation {
+    pub target_faces: usize,
+    // Faces in the mesh currently drawn
+    pub drawn_faces: usize,
+}
+
+impl DisplayDecimation {
+    pub fn new(target_faces: usize) -> Self {
+        Self {
+            target_faces,
+            drawn_faces: 0,
+        }
+    }
+}
+
+// Low-poly stand-in for its parent mesh, shown only while the camera moves
+#[derive(Component)]
+pub struct NavigationProxy;
+
+// On a mesh: its cached proxy, dropped when the mesh changes
+#[derive(Component)]
+pub struct NavigationProxyCache {
+    pub proxy: Entity,
+}
+
+fn mesh_counts(cgar_data: &CgarMeshData) -> MeshCounts {
+    MeshCounts {
+        faces: cgar_data.0.faces.iter().filter(|f| !f.removed).count(),
+        vertices: cgar_data.0.vertices.len(),
+    }
+}
+
+// Flags loaded, generated or edited meshes that go over budget, warning once
+// when a mesh first crosses it
+pub fn check_mesh_budgets(
+    mut commands: Commands,
+    mut budget: ResMut<MeshBudget>,
+    mut removed: RemovedComponents<CgarMeshData>,
+    mut notices: EventWriter<Notify>,
+    mesh_query: Query<(Entity, Ref<CgarMeshData>, Option<&Name>)>,
+) {
+    let recheck_all = budget.is_changed();
+    // Bookkeeping below must not look like a settings change next frame
+    let budget = budget.bypass_change_detection();
+    for entity in removed.read() {
+        budget.over.remove(&entity);
+    }
+    for (entity, cgar_data, name) in &mesh_query {
+        if !recheck_all && !cgar_data.is_changed() {
+            continue;
+        }
+        let counts = mesh_counts(&cgar_data);
+        if !budget.exceeds(counts) {
+            budget.over.remove(&entity);
+            continue;
+        }
+        if budget.over.insert(entity, counts).is_some() {
+            continue;
+        }
+        let name = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+        notices.write(Notify::warning(format!(
+            "{} has {} faces and {} vertices, over the budget of {} / {}; see the Mesh budget panel",
+            name, counts.faces, counts.vertices, budget.max_faces, budget.max_vertices
+        )));
+        if budget.auto_decimate {
+            commands
+                .entity(entity)
+                .insert(DisplayDecimation::new(budget.display_faces));
+        }
+    }
+}
+
+// Swaps in the decimated render mesh whenever the full one would be redrawn,
+// and restores the full mesh when decimation is switched off
+pub fn apply_display_decimation(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut changed: Query<
+        (&Mesh3d, &CgarMeshData, &mut DisplayDecimation),
+        Or<(
+            Changed<DisplayDecimation>,
+            Changed<CgarMeshData>,
+            Changed<FaceColorOverlay>,
+            Changed<FeatureEdges>,
+            Changed<ImportedNormals>,
+            Changed<NormalSettings>,
+        )>,
+    >,
+    mut removed: RemovedComponents<DisplayDecimation>,
+    mesh_query: Query<(
+        &Mesh3d,
+        &CgarMeshData,
+        Option<&FaceColorOverlay>,
+        Option<&FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    for (mesh_handle, cgar_data, mut decimation) in &mut changed {
+        let soup = TriangleSoup::from_topology(&MeshTopology::from_cgar(&cgar_data.0));
+        let decimated = decimate_for_display(&soup, decimation.target_faces);
+        decimation.bypass_change_detection().drawn_faces = decimated.triangles.len();
+        meshes.insert(&mesh_handle.0, soup_to_bevy_mesh(&decimated));
+    }
+    for entity in removed.read() {
+        if let Ok((mesh_handle, cgar_data, overlay, features, normals, settings)) =
+            mesh_query.get(entity)
+        {
+            meshes.insert(
+                &mesh_handle.0,
+                render_mesh(&cgar_data.0, overlay, features, normals, settings),
+            );
+        }
+    }
+}
+
+// Mesh picking ray casts every mesh under the cursor each frame. Over-budget
+// meshes only take part on frames where a button goes down or up, which is
+// all clicks, long presses and context menus need.
+pub fn limit_hover_picking(
+    budget: Res<MeshBudget>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    mut mesh_query: Query<(Entity, &mut Pickable), With<CgarMeshData>>,
+) {
+    let pressing = mouse_buttons.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_released().next().is_some()
+        || touches.any_just_pressed()
+        || touches.any_just_released();
+    for (entity, mut pickable) in &mut mesh_query {
+        let wanted = if pressing || !budget.hover_limited(entity) {
+            Pickable::default()
+        } else {
+            Pickable::IGNORE
+        };
+        pickable.set_if_neq(wanted);
+    }
+}
+
+pub fn budget_panel(
+    mut contexts: EguiContexts,
+    mut commands: Commands,
+    mut budget: ResMut<MeshBudget>,
+    mesh_query: Query<(Option<&Name>, Option<&DisplayDecimation>)>,
+    memory_query: Query<(Entity, Option<&Name>, &MeshMemory)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Mesh budget")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let (mut max_faces, mut max_vertices, mut display_faces) =
+                (budget.max_faces, budget.max_vertices, budget.display_faces);
+            ui.add(
+                egui::DragValue::new(&mut max_faces)
+                    .range(1000..=100_000_000)
+                    .speed(10_000)
+                    .prefix("Face budget: "),
+            );
+            ui.add(
+                egui::DragValue::new(&mut max_vertices)
+                    .range(1000..=100_000_000)
+                    .speed(10_000)
+                    .prefix("Vertex budget: "),
+            );
+            ui.add(
+                egui::DragValue::new(&mut display_faces)
+                    .range(1000..=10_000_000)
+                    .speed(1000)
+                    .prefix("Display faces: "),
+            );
+            if (max_faces, max_vertices, display_faces)
+                != (budget.max_faces, budget.max_vertices, budget.display_faces)
+            {
+                budget.max_faces = max_faces;
+                budget.max_vertices = max_vertices;
+                budget.display_faces = display_faces;
+            }
+
+            let (mut auto_decimate, mut limit_hover, mut batch) = (
+                budget.auto_decimate,
+                budget.limit_hover_picking,
+                budget.batch_highlights,
+            );
+            ui.checkbox(&mut auto_decimate, "Decimate over-budget meshes automatically");
+            ui.checkbox(&mut limit_hover, "Pick over-budget meshes on click only");
+            ui.checkbox(&mut batch, "Batch selection highlights when over budget");
+            if auto_decimate != budget.auto_decimate {
+                budget.auto_decimate = auto_decimate;
+            }
+            if limit_hover != budget.limit_hover_picking {
+                budget.limit_hover_picking = limit_hover;
+            }
+            if batch != budget.batch_highlights {
+                budget.batch_highlights = batch;
+            }
+
+            let (mut proxy, mut proxy_faces) =
+                (budget.proxy_while_navigating, budget.proxy_faces);
+            ui.checkbox(&mut proxy, "Draw large meshes decimated while the camera moves");
+            ui.add_enabled(
+                proxy,
+                egui::DragValue::new(&mut proxy_faces)
+                    .range(1000..=10_000_000)
+                    .speed(1000)
+                    .prefix("Proxy faces: "),
+            );
+            if (proxy, proxy_faces) != (budget.proxy_while_navigating, budget.proxy_faces) {
+                budget.proxy_while_navigating = proxy;
+                budget.proxy_faces = proxy_faces;
+            }
+
+            ui.separator();
+            let mut ceiling = budget.memory_ceiling_mib;
+            ui.add(
+                egui::DragValue::new(&mut ceiling)
+                    .range(256..=1_048_576)
+                    .speed(256)
+                    .prefix("Memory ceiling: ")
+                    .suffix(" MiB"),
+            );
+            if ceiling != budget.memory_ceiling_mib {
+                budget.memory_ceiling_mib = ceiling;
+            }
+            let mut usage: Vec<(String, MeshMemory)> = memory_query
+                .iter()
+                .map(|(entity, name, memory)| {
+                    let name = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+                    (name, *memory)
+                })
+                .collect();
+            usage.sort_by_key(|(_, memory)| std::cmp::Reverse(memory.total()));
+            let total: usize = usage.iter().map(|(_, memory)| memory.total()).sum();
+            ui.label(format!(
+                "Meshes use about {} of {}",
+                format_bytes(total),
+                format_bytes(ceiling * 1024 * 1024)
+            ));
+            egui::CollapsingHeader::new("Memory per mesh").show(ui, |ui| {
+                egui::Grid::new("mesh_memory").striped(true).show(ui, |ui| {
+                    for header in ["", "cgar", "GPU", "Face tree", "Total"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    for (name, memory) in &usage {
+                        ui.label(name.as_str());
+                        ui.label(format_bytes(memory.cgar));
+                        ui.label(format_bytes(memory.gpu));
+                        ui.label(format_bytes(memory.face_tree));
+                        ui.label(format_bytes(memory.total()));
+                        ui.end_row();
+                    }
+                });
+            });
+
+            ui.separator();
+            if budget.over.is_empty() {
+                ui.label("All meshes are within budget");
+                return;
+            }
+            let mut over: Vec<(Entity, MeshCounts)> =
+                budget.over.iter().map(|(&e, &c)| (e, c)).collect();
+            over.sort_by_key(|(entity, _)| *entity);
+            for (entity, counts) in over {
+                let Ok((name, decimation)) = mesh_query.get(entity) else {
+                    continue;
+                };
+                let name = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+                ui.label(format!(
+                    "{}: {} faces, {} vertices",
+                    name, counts.faces, counts.vertices
+                ));
+                ui.horizontal(|ui| match decimation {
+                    Some(decimation) => {
+                        ui.label(format!("Drawing {} faces", decimation.drawn_faces));
+                        if ui.button("Show full mesh").clicked() {
+                            commands.entity(entity).remove::<DisplayDecimation>();
+                        }
+                    }
+                    None => {
+                        if ui.button("Decimate for display").clicked() {
+                            commands
+                                .entity(entity)
+                                .insert(DisplayDecimation::new(budget.display_faces));
+                        }
+                    }
+                });
+            }
+            ui.label("Edits and analysis still use the full mesh; overlays show once it is drawn in full");
+        });
+}
+
+// Swaps huge meshes for decimated proxies while the camera moves and back once
+// it settles. The full mesh only moves to a layer no camera draws, so its
+// render mesh, children and data are left alone. Proxies are built on the
+// first move after a mesh changes and reused until it changes again.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_navigation_proxies(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    budget: Res<MeshBudget>,
+    time: Res<Time>,
+    (mut last_moved, mut showing): (Local<Option<f64>>, Local<bool>),
+    camera_query: Query<(Ref<Transform>, Ref<Projection>), With<OrbitCamera>>,
+    mesh_query: Query<(
+        Entity,
+        Ref<CgarMeshData>,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+        Option<&NavigationProxyCache>,
+        Has<DisplayDecimation>,
+    )>,
+    mut proxy_query: Query<&mut Visibility, With<NavigationProxy>>,
+) {
+    // Cached proxies go stale when their mesh changes; meshes already drawn
+    // decimated need none
+    for (entity, cgar_data, _, cache, decimated) in &mesh_query {
+        let Some(cache) = cache else {
+            continue;
+        };
+        if cgar_data.is_changed() || decimated {
+            commands.entity(cache.proxy).despawn();
+            commands
+                .entity(entity)
+                .remove::<(NavigationProxyCache, RenderLayers)>();
+        }
+    }
+
+    let now = time.elapsed_secs_f64();
+    let moved = camera_query
+        .single()
+        .is_ok_and(|(transform, projection)| transform.is_changed() || projection.is_changed());
+    if moved {
+        *last_moved = Some(now);
+    }
+    let navigating = budget.proxy_while_navigating
+        && last_moved.is_some_and(|moved| now - moved < PROXY_SETTLE_SECS);
+    if navigating == *showing {
+        return;
+    }
+    *showing = navigating;
+
+    for (entity, cgar_data, material, cache, decimated) in &mesh_query {
+        if !navigating {
+            if let Some(cache) = cache {
+                if let Ok(mut visibility) = proxy_query.get_mut(cache.proxy) {
+                    *visibility = Visibility::Hidden;
+                }
+                commands.entity(entity).remove::<RenderLayers>();
+            }
+            continue;
+        }
+        if cgar_data.is_changed() || decimated {
+            continue;
+        }
+        let proxy = match cache {
+            Some(cache) => cache.proxy,
+            None => {
+                if mesh_counts(&cgar_data).faces <= budget.proxy_faces {
+                    continue;
+                }
+                let soup = TriangleSoup::from_topology(&MeshTopology::from_cgar(&cgar_data.0));
+                let decimated = decimate_for_display(&soup, budget.proxy_faces);
+                let proxy = commands
+                    .spawn((
+                        NavigationProxy,
+                        Mesh3d(meshes.add(soup_to_bevy_mesh(&decimated))),
+                        Transform::default(),
+                        Visibility::Inherited,
+                        Pickable::IGNORE,
+                        ChildOf(entity),
+                    ))
+                    .id();
+                commands
+                    .entity(entity)
+                    .insert(NavigationProxyCache { proxy });
+                proxy
+            }
+        };
+        if let Ok(mut visibility) = proxy_query.get_mut(proxy) {
+            *visibility = Visibility::Inherited;
+        }
+        // The material may have changed since the proxy was built
+        if let Some(material) = material {
+            commands.entity(proxy).insert(material.clone());
+        }
+        commands
+            .entity(entity)
+            .insert(RenderLayers::layer(PROXY_HIDDEN_LAYER));
+    }
+}
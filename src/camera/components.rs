@@ -21,8 +21,8 @@
 // SOFTWARE.
 
 use bevy::{
-    ecs::component::Component,
-    math::{Vec2, Vec3},
+    ecs::{component::Component, resource::Resource},
+    math::Vec3,
 };
 use cgar::{mesh::basic_types::Mesh as CgarMesh, numeric::cgar_f64::CgarF64};
 
@@ -31,9 +31,110 @@ pub struct OrbitCamera {
     pub focus: Vec3,
     pub radius: f32,
     pub upside_down: bool,
-    pub last_mouse_pos: Option<Vec2>,
+    // Point the focus glides toward, set by double-clicking the mesh
+    pub focus_target: Option<Vec3>,
 }
 
 // Component for cgar mesh wrapper
 #[derive(Component)]
 pub struct CgarMeshData(pub CgarMesh<CgarF64, 3>);
+
+// How the left button is shared between orbiting and picking
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NavigationScheme {
+    // Left-drag orbits and a left click picks, told apart by the click deadzone
+    #[default]
+    Shared,
+    // Alt+left-drag orbits; plain left clicks only pick
+    ModifierOrbit,
+    // Left-drag orbits; only Alt+left clicks pick
+    ModifierPick,
+}
+
+impl NavigationScheme {
+    pub const ALL: [NavigationScheme; 3] = [
+        NavigationScheme::Shared,
+        NavigationScheme::ModifierOrbit,
+        NavigationScheme::ModifierPick,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NavigationScheme::Shared => "Drag orbits, click picks",
+            NavigationScheme::ModifierOrbit => "Alt+drag orbits",
+            NavigationScheme::ModifierPick => "Alt+click picks",
+        }
+    }
+
+    // Middle-drag orbits under every scheme
+    pub fn orbits(self, left: bool, middle: bool, alt: bool) -> bool {
+        middle
+            || match self {
+                NavigationScheme::Shared | NavigationScheme::ModifierPick => left,
+                NavigationScheme::ModifierOrbit => left && alt,
+            }
+    }
+
+    pub fn picks(self, alt: bool) -> bool {
+        match self {
+            NavigationScheme::Shared => true,
+            NavigationScheme::ModifierOrbit => !alt,
+            NavigationScheme::ModifierPick => alt,
+        }
+    }
+}
+
+// User-facing navigation options
+#[derive(Resource, Debug)]
+pub struct OrbitSettings {
+    // Orbit around the selection centroid while something is selected, and
+    // around the mesh center otherwise
+    pub pivot_on_selection: bool,
+    // Multipliers on top of the scene-size scaling
+    pub pan_speed: f32,
+    pub zoom_speed: f32,
+    // Orbit speed multipliers per screen axis
+    pub rotate_speed_x: f32,
+    pub rotate_speed_y: f32,
+    pub invert_x: bool,
+    pub invert_y: bool,
+    pub navigation: NavigationScheme,
+    // Fit near/far to the scene every frame; otherwise `near`/`far` are used as-is
+    pub auto_clip: bool,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Default for OrbitSettings {
+    fn default() -> Self {
+        Self {
+            pivot_on_selection: false,
+            pan_speed: 1.0,
+            zoom_speed: 1.0,
+            rotate_speed_x: 1.0,
+            rotate_speed_y: 1.0,
+            invert_x: false,
+            invert_y: false,
+            navigation: NavigationScheme::default(),
+            auto_clip: true,
+            near: 0.01,
+            far: 1000.0,
+        }
+    }
+}
+
+// World-space extent of everything loaded, for scaling navigation
+#[derive(Resource, Debug)]
+pub struct SceneBounds {
+    pub center: Vec3,
+    pub diagonal: f32,
+}
+
+impl Default for SceneBounds {
+    fn default() -> Self {
+        Self {
+            center: Vec3::ZERO,
+            diagonal: 1.0,
+        }
+    }
+}
@@ -21,23 +21,45 @@
 // SOFTWARE.
 
 use bevy::{
-    core_pipeline::core_3d::Camera3d,
+    color::Color,
     ecs::{
+        change_detection::{DetectChanges, Ref},
         event::EventReader,
-        query::With,
-        system::{Query, Res},
+        query::{Added, With, Without},
+        removal_detection::RemovedComponents,
+        system::{Query, Res, ResMut},
     },
+    gizmos::gizmos::Gizmos,
     input::{
         ButtonInput,
         keyboard::KeyCode,
         mouse::{MouseButton, MouseMotion, MouseWheel},
     },
-    math::{Vec2, Vec3},
-    render::camera::Projection,
-    transform::components::Transform,
+    math::{DVec3, Isometry3d, Vec2, Vec3},
+    render::camera::{Projection, ScalingMode},
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
 };
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
 
-use crate::camera::components::OrbitCamera;
+use crate::camera::components::{
+    CgarMeshData, NavigationScheme, OrbitCamera, OrbitSettings, SceneBounds,
+};
+use crate::edit::systems::VertexEdit;
+use crate::mesh::conversion::vertex_position;
+use crate::mesh::edge::{MeshPicked, PickSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::sculpt::systems::SculptBrush;
+use crate::selection::components::{AreaSelect, SelectionSet};
+use crate::workspace::systems::Stashed;
+
+// Scene diagonal the navigation constants were tuned for
+const REFERENCE_DIAGONAL: f32 = 1.0;
+// Orbit angle per pixel of mouse motion at speed 1
+const ROTATE_RADIANS_PER_PIXEL: f32 = 0.005;
+// Orthographic zoom range, as visible height over the scene diagonal
+const ORTHO_MIN_VIEW: f32 = 1e-4;
+const ORTHO_MAX_VIEW: f32 = 20.0;
 
 // Camera controller system for orbit camera
 pub fn camera_controller(
@@ -45,58 +67,76 @@ pub fn camera_controller(
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     mut mouse_motion: EventReader<MouseMotion>,
     mut mouse_wheel: EventReader<MouseWheel>,
-    mut camera_query: Query<(&mut Transform, &mut OrbitCamera), With<Camera3d>>,
-    mut projection_query: Query<&mut Projection, With<Camera3d>>,
+    mut camera_query: Query<(&mut Transform, &mut OrbitCamera), With<OrbitCamera>>,
+    mut projection_query: Query<&mut Projection, With<OrbitCamera>>,
+    vertex_edit: Res<VertexEdit>,
+    sculpt: Res<SculptBrush>,
+    area: Res<AreaSelect>,
+    settings: Res<OrbitSettings>,
+    bounds: Res<SceneBounds>,
 ) {
     let Ok((mut transform, mut orbit)) = camera_query.single_mut() else {
         return;
     };
 
-    let mut rotation_move = Vec2::ZERO;
-    let mut pan_move = Vec2::ZERO;
     let mut scroll = 0.0;
     let mut orbit_button_changed = false;
 
-    if mouse_buttons.pressed(MouseButton::Left) {
-        for mouse_event in mouse_motion.read() {
-            if let Some(last_pos) = orbit.last_mouse_pos {
-                let actual_delta = mouse_event.delta - last_pos;
-                rotation_move += actual_delta;
-            }
-            orbit.last_mouse_pos = Some(mouse_event.delta);
-        }
-    } else if mouse_buttons.pressed(MouseButton::Right) {
-        // Handle panning with right mouse button
-        for mouse_event in mouse_motion.read() {
-            if let Some(last_pos) = orbit.last_mouse_pos {
-                let actual_delta = mouse_event.delta - last_pos;
-                pan_move += actual_delta;
-            }
-            orbit.last_mouse_pos = Some(mouse_event.delta);
-        }
-    } else {
-        orbit.last_mouse_pos = None;
-        // Still consume events
-        for _mouse_event in mouse_motion.read() {}
-    }
-
-    // Consume remaining mouse motion events
-    for _mouse_event in mouse_motion.read() {
-        // Consume the events
-    }
+    // Motion deltas are already relative, so they simply add up over the frame
+    let motion: Vec2 = mouse_motion.read().map(|event| event.delta).sum();
+    let alt = keyboard.pressed(KeyCode::AltLeft) || keyboard.pressed(KeyCode::AltRight);
+    let orbiting = settings.navigation.orbits(
+        mouse_buttons.pressed(MouseButton::Left),
+        mouse_buttons.pressed(MouseButton::Middle),
+        alt,
+    );
+    // A vertex drag, sculpt stroke or area drag owns the left button until it
+    // is released
+    let (rotation_move, pan_move) =
+        if orbiting && vertex_edit.drag.is_none() && sculpt.stroke.is_none() && area.drag.is_none()
+        {
+            (motion, Vec2::ZERO)
+        } else if mouse_buttons.pressed(MouseButton::Right) {
+            (Vec2::ZERO, motion)
+        } else {
+            (Vec2::ZERO, Vec2::ZERO)
+        };
 
     for wheel_event in mouse_wheel.read() {
         scroll += wheel_event.y;
     }
 
+    // The base constants were tuned for a unit-sized scene; scale zoom limits
+    // and pan speed by the actual scene size and the current zoom level
+    let scene_scale = (bounds.diagonal / REFERENCE_DIAGONAL).max(f32::EPSILON);
+    let zoom_factor = (-scroll * 0.1 * settings.zoom_speed).exp();
+    // Roughly the visible extent around the focus, used to scale panning
+    let mut view_extent = orbit.radius;
     if let Ok(mut projection) = projection_query.single_mut() {
         match projection.as_mut() {
             bevy::render::camera::Projection::Orthographic(ortho) => {
-                // For orthographic, adjust scale instead of distance
+                // For orthographic, adjust scale instead of distance, keeping
+                // the visible height within a range around the scene size
+                let viewport_height = match ortho.scaling_mode {
+                    ScalingMode::FixedVertical { viewport_height } => viewport_height,
+                    _ => ortho.area.height() / ortho.scale.max(f32::EPSILON),
+                };
+                if scroll != 0.0 && viewport_height > 0.0 {
+                    let visible = (ortho.scale * zoom_factor * viewport_height).clamp(
+                        ORTHO_MIN_VIEW * bounds.diagonal,
+                        ORTHO_MAX_VIEW * bounds.diagonal,
+                    );
+                    ortho.scale = visible / viewport_height;
+                }
+                // Matches the old radius-based speed at the original scale of 2
+                // over a viewport height of 2
+                view_extent = ortho.scale * viewport_height * 2.5;
+            }
+            bevy::render::camera::Projection::Perspective(_) => {
                 if scroll != 0.0 {
-                    let zoom_speed = 0.1;
-                    ortho.scale *= 1.0 - scroll * zoom_speed;
-                    ortho.scale = ortho.scale.clamp(0.1, 10.0); // Reasonable limits
+                    orbit.radius *= zoom_factor;
+                    orbit.radius = orbit.radius.clamp(0.01 * scene_scale, 100.0 * scene_scale);
+                    orbit_button_changed = true;
                 }
             }
             _ => {}
@@ -105,9 +145,15 @@ pub fn camera_controller(
 
     // Handle rotation
     if rotation_move.length_squared() > 0.0 {
-        let sensitivity = 0.005;
-        let delta_x = rotation_move.x * sensitivity;
-        let delta_y = rotation_move.y * sensitivity;
+        let sign = |invert: bool| if invert { -1.0 } else { 1.0 };
+        let delta_x = rotation_move.x
+            * ROTATE_RADIANS_PER_PIXEL
+            * settings.rotate_speed_x
+            * sign(settings.invert_x);
+        let delta_y = rotation_move.y
+            * ROTATE_RADIANS_PER_PIXEL
+            * settings.rotate_speed_y
+            * sign(settings.invert_y);
 
         // Convert current position to spherical coordinates
         let offset = transform.translation - orbit.focus;
@@ -143,11 +189,14 @@ pub fn camera_controller(
         let camera_up = transform.local_y();
 
         // Calculate pan offset in world space
-        let pan_offset =
-            (-camera_right * pan_move.x + camera_up * pan_move.y) * pan_sensitivity * orbit.radius;
+        let pan_offset = (-camera_right * pan_move.x + camera_up * pan_move.y)
+            * pan_sensitivity
+            * settings.pan_speed
+            * view_extent;
 
-        // Move the focus point
+        // Move the focus point; panning overrides a double-click glide
         orbit.focus += pan_offset;
+        orbit.focus_target = None;
 
         // Update camera position to maintain same relative position to new focus
         let offset = transform.translation - (orbit.focus - pan_offset);
@@ -165,3 +214,345 @@ pub fn camera_controller(
         transform.look_at(orbit.focus, Vec3::Y);
     }
 }
+
+// World-space bounds of all meshes, recomputed when any of them changes
+pub fn update_scene_bounds(
+    mut bounds: ResMut<SceneBounds>,
+    // Meshes of other workspaces don't count
+    mesh_query: Query<(Ref<GlobalTransform>, Ref<CgarMeshData>), Without<Stashed>>,
+    stashed: Query<(), Added<Stashed>>,
+    mut removed: RemovedComponents<CgarMeshData>,
+    mut unstashed: RemovedComponents<Stashed>,
+) {
+    let removed_any =
+        removed.read().count() > 0 || unstashed.read().count() > 0 || !stashed.is_empty();
+    let changed = mesh_query
+        .iter()
+        .any(|(global, cgar_data)| global.is_changed() || cgar_data.is_changed());
+    if !changed && !removed_any {
+        return;
+    }
+    let (lo, hi) = mesh_query.iter().fold(
+        (Vec3::INFINITY, Vec3::NEG_INFINITY),
+        |(lo, hi), (global, cgar_data)| {
+            (0..cgar_data.0.vertices.len()).fold((lo, hi), |(lo, hi), v| {
+                let p = global.transform_point(vertex_position(&cgar_data.0, v));
+                (lo.min(p), hi.max(p))
+            })
+        },
+    );
+    let (center, diagonal) = ((lo + hi) * 0.5, (hi - lo).length());
+    if diagonal.is_finite()
+        && diagonal > 0.0
+        && (center, diagonal) != (bounds.center, bounds.diagonal)
+    {
+        bounds.center = center;
+        bounds.diagonal = diagonal;
+    }
+}
+
+// Sizes the orthographic view volume to the scene so the zoom level means
+// the same thing for a tiny part and a building: at scale 1 the visible
+// height equals the scene diagonal
+pub fn fit_orthographic_height(
+    bounds: Res<SceneBounds>,
+    mut projection_query: Query<&mut Projection, With<OrbitCamera>>,
+) {
+    if !bounds.is_changed() {
+        return;
+    }
+    let Ok(mut projection) = projection_query.single_mut() else {
+        return;
+    };
+    let Projection::Orthographic(ortho) = projection.as_mut() else {
+        return;
+    };
+    let wanted = ScalingMode::FixedVertical {
+        viewport_height: bounds.diagonal,
+    };
+    if ortho.scaling_mode != wanted {
+        ortho.scaling_mode = wanted;
+    }
+}
+
+// Keeps the near/far planes around the scene's bounding sphere so large
+// meshes don't get clipped, or applies the manual planes from the settings
+pub fn fit_clipping_planes(
+    settings: Res<OrbitSettings>,
+    bounds: Res<SceneBounds>,
+    mut camera_query: Query<(&GlobalTransform, &mut Projection), With<OrbitCamera>>,
+) {
+    let Ok((camera_global, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+    let (near, far) = if settings.auto_clip {
+        let radius = bounds.diagonal * 0.5;
+        let margin = bounds.diagonal * 0.05;
+        let depth = (bounds.center - camera_global.translation()).dot(*camera_global.forward());
+        (depth - radius - margin, depth + radius + margin)
+    } else {
+        (settings.near, settings.far)
+    };
+
+    let (current, wanted) = match projection.as_ref() {
+        // Orthographic planes may sit behind the camera
+        Projection::Orthographic(ortho) => ((ortho.near, ortho.far), (near, far)),
+        // Perspective needs a positive near plane and a sane depth range
+        Projection::Perspective(perspective) => {
+            let far = far.max(f32::EPSILON);
+            (
+                (perspective.near, perspective.far),
+                (near.max(far * 1e-5), far),
+            )
+        }
+        _ => return,
+    };
+    if current == wanted {
+        return;
+    }
+    match projection.as_mut() {
+        Projection::Orthographic(ortho) => (ortho.near, ortho.far) = wanted,
+        Projection::Perspective(perspective) => (perspective.near, perspective.far) = wanted,
+        _ => {}
+    }
+}
+
+// Double-clicking the mesh re-targets the orbit focus at the point under the cursor
+pub fn focus_on_double_click(
+    mut picked: EventReader<MeshPicked>,
+    mut camera_query: Query<&mut OrbitCamera, With<OrbitCamera>>,
+) {
+    let Ok(mut orbit) = camera_query.single_mut() else {
+        return;
+    };
+    for event in picked.read().filter(|event| event.double_click) {
+        if let Some(position) = event.world_position {
+            orbit.focus_target = Some(position);
+        }
+    }
+}
+
+// Eases the focus toward its target, moving the camera along so the view
+// direction and distance stay the same
+pub fn animate_orbit_focus(
+    time: Res<Time>,
+    mut camera_query: Query<(&mut Transform, &mut OrbitCamera), With<OrbitCamera>>,
+) {
+    let Ok((mut transform, mut orbit)) = camera_query.single_mut() else {
+        return;
+    };
+    let Some(target) = orbit.focus_target else {
+        return;
+    };
+    let remaining = target - orbit.focus;
+    let step = if remaining.length() <= orbit.radius * 1e-4 {
+        orbit.focus_target = None;
+        remaining
+    } else {
+        // Covers ~99% of the way in about a third of a second
+        remaining * (1.0 - (-14.0 * time.delta_secs()).exp())
+    };
+    orbit.focus += step;
+    transform.translation += step;
+}
+
+// Centroid of the selected elements, or of the whole mesh when nothing is
+// selected, in world space
+fn pivot_point(topology: &MeshTopology, selection: &SelectionSet) -> Option<DVec3> {
+    let selected = selection.touched_vertices(topology);
+    let (sum, count) = if selected.is_empty() {
+        // Bounding box center, so dense regions don't pull the pivot around
+        let (lo, hi) = topology.used_vertices().fold(
+            (DVec3::splat(f64::INFINITY), DVec3::splat(f64::NEG_INFINITY)),
+            |(lo, hi), v| (lo.min(topology.positions[v]), hi.max(topology.positions[v])),
+        );
+        (lo + hi, 2)
+    } else {
+        let sum = selected
+            .iter()
+            .map(|&v| topology.positions[v])
+            .sum::<DVec3>();
+        (sum, selected.len())
+    };
+    let center = sum / count as f64;
+    center.is_finite().then_some(center)
+}
+
+// Moves the orbit pivot to follow the selection while `pivot_on_selection` is set
+pub fn pivot_on_selection(
+    settings: Res<OrbitSettings>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+    mut camera_query: Query<&mut OrbitCamera, With<OrbitCamera>>,
+) {
+    if !settings.pivot_on_selection || !(settings.is_changed() || selection.is_changed()) {
+        return;
+    }
+    let Ok(mut orbit) = camera_query.single_mut() else {
+        return;
+    };
+    let target = selection
+        .mesh
+        .and_then(|entity| mesh_query.get(entity).ok())
+        .or_else(|| mesh_query.iter().next());
+    let Some((mesh_global, cgar_data)) = target else {
+        return;
+    };
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    if let Some(pivot) = pivot_point(&topology, &selection) {
+        orbit.focus_target = Some(mesh_global.transform_point(pivot.as_vec3()));
+    }
+}
+
+pub fn draw_orbit_pivot(
+    mut gizmos: Gizmos,
+    settings: Res<OrbitSettings>,
+    camera_query: Query<(&GlobalTransform, &OrbitCamera), With<OrbitCamera>>,
+) {
+    if !settings.pivot_on_selection {
+        return;
+    }
+    let Ok((camera_global, orbit)) = camera_query.single() else {
+        return;
+    };
+    // Constant size on screen, facing the camera
+    let size = camera_global.translation().distance(orbit.focus) * 0.015;
+    let facing = Isometry3d::new(orbit.focus, camera_global.rotation());
+    let color = Color::srgb(1.0, 1.0, 0.3);
+    gizmos.circle(facing, size, color);
+    gizmos.cross(facing, size * 0.6, color);
+}
+
+pub fn camera_panel(
+    mut contexts: EguiContexts,
+    mut settings: ResMut<OrbitSettings>,
+    mut pick_settings: ResMut<PickSettings>,
+    bounds: Res<SceneBounds>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Camera").show(ctx, |ui| {
+        let mut pivot_on_selection = settings.pivot_on_selection;
+        if ui
+            .checkbox(&mut pivot_on_selection, "Orbit around selection")
+            .changed()
+        {
+            settings.pivot_on_selection = pivot_on_selection;
+        }
+        ui.label("Double-click the mesh to re-center the orbit");
+        let mut navigation = settings.navigation;
+        egui::ComboBox::from_label("Left button")
+            .selected_text(navigation.label())
+            .show_ui(ui, |ui| {
+                for option in NavigationScheme::ALL {
+                    ui.selectable_value(&mut navigation, option, option.label());
+                }
+            });
+        if navigation != settings.navigation {
+            settings.navigation = navigation;
+        }
+        ui.label("Middle-drag always orbits");
+
+        ui.separator();
+        let mut speeds = (settings.pan_speed, settings.zoom_speed);
+        ui.add(
+            egui::Slider::new(&mut speeds.0, 0.1..=10.0)
+                .logarithmic(true)
+                .text("Pan speed"),
+        );
+        ui.add(
+            egui::Slider::new(&mut speeds.1, 0.1..=10.0)
+                .logarithmic(true)
+                .text("Zoom speed"),
+        );
+        if speeds != (settings.pan_speed, settings.zoom_speed) {
+            (settings.pan_speed, settings.zoom_speed) = speeds;
+        }
+        let mut rotate = (
+            settings.rotate_speed_x,
+            settings.rotate_speed_y,
+            settings.invert_x,
+            settings.invert_y,
+        );
+        ui.add(
+            egui::Slider::new(&mut rotate.0, 0.1..=10.0)
+                .logarithmic(true)
+                .text("Orbit speed X"),
+        );
+        ui.add(
+            egui::Slider::new(&mut rotate.1, 0.1..=10.0)
+                .logarithmic(true)
+                .text("Orbit speed Y"),
+        );
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut rotate.2, "Invert X");
+            ui.checkbox(&mut rotate.3, "Invert Y");
+        });
+        if rotate
+            != (
+                settings.rotate_speed_x,
+                settings.rotate_speed_y,
+                settings.invert_x,
+                settings.invert_y,
+            )
+        {
+            (
+                settings.rotate_speed_x,
+                settings.rotate_speed_y,
+                settings.invert_x,
+                settings.invert_y,
+            ) = rotate;
+        }
+        ui.label(format!("Scene size: {:.4}", bounds.diagonal));
+
+        ui.separator();
+        let mut auto_clip = settings.auto_clip;
+        ui.checkbox(&mut auto_clip, "Fit near/far to scene");
+        let (mut near, mut far) = (settings.near, settings.far);
+        ui.add_enabled_ui(!auto_clip, |ui| {
+            let speed = bounds.diagonal * 0.01;
+            ui.horizontal(|ui| {
+                ui.label("Near");
+                ui.add(egui::DragValue::new(&mut near).speed(speed));
+                ui.label("Far");
+                ui.add(egui::DragValue::new(&mut far).speed(speed));
+            });
+        });
+        // Keep the planes ordered
+        far = far.max(near + f32::EPSILON);
+        if (auto_clip, near, far) != (settings.auto_clip, settings.near, settings.far) {
+            (settings.auto_clip, settings.near, settings.far) = (auto_clip, near, far);
+        }
+
+        ui.separator();
+        let mut radius_px = pick_settings.radius_px;
+        ui.add(egui::Slider::new(&mut radius_px, 1.0..=30.0).text("Pick radius (px)"));
+        if radius_px != pick_settings.radius_px {
+            pick_settings.radius_px = radius_px;
+        }
+        let mut ignore_backfaces = pick_settings.ignore_backfaces;
+        ui.checkbox(&mut ignore_backfaces, "Ignore back faces when picking");
+        if ignore_backfaces != pick_settings.ignore_backfaces {
+            pick_settings.ignore_backfaces = ignore_backfaces;
+        }
+        let mut click = (
+            pick_settings.click_deadzone_px,
+            pick_settings.long_press_secs,
+        );
+        ui.add(egui::Slider::new(&mut click.0, 0.0..=20.0).text("Click deadzone (px)"));
+        ui.add(egui::Slider::new(&mut click.1, 0.2..=2.0).text("Long press (s)"));
+        if click
+            != (
+                pick_settings.click_deadzone_px,
+                pick_settings.long_press_secs,
+            )
+        {
+            (
+                pick_settings.click_deadzone_px,
+                pick_settings.long_press_secs,
+            ) = click;
+        }
+    });
+}
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    image::Image,
+    math::Vec3,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+// Per-pixel geometry of the current view, row 0 at the top. Pixels that hit
+// nothing have zero depth and a zero normal.
+pub struct GeometryMaps {
+    pub width: u32,
+    pub height: u32,
+    // Linear depth: distance along the camera's forward axis
+    pub depth: Vec<f32>,
+    // World-space unit normals
+    pub normals: Vec<Vec3>,
+}
+
+impl GeometryMaps {
+    pub fn new(width: u32, height: u32) -> Self {
+        let count = (width * height) as usize;
+        Self {
+            width,
+            height,
+            depth: vec![0.0; count],
+            normals: vec![Vec3::ZERO; count],
+        }
+    }
+
+    pub fn hits(&self) -> usize {
+        self.depth.iter().filter(|&&d| d > 0.0).count()
+    }
+
+    // Nearest and farthest hit depth
+    pub fn depth_range(&self) -> Option<(f32, f32)> {
+        self.depth
+            .iter()
+            .filter(|&&d| d > 0.0)
+            .fold(None, |range, &d| match range {
+                None => Some((d, d)),
+                Some((lo, hi)) => Some((lo.min(d), hi.max(d))),
+            })
+    }
+
+    // Near is white and far is dark; misses stay black
+    pub fn depth_rgba(&self) -> Vec<u8> {
+        let (near, far) = self.depth_range().unwrap_or((0.0, 1.0));
+        let span = (far - near).max(f32::EPSILON);
+        self.depth
+            .iter()
+            .flat_map(|&d| {
+                let value = if d > 0.0 {
+                    (255.0 - (d - near) / span * 223.0) as u8
+                } else {
+                    0
+                };
+                [value, value, value, 255]
+            })
+            .collect()
+    }
+
+    // Components mapped from [-1, 1] to [0, 255]
+    pub fn normals_rgba(&self) -> Vec<u8> {
+        self.normals
+            .iter()
+            .flat_map(|&n| {
+                let [r, g, b] = if n == Vec3::ZERO {
+                    [0; 3]
+                } else {
+                    ((n * 0.5 + 0.5) * 255.0)
+                        .round()
+                        .to_array()
+                        .map(|c| c as u8)
+                };
+                [r, g, b, 255]
+            })
+            .collect()
+    }
+}
+
+pub fn write_png(path: &Path, width: u32, height: u32, rgba: Vec<u8>) -> Result<(), String> {
+    let image = Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        rgba,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::default(),
+    );
+    image
+        .try_into_dynamic()
+        .map_err(|err| err.to_string())?
+        .to_rgb8()
+        .save(path)
+        .map_err(|err| err.to_string())
+}
+
+// Portable float map: full-precision values any float-image tool can read.
+// `values` has `channels` floats per pixel (1 or 3), top row first.
+pub fn write_pfm(
+    path: &Path,
+    width: u32,
+    height: u32,
+    channels: usize,
+    values: &[f32],
+) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    let magic = if channels == 1 { "Pf" } else { "PF" };
+    // A negative scale marks little-endian data
+    write!(out, "{}\n{} {}\n-1.0\n", magic, width, height)?;
+    // Rows are stored bottom to top
+    for row in values.chunks(width as usize * channels).rev() {
+        for value in row {
+            out.write_all(&value.to_le_bytes())?;
+        }
+    }
+    out.flush()
+}
@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::path::PathBuf;
+
+use bevy::{
+    ecs::{
+        event::EventWriter,
+        query::With,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    log::info,
+    math::{Affine3A, Mat3, Vec2, Vec3},
+    render::{camera::Camera, view::InheritedVisibility},
+    transform::components::GlobalTransform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::capture::maps::{GeometryMaps, write_pfm, write_png};
+use crate::mesh::face_tree::{FaceSurface, FaceTreeCache, RayHit};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+
+// Exports the current view's linear depth and world-space normals as images
+// for external pipelines. Maps are ray cast against the cgar geometry, so
+// they hold exact per-face values rather than rasterized ones.
+#[derive(Resource)]
+pub struct MapExport {
+    // Output size as a fraction of the viewport's physical size
+    pub scale: f32,
+    pub depth: bool,
+    pub normals: bool,
+    pub requested: bool,
+    // Number of views exported so far, used to name the files
+    pub exported: usize,
+    pub status: Option<String>,
+}
+
+impl Default for MapExport {
+    fn default() -> Self {
+        Self {
+            scale: 0.5,
+            depth: true,
+            normals: true,
+            requested: false,
+            exported: 0,
+            status: None,
+        }
+    }
+}
+
+struct MapTarget<'a> {
+    to_local: Affine3A,
+    to_world: Affine3A,
+    // Inverse transpose of the linear part, for normals
+    normal_matrix: Mat3,
+    topology: MeshTopology,
+    mesh: &'a CgarMesh<CgarF64, 3>,
+    surface: &'a FaceSurface,
+}
+
+fn render_maps(
+    camera: &Camera,
+    camera_global: &GlobalTransform,
+    viewport: Vec2,
+    maps: &mut GeometryMaps,
+    targets: &[MapTarget],
+) {
+    let eye = camera_global.translation();
+    let forward = camera_global.forward().as_vec3();
+    let size = Vec2::new(maps.width as f32, maps.height as f32);
+    for y in 0..maps.height {
+        for x in 0..maps.width {
+            let pixel = (Vec2::new(x as f32, y as f32) + 0.5) / size * viewport;
+            let Ok(ray) = camera.viewport_to_world(camera_global, pixel) else {
+                continue;
+            };
+            let mut nearest: Option<(f32, Vec3)> = None;
+            for target in targets {
+                let origin = target.to_local.transform_point3(ray.origin).as_dvec3();
+                let direction = target
+                    .to_local
+                    .transform_vector3(ray.direction.as_vec3())
+                    .as_dvec3();
+                let Some(RayHit { face, t, .. }) =
+                    target
+                        .surface
+                        .cast_ray(target.mesh, origin, direction, None, |_| true)
+                else {
+                    continue;
+                };
+                let hit = target
+                    .to_world
+                    .transform_point3((origin + direction * t).as_vec3());
+                let depth = (hit - eye).dot(forward);
+                if nearest.is_some_and(|(nearest, _)| nearest <= depth) {
+                    continue;
+                }
+                let normal = target.normal_matrix * target.topology.face_normal(face).as_vec3();
+                nearest = Some((depth, normal.normalize_or_zero()));
+            }
+            if let Some((depth, normal)) = nearest {
+                let i = (y * maps.width + x) as usize;
+                maps.depth[i] = depth;
+                maps.normals[i] = normal;
+            }
+        }
+    }
+}
+
+fn save_maps(maps: &GeometryMaps, stem: &str, depth: bool, normals: bool) -> Result<(), String> {
+    let (width, height) = (maps.width, maps.height);
+    let error =
+        |path: &PathBuf, err: String| format!("Failed to write {}: {}", path.display(), err);
+    if depth {
+        let path = PathBuf::from(format!("{}_depth.png", stem));
+        write_png(&path, width, height, maps.depth_rgba()).map_err(|err| error(&path, err))?;
+        let path = PathBuf::from(format!("{}_depth.pfm", stem));
+        write_pfm(&path, width, height, 1, &maps.depth)
+            .map_err(|err| error(&path, err.to_string()))?;
+    }
+    if normals {
+        let path = PathBuf::from(format!("{}_normals.png", stem));
+        write_png(&path, width, height, maps.normals_rgba()).map_err(|err| error(&path, err))?;
+        let values: Vec<f32> = maps.normals.iter().flat_map(|n| n.to_array()).collect();
+        let path = PathBuf::from(format!("{}_normals.pfm", stem));
+        write_pfm(&path, width, height, 3, &values).map_err(|err| error(&path, err.to_string()))?;
+    }
+    Ok(())
+}
+
+pub fn run_map_export(
+    mut export: ResMut<MapExport>,
+    mut notices: EventWriter<Notify>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mesh_query: Query<(
+        &GlobalTransform,
+        &CgarMeshData,
+        &FaceTreeCache,
+        &InheritedVisibility,
+    )>,
+) {
+    if !export.requested {
+        return;
+    }
+    let Ok((camera, camera_global)) = camera_query.single() else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_size() else {
+        return;
+    };
+    export.requested = false;
+
+    let scale = camera.target_scaling_factor().unwrap_or(1.0) * export.scale;
+    let size = (viewport * scale).round().max(Vec2::ONE);
+    let mut maps = GeometryMaps::new(size.x as u32, size.y as u32);
+    let targets: Vec<MapTarget> = mesh_query
+        .iter()
+        .filter(|(.., visibility)| visibility.get())
+        .map(|(mesh_global, cgar_data, cache, _)| {
+            let to_world = mesh_global.affine();
+            MapTarget {
+                to_local: to_world.inverse(),
+                to_world,
+                normal_matrix: Mat3::from(to_world.matrix3).inverse().transpose(),
+                topology: MeshTopology::from_cgar(&cgar_data.0),
+                mesh: &cgar_data.0,
+                surface: &cache.0,
+            }
+        })
+        .collect();
+    render_maps(camera, camera_global, viewport, &mut maps, &targets);
+
+    export.exported += 1;
+    let stem = format!("view_{:03}", export.exported);
+    let status = match save_maps(&maps, &stem, export.depth, export.normals) {
+        Ok(()) => {
+            let range = maps
+                .depth_range()
+                .map(|(near, far)| format!(", depth {:.6} to {:.6}", near, far))
+                .unwrap_or_default();
+            let status = format!(
+                "Exported {}x{} maps as {}_*{}",
+                maps.width, maps.height, stem, range
+            );
+            info!("{} ({} pixels hit)", status, maps.hits());
+            notices.write(Notify::info(status.clone()));
+            status
+        }
+        Err(err) => {
+            notices.write(Notify::error(err.clone()));
+            err
+        }
+    };
+    export.status = Some(status);
+}
+
+pub fn map_export_panel(mut contexts: EguiContexts, mut export: ResMut<MapExport>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Depth / Normal Maps")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut scale = export.scale;
+            ui.add(egui::Slider::new(&mut scale, 0.1..=2.0).text("Resolution scale"));
+            if scale != export.scale {
+                export.scale = scale;
+            }
+            let mut depth = export.depth;
+            let mut normals = export.normals;
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut depth, "Linear depth");
+                ui.checkbox(&mut normals, "World normals");
+            });
+            if depth != export.depth || normals != export.normals {
+                export.depth = depth;
+                export.normals = normals;
+            }
+            #[cfg(feature = "native")]
+            if ui
+                .add_enabled(depth || normals, egui::Button::new("Export view"))
+                .clicked()
+            {
+                export.requested = true;
+            }
+            ui.label("Writes PNG previews and full-precision PFM files");
+            if let Some(status) = &export.status {
+                ui.separator();
+                ui.label(status);
+            }
+        });
+}
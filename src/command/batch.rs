@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+
+use bevy::{
+    app::AppExit,
+    ecs::{
+        event::EventWriter,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    log::{error, info},
+    render::camera::Camera,
+};
+
+use crate::command::systems::CommandLine;
+use crate::utils::cli::CliOptions;
+
+// Command-line commands read from the file given with `--run`, submitted one
+// per frame so each sees the previous one's meshes. The app exits once the
+// script ends, with a failure code at the first command that fails.
+#[derive(Resource, Default)]
+pub struct BatchScript {
+    pub path: String,
+    // Remaining commands with their 1-based line numbers
+    lines: VecDeque<(usize, String)>,
+    // Line of the command submitted last frame
+    running: Option<usize>,
+    // Set when the script couldn't be read
+    error: Option<String>,
+    active: bool,
+}
+
+impl BatchScript {
+    pub fn from_options(options: &CliOptions) -> Self {
+        let Some(path) = options.run_script.clone() else {
+            return Self::default();
+        };
+        let (lines, error) = match std::fs::read_to_string(&path) {
+            Ok(text) => (parse_script(&text), None),
+            Err(err) => (VecDeque::new(), Some(format!("{}: {}", path, err))),
+        };
+        Self {
+            path,
+            lines,
+            running: None,
+            error,
+            active: true,
+        }
+    }
+}
+
+// Non-empty lines, without `#` comments
+fn parse_script(text: &str) -> VecDeque<(usize, String)> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.split_once('#').map_or(line, |(code, _)| code).trim();
+            (!line.is_empty()).then(|| (i + 1, line.to_string()))
+        })
+        .collect()
+}
+
+pub fn run_batch_script(
+    mut script: ResMut<BatchScript>,
+    mut command_line: ResMut<CommandLine>,
+    camera_query: Query<&Camera>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if !script.active {
+        return;
+    }
+    // Screenshots need the render target, so wait until it is set up
+    if camera_query
+        .iter()
+        .all(|camera| camera.logical_viewport_size().is_none())
+    {
+        return;
+    }
+    if let Some(err) = script.error.take() {
+        error!("Batch script failed to load: {}", err);
+        script.active = false;
+        exit.write(AppExit::error());
+        return;
+    }
+    if command_line.pending.is_some() || command_line.captures.load(Ordering::Relaxed) > 0 {
+        return;
+    }
+
+    // `run_commands` logs each command it ran as the newest history entry
+    if let Some(line) = script.running.take() {
+        match command_line.history.last().map(|(_, outcome)| outcome) {
+            Some(Ok(message)) => info!("{}:{}: {}", script.path, line, message),
+            Some(Err(err)) => {
+                error!("{}:{}: {}", script.path, line, err);
+                script.active = false;
+                exit.write(AppExit::error());
+                return;
+            }
+            None => {}
+        }
+    }
+    let Some((line, command)) = script.lines.pop_front() else {
+        info!("Batch script {} finished", script.path);
+        script.active = false;
+        exit.write(AppExit::Success);
+        return;
+    };
+    script.running = Some(line);
+    command_line.pending = Some(command);
+}
@@ -0,0 +1,476 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+// Reduced fraction with a positive denominator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    pub num: i128,
+    pub den: i128,
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let g = gcd(num, den).max(1);
+        let sign = if den < 0 { -1 } else { 1 };
+        Some(Self {
+            num: num.checked_div(g)?.checked_mul(sign)?,
+            den: den.checked_div(g)?.checked_mul(sign)?,
+        })
+    }
+
+    pub fn integer(n: i128) -> Self {
+        Self { num: n, den: 1 }
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    fn add(self, other: Self) -> Option<Self> {
+        let num = self
+            .num
+            .checked_mul(other.den)?
+            .checked_add(other.num.checked_mul(self.den)?)?;
+        Self::new(num, self.den.checked_mul(other.den)?)
+    }
+
+    fn mul(self, other: Self) -> Option<Self> {
+        Self::new(
+            self.num.checked_mul(other.num)?,
+            self.den.checked_mul(other.den)?,
+        )
+    }
+
+    fn recip(self) -> Option<Self> {
+        Self::new(self.den, self.num)
+    }
+
+    fn pow(self, exponent: i128) -> Option<Self> {
+        // Anything bigger overflows unless the base is 0 or ±1; leave those to floats
+        if exponent.unsigned_abs() > 512 {
+            return None;
+        }
+        let base = if exponent < 0 { self.recip()? } else { self };
+        let mut result = Self::integer(1);
+        for _ in 0..exponent.unsigned_abs() {
+            result = result.mul(base)?;
+        }
+        Some(result)
+    }
+
+    fn sqrt(self) -> Option<Self> {
+        let root = |n: i128| {
+            let r = (n as f64).sqrt().round() as i128;
+            (n >= 0 && r.checked_mul(r) == Some(n)).then_some(r)
+        };
+        Self::new(root(self.num)?, root(self.den)?)
+    }
+}
+
+// Result of evaluating an expression: exact while every step could be done
+// in rationals, approximate once an irrational step or overflow was hit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Exact(Rational),
+    Approx(f64),
+}
+
+impl Number {
+    pub fn to_f64(self) -> f64 {
+        match self {
+            Number::Exact(r) => r.to_f64(),
+            Number::Approx(x) => x,
+        }
+    }
+
+    pub fn is_exact(self) -> bool {
+        matches!(self, Number::Exact(_))
+    }
+
+    fn exact_or(
+        self,
+        other: Number,
+        exact: impl Fn(Rational, Rational) -> Option<Rational>,
+        approx: impl Fn(f64, f64) -> f64,
+    ) -> Number {
+        match (self, other) {
+            (Number::Exact(a), Number::Exact(b)) => exact(a, b)
+                .map(Number::Exact)
+                .unwrap_or_else(|| Number::Approx(approx(a.to_f64(), b.to_f64()))),
+            _ => Number::Approx(approx(self.to_f64(), other.to_f64())),
+        }
+    }
+
+    fn add(self, other: Number) -> Number {
+        self.exact_or(other, Rational::add, |a, b| a + b)
+    }
+
+    fn sub(self, other: Number) -> Number {
+        self.add(other.neg())
+    }
+
+    fn mul(self, other: Number) -> Number {
+        self.exact_or(other, Rational::mul, |a, b| a * b)
+    }
+
+    fn div(self, other: Number) -> Result<Number, String> {
+        if other.to_f64() == 0.0 {
+            return Err("division by zero".to_string());
+        }
+        Ok(self.exact_or(other, |a, b| a.mul(b.recip()?), |a, b| a / b))
+    }
+
+    fn neg(self) -> Number {
+        match self {
+            Number::Exact(r) => Number::Exact(Rational {
+                num: -r.num,
+                den: r.den,
+            }),
+            Number::Approx(x) => Number::Approx(-x),
+        }
+    }
+
+    fn pow(self, exponent: Number) -> Number {
+        self.exact_or(
+            exponent,
+            |base, exponent| {
+                if exponent.den == 1 {
+                    base.pow(exponent.num)
+                } else {
+                    None
+                }
+            },
+            f64::powf,
+        )
+    }
+
+    fn sqrt(self) -> Number {
+        match self {
+            Number::Exact(r) => r
+                .sqrt()
+                .map(Number::Exact)
+                .unwrap_or_else(|| Number::Approx(r.to_f64().sqrt())),
+            Number::Approx(x) => Number::Approx(x.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::Exact(r) if r.den == 1 => write!(f, "{}", r.num),
+            Number::Exact(r) => write!(f, "{}/{} (= {})", r.num, r.den, r.to_f64()),
+            Number::Approx(x) => write!(f, "≈ {}", x),
+        }
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LengthUnit {
+    #[default]
+    Millimeter,
+    Centimeter,
+    Meter,
+    Inch,
+    Foot,
+}
+
+impl LengthUnit {
+    pub const ALL: [LengthUnit; 5] = [
+        LengthUnit::Millimeter,
+        LengthUnit::Centimeter,
+        LengthUnit::Meter,
+        LengthUnit::Inch,
+        LengthUnit::Foot,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            LengthUnit::Millimeter => "mm",
+            LengthUnit::Centimeter => "cm",
+            LengthUnit::Meter => "m",
+            LengthUnit::Inch => "in",
+            LengthUnit::Foot => "ft",
+        }
+    }
+
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|unit| unit.symbol() == symbol)
+    }
+
+    // Exact length in meters
+    fn meters(self) -> Rational {
+        let (num, den) = match self {
+            LengthUnit::Millimeter => (1, 1000),
+            LengthUnit::Centimeter => (1, 100),
+            LengthUnit::Meter => (1, 1),
+            LengthUnit::Inch => (127, 5000),
+            LengthUnit::Foot => (381, 1250),
+        };
+        Rational { num, den }
+    }
+
+    // Factor turning lengths in `self` into lengths in `model`
+    fn factor(self, model: LengthUnit) -> Number {
+        Number::Exact(self.meters()).exact_or(
+            Number::Exact(model.meters()),
+            |a, b| a.mul(b.recip()?),
+            |a, b| a / b,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Number),
+    Ident(String),
+    Symbol(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "number {}", n.to_f64()),
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Symbol(c) => write!(f, "'{}'", c),
+        }
+    }
+}
+
+// Decimal literals become exact fractions, so `0.1` is 1/10 rather than the
+// nearest double
+fn parse_literal(text: &str) -> Result<Number, String> {
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (
+            mantissa,
+            exponent
+                .parse::<i32>()
+                .map_err(|_| format!("bad exponent in {}", text))?,
+        ),
+        None => (text, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{}{}", whole, fraction);
+    let exponent = exponent - fraction.len() as i32;
+    let exact = digits.parse::<i128>().ok().and_then(|n| {
+        let scale = Rational::integer(10).pow(exponent as i128)?;
+        Rational::integer(n).mul(scale)
+    });
+    match exact {
+        Some(r) => Ok(Number::Exact(r)),
+        None => text
+            .parse::<f64>()
+            .map(Number::Approx)
+            .map_err(|_| format!("bad number {}", text)),
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // Exponent, only when digits follow so `2e` stays a number and a name
+            let sign = chars.get(i + 1).is_some_and(|c| *c == '+' || *c == '-') as usize;
+            if chars.get(i).is_some_and(|c| *c == 'e' || *c == 'E')
+                && chars.get(i + 1 + sign).is_some_and(char::is_ascii_digit)
+            {
+                i += 1 + sign;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let literal: String = chars[start..i].iter().collect();
+            tokens.push(Token::Number(parse_literal(&literal)?));
+        } else if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_alphanumeric() {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if "+-*/^()".contains(c) {
+            tokens.push(Token::Symbol(c));
+            i += 1;
+        } else {
+            return Err(format!("unexpected '{}'", c));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    at: usize,
+    model_unit: LengthUnit,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.at)
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        let found = self.peek() == Some(&Token::Symbol(symbol));
+        self.at += found as usize;
+        found
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Number, String> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value = value.add(self.term()?);
+            } else if self.eat('-') {
+                value = value.sub(self.term()?);
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Number, String> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat('*') {
+                value = value.mul(self.unary()?);
+            } else if self.eat('/') {
+                value = value.div(self.unary()?)?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    // unary := '-' unary | power
+    fn unary(&mut self) -> Result<Number, String> {
+        if self.eat('-') {
+            return Ok(self.unary()?.neg());
+        }
+        self.eat('+');
+        self.power()
+    }
+
+    // power := atom ('^' unary)?
+    fn power(&mut self) -> Result<Number, String> {
+        let base = self.atom()?;
+        if self.eat('^') {
+            return Ok(base.pow(self.unary()?));
+        }
+        Ok(base)
+    }
+
+    // atom := (number | '(' expr ')' | function '(' expr ')' | constant) unit?
+    fn atom(&mut self) -> Result<Number, String> {
+        let value = match self.tokens.get(self.at).cloned() {
+            Some(Token::Number(n)) => {
+                self.at += 1;
+                n
+            }
+            Some(Token::Symbol('(')) => {
+                self.at += 1;
+                let value = self.expr()?;
+                if !self.eat(')') {
+                    return Err("missing ')'".to_string());
+                }
+                value
+            }
+            Some(Token::Ident(name)) => {
+                self.at += 1;
+                self.named(&name)?
+            }
+            Some(token) => return Err(format!("unexpected {}", token)),
+            None => return Err("expression ends early".to_string()),
+        };
+        Ok(match self.peek() {
+            Some(Token::Ident(name)) => match LengthUnit::from_symbol(name) {
+                Some(unit) => {
+                    self.at += 1;
+                    value.mul(unit.factor(self.model_unit))
+                }
+                None => value,
+            },
+            _ => value,
+        })
+    }
+
+    fn named(&mut self, name: &str) -> Result<Number, String> {
+        if name == "pi" {
+            return Ok(Number::Approx(std::f64::consts::PI));
+        }
+        if !self.eat('(') {
+            return Err(format!("unknown name {}", name));
+        }
+        let argument = self.expr()?;
+        if !self.eat(')') {
+            return Err("missing ')'".to_string());
+        }
+        // Trigonometry takes degrees, matching the rest of the UI
+        let approx = |f: fn(f64) -> f64| Number::Approx(f(argument.to_f64()));
+        Ok(match name {
+            "sqrt" => argument.sqrt(),
+            "abs" if argument.to_f64() < 0.0 => argument.neg(),
+            "abs" => argument,
+            "sin" => approx(|x| x.to_radians().sin()),
+            "cos" => approx(|x| x.to_radians().cos()),
+            "tan" => approx(|x| x.to_radians().tan()),
+            _ => return Err(format!("unknown function {}", name)),
+        })
+    }
+}
+
+// Evaluates arithmetic with + - * / ^, parentheses, sqrt/abs/sin/cos/tan,
+// `pi` and length units (mm, cm, m, in, ft) converted into `model_unit`
+pub fn evaluate(text: &str, model_unit: LengthUnit) -> Result<Number, String> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        at: 0,
+        model_unit,
+    };
+    let value = parser.expr()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(token) => Err(format!("unexpected {}", token)),
+    }
+}
@@ -0,0 +1,527 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        entity::Entity,
+        event::EventWriter,
+        name::Name,
+        observer::Trigger,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::DVec3,
+    render::{
+        mesh::{Mesh, Mesh3d},
+        view::screenshot::{Screenshot, ScreenshotCaptured, save_to_disk},
+    },
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::background::systems::{JobOutcome, OperationProgress};
+use crate::camera::components::CgarMeshData;
+use crate::command::expr::{LengthUnit, Number, evaluate};
+use crate::edit::ops::split_edge;
+use crate::import::systems::{ImportSettings, is_obj_path};
+use crate::mesh::attributes::MeshAttributes;
+use crate::mesh::conversion::{build_cgar_mesh, set_vertex_position};
+use crate::mesh::export::{write_3mf, write_obj_faces};
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::obj::load_obj_file;
+use crate::mesh::setup::{DefaultMeshMaterial, MeshSource, spawn_loaded_mesh};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::outliner::export::SceneExport;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+use crate::stats::systems::MeshFingerprints;
+use crate::stepper::ops::{StepOperation, run_to_end};
+
+// Commands kept in the panel's history
+const HISTORY_LEN: usize = 100;
+
+const HELP: &str = "= <expr>  evaluate an expression
+move x=<expr> y=<expr> z=<expr>  offset the selection (or: move <x>, <y>, <z>)
+place x=<expr> ...  set coordinates of the selection
+scale <expr>  scale the selection about its centroid
+split <expr>  split the selected edge at a parameter in [0, 1]
+units mm|cm|m|in|ft  unit of the model's coordinates
+load <path.obj>  load a mesh and make it the target
+decimate <faces>  collapse short edges of the target mesh down to a face count
+fill-holes <edges>  fill the target mesh's holes of at most that many edges
+export <path.obj|.3mf>  write the target mesh; 3MF keeps overlay colors and face attributes
+export-scene <path.glb|.gltf>  write every group and mesh
+fingerprint  hash the target mesh's geometry and connectivity (see Mesh Stats)
+screenshot <path.png>  save the window";
+
+// One line of a CAD-style command prompt. Numbers are expressions with
+// optional length units, kept as exact fractions until they are applied.
+#[derive(Resource, Default)]
+pub struct CommandLine {
+    pub input: String,
+    // Unit the mesh coordinates are in; typed lengths are converted to it
+    pub model_unit: LengthUnit,
+    pub pending: Option<String>,
+    pub history: Vec<(String, Result<String, String>)>,
+    // Screenshots asked for and not written yet
+    pub captures: Arc<AtomicUsize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Eval(Number),
+    Units(LengthUnit),
+    Move([Option<Number>; 3]),
+    Place([Option<Number>; 3]),
+    Scale(Number),
+    Split(Number),
+    Load(PathBuf),
+    Decimate(usize),
+    FillHoles(usize),
+    Export(PathBuf),
+    ExportScene(PathBuf),
+    Screenshot(PathBuf),
+    Fingerprint,
+    Help,
+}
+
+fn path_arg(args: &str) -> Result<PathBuf, String> {
+    let path = args.trim();
+    if path.is_empty() {
+        return Err("expected a file path".to_string());
+    }
+    Ok(PathBuf::from(path))
+}
+
+fn count_arg(args: &str) -> Result<usize, String> {
+    args.trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| "expected a positive whole number".to_string())
+}
+
+// `x=<expr> z=<expr>` in any order, or `<x>, <y>, <z>` by position
+fn parse_axes(args: &str, unit: LengthUnit) -> Result<[Option<Number>; 3], String> {
+    let mut axes = [None; 3];
+    if !args.contains('=') {
+        let values: Vec<&str> = args.split(',').collect();
+        if values.len() != 3 {
+            return Err("expected x=..., y=..., z=... or three comma-separated values".to_string());
+        }
+        for (axis, value) in axes.iter_mut().zip(values) {
+            *axis = Some(evaluate(value, unit)?);
+        }
+        return Ok(axes);
+    }
+
+    // Split before each `x=`, `y=` or `z=` that starts a word
+    let chars: Vec<char> = args.chars().collect();
+    let is_key = |i: usize| {
+        "xyz".contains(chars[i])
+            && (i == 0 || chars[i - 1].is_whitespace())
+            && chars[i + 1..]
+                .iter()
+                .find(|c| !c.is_whitespace())
+                .is_some_and(|&c| c == '=')
+    };
+    let starts: Vec<usize> = (0..chars.len()).filter(|&i| is_key(i)).collect();
+    if starts
+        .first()
+        .is_none_or(|&start| chars[..start].iter().any(|c| !c.is_whitespace()))
+    {
+        return Err("expected x=, y= or z=".to_string());
+    }
+    for (k, &start) in starts.iter().enumerate() {
+        let end = starts.get(k + 1).copied().unwrap_or(chars.len());
+        let part: String = chars[start..end].iter().collect();
+        let (key, value) = part.split_once('=').expect("keys are followed by '='");
+        let axis = match key.trim() {
+            "x" => 0,
+            "y" => 1,
+            _ => 2,
+        };
+        if axes[axis].is_some() {
+            return Err(format!("{} given twice", key.trim()));
+        }
+        axes[axis] = Some(evaluate(value, unit)?);
+    }
+    Ok(axes)
+}
+
+fn parse_command(line: &str, unit: LengthUnit) -> Result<Command, String> {
+    let line = line.trim();
+    if let Some(expr) = line.strip_prefix('=') {
+        return evaluate(expr, unit).map(Command::Eval);
+    }
+    let (verb, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    match verb {
+        "help" | "?" => Ok(Command::Help),
+        "units" => LengthUnit::from_symbol(args.trim())
+            .map(Command::Units)
+            .ok_or_else(|| "units takes mm, cm, m, in or ft".to_string()),
+        "move" => parse_axes(args, unit).map(Command::Move),
+        "place" => parse_axes(args, unit).map(Command::Place),
+        "scale" => evaluate(args, unit).map(Command::Scale),
+        "split" => evaluate(args, unit).map(Command::Split),
+        "eval" => evaluate(args, unit).map(Command::Eval),
+        "load" => path_arg(args).map(Command::Load),
+        "decimate" => count_arg(args).map(Command::Decimate),
+        "fill-holes" => count_arg(args).map(Command::FillHoles),
+        "export" => path_arg(args).map(Command::Export),
+        "export-scene" => path_arg(args).map(Command::ExportScene),
+        "screenshot" => path_arg(args).map(Command::Screenshot),
+        "fingerprint" => Ok(Command::Fingerprint),
+        _ => Err(format!("unknown command '{}' (try help)", verb)),
+    }
+}
+
+fn describe(axes: &[Option<Number>; 3]) -> String {
+    axes.iter()
+        .zip(["x", "y", "z"])
+        .filter_map(|(value, name)| value.map(|value| format!("{}={}", name, value)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn exactness(values: impl IntoIterator<Item = Number>) -> &'static str {
+    if values.into_iter().all(Number::is_exact) {
+        "exact"
+    } else {
+        "rounded"
+    }
+}
+
+pub fn run_commands(
+    mut commands: Commands,
+    mut command_line: ResMut<CommandLine>,
+    mut selection: ResMut<SelectionSet>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut notices: EventWriter<Notify>,
+    material: Option<Res<DefaultMeshMaterial>>,
+    import_settings: Res<ImportSettings>,
+    mut scene_export: ResMut<SceneExport>,
+    mut fingerprints: ResMut<MeshFingerprints>,
+    mut mesh_query: Query<(
+        Entity,
+        &Mesh3d,
+        &mut CgarMeshData,
+        Option<&FaceColorOverlay>,
+        Option<&FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+        Option<&Name>,
+        Option<&MeshAttributes>,
+    )>,
+) {
+    let Some(line) = command_line.pending.take() else {
+        return;
+    };
+    let unit = command_line.model_unit;
+    let outcome = parse_command(&line, unit).and_then(|command| match command {
+        Command::Help => Ok(HELP.to_string()),
+        Command::Eval(value) => Ok(value.to_string()),
+        Command::Units(unit) => {
+            command_line.model_unit = unit;
+            Ok(format!("Model coordinates are in {}", unit.symbol()))
+        }
+        Command::Load(path) => {
+            let material = material.as_ref().ok_or("the scene isn't set up yet")?;
+            if !is_obj_path(&path) {
+                return Err(format!("{}: load takes .obj files", path.display()));
+            }
+            let loaded = load_obj_file(&path, &import_settings)?;
+            let name = path.file_stem().map_or_else(
+                || "import".to_string(),
+                |stem| stem.to_string_lossy().into_owned(),
+            );
+            let entity = spawn_loaded_mesh(
+                &mut commands,
+                &mut meshes,
+                material,
+                name.clone(),
+                loaded,
+                &mut notices,
+            )
+            .ok_or_else(|| format!("{} could not be converted", name))?;
+            commands.entity(entity).insert(MeshSource(path));
+            // Later commands act on the new mesh
+            selection.clear();
+            selection.mesh = Some(entity);
+            Ok(format!("Loaded {}", name))
+        }
+        Command::ExportScene(path) => {
+            let message = format!("Exporting the scene to {}", path.display());
+            scene_export.requested = Some(path);
+            Ok(message)
+        }
+        Command::Screenshot(path) => {
+            let captures = command_line.captures.clone();
+            captures.fetch_add(1, Ordering::Relaxed);
+            commands
+                .spawn(Screenshot::primary_window())
+                .observe(save_to_disk(path.clone()))
+                .observe(move |_: Trigger<ScreenshotCaptured>| {
+                    captures.fetch_sub(1, Ordering::Relaxed);
+                });
+            Ok(format!("Saving a screenshot to {}", path.display()))
+        }
+        Command::Fingerprint => {
+            let (entity, _, cgar_data, ..) = selection
+                .mesh
+                .and_then(|e| mesh_query.get(e).ok())
+                .or_else(|| mesh_query.iter().next())
+                .ok_or("no mesh loaded")?;
+            Ok(fingerprints.compute(entity, cgar_data).describe())
+        }
+        Command::Decimate(..) | Command::FillHoles(..) | Command::Export(..) => {
+            let entity = selection
+                .mesh
+                .filter(|e| mesh_query.contains(*e))
+                .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity))
+                .ok_or("no mesh loaded")?;
+            let (
+                _,
+                mesh_handle,
+                mut cgar_data,
+                overlay,
+                features,
+                normals,
+                settings,
+                name,
+                attributes,
+            ) = mesh_query
+                .get_mut(entity)
+                .map_err(|_| "the target mesh is gone".to_string())?;
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            let (operation, limit) = match command {
+                Command::Export(path) => {
+                    let faces: Vec<usize> = topology.live_faces().map(|(f, _)| f).collect();
+                    let is_3mf = path
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("3mf"));
+                    if is_3mf {
+                        let name = name.map_or_else(|| "mesh".to_string(), |name| name.to_string());
+                        write_3mf(
+                            &path,
+                            &name,
+                            &topology,
+                            overlay.map(|overlay| overlay.colors.as_slice()),
+                            attributes,
+                            unit,
+                        )
+                    } else {
+                        write_obj_faces(&path, &topology, &faces)
+                    }
+                    .map_err(|e| format!("{}: {}", path.display(), e))?;
+                    return Ok(format!(
+                        "Exported {} faces to {}",
+                        faces.len(),
+                        path.display()
+                    ));
+                }
+                Command::Decimate(faces) => (StepOperation::Decimate, faces),
+                Command::FillHoles(edges) => (StepOperation::FillHoles, edges),
+                _ => unreachable!("other commands are handled above"),
+            };
+            let soup = TriangleSoup::from_topology(&topology);
+            let outcome = run_to_end(
+                &soup,
+                operation,
+                features,
+                &BTreeSet::new(),
+                None,
+                limit,
+                None,
+                &OperationProgress::default(),
+            );
+            let (soup, summary) = match outcome {
+                JobOutcome::Done(soup, summary) => (soup, summary),
+                JobOutcome::Cancelled => return Err("cancelled".to_string()),
+                JobOutcome::Failed(err) => return Err(err),
+            };
+            cgar_data.0 = build_cgar_mesh(&soup.positions, soup.triangles.iter().copied());
+            meshes.insert(
+                &mesh_handle.0,
+                render_mesh(&cgar_data.0, None, features, normals, settings),
+            );
+            commands.entity(entity).remove::<FaceColorOverlay>();
+            // Face ids no longer mean the same thing, but the mesh stays the target
+            selection.clear();
+            selection.mesh = Some(entity);
+            Ok(format!("{}: {}", operation.label(), summary))
+        }
+        Command::Move(..) | Command::Place(..) | Command::Scale(..) | Command::Split(..) => {
+            let entity = selection
+                .mesh
+                .filter(|_| !selection.is_empty())
+                .ok_or("nothing selected")?;
+            let (_, mesh_handle, mut cgar_data, overlay, features, normals, settings, ..) =
+                mesh_query
+                    .get_mut(entity)
+                    .map_err(|_| "the selected mesh is gone".to_string())?;
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+
+            if let Command::Split(t) = command {
+                let [edge] = selection.edges.iter().copied().collect::<Vec<_>>()[..] else {
+                    return Err("select exactly one edge to split".to_string());
+                };
+                if !(0.0..=1.0).contains(&t.to_f64()) {
+                    return Err(format!("split parameter {} is outside [0, 1]", t));
+                }
+                let soup = split_edge(&topology, edge, t.to_f64());
+                cgar_data.0 = build_cgar_mesh(&soup.positions, soup.triangles.iter().copied());
+                meshes.insert(
+                    &mesh_handle.0,
+                    render_mesh(&cgar_data.0, None, features, normals, settings),
+                );
+                commands.entity(entity).remove::<FaceColorOverlay>();
+                selection.clear();
+                return Ok(format!(
+                    "Split edge ({}, {}) at {} ({})",
+                    edge.0,
+                    edge.1,
+                    t,
+                    exactness([t])
+                ));
+            }
+
+            let vertices = selection.touched_vertices(&topology);
+            let centroid = vertices
+                .iter()
+                .map(|&v| topology.positions[v])
+                .sum::<DVec3>()
+                / vertices.len().max(1) as f64;
+            let moved = |p: DVec3| -> DVec3 {
+                match command {
+                    Command::Move(axes) => {
+                        p + DVec3::from_array(axes.map(|a| a.map_or(0.0, Number::to_f64)))
+                    }
+                    Command::Place(axes) => {
+                        let mut p = p.to_array();
+                        for (coordinate, value) in p.iter_mut().zip(axes) {
+                            if let Some(value) = value {
+                                *coordinate = value.to_f64();
+                            }
+                        }
+                        DVec3::from_array(p)
+                    }
+                    Command::Scale(factor) => centroid + (p - centroid) * factor.to_f64(),
+                    _ => p,
+                }
+            };
+            for &v in &vertices {
+                set_vertex_position(&mut cgar_data.0, v, moved(topology.positions[v]));
+            }
+            meshes.insert(
+                &mesh_handle.0,
+                render_mesh(&cgar_data.0, overlay, features, normals, settings),
+            );
+            Ok(match command {
+                Command::Move(axes) => format!(
+                    "Moved {} vertices by {} ({})",
+                    vertices.len(),
+                    describe(&axes),
+                    exactness(axes.into_iter().flatten())
+                ),
+                Command::Place(axes) => format!(
+                    "Placed {} vertices at {} ({})",
+                    vertices.len(),
+                    describe(&axes),
+                    exactness(axes.into_iter().flatten())
+                ),
+                Command::Scale(factor) => format!(
+                    "Scaled {} vertices by {} ({})",
+                    vertices.len(),
+                    factor,
+                    exactness([factor])
+                ),
+                _ => unreachable!("other commands return above"),
+            })
+        }
+    });
+
+    command_line.history.push((line, outcome));
+    let overflow = command_line.history.len().saturating_sub(HISTORY_LEN);
+    command_line.history.drain(..overflow);
+}
+
+pub fn command_line_panel(mut contexts: EguiContexts, mut command_line: ResMut<CommandLine>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Command")
+        .default_open(false)
+        .show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(160.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for (line, outcome) in &command_line.history {
+                        ui.monospace(format!("> {}", line));
+                        match outcome {
+                            Ok(text) => ui.label(text),
+                            Err(err) => ui.colored_label(egui::Color32::LIGHT_RED, err),
+                        };
+                    }
+                });
+
+            let mut input = command_line.input.clone();
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut input)
+                    .hint_text("move x=1/3 z=2mm")
+                    .code_editor()
+                    .desired_width(f32::INFINITY),
+            );
+            let submitted =
+                response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            if submitted && !input.trim().is_empty() {
+                command_line.pending = Some(std::mem::take(&mut input));
+                response.request_focus();
+            }
+            if input != command_line.input {
+                command_line.input = input;
+            }
+
+            let mut unit = command_line.model_unit;
+            egui::ComboBox::from_label("Model units")
+                .selected_text(unit.symbol())
+                .show_ui(ui, |ui| {
+                    for option in LengthUnit::ALL {
+                        ui.selectable_value(&mut unit, option, option.symbol());
+                    }
+                });
+            if unit != command_line.model_unit {
+                command_line.model_unit = unit;
+            }
+            ui.label("Type help for the list of commands");
+        });
+}
@@ -0,0 +1,583 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        name::Name,
+        query::{Or, With},
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+    },
+    input::{ButtonInput, keyboard::KeyCode},
+    math::{DVec3, Vec2, Vec3},
+    picking::{
+        events::{Pointer, Pressed, Released},
+        pointer::{PointerButton, PointerId},
+    },
+    platform::collections::HashMap,
+    render::{
+        camera::Camera,
+        mesh::{Mesh, Mesh3d},
+        view::Visibility,
+    },
+    transform::components::GlobalTransform,
+    window::{PrimaryWindow, Window},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::edit::ops::{
+    MIN_SPLIT_T, delete_faces, flip_edge, split_edge, split_face, subdivide_faces,
+};
+use crate::mesh::collapse::{CollapseOptions, collapse_with_placement};
+use crate::mesh::constraints::{EditConstraints, constrain_placement};
+use crate::mesh::conversion::{build_cgar_mesh, vertex_position};
+use crate::mesh::edge::{MeshLongPressed, PickSettings, cast_pick_ray};
+use crate::mesh::face_tree::{FaceHit, FaceSurface, FaceTreeCache};
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::outliner::systems::MeshGroup;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+use crate::selection::region::EditRegion;
+use crate::tools::systems::Measurement;
+use crate::workspace::systems::Stashed;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeshElement {
+    Vertex(usize),
+    // Undirected (min, max) vertex pair
+    Edge(usize, usize),
+    Face(usize),
+}
+
+impl MeshElement {
+    pub fn label(self) -> String {
+        match self {
+            MeshElement::Vertex(v) => format!("Vertex {}", v),
+            MeshElement::Edge(v0, v1) => format!("Edge ({}, {})", v0, v1),
+            MeshElement::Face(f) => format!("Face {}", f),
+        }
+    }
+
+    // Faces that go away when the element is deleted
+    fn faces(self, topology: &MeshTopology) -> BTreeSet<usize> {
+        match self {
+            MeshElement::Vertex(v) => topology.vertex_faces[v].iter().copied().collect(),
+            MeshElement::Edge(v0, v1) => topology
+                .edge_faces
+                .get(&(v0, v1))
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect(),
+            MeshElement::Face(f) => BTreeSet::from([f]),
+        }
+    }
+
+    // Multi-line description for the clipboard, in mesh-local coordinates
+    fn describe(self, topology: &MeshTopology) -> String {
+        let p = |v: usize| topology.positions[v].to_array();
+        match self {
+            MeshElement::Vertex(v) => format!(
+                "vertex {}\nposition {:?}\nfaces {}",
+                v,
+                p(v),
+                topology.vertex_faces[v].len()
+            ),
+            MeshElement::Edge(v0, v1) => format!(
+                "edge ({}, {})\nfrom {:?}\nto {:?}\nlength {}\nfaces {}",
+                v0,
+                v1,
+                p(v0),
+                p(v1),
+                topology.edge_length((v0, v1)),
+                topology.edge_faces.get(&(v0, v1)).map_or(0, Vec::len)
+            ),
+            MeshElement::Face(f) => {
+                let tri = topology.triangles[f].unwrap_or_default();
+                format!(
+                    "face {}\nvertices {:?}\narea {}\nnormal {:?}",
+                    f,
+                    tri,
+                    topology.face_area(f),
+                    topology.face_normal(f).to_array()
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContextTarget {
+    pub entity: Entity,
+    pub element: MeshElement,
+    // Point under the cursor on the hit face
+    pub hit: FaceHit,
+    // Where the menu opens, in logical pixels
+    pub screen_position: Vec2,
+    pub info: String,
+    // Set until the menu has been drawn once, so the opening click doesn't close it
+    pub just_opened: bool,
+}
+
+// Popup of actions on the element under a right click or long press
+#[derive(Resource, Default)]
+pub struct ContextMenu {
+    pub target: Option<ContextTarget>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextActionKind {
+    Collapse,
+    Flip,
+    Split,
+    Subdivide,
+    Delete,
+    Hide,
+}
+
+impl ContextActionKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ContextActionKind::Collapse => "Collapse",
+            ContextActionKind::Flip => "Flip",
+            ContextActionKind::Split => "Split",
+            ContextActionKind::Subdivide => "Subdivide",
+            ContextActionKind::Delete => "Delete",
+            ContextActionKind::Hide => "Hide mesh",
+        }
+    }
+
+    // Actions offered for an element, in menu order
+    fn for_element(element: MeshElement) -> &'static [ContextActionKind] {
+        match element {
+            MeshElement::Edge(..) => &[
+                ContextActionKind::Collapse,
+                ContextActionKind::Flip,
+                ContextActionKind::Split,
+                ContextActionKind::Delete,
+            ],
+            MeshElement::Face(_) => &[
+                ContextActionKind::Split,
+                ContextActionKind::Subdivide,
+                ContextActionKind::Delete,
+            ],
+            MeshElement::Vertex(_) => &[ContextActionKind::Delete],
+        }
+    }
+}
+
+// Chosen in the menu, applied by `apply_context_actions`
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ContextAction {
+    pub entity: Entity,
+    pub element: MeshElement,
+    pub kind: ContextActionKind,
+    // Mesh-local point the action was picked at, for placing new vertices
+    pub point: Option<DVec3>,
+}
+
+pub fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = ((p - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+// Element under `cursor` (logical pixels): a corner of the hit face when it
+// lies within the pick radius on screen, the edge cgar's ray cast reported,
+// a side within the radius, or the face itself otherwise. Also returns
+// where the ray hit the face.
+pub fn pick_element(
+    (camera, camera_global): (&Camera, &GlobalTransform),
+    mesh_global: &GlobalTransform,
+    cgar_mesh: &CgarMesh<CgarF64, 3>,
+    surface: &FaceSurface,
+    cursor: Vec2,
+    depth_at: Option<Vec3>,
+    settings: &PickSettings,
+) -> Option<(MeshElement, FaceHit)> {
+    let radius_px = settings.radius_px;
+    let cast = cast_pick_ray(
+        (camera, camera_global),
+        mesh_global,
+        cgar_mesh,
+        surface,
+        cursor,
+        depth_at,
+        settings,
+    )?;
+    let hit = FaceHit::from_barycentric(cgar_mesh, cast.face, cast.barycentric, mesh_global)?;
+    let (face, tri) = (hit.face, hit.vertices);
+
+    // Projections are relative to the camera's viewport
+    let cursor = cursor
+        - camera
+            .logical_viewport_rect()
+            .map_or(Vec2::ZERO, |rect| rect.min);
+    let screen = |v: usize| {
+        let world = mesh_global.transform_point(vertex_position(cgar_mesh, v));
+        camera.world_to_viewport(camera_global, world).ok()
+    };
+    let closest = |candidates: Vec<(f32, MeshElement)>| {
+        candidates
+            .into_iter()
+            .filter(|(distance, _)| *distance <= radius_px)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, element)| element)
+    };
+    let vertices = tri
+        .iter()
+        .filter_map(|&v| Some((screen(v)?.distance(cursor), MeshElement::Vertex(v))))
+        .collect();
+    let edges = (0..3)
+        .filter_map(|k| {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            let distance = distance_to_segment(cursor, screen(a)?, screen(b)?);
+            Some((distance, MeshElement::Edge(a.min(b), a.max(b))))
+        })
+        .collect();
+    let element = closest(vertices)
+        .or_else(|| {
+            cast.edge
+                .map(|(a, b, _)| MeshElement::Edge(a.min(b), a.max(b)))
+        })
+        .or_else(|| closest(edges))
+        .unwrap_or(MeshElement::Face(face));
+    Some((element, hit))
+}
+
+// Opens the menu on a right click that didn't move past the click deadzone
+// (right drags pan the camera) or on a long press
+pub fn open_context_menu(
+    mut menu: ResMut<ContextMenu>,
+    mut presses: Local<HashMap<PointerId, Vec2>>,
+    mut press_events: EventReader<Pointer<Pressed>>,
+    mut release_events: EventReader<Pointer<Released>>,
+    mut long_presses: EventReader<MeshLongPressed>,
+    pick_settings: Res<PickSettings>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData, Option<&FaceTreeCache>)>,
+) {
+    for event in press_events
+        .read()
+        .filter(|event| event.button == PointerButton::Secondary)
+    {
+        presses.insert(event.pointer_id, event.pointer_location.position);
+    }
+    let deadzone = pick_settings.click_deadzone(window_query.single().ok());
+    let mut request = None;
+    for event in release_events
+        .read()
+        .filter(|event| event.button == PointerButton::Secondary)
+    {
+        let end = event.pointer_location.position;
+        let moved = presses
+            .remove(&event.pointer_id)
+            .is_none_or(|start| start.distance(end) > deadzone);
+        if !moved {
+            request = Some((event.target, end));
+        }
+    }
+    for event in long_presses.read() {
+        request = Some((event.entity, event.screen_position));
+    }
+
+    let Some((entity, cursor)) = request else {
+        return;
+    };
+    let (Ok(camera), Ok((mesh_global, cgar_data, cache))) =
+        (camera_query.single(), mesh_query.get(entity))
+    else {
+        return;
+    };
+    // The cached tree lags a frame behind mesh edits
+    let built;
+    let surface = match cache {
+        Some(cache) => &cache.0,
+        None => {
+            built = FaceSurface::build(&cgar_data.0);
+            &built
+        }
+    };
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    menu.target = pick_element(
+        camera,
+        mesh_global,
+        &cgar_data.0,
+        surface,
+        cursor,
+        None,
+        &pick_settings,
+    )
+    .map(|(element, hit)| ContextTarget {
+        entity,
+        element,
+        hit,
+        screen_position: cursor,
+        info: format!("{}\n{}", element.describe(&topology), hit.describe()),
+        just_opened: true,
+    });
+}
+
+pub fn context_menu_panel(
+    mut contexts: EguiContexts,
+    kb: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<ContextMenu>,
+    mut measurement: ResMut<Measurement>,
+    mut actions: EventWriter<ContextAction>,
+    mut notices: EventWriter<Notify>,
+    name_query: Query<Option<&Name>, With<CgarMeshData>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let Some(target) = menu.target.clone() else {
+        return;
+    };
+    let Ok(name) = name_query.get(target.entity) else {
+        menu.target = None;
+        return;
+    };
+    if kb.just_pressed(KeyCode::Escape) {
+        menu.target = None;
+        return;
+    }
+    let mesh_label = match name {
+        Some(name) => format!("{} ({})", name, target.entity),
+        None => format!("{}", target.entity),
+    };
+
+    let mut chosen = None;
+    let mut measure_from = false;
+    let mut measure_to = false;
+    let mut copy = false;
+    let response = egui::Area::new(egui::Id::new("mesh_context_menu"))
+        .fixed_pos(egui::pos2(
+            target.screen_position.x,
+            target.screen_position.y,
+        ))
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            egui::Frame::menu(ui.style()).show(ui, |ui| {
+                ui.set_min_width(160.0);
+                ui.strong(target.element.label());
+                ui.label(mesh_label);
+                let [u, v, w] = target.hit.barycentric.to_array();
+                ui.small(format!(
+                    "Face {} at ({:.3}, {:.3}, {:.3})",
+                    target.hit.face, u, v, w
+                ))
+                .on_hover_text(format!("{:?}", target.hit.local.to_array()));
+                ui.separator();
+                for &kind in ContextActionKind::for_element(target.element) {
+                    if ui.button(kind.label()).clicked() {
+                        chosen = Some(kind);
+                    }
+                }
+                ui.separator();
+                measure_from = ui.button("Measure from here").clicked();
+                if measurement.from.is_some() {
+                    measure_to = ui.button("Measure to here").clicked();
+                }
+                copy = ui.button("Copy info").clicked();
+                ui.separator();
+                if ui.button(ContextActionKind::Hide.label()).clicked() {
+                    chosen = Some(ContextActionKind::Hide);
+                }
+            });
+        })
+        .response;
+
+    if copy {
+        ctx.copy_text(target.info.clone());
+    }
+    if measure_from {
+        measurement.from = Some(target.hit.world);
+        measurement.to = None;
+    }
+    if let Some(from) = measurement.from.filter(|_| measure_to) {
+        measurement.to = Some(target.hit.world);
+        notices.write(Notify::info(format!(
+            "Distance: {:.6}",
+            from.distance(target.hit.world)
+        )));
+    }
+    if let Some(kind) = chosen {
+        actions.write(ContextAction {
+            entity: target.entity,
+            element: target.element,
+            kind,
+            point: Some(target.hit.local),
+        });
+    }
+
+    let done = chosen.is_some() || measure_from || measure_to || copy;
+    let dismissed = !target.just_opened && response.clicked_elsewhere();
+    if done || dismissed {
+        menu.target = None;
+    } else if let Some(open) = menu.target.as_mut().filter(|_| target.just_opened) {
+        open.just_opened = false;
+    }
+}
+
+pub fn apply_context_actions(
+    mut commands: Commands,
+    mut actions: EventReader<ContextAction>,
+    mut notices: EventWriter<Notify>,
+    mut selection: ResMut<SelectionSet>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    collapse_options: Res<CollapseOptions>,
+    constraints: Res<EditConstraints>,
+    mut mesh_query: Query<(
+        &Mesh3d,
+        &mut CgarMeshData,
+        &mut Visibility,
+        Option<&FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    for action in actions.read() {
+        let Ok((mesh_handle, mut cgar_data, mut visibility, features, normals, settings)) =
+            mesh_query.get_mut(action.entity)
+        else {
+            continue;
+        };
+        let topology = MeshTopology::from_cgar(&cgar_data.0);
+        // `Ok(None)` means the cgar mesh was edited in place
+        let result: Result<Option<TriangleSoup>, String> = match (action.kind, action.element) {
+            (ContextActionKind::Hide, _) => {
+                *visibility = Visibility::Hidden;
+                notices.write(Notify::info(
+                    "Mesh hidden; press Alt+H to show hidden meshes",
+                ));
+                continue;
+            }
+            (ContextActionKind::Collapse, MeshElement::Edge(v0, v1)) => {
+                if features.is_some_and(|features| !features.allows_collapse(v0, v1)) {
+                    Err("it would break a feature line".to_string())
+                } else {
+                    let picked = Some(&*selection).filter(|s| s.mesh == Some(action.entity));
+                    let locked = constraints.locked(&topology, features, picked);
+                    // The menu has no click point, so "nearer" keeps the second end
+                    constrain_placement(&locked, (v0, v1), collapse_options.placement).and_then(
+                        |placement| {
+                            collapse_with_placement(&mut cgar_data.0, (v0, v1), 1.0, placement)
+                                .map(|_| None)
+                                .map_err(|_| "rejected by the mesh".to_string())
+                        },
+                    )
+                }
+            }
+            (ContextActionKind::Flip, MeshElement::Edge(v0, v1)) => {
+                flip_edge(&topology, (v0, v1)).map(Some)
+            }
+            (ContextActionKind::Split, MeshElement::Edge(v0, v1)) => {
+                // Splits where the edge passes closest to the picked point
+                let t = action.point.map_or(0.5, |point| {
+                    let (a, b) = (topology.positions[v0], topology.positions[v1]);
+                    let t = (point - a).dot(b - a) / (b - a).length_squared();
+                    if t.is_finite() {
+                        t.clamp(MIN_SPLIT_T, 1.0 - MIN_SPLIT_T)
+                    } else {
+                        0.5
+                    }
+                });
+                Ok(Some(split_edge(&topology, (v0, v1), t)))
+            }
+            (ContextActionKind::Split, MeshElement::Face(f)) => {
+                split_face(&topology, f, action.point).map(Some)
+            }
+            (ContextActionKind::Subdivide, MeshElement::Face(f)) => {
+                // A face inside the selection subdivides the whole selected region
+                let faces = Some(&*selection)
+                    .filter(|s| s.mesh == Some(action.entity))
+                    .and_then(|s| EditRegion::from_selection(s, &topology))
+                    .map(|region| region.faces)
+                    .filter(|faces| faces.contains(&f))
+                    .unwrap_or_else(|| BTreeSet::from([f]));
+                Ok(Some(subdivide_faces(&topology, &faces)))
+            }
+            (ContextActionKind::Delete, element) => {
+                Ok(Some(delete_faces(&topology, &element.faces(&topology))))
+            }
+            _ => continue,
+        };
+
+        match result {
+            Ok(soup) => {
+                if let Some(soup) = soup {
+                    cgar_data.0 = build_cgar_mesh(&soup.positions, soup.triangles.iter().copied());
+                }
+                meshes.insert(
+                    &mesh_handle.0,
+                    render_mesh(&cgar_data.0, None, features, normals, settings),
+                );
+                commands.entity(action.entity).remove::<FaceColorOverlay>();
+                // Face ids no longer mean the same thing
+                if selection.mesh == Some(action.entity) {
+                    selection.clear();
+                }
+            }
+            Err(err) => {
+                notices.write(Notify::warning(format!(
+                    "{} {}: {}",
+                    action.kind.label(),
+                    action.element.label(),
+                    err
+                )));
+            }
+        }
+    }
+}
+
+// Alt+H brings back meshes hidden from the context menu or the outliner,
+// along with hidden groups. Other workspaces stay stashed.
+pub fn unhide_meshes(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut mesh_query: Query<
+        &mut Visibility,
+        (Or<(With<CgarMeshData>, With<MeshGroup>)>, Without<Stashed>),
+    >,
+) {
+    let alt = kb.pressed(KeyCode::AltLeft) || kb.pressed(KeyCode::AltRight);
+    if !alt || !kb.just_pressed(KeyCode::KeyH) {
+        return;
+    }
+    for mut visibility in &mut mesh_query {
+        if *visibility == Visibility::Hidden {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
@@ -0,0 +1,377 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashSet;
+
+use bevy::{
+    asset::{Assets, Handle},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        hierarchy::ChildOf,
+        name::Name,
+        query::Without,
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+    },
+    input::{ButtonInput, keyboard::KeyCode},
+    math::{Affine3A, DAffine3, DVec3, Mat3A, Mat4, Vec3, Vec3A},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    picking::Pickable,
+    render::mesh::{Mesh, Mesh3d},
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::camera::components::CgarMeshData;
+use crate::mesh::conversion::{build_cgar_mesh, cgar_to_bevy_mesh};
+use crate::mesh::normals::NormalWeighting;
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+use crate::workspace::systems::Stashed;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateMode {
+    // Shares geometry with the original; edits to either show in both
+    #[default]
+    Linked,
+    // Independent copy of the geometry
+    Copy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MirrorAxis {
+    #[default]
+    X,
+    Y,
+    Z,
+}
+
+impl MirrorAxis {
+    pub const ALL: [MirrorAxis; 3] = [MirrorAxis::X, MirrorAxis::Y, MirrorAxis::Z];
+
+    pub fn normal(self) -> Vec3 {
+        match self {
+            MirrorAxis::X => Vec3::X,
+            MirrorAxis::Y => Vec3::Y,
+            MirrorAxis::Z => Vec3::Z,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MirrorAxis::X => "YZ plane (flip X)",
+            MirrorAxis::Y => "XZ plane (flip Y)",
+            MirrorAxis::Z => "XY plane (flip Z)",
+        }
+    }
+}
+
+// Links a mesh to the one it was instanced from. Every mesh with the same
+// link root draws the root's render mesh and has its geometry kept in sync.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LinkedMesh(pub Entity);
+
+#[derive(Resource, Default)]
+pub struct MeshDuplication {
+    pub mode: DuplicateMode,
+    pub mirror_axis: MirrorAxis,
+    // Mirror across the plane through the mesh's center instead of the origin
+    pub about_center: bool,
+    pub duplicate_requested: bool,
+    pub mirror_requested: bool,
+}
+
+fn copy_cgar_mesh(cgar_mesh: &CgarMesh<CgarF64, 3>) -> CgarMesh<CgarF64, 3> {
+    let soup = TriangleSoup::from_topology(&MeshTopology::from_cgar(cgar_mesh));
+    build_cgar_mesh(&soup.positions, soup.triangles.iter().copied())
+}
+
+// Reflection across the plane through `point` with unit `normal`
+fn reflection(point: Vec3, normal: Vec3) -> Affine3A {
+    let n = Vec3A::from(normal);
+    let linear = Mat3A::IDENTITY - Mat3A::from_cols(n * n.x, n * n.y, n * n.z) * 2.0;
+    Affine3A {
+        matrix3: linear,
+        translation: n * (2.0 * normal.dot(point)),
+    }
+}
+
+fn to_daffine(affine: Affine3A) -> DAffine3 {
+    DAffine3::from_mat4(Mat4::from(affine).as_dmat4())
+}
+
+pub fn duplicate_shortcut(kb: Res<ButtonInput<KeyCode>>, mut duplication: ResMut<MeshDuplication>) {
+    let ctrl = kb.pressed(KeyCode::ControlLeft) || kb.pressed(KeyCode::ControlRight);
+    if ctrl && kb.just_pressed(KeyCode::KeyD) {
+        duplication.duplicate_requested = true;
+    }
+}
+
+// Duplicates or mirrors the selected mesh (or the first one). Duplicates are
+// placed beside the original; mirrored linked instances reflect through
+// their transform and draw both faces, since the reflection turns the
+// shared triangles inside out.
+pub fn duplicate_meshes(
+    mut commands: Commands,
+    mut duplication: ResMut<MeshDuplication>,
+    mut selection: ResMut<SelectionSet>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut notices: EventWriter<Notify>,
+    mesh_query: Query<
+        (
+            Entity,
+            &CgarMeshData,
+            &Mesh3d,
+            &MeshMaterial3d<StandardMaterial>,
+            &GlobalTransform,
+            Option<&Name>,
+            Option<&ChildOf>,
+            Option<&LinkedMesh>,
+        ),
+        // Meshes of other workspaces can't be duplicated
+        Without<Stashed>,
+    >,
+    parent_query: Query<&GlobalTransform>,
+) {
+    let (duplicate, mirror) = (
+        duplication.duplicate_requested,
+        duplication.mirror_requested,
+    );
+    if !duplicate && !mirror {
+        return;
+    }
+    duplication.duplicate_requested = false;
+    duplication.mirror_requested = false;
+    let Some(entity) = selection
+        .mesh
+        .filter(|e| mesh_query.contains(*e))
+        .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity))
+    else {
+        notices.write(Notify::warning("Duplicate: no mesh loaded"));
+        return;
+    };
+    let Ok((_, cgar_data, mesh_handle, material, global, name, parent, link)) =
+        mesh_query.get(entity)
+    else {
+        return;
+    };
+    let name = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+    let parent_global = parent.and_then(|parent| parent_query.get(parent.parent()).ok());
+    let linked = duplication.mode == DuplicateMode::Linked;
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+
+    // World transform of the new mesh, and the geometry it draws
+    let (world, cgar_mesh, suffix) = if !mirror {
+        let (min_x, max_x) = topology
+            .used_vertices()
+            .map(|v| global.transform_point(topology.positions[v].as_vec3()).x)
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), x| {
+                (min.min(x), max.max(x))
+            });
+        let width = (max_x - min_x).max(0.0);
+        let offset = Affine3A::from_translation(Vec3::X * width * 1.1);
+        (
+            offset * global.affine(),
+            copy_cgar_mesh(&cgar_data.0),
+            "copy",
+        )
+    } else {
+        let center = if duplication.about_center {
+            let (sum, count) = topology
+                .used_vertices()
+                .fold((DVec3::ZERO, 0usize), |(sum, count), v| {
+                    (sum + topology.positions[v], count + 1)
+                });
+            global.transform_point((sum / count.max(1) as f64).as_vec3())
+        } else {
+            Vec3::ZERO
+        };
+        let reflect = reflection(center, duplication.mirror_axis.normal());
+        if linked {
+            (
+                reflect * global.affine(),
+                copy_cgar_mesh(&cgar_data.0),
+                "mirror",
+            )
+        } else {
+            // Bake the reflection into local coordinates, flipping the winding
+            // so the copy faces outward
+            let to_world = to_daffine(global.affine());
+            let local = to_world.inverse() * to_daffine(reflect) * to_world;
+            let mut soup = TriangleSoup::from_topology(&topology);
+            for p in &mut soup.positions {
+                *p = local.transform_point3(*p);
+            }
+            for tri in &mut soup.triangles {
+                tri.swap(1, 2);
+            }
+            let baked = build_cgar_mesh(&soup.positions, soup.triangles.iter().copied());
+            (global.affine(), baked, "mirror")
+        }
+    };
+    let world = GlobalTransform::from(world);
+    let transform = match parent_global {
+        Some(parent_global) => world.reparented_to(parent_global),
+        None => world.compute_transform(),
+    };
+
+    let (render, link) = if linked {
+        (
+            mesh_handle.0.clone(),
+            Some(LinkedMesh(link.map_or(entity, |link| link.0))),
+        )
+    } else {
+        (
+            meshes.add(cgar_to_bevy_mesh(&cgar_mesh, NormalWeighting::default())),
+            None,
+        )
+    };
+    let material: Handle<StandardMaterial> = if mirror && linked {
+        let double_sided = materials
+            .get(&material.0)
+            .cloned()
+            .map(|base| StandardMaterial {
+                cull_mode: None,
+                double_sided: true,
+                ..base
+            });
+        double_sided.map_or_else(|| material.0.clone(), |m| materials.add(m))
+    } else {
+        material.0.clone()
+    };
+    let copy = commands
+        .spawn((
+            Name::new(format!("{}.{}", name, suffix)),
+            MeshMaterial3d(material),
+            Mesh3d(render),
+            transform,
+            Pickable::default(),
+            CgarMeshData(cgar_mesh),
+        ))
+        .id();
+    if let Some(link) = link {
+        commands.entity(copy).insert(link);
+    }
+    if let Some(parent) = parent {
+        commands.entity(copy).insert(ChildOf(parent.parent()));
+    }
+    selection.clear();
+    selection.mesh = Some(copy);
+    notices.write(Notify::info(format!(
+        "Created {}{}.{}",
+        if linked { "linked " } else { "" },
+        name,
+        suffix
+    )));
+}
+
+// Copies edits made to any member of a link set into the others. Members
+// written here are skipped once so the copy doesn't bounce back.
+pub fn sync_linked_meshes(
+    mut written: Local<HashSet<Entity>>,
+    mut mesh_query: Query<(Entity, &mut CgarMeshData, Option<&LinkedMesh>)>,
+) {
+    let skip = std::mem::take(&mut *written);
+    let root_of = |entity: Entity, link: Option<&LinkedMesh>| link.map_or(entity, |link| link.0);
+    let edited: Vec<(Entity, Entity)> = mesh_query
+        .iter_mut()
+        .filter(|(entity, cgar_data, _)| {
+            cgar_data.is_changed() && !cgar_data.is_added() && !skip.contains(entity)
+        })
+        .map(|(entity, _, link)| (root_of(entity, link), entity))
+        .collect();
+    for (root, source) in edited {
+        let members: Vec<Entity> = mesh_query
+            .iter()
+            .filter(|&(entity, _, link)| entity != source && root_of(entity, link) == root)
+            .map(|(entity, ..)| entity)
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        let Ok((_, cgar_data, _)) = mesh_query.get(source) else {
+            continue;
+        };
+        let soup = TriangleSoup::from_topology(&MeshTopology::from_cgar(&cgar_data.0));
+        for member in members {
+            if let Ok((_, mut cgar_data, _)) = mesh_query.get_mut(member) {
+                cgar_data.0 = build_cgar_mesh(&soup.positions, soup.triangles.iter().copied());
+                written.insert(member);
+            }
+        }
+    }
+}
+
+pub fn duplicate_panel(mut contexts: EguiContexts, mut duplication: ResMut<MeshDuplication>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Duplicate / Mirror")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut mode = duplication.mode;
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut mode, DuplicateMode::Linked, "Linked instance");
+                ui.radio_value(&mut mode, DuplicateMode::Copy, "Deep copy");
+            });
+            if mode != duplication.mode {
+                duplication.mode = mode;
+            }
+            if ui.button("Duplicate (Ctrl+D)").clicked() {
+                duplication.duplicate_requested = true;
+            }
+            ui.separator();
+
+            let mut axis = duplication.mirror_axis;
+            egui::ComboBox::from_label("Mirror plane")
+                .selected_text(axis.label())
+                .show_ui(ui, |ui| {
+                    for option in MirrorAxis::ALL {
+                        ui.selectable_value(&mut axis, option, option.label());
+                    }
+                });
+            if axis != duplication.mirror_axis {
+                duplication.mirror_axis = axis;
+            }
+            let mut about_center = duplication.about_center;
+            ui.checkbox(
+                &mut about_center,
+                "Through the mesh center (else the origin)",
+            );
+            if about_center != duplication.about_center {
+                duplication.about_center = about_center;
+            }
+            if ui.button("Mirror").clicked() {
+                duplication.mirror_requested = true;
+            }
+            ui.label("Linked instances share one render mesh, so overlays show on all of them");
+        });
+}
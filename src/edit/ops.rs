@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use bevy::math::DVec3;
+
+use crate::mesh::face_tree::barycentric;
+use crate::mesh::topology::MeshTopology;
+use crate::repair::ops::{TriangleSoup, find};
+
+// Smallest barycentric weight a face split point may have
+const MIN_SPLIT_WEIGHT: f64 = 1e-3;
+// Edge splits stay this far (as a fraction of the edge) from either end
+pub const MIN_SPLIT_T: f64 = 1e-3;
+
+// Live faces except `faces`. Vertices stay in place so ids keep their meaning.
+pub fn delete_faces(topology: &MeshTopology, faces: &BTreeSet<usize>) -> TriangleSoup {
+    TriangleSoup {
+        positions: topology.positions.clone(),
+        triangles: topology
+            .live_faces()
+            .filter(|(fi, _)| !faces.contains(fi))
+            .map(|(_, tri)| tri)
+            .collect(),
+    }
+}
+
+// Vertex of `tri` that isn't on edge (v0, v1)
+fn opposite(tri: [usize; 3], v0: usize, v1: usize) -> usize {
+    tri.into_iter()
+        .find(|&v| v != v0 && v != v1)
+        .unwrap_or(tri[0])
+}
+
+// Replaces the two triangles sharing (v0, v1) with the two sharing the
+// other diagonal of their quad. Boundary, non-manifold and already existing
+// diagonals are refused.
+pub fn flip_edge(topology: &MeshTopology, edge: (usize, usize)) -> Result<TriangleSoup, String> {
+    let key = (edge.0.min(edge.1), edge.0.max(edge.1));
+    let faces = topology.edge_faces.get(&key).cloned().unwrap_or_default();
+    let [f0, f1] = faces[..] else {
+        return Err(format!(
+            "edge ({}, {}) needs exactly two faces to flip, it has {}",
+            key.0,
+            key.1,
+            faces.len()
+        ));
+    };
+    let (Some(t0), Some(t1)) = (topology.triangles[f0], topology.triangles[f1]) else {
+        return Err("edge faces were removed".to_string());
+    };
+    // Orient so t0 runs v0 -> v1
+    let k = (0..3).find(|&k| {
+        let (a, b) = (t0[k], t0[(k + 1) % 3]);
+        (a, b) == key || (b, a) == key
+    });
+    let Some(k) = k else {
+        return Err("edge is not on its face".to_string());
+    };
+    let (v0, v1) = (t0[k], t0[(k + 1) % 3]);
+    let (a, b) = (opposite(t0, v0, v1), opposite(t1, v0, v1));
+    if a == b || topology.edge_faces.contains_key(&(a.min(b), a.max(b))) {
+        return Err(format!("flipped edge ({}, {}) already exists", a, b));
+    }
+
+    let mut soup = TriangleSoup {
+        positions: topology.positions.clone(),
+        triangles: Vec::new(),
+    };
+    for (fi, tri) in topology.live_faces() {
+        if fi != f0 && fi != f1 {
+            soup.triangles.push(tri);
+        }
+    }
+    soup.triangles.push([a, v0, b]);
+    soup.triangles.push([b, v1, a]);
+    Ok(soup)
+}
+
+// Inserts a vertex at parameter `t` along (v0, v1) and splits every face on
+// the edge in two. The new vertex is appended, so existing ids are kept.
+pub fn split_edge(topology: &MeshTopology, edge: (usize, usize), t: f64) -> TriangleSoup {
+    let (v0, v1) = edge;
+    let mut positions = topology.positions.clone();
+    let mid = positions.len();
+    positions.push(positions[v0].lerp(positions[v1], t));
+
+    let mut triangles = Vec::new();
+    for (_, tri) in topology.live_faces() {
+        let k = (0..3).find(|&k| {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            (a, b) == (v0, v1) || (a, b) == (v1, v0)
+        });
+        match k {
+            Some(k) => {
+                let (a, b, c) = (tri[k], tri[(k + 1) % 3], tri[(k + 2) % 3]);
+                triangles.push([a, mid, c]);
+                triangles.push([mid, b, c]);
+            }
+            None => triangles.push(tri),
+        }
+    }
+    TriangleSoup {
+        positions,
+        triangles,
+    }
+}
+
+// Inserts a vertex at `point` (the centroid when `None`) and fans `face` into
+// three triangles around it. Points on the face's sides would leave a sliver,
+// so those are refused. The new vertex is appended, so existing ids are kept.
+pub fn split_face(
+    topology: &MeshTopology,
+    face: usize,
+    point: Option<DVec3>,
+) -> Result<TriangleSoup, String> {
+    let tri = topology
+        .triangles
+        .get(face)
+        .copied()
+        .flatten()
+        .ok_or("the face no longer exists")?;
+    let corners = topology.corners(tri);
+    let point = point.unwrap_or((corners[0] + corners[1] + corners[2]) / 3.0);
+    if barycentric(point, &corners).min_element() < MIN_SPLIT_WEIGHT {
+        return Err("the point is on the face's side; split the edge instead".to_string());
+    }
+    let mut positions = topology.positions.clone();
+    let center = positions.len();
+    positions.push(point);
+
+    let mut triangles = Vec::new();
+    for (fi, [a, b, c]) in topology.live_faces() {
+        if fi == face {
+            triangles.extend([[a, b, center], [b, c, center], [c, a, center]]);
+        } else {
+            triangles.push([a, b, c]);
+        }
+    }
+    Ok(TriangleSoup {
+        positions,
+        triangles,
+    })
+}
+
+// Fans of faces around `v`: each face links the two corners opposite `v`,
+// and faces whose corners end up linked belong to the same fan
+fn fan_count(faces: &[[usize; 3]], v: usize) -> usize {
+    let mut ids: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut parent: Vec<usize> = Vec::new();
+    let mut id = |u: usize, parent: &mut Vec<usize>| {
+        *ids.entry(u).or_insert_with(|| {
+            parent.push(parent.len());
+            parent.len() - 1
+        })
+    };
+    for tri in faces {
+        let k = tri.iter().position(|&u| u == v).unwrap_or(0);
+        let x = id(tri[(k + 1) % 3], &mut parent);
+        let y = id(tri[(k + 2) % 3], &mut parent);
+        let (rx, ry) = (find(&mut parent, x), find(&mut parent, y));
+        parent[rx] = ry;
+    }
+    (0..parent.len())
+        .filter(|&i| find(&mut parent, i) == i)
+        .count()
+}
+
+// Merges vertex `b` into `a` and moves `a` to `target`. Faces on an edge
+// (a, b) collapse and are dropped; vertices on either side of a crack are
+// stitched together. Refused when the weld would leave an edge with more than
+// two faces, flip a face against its new neighbors or pinch the surface at
+// `a`. `b` is left unused so every other id keeps its meaning.
+pub fn weld_vertex_pair(
+    topology: &MeshTopology,
+    (a, b): (usize, usize),
+    target: DVec3,
+) -> Result<TriangleSoup, String> {
+    if a == b {
+        return Err("pick two different vertices".to_string());
+    }
+    let mut positions = topology.positions.clone();
+    positions[a] = target;
+
+    let mut triangles = Vec::new();
+    for (_, tri) in topology.live_faces() {
+        let [x, y, z] = tri.map(|v| if v == b { a } else { v });
+        if x != y && y != z && z != x {
+            triangles.push([x, y, z]);
+        }
+    }
+
+    // Around `a`, every directed edge may appear once, or two faces share a
+    // side with the same winding (or three share it at all)
+    let around: Vec<[usize; 3]> = triangles
+        .iter()
+        .copied()
+        .filter(|tri| tri.contains(&a))
+        .collect();
+    let mut directed = BTreeSet::new();
+    for tri in &around {
+        for k in 0..3 {
+            if !directed.insert((tri[k], tri[(k + 1) % 3])) {
+                return Err(
+                    "the faces around it would overlap or disagree on orientation".to_string(),
+                );
+            }
+        }
+    }
+    if fan_count(&around, a) > 1 {
+        return Err(
+            "it would pinch the surface at one vertex; weld from the end of the crack".to_string(),
+        );
+    }
+    Ok(TriangleSoup {
+        positions,
+        triangles,
+    })
+}
+
+// Splits each of `faces` into four at its edge midpoints. Neighbors sharing a
+// split edge are split too, so the result has no T-junctions: one split edge
+// halves a face, two cut it in three and three make it a 1-to-4 as well.
+// Midpoints are appended, so existing ids are kept.
+pub fn subdivide_faces(topology: &MeshTopology, faces: &BTreeSet<usize>) -> TriangleSoup {
+    let mut positions = topology.positions.clone();
+    let mut midpoints = BTreeMap::new();
+    for &f in faces {
+        let Some(tri) = topology.triangles.get(f).copied().flatten() else {
+            continue;
+        };
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let mid = positions[a].lerp(positions[b], 0.5);
+                positions.push(mid);
+                positions.len() - 1
+            });
+        }
+    }
+
+    let mut triangles = Vec::new();
+    for (_, tri) in topology.live_faces() {
+        let mid = |k: usize| {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            midpoints.get(&(a.min(b), a.max(b))).copied()
+        };
+        let split = [mid(0), mid(1), mid(2)];
+        // Rotate so the first split edge follows an unsplit one
+        let k = (0..3)
+            .find(|&k| split[k].is_some() && split[(k + 2) % 3].is_none())
+            .unwrap_or(0);
+        let (a, b, c) = (tri[k], tri[(k + 1) % 3], tri[(k + 2) % 3]);
+        match (split[k], split[(k + 1) % 3], split[(k + 2) % 3]) {
+            (Some(ab), Some(bc), Some(ca)) => {
+                triangles.extend([[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]])
+            }
+            (Some(ab), Some(bc), None) => triangles.extend([[a, ab, bc], [ab, b, bc], [a, bc, c]]),
+            (Some(ab), None, None) => triangles.extend([[a, ab, c], [ab, b, c]]),
+            _ => triangles.push(tri),
+        }
+    }
+    TriangleSoup {
+        positions,
+        triangles,
+    }
+}
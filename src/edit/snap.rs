@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::{DVec3, Vec2};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::mesh::face_tree::FaceSurface;
+use crate::mesh::topology::MeshTopology;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapKind {
+    Vertex,
+    Edge,
+    Face,
+}
+
+impl SnapKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            SnapKind::Vertex => "vertex",
+            SnapKind::Edge => "edge",
+            SnapKind::Face => "face",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SnapTarget {
+    pub kind: SnapKind,
+    // Mesh-local position the dragged vertex snaps to
+    pub point: DVec3,
+}
+
+// Point on segment `a`-`b` closest to the line `origin + t * direction`
+pub fn closest_on_segment_to_line(origin: DVec3, direction: DVec3, a: DVec3, b: DVec3) -> DVec3 {
+    let edge = b - a;
+    let r = origin - a;
+    let (dd, ee, de) = (
+        direction.dot(direction),
+        edge.dot(edge),
+        direction.dot(edge),
+    );
+    if ee <= f64::EPSILON {
+        return a;
+    }
+    let (dr, er) = (direction.dot(r), edge.dot(r));
+    let denom = dd * ee - de * de;
+    // Parameter along the line; parallel lines pick the line point nearest `a`
+    let s = if denom > f64::EPSILON * dd * ee {
+        (de * er - dr * ee) / denom
+    } else {
+        0.0
+    };
+    let t = ((de * s + er) / ee).clamp(0.0, 1.0);
+    a + edge * t
+}
+
+// Snap target under the cursor for `dragged`, preferring vertices over edges
+// over faces. Candidates must project within `tolerance_px` of `cursor`;
+// `project` maps mesh-local points to viewport pixels, and the ray is the
+// cursor ray (origin, direction) in mesh-local space. Elements touching
+// `dragged` are ignored.
+pub fn find_snap(
+    topology: &MeshTopology,
+    surface: Option<(&CgarMesh<CgarF64, 3>, &FaceSurface)>,
+    dragged: usize,
+    (ray_origin, ray_direction): (DVec3, DVec3),
+    cursor: Vec2,
+    tolerance_px: f32,
+    project: impl Fn(DVec3) -> Option<Vec2>,
+) -> Option<SnapTarget> {
+    let screen_distance = |p: DVec3| {
+        project(p)
+            .map(|s| s.distance(cursor))
+            .filter(|&d| d <= tolerance_px)
+    };
+    let nearest = |candidates: &mut dyn Iterator<Item = DVec3>| {
+        candidates
+            .filter_map(|p| screen_distance(p).map(|d| (p, d)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(p, _)| p)
+    };
+
+    let mut vertices = topology
+        .used_vertices()
+        .filter(|&v| v != dragged)
+        .map(|v| topology.positions[v]);
+    if let Some(point) = nearest(&mut vertices) {
+        return Some(SnapTarget {
+            kind: SnapKind::Vertex,
+            point,
+        });
+    }
+
+    let mut edges = topology
+        .edge_faces
+        .keys()
+        .filter(|&&(a, b)| a != dragged && b != dragged)
+        .map(|&(a, b)| {
+            closest_on_segment_to_line(
+                ray_origin,
+                ray_direction,
+                topology.positions[a],
+                topology.positions[b],
+            )
+        });
+    if let Some(point) = nearest(&mut edges) {
+        return Some(SnapTarget {
+            kind: SnapKind::Edge,
+            point,
+        });
+    }
+
+    // Faces around the dragged vertex follow the cursor, so look through them
+    let (mesh, surface) = surface?;
+    let hit = surface.cast_ray(mesh, ray_origin, ray_direction, None, |face| {
+        !topology
+            .triangles
+            .get(face)
+            .copied()
+            .flatten()
+            .is_some_and(|tri| tri.contains(&dragged))
+    })?;
+    Some(SnapTarget {
+        kind: SnapKind::Face,
+        point: ray_origin + ray_direction * hit.t,
+    })
+}
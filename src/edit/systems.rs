@@ -0,0 +1,605 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    asset::Assets,
+    color::Color,
+    ecs::{
+        entity::Entity,
+        name::Name,
+        query::With,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    input::{ButtonInput, keyboard::KeyCode, mouse::MouseButton},
+    math::{DVec3, Isometry3d, Vec3},
+    render::{
+        camera::Camera,
+        mesh::{Mesh, Mesh3d},
+    },
+    state::state::{NextState, State},
+    transform::components::GlobalTransform,
+    window::{PrimaryWindow, Window},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::colormap::heat;
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::edit::snap::{SnapKind, SnapTarget, find_snap};
+use crate::mesh::conversion::{set_vertex_position, vertex_position};
+use crate::mesh::edge::local_pixel_size;
+use crate::mesh::face_tree::FaceTreeCache;
+use crate::mesh::features::{FeatureEdges, detect_feature_edges};
+use crate::mesh::normals::{ImportedNormals, NormalSettings, NormalWeighting};
+use crate::mesh::topology::MeshTopology;
+use crate::probe::systems::cursor_on_view_plane;
+use crate::sculpt::brush::Falloff;
+use crate::selection::components::SelectionSet;
+use crate::tools::systems::ActiveTool;
+
+pub struct VertexDrag {
+    pub mesh: Entity,
+    pub vertex: usize,
+    // World position of the vertex when grabbed; the drag plane passes through it
+    pub anchor: Vec3,
+    // Vertex minus cursor at grab time, so the vertex doesn't jump to the cursor
+    pub offset: Vec3,
+    // Snapshot taken at grab time; only the dragged vertex and its soft
+    // selection move meanwhile
+    pub topology: MeshTopology,
+    // Vertices dragged along with soft selection and the fraction of the
+    // dragged vertex's motion each follows
+    pub soft: Vec<(usize, f64)>,
+}
+
+// Left-drag vertices to move them; hold Ctrl to snap onto nearby elements.
+// `enabled` follows the Move vertex tool (see `ActiveTool`). With soft
+// selection, vertices within `soft_radius_px` of the grabbed one (measured
+// on screen at grab time) follow it, fading out by `soft_falloff`.
+#[derive(Resource)]
+pub struct VertexEdit {
+    pub enabled: bool,
+    pub snap_tolerance_px: f32,
+    pub soft_selection: bool,
+    pub soft_radius_px: f32,
+    pub soft_falloff: Falloff,
+    pub drag: Option<VertexDrag>,
+    pub snap: Option<SnapTarget>,
+}
+
+impl Default for VertexEdit {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            snap_tolerance_px: 12.0,
+            soft_selection: false,
+            soft_radius_px: 60.0,
+            soft_falloff: Falloff::default(),
+            drag: None,
+            snap: None,
+        }
+    }
+}
+
+// Used vertices within `radius` of `vertex`, excluding it, with their
+// falloff weights
+fn soft_weights(
+    topology: &MeshTopology,
+    vertex: usize,
+    radius: f64,
+    falloff: Falloff,
+) -> Vec<(usize, f64)> {
+    let center = topology.positions[vertex];
+    topology
+        .used_vertices()
+        .filter(|&v| v != vertex)
+        .filter_map(|v| {
+            let weight = falloff.weight(topology.positions[v].distance(center) / radius);
+            (weight > 0.0).then_some((v, weight))
+        })
+        .collect()
+}
+
+pub fn drag_vertex(
+    mut edit: ResMut<VertexEdit>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    kb: Res<ButtonInput<KeyCode>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mut mesh_query: Query<(
+        Entity,
+        &Mesh3d,
+        &GlobalTransform,
+        &mut CgarMeshData,
+        Option<&FaceColorOverlay>,
+        Option<&FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+        Option<&FaceTreeCache>,
+    )>,
+) {
+    if !edit.enabled || !mouse_buttons.pressed(MouseButton::Left) {
+        if edit.drag.is_some() || edit.snap.is_some() {
+            edit.drag = None;
+            edit.snap = None;
+        }
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_global))) = (windows.single(), camera_query.single())
+    else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        // Grab the vertex nearest the cursor on screen, across all meshes
+        let mut best: Option<(f32, Entity, usize, Vec3)> = None;
+        for (entity, _, mesh_global, cgar_data, ..) in &mesh_query {
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            for v in topology.used_vertices() {
+                let world = mesh_global.transform_point(topology.positions[v].as_vec3());
+                let Ok(screen) = camera.world_to_viewport(camera_global, world) else {
+                    continue;
+                };
+                let distance = screen.distance(cursor);
+                if distance <= edit.snap_tolerance_px
+                    && best.is_none_or(|(best_distance, ..)| distance < best_distance)
+                {
+                    best = Some((distance, entity, v, world));
+                }
+            }
+        }
+        let soft_radius_px = Some(edit.soft_radius_px).filter(|_| edit.soft_selection);
+        let soft_falloff = edit.soft_falloff;
+        edit.drag = best.and_then(|(_, mesh, vertex, anchor)| {
+            let (_, _, mesh_global, cgar_data, ..) = mesh_query.get(mesh).ok()?;
+            let grab = cursor_on_view_plane(window, camera, camera_global, anchor)?;
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            let soft = soft_radius_px
+                .and_then(|radius_px| {
+                    let pixel = local_pixel_size(camera, camera_global, mesh_global, anchor)?;
+                    Some(soft_weights(
+                        &topology,
+                        vertex,
+                        (pixel * radius_px) as f64,
+                        soft_falloff,
+                    ))
+                })
+                .unwrap_or_default();
+            Some(VertexDrag {
+                mesh,
+                vertex,
+                anchor,
+                offset: anchor - grab,
+                topology,
+                soft,
+            })
+        });
+    }
+
+    let Some(drag) = &edit.drag else {
+        return;
+    };
+    let Ok((
+        _,
+        mesh_handle,
+        mesh_global,
+        mut cgar_data,
+        overlay,
+        features,
+        normals,
+        settings,
+        cache,
+    )) = mesh_query.get_mut(drag.mesh)
+    else {
+        edit.drag = None;
+        return;
+    };
+    let Some(on_plane) = cursor_on_view_plane(window, camera, camera_global, drag.anchor) else {
+        return;
+    };
+    let to_local = mesh_global.affine().inverse();
+    let mut target = to_local.transform_point3(on_plane + drag.offset).as_dvec3();
+
+    let snapping = kb.pressed(KeyCode::ControlLeft) || kb.pressed(KeyCode::ControlRight);
+    let snap = camera
+        .viewport_to_world(camera_global, cursor)
+        .ok()
+        .filter(|_| snapping)
+        .and_then(|ray| {
+            let origin = to_local.transform_point3(ray.origin).as_dvec3();
+            let direction = to_local
+                .transform_vector3(ray.direction.as_vec3())
+                .normalize()
+                .as_dvec3();
+            find_snap(
+                &drag.topology,
+                cache.map(|cache| (&cgar_data.0, &cache.0)),
+                drag.vertex,
+                (origin, direction),
+                cursor,
+                edit.snap_tolerance_px,
+                |p| {
+                    camera
+                        .world_to_viewport(camera_global, mesh_global.transform_point(p.as_vec3()))
+                        .ok()
+                },
+            )
+        });
+    if let Some(snap) = snap {
+        target = snap.point;
+    }
+
+    let vertex = drag.vertex;
+    let p = &cgar_data.0.vertices[vertex].position;
+    let current = DVec3::new(p[0].0, p[1].0, p[2].0);
+    if current != target {
+        set_vertex_position(&mut cgar_data.0, vertex, target);
+        let delta = target - drag.topology.positions[vertex];
+        for &(v, weight) in &drag.soft {
+            set_vertex_position(
+                &mut cgar_data.0,
+                v,
+                drag.topology.positions[v] + delta * weight,
+            );
+        }
+        meshes.insert(
+            &mesh_handle.0,
+            render_mesh(&cgar_data.0, overlay, features, normals, settings),
+        );
+    }
+    edit.snap = snap;
+}
+
+pub fn draw_vertex_edit(
+    mut gizmos: Gizmos,
+    edit: Res<VertexEdit>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+    camera_query: Query<&GlobalTransform, With<OrbitCamera>>,
+) {
+    let Some(drag) = &edit.drag else {
+        return;
+    };
+    let (Ok((mesh_global, cgar_data)), Ok(camera_global)) =
+        (mesh_query.get(drag.mesh), camera_query.single())
+    else {
+        return;
+    };
+    let p = &cgar_data.0.vertices[drag.vertex].position;
+    let world = mesh_global.transform_point(Vec3::new(p[0].0 as f32, p[1].0 as f32, p[2].0 as f32));
+    // Keep markers a constant size on screen
+    let radius = camera_global.translation().distance(world) * 0.01;
+    gizmos.sphere(
+        Isometry3d::from_translation(world),
+        radius,
+        Color::srgb(1.0, 0.8, 0.2),
+    );
+
+    // Soft selection as a heat gradient, red where vertices follow fully
+    for &(v, weight) in &drag.soft {
+        let at = mesh_global.transform_point(vertex_position(&cgar_data.0, v));
+        gizmos.circle(
+            Isometry3d::new(at, camera_global.rotation()),
+            radius * 0.4,
+            heat(weight as f32),
+        );
+    }
+
+    if let Some(snap) = edit.snap {
+        let color = match snap.kind {
+            SnapKind::Vertex => Color::srgb(0.2, 1.0, 0.2),
+            SnapKind::Edge => Color::srgb(0.2, 0.8, 1.0),
+            SnapKind::Face => Color::srgb(1.0, 0.3, 1.0),
+        };
+        let at = mesh_global.transform_point(snap.point.as_vec3());
+        let facing = Isometry3d::new(at, camera_global.rotation());
+        gizmos.circle(facing, radius * 2.0, color);
+        gizmos.cross(facing, radius * 1.5, color);
+    }
+}
+
+pub fn vertex_edit_panel(
+    mut contexts: EguiContexts,
+    mut edit: ResMut<VertexEdit>,
+    tool: Res<State<ActiveTool>>,
+    mut next_tool: ResMut<NextState<ActiveTool>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Vertex Edit").show(ctx, |ui| {
+        let mut enabled = *tool.get() == ActiveTool::VertexMove;
+        if ui.checkbox(&mut enabled, "Enabled (V)").changed() {
+            next_tool.set(if enabled {
+                ActiveTool::VertexMove
+            } else {
+                ActiveTool::Select
+            });
+        }
+        let mut tolerance = edit.snap_tolerance_px;
+        ui.add(egui::Slider::new(&mut tolerance, 2.0..=50.0).text("Snap tolerance (px)"));
+        if tolerance != edit.snap_tolerance_px {
+            edit.snap_tolerance_px = tolerance;
+        }
+        ui.label("Left-drag a vertex to move it; hold Ctrl to snap");
+
+        ui.separator();
+        let mut soft = edit.soft_selection;
+        ui.checkbox(&mut soft, "Soft selection");
+        if soft != edit.soft_selection {
+            edit.soft_selection = soft;
+        }
+        ui.add_enabled_ui(soft, |ui| {
+            let mut radius = edit.soft_radius_px;
+            ui.add(egui::Slider::new(&mut radius, 5.0..=400.0).text("Falloff radius (px)"));
+            if radius != edit.soft_radius_px {
+                edit.soft_radius_px = radius;
+            }
+            let mut falloff = edit.soft_falloff;
+            egui::ComboBox::from_label("Falloff")
+                .selected_text(falloff.label())
+                .show_ui(ui, |ui| {
+                    for option in Falloff::ALL {
+                        ui.selectable_value(&mut falloff, option, option.label());
+                    }
+                });
+            if falloff != edit.soft_falloff {
+                edit.soft_falloff = falloff;
+            }
+        });
+
+        if let Some(drag) = &edit.drag {
+            ui.separator();
+            ui.label(format!("Dragging vertex {}", drag.vertex));
+            if !drag.soft.is_empty() {
+                ui.label(format!("{} vertices follow", drag.soft.len()));
+            }
+            match edit.snap {
+                Some(snap) => ui.label(format!("Snapped to {}", snap.kind.label())),
+                None => ui.label("Not snapped"),
+            };
+        }
+    });
+}
+
+#[derive(Resource)]
+pub struct FeatureEdgeTool {
+    // Dihedral angle at or above which "Detect" tags an edge
+    pub angle_deg: f32,
+    pub include_boundary: bool,
+    pub show: bool,
+}
+
+impl Default for FeatureEdgeTool {
+    fn default() -> Self {
+        Self {
+            angle_deg: 40.0,
+            include_boundary: false,
+            show: true,
+        }
+    }
+}
+
+pub fn feature_edges_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut tool: ResMut<FeatureEdgeTool>,
+    selection: Res<SelectionSet>,
+    mut mesh_query: Query<(
+        Entity,
+        Option<&Name>,
+        &CgarMeshData,
+        Option<&mut FeatureEdges>,
+    )>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let target = selection
+        .mesh
+        .filter(|e| mesh_query.contains(*e))
+        .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+
+    egui::Window::new("Feature Edges").show(ctx, |ui| {
+        let Some((entity, name, cgar_data, mut features)) =
+            target.and_then(|e| mesh_query.get_mut(e).ok())
+        else {
+            ui.label("No mesh loaded");
+            return;
+        };
+        match name {
+            Some(name) => ui.label(format!("Mesh: {} ({})", name, entity)),
+            None => ui.label(format!("Mesh: {}", entity)),
+        };
+
+        let current = features
+            .as_deref()
+            .map(|features| features.edges.clone())
+            .unwrap_or_default();
+        ui.label(format!("{} tagged edges", current.len()));
+
+        let mut settings = (tool.angle_deg, tool.include_boundary, tool.show);
+        ui.add(egui::Slider::new(&mut settings.0, 1.0..=180.0).text("Min dihedral angle (deg)"));
+        ui.checkbox(&mut settings.1, "Include boundary edges");
+        ui.checkbox(&mut settings.2, "Show tagged edges");
+        if settings != (tool.angle_deg, tool.include_boundary, tool.show) {
+            (tool.angle_deg, tool.include_boundary, tool.show) = settings;
+        }
+
+        let selected = if selection.mesh == Some(entity) {
+            selection.edges.clone()
+        } else {
+            Default::default()
+        };
+        let mut edges = current.clone();
+        ui.horizontal(|ui| {
+            if ui.button("Detect").clicked() {
+                let topology = MeshTopology::from_cgar(&cgar_data.0);
+                edges = detect_feature_edges(
+                    &topology,
+                    (tool.angle_deg as f64).to_radians(),
+                    tool.include_boundary,
+                );
+            }
+            if ui
+                .add_enabled(!selected.is_empty(), egui::Button::new("Tag selected"))
+                .clicked()
+            {
+                edges.extend(selected.iter().copied());
+            }
+            if ui
+                .add_enabled(!selected.is_empty(), egui::Button::new("Untag selected"))
+                .clicked()
+            {
+                edges.retain(|edge| !selected.contains(edge));
+            }
+            if ui.button("Clear").clicked() {
+                edges.clear();
+            }
+        });
+        ui.label("Press F, then click edges to toggle their tag");
+
+        if edges != current {
+            match features.as_mut() {
+                Some(features) => features.edges = edges,
+                None => {
+                    commands.entity(entity).insert(FeatureEdges { edges });
+                }
+            }
+        }
+    });
+}
+
+// Per-mesh shading normals: the ones stored in the file or recomputed from
+// the geometry, optionally split at sharp edges
+pub fn normals_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    selection: Res<SelectionSet>,
+    mut mesh_query: Query<
+        (
+            Entity,
+            Option<&Name>,
+            Option<&mut ImportedNormals>,
+            Option<&mut NormalSettings>,
+        ),
+        With<CgarMeshData>,
+    >,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let target = selection
+        .mesh
+        .filter(|e| mesh_query.contains(*e))
+        .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+
+    egui::Window::new("Normals").show(ctx, |ui| {
+        let Some((entity, name, imported, settings)) =
+            target.and_then(|e| mesh_query.get_mut(e).ok())
+        else {
+            ui.label("No mesh loaded");
+            return;
+        };
+        match name {
+            Some(name) => ui.label(format!("Mesh: {} ({})", name, entity)),
+            None => ui.label(format!("Mesh: {}", entity)),
+        };
+
+        match imported {
+            Some(mut imported) => {
+                ui.label(format!(
+                    "{} authored corner normals",
+                    imported.corners.len()
+                ));
+                let mut enabled = imported.enabled;
+                ui.radio_value(&mut enabled, true, "Imported");
+                ui.radio_value(&mut enabled, false, "Recomputed");
+                if enabled != imported.enabled {
+                    imported.enabled = enabled;
+                }
+            }
+            None => {
+                ui.label("The file had no normals; shading uses recomputed ones");
+            }
+        }
+
+        ui.separator();
+        let current = settings.as_deref().copied().unwrap_or_default();
+        let mut edited = current;
+        egui::ComboBox::from_label("Weighting")
+            .selected_text(edited.weighting.label())
+            .show_ui(ui, |ui| {
+                for weighting in NormalWeighting::ALL {
+                    ui.selectable_value(&mut edited.weighting, weighting, weighting.label());
+                }
+            });
+        ui.checkbox(&mut edited.split_creases, "Split at crease angle");
+        ui.add_enabled(
+            edited.split_creases,
+            egui::Slider::new(&mut edited.crease_angle_deg, 1.0..=180.0).text("Angle (deg)"),
+        );
+        if edited != current {
+            match settings {
+                Some(mut settings) => *settings = edited,
+                None => {
+                    commands.entity(entity).insert(edited);
+                }
+            }
+        }
+    });
+}
+
+pub fn draw_feature_edges(
+    mut gizmos: Gizmos,
+    tool: Res<FeatureEdgeTool>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData, &FeatureEdges)>,
+) {
+    if !tool.show {
+        return;
+    }
+    let color = Color::srgb(1.0, 0.55, 0.0);
+    for (mesh_global, cgar_data, features) in &mesh_query {
+        let cgar_mesh = &cgar_data.0;
+        for &(v0, v1) in &features.edges {
+            // Tags on edges an operation removed stay dormant
+            if !cgar_mesh.edge_map.contains_key(&(v0, v1))
+                && !cgar_mesh.edge_map.contains_key(&(v1, v0))
+            {
+                continue;
+            }
+            gizmos.line(
+                mesh_global.transform_point(vertex_position(cgar_mesh, v0)),
+                mesh_global.transform_point(vertex_position(cgar_mesh, v1)),
+                color,
+            );
+        }
+    }
+}
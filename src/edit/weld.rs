@@ -0,0 +1,321 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    asset::Assets,
+    color::Color,
+    ecs::{
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        query::{QueryItem, With},
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    input::{ButtonInput, keyboard::KeyCode},
+    math::Isometry3d,
+    render::mesh::{Mesh, Mesh3d},
+    state::state::State,
+    transform::components::GlobalTransform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::edit::ops::weld_vertex_pair;
+use crate::mesh::conversion::{build_cgar_mesh, vertex_position};
+use crate::mesh::edge::MeshPicked;
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+use crate::tools::systems::ActiveTool;
+
+// Welds kept for undo; older ones are forgotten
+const WELD_UNDO_LEN: usize = 32;
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WeldPlacement {
+    #[default]
+    Midpoint,
+    First,
+    Second,
+}
+
+impl WeldPlacement {
+    pub const ALL: [WeldPlacement; 3] = [
+        WeldPlacement::Midpoint,
+        WeldPlacement::First,
+        WeldPlacement::Second,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WeldPlacement::Midpoint => "Midpoint",
+            WeldPlacement::First => "First vertex",
+            WeldPlacement::Second => "Second vertex",
+        }
+    }
+}
+
+// State of the Weld tool (see `ActiveTool::Weld`): the first picked vertex
+// while waiting for the second, and the meshes as they were before each weld
+#[derive(Resource, Default)]
+pub struct VertexWeld {
+    pub placement: WeldPlacement,
+    pub first: Option<(Entity, usize)>,
+    undo: Vec<(Entity, TriangleSoup)>,
+}
+
+type WeldMesh = (
+    &'static Mesh3d,
+    &'static mut CgarMeshData,
+    Option<&'static FeatureEdges>,
+    Option<&'static ImportedNormals>,
+    Option<&'static NormalSettings>,
+);
+
+// Puts a rebuilt mesh in place the way the other edit actions do: overlays
+// and the selection describe the old faces, so they go
+fn replace_mesh(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    selection: &mut SelectionSet,
+    entity: Entity,
+    (mesh_handle, mut cgar_data, features, normals, settings): QueryItem<WeldMesh>,
+    soup: &TriangleSoup,
+) {
+    cgar_data.0 = build_cgar_mesh(&soup.positions, soup.triangles.iter().copied());
+    meshes.insert(
+        &mesh_handle.0,
+        render_mesh(&cgar_data.0, None, features, normals, settings),
+    );
+    commands.entity(entity).remove::<FaceColorOverlay>();
+    if selection.mesh == Some(entity) {
+        selection.clear();
+    }
+}
+
+// Clicks pick the corner nearest the hit; the second click welds
+pub fn weld_on_pick(
+    mut commands: Commands,
+    mut picked: EventReader<MeshPicked>,
+    mut weld: ResMut<VertexWeld>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut selection: ResMut<SelectionSet>,
+    mut notices: EventWriter<Notify>,
+    mut mesh_query: Query<WeldMesh>,
+) {
+    for pick in picked.read().filter(|pick| !pick.double_click) {
+        let Some(hit) = pick.face_hit else {
+            notices.write(Notify::warning("The mesh isn't ready for picking yet"));
+            continue;
+        };
+        let corner = (0..3)
+            .max_by(|&i, &j| hit.barycentric[i].total_cmp(&hit.barycentric[j]))
+            .unwrap_or(0);
+        let vertex = hit.vertices[corner];
+        let Some((mesh, first)) = weld.first.filter(|(mesh, _)| *mesh == pick.entity) else {
+            weld.first = Some((pick.entity, vertex));
+            continue;
+        };
+        if first == vertex {
+            // Clicking the first vertex again drops it
+            weld.first = None;
+            continue;
+        }
+        weld.first = None;
+        let Ok(item) = mesh_query.get_mut(mesh) else {
+            continue;
+        };
+        let topology = MeshTopology::from_cgar(&item.1.0);
+        let (p, q) = (topology.positions[first], topology.positions[vertex]);
+        let target = match weld.placement {
+            WeldPlacement::Midpoint => (p + q) * 0.5,
+            WeldPlacement::First => p,
+            WeldPlacement::Second => q,
+        };
+        let adjacent = topology
+            .edge_faces
+            .contains_key(&(first.min(vertex), first.max(vertex)));
+        match weld_vertex_pair(&topology, (first, vertex), target) {
+            Ok(soup) => {
+                weld.undo
+                    .push((mesh, TriangleSoup::from_topology(&topology)));
+                let overflow = weld.undo.len().saturating_sub(WELD_UNDO_LEN);
+                weld.undo.drain(..overflow);
+                replace_mesh(
+                    &mut commands,
+                    &mut meshes,
+                    &mut selection,
+                    mesh,
+                    item,
+                    &soup,
+                );
+                notices.write(Notify::info(format!(
+                    "{} vertices {} and {}",
+                    if adjacent { "Collapsed" } else { "Stitched" },
+                    first,
+                    vertex
+                )));
+            }
+            Err(err) => {
+                notices.write(Notify::warning(format!(
+                    "Vertices {} and {} can't be welded: {}",
+                    first, vertex, err
+                )));
+            }
+        }
+    }
+}
+
+fn undo_last_weld(
+    commands: &mut Commands,
+    weld: &mut VertexWeld,
+    meshes: &mut Assets<Mesh>,
+    selection: &mut SelectionSet,
+    notices: &mut EventWriter<Notify>,
+    mesh_query: &mut Query<WeldMesh>,
+) {
+    let Some((mesh, soup)) = weld.undo.pop() else {
+        notices.write(Notify::info("Nothing to undo"));
+        return;
+    };
+    let Ok(item) = mesh_query.get_mut(mesh) else {
+        notices.write(Notify::warning("The welded mesh is gone"));
+        return;
+    };
+    replace_mesh(commands, meshes, selection, mesh, item, &soup);
+    notices.write(Notify::info("Undid the last weld"));
+}
+
+// Ctrl+Z while the Weld tool is active
+pub fn weld_undo_shortcut(
+    mut commands: Commands,
+    kb: Res<ButtonInput<KeyCode>>,
+    mut weld: ResMut<VertexWeld>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut selection: ResMut<SelectionSet>,
+    mut notices: EventWriter<Notify>,
+    mut mesh_query: Query<WeldMesh>,
+) {
+    let ctrl = kb.pressed(KeyCode::ControlLeft) || kb.pressed(KeyCode::ControlRight);
+    if !ctrl || !kb.just_pressed(KeyCode::KeyZ) {
+        return;
+    }
+    undo_last_weld(
+        &mut commands,
+        &mut weld,
+        &mut meshes,
+        &mut selection,
+        &mut notices,
+        &mut mesh_query,
+    );
+}
+
+// Leaving the tool drops a half-made pick
+pub fn exit_weld(mut weld: ResMut<VertexWeld>) {
+    weld.first = None;
+}
+
+pub fn draw_vertex_weld(
+    mut gizmos: Gizmos,
+    weld: Res<VertexWeld>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+    camera_query: Query<&GlobalTransform, With<OrbitCamera>>,
+) {
+    let Some((mesh, vertex)) = weld.first else {
+        return;
+    };
+    let (Ok((mesh_global, cgar_data)), Ok(camera_global)) =
+        (mesh_query.get(mesh), camera_query.single())
+    else {
+        return;
+    };
+    if vertex >= cgar_data.0.vertices.len() {
+        return;
+    }
+    let world = mesh_global.transform_point(vertex_position(&cgar_data.0, vertex));
+    // Keep the marker a constant size on screen
+    let radius = camera_global.translation().distance(world) * 0.01;
+    gizmos.sphere(
+        Isometry3d::from_translation(world),
+        radius,
+        Color::srgb(1.0, 0.5, 0.1),
+    );
+}
+
+pub fn vertex_weld_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    tool: Res<State<ActiveTool>>,
+    mut weld: ResMut<VertexWeld>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut selection: ResMut<SelectionSet>,
+    mut notices: EventWriter<Notify>,
+    mut mesh_query: Query<WeldMesh>,
+) {
+    if *tool.get() != ActiveTool::Weld {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut undo = false;
+    egui::Window::new("Weld Vertices").show(ctx, |ui| {
+        match weld.first {
+            Some((_, vertex)) => ui.label(format!("First vertex: {}; pick the second", vertex)),
+            None => ui.label("Pick the first vertex"),
+        };
+        let mut placement = weld.placement;
+        egui::ComboBox::from_label("Weld at")
+            .selected_text(placement.label())
+            .show_ui(ui, |ui| {
+                for option in WeldPlacement::ALL {
+                    ui.selectable_value(&mut placement, option, option.label());
+                }
+            });
+        if placement != weld.placement {
+            weld.placement = placement;
+        }
+        undo = ui
+            .add_enabled(
+                !weld.undo.is_empty(),
+                egui::Button::new(format!("Undo weld ({})", weld.undo.len())),
+            )
+            .on_hover_text("Ctrl+Z")
+            .clicked();
+    });
+    if undo {
+        undo_last_weld(
+            &mut commands,
+            &mut weld,
+            &mut meshes,
+            &mut selection,
+            &mut notices,
+            &mut mesh_query,
+        );
+    }
+}
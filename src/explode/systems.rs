@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        change_detection::{DetectChanges, DetectChangesMut},
+        entity::Entity,
+        event::EventWriter,
+        hierarchy::ChildOf,
+        name::Name,
+        query::{Added, Has},
+        removal_detection::RemovedComponents,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::{DVec3, Vec3},
+    render::mesh::Mesh,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::CgarMeshData;
+use crate::mesh::conversion::build_cgar_mesh;
+use crate::mesh::setup::{DefaultMeshMaterial, spawn_cgar_mesh};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+use crate::workspace::systems::Stashed;
+
+// Pushes every mesh away from the common centroid so the parts of an
+// assembly can be told apart. Only transforms move; a mesh imported as one
+// file is split into its connected components first.
+#[derive(Resource, Default)]
+pub struct ExplodedView {
+    // Offset as a multiple of each part's distance from the common centroid
+    pub factor: f32,
+    pub split_requested: bool,
+    // Translation each mesh had before exploding, restored at factor zero
+    bases: HashMap<Entity, Vec3>,
+    // Part centroids in mesh-local space
+    centers: HashMap<Entity, Vec3>,
+}
+
+impl ExplodedView {
+    pub fn exploded(&self) -> bool {
+        self.factor > 0.0
+    }
+}
+
+fn local_center(cgar_data: &CgarMeshData) -> Vec3 {
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    let (sum, count) = topology
+        .used_vertices()
+        .fold((DVec3::ZERO, 0usize), |(sum, count), v| {
+            (sum + topology.positions[v], count + 1)
+        });
+    (sum / count.max(1) as f64).as_vec3()
+}
+
+pub fn apply_explode(
+    mut explode: ResMut<ExplodedView>,
+    added: Query<(), Added<CgarMeshData>>,
+    stashed: Query<(), Added<Stashed>>,
+    mut unstashed: RemovedComponents<Stashed>,
+    mut mesh_query: Query<(
+        Entity,
+        &mut Transform,
+        &CgarMeshData,
+        Option<&ChildOf>,
+        Has<Stashed>,
+    )>,
+    parent_query: Query<&GlobalTransform>,
+) {
+    // Switching workspaces changes which meshes are laid out
+    let workspace_changed = unstashed.read().count() > 0 || !stashed.is_empty();
+    if !explode.is_changed() && added.is_empty() && !workspace_changed {
+        return;
+    }
+    let explode = explode.bypass_change_detection();
+    explode
+        .bases
+        .retain(|entity, _| mesh_query.contains(*entity));
+    explode
+        .centers
+        .retain(|entity, _| mesh_query.contains(*entity));
+
+    if !explode.exploded() {
+        for (entity, mut transform, ..) in &mut mesh_query {
+            if let Some(base) = explode.bases.remove(&entity) {
+                transform.translation = base;
+            }
+        }
+        return;
+    }
+
+    // Part centers in world space where they sit unexploded, so parts under
+    // different parents are pushed apart consistently. Meshes of other
+    // workspaces keep their bases and rejoin when their workspace is back.
+    let mut parts = Vec::new();
+    for (entity, transform, cgar_data, child_of, stashed) in &mesh_query {
+        if stashed {
+            continue;
+        }
+        let base = *explode.bases.entry(entity).or_insert(transform.translation);
+        let local = *explode
+            .centers
+            .entry(entity)
+            .or_insert_with(|| local_center(cgar_data));
+        let at_base = Transform {
+            translation: base,
+            ..*transform
+        };
+        let parent = child_of
+            .and_then(|child_of| parent_query.get(child_of.parent()).ok())
+            .copied()
+            .unwrap_or(GlobalTransform::IDENTITY);
+        let global = parent * at_base;
+        parts.push((entity, parent, global, global.transform_point(local)));
+    }
+    if parts.len() < 2 {
+        return;
+    }
+    let centroid = parts.iter().map(|(.., center)| *center).sum::<Vec3>() / parts.len() as f32;
+    for (entity, parent, global, center) in parts {
+        if let Ok((_, mut transform, ..)) = mesh_query.get_mut(entity) {
+            // The offset is in world space; only the translation goes back
+            // through the parent so rotation and scale stay exact
+            let moved =
+                GlobalTransform::from_translation((center - centroid) * explode.factor) * global;
+            transform.translation = moved.reparented_to(&parent).translation;
+        }
+    }
+}
+
+pub fn split_into_parts(
+    mut commands: Commands,
+    mut explode: ResMut<ExplodedView>,
+    mut selection: ResMut<SelectionSet>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Res<DefaultMeshMaterial>,
+    mut notices: EventWriter<Notify>,
+    mesh_query: Query<(Entity, &CgarMeshData, &Transform, Option<&Name>)>,
+) {
+    if !explode.split_requested {
+        return;
+    }
+    explode.split_requested = false;
+    let Some(entity) = selection
+        .mesh
+        .filter(|e| mesh_query.contains(*e))
+        .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity))
+    else {
+        notices.write(Notify::warning("Split: no mesh loaded"));
+        return;
+    };
+    let Ok((_, cgar_data, transform, name)) = mesh_query.get(entity) else {
+        return;
+    };
+    let name = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+
+    let soup = TriangleSoup::from_topology(&MeshTopology::from_cgar(&cgar_data.0));
+    let parts = soup.split_components();
+    if parts.len() < 2 {
+        notices.write(Notify::info(format!("{} is a single connected part", name)));
+        return;
+    }
+
+    // Parts start where the whole mesh sat before any explode offset
+    let mut transform = *transform;
+    if let Some(base) = explode.bases.remove(&entity) {
+        transform.translation = base;
+    }
+    explode.centers.remove(&entity);
+    for (i, part) in parts.iter().enumerate() {
+        spawn_cgar_mesh(
+            &mut commands,
+            &mut meshes,
+            &material,
+            format!("{}.{}", name, i + 1),
+            build_cgar_mesh(&part.positions, part.triangles.iter().copied()),
+            transform,
+        );
+    }
+    commands.entity(entity).despawn();
+    if selection.mesh == Some(entity) {
+        selection.clear();
+    }
+    notices.write(Notify::info(format!(
+        "Split {} into {} parts",
+        name,
+        parts.len()
+    )));
+}
+
+pub fn explode_panel(mut contexts: EguiContexts, mut explode: ResMut<ExplodedView>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Exploded view")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut factor = explode.factor;
+            ui.add(egui::Slider::new(&mut factor, 0.0..=3.0).text("Explode"));
+            if factor != explode.factor {
+                explode.factor = factor;
+            }
+            if ui.button("Split selected mesh into parts").clicked() {
+                explode.split_requested = true;
+            }
+            ui.label("Splitting separates connected components into their own meshes");
+        });
+}
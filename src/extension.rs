@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    app::App,
+    ecs::{entity::Entity, resource::Resource, world::World},
+    gizmos::gizmos::Gizmos,
+    input::keyboard::KeyCode,
+    math::{DVec3, Vec3},
+    window::SystemCursorIcon,
+};
+use bevy_inspector_egui::egui;
+
+// A click on a mesh while a custom tool is active. Double clicks are left
+// to the camera.
+#[derive(Debug, Clone, Copy)]
+pub struct ToolPick {
+    pub entity: Entity,
+    // World-space hit position, on the full-resolution mesh when its face
+    // tree is ready and from the picking backend otherwise
+    pub world_position: Option<Vec3>,
+    // Hit face and the click's barycentric weights over its corners
+    pub face: Option<(usize, DVec3)>,
+}
+
+// An interactive tool living next to the built-in ones: it shows up in the
+// status bar, gets a shortcut, owns left clicks on meshes while active and
+// can draw gizmos and options. Tool state lives in the implementing type;
+// hooks that take the world can read and change anything else.
+pub trait ViewerTool: Send + Sync + 'static {
+    fn label(&self) -> &str;
+
+    // Status-bar hint while the tool is active
+    fn hint(&self) -> &str {
+        ""
+    }
+
+    // Key and its label in the status bar; pressing it again returns to Select
+    fn shortcut(&self) -> Option<(KeyCode, &str)> {
+        None
+    }
+
+    fn cursor(&self) -> SystemCursorIcon {
+        SystemCursorIcon::Crosshair
+    }
+
+    fn activate(&mut self, _world: &mut World) {}
+
+    fn deactivate(&mut self, _world: &mut World) {}
+
+    fn on_pick(&mut self, _pick: ToolPick, _world: &mut World) {}
+
+    // Every frame while active, for keyboard or drag input
+    fn update(&mut self, _world: &mut World) {}
+
+    fn draw_gizmos(&self, _gizmos: &mut Gizmos) {}
+
+    // Options shown in the status bar next to the hint
+    fn options_ui(&mut self, _ui: &mut egui::Ui) {}
+}
+
+// Registered custom tools, indexed by registration order
+#[derive(Resource, Default)]
+pub struct ViewerTools {
+    tools: Vec<Box<dyn ViewerTool>>,
+}
+
+impl ViewerTools {
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&dyn ViewerTool> {
+        self.tools.get(id).map(|tool| &**tool)
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut dyn ViewerTool> {
+        match self.tools.get_mut(id) {
+            Some(tool) => Some(&mut **tool),
+            None => None,
+        }
+    }
+}
+
+pub trait ViewerToolsAppExt {
+    // Adds a tool after the built-in ones; returns the app for chaining
+    fn add_viewer_tool(&mut self, tool: impl ViewerTool) -> &mut Self;
+}
+
+impl ViewerToolsAppExt for App {
+    fn add_viewer_tool(&mut self, tool: impl ViewerTool) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<ViewerTools>()
+            .tools
+            .push(Box::new(tool));
+        self
+    }
+}
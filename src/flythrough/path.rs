@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::{Quat, Vec3};
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Easing {
+    Linear,
+    #[default]
+    EaseInOut,
+    EaseIn,
+    EaseOut,
+}
+
+impl Easing {
+    pub const ALL: [Easing; 4] = [
+        Easing::Linear,
+        Easing::EaseInOut,
+        Easing::EaseIn,
+        Easing::EaseOut,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Easing::Linear => "Linear",
+            Easing::EaseInOut => "Ease in/out",
+            Easing::EaseIn => "Ease in",
+            Easing::EaseOut => "Ease out",
+        }
+    }
+
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+        }
+    }
+}
+
+// Orbit camera pose. The camera sits `radius` behind `focus` along its view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    pub focus: Vec3,
+    pub radius: f32,
+    pub rotation: Quat,
+    // Seconds spent travelling to the next keyframe
+    pub duration_secs: f32,
+    // Easing of that travel
+    pub easing: Easing,
+}
+
+impl Keyframe {
+    pub fn translation(&self) -> Vec3 {
+        self.focus + self.rotation * Vec3::Z * self.radius
+    }
+
+    fn lerp(&self, other: &Keyframe, t: f32) -> Keyframe {
+        Keyframe {
+            focus: self.focus.lerp(other.focus, t),
+            radius: self.radius + (other.radius - self.radius) * t,
+            rotation: self.rotation.slerp(other.rotation, t),
+            ..*self
+        }
+    }
+}
+
+// Seconds to play `keyframes`; a looping path also travels from the last back to the first
+pub fn path_duration(keyframes: &[Keyframe], looped: bool) -> f32 {
+    let segments = if looped {
+        keyframes.len()
+    } else {
+        keyframes.len().saturating_sub(1)
+    };
+    keyframes[..segments.min(keyframes.len())]
+        .iter()
+        .map(|key| key.duration_secs.max(0.0))
+        .sum()
+}
+
+// Seconds into the path at which keyframe `index` is reached
+pub fn keyframe_time(keyframes: &[Keyframe], index: usize) -> f32 {
+    keyframes[..index.min(keyframes.len())]
+        .iter()
+        .map(|key| key.duration_secs.max(0.0))
+        .sum()
+}
+
+// Pose `time` seconds into the path; past the end it holds the final pose
+pub fn sample_path(keyframes: &[Keyframe], looped: bool, time: f32) -> Option<Keyframe> {
+    let first = keyframes.first()?;
+    let total = path_duration(keyframes, looped);
+    if total <= 0.0 {
+        return Some(*first);
+    }
+    let mut time = if looped {
+        time.rem_euclid(total)
+    } else {
+        time.clamp(0.0, total)
+    };
+    for (i, key) in keyframes.iter().enumerate() {
+        let Some(next) = keyframes.get(i + 1).or(looped.then_some(first)) else {
+            break;
+        };
+        let duration = key.duration_secs.max(0.0);
+        if time <= duration && duration > 0.0 {
+            return Some(key.lerp(next, key.easing.apply(time / duration)));
+        }
+        time -= duration;
+    }
+    keyframes.last().copied()
+}
@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(feature = "native")]
+use bevy::ecs::system::Commands;
+#[cfg(feature = "native")]
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+use bevy::{
+    ecs::{
+        event::EventWriter,
+        query::With,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    log::info,
+    time::Time,
+    transform::components::Transform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::OrbitCamera;
+use crate::flythrough::path::{Easing, Keyframe, keyframe_time, path_duration, sample_path};
+use crate::notifications::systems::Notify;
+
+// Folder frames are written to, relative to the working directory
+#[cfg(feature = "native")]
+const FRAMES_DIR: &str = "flythrough";
+
+// Frame export in progress: time advances by exactly one frame per update
+// and every frame is captured, however long rendering takes
+#[derive(Debug, Clone, Copy)]
+pub struct Recording {
+    pub frame: usize,
+    pub frames: usize,
+}
+
+// Keyframed camera flythrough, played back in the viewer or exported as
+// numbered frames for video encoding
+#[derive(Resource)]
+pub struct CameraPath {
+    pub keyframes: Vec<Keyframe>,
+    pub looped: bool,
+    pub playing: bool,
+    pub time: f32,
+    // Move the camera to `time` once, e.g. after scrubbing
+    pub seek: bool,
+    pub fps: u32,
+    pub recording: Option<Recording>,
+}
+
+impl Default for CameraPath {
+    fn default() -> Self {
+        Self {
+            keyframes: Vec::new(),
+            looped: false,
+            playing: false,
+            time: 0.0,
+            seek: false,
+            fps: 30,
+            recording: None,
+        }
+    }
+}
+
+impl CameraPath {
+    pub fn duration(&self) -> f32 {
+        path_duration(&self.keyframes, self.looped)
+    }
+}
+
+pub fn play_camera_path(
+    #[cfg(feature = "native")] mut commands: Commands,
+    time: Res<Time>,
+    mut path: ResMut<CameraPath>,
+    mut notices: EventWriter<Notify>,
+    mut camera_query: Query<(&mut Transform, &mut OrbitCamera), With<OrbitCamera>>,
+) {
+    if !path.playing && !path.seek {
+        return;
+    }
+    path.seek = false;
+    let Ok((mut transform, mut orbit)) = camera_query.single_mut() else {
+        return;
+    };
+    let Some(pose) = sample_path(&path.keyframes, path.looped, path.time) else {
+        path.playing = false;
+        path.recording = None;
+        return;
+    };
+    transform.translation = pose.translation();
+    transform.rotation = pose.rotation;
+    orbit.focus = pose.focus;
+    orbit.radius = pose.radius;
+    orbit.focus_target = None;
+    if !path.playing {
+        return;
+    }
+
+    let Some(mut recording) = path.recording else {
+        path.time += time.delta_secs();
+        if !path.looped && path.time > path.duration() {
+            path.playing = false;
+            path.time = path.duration();
+        }
+        return;
+    };
+    #[cfg(feature = "native")]
+    {
+        let created = if recording.frame == 0 {
+            std::fs::create_dir_all(FRAMES_DIR)
+        } else {
+            Ok(())
+        };
+        if let Err(err) = created {
+            notices.write(Notify::error(format!(
+                "Failed to create {}: {}",
+                FRAMES_DIR, err
+            )));
+            path.playing = false;
+            path.recording = None;
+            return;
+        }
+        let file = format!("{}/frame_{:05}.png", FRAMES_DIR, recording.frame);
+        commands
+            .spawn(Screenshot::primary_window())
+            .observe(save_to_disk(file));
+    }
+    recording.frame += 1;
+    if recording.frame < recording.frames {
+        path.time = recording.frame as f32 / path.fps.max(1) as f32;
+        path.recording = Some(recording);
+        return;
+    }
+    path.playing = false;
+    path.recording = None;
+    let message = format!("Exported {} flythrough frames", recording.frames);
+    info!("{}", message);
+    notices.write(Notify::info(message));
+}
+
+pub fn camera_path_panel(
+    mut contexts: EguiContexts,
+    mut path: ResMut<CameraPath>,
+    camera_query: Query<(&Transform, &OrbitCamera)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Camera Path")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let recording = path.recording.is_some();
+            if let (true, Ok((transform, orbit))) = (
+                ui.button("Add current view").clicked(),
+                camera_query.single(),
+            ) {
+                path.keyframes.push(Keyframe {
+                    focus: orbit.focus,
+                    radius: orbit.radius,
+                    rotation: transform.rotation,
+                    duration_secs: 2.0,
+                    easing: Easing::default(),
+                });
+            }
+
+            let mut keyframes = path.keyframes.clone();
+            let mut go_to = None;
+            let mut moved = None;
+            let mut removed = None;
+            let last = keyframes.len().saturating_sub(1);
+            let looped = path.looped;
+            egui::Grid::new("camera_path_keys").show(ui, |ui| {
+                for (i, key) in keyframes.iter_mut().enumerate() {
+                    ui.label(format!("#{}", i + 1));
+                    if ui.small_button("Go").clicked() {
+                        go_to = Some(i);
+                    }
+                    // The last keyframe only travels on when the path loops
+                    ui.add_enabled_ui(i < last || looped, |ui| {
+                        ui.add(
+                            egui::DragValue::new(&mut key.duration_secs)
+                                .speed(0.05)
+                                .range(0.0..=600.0)
+                                .suffix(" s"),
+                        );
+                        egui::ComboBox::from_id_salt(("camera_path_easing", i))
+                            .selected_text(key.easing.label())
+                            .show_ui(ui, |ui| {
+                                for easing in Easing::ALL {
+                                    ui.selectable_value(&mut key.easing, easing, easing.label());
+                                }
+                            });
+                    });
+                    if ui
+                        .add_enabled(i > 0, egui::Button::new("⬆").small())
+                        .clicked()
+                    {
+                        moved = Some((i, i - 1));
+                    }
+                    if ui
+                        .add_enabled(i < last, egui::Button::new("⬇").small())
+                        .clicked()
+                    {
+                        moved = Some((i, i + 1));
+                    }
+                    if ui.small_button("✕").clicked() {
+                        removed = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some((a, b)) = moved {
+                keyframes.swap(a, b);
+            }
+            if let Some(i) = removed {
+                keyframes.remove(i);
+            }
+            if keyframes != path.keyframes {
+                path.keyframes = keyframes;
+            }
+            if let Some(i) = go_to {
+                path.playing = false;
+                path.time = keyframe_time(&path.keyframes, i);
+                path.seek = true;
+            }
+            if path.keyframes.len() < 2 {
+                ui.label("Add at least two views to play a path");
+                return;
+            }
+
+            ui.separator();
+            ui.add_enabled_ui(!recording, |ui| {
+                ui.horizontal(|ui| {
+                    let label = if path.playing { "Pause" } else { "Play" };
+                    if ui.button(label).clicked() {
+                        if !path.playing && !path.looped && path.time >= path.duration() {
+                            path.time = 0.0;
+                        }
+                        path.playing = !path.playing;
+                    }
+                    if ui.button("Stop").clicked() {
+                        path.playing = false;
+                        path.time = 0.0;
+                        path.seek = true;
+                    }
+                    let mut looped = path.looped;
+                    ui.checkbox(&mut looped, "Loop");
+                    if looped != path.looped {
+                        path.looped = looped;
+                    }
+                });
+                let mut time = path.time;
+                let duration = path.duration();
+                ui.add(
+                    egui::Slider::new(&mut time, 0.0..=duration)
+                        .text("Time (s)")
+                        .max_decimals(2),
+                );
+                if time != path.time {
+                    path.time = time;
+                    path.seek = true;
+                }
+            });
+
+            ui.separator();
+            let mut fps = path.fps;
+            ui.add(egui::Slider::new(&mut fps, 1..=120).text("Frames per second"));
+            if fps != path.fps {
+                path.fps = fps;
+            }
+            if let Some(recording) = path.recording {
+                ui.label(format!(
+                    "Exporting frame {} of {}",
+                    recording.frame + 1,
+                    recording.frames
+                ));
+                if ui.button("Cancel").clicked() {
+                    path.playing = false;
+                    path.recording = None;
+                }
+            } else {
+                #[cfg(feature = "native")]
+                if ui.button("Export frames").clicked() {
+                    let frames = (path.duration() * fps as f32).ceil() as usize + 1;
+                    path.recording = Some(Recording { frame: 0, frames });
+                    path.time = 0.0;
+                    path.playing = true;
+                }
+                #[cfg(feature = "native")]
+                ui.label(format!("Frames are written to {}/", FRAMES_DIR));
+            }
+        });
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bevy::ecs::{
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use flate2::read::GzDecoder;
+use zip::ZipArchive;
+
+use crate::import::systems::{ImportQueue, ImportSource, is_obj_path, is_off_path};
+use crate::pointcloud::io::is_point_cloud_path;
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+pub fn is_gzip_path(path: &Path) -> bool {
+    has_extension(path, "gz")
+}
+
+pub fn is_zip_path(path: &Path) -> bool {
+    has_extension(path, "zip")
+}
+
+// `scan.obj.gz` -> `scan.obj`
+pub fn decompressed_path(path: &Path) -> PathBuf {
+    if is_gzip_path(path) {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+// Files the importer can parse, compressed or not
+fn is_importable(path: &Path) -> bool {
+    let path = decompressed_path(path);
+    is_obj_path(&path) || is_off_path(&path) || is_point_cloud_path(&path)
+}
+
+pub fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    GzDecoder::new(bytes)
+        .read_to_end(&mut out)
+        .map_err(|e| format!("not a valid gzip file ({})", e))?;
+    Ok(out)
+}
+
+// Importable files in a zip archive, in archive order
+pub fn archive_entries(bytes: &[u8]) -> Result<Vec<String>, String> {
+    let archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    Ok(archive
+        .file_names()
+        .filter(|name| !name.ends_with('/') && is_importable(Path::new(name)))
+        .map(str::to_string)
+        .collect())
+}
+
+pub fn extract_entry(bytes: &[u8], entry: &str) -> Result<Vec<u8>, String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    let mut file = archive
+        .by_name(entry)
+        .map_err(|e| format!("{}: {}", entry, e))?;
+    let mut out = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut out)
+        .map_err(|e| format!("{}: {}", entry, e))?;
+    Ok(out)
+}
+
+// A zip archive holding several importable files, kept in memory until one
+// is picked
+pub struct ArchiveListing {
+    pub archive: String,
+    pub bytes: Arc<Vec<u8>>,
+    pub entries: Vec<String>,
+    pub selected: usize,
+}
+
+#[derive(Resource, Default)]
+pub struct ArchiveChooser {
+    pub pending: Vec<ArchiveListing>,
+}
+
+pub fn archive_chooser_panel(
+    mut contexts: EguiContexts,
+    mut chooser: ResMut<ArchiveChooser>,
+    queue: Res<ImportQueue>,
+) {
+    if chooser.pending.is_empty() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut opened: Option<Vec<usize>> = None;
+    egui::Window::new("Open from archive")
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let listing = &mut chooser.pending[0];
+            ui.label(format!(
+                "{} holds {} files",
+                listing.archive,
+                listing.entries.len()
+            ));
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    for (i, entry) in listing.entries.iter().enumerate() {
+                        ui.radio_value(&mut listing.selected, i, entry.as_str());
+                    }
+                });
+            ui.horizontal(|ui| {
+                if ui.button("Open").clicked() {
+                    opened = Some(vec![listing.selected]);
+                }
+                if ui.button("Open all").clicked() {
+                    opened = Some((0..listing.entries.len()).collect());
+                }
+                if ui.button("Cancel").clicked() {
+                    opened = Some(Vec::new());
+                }
+            });
+        });
+
+    let Some(opened) = opened else {
+        return;
+    };
+    let listing = chooser.pending.remove(0);
+    for i in opened {
+        queue.push(ImportSource::ArchiveEntry {
+            archive: listing.archive.clone(),
+            bytes: listing.bytes.clone(),
+            entry: listing.entries[i].clone(),
+        });
+    }
+}
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Browser-side file sources: a transient file input, drops onto the page and
+// `?mesh=<url>` query parameters, so a hosted viewer link can open a result
+// directly. Everything lands in the shared `ImportQueue`.
+
+use bevy::log::warn;
+use js_sys::Uint8Array;
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use wasm_bindgen_futures::{JsFuture, spawn_local};
+use web_sys::{DragEvent, Event, File, FileList, HtmlInputElement, Response, UrlSearchParams};
+
+use crate::import::download::url_file_name;
+use crate::import::systems::{ImportQueue, ImportSource};
+
+fn queue_file(queue: ImportQueue, file: File) {
+    spawn_local(async move {
+        let name = file.name();
+        match JsFuture::from(file.array_buffer()).await {
+            Ok(buffer) => queue.push(ImportSource::Bytes {
+                name,
+                bytes: Uint8Array::new(&buffer).to_vec(),
+            }),
+            Err(err) => queue.push_failure(format!("Failed to read {}: {:?}", name, err)),
+        }
+    });
+}
+
+fn queue_files(queue: &ImportQueue, files: Option<FileList>) {
+    let Some(files) = files else {
+        return;
+    };
+    for file in (0..files.length()).filter_map(|i| files.get(i)) {
+        queue_file(queue.clone(), file);
+    }
+}
+
+async fn fetch_bytes(url: &str) -> Result<Vec<u8>, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    let response: Response = JsFuture::from(window.fetch_with_str(url))
+        .await?
+        .dyn_into()?;
+    if !response.ok() {
+        return Err(JsValue::from_str(&format!("HTTP {}", response.status())));
+    }
+    let buffer = JsFuture::from(response.array_buffer()?).await?;
+    Ok(Uint8Array::new(&buffer).to_vec())
+}
+
+pub fn queue_url(queue: ImportQueue, url: String) {
+    spawn_local(async move {
+        let name = url_file_name(&url);
+        match fetch_bytes(&url).await {
+            Ok(bytes) => queue.push(ImportSource::Bytes { name, bytes }),
+            Err(err) => queue.push_failure(format!("Failed to fetch {}: {:?}", url, err)),
+        }
+    });
+}
+
+// Opens the browser's file dialog; must run in response to a user click
+pub fn open_file_picker(queue: &ImportQueue) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let Some(input) = document
+        .create_element("input")
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlInputElement>().ok())
+    else {
+        return;
+    };
+    input.set_type("file");
+    input.set_multiple(true);
+    input.set_accept(".obj,.off,.xyz,.ply,.gz,.zip");
+
+    let queue = queue.clone();
+    let target = input.clone();
+    let on_change = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+        queue_files(&queue, target.files());
+    });
+    input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    // The input lives until the page does; one leaked closure per dialog is fine
+    on_change.forget();
+    input.click();
+}
+
+// Page drag-drop plus any `?mesh=` URLs
+pub fn install_browser_file_sources(queue: &ImportQueue) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(document) = window.document() else {
+        return;
+    };
+
+    // Without cancelling dragover the browser navigates to the dropped file
+    let on_drag_over = Closure::<dyn FnMut(DragEvent)>::new(|event: DragEvent| {
+        event.prevent_default();
+    });
+    let drop_queue = queue.clone();
+    let on_drop = Closure::<dyn FnMut(DragEvent)>::new(move |event: DragEvent| {
+        event.prevent_default();
+        queue_files(
+            &drop_queue,
+            event.data_transfer().and_then(|transfer| transfer.files()),
+        );
+    });
+    for (kind, listener) in [
+        ("dragover", on_drag_over.as_ref()),
+        ("drop", on_drop.as_ref()),
+    ] {
+        if let Err(err) = document.add_event_listener_with_callback(kind, listener.unchecked_ref())
+        {
+            warn!("Failed to listen for {}: {:?}", kind, err);
+        }
+    }
+    on_drag_over.forget();
+    on_drop.forget();
+
+    let search = window.location().search().unwrap_or_default();
+    if let Ok(params) = UrlSearchParams::new_with_str(&search) {
+        for url in params.get_all("mesh").iter().filter_map(|v| v.as_string()) {
+            queue_url(queue.clone(), url);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::ecs::{
+    event::EventWriter,
+    resource::Resource,
+    system::{Query, Res, ResMut},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::CgarMeshData;
+use crate::import::systems::{ImportQueue, ImportSource};
+use crate::mesh::obj::faces_to_obj;
+use crate::mesh::off::is_off_text;
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::selection::components::SelectionSet;
+
+// Extension the importer should treat pasted text as, if it looks like a mesh
+pub fn mesh_text_extension(text: &str) -> Option<&'static str> {
+    if is_off_text(text) {
+        return Some("off");
+    }
+    let mut records = text.lines().map(|line| line.trim_start());
+    let has_vertices = records.clone().any(|line| line.starts_with("v "));
+    let has_faces = records.any(|line| line.starts_with("f "));
+    (has_vertices && has_faces).then_some("obj")
+}
+
+// Tiny meshes shared as text: Ctrl+V over the viewport, or the "Clipboard"
+// window's text box, adds pasted OBJ/OFF geometry to the scene, and the
+// window copies the current face selection back out as OBJ
+#[derive(Resource, Default)]
+pub struct ClipboardGeometry {
+    pub text: String,
+    pasted: usize,
+}
+
+impl ClipboardGeometry {
+    // Hands the text to the importer as an in-memory file so it gets the same
+    // validation and tolerant fallback as anything loaded from disk
+    fn paste(&mut self, text: &str, queue: &ImportQueue) -> Result<String, String> {
+        let extension = mesh_text_extension(text).ok_or("the text isn't OBJ or OFF geometry")?;
+        self.pasted += 1;
+        let name = format!("pasted-{}", self.pasted);
+        queue.push(ImportSource::Bytes {
+            name: format!("{}.{}", name, extension),
+            bytes: text.as_bytes().to_vec(),
+        });
+        Ok(name)
+    }
+}
+
+// Selected faces as OBJ, or the whole mesh when the selection has no faces
+fn selection_obj(
+    selection: &SelectionSet,
+    mesh_query: &Query<&CgarMeshData>,
+) -> Result<(String, usize), String> {
+    let entity = selection.mesh.ok_or("select faces or a mesh to copy")?;
+    let cgar_data = mesh_query
+        .get(entity)
+        .map_err(|_| "the selected mesh is gone".to_string())?;
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    let faces: Vec<usize> = if selection.faces.is_empty() {
+        topology.live_faces().map(|(f, _)| f).collect()
+    } else {
+        selection.faces.iter().copied().collect()
+    };
+    Ok((faces_to_obj(&topology, &faces), faces.len()))
+}
+
+pub fn clipboard_panel(
+    mut contexts: EguiContexts,
+    mut clipboard: ResMut<ClipboardGeometry>,
+    queue: Res<ImportQueue>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<&CgarMeshData>,
+    mut notices: EventWriter<Notify>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    // A paste that no text field took lands in the scene; text that isn't
+    // geometry is ignored quietly since it was probably meant for something else
+    if !ctx.wants_keyboard_input() {
+        let pasted: Vec<String> = ctx.input(|input| {
+            input
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                })
+                .collect()
+        });
+        for text in pasted {
+            if let Ok(name) = clipboard.paste(&text, &queue) {
+                notices.write(Notify::info(format!("Pasting {} from the clipboard", name)));
+            }
+        }
+    }
+
+    egui::Window::new("Clipboard")
+        .default_open(false)
+        .show(ctx, |ui| {
+            ui.label("Paste OBJ or OFF text here, or press Ctrl+V over the viewport");
+            let mut text = clipboard.text.clone();
+            ui.add(
+                egui::TextEdit::multiline(&mut text)
+                    .code_editor()
+                    .desired_rows(6)
+                    .hint_text("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3"),
+            );
+            if text != clipboard.text {
+                clipboard.text = text.clone();
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!text.trim().is_empty(), egui::Button::new("Add to scene"))
+                    .clicked()
+                {
+                    match clipboard.paste(&text, &queue) {
+                        Ok(name) => {
+                            notices.write(Notify::info(format!("Adding {} to the scene", name)));
+                            clipboard.text.clear();
+                        }
+                        Err(err) => {
+                            notices.write(Notify::warning(format!("Can't paste: {}", err)));
+                        }
+                    }
+                }
+                if ui
+                    .button("Copy selection as OBJ")
+                    .on_hover_text(
+                        "Copies the selected faces, or the whole mesh if none are selected",
+                    )
+                    .clicked()
+                {
+                    match selection_obj(&selection, &mesh_query) {
+                        Ok((obj, faces)) => {
+                            ui.ctx().copy_text(obj);
+                            notices.write(Notify::info(format!("Copied {} faces as OBJ", faces)));
+                        }
+                        Err(err) => {
+                            notices.write(Notify::warning(format!("Nothing copied: {}", err)));
+                        }
+                    }
+                }
+            });
+        });
+}
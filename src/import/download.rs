@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::JoinHandle;
+
+use bevy::ecs::{
+    event::EventWriter,
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::budget::memory::format_bytes;
+use crate::import::systems::{ImportQueue, ImportSource};
+use crate::notifications::systems::Notify;
+use crate::utils::cli::CliOptions;
+
+pub fn is_url(text: &str) -> bool {
+    let text = text.trim().to_ascii_lowercase();
+    text.starts_with("http://") || text.starts_with("https://")
+}
+
+// The last path segment names the mesh and picks the parser
+pub fn url_file_name(url: &str) -> String {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("mesh.obj")
+        .to_string()
+}
+
+// Shared between the progress window and a download running on its thread
+#[derive(Default)]
+pub struct DownloadProgress {
+    pub received: AtomicU64,
+    // Zero while the server hasn't said how long the body is
+    pub total: AtomicU64,
+    pub cancel: AtomicBool,
+}
+
+struct Download {
+    url: String,
+    progress: Arc<DownloadProgress>,
+    // A blocking read can stall for as long as the server does, so each
+    // download gets its own thread instead of holding an IO pool worker
+    thread: JoinHandle<Result<PathBuf, String>>,
+}
+
+// Meshes fetched over HTTP(S), from the command line or the "Open URL"
+// window. Desktop builds download into a cache in the temp directory and
+// import from there, reusing earlier downloads of the same URL; the browser
+// build fetches straight into memory.
+#[derive(Resource)]
+pub struct UrlDownloads {
+    pub url: String,
+    pub use_cache: bool,
+    pending: Vec<String>,
+    jobs: Vec<Download>,
+}
+
+impl UrlDownloads {
+    pub fn from_options(options: &CliOptions) -> Self {
+        Self {
+            url: String::new(),
+            use_cache: true,
+            pending: options.mesh_urls.clone(),
+            jobs: Vec::new(),
+        }
+    }
+
+    pub fn request(&mut self, url: impl Into<String>) {
+        self.pending.push(url.into());
+    }
+}
+
+// Gated like the ureq dependency it uses
+#[cfg(not(target_arch = "wasm32"))]
+mod fetch {
+    use std::fs::File;
+    use std::hash::{DefaultHasher, Hash, Hasher};
+    use std::io::{Read, Write};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::Ordering;
+
+    use super::{DownloadProgress, url_file_name};
+    use crate::utils::constants::DOWNLOAD_CACHE_DIR;
+
+    // One directory per URL, so files with the same name from different
+    // places don't collide and the file keeps its own name and extension
+    pub fn cache_path(url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        std::env::temp_dir()
+            .join(DOWNLOAD_CACHE_DIR)
+            .join(format!("{:016x}", hasher.finish()))
+            .join(url_file_name(url))
+    }
+
+    // Streams the body next to `path` and renames it into place once it is
+    // complete, so an interrupted download is never mistaken for a cached one
+    pub fn download(url: &str, path: &Path, progress: &DownloadProgress) -> Result<(), String> {
+        let response = ureq::get(url).call().map_err(|e| e.to_string())?;
+        if let Some(total) = response
+            .header("Content-Length")
+            .and_then(|length| length.parse().ok())
+        {
+            progress.total.store(total, Ordering::Relaxed);
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        let partial = PathBuf::from(partial);
+        let mut out = File::create(&partial).map_err(|e| e.to_string())?;
+        let mut reader = response.into_reader();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            if progress.cancel.load(Ordering::Relaxed) {
+                drop(out);
+                let _ = std::fs::remove_file(&partial);
+                return Err("cancelled".to_string());
+            }
+            let read = reader.read(&mut buffer).map_err(|e| e.to_string())?;
+            if read == 0 {
+                break;
+            }
+            out.write_all(&buffer[..read]).map_err(|e| e.to_string())?;
+            progress.received.fetch_add(read as u64, Ordering::Relaxed);
+        }
+        out.flush().map_err(|e| e.to_string())?;
+        std::fs::rename(&partial, path).map_err(|e| e.to_string())
+    }
+}
+
+pub fn run_downloads(
+    mut downloads: ResMut<UrlDownloads>,
+    queue: Res<ImportQueue>,
+    mut notices: EventWriter<Notify>,
+) {
+    for url in std::mem::take(&mut downloads.pending) {
+        let url = url.trim().to_string();
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        crate::import::browser::queue_url(queue.clone(), url);
+        #[cfg(all(target_arch = "wasm32", not(feature = "web")))]
+        notices.write(Notify::error(format!(
+            "Can't download {}: this build has no HTTP client",
+            url
+        )));
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let path = fetch::cache_path(&url);
+            if downloads.use_cache && path.is_file() {
+                notices.write(Notify::info(format!("Opening cached copy of {}", url)));
+                queue.push(ImportSource::Path(path));
+                continue;
+            }
+            let progress = Arc::new(DownloadProgress::default());
+            let task_progress = progress.clone();
+            let task_url = url.clone();
+            let spawned = std::thread::Builder::new()
+                .name("cgar-viewer download".to_string())
+                .spawn(move || fetch::download(&task_url, &path, &task_progress).map(|()| path));
+            match spawned {
+                Ok(thread) => downloads.jobs.push(Download {
+                    url,
+                    progress,
+                    thread,
+                }),
+                Err(err) => {
+                    notices.write(Notify::error(format!(
+                        "Failed to download {}: {}",
+                        url, err
+                    )));
+                }
+            }
+        }
+    }
+
+    let mut i = 0;
+    while i < downloads.jobs.len() {
+        if !downloads.jobs[i].thread.is_finished() {
+            i += 1;
+            continue;
+        }
+        let download = downloads.jobs.swap_remove(i);
+        let result = download
+            .thread
+            .join()
+            .unwrap_or_else(|_| Err("the download thread panicked".to_string()));
+        match result {
+            Ok(path) => {
+                notices.write(Notify::info(format!("Downloaded {}", download.url)));
+                queue.push(ImportSource::Path(path));
+            }
+            Err(_) if download.progress.cancel.load(Ordering::Relaxed) => {
+                notices.write(Notify::info(format!(
+                    "Download of {} cancelled",
+                    download.url
+                )));
+            }
+            Err(err) => {
+                notices.write(Notify::error(format!(
+                    "Failed to download {}: {}",
+                    download.url, err
+                )));
+            }
+        }
+    }
+}
+
+pub fn open_url_panel(mut contexts: EguiContexts, mut downloads: ResMut<UrlDownloads>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Open URL")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut url = downloads.url.clone();
+            let open = ui
+                .horizontal(|ui| {
+                    ui.text_edit_singleline(&mut url);
+                    ui.add_enabled(is_url(&url), egui::Button::new("Open"))
+                        .clicked()
+                })
+                .inner;
+            if url != downloads.url {
+                downloads.url = url.clone();
+            }
+            if open {
+                downloads.request(url);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let mut use_cache = downloads.use_cache;
+                ui.checkbox(&mut use_cache, "Reuse earlier downloads")
+                    .on_hover_text("Open the cached copy of a URL that was downloaded before");
+                if use_cache != downloads.use_cache {
+                    downloads.use_cache = use_cache;
+                }
+            }
+            ui.label("Downloads .obj, .off, .xyz or .ply files, also in .gz or .zip");
+        });
+}
+
+pub fn download_progress_panel(mut contexts: EguiContexts, downloads: Res<UrlDownloads>) {
+    if downloads.jobs.is_empty() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Downloading")
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            for download in &downloads.jobs {
+                let progress = &download.progress;
+                let received = progress.received.load(Ordering::Relaxed);
+                let total = progress.total.load(Ordering::Relaxed);
+                ui.label(&download.url);
+                ui.horizontal(|ui| {
+                    let bar = if total > 0 {
+                        egui::ProgressBar::new(received as f32 / total as f32).text(format!(
+                            "{} / {}",
+                            format_bytes(received as usize),
+                            format_bytes(total as usize)
+                        ))
+                    } else {
+                        egui::ProgressBar::new(0.0)
+                            .animate(true)
+                            .text(format_bytes(received as usize))
+                    };
+                    ui.add(bar);
+                    if progress.cancel.load(Ordering::Relaxed) {
+                        ui.label("Cancelling...");
+                    } else if ui.button("Cancel").clicked() {
+                        progress.cancel.store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+            ui.ctx().request_repaint();
+        });
+}
@@ -0,0 +1,319 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        entity::Entity,
+        event::EventWriter,
+        resource::Resource,
+        system::{Commands, Res, ResMut},
+    },
+    math::Vec3,
+    render::mesh::Mesh,
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future},
+    transform::components::Transform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::import::systems::{ImportSettings, is_obj_path};
+use crate::mesh::conversion::vertex_position;
+use crate::mesh::obj::{LoadedMesh, load_obj_file};
+use crate::mesh::setup::{DefaultMeshMaterial, MeshSource, spawn_loaded_mesh};
+use crate::notifications::systems::Notify;
+
+// Gap between grid cells as a fraction of the largest mesh
+const GRID_MARGIN: f32 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FolderLayout {
+    // Side by side on the ground plane, one cell per file
+    #[default]
+    Grid,
+    // Where the files put them, for outputs that share a coordinate frame
+    Original,
+}
+
+impl FolderLayout {
+    pub const ALL: [FolderLayout; 2] = [FolderLayout::Grid, FolderLayout::Original];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FolderLayout::Grid => "Grid",
+            FolderLayout::Original => "Original coordinates",
+        }
+    }
+}
+
+// A folder file being parsed on the task pool, with its place in name order
+struct FolderJob {
+    index: usize,
+    path: PathBuf,
+    task: Task<Result<(LoadedMesh, Vec3, Vec3), String>>,
+}
+
+// Imports every .obj in a directory as its own named mesh, parsing the files
+// concurrently and spawning each as it finishes, then lays the batch out
+#[derive(Resource, Default)]
+pub struct FolderImport {
+    pub layout: FolderLayout,
+    pub folder: Option<PathBuf>,
+    // Files not handed to the task pool yet
+    pub pending: Vec<PathBuf>,
+    jobs: Vec<FolderJob>,
+    pub total: usize,
+    pub failed: usize,
+    pub cancel_requested: bool,
+    // Spawned meshes with their name order and local bounds, placed once the
+    // batch is done
+    placed: Vec<(usize, Entity, Vec3, Vec3)>,
+}
+
+impl FolderImport {
+    pub fn active(&self) -> bool {
+        self.folder.is_some()
+    }
+
+    pub fn done(&self) -> usize {
+        self.total - self.pending.len() - self.jobs.len()
+    }
+
+    // Queues the folder's mesh files in name order
+    pub fn start(&mut self, folder: &Path) -> Result<usize, String> {
+        if self.active() {
+            return Err("a folder import is already running".to_string());
+        }
+        let mut files: Vec<PathBuf> = std::fs::read_dir(folder)
+            .map_err(|e| format!("{}: {}", folder.display(), e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && is_obj_path(path))
+            .collect();
+        if files.is_empty() {
+            return Err(format!("{}: no .obj files found", folder.display()));
+        }
+        files.sort();
+        self.folder = Some(folder.to_path_buf());
+        self.total = files.len();
+        self.failed = 0;
+        self.cancel_requested = false;
+        self.pending = files;
+        self.jobs.clear();
+        self.placed.clear();
+        Ok(self.total)
+    }
+
+    // Hands every queued file to the task pool, which parses as many at once
+    // as it has threads
+    fn spawn_jobs(&mut self, settings: &ImportSettings) {
+        let first = self.total - self.pending.len();
+        for (offset, path) in std::mem::take(&mut self.pending).into_iter().enumerate() {
+            let task_path = path.clone();
+            let settings = settings.clone();
+            let task = AsyncComputeTaskPool::get().spawn(async move {
+                let loaded = load_obj_file(&task_path, &settings)?;
+                let (min, max) = (0..loaded.mesh.vertices.len())
+                    .map(|v| vertex_position(&loaded.mesh, v))
+                    .fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), p| {
+                        (min.min(p), max.max(p))
+                    });
+                Ok((loaded, min, max))
+            });
+            self.jobs.push(FolderJob {
+                index: first + offset,
+                path,
+                task,
+            });
+        }
+    }
+}
+
+// Row-major grid on the XZ plane centered on the origin, with every mesh
+// centered in its cell
+fn grid_transforms(placed: &[(Entity, Vec3, Vec3)]) -> Vec<(Entity, Transform)> {
+    let cell = placed
+        .iter()
+        .map(|(_, min, max)| (*max - *min).max_element())
+        .fold(0.0f32, f32::max)
+        .max(f32::EPSILON)
+        * (1.0 + GRID_MARGIN);
+    let columns = (placed.len() as f32).sqrt().ceil().max(1.0) as usize;
+    let rows = placed.len().div_ceil(columns);
+    let origin = Vec3::new(
+        (columns - 1) as f32 * cell * 0.5,
+        0.0,
+        (rows - 1) as f32 * cell * 0.5,
+    );
+    placed
+        .iter()
+        .enumerate()
+        .map(|(i, &(entity, min, max))| {
+            let slot = Vec3::new(
+                (i % columns) as f32 * cell,
+                0.0,
+                (i / columns) as f32 * cell,
+            );
+            let center = (min + max) * 0.5;
+            (entity, Transform::from_translation(slot - origin - center))
+        })
+        .collect()
+}
+
+pub fn process_folder_import(
+    mut commands: Commands,
+    mut folder: ResMut<FolderImport>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Option<Res<DefaultMeshMaterial>>,
+    settings: Res<ImportSettings>,
+    mut notices: EventWriter<Notify>,
+) {
+    let Some(material) = material.filter(|_| folder.active()) else {
+        return;
+    };
+    if folder.cancel_requested {
+        // Dropping the tasks cancels the files that haven't started parsing
+        let jobs = std::mem::take(&mut folder.jobs);
+        folder.pending.extend(jobs.into_iter().map(|job| job.path));
+    } else {
+        folder.spawn_jobs(&settings);
+    }
+
+    let mut i = 0;
+    while i < folder.jobs.len() {
+        let Some(result) = block_on(future::poll_once(&mut folder.jobs[i].task)) else {
+            i += 1;
+            continue;
+        };
+        let FolderJob { index, path, .. } = folder.jobs.swap_remove(i);
+        let (loaded, min, max) = match result {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                folder.failed += 1;
+                notices.write(Notify::error(format!("Failed to import {}", err)));
+                continue;
+            }
+        };
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let spawned = spawn_loaded_mesh(
+            &mut commands,
+            &mut meshes,
+            &material,
+            name,
+            loaded,
+            &mut notices,
+        );
+        match spawned {
+            Some(entity) => {
+                commands.entity(entity).insert(MeshSource(path));
+                folder.placed.push((index, entity, min, max));
+            }
+            None => folder.failed += 1,
+        }
+    }
+    if !folder.jobs.is_empty() {
+        return;
+    }
+
+    // Files finish in any order; lay them out in name order
+    folder.placed.sort_unstable_by_key(|&(index, ..)| index);
+    if folder.layout == FolderLayout::Grid {
+        let placed: Vec<(Entity, Vec3, Vec3)> = folder
+            .placed
+            .iter()
+            .map(|&(_, entity, min, max)| (entity, min, max))
+            .collect();
+        for (entity, transform) in grid_transforms(&placed) {
+            commands.entity(entity).insert(transform);
+        }
+    }
+    let imported = folder.placed.len();
+    let skipped = folder.pending.len();
+    let name = folder
+        .folder
+        .take()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
+    notices.write(Notify::info(format!(
+        "Imported {} meshes from {}{}{}",
+        imported,
+        name,
+        if folder.failed > 0 {
+            format!(", {} failed", folder.failed)
+        } else {
+            String::new()
+        },
+        if skipped > 0 {
+            format!(", {} skipped", skipped)
+        } else {
+            String::new()
+        }
+    )));
+    folder.pending.clear();
+    folder.placed.clear();
+    folder.cancel_requested = false;
+}
+
+pub fn folder_import_panel(mut contexts: EguiContexts, mut folder: ResMut<FolderImport>) {
+    if !folder.active() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Importing folder")
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            if let Some(path) = &folder.folder {
+                ui.label(path.display().to_string());
+            }
+            let (done, total) = (folder.done(), folder.total);
+            ui.add(
+                egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                    .text(format!("{} / {}", done, total)),
+            );
+            let loading: Vec<String> = folder
+                .jobs
+                .iter()
+                .take(3)
+                .filter_map(|job| job.path.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .collect();
+            let more = folder.jobs.len().saturating_sub(loading.len());
+            if more > 0 {
+                ui.label(format!("Loading {} and {} more", loading.join(", "), more));
+            } else if !loading.is_empty() {
+                ui.label(format!("Loading {}", loading.join(", ")));
+            }
+            if folder.failed > 0 {
+                ui.label(format!("{} failed", folder.failed));
+            }
+            if ui.button("Cancel").clicked() {
+                folder.cancel_requested = true;
+            }
+        });
+}
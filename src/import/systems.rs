@@ -0,0 +1,501 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        event::{EventReader, EventWriter},
+        resource::Resource,
+        system::{Commands, Local, Res, ResMut},
+    },
+    render::mesh::Mesh,
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future},
+    transform::components::Transform,
+    window::FileDragAndDrop,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::import::archive::{
+    ArchiveChooser, ArchiveListing, archive_entries, decompressed_path, extract_entry, gunzip,
+    is_gzip_path, is_zip_path,
+};
+use crate::import::folder::FolderImport;
+#[cfg(feature = "native")]
+use crate::import::folder::FolderLayout;
+use crate::mesh::obj::{LoadedMesh, load_obj_file, load_obj_text};
+use crate::mesh::off::load_off_text;
+use crate::mesh::setup::{DefaultMeshMaterial, MeshSource, spawn_loaded_mesh};
+use crate::notifications::systems::Notify;
+use crate::pointcloud::components::PointCloud;
+use crate::pointcloud::io::{is_point_cloud_path, parse_point_cloud, read_point_cloud};
+use crate::pointcloud::systems::spawn_point_cloud;
+use crate::utils::cli::CliOptions;
+
+// A file to load, either from disk, already read into memory by the browser,
+// or a file inside a zip archive held in memory
+pub enum ImportSource {
+    Path(PathBuf),
+    Bytes {
+        name: String,
+        bytes: Vec<u8>,
+    },
+    ArchiveEntry {
+        archive: String,
+        bytes: Arc<Vec<u8>>,
+        entry: String,
+    },
+}
+
+impl ImportSource {
+    fn path(&self) -> &Path {
+        match self {
+            ImportSource::Path(path) => path,
+            ImportSource::Bytes { name, .. } => Path::new(name),
+            ImportSource::ArchiveEntry { entry, .. } => Path::new(entry),
+        }
+    }
+
+    fn name(&self) -> String {
+        decompressed_path(self.path())
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "import".to_string())
+    }
+}
+
+enum Imported {
+    Mesh(LoadedMesh),
+    Cloud(PointCloud),
+    // A zip archive with more than one importable file
+    Archive(ArchiveListing),
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ImportSettings {
+    // Clean up non-manifold soups instead of handing them to the strict reader
+    pub tolerant: bool,
+    // Shade new meshes with recomputed normals even when the file has its own
+    pub recompute_normals: bool,
+}
+
+impl ImportSettings {
+    pub fn from_options(options: &CliOptions) -> Self {
+        Self {
+            tolerant: options.tolerant,
+            recompute_normals: options.recompute_normals,
+        }
+    }
+}
+
+pub fn is_obj_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("obj"))
+}
+
+pub fn is_off_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("off"))
+}
+
+// Decompresses `.gz` files and opens `.zip` archives in memory: an archive
+// with a single importable file loads it, one with several asks which to open
+fn unpack(source: &ImportSource, settings: &ImportSettings) -> Option<Result<Imported, String>> {
+    let path = source.path();
+    if let ImportSource::ArchiveEntry {
+        archive,
+        bytes,
+        entry,
+    } = source
+    {
+        let bytes = match extract_entry(bytes, entry) {
+            Ok(bytes) => bytes,
+            Err(err) => return Some(Err(format!("{}: {}", archive, err))),
+        };
+        let name = entry.clone();
+        return Some(load(&ImportSource::Bytes { name, bytes }, settings));
+    }
+    if !is_gzip_path(path) && !is_zip_path(path) {
+        return None;
+    }
+    let bytes = match source {
+        ImportSource::Path(path) => match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) => return Some(Err(format!("{}: {}", path.display(), err))),
+        },
+        ImportSource::Bytes { bytes, .. } => bytes.clone(),
+        ImportSource::ArchiveEntry { .. } => unreachable!("archive entries are extracted above"),
+    };
+    let archive = path.display().to_string();
+    if is_gzip_path(path) {
+        let name = decompressed_path(path).to_string_lossy().into_owned();
+        return Some(
+            gunzip(&bytes)
+                .map_err(|e| format!("{}: {}", archive, e))
+                .and_then(|bytes| load(&ImportSource::Bytes { name, bytes }, settings)),
+        );
+    }
+    let mut entries = match archive_entries(&bytes) {
+        Ok(entries) => entries,
+        Err(err) => return Some(Err(format!("{}: {}", archive, err))),
+    };
+    let bytes = Arc::new(bytes);
+    Some(match entries.len() {
+        0 => Err(format!(
+            "{}: no .obj, .off, .xyz or .ply files inside",
+            archive
+        )),
+        1 => load(
+            &ImportSource::ArchiveEntry {
+                archive,
+                bytes,
+                entry: entries.remove(0),
+            },
+            settings,
+        ),
+        _ => Ok(Imported::Archive(ArchiveListing {
+            archive,
+            bytes,
+            entries,
+            selected: 0,
+        })),
+    })
+}
+
+fn load(source: &ImportSource, settings: &ImportSettings) -> Result<Imported, String> {
+    if let Some(unpacked) = unpack(source, settings) {
+        return unpacked;
+    }
+    let path = source.path();
+    if is_point_cloud_path(path) {
+        return match source {
+            ImportSource::Bytes { bytes, .. } => parse_point_cloud(path, Cursor::new(bytes)),
+            _ => read_point_cloud(path),
+        }
+        .map(Imported::Cloud);
+    }
+    let off = is_off_path(path);
+    if !is_obj_path(path) && !off {
+        return Err(format!(
+            "{}: unsupported file type (expected .obj, .off, .xyz or .ply, optionally in .gz or .zip)",
+            path.display()
+        ));
+    }
+    let load_text = |text: &str| {
+        if off {
+            load_off_text(text, settings)
+        } else {
+            load_obj_text(text, settings)
+        }
+    };
+    match source {
+        ImportSource::Bytes { bytes, .. } => std::str::from_utf8(bytes)
+            .map_err(|e| e.to_string())
+            .and_then(load_text)
+            .map_err(|e| format!("{}: {}", path.display(), e)),
+        _ if off => std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| load_text(&text))
+            .map_err(|e| format!("{}: {}", path.display(), e)),
+        _ => load_obj_file(path, settings),
+    }
+    .map(Imported::Mesh)
+}
+
+// Files waiting to be loaded, or reasons they couldn't be read. Cloning shares
+// the queue, so browser callbacks running outside the schedule can push into it.
+#[derive(Resource, Clone, Default)]
+pub struct ImportQueue(Arc<Mutex<Vec<Result<ImportSource, String>>>>);
+
+impl ImportQueue {
+    pub fn push(&self, source: ImportSource) {
+        if let Ok(mut pending) = self.0.lock() {
+            pending.push(Ok(source));
+        }
+    }
+
+    pub fn push_failure(&self, message: String) {
+        if let Ok(mut pending) = self.0.lock() {
+            pending.push(Err(message));
+        }
+    }
+
+    fn take(&self) -> Vec<Result<ImportSource, String>> {
+        self.0
+            .lock()
+            .map(|mut pending| std::mem::take(&mut *pending))
+            .unwrap_or_default()
+    }
+}
+
+// Files dropped onto the native window; the browser build installs its own
+// DOM listeners since winit doesn't hand over dropped file contents there.
+// A dropped directory is imported as a folder.
+pub fn queue_dropped_files(
+    mut events: EventReader<FileDragAndDrop>,
+    queue: Res<ImportQueue>,
+    mut folder: ResMut<FolderImport>,
+    mut notices: EventWriter<Notify>,
+) {
+    for event in events.read() {
+        let FileDragAndDrop::DroppedFile { path_buf, .. } = event else {
+            continue;
+        };
+        if !path_buf.is_dir() {
+            queue.push(ImportSource::Path(path_buf.clone()));
+        } else if let Err(err) = folder.start(path_buf) {
+            notices.write(Notify::error(format!("Failed to open folder {}", err)));
+        }
+    }
+}
+
+// Hooks up the page's drag-drop and `?mesh=` sources; native drops arrive as
+// window events instead
+pub fn install_file_sources(
+    #[cfg_attr(
+        not(all(target_arch = "wasm32", feature = "web")),
+        allow(unused_variables)
+    )]
+    queue: Res<ImportQueue>,
+) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    crate::import::browser::install_browser_file_sources(&queue);
+}
+
+// A queued file being parsed on the task pool
+struct ImportJob {
+    name: String,
+    path: PathBuf,
+    task: Task<Result<Imported, String>>,
+}
+
+// Files from the queue that are still being parsed. Each is spawned as soon
+// as it finishes, so a batch shows up one mesh at a time.
+#[derive(Resource, Default)]
+pub struct ImportJobs {
+    jobs: Vec<ImportJob>,
+    // Files in the current batch, counting finished ones until it drains
+    total: usize,
+    failed: usize,
+}
+
+impl ImportJobs {
+    fn spawn(&mut self, source: ImportSource, settings: &ImportSettings) {
+        let name = source.name();
+        let path = source.path().to_path_buf();
+        let settings = settings.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move { load(&source, &settings) });
+        self.jobs.push(ImportJob { name, path, task });
+        self.total += 1;
+    }
+
+    fn done(&self) -> usize {
+        self.total - self.jobs.len()
+    }
+}
+
+pub fn process_imports(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Option<Res<DefaultMeshMaterial>>,
+    mut notices: EventWriter<Notify>,
+    queue: Res<ImportQueue>,
+    settings: Res<ImportSettings>,
+    mut jobs: ResMut<ImportJobs>,
+    mut chooser: ResMut<ArchiveChooser>,
+) {
+    // The shared material is created at startup; keep files queued until then
+    let Some(material) = material else {
+        return;
+    };
+    for source in queue.take() {
+        match source {
+            Ok(source) => jobs.spawn(source, &settings),
+            Err(message) => {
+                notices.write(Notify::error(message));
+            }
+        }
+    }
+
+    let mut index = 0;
+    while index < jobs.jobs.len() {
+        let Some(result) = block_on(future::poll_once(&mut jobs.jobs[index].task)) else {
+            index += 1;
+            continue;
+        };
+        let ImportJob { name, path, .. } = jobs.jobs.swap_remove(index);
+        match result {
+            Ok(Imported::Mesh(loaded)) => {
+                let spawned = spawn_loaded_mesh(
+                    &mut commands,
+                    &mut meshes,
+                    &material,
+                    name.clone(),
+                    loaded,
+                    &mut notices,
+                );
+                match spawned {
+                    Some(entity) => {
+                        commands.entity(entity).insert(MeshSource(path));
+                        notices.write(Notify::info(format!("Imported mesh {}", name)));
+                    }
+                    None => jobs.failed += 1,
+                }
+            }
+            Ok(Imported::Cloud(cloud)) => {
+                notices.write(Notify::info(format!(
+                    "Imported point cloud {} ({} points)",
+                    name,
+                    cloud.len()
+                )));
+                spawn_point_cloud(&mut commands, name, cloud, Transform::default());
+            }
+            Ok(Imported::Archive(listing)) => {
+                chooser.pending.push(listing);
+            }
+            Err(err) => {
+                jobs.failed += 1;
+                notices.write(Notify::error(format!("Failed to import {}", err)));
+            }
+        }
+    }
+    if jobs.jobs.is_empty() {
+        jobs.total = 0;
+        jobs.failed = 0;
+    }
+}
+
+// One dialog for a batch of files instead of a notice per file while they load
+pub fn import_progress_panel(mut contexts: EguiContexts, mut jobs: ResMut<ImportJobs>) {
+    if jobs.total < 2 || jobs.jobs.is_empty() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Importing files")
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let (done, total) = (jobs.done(), jobs.total);
+            ui.add(
+                egui::ProgressBar::new(done as f32 / total as f32)
+                    .text(format!("{} / {}", done, total)),
+            );
+            let loading: Vec<&str> = jobs
+                .jobs
+                .iter()
+                .take(3)
+                .map(|job| job.name.as_str())
+                .collect();
+            let more = jobs.jobs.len() - loading.len();
+            ui.label(if more > 0 {
+                format!("Loading {} and {} more", loading.join(", "), more)
+            } else {
+                format!("Loading {}", loading.join(", "))
+            });
+            if jobs.failed > 0 {
+                ui.label(format!("{} failed", jobs.failed));
+            }
+            // Dropping a task cancels it if it hasn't started parsing yet
+            if ui.button("Cancel").clicked() {
+                jobs.jobs.clear();
+                jobs.total = 0;
+                jobs.failed = 0;
+            }
+            ui.ctx().request_repaint();
+        });
+}
+
+pub fn import_panel(
+    mut contexts: EguiContexts,
+    queue: Res<ImportQueue>,
+    mut settings: ResMut<ImportSettings>,
+    #[cfg(feature = "native")] mut path: Local<String>,
+    #[cfg(feature = "native")] mut folder: ResMut<FolderImport>,
+    #[cfg(feature = "native")] mut notices: EventWriter<Notify>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Open").show(ctx, |ui| {
+        #[cfg(feature = "native")]
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut *path);
+            if ui.button("Load").clicked() && !path.trim().is_empty() {
+                queue.push(ImportSource::Path(PathBuf::from(path.trim())));
+            }
+        });
+        #[cfg(feature = "native")]
+        ui.horizontal(|ui| {
+            let open = ui
+                .add_enabled(!folder.active(), egui::Button::new("Open folder"))
+                .on_hover_text("Import every .obj in the directory above as separate meshes")
+                .clicked();
+            let started = if open && !path.trim().is_empty() {
+                folder.start(Path::new(path.trim())).map(|_| ())
+            } else {
+                Ok(())
+            };
+            if let Err(err) = started {
+                notices.write(Notify::error(format!("Failed to open folder {}", err)));
+            }
+            let mut layout = folder.layout;
+            egui::ComboBox::from_id_salt("folder_layout")
+                .selected_text(layout.label())
+                .show_ui(ui, |ui| {
+                    for option in FolderLayout::ALL {
+                        ui.selectable_value(&mut layout, option, option.label());
+                    }
+                });
+            if layout != folder.layout {
+                folder.layout = layout;
+            }
+        });
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        if ui.button("Choose files...").clicked() {
+            crate::import::browser::open_file_picker(&queue);
+        }
+        ui.label(
+            "Or drop .obj, .off, .xyz or .ply files (also .gz or .zip), or a folder, onto the window",
+        );
+
+        let mut tolerant = settings.tolerant;
+        ui.checkbox(&mut tolerant, "Tolerant import").on_hover_text(
+            "Split non-manifold edges into separate shells instead of refusing the mesh",
+        );
+        if tolerant != settings.tolerant {
+            settings.tolerant = tolerant;
+        }
+        let mut recompute = settings.recompute_normals;
+        ui.checkbox(&mut recompute, "Recompute normals")
+            .on_hover_text("Ignore normals stored in the file when shading new meshes");
+        if recompute != settings.recompute_normals {
+            settings.recompute_normals = recompute;
+        }
+    });
+}
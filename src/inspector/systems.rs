@@ -0,0 +1,375 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(feature = "native")]
+use std::path::PathBuf;
+
+#[cfg(feature = "native")]
+use bevy::ecs::event::EventWriter;
+use bevy::ecs::{
+    entity::Entity,
+    name::Name,
+    resource::Resource,
+    system::{Commands, Query, Res, ResMut},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::layers::{AnalysisLayerKind, ScalarField, clear_layer, set_scalar_layer};
+use crate::camera::components::CgarMeshData;
+use crate::mesh::attributes::{
+    AttributeDomain, AttributeKind, AttributeValues, MeshAttributes, edge_order,
+};
+#[cfg(feature = "native")]
+use crate::mesh::export::write_ply;
+use crate::mesh::topology::MeshTopology;
+#[cfg(feature = "native")]
+use crate::notifications::systems::Notify;
+use crate::selection::components::SelectionSet;
+
+#[derive(Resource)]
+pub struct AttributeInspector {
+    pub selected: Option<(AttributeDomain, String)>,
+    pub element: usize,
+    pub new_name: String,
+    pub new_domain: AttributeDomain,
+    pub new_kind: AttributeKind,
+    // Value written to every selected element by "Set on selection"
+    pub fill_value: f64,
+}
+
+impl Default for AttributeInspector {
+    fn default() -> Self {
+        Self {
+            selected: None,
+            element: 0,
+            new_name: String::new(),
+            new_domain: AttributeDomain::Face,
+            new_kind: AttributeKind::Float,
+            fill_value: 0.0,
+        }
+    }
+}
+
+// Selected elements of `domain`, as indices into a layer on that domain
+fn selected_elements(
+    selection: &SelectionSet,
+    domain: AttributeDomain,
+    topology: &MeshTopology,
+) -> Vec<usize> {
+    match domain {
+        AttributeDomain::Vertex => selection.vertices.iter().copied().collect(),
+        AttributeDomain::Face => selection.faces.iter().copied().collect(),
+        AttributeDomain::Edge => edge_order(topology)
+            .iter()
+            .enumerate()
+            .filter(|(_, (v0, v1))| selection.contains_edge(*v0, *v1))
+            .map(|(i, _)| i)
+            .collect(),
+    }
+}
+
+// Per-face values for coloring; vertex layers are averaged over each face's corners
+fn face_values(
+    values: &AttributeValues,
+    domain: AttributeDomain,
+    topology: &MeshTopology,
+) -> Option<Vec<f64>> {
+    let mut per_face = vec![f64::NAN; topology.triangles.len()];
+    for (f, tri) in topology.live_faces() {
+        per_face[f] = match domain {
+            AttributeDomain::Face => values.get_f64(f).unwrap_or(f64::NAN),
+            AttributeDomain::Vertex => {
+                tri.iter()
+                    .map(|&v| values.get_f64(v).unwrap_or(f64::NAN))
+                    .sum::<f64>()
+                    / 3.0
+            }
+            AttributeDomain::Edge => return None,
+        };
+    }
+    Some(per_face)
+}
+
+pub fn attribute_inspector_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut inspector: ResMut<AttributeInspector>,
+    selection: Res<SelectionSet>,
+    #[cfg(feature = "native")] mut notices: EventWriter<Notify>,
+    mut mesh_query: Query<(
+        Entity,
+        Option<&Name>,
+        &CgarMeshData,
+        Option<&mut MeshAttributes>,
+    )>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let target = selection
+        .mesh
+        .filter(|e| mesh_query.contains(*e))
+        .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+
+    egui::Window::new("Attributes").show(ctx, |ui| {
+        let Some((entity, name, cgar_data, mut attributes)) =
+            target.and_then(|e| mesh_query.get_mut(e).ok())
+        else {
+            ui.label("No mesh loaded");
+            return;
+        };
+        let label = match name {
+            Some(name) => format!("{} ({})", name, entity),
+            None => format!("{}", entity),
+        };
+        ui.label(format!("Mesh: {}", label));
+        let topology = MeshTopology::from_cgar(&cgar_data.0);
+
+        // Layer list
+        let keys: Vec<(AttributeDomain, String)> = attributes
+            .as_deref()
+            .map(|attributes| attributes.layers.keys().cloned().collect())
+            .unwrap_or_default();
+        if keys.is_empty() {
+            ui.label("No attribute layers");
+        }
+        let mut selected = inspector.selected.clone();
+        for key in &keys {
+            let Some(values) = attributes.as_deref().and_then(|a| a.layers.get(key)) else {
+                continue;
+            };
+            let text = format!(
+                "{} / {} [{}] ({})",
+                key.0.label(),
+                key.1,
+                values.kind().label(),
+                values.len()
+            );
+            ui.radio_value(&mut selected, Some(key.clone()), text);
+        }
+        if selected != inspector.selected {
+            inspector.selected = selected;
+            inspector.element = 0;
+        }
+
+        // New layer
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("New layer");
+            ui.text_edit_singleline(&mut inspector.new_name);
+        });
+        let (mut domain, mut kind) = (inspector.new_domain, inspector.new_kind);
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("attribute_domain")
+                .selected_text(domain.label())
+                .show_ui(ui, |ui| {
+                    for option in AttributeDomain::ALL {
+                        ui.selectable_value(&mut domain, option, option.label());
+                    }
+                });
+            egui::ComboBox::from_id_salt("attribute_kind")
+                .selected_text(kind.label())
+                .show_ui(ui, |ui| {
+                    for option in AttributeKind::ALL {
+                        ui.selectable_value(&mut kind, option, option.label());
+                    }
+                });
+        });
+        if (domain, kind) != (inspector.new_domain, inspector.new_kind) {
+            inspector.new_domain = domain;
+            inspector.new_kind = kind;
+        }
+        let new_name = inspector.new_name.trim().to_string();
+        if ui
+            .add_enabled(!new_name.is_empty(), egui::Button::new("Add layer"))
+            .clicked()
+        {
+            let values = AttributeValues::new(kind, domain.element_count(&topology));
+            match attributes.as_mut() {
+                Some(attributes) => attributes.insert(domain, new_name.clone(), values),
+                None => {
+                    let mut layers = MeshAttributes::default();
+                    layers.insert(domain, new_name.clone(), values);
+                    commands.entity(entity).insert(layers);
+                }
+            }
+            inspector.selected = Some((domain, new_name));
+            inspector.new_name.clear();
+        }
+
+        // Selected layer
+        let Some((domain, layer_name)) = inspector.selected.clone() else {
+            return;
+        };
+        let Some(attributes) = attributes.as_mut() else {
+            return;
+        };
+        let Some(values) = attributes.get(domain, &layer_name) else {
+            return;
+        };
+        ui.separator();
+        ui.heading(format!("{} / {}", domain.label(), layer_name));
+
+        let expected = domain.element_count(&topology);
+        let mut resize = false;
+        if values.len() != expected {
+            ui.horizontal(|ui| {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!("{} values for {} elements", values.len(), expected),
+                );
+                resize = ui.button("Resize").clicked();
+            });
+        }
+
+        let numbers: Vec<f64> = (0..values.len())
+            .filter_map(|i| values.get_f64(i))
+            .filter(|v| v.is_finite())
+            .collect();
+        if !numbers.is_empty() {
+            let min = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
+            ui.label(format!("min {:.6}  max {:.6}  mean {:.6}", min, max, mean));
+        }
+
+        // Single element editor
+        let mut element = inspector.element.min(values.len().saturating_sub(1));
+        ui.horizontal(|ui| {
+            ui.label("Element");
+            ui.add(egui::DragValue::new(&mut element).range(0..=values.len().saturating_sub(1)));
+        });
+        if element != inspector.element {
+            inspector.element = element;
+        }
+        let mut edited = values.clone();
+        match &mut edited {
+            AttributeValues::Float(values) => {
+                if let Some(value) = values.get_mut(element) {
+                    ui.add(egui::DragValue::new(value).speed(0.01));
+                }
+            }
+            AttributeValues::Int(values) => {
+                if let Some(value) = values.get_mut(element) {
+                    ui.add(egui::DragValue::new(value));
+                }
+            }
+            AttributeValues::Bool(values) => {
+                if let Some(value) = values.get_mut(element) {
+                    ui.checkbox(value, "Value");
+                }
+            }
+        }
+
+        let targets = if selection.mesh == Some(entity) {
+            selected_elements(&selection, domain, &topology)
+        } else {
+            Vec::new()
+        };
+        let mut fill_value = inspector.fill_value;
+        let mut fill = false;
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut fill_value).speed(0.01));
+            fill = ui
+                .add_enabled(
+                    !targets.is_empty(),
+                    egui::Button::new(format!("Set on {} selected", targets.len())),
+                )
+                .clicked();
+        });
+        if fill_value != inspector.fill_value {
+            inspector.fill_value = fill_value;
+        }
+        if fill {
+            for &i in &targets {
+                edited.set_f64(i, fill_value);
+            }
+        }
+        if resize {
+            edited.resize(expected);
+        }
+        if &edited != values {
+            attributes.insert(domain, layer_name.clone(), edited);
+        }
+
+        ui.horizontal(|ui| {
+            let colorable = domain != AttributeDomain::Edge;
+            if ui
+                .add_enabled(colorable, egui::Button::new("Color faces"))
+                .clicked()
+            {
+                let values = attributes.get(domain, &layer_name);
+                if let Some(per_face) = values.and_then(|v| face_values(v, domain, &topology)) {
+                    let finite = per_face.iter().copied().filter(|v| v.is_finite());
+                    let (min, max) = finite
+                        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                            (lo.min(v), hi.max(v))
+                        });
+                    // Vertex values let the cursor readout interpolate
+                    let vertex_values =
+                        values
+                            .filter(|_| domain == AttributeDomain::Vertex)
+                            .map(|values| {
+                                (0..topology.positions.len())
+                                    .map(|v| values.get_f64(v).unwrap_or(f64::NAN))
+                                    .collect()
+                            });
+                    commands.entity(entity).queue(set_scalar_layer(
+                        AnalysisLayerKind::Attribute,
+                        ScalarField {
+                            vertex_values,
+                            ..ScalarField::new(per_face, min, max)
+                        },
+                    ));
+                }
+            }
+            if ui.button("Clear colors").clicked() {
+                commands
+                    .entity(entity)
+                    .queue(clear_layer(AnalysisLayerKind::Attribute));
+            }
+            if ui.button("Remove layer").clicked() {
+                attributes.remove(domain, &layer_name);
+                inspector.selected = None;
+            }
+        });
+
+        // Writing next to the working directory only makes sense on desktop
+        #[cfg(feature = "native")]
+        {
+            ui.separator();
+            if ui.button("Export PLY with attributes").clicked() {
+                let stem = name
+                    .map(|name| name.to_string())
+                    .unwrap_or_else(|| "mesh".into());
+                let path = PathBuf::from(format!("{}.ply", stem));
+                notices.write(match write_ply(&path, &topology, Some(&**attributes)) {
+                    Ok(()) => Notify::info(format!("Exported {} to {}", label, path.display())),
+                    Err(err) => {
+                        Notify::error(format!("Failed to export {}: {}", path.display(), err))
+                    }
+                });
+            }
+        }
+    });
+}
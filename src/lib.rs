@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#![recursion_limit = "512"]
+
+mod analysis;
+mod app;
+mod background;
+mod benchmark;
+mod blink;
+mod budget;
+mod camera;
+mod capture;
+mod command;
+mod context_menu;
+mod duplicate;
+mod edit;
+mod explode;
+// Public API for tools compiled into the viewer from outside its core
+// systems. See `extension::ViewerTool`.
+pub mod extension;
+mod flythrough;
+mod import;
+mod input;
+mod inspector;
+mod lighting;
+mod mesh;
+mod morph;
+mod notifications;
+mod outliner;
+mod perturb;
+mod picklog;
+mod pointcloud;
+mod probe;
+mod registration;
+mod remote;
+mod repair;
+mod sculpt;
+mod selection;
+mod selftest;
+mod session;
+mod silhouette;
+mod snapshot;
+mod stats;
+mod stepper;
+mod stereo;
+mod tools;
+mod trajectory;
+mod utils;
+mod wireframe;
+mod workspace;
+
+pub use crate::app::build_app;
@@ -43,8 +43,11 @@ pub fn setup_camera_and_light(mut commands: Commands) {
             Projection::Orthographic(OrthographicProjection {
                 near: 0.01,
                 far: 1000.0,
-                scale: 2.0, // Increase scale to see the unit cube better
+                // A little more than the scene diagonal stays in view
+                scale: 1.2,
                 viewport_origin: Vec2::new(0.5, 0.5),
+                // Resized to the scene diagonal by `fit_orthographic_height`
+                // once meshes are loaded
                 scaling_mode: ScalingMode::FixedVertical {
                     viewport_height: 2.0,
                 },
@@ -58,7 +61,7 @@ pub fn setup_camera_and_light(mut commands: Commands) {
                 focus: Vec3::ZERO,
                 radius: 10.0,
                 upside_down: false,
-                last_mouse_pos: None,
+                focus_target: None,
             },
         ))
         .id();
@@ -20,62 +20,6 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-#![recursion_limit = "512"]
-
-use bevy::pbr::wireframe::WireframePlugin;
-use bevy::picking::prelude::*;
-use bevy::prelude::*;
-
-mod camera;
-mod input;
-mod lighting;
-mod mesh;
-mod utils;
-
-use crate::camera::systems::camera_controller;
-use crate::input::systems::toggle_wireframe;
-use crate::lighting::setup::{setup_camera_and_light, sync_camera_aspect};
-use crate::mesh::edge::{
-    HighlightedEdges, PointerPresses, ToggledEdgeOperations, handle_mesh_click,
-    toggle_collapse_edge,
-};
-use crate::mesh::setup::setup_cgar_mesh;
-// ... other imports
-
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "CGAR Viewer".into(),
-                ..default()
-            }),
-            ..default()
-        }))
-        .init_resource::<HighlightedEdges>()
-        .init_resource::<PointerPresses>()
-        .init_resource::<ToggledEdgeOperations>()
-        .add_plugins((
-            MeshPickingPlugin, // built-in mesh picking
-            WireframePlugin::default(),
-        ))
-        .add_systems(Startup, (setup_camera_and_light, setup_cgar_mesh))
-        .add_systems(
-            Update,
-            (
-                toggle_wireframe,
-                camera_controller,
-                handle_mesh_click,
-                toggle_collapse_edge,
-            ),
-        )
-        .add_systems(
-            PostUpdate,
-            (
-                sync_camera_aspect, // updates aspect from viewport/window
-                                    // handle_mesh_click,  // computes ray using correct projection + transforms
-            )
-                .chain()
-                .after(TransformSystem::TransformPropagate),
-        )
-        .run();
+    cgar_viewer::build_app().run();
 }
@@ -0,0 +1,358 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+
+use bevy::ecs::{
+    change_detection::{DetectChanges, DetectChangesMut, Ref},
+    component::Component,
+    entity::Entity,
+    event::EventWriter,
+    name::Name,
+    query::{Changed, Or},
+    system::{Commands, Query},
+};
+
+use crate::camera::components::CgarMeshData;
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+
+// Element type an attribute layer is indexed by. Vertex and face layers use
+// cgar ids; edge layers follow the sorted (min, max) order of
+// `MeshTopology::edge_faces`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AttributeDomain {
+    Vertex,
+    Edge,
+    Face,
+}
+
+impl AttributeDomain {
+    pub const ALL: [AttributeDomain; 3] = [
+        AttributeDomain::Vertex,
+        AttributeDomain::Edge,
+        AttributeDomain::Face,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AttributeDomain::Vertex => "Vertex",
+            AttributeDomain::Edge => "Edge",
+            AttributeDomain::Face => "Face",
+        }
+    }
+
+    // Number of elements a layer on this domain should hold
+    pub fn element_count(self, topology: &MeshTopology) -> usize {
+        match self {
+            AttributeDomain::Vertex => topology.positions.len(),
+            AttributeDomain::Edge => topology.edge_faces.len(),
+            AttributeDomain::Face => topology.triangles.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeKind {
+    Float,
+    Int,
+    Bool,
+}
+
+impl AttributeKind {
+    pub const ALL: [AttributeKind; 3] = [
+        AttributeKind::Float,
+        AttributeKind::Int,
+        AttributeKind::Bool,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AttributeKind::Float => "f64",
+            AttributeKind::Int => "int",
+            AttributeKind::Bool => "bool",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValues {
+    Float(Vec<f64>),
+    Int(Vec<i32>),
+    Bool(Vec<bool>),
+}
+
+impl AttributeValues {
+    // `len` default values of `kind`
+    pub fn new(kind: AttributeKind, len: usize) -> Self {
+        match kind {
+            AttributeKind::Float => AttributeValues::Float(vec![0.0; len]),
+            AttributeKind::Int => AttributeValues::Int(vec![0; len]),
+            AttributeKind::Bool => AttributeValues::Bool(vec![false; len]),
+        }
+    }
+
+    pub fn kind(&self) -> AttributeKind {
+        match self {
+            AttributeValues::Float(_) => AttributeKind::Float,
+            AttributeValues::Int(_) => AttributeKind::Int,
+            AttributeValues::Bool(_) => AttributeKind::Bool,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            AttributeValues::Float(values) => values.len(),
+            AttributeValues::Int(values) => values.len(),
+            AttributeValues::Bool(values) => values.len(),
+        }
+    }
+
+    // Value as a number, with booleans as 0/1
+    pub fn get_f64(&self, i: usize) -> Option<f64> {
+        match self {
+            AttributeValues::Float(values) => values.get(i).copied(),
+            AttributeValues::Int(values) => values.get(i).map(|&v| v as f64),
+            AttributeValues::Bool(values) => values.get(i).map(|&v| v as u8 as f64),
+        }
+    }
+
+    // Stores a number, rounding for ints and testing non-zero for booleans
+    pub fn set_f64(&mut self, i: usize, value: f64) {
+        match self {
+            AttributeValues::Float(values) => {
+                if let Some(slot) = values.get_mut(i) {
+                    *slot = value;
+                }
+            }
+            AttributeValues::Int(values) => {
+                if let Some(slot) = values.get_mut(i) {
+                    *slot = value.round() as i32;
+                }
+            }
+            AttributeValues::Bool(values) => {
+                if let Some(slot) = values.get_mut(i) {
+                    *slot = value != 0.0;
+                }
+            }
+        }
+    }
+
+    // Grows with default values or truncates to `len`
+    pub fn resize(&mut self, len: usize) {
+        match self {
+            AttributeValues::Float(values) => values.resize(len, 0.0),
+            AttributeValues::Int(values) => values.resize(len, 0),
+            AttributeValues::Bool(values) => values.resize(len, false),
+        }
+    }
+
+    // `len` values where each (old, new) pair copies a value across and the
+    // rest are defaults
+    fn remapped(&self, len: usize, pairs: impl Iterator<Item = (usize, usize)>) -> Self {
+        let mut values = Self::new(self.kind(), len);
+        for (old, new) in pairs {
+            if let Some(value) = self.get_f64(old) {
+                values.set_f64(new, value);
+            }
+        }
+        values
+    }
+}
+
+// Element layout the layers were last matched to. Faces are kept as sorted
+// corners so they can be found again by their vertices.
+#[derive(Debug, Clone, PartialEq)]
+struct AttributeBasis {
+    vertices: usize,
+    faces: Vec<Option<[usize; 3]>>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl AttributeBasis {
+    fn of(topology: &MeshTopology) -> Self {
+        Self {
+            vertices: topology.positions.len(),
+            faces: topology
+                .triangles
+                .iter()
+                .map(|tri| {
+                    tri.map(|mut tri| {
+                        tri.sort_unstable();
+                        tri
+                    })
+                })
+                .collect(),
+            edges: edge_order(topology),
+        }
+    }
+}
+
+// What carrying the layers over a topology edit did
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayerRemap {
+    Unchanged,
+    Remapped,
+    // The mesh was renumbered; names of the layers that had to go
+    Dropped(Vec<String>),
+}
+
+// Named per-element data layers attached to a mesh entity next to its
+// `CgarMeshData`, for analyses, scripts and experiments to stash results in
+#[derive(Component, Default, Debug, Clone)]
+pub struct MeshAttributes {
+    pub layers: BTreeMap<(AttributeDomain, String), AttributeValues>,
+    // Set by `remap_mesh_attributes` once it has seen the mesh
+    basis: Option<AttributeBasis>,
+}
+
+impl MeshAttributes {
+    pub fn get(&self, domain: AttributeDomain, name: &str) -> Option<&AttributeValues> {
+        self.layers.get(&(domain, name.to_string()))
+    }
+
+    pub fn insert(&mut self, domain: AttributeDomain, name: String, values: AttributeValues) {
+        self.layers.insert((domain, name), values);
+    }
+
+    pub fn remove(&mut self, domain: AttributeDomain, name: &str) -> Option<AttributeValues> {
+        self.layers.remove(&(domain, name.to_string()))
+    }
+
+    // Layers on `domain`, in name order
+    pub fn domain(
+        &self,
+        domain: AttributeDomain,
+    ) -> impl Iterator<Item = (&str, &AttributeValues)> + '_ {
+        self.layers
+            .iter()
+            .filter(move |((d, _), _)| *d == domain)
+            .map(|((_, name), values)| (name.as_str(), values))
+    }
+
+    // Carries the layers over to `topology`. The viewer's edits keep vertex
+    // ids, so vertex layers grow with defaults and face and edge layers follow
+    // their vertices. With fewer vertices the mesh was renumbered, and the
+    // layers can't be matched up at all.
+    pub fn remap(&mut self, topology: &MeshTopology) -> LayerRemap {
+        let basis = AttributeBasis::of(topology);
+        let Some(old) = self.basis.replace(basis.clone()) else {
+            return LayerRemap::Unchanged;
+        };
+        if old == basis || self.layers.is_empty() {
+            return LayerRemap::Unchanged;
+        }
+        if basis.vertices < old.vertices {
+            let dropped = self.layers.keys().map(|(_, name)| name.clone()).collect();
+            self.layers.clear();
+            return LayerRemap::Dropped(dropped);
+        }
+        let faces: BTreeMap<[usize; 3], usize> = basis
+            .faces
+            .iter()
+            .enumerate()
+            .filter_map(|(f, tri)| tri.map(|tri| (tri, f)))
+            .collect();
+        let edges: BTreeMap<(usize, usize), usize> = basis
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(e, &edge)| (edge, e))
+            .collect();
+        for ((domain, _), values) in self.layers.iter_mut() {
+            *values = match domain {
+                AttributeDomain::Vertex => {
+                    values.remapped(basis.vertices, (0..old.vertices).map(|v| (v, v)))
+                }
+                AttributeDomain::Face => values.remapped(
+                    basis.faces.len(),
+                    old.faces.iter().enumerate().filter_map(|(f, tri)| {
+                        tri.and_then(|tri| faces.get(&tri)).map(|&new| (f, new))
+                    }),
+                ),
+                AttributeDomain::Edge => values.remapped(
+                    basis.edges.len(),
+                    old.edges
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(e, edge)| edges.get(edge).map(|&new| (e, new))),
+                ),
+            };
+        }
+        LayerRemap::Remapped
+    }
+}
+
+// Undirected edges in the order edge layers are indexed by
+pub fn edge_order(topology: &MeshTopology) -> Vec<(usize, usize)> {
+    topology.edge_faces.keys().copied().collect()
+}
+
+// Adds or replaces a layer on `entity`, creating its `MeshAttributes` if needed
+pub fn store_attribute(
+    commands: &mut Commands,
+    entity: Entity,
+    domain: AttributeDomain,
+    name: String,
+    values: AttributeValues,
+) {
+    commands
+        .entity(entity)
+        .entry::<MeshAttributes>()
+        .or_default()
+        .and_modify(move |mut attributes| attributes.insert(domain, name, values));
+}
+
+// Keeps attribute layers in step with topology edits to their mesh
+pub fn remap_mesh_attributes(
+    mut notices: EventWriter<Notify>,
+    mut query: Query<
+        (
+            Entity,
+            Option<&Name>,
+            Ref<CgarMeshData>,
+            &mut MeshAttributes,
+        ),
+        Or<(Changed<CgarMeshData>, Changed<MeshAttributes>)>,
+    >,
+) {
+    for (entity, name, cgar_data, mut attributes) in &mut query {
+        // Layers added since the last edit were sized for the current mesh
+        if attributes.basis.is_some() && !cgar_data.is_changed() {
+            continue;
+        }
+        let topology = MeshTopology::from_cgar(&cgar_data.0);
+        match attributes.bypass_change_detection().remap(&topology) {
+            LayerRemap::Unchanged => {}
+            LayerRemap::Remapped => attributes.set_changed(),
+            LayerRemap::Dropped(layers) => {
+                attributes.set_changed();
+                let label = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+                notices.write(Notify::warning(format!(
+                    "{}: the mesh was renumbered, so attribute layers {} were dropped",
+                    label,
+                    layers.join(", ")
+                )));
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::ecs::resource::Resource;
+use bevy::math::{DMat3, DVec3};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::mesh::edge_collapse::CollapseReject;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::mesh::conversion::{set_vertex_position, tri_vertices_of_face};
+use crate::mesh::topology::MeshTopology;
+
+// Where the vertex surviving an edge collapse is placed
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollapsePlacement {
+    // The endpoint nearer to where the edge was clicked
+    #[default]
+    Nearer,
+    // The edge's first endpoint
+    KeepSource,
+    // The edge's second endpoint
+    KeepTarget,
+    Midpoint,
+    // The point with the least squared distance to the planes of the faces
+    // around both endpoints
+    Qem,
+}
+
+impl CollapsePlacement {
+    pub const ALL: [CollapsePlacement; 5] = [
+        CollapsePlacement::Nearer,
+        CollapsePlacement::KeepSource,
+        CollapsePlacement::KeepTarget,
+        CollapsePlacement::Midpoint,
+        CollapsePlacement::Qem,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CollapsePlacement::Nearer => "Nearer endpoint",
+            CollapsePlacement::KeepSource => "Keep source",
+            CollapsePlacement::KeepTarget => "Keep target",
+            CollapsePlacement::Midpoint => "Midpoint",
+            CollapsePlacement::Qem => "Optimal (QEM)",
+        }
+    }
+}
+
+// Options of the Collapse tool
+#[derive(Resource, Default, Debug)]
+pub struct CollapseOptions {
+    pub placement: CollapsePlacement,
+}
+
+// Quadric error of the face planes around `vertices`, as (A, b, c) with
+// error(x) = x·Ax + 2 b·x + c
+fn quadric(topology: &MeshTopology, vertices: [usize; 2]) -> (DMat3, DVec3, f64) {
+    let mut faces: Vec<usize> = vertices
+        .iter()
+        .flat_map(|&v| topology.vertex_faces[v].iter().copied())
+        .collect();
+    faces.sort_unstable();
+    faces.dedup();
+    let mut quadric = (DMat3::ZERO, DVec3::ZERO, 0.0);
+    for f in faces {
+        let Some(tri) = topology.triangles[f] else {
+            continue;
+        };
+        let n = topology.face_normal(f);
+        if !n.is_finite() || n == DVec3::ZERO {
+            continue;
+        }
+        let d = -n.dot(topology.positions[tri[0]]);
+        quadric.0 += DMat3::from_cols(n * n.x, n * n.y, n * n.z);
+        quadric.1 += n * d;
+        quadric.2 += d * d;
+    }
+    quadric
+}
+
+// Least-error point for collapsing `edge`. Flat or nearly flat neighborhoods
+// have no unique minimum, so the best of the endpoints and the midpoint is
+// used there.
+pub fn qem_position(topology: &MeshTopology, edge: (usize, usize)) -> DVec3 {
+    let (a, b, c) = quadric(topology, [edge.0, edge.1]);
+    let error = |x: DVec3| x.dot(a * x) + 2.0 * b.dot(x) + c;
+    let (p0, p1) = (topology.positions[edge.0], topology.positions[edge.1]);
+    let fallback = [p0, p1, p0.lerp(p1, 0.5)]
+        .into_iter()
+        .min_by(|x, y| error(*x).total_cmp(&error(*y)))
+        .unwrap_or(p0);
+    // Relative to the quadric's scale, so the test doesn't depend on units
+    let scale = a.x_axis.x + a.y_axis.y + a.z_axis.z;
+    if scale <= 0.0 || a.determinant().abs() <= 1e-9 * scale * scale * scale {
+        return fallback;
+    }
+    let optimal = a.inverse() * -b;
+    // A minimum far from the edge usually means a badly conditioned quadric
+    let reach = p0.distance(p1) * 2.0;
+    if optimal.is_finite() && optimal.distance(p0.lerp(p1, 0.5)) <= reach {
+        optimal
+    } else {
+        fallback
+    }
+}
+
+// Where `placement` puts the survivor of collapsing `edge`, clicked at
+// parameter `u` from its first to its second endpoint
+pub fn placement_position(
+    topology: &MeshTopology,
+    edge: (usize, usize),
+    u: f64,
+    placement: CollapsePlacement,
+) -> DVec3 {
+    let (p0, p1) = (topology.positions[edge.0], topology.positions[edge.1]);
+    match placement {
+        CollapsePlacement::Nearer if u < 0.5 => p0,
+        CollapsePlacement::Nearer => p1,
+        CollapsePlacement::KeepSource => p0,
+        CollapsePlacement::KeepTarget => p1,
+        CollapsePlacement::Midpoint => p0.lerp(p1, 0.5),
+        CollapsePlacement::Qem => qem_position(topology, edge),
+    }
+}
+
+// Collapses `removed` into `kept` through cgar and moves whichever endpoint
+// survived to `position`. Returns the survivors.
+pub fn collapse_edge_to(
+    mesh: &mut CgarMesh<CgarF64, 3>,
+    removed: usize,
+    kept: usize,
+    position: DVec3,
+) -> Result<Vec<usize>, CollapseReject> {
+    mesh.collapse_edge(removed, kept)?;
+    let after = MeshTopology::from_cgar(mesh);
+    let survivors: Vec<usize> = [removed, kept]
+        .into_iter()
+        .filter(|&v| after.vertex_faces.get(v).is_some_and(|f| !f.is_empty()))
+        .collect();
+    for &v in &survivors {
+        set_vertex_position(mesh, v, position);
+    }
+    Ok(survivors)
+}
+
+// `collapse_edge_to` for callers that already know `around`, the faces
+// touching either endpoint, so finding the survivors only looks at those
+pub fn collapse_edge_around(
+    mesh: &mut CgarMesh<CgarF64, 3>,
+    removed: usize,
+    kept: usize,
+    position: DVec3,
+    around: &[usize],
+) -> Result<Vec<usize>, CollapseReject> {
+    mesh.collapse_edge(removed, kept)?;
+    let survivors: Vec<usize> = [removed, kept]
+        .into_iter()
+        .filter(|&v| {
+            around
+                .iter()
+                .any(|&f| !mesh.faces[f].removed && tri_vertices_of_face(mesh, f).contains(&v))
+        })
+        .collect();
+    for &v in &survivors {
+        set_vertex_position(mesh, v, position);
+    }
+    Ok(survivors)
+}
+
+// Collapses `edge` clicked at `u`, placing the survivor by `placement`.
+// Endpoint placements remove the other endpoint; the rest keep the nearer one.
+pub fn collapse_with_placement(
+    mesh: &mut CgarMesh<CgarF64, 3>,
+    edge: (usize, usize),
+    u: f64,
+    placement: CollapsePlacement,
+) -> Result<Vec<usize>, CollapseReject> {
+    let topology = MeshTopology::from_cgar(mesh);
+    let position = placement_position(&topology, edge, u, placement);
+    let (v0, v1) = edge;
+    let keep_source = match placement {
+        CollapsePlacement::KeepSource => true,
+        CollapsePlacement::KeepTarget => false,
+        _ => u < 0.5,
+    };
+    if keep_source {
+        collapse_edge_to(mesh, v1, v0, position)
+    } else {
+        collapse_edge_to(mesh, v0, v1, position)
+    }
+}
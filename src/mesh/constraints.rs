@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use bevy::ecs::{
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::mesh::collapse::CollapsePlacement;
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::topology::MeshTopology;
+use crate::selection::components::SelectionSet;
+
+// Vertices editing tools leave alone. Locked vertices are never moved by
+// brushes or smoothing, and a collapse keeps a locked endpoint where it is
+// or is refused if both endpoints are locked.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditConstraints {
+    pub lock_boundary: bool,
+    pub lock_features: bool,
+    pub lock_selection: bool,
+}
+
+impl EditConstraints {
+    pub fn any(&self) -> bool {
+        self.lock_boundary || self.lock_features || self.lock_selection
+    }
+
+    // Locked vertices of one mesh. `selection` only counts if it was picked
+    // on that mesh, so callers pass `None` otherwise.
+    pub fn locked(
+        &self,
+        topology: &MeshTopology,
+        features: Option<&FeatureEdges>,
+        selection: Option<&SelectionSet>,
+    ) -> BTreeSet<usize> {
+        let mut locked = BTreeSet::new();
+        if self.lock_boundary {
+            for (&(v0, v1), faces) in &topology.edge_faces {
+                if faces.len() == 1 {
+                    locked.extend([v0, v1]);
+                }
+            }
+        }
+        if let Some(features) = features.filter(|_| self.lock_features) {
+            for &(v0, v1) in &features.edges {
+                locked.extend([v0, v1]);
+            }
+        }
+        if let Some(selection) = selection.filter(|_| self.lock_selection) {
+            locked.extend(selection.touched_vertices(topology));
+        }
+        locked
+    }
+}
+
+// Placement a collapse of `edge` must use with `locked` vertices: a locked
+// endpoint has to stay put, and two locked endpoints can't merge
+pub fn constrain_placement(
+    locked: &BTreeSet<usize>,
+    edge: (usize, usize),
+    placement: CollapsePlacement,
+) -> Result<CollapsePlacement, String> {
+    match (locked.contains(&edge.0), locked.contains(&edge.1)) {
+        (true, true) => Err("both of its ends are locked".to_string()),
+        (true, false) => Ok(CollapsePlacement::KeepSource),
+        (false, true) => Ok(CollapsePlacement::KeepTarget),
+        (false, false) => Ok(placement),
+    }
+}
+
+pub fn edit_constraints_panel(
+    mut contexts: EguiContexts,
+    mut constraints: ResMut<EditConstraints>,
+    selection: Res<SelectionSet>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Edit Constraints").show(ctx, |ui| {
+        let mut edited = *constraints;
+        ui.checkbox(&mut edited.lock_boundary, "Lock boundary vertices");
+        ui.checkbox(&mut edited.lock_features, "Lock tagged feature edges");
+        ui.checkbox(
+            &mut edited.lock_selection,
+            format!("Lock selected elements ({})", selection.len()),
+        );
+        if edited != *constraints {
+            *constraints = edited;
+        }
+        ui.label("Applies to collapses, decimation, sculpt brushes and smoothing");
+    });
+}
@@ -20,18 +20,185 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::collections::{BTreeSet, HashMap};
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
 use bevy::asset::RenderAssetUsages;
-use bevy::math::Vec3;
+use bevy::math::{DVec3, Vec3};
 use bevy::render::mesh::{Indices, Mesh};
 
+use cgar::geometry::spatial_element::SpatialElement;
 use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
 use cgar::numeric::scalar::Scalar as CgarScalar;
 
+use crate::mesh::normals::{ImportedNormals, NormalWeighting};
+use crate::mesh::triangulate::triangulate_polygon;
+
 // ---- Example: convert a CGAR mesh (3D) to a Bevy Mesh ----
 // Adapt trait bounds to your Scalar setup. We’ll cast to f32 for GPU.
-pub fn cgar_to_bevy_mesh<T: CgarScalar>(m: &CgarMesh<T, 3>) -> Mesh
+pub fn cgar_to_bevy_mesh<T: CgarScalar>(m: &CgarMesh<T, 3>, weighting: NormalWeighting) -> Mesh
+where
+    for<'a> &'a T: Add<&'a T, Output = T>
+        + Sub<&'a T, Output = T>
+        + Mul<&'a T, Output = T>
+        + Div<&'a T, Output = T>
+        + Neg<Output = T>,
+{
+    let buffers = smooth_buffers(m, weighting);
+
+    // 4) Build bevy::Mesh
+    let mut mesh = Mesh::new(
+        bevy::render::mesh::PrimitiveTopology::TriangleList,
+        RenderAssetUsages::all(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, buffers.positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, buffers.normals);
+    mesh.insert_indices(Indices::U32(buffers.indices));
+    mesh
+}
+
+// Same as `cgar_to_bevy_mesh`, but with unshared corners so every face can
+// carry its own vertex color and normals can be split along creases.
+// `face_colors` is indexed by cgar face id; faces without an entry are drawn
+// white. `creases` holds undirected (min, max) vertex pairs. Corners with an
+// authored normal in `imported` use it over the computed one.
+pub fn cgar_to_bevy_mesh_unshared<T: CgarScalar>(
+    m: &CgarMesh<T, 3>,
+    face_colors: &[[f32; 4]],
+    creases: Option<&BTreeSet<(usize, usize)>>,
+    imported: Option<&ImportedNormals>,
+    weighting: NormalWeighting,
+) -> Mesh
+where
+    for<'a> &'a T: Add<&'a T, Output = T>
+        + Sub<&'a T, Output = T>
+        + Mul<&'a T, Output = T>
+        + Div<&'a T, Output = T>
+        + Neg<Output = T>,
+{
+    let buffers = smooth_buffers(m, weighting);
+    let crease_normals = creases.map(|creases| {
+        crease_corner_normals(&buffers.positions, &buffers.indices, creases, weighting)
+    });
+
+    let corner_count = buffers.indices.len();
+    let mut positions = Vec::with_capacity(corner_count);
+    let mut normals = Vec::with_capacity(corner_count);
+    let mut colors = Vec::with_capacity(corner_count);
+    for (t, (tri, &face)) in buffers
+        .indices
+        .chunks_exact(3)
+        .zip(&buffers.face_ids)
+        .enumerate()
+    {
+        let color = face_colors.get(face).copied().unwrap_or([1.0; 4]);
+        for (k, &i) in tri.iter().enumerate() {
+            positions.push(buffers.positions[i as usize]);
+            let next = tri[(k + 1) % 3] as usize;
+            let authored = imported.and_then(|normals| normals.corner(i as usize, next));
+            normals.push(match (authored, &crease_normals) {
+                (Some(n), _) => n.to_array(),
+                (None, Some(corner_normals)) => corner_normals[t * 3 + k],
+                (None, None) => buffers.normals[i as usize],
+            });
+            colors.push(color);
+        }
+    }
+
+    let mut mesh = Mesh::new(
+        bevy::render::mesh::PrimitiveTopology::TriangleList,
+        RenderAssetUsages::all(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32((0..corner_count as u32).collect()));
+    mesh
+}
+
+// cgar vertex drawn by each vertex of the mesh `cgar_to_bevy_mesh_unshared`
+// builds; `cgar_to_bevy_mesh` draws cgar vertex i as vertex i
+pub fn unshared_corner_vertices<T: CgarScalar>(m: &CgarMesh<T, 3>) -> Vec<u32>
+where
+    for<'a> &'a T: Add<&'a T, Output = T>
+        + Sub<&'a T, Output = T>
+        + Mul<&'a T, Output = T>
+        + Div<&'a T, Output = T>
+        + Neg<Output = T>,
+{
+    smooth_buffers(m, NormalWeighting::default()).indices
+}
+
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+// Normal for every corner of `indices`. Corners of one vertex share a normal
+// only if their faces connect around the vertex without crossing a crease.
+fn crease_corner_normals(
+    positions: &[[f32; 3]],
+    indices: &[u32],
+    creases: &BTreeSet<(usize, usize)>,
+    weighting: NormalWeighting,
+) -> Vec<[f32; 3]> {
+    // Undirected edge -> (corner at min vertex, corner at max vertex) per face
+    let mut edge_corners: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+    for t in 0..indices.len() / 3 {
+        for k in 0..3 {
+            let (ca, cb) = (t * 3 + k, t * 3 + (k + 1) % 3);
+            let (a, b) = (indices[ca] as usize, indices[cb] as usize);
+            let entry = if a < b { (ca, cb) } else { (cb, ca) };
+            edge_corners
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push(entry);
+        }
+    }
+
+    let mut parent: Vec<usize> = (0..indices.len()).collect();
+    for (edge, corners) in &edge_corners {
+        if creases.contains(edge) {
+            continue;
+        }
+        for pair in corners.windows(2) {
+            for (c0, c1) in [(pair[0].0, pair[1].0), (pair[0].1, pair[1].1)] {
+                let (r0, r1) = (find_root(&mut parent, c0), find_root(&mut parent, c1));
+                parent[r0] = r1;
+            }
+        }
+    }
+
+    let mut sums = vec![Vec3::ZERO; indices.len()];
+    for (t, tri) in indices.chunks_exact(3).enumerate() {
+        let [pa, pb, pc] = [tri[0], tri[1], tri[2]].map(|i| Vec3::from(positions[i as usize]));
+        let corners = [(pa, pb, pc), (pb, pc, pa), (pc, pa, pb)];
+        for (k, (a, b, c)) in corners.into_iter().enumerate() {
+            let root = find_root(&mut parent, t * 3 + k);
+            sums[root] += weighting.corner_contribution(a, b, c);
+        }
+    }
+    (0..indices.len())
+        .map(|c| {
+            let n = sums[find_root(&mut parent, c)];
+            n.try_normalize().unwrap_or(Vec3::Y).to_array()
+        })
+        .collect()
+}
+
+struct SmoothBuffers {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+    // cgar face id of each triangle in `indices`
+    face_ids: Vec<usize>,
+}
+
+fn smooth_buffers<T: CgarScalar>(m: &CgarMesh<T, 3>, weighting: NormalWeighting) -> SmoothBuffers
 where
     for<'a> &'a T: Add<&'a T, Output = T>
         + Sub<&'a T, Output = T>
@@ -51,19 +218,30 @@ where
         positions.push(p);
     }
 
-    // 2) Indices
-    // Replace with your face loop; assume triangles:
+    // 2) Indices; polygon faces are split for drawing, every piece keeping the face id
     let mut indices: Vec<u32> = Vec::with_capacity(m.faces.len() * 3);
+    let mut face_ids: Vec<usize> = Vec::with_capacity(m.faces.len());
     for (fi, f) in m.faces.iter().enumerate() {
         if f.removed {
             continue;
         }
-        // If you store half-edges, fetch the three vertex ids:
-        let [i0, i1, i2] = tri_vertices_of_face(m, fi); // implement below
-        indices.extend_from_slice(&[i0 as u32, i1 as u32, i2 as u32]);
+        let corners = face_vertices(m, fi);
+        let triangles = if corners.len() == 3 {
+            vec![[0, 1, 2]]
+        } else {
+            let points: Vec<DVec3> = corners
+                .iter()
+                .map(|&v| Vec3::from(positions[v]).as_dvec3())
+                .collect();
+            triangulate_polygon(&points)
+        };
+        for tri in triangles {
+            indices.extend(tri.map(|k| corners[k] as u32));
+            face_ids.push(fi);
+        }
     }
 
-    // 3) Normals (vertex-averaged)
+    // 3) Normals (vertex-averaged with the chosen weighting)
     let mut normals = vec![[0.0f32; 3]];
     normals.resize(positions.len(), [0.0; 3]);
 
@@ -72,12 +250,14 @@ where
         let pa = Vec3::from(positions[a]);
         let pb = Vec3::from(positions[b]);
         let pc = Vec3::from(positions[c]);
-        let n = (pb - pa).cross(pc - pa);
-        let n_arr = [n.x, n.y, n.z];
-        for &i in &[a, b, c] {
-            normals[i][0] += n_arr[0];
-            normals[i][1] += n_arr[1];
-            normals[i][2] += n_arr[2];
+        for (i, n) in [
+            (a, weighting.corner_contribution(pa, pb, pc)),
+            (b, weighting.corner_contribution(pb, pc, pa)),
+            (c, weighting.corner_contribution(pc, pa, pb)),
+        ] {
+            normals[i][0] += n.x;
+            normals[i][1] += n.y;
+            normals[i][2] += n.z;
         }
     }
     for n in &mut normals {
@@ -90,19 +270,69 @@ where
             *n = [0.0, 1.0, 0.0];
         }
     }
-    // 4) Build bevy::Mesh
-    let mut mesh = Mesh::new(
-        bevy::render::mesh::PrimitiveTopology::TriangleList,
-        RenderAssetUsages::all(),
-    );
-    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-    mesh.insert_indices(Indices::U32(indices));
+
+    SmoothBuffers {
+        positions,
+        normals,
+        indices,
+        face_ids,
+    }
+}
+
+// Builds a cgar mesh from raw positions and triangles. Every position becomes
+// a vertex, so vertex ids are preserved; face ids follow `triangles` order.
+pub fn build_cgar_mesh(
+    positions: &[DVec3],
+    triangles: impl IntoIterator<Item = [usize; 3]>,
+) -> CgarMesh<CgarF64, 3> {
+    let mut mesh = CgarMesh::<CgarF64, 3>::new();
+    for p in positions {
+        mesh.add_vertex(cgar::geometry::Point3::from_vals([
+            CgarF64::from(p.x),
+            CgarF64::from(p.y),
+            CgarF64::from(p.z),
+        ]));
+    }
+    for [a, b, c] in triangles {
+        mesh.add_triangle(a, b, c);
+    }
+    mesh.validate_connectivity();
     mesh
 }
 
-// Stub: fetch triangle’s vertex indices from your half-edge structure
-fn tri_vertices_of_face<T: CgarScalar>(m: &CgarMesh<T, 3>, face_idx: usize) -> [usize; 3]
+// Moves a cgar vertex in place; connectivity is untouched
+pub fn set_vertex_position(m: &mut CgarMesh<CgarF64, 3>, v: usize, p: DVec3) {
+    m.vertices[v].position = cgar::geometry::Point3::from_vals([
+        CgarF64::from(p.x),
+        CgarF64::from(p.y),
+        CgarF64::from(p.z),
+    ]);
+}
+
+// Mesh-local position of a cgar vertex, cast to f32 for rendering
+pub fn vertex_position(m: &CgarMesh<CgarF64, 3>, v: usize) -> Vec3 {
+    let p = &m.vertices[v].position;
+    Vec3::new(p[0].0 as f32, p[1].0 as f32, p[2].0 as f32)
+}
+
+// Vertex ids around a face, in half-edge order
+pub fn face_vertices<T: CgarScalar>(m: &CgarMesh<T, 3>, face_idx: usize) -> Vec<usize>
+where
+    for<'a> &'a T: Add<&'a T, Output = T>
+        + Sub<&'a T, Output = T>
+        + Mul<&'a T, Output = T>
+        + Div<&'a T, Output = T>
+        + Neg<Output = T>,
+{
+    m.face_half_edges(face_idx)
+        .iter()
+        .map(|&he| m.half_edges[he].vertex)
+        .collect()
+}
+
+// First three corners of a face. Loaded meshes are triangulated on import
+// (see `triangulate_mesh`), so this is the whole face everywhere else.
+pub fn tri_vertices_of_face<T: CgarScalar>(m: &CgarMesh<T, 3>, face_idx: usize) -> [usize; 3]
 where
     for<'a> &'a T: Add<&'a T, Output = T>
         + Sub<&'a T, Output = T>
@@ -20,59 +20,65 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::collections::BTreeSet;
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
-use bevy::core_pipeline::core_3d::Camera3d;
+use bevy::ecs::change_detection::DetectChangesMut;
 use bevy::ecs::query::With;
 use bevy::ecs::resource::Resource;
 use bevy::ecs::system::{Query, Res};
 use bevy::input::ButtonInput;
 use bevy::input::keyboard::KeyCode;
-use bevy::math::{Vec2, Vec3, Vec3A};
+use bevy::log::info;
+use bevy::math::{DVec3, Vec2, Vec3, Vec3A, primitives::InfinitePlane3d};
 use bevy::pbr::wireframe::NoWireframe;
 use bevy::picking::events::{Click, Pressed, Released};
-use bevy::picking::pointer::PointerId;
+use bevy::picking::pointer::{PointerButton, PointerId};
 use bevy::render::camera::Camera;
+use bevy::state::state::State;
+use bevy::time::Time;
 use bevy::transform::components::GlobalTransform;
 use bevy::window::{PrimaryWindow, Window};
 use bevy::{
     asset::Assets,
-    color::Color,
     ecs::{
         component::Component,
         entity::Entity,
-        event::{Event, EventReader},
+        event::{Event, EventReader, EventWriter},
         system::{Commands, ResMut},
     },
     input::{ButtonState, mouse::MouseButtonInput},
-    pbr::{MeshMaterial3d, StandardMaterial},
-    picking::{events::Pointer, pointer::PointerInteraction},
+    pbr::MeshMaterial3d,
+    picking::{Pickable, events::Pointer, pointer::PointerInteraction},
     render::mesh::{Mesh, Mesh3d, PrimitiveTopology},
     transform::components::Transform,
-    utils::default,
 };
 use bevy_inspector_egui::egui::ahash::HashMap;
 use cgar::geometry::spatial_element::SpatialElement;
-use cgar::geometry::{Point3, Vector3};
-use cgar::mesh::basic_types::{IntersectionHit, IntersectionResult, Mesh as CgarMesh};
-use cgar::mesh::edge_collapse::CollapseReject;
+use cgar::mesh::basic_types::Mesh as CgarMesh;
 use cgar::numeric::cgar_f64::CgarF64;
 use cgar::numeric::scalar::Scalar;
 
-use crate::camera::components::CgarMeshData;
-use crate::mesh::conversion::cgar_to_bevy_mesh;
-
-#[derive(Resource, Default, Debug, PartialEq, Eq, Clone, Copy)]
-pub enum EdgeOperation {
-    #[default]
-    None,
-    Collapse,
-    Split,
-}
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::{CgarMeshData, NavigationScheme, OrbitCamera, OrbitSettings};
+use crate::context_menu::systems::{ContextAction, ContextActionKind, MeshElement};
+use crate::mesh::collapse::{CollapseOptions, collapse_with_placement};
+use crate::mesh::constraints::{EditConstraints, constrain_placement};
+use crate::mesh::conversion::{tri_vertices_of_face, vertex_position};
+use crate::mesh::face_tree::{FaceHit, FaceSurface, FaceTreeCache, RayHit};
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::highlight::{HighlightAssets, HighlightKind};
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::selection::components::{RegionGrowSeeded, RegionGrowSettings, SelectionSet};
+use crate::selection::loops::{edge_loop, edge_ring};
+use crate::tools::systems::ActiveTool;
 
 #[derive(Component)]
 pub struct EdgeHighlight {
     pub original_entity: Entity,
+    pub kind: HighlightKind,
 }
 
 #[derive(Resource, Default)]
@@ -84,46 +90,175 @@ pub struct HighlightedEdges {
 pub struct PointerPresses {
     pub pos: HashMap<PointerId, Vec2>,
     pub target: HashMap<PointerId, Entity>,
+    // Time (seconds since startup) each pointer went down
+    pub pressed_at: HashMap<PointerId, f64>,
+    // Time (seconds since startup) and position of each pointer's last click
+    pub last_click: HashMap<PointerId, (f64, Vec2)>,
 }
 
-#[derive(Resource, Default)]
-pub struct ToggledEdgeOperations {
-    pub toggled: EdgeOperation,
+// Emitted for every completed click (press and release without dragging) on a
+// mesh, so tools can react to picks without re-implementing the deadzone logic
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MeshPicked {
+    pub entity: Entity,
+    // World-space hit position reported by the picking backend
+    pub world_position: Option<Vec3>,
+    // The hit on the full-resolution mesh, once its face tree is built
+    pub face_hit: Option<FaceHit>,
+    // Second click of a double-click; edit tools ignore these
+    pub double_click: bool,
+    // Pointer position in logical pixels
+    pub screen_position: Vec2,
 }
 
-pub fn toggle_collapse_edge(
-    kb: Res<ButtonInput<KeyCode>>,
-    mut toggled_edges: ResMut<ToggledEdgeOperations>,
-) {
-    if kb.just_pressed(KeyCode::KeyE) {
-        if toggled_edges.toggled == EdgeOperation::Collapse {
-            toggled_edges.toggled = EdgeOperation::None;
-        } else {
-            toggled_edges.toggled = EdgeOperation::Collapse;
+// A press held in place for at least `PickSettings::long_press_secs`. It
+// replaces the click, so tools can open context actions instead.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MeshLongPressed {
+    pub entity: Entity,
+    pub world_position: Option<Vec3>,
+    // Pointer position in logical pixels
+    pub screen_position: Vec2,
+}
+
+// How clicks on meshes are recognized. `radius_px` is how close to an edge
+// or vertex a click must land; the ray-cast tolerance is derived from it at
+// the clicked depth, so picking feels the same at any zoom level and mesh size.
+#[derive(Resource, Debug)]
+pub struct PickSettings {
+    pub radius_px: f32,
+    // Movement between press and release, in physical pixels, that turns a
+    // click into a drag. Scaled by the window's scale factor before use.
+    pub click_deadzone_px: f32,
+    // Presses held at least this long count as long presses, not clicks
+    pub long_press_secs: f32,
+    // Skip triangles facing away from the camera, so clicks near a silhouette
+    // land on the visible surface rather than the far side
+    pub ignore_backfaces: bool,
+}
+
+impl Default for PickSettings {
+    fn default() -> Self {
+        Self {
+            radius_px: 6.0,
+            click_deadzone_px: 3.0,
+            long_press_secs: 0.5,
+            ignore_backfaces: true,
         }
-        println!("Edge Operation set to {:?}", toggled_edges.toggled);
     }
-    if kb.just_pressed(KeyCode::KeyS) {
-        if toggled_edges.toggled == EdgeOperation::Split {
-            toggled_edges.toggled = EdgeOperation::None;
-        } else {
-            toggled_edges.toggled = EdgeOperation::Split;
-        }
-        println!("Edge Operation set to {:?}", toggled_edges.toggled);
+}
+
+impl PickSettings {
+    // Deadzone in logical pixels, which pointer positions are reported in
+    pub fn click_deadzone(&self, window: Option<&Window>) -> f32 {
+        self.click_deadzone_px / window.map_or(1.0, |window| window.scale_factor())
     }
 }
 
+// Mesh-local length of one screen pixel at `world_point`, measured on the
+// plane facing the camera so it covers perspective and orthographic views
+pub fn local_pixel_size(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    mesh_global: &GlobalTransform,
+    world_point: Vec3,
+) -> Option<f32> {
+    let screen = camera
+        .world_to_viewport(camera_transform, world_point)
+        .ok()?;
+    let ray = camera
+        .viewport_to_world(camera_transform, screen + Vec2::X)
+        .ok()?;
+    let distance = ray.intersect_plane(
+        world_point,
+        InfinitePlane3d::new(camera_transform.forward()),
+    )?;
+    let offset = ray.get_point(distance) - world_point;
+    let local = mesh_global
+        .affine()
+        .inverse()
+        .transform_vector3(offset)
+        .length();
+    Some(local).filter(|size| size.is_finite() && *size > 0.0)
+}
+
+// Casts the pick ray under `cursor` (logical pixels) through cgar's face
+// tree; clicks, hovers and every other screen pick go through here so they
+// agree. The tolerance is `radius_px` on screen at `depth_at`, or at the
+// mesh origin without one. With `ignore_backfaces`, faces turned away from
+// the camera are stepped past.
+pub fn cast_pick_ray(
+    (camera, camera_global): (&Camera, &GlobalTransform),
+    mesh_global: &GlobalTransform,
+    cgar_mesh: &CgarMesh<CgarF64, 3>,
+    surface: &FaceSurface,
+    cursor: Vec2,
+    depth_at: Option<Vec3>,
+    settings: &PickSettings,
+) -> Option<RayHit> {
+    // Rays are built relative to the camera's viewport
+    let viewport_min = camera
+        .logical_viewport_rect()
+        .map_or(Vec2::ZERO, |rect| rect.min);
+    let ray = camera
+        .viewport_to_world(camera_global, cursor - viewport_min)
+        .ok()?;
+    let to_local = mesh_global.affine().inverse();
+    let origin = to_local.transform_point3(ray.origin).as_dvec3();
+    let direction = to_local
+        .transform_vector3(ray.direction.as_vec3())
+        .as_dvec3();
+
+    let pixel = local_pixel_size(
+        camera,
+        camera_global,
+        mesh_global,
+        depth_at.unwrap_or_else(|| mesh_global.translation()),
+    );
+    let tolerance = pixel.map_or(0.05, |pixel| (pixel * settings.radius_px) as f64);
+    let mirrored = mesh_global.affine().matrix3.determinant() < 0.0;
+    surface.cast_ray(cgar_mesh, origin, direction, Some(tolerance), |face| {
+        !settings.ignore_backfaces || !faces_away(cgar_mesh, face, direction.as_vec3(), mirrored)
+    })
+}
+
 pub fn handle_mesh_click(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
     mut highlighted_edges: ResMut<HighlightedEdges>,
     mut press_events: EventReader<Pointer<Pressed>>,
     mut release_events: EventReader<Pointer<Released>>,
     mut presses: ResMut<PointerPresses>,
-    toggled_edges: ResMut<ToggledEdgeOperations>,
-    mut mesh_query: Query<(&Mesh3d, &GlobalTransform, &mut CgarMeshData)>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    tool: Res<State<ActiveTool>>,
+    kb: Res<ButtonInput<KeyCode>>,
+    mut selection: ResMut<SelectionSet>,
+    mut region_grow: ResMut<RegionGrowSettings>,
+    (mut picked, mut long_presses, mut actions, mut notices, mut seeded): (
+        EventWriter<MeshPicked>,
+        EventWriter<MeshLongPressed>,
+        EventWriter<ContextAction>,
+        EventWriter<Notify>,
+        EventWriter<RegionGrowSeeded>,
+    ),
+    (pick_settings, orbit_settings, time, highlight_assets, collapse_options, constraints): (
+        Res<PickSettings>,
+        Res<OrbitSettings>,
+        Res<Time>,
+        Res<HighlightAssets>,
+        Res<CollapseOptions>,
+        Res<EditConstraints>,
+    ),
+    mut mesh_query: Query<(
+        &Mesh3d,
+        &GlobalTransform,
+        &mut CgarMeshData,
+        Option<&FaceColorOverlay>,
+        Option<&mut FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+        Option<&FaceTreeCache>,
+    )>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
 ) where
     for<'a> &'a CgarF64: Add<&'a CgarF64, Output = CgarF64>
@@ -132,212 +267,394 @@ pub fn handle_mesh_click(
         + Div<&'a CgarF64, Output = CgarF64>
         + Neg<Output = CgarF64>,
 {
-    for event in press_events.read() {
+    // Other buttons navigate or open context menus
+    for event in press_events
+        .read()
+        .filter(|event| event.button == PointerButton::Primary)
+    {
         presses
             .pos
             .insert(event.pointer_id, event.pointer_location.position);
         presses.target.insert(event.pointer_id, event.target);
+        presses
+            .pressed_at
+            .insert(event.pointer_id, time.elapsed_secs_f64());
     }
 
-    let click_deadzone = 3.0;
+    let click_deadzone = pick_settings.click_deadzone(window_query.single().ok());
     let deadzone_sq = click_deadzone * click_deadzone;
+    let double_click_secs = 0.4;
+    let double_click_distance = 6.0;
 
-    for event in release_events.read() {
+    for event in release_events
+        .read()
+        .filter(|event| event.button == PointerButton::Primary)
+    {
         let Some(start_pos) = presses.pos.remove(&event.pointer_id) else {
             continue;
         };
-        let _ = presses.target.remove(&event.pointer_id);
+        let start_target = presses.target.remove(&event.pointer_id);
+        let pressed_at = presses.pressed_at.remove(&event.pointer_id);
 
         let end_pos = event.pointer_location.position;
         let moved_sq = (end_pos - start_pos).length_squared();
 
-        let same_target = presses
-            .target
-            .get(&event.pointer_id)
-            .map(|t| *t == event.target)
-            .unwrap_or(true);
+        let same_target = start_target.is_none_or(|t| t == event.target);
 
         if moved_sq > deadzone_sq || !same_target {
             // Treat as drag; do not click
             continue;
         }
 
-        if let Ok((mesh_handle, mesh_global, mut cgar_data)) = mesh_query.get_mut(event.target) {
+        let now = time.elapsed_secs_f64();
+        let held = pressed_at.map_or(0.0, |at| now - at);
+        if held >= pick_settings.long_press_secs as f64 {
+            long_presses.write(MeshLongPressed {
+                entity: event.target,
+                world_position: event.hit.position,
+                screen_position: end_pos,
+            });
+            continue;
+        }
+        // Under a modifier scheme, clicks without (or with) Alt only navigate
+        let alt = kb.pressed(KeyCode::AltLeft) || kb.pressed(KeyCode::AltRight);
+        if !orbit_settings.navigation.picks(alt) {
+            continue;
+        }
+
+        let double_click = presses
+            .last_click
+            .get(&event.pointer_id)
+            .is_some_and(|&(at, pos)| {
+                now - at <= double_click_secs && pos.distance(end_pos) <= double_click_distance
+            });
+        if double_click {
+            // A third click starts a new pair
+            presses.last_click.remove(&event.pointer_id);
+        } else {
+            presses.last_click.insert(event.pointer_id, (now, end_pos));
+        }
+
+        // The click ray is cast once through cgar's face tree; its hit feeds
+        // both `MeshPicked` and the edit tools below. The picking backend
+        // hits the drawn mesh, which may be a decimated proxy, so its hit
+        // isn't used for this.
+        let cast = match (camera_query.single(), mesh_query.get(event.target)) {
+            (Ok(camera), Ok((_, mesh_global, cgar_data, .., face_tree))) => {
+                // Meshes picked before their first cache refresh build a tree once
+                let built;
+                let surface = match face_tree {
+                    Some(cache) => &cache.0,
+                    None => {
+                        built = FaceSurface::build(&cgar_data.0);
+                        &built
+                    }
+                };
+                cast_pick_ray(
+                    camera,
+                    mesh_global,
+                    &cgar_data.0,
+                    surface,
+                    event.pointer_location.position,
+                    event.hit.position,
+                    &pick_settings,
+                )
+            }
+            _ => None,
+        };
+        let face_hit = match (cast, mesh_query.get(event.target)) {
+            (Some(hit), Ok((_, mesh_global, cgar_data, ..))) => {
+                FaceHit::from_barycentric(&cgar_data.0, hit.face, hit.barycentric, mesh_global)
+            }
+            _ => None,
+        };
+        picked.write(MeshPicked {
+            entity: event.target,
+            world_position: event.hit.position,
+            face_hit,
+            double_click,
+            screen_position: end_pos,
+        });
+        // The first click already picked; the second one only navigates.
+        // Measure, weld, vertex move and custom tools work from `MeshPicked` and
+        // drags alone, and area select from its own drag.
+        let tool = *tool.get();
+        if double_click
+            || matches!(
+                tool,
+                ActiveTool::Measure
+                    | ActiveTool::VertexMove
+                    | ActiveTool::Sculpt
+                    | ActiveTool::AreaSelect
+                    | ActiveTool::Weld
+                    | ActiveTool::FacePath
+                    | ActiveTool::Custom(_)
+            )
+        {
+            continue;
+        }
+
+        if let Ok((
+            mesh_handle,
+            mesh_global,
+            mut cgar_data,
+            overlay,
+            mut features,
+            normals,
+            settings,
+            ..,
+        )) = mesh_query.get_mut(event.target)
+        {
             clear_edge_highlights(&mut commands, &mut highlighted_edges);
-            if let (Ok((camera, camera_transform)), Ok(window)) =
-                (camera_query.single(), window_query.single())
-            {
-                // Start from the pointer's position (likely logical)
-                let mut pos = event.pointer_location.position;
-
-                // Convert to physical pixels
-                pos *= window.resolution.scale_factor() as f32;
-
-                // If the camera uses a viewport, make the pos relative to it
-                if let Some(vp) = camera.viewport.as_ref() {
-                    pos -= vp.physical_position.as_vec2();
-                }
 
-                if let Ok(ray) = camera.viewport_to_world(camera_transform, pos) {
-                    let inv_affine = mesh_global.affine().inverse();
-
-                    // Correct: use the ray's own origin and direction
-                    let local_o = inv_affine.transform_point3a(ray.origin.into());
-                    let local_dir_a = inv_affine
-                        .transform_vector3a(ray.direction.as_vec3().into())
-                        .normalize();
-
-                    // Optional: two-point variant using the same ray origin
-                    // let local_p1 = inv_affine.transform_point3a((ray.origin + ray.direction.as_vec3()).into());
-                    // let local_dir_a = (local_p1 - local_o).normalize();
-
-                    let local_origin = Point3::<CgarF64>::from_vals([
-                        local_o.x as f64,
-                        local_o.y as f64,
-                        local_o.z as f64,
-                    ]);
-                    let local_direction = Vector3::<CgarF64>::from_vals([
-                        local_dir_a.x as f64,
-                        local_dir_a.y as f64,
-                        local_dir_a.z as f64,
-                    ]);
-
-                    println!(
-                        "Local origin: {:?}, Local dir: {:?}",
-                        local_origin, local_direction
-                    );
-
-                    let cgar_mesh = &mut cgar_data.0;
-                    let tree = cgar_mesh.build_face_tree();
-                    let tolerance = CgarF64::from(0.05);
-
-                    match cgar_mesh.cast_ray(
-                        &local_origin,
-                        &local_direction,
-                        &tree,
-                        &Some(tolerance),
-                    ) {
-                        IntersectionResult::Hit(hit, _distance) => match hit {
-                            IntersectionHit::Edge(v0, v1, u) => {
-                                if toggled_edges.toggled == EdgeOperation::Collapse {
-                                    // if u is closer to v0, collapse towards v1, else towards v0
-                                    let result: Result<(), CollapseReject>;
-
-                                    if u < CgarF64::from(0.5) {
-                                        result = cgar_mesh.collapse_edge(v1, v0);
-                                    } else {
-                                        result = cgar_mesh.collapse_edge(v0, v1);
-                                    }
-
-                                    if result.is_ok() {
-                                        let new_mesh = cgar_to_bevy_mesh(&cgar_data.0);
-                                        meshes.insert(&mesh_handle.0, new_mesh);
-                                        println!("success");
-                                    }
-                                } else if toggled_edges.toggled == EdgeOperation::Split {
-                                    // Split edge at u
-                                    // let new_vertex_index =
-                                    //     cgar_mesh.split_edge();
-
-                                    // let new_mesh = cgar_to_bevy_mesh(&cgar_data.0);
-                                    // meshes.insert(&mesh_handle.0, new_mesh);
-                                    // println!(
-                                    //     "Split edge ({}, {}) at u={} -> new vertex {}",
-                                    //     v0, v1, u, new_vertex_index
-                                    // );
-                                } else {
-                                    let he_idx = cgar_mesh.edge_map[&(v0, v1)];
-                                    let half_edge = &cgar_mesh.half_edges[he_idx];
-                                    highlight_cgar_edge(
-                                        &mut commands,
-                                        &mut meshes,
-                                        &mut materials,
-                                        &mut highlighted_edges,
-                                        cgar_mesh,
-                                        (v0, v1),
-                                        mesh_global,
-                                        event.target,
-                                        Color::srgb(0.2, 1.0, 0.2),
-                                    );
-
-                                    println!(
-                                        "Highlighted half-edge {}: {:?}\n  Vertices: ({}, {})",
-                                        he_idx, half_edge, v0, v1
-                                    );
-                                    println!("  Next is red, Prev is blue");
-
-                                    if half_edge.twin != usize::MAX {
-                                        highlight_cgar_edge(
-                                            &mut commands,
-                                            &mut meshes,
-                                            &mut materials,
-                                            &mut highlighted_edges,
-                                            cgar_mesh,
-                                            (v1, v0),
-                                            mesh_global,
-                                            event.target,
-                                            Color::srgb(0.2, 1.0, 0.2),
-                                        );
-                                    }
-
-                                    if half_edge.next != usize::MAX {
-                                        let next_he = &cgar_mesh.half_edges[half_edge.next];
-                                        let next_v0 = next_he.vertex;
-                                        let next_v1 = cgar_mesh.half_edges[next_he.next].vertex;
-                                        highlight_cgar_edge(
-                                            &mut commands,
-                                            &mut meshes,
-                                            &mut materials,
-                                            &mut highlighted_edges,
-                                            cgar_mesh,
-                                            (next_v0, next_v1),
-                                            mesh_global,
-                                            event.target,
-                                            Color::srgb(1.0, 0.2, 0.2),
-                                        );
-                                    }
-
-                                    if half_edge.prev != usize::MAX {
-                                        let prev_he = &cgar_mesh.half_edges[half_edge.prev];
-                                        let prev_v1 = half_edge.vertex;
-                                        let prev_v0 = cgar_mesh.half_edges[prev_he.prev].vertex;
-                                        highlight_cgar_edge(
-                                            &mut commands,
-                                            &mut meshes,
-                                            &mut materials,
-                                            &mut highlighted_edges,
-                                            cgar_mesh,
-                                            (prev_v0, prev_v1),
-                                            mesh_global,
-                                            event.target,
-                                            Color::srgb(0.2, 0.2, 1.0),
-                                        );
-                                    }
-                                }
-                            }
-                            IntersectionHit::Face(face_id, _) => {
-                                for edge_idx in cgar_mesh.face_half_edges(face_id).iter() {
-                                    if let Some(he) = cgar_mesh.half_edges.get(*edge_idx) {
-                                        let v0 = he.vertex;
-                                        let v1 = cgar_mesh.half_edges[he.next].vertex;
-                                        highlight_cgar_edge(
-                                            &mut commands,
-                                            &mut meshes,
-                                            &mut materials,
-                                            &mut highlighted_edges,
-                                            cgar_mesh,
-                                            (v0, v1),
-                                            mesh_global,
-                                            event.target,
-                                            Color::srgb(0.2, 1.0, 0.2),
-                                        );
-                                    }
-                                }
+            // Shift extends the active selection instead of replacing it
+            let extend_selection =
+                kb.pressed(KeyCode::ShiftLeft) || kb.pressed(KeyCode::ShiftRight);
+            // Alt+click walks an edge loop and Ctrl+Alt+click an edge ring;
+            // the modifier schemes keep Alt for navigation
+            let walk_edges = alt && orbit_settings.navigation == NavigationScheme::Shared;
+            if !extend_selection || selection.mesh != Some(event.target) {
+                selection.clear();
+            }
+            selection.mesh = Some(event.target);
+            let Some(hit) = cast else {
+                continue;
+            };
+            // Only a collapse below edits the mesh; picks and selections must
+            // not mark it changed
+            let cgar_mesh = &mut cgar_data.bypass_change_detection().0;
+            match hit.edge {
+                Some((v0, v1, u)) => {
+                    let keeps_features = features
+                        .as_deref()
+                        .is_none_or(|features| features.allows_collapse(v0, v1));
+                    if tool == ActiveTool::Collapse && !keeps_features {
+                        notices.write(Notify::warning(format!(
+                            "Edge ({}, {}) can't be collapsed: it would break a feature line",
+                            v0, v1
+                        )));
+                        highlight_cgar_edge(
+                            &mut commands,
+                            &mut highlighted_edges,
+                            cgar_mesh,
+                            (v0, v1),
+                            mesh_global,
+                            event.target,
+                            &highlight_assets,
+                            HighlightKind::Error,
+                        );
+                    } else if tool == ActiveTool::Collapse {
+                        let locked = if constraints.any() {
+                            constraints.locked(
+                                &MeshTopology::from_cgar(cgar_mesh),
+                                features.as_deref(),
+                                Some(&*selection).filter(|s| s.mesh == Some(event.target)),
+                            )
+                        } else {
+                            BTreeSet::new()
+                        };
+                        let result =
+                            constrain_placement(&locked, (v0, v1), collapse_options.placement)
+                                .and_then(|placement| {
+                                    collapse_with_placement(cgar_mesh, (v0, v1), u, placement)
+                                        .map_err(|_| "it was rejected by the mesh".to_string())
+                                });
+
+                        if let Err(reason) = result {
+                            notices.write(Notify::warning(format!(
+                                "Edge ({}, {}) can't be collapsed: {}",
+                                v0, v1, reason
+                            )));
+                            highlight_cgar_edge(
+                                &mut commands,
+                                &mut highlighted_edges,
+                                cgar_mesh,
+                                (v0, v1),
+                                mesh_global,
+                                event.target,
+                                &highlight_assets,
+                                HighlightKind::Error,
+                            );
+                        } else {
+                            cgar_data.set_changed();
+                            let new_mesh = render_mesh(
+                                &cgar_data.0,
+                                overlay,
+                                features.as_deref(),
+                                normals,
+                                settings,
+                            );
+                            meshes.insert(&mesh_handle.0, new_mesh);
+                            println!("success");
+                        }
+                    } else if tool == ActiveTool::TagFeature {
+                        match features.as_mut() {
+                            Some(features) => features.toggle(v0, v1),
+                            None => {
+                                let mut features = FeatureEdges::default();
+                                features.toggle(v0, v1);
+                                commands.entity(event.target).insert(features);
                             }
-                            _ => {}
-                        },
-                        IntersectionResult::Miss => {
-                            println!("Ray missed the mesh");
+                        }
+                        info!("Toggled feature tag on edge ({}, {})", v0, v1);
+                    } else if let Some(kind) = match tool {
+                        ActiveTool::Flip => Some(ContextActionKind::Flip),
+                        ActiveTool::Split => Some(ContextActionKind::Split),
+                        _ => None,
+                    } {
+                        let [a, b] = [v0, v1].map(|v| {
+                            let p = &cgar_mesh.vertices[v].position;
+                            DVec3::new(p[0].0, p[1].0, p[2].0)
+                        });
+                        actions.write(ContextAction {
+                            entity: event.target,
+                            element: MeshElement::Edge(v0.min(v1), v0.max(v1)),
+                            kind,
+                            point: Some(a.lerp(b, u)),
+                        });
+                    } else if walk_edges {
+                        let topology = MeshTopology::from_cgar(cgar_mesh);
+                        let ring =
+                            kb.pressed(KeyCode::ControlLeft) || kb.pressed(KeyCode::ControlRight);
+                        let (edges, walk) = if ring {
+                            (edge_ring(&topology, (v0, v1)), "ring")
+                        } else {
+                            (edge_loop(&topology, (v0, v1)), "loop")
+                        };
+                        for &(a, b) in &edges {
+                            selection.insert_edge(a, b);
+                            let boundary = topology
+                                .edge_faces
+                                .get(&(a, b))
+                                .is_some_and(|faces| faces.len() == 1);
+                            highlight_cgar_edge(
+                                &mut commands,
+                                &mut highlighted_edges,
+                                cgar_mesh,
+                                (a, b),
+                                mesh_global,
+                                event.target,
+                                &highlight_assets,
+                                if boundary {
+                                    HighlightKind::Boundary
+                                } else {
+                                    HighlightKind::Selection
+                                },
+                            );
+                        }
+                        notices.write(Notify::info(format!(
+                            "Selected an edge {walk} of {} edges",
+                            edges.len()
+                        )));
+                    } else {
+                        selection.insert_edge(v0, v1);
+                        // Edges with a single face sit on the boundary
+                        let boundary = MeshTopology::from_cgar(cgar_mesh)
+                            .edge_faces
+                            .get(&(v0.min(v1), v0.max(v1)))
+                            .is_some_and(|faces| faces.len() == 1);
+                        let selected_kind = if boundary {
+                            HighlightKind::Boundary
+                        } else {
+                            HighlightKind::Selection
+                        };
+
+                        let he_idx = cgar_mesh.edge_map[&(v0, v1)];
+                        let half_edge = &cgar_mesh.half_edges[he_idx];
+                        highlight_cgar_edge(
+                            &mut commands,
+                            &mut highlighted_edges,
+                            cgar_mesh,
+                            (v0, v1),
+                            mesh_global,
+                            event.target,
+                            &highlight_assets,
+                            selected_kind,
+                        );
+
+                        println!(
+                            "Highlighted half-edge {}: {:?}\n  Vertices: ({}, {})",
+                            he_idx, half_edge, v0, v1
+                        );
+                        println!("  Next is red, Prev is blue");
+
+                        if half_edge.twin != usize::MAX {
+                            highlight_cgar_edge(
+                                &mut commands,
+                                &mut highlighted_edges,
+                                cgar_mesh,
+                                (v1, v0),
+                                mesh_global,
+                                event.target,
+                                &highlight_assets,
+                                selected_kind,
+                            );
+                        }
+
+                        if half_edge.next != usize::MAX {
+                            let next_he = &cgar_mesh.half_edges[half_edge.next];
+                            let next_v0 = next_he.vertex;
+                            let next_v1 = cgar_mesh.half_edges[next_he.next].vertex;
+                            highlight_cgar_edge(
+                                &mut commands,
+                                &mut highlighted_edges,
+                                cgar_mesh,
+                                (next_v0, next_v1),
+                                mesh_global,
+                                event.target,
+                                &highlight_assets,
+                                HighlightKind::Next,
+                            );
+                        }
+
+                        if half_edge.prev != usize::MAX {
+                            let prev_he = &cgar_mesh.half_edges[half_edge.prev];
+                            let prev_v1 = half_edge.vertex;
+                            let prev_v0 = cgar_mesh.half_edges[prev_he.prev].vertex;
+                            highlight_cgar_edge(
+                                &mut commands,
+                                &mut highlighted_edges,
+                                cgar_mesh,
+                                (prev_v0, prev_v1),
+                                mesh_global,
+                                event.target,
+                                &highlight_assets,
+                                HighlightKind::Prev,
+                            );
+                        }
+                    }
+                }
+                None if tool == ActiveTool::Split => {
+                    // Splits at the clicked point, not the face's centroid
+                    let point = face_hit.map(|hit| hit.local);
+                    actions.write(ContextAction {
+                        entity: event.target,
+                        element: MeshElement::Face(hit.face),
+                        kind: ContextActionKind::Split,
+                        point,
+                    });
+                }
+                None => {
+                    let face_id = hit.face;
+                    if region_grow.enabled {
+                        region_grow.seed = Some((event.target, face_id));
+                        seeded.write(RegionGrowSeeded);
+                    }
+                    selection.faces.insert(face_id);
+                    for edge_idx in cgar_mesh.face_half_edges(face_id).iter() {
+                        if let Some(he) = cgar_mesh.half_edges.get(*edge_idx) {
+                            let v0 = he.vertex;
+                            let v1 = cgar_mesh.half_edges[he.next].vertex;
+                            highlight_cgar_edge(
+                                &mut commands,
+                                &mut highlighted_edges,
+                                cgar_mesh,
+                                (v0, v1),
+                                mesh_global,
+                                event.target,
+                                &highlight_assets,
+                                HighlightKind::Selection,
+                            );
                         }
                     }
                 }
@@ -346,6 +663,17 @@ pub fn handle_mesh_click(
     }
 }
 
+// Whether a face points away from a ray travelling along `direction`
+fn faces_away(
+    cgar_mesh: &CgarMesh<CgarF64, 3>,
+    face: usize,
+    direction: Vec3,
+    mirrored: bool,
+) -> bool {
+    let [a, b, c] = tri_vertices_of_face(cgar_mesh, face).map(|v| vertex_position(cgar_mesh, v));
+    ((b - a).cross(c - a).dot(direction) > 0.0) != mirrored
+}
+
 // Simple slab test against [0,1]^3 in mesh-local space
 fn ray_hits_unit_aabb(o: Vec3A, d: Vec3A) -> bool {
     let inv = Vec3A::new(
@@ -380,14 +708,13 @@ fn ray_hits_unit_aabb(o: Vec3A, d: Vec3A) -> bool {
 fn extract_edges_from_mesh(mesh: &Mesh) -> Vec<(Vec3, Vec3)> {
     let mut edges = Vec::new();
 
-    if let Some(vertices) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+    // Positions that aren't plain float3 (never produced by the conversion) yield no edges
+    if let Some(vertices) = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .and_then(|attribute| attribute.as_float3())
+    {
         if let Some(indices) = mesh.indices() {
-            let positions: Vec<Vec3> = vertices
-                .as_float3()
-                .unwrap()
-                .iter()
-                .map(|&pos| Vec3::from(pos))
-                .collect();
+            let positions: Vec<Vec3> = vertices.iter().map(|&pos| Vec3::from(pos)).collect();
 
             match indices {
                 bevy::render::mesh::Indices::U16(indices) => {
@@ -430,14 +757,13 @@ fn clear_edge_highlights(
 
 fn highlight_cgar_edge(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
     highlighted_edges: &mut ResMut<HighlightedEdges>,
     cgar_mesh: &CgarMesh<CgarF64, 3>,
     edge_vertices: (usize, usize),
     mesh_transform: &GlobalTransform,
     original_entity: Entity,
-    color: Color,
+    assets: &HighlightAssets,
+    kind: HighlightKind,
 ) {
     // Get the specific edge from CGAR mesh
     if let Some(edge) = cgar_mesh.edge_half_edges(edge_vertices.0, edge_vertices.1) {
@@ -459,29 +785,29 @@ fn highlight_cgar_edge(
         // Create cylinder to highlight this specific edge
         let cylinder = create_edge_cylinder(
             commands,
-            meshes,
-            materials,
             start,
             end,
             mesh_transform,
             edge_vertices,
             original_entity,
-            color,
+            assets,
+            kind,
         );
         highlighted_edges.cylinders.push(cylinder);
     }
 }
 
-fn create_edge_cylinder(
+// Stretches the shared unit cylinder over the edge; `update_edge_highlights`
+// sets its radius for the view before it is drawn
+pub fn create_edge_cylinder(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
     start: bevy::math::Vec3,
     end: bevy::math::Vec3,
     mesh_transform: &GlobalTransform,
     edge_vertices: (usize, usize),
     original_entity: Entity,
-    color: Color,
+    assets: &HighlightAssets,
+    kind: HighlightKind,
 ) -> Entity {
     let world_start = mesh_transform.transform_point(start);
     let world_end = mesh_transform.transform_point(end);
@@ -490,19 +816,6 @@ fn create_edge_cylinder(
     let length = direction.length();
     let center = (world_start + world_end) / 2.0;
 
-    // Create cylinder mesh
-    let cylinder_mesh = Mesh::from(bevy::math::primitives::Cylinder {
-        radius: 0.005, // Slightly larger for better visibility
-        half_height: length / 2.0,
-    });
-
-    let mesh_handle = meshes.add(cylinder_mesh);
-    let material_handle = materials.add(StandardMaterial {
-        base_color: color,
-        emissive: color.into(), // Brighter emission
-        ..default()
-    });
-
     // Calculate rotation to align cylinder with edge
     let up = bevy::math::Vec3::Y;
     let rotation = if direction.length() > 0.001 {
@@ -513,15 +826,19 @@ fn create_edge_cylinder(
 
     commands
         .spawn((
-            MeshMaterial3d(material_handle),
-            Mesh3d(mesh_handle),
+            MeshMaterial3d(assets.material(kind)),
+            Mesh3d(assets.cylinder.clone()),
             Transform {
                 translation: center,
                 rotation,
-                ..default()
+                scale: bevy::math::Vec3::new(0.0, length, 0.0),
             },
             NoWireframe,
-            EdgeHighlight { original_entity },
+            Pickable::IGNORE,
+            EdgeHighlight {
+                original_entity,
+                kind,
+            },
         ))
         .id()
 }
@@ -0,0 +1,385 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use bevy::{
+    color::{LinearRgba, Srgba},
+    log::warn,
+    math::DVec3,
+};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::command::expr::LengthUnit;
+use crate::mesh::attributes::{
+    AttributeDomain, AttributeKind, AttributeValues, MeshAttributes, edge_order,
+};
+use crate::mesh::obj::faces_to_obj;
+use crate::mesh::topology::MeshTopology;
+
+// Writes a subset of faces as a standalone OBJ, renumbering the used vertices
+pub fn write_obj_faces(
+    path: &Path,
+    topology: &MeshTopology,
+    faces: &[usize],
+) -> std::io::Result<()> {
+    std::fs::write(path, faces_to_obj(topology, faces))
+}
+
+// Writes a line graph as OBJ `v` and `l` records
+pub fn write_obj_lines(
+    path: &Path,
+    points: &[DVec3],
+    segments: &[(usize, usize)],
+) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    for p in points {
+        writeln!(out, "v {} {} {}", p.x, p.y, p.z)?;
+    }
+    for &(a, b) in segments {
+        writeln!(out, "l {} {}", a + 1, b + 1)?;
+    }
+    out.flush()
+}
+
+fn ply_property_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+// Layers on `domain` with one value per element. A layer of another length
+// no longer matches the mesh and is left out.
+fn ply_layers<'a>(
+    attributes: Option<&'a MeshAttributes>,
+    domain: AttributeDomain,
+    topology: &MeshTopology,
+) -> Vec<(&'a str, &'a AttributeValues)> {
+    let Some(attributes) = attributes else {
+        return Vec::new();
+    };
+    let elements = domain.element_count(topology);
+    attributes
+        .domain(domain)
+        .filter(|(name, values)| {
+            let matches = values.len() == elements;
+            if !matches {
+                warn!(
+                    "Not exporting {} layer '{}': {} values for {} elements",
+                    domain.label(),
+                    name,
+                    values.len(),
+                    elements
+                );
+            }
+            matches
+        })
+        .collect()
+}
+
+fn ply_type(values: &AttributeValues) -> &'static str {
+    match values.kind() {
+        AttributeKind::Float => "double",
+        AttributeKind::Int => "int",
+        AttributeKind::Bool => "uchar",
+    }
+}
+
+fn write_ply_values(
+    out: &mut impl Write,
+    layers: &[(&str, &AttributeValues)],
+    i: usize,
+) -> std::io::Result<()> {
+    for (_, values) in layers {
+        match values {
+            AttributeValues::Float(values) => write!(out, " {}", values[i])?,
+            AttributeValues::Int(values) => write!(out, " {}", values[i])?,
+            AttributeValues::Bool(values) => write!(out, " {}", values[i] as u8)?,
+        }
+    }
+    Ok(())
+}
+
+// Writes the mesh as ASCII PLY with every attribute layer as an extra
+// property. All vertices are written so vertex ids keep their meaning; edges
+// are only written when edge layers exist.
+pub fn write_ply(
+    path: &Path,
+    topology: &MeshTopology,
+    attributes: Option<&MeshAttributes>,
+) -> std::io::Result<()> {
+    let (vertex_layers, edge_layers, face_layers) = (
+        ply_layers(attributes, AttributeDomain::Vertex, topology),
+        ply_layers(attributes, AttributeDomain::Edge, topology),
+        ply_layers(attributes, AttributeDomain::Face, topology),
+    );
+    let faces: Vec<(usize, [usize; 3])> = topology.live_faces().collect();
+
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "ply")?;
+    writeln!(out, "format ascii 1.0")?;
+    writeln!(out, "element vertex {}", topology.positions.len())?;
+    for axis in ["x", "y", "z"] {
+        writeln!(out, "property double {}", axis)?;
+    }
+    for (name, values) in &vertex_layers {
+        writeln!(
+            out,
+            "property {} {}",
+            ply_type(values),
+            ply_property_name(name)
+        )?;
+    }
+    writeln!(out, "element face {}", faces.len())?;
+    writeln!(out, "property list uchar int vertex_indices")?;
+    for (name, values) in &face_layers {
+        writeln!(
+            out,
+            "property {} {}",
+            ply_type(values),
+            ply_property_name(name)
+        )?;
+    }
+    if !edge_layers.is_empty() {
+        writeln!(out, "element edge {}", topology.edge_faces.len())?;
+        writeln!(out, "property int vertex1")?;
+        writeln!(out, "property int vertex2")?;
+        for (name, values) in &edge_layers {
+            writeln!(
+                out,
+                "property {} {}",
+                ply_type(values),
+                ply_property_name(name)
+            )?;
+        }
+    }
+    writeln!(out, "end_header")?;
+
+    for (v, p) in topology.positions.iter().enumerate() {
+        write!(out, "{} {} {}", p.x, p.y, p.z)?;
+        write_ply_values(&mut out, &vertex_layers, v)?;
+        writeln!(out)?;
+    }
+    for (f, [a, b, c]) in &faces {
+        write!(out, "3 {} {} {}", a, b, c)?;
+        write_ply_values(&mut out, &face_layers, *f)?;
+        writeln!(out)?;
+    }
+    if !edge_layers.is_empty() {
+        for (e, (v0, v1)) in edge_order(topology).into_iter().enumerate() {
+            write!(out, "{} {}", v0, v1)?;
+            write_ply_values(&mut out, &edge_layers, e)?;
+            writeln!(out)?;
+        }
+    }
+    out.flush()
+}
+
+const THREEMF_CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const THREEMF_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+// Namespace of the per-face attribute values written on triangles; readers
+// that don't know it ignore them
+const THREEMF_ATTRIBUTE_NS: &str = "https://github.com/aseverino/cgar-viewer/3mf/attributes";
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// Layer name as an XML attribute name
+fn xml_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        _ => format!("_{}", name),
+    }
+}
+
+fn threemf_unit(unit: LengthUnit) -> &'static str {
+    match unit {
+        LengthUnit::Millimeter => "millimeter",
+        LengthUnit::Centimeter => "centimeter",
+        LengthUnit::Meter => "meter",
+        LengthUnit::Inch => "inch",
+        LengthUnit::Foot => "foot",
+    }
+}
+
+// Builds the 3D model part: live faces over the vertices they use, a color
+// group with the distinct `face_colors` (linear RGBA by face id, as overlays
+// store them) and every face attribute layer as a namespaced triangle value
+fn threemf_model(
+    name: &str,
+    topology: &MeshTopology,
+    face_colors: Option<&[[f32; 4]]>,
+    attributes: Option<&MeshAttributes>,
+    unit: LengthUnit,
+) -> String {
+    let faces: Vec<(usize, [usize; 3])> = topology.live_faces().collect();
+    let mut remap: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut vertices = String::new();
+    for (_, tri) in &faces {
+        for &v in tri {
+            let next = remap.len();
+            if let Entry::Vacant(entry) = remap.entry(v) {
+                entry.insert(next);
+                let p = topology.positions[v];
+                vertices.push_str(&format!(
+                    "          <vertex x=\"{}\" y=\"{}\" z=\"{}\"/>\n",
+                    p.x, p.y, p.z
+                ));
+            }
+        }
+    }
+
+    let mut palette: Vec<String> = Vec::new();
+    let mut palette_index: HashMap<String, usize> = HashMap::new();
+    let mut face_color = |f: usize| {
+        let [r, g, b, a] = face_colors?.get(f).copied()?;
+        let hex = Srgba::from(LinearRgba::new(r, g, b, a)).to_hex();
+        let next = palette.len();
+        let index = *palette_index.entry(hex.clone()).or_insert(next);
+        if index == next {
+            palette.push(hex);
+        }
+        Some(index)
+    };
+    let layers: Vec<(String, &AttributeValues)> = attributes
+        .map(|attributes| {
+            attributes
+                .domain(AttributeDomain::Face)
+                .map(|(name, values)| (xml_name(name), values))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut triangles = String::new();
+    for (f, [a, b, c]) in &faces {
+        triangles.push_str(&format!(
+            "          <triangle v1=\"{}\" v2=\"{}\" v3=\"{}\"",
+            remap[a], remap[b], remap[c]
+        ));
+        if let Some(index) = face_color(*f) {
+            triangles.push_str(&format!(" pid=\"2\" p1=\"{}\"", index));
+        }
+        for (layer, values) in &layers {
+            if let Some(value) = values.get_f64(*f) {
+                triangles.push_str(&format!(" cgar:{}=\"{}\"", layer, value));
+            }
+        }
+        triangles.push_str("/>\n");
+    }
+
+    let mut model = String::new();
+    model.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    model.push_str(&format!(
+        "<model unit=\"{}\" xml:lang=\"en-US\" \
+         xmlns=\"http://schemas.microsoft.com/3dmanufacturing/core/2015/02\" \
+         xmlns:m=\"http://schemas.microsoft.com/3dmanufacturing/material/2015/02\" \
+         xmlns:cgar=\"{}\">\n",
+        threemf_unit(unit),
+        THREEMF_ATTRIBUTE_NS
+    ));
+    model.push_str(&format!(
+        "  <metadata name=\"Title\">{}</metadata>\n",
+        xml_escape(name)
+    ));
+    model.push_str("  <metadata name=\"Application\">cgar-viewer</metadata>\n");
+    if !layers.is_empty() {
+        let names: Vec<&str> = layers.iter().map(|(name, _)| name.as_str()).collect();
+        model.push_str(&format!(
+            "  <metadata name=\"cgar:FaceAttributes\">{}</metadata>\n",
+            xml_escape(&names.join(" "))
+        ));
+    }
+    model.push_str("  <resources>\n");
+    if !palette.is_empty() {
+        model.push_str("    <m:colorgroup id=\"2\">\n");
+        for color in &palette {
+            model.push_str(&format!("      <m:color color=\"{}\"/>\n", color));
+        }
+        model.push_str("    </m:colorgroup>\n");
+    }
+    // Uncolored faces take the first color rather than none, which 3MF
+    // doesn't allow once any face has one
+    let object_color = if palette.is_empty() {
+        ""
+    } else {
+        " pid=\"2\" pindex=\"0\""
+    };
+    model.push_str(&format!(
+        "    <object id=\"1\" type=\"model\" name=\"{}\"{}>\n",
+        xml_escape(name),
+        object_color
+    ));
+    model.push_str("      <mesh>\n        <vertices>\n");
+    model.push_str(&vertices);
+    model.push_str("        </vertices>\n        <triangles>\n");
+    model.push_str(&triangles);
+    model.push_str("        </triangles>\n      </mesh>\n    </object>\n");
+    model.push_str("  </resources>\n");
+    model.push_str("  <build>\n    <item objectid=\"1\"/>\n  </build>\n</model>\n");
+    model
+}
+
+// Writes the mesh as a 3MF package for slicers, with analysis colors as a
+// per-face color group and face attribute layers alongside
+pub fn write_3mf(
+    path: &Path,
+    name: &str,
+    topology: &MeshTopology,
+    face_colors: Option<&[[f32; 4]]>,
+    attributes: Option<&MeshAttributes>,
+    unit: LengthUnit,
+) -> std::io::Result<()> {
+    let model = threemf_model(name, topology, face_colors, attributes, unit);
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(path)?));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (part, contents) in [
+        ("[Content_Types].xml", THREEMF_CONTENT_TYPES),
+        ("_rels/.rels", THREEMF_RELS),
+        ("3D/3dmodel.model", model.as_str()),
+    ] {
+        zip.start_file(part, options)
+            .map_err(std::io::Error::other)?;
+        zip.write_all(contents.as_bytes())?;
+    }
+    zip.finish().map_err(std::io::Error::other)?.flush()
+}
@@ -0,0 +1,622 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::Changed,
+        system::{Commands, Query},
+    },
+    math::{DVec3, Vec3},
+    transform::components::GlobalTransform,
+};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use cgar::geometry::aabb::Aabb;
+use cgar::geometry::aabb_tree::AabbTree;
+use cgar::geometry::{Point3, Vector3};
+use cgar::mesh::basic_types::{IntersectionHit, IntersectionResult, Mesh as CgarMesh};
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::camera::components::CgarMeshData;
+use crate::mesh::conversion::tri_vertices_of_face;
+
+// What `CgarMesh::build_face_tree` returns: face ids keyed by their bounds
+pub type CgarFaceTree = AabbTree<CgarF64, 3, Point3<CgarF64>, usize>;
+
+// Rejected hits a ray cast steps past before giving up
+const MAX_RECASTS: usize = 64;
+// Faces removed by refits, as a fraction of the live faces, past which the
+// tree is rebuilt instead; refits keep the old layout, so churn slowly
+// degrades it
+const MAX_CHURN_FRACTION: f64 = 0.05;
+const MIN_CHURN: usize = 64;
+
+fn cgar_point(p: DVec3) -> Point3<CgarF64> {
+    Point3::from_vals([CgarF64::from(p.x), CgarF64::from(p.y), CgarF64::from(p.z)])
+}
+
+fn cgar_aabb((min, max): (DVec3, DVec3)) -> Aabb<CgarF64, 3, Point3<CgarF64>> {
+    Aabb::from_points(&cgar_point(min), &cgar_point(max))
+}
+
+// Corners of every face, indexed by cgar face id; removed faces are `None`
+fn face_corners(mesh: &CgarMesh<CgarF64, 3>) -> Vec<Option<[DVec3; 3]>> {
+    let position = |v: usize| {
+        let p = &mesh.vertices[v].position;
+        DVec3::new(p[0].0, p[1].0, p[2].0)
+    };
+    mesh.faces
+        .iter()
+        .enumerate()
+        .map(|(f, face)| (!face.removed).then(|| tri_vertices_of_face(mesh, f).map(position)))
+        .collect()
+}
+
+fn corner_bounds<'a>(tris: impl Iterator<Item = &'a [DVec3; 3]>) -> (DVec3, DVec3) {
+    tris.flatten().fold(
+        (DVec3::splat(f64::INFINITY), DVec3::splat(f64::NEG_INFINITY)),
+        |(min, max), &p| (min.min(p), max.max(p)),
+    )
+}
+
+// Refits a subtree to `corners` and returns its bounds, or `None` when none
+// of its faces are left. Nodes left with a single live child are replaced
+// by that child.
+fn refit_node(node: &mut CgarFaceTree, corners: &[Option<[DVec3; 3]>]) -> Option<(DVec3, DVec3)> {
+    let (bounds, survivor) = match node {
+        AabbTree::Leaf { aabb, data } => {
+            let tri = corners.get(*data).copied().flatten()?;
+            let bounds = corner_bounds(std::iter::once(&tri));
+            *aabb = cgar_aabb(bounds);
+            return Some(bounds);
+        }
+        AabbTree::Node { aabb, left, right } => {
+            match (refit_node(left, corners), refit_node(right, corners)) {
+                (Some(l), Some(r)) => {
+                    let bounds = (l.0.min(r.0), l.1.max(r.1));
+                    *aabb = cgar_aabb(bounds);
+                    return Some(bounds);
+                }
+                (Some(bounds), None) => (bounds, std::mem::replace(&mut **left, placeholder())),
+                (None, Some(bounds)) => (bounds, std::mem::replace(&mut **right, placeholder())),
+                (None, None) => return None,
+            }
+        }
+    };
+    *node = survivor;
+    Some(bounds)
+}
+
+// Stands in for a child while it is moved up the tree
+fn placeholder() -> CgarFaceTree {
+    AabbTree::Leaf {
+        aabb: cgar_aabb((DVec3::ZERO, DVec3::ZERO)),
+        data: usize::MAX,
+    }
+}
+
+// A hit of cgar's `cast_ray`, put on a face
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    pub face: usize,
+    // Weights over the face's corners, in `tri_vertices_of_face` order
+    pub barycentric: DVec3,
+    // The edge's end vertices and the parameter along it, when cgar
+    // reported the hit on an edge
+    pub edge: Option<(usize, usize, f64)>,
+    // Ray parameter of the hit, in units of the direction cast along
+    pub t: f64,
+}
+
+// Where a pick ray met a face: the point in mesh-local and world space and
+// its barycentric weights over the face's corners, in `vertices` order
+#[derive(Debug, Clone, Copy)]
+pub struct FaceHit {
+    pub face: usize,
+    pub vertices: [usize; 3],
+    pub barycentric: DVec3,
+    pub local: DVec3,
+    pub world: Vec3,
+}
+
+impl FaceHit {
+    // A hit given by its weights over the face's corners, in
+    // `tri_vertices_of_face` order, as cgar's ray casts report them
+    pub fn from_barycentric(
+        mesh: &CgarMesh<CgarF64, 3>,
+        face: usize,
+        barycentric: DVec3,
+        mesh_global: &GlobalTransform,
+    ) -> Option<Self> {
+        if mesh.faces.get(face).is_none_or(|f| f.removed) {
+            return None;
+        }
+        let vertices = tri_vertices_of_face(mesh, face);
+        let tri = vertices.map(|v| {
+            let p = &mesh.vertices[v].position;
+            DVec3::new(p[0].0, p[1].0, p[2].0)
+        });
+        let local = tri[0] * barycentric.x + tri[1] * barycentric.y + tri[2] * barycentric.z;
+        Some(Self {
+            face,
+            vertices,
+            barycentric,
+            local,
+            world: mesh_global.transform_point(local.as_vec3()),
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "hit face {}\nbarycentric {:?} over {:?}\npoint {:?}",
+            self.face,
+            self.barycentric.to_array(),
+            self.vertices,
+            self.local.to_array()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClosestPoint {
+    pub face: usize,
+    pub point: DVec3,
+    pub distance: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryShape {
+    Aabb { min: DVec3, max: DVec3 },
+    Sphere { center: DVec3, radius: f64 },
+}
+
+impl QueryShape {
+    fn bounds(&self) -> (DVec3, DVec3) {
+        match *self {
+            QueryShape::Aabb { min, max } => (min, max),
+            QueryShape::Sphere { center, radius } => {
+                (center - DVec3::splat(radius), center + DVec3::splat(radius))
+            }
+        }
+    }
+
+    fn contains_point(&self, p: DVec3) -> bool {
+        match *self {
+            QueryShape::Aabb { min, max } => min.cmple(p).all() && p.cmple(max).all(),
+            QueryShape::Sphere { center, radius } => p.distance_squared(center) <= radius * radius,
+        }
+    }
+
+    fn matches(&self, tri: &[DVec3; 3], contained: bool) -> bool {
+        if contained {
+            tri.iter().all(|&p| self.contains_point(p))
+        } else {
+            self.intersects_triangle(tri)
+        }
+    }
+
+    fn intersects_triangle(&self, tri: &[DVec3; 3]) -> bool {
+        match *self {
+            QueryShape::Aabb { min, max } => triangle_intersects_aabb(tri, min, max),
+            QueryShape::Sphere { center, radius } => {
+                closest_point_on_triangle(center, tri).distance_squared(center) <= radius * radius
+            }
+        }
+    }
+}
+
+// cgar's face tree over a mesh, with the f64 corners of each face so
+// candidates it returns can be tested exactly without going back to the mesh
+pub struct FaceSurface {
+    tree: CgarFaceTree,
+    // Indexed by cgar face id; removed faces are `None`
+    corners: Vec<Option<[DVec3; 3]>>,
+    min: DVec3,
+    max: DVec3,
+    // Faces removed by refits since the last build
+    churn: usize,
+}
+
+impl FaceSurface {
+    pub fn build(mesh: &CgarMesh<CgarF64, 3>) -> Self {
+        let corners = face_corners(mesh);
+        let (min, max) = corner_bounds(corners.iter().flatten());
+        Self {
+            tree: mesh.build_face_tree(),
+            corners,
+            min,
+            max,
+            churn: 0,
+        }
+    }
+
+    // Updates the tree in place after an edit: faces keep their leaves and
+    // take their new bounds, leaves of removed faces are dropped and node
+    // bounds are recomputed bottom-up. Returns false, leaving the surface
+    // unusable, when faces were added or too much churn has built up;
+    // rebuild it then.
+    pub fn refit(&mut self, mesh: &CgarMesh<CgarF64, 3>) -> bool {
+        let corners = face_corners(mesh);
+        let added = corners
+            .iter()
+            .enumerate()
+            .any(|(f, tri)| tri.is_some() && self.corners(f).is_none());
+        if added {
+            return false;
+        }
+        let live = corners.iter().flatten().count();
+        self.churn += self.len() - live;
+        let limit = ((live as f64 * MAX_CHURN_FRACTION) as usize).max(MIN_CHURN);
+        if self.churn > limit || refit_node(&mut self.tree, &corners).is_none() {
+            return false;
+        }
+        (self.min, self.max) = corner_bounds(corners.iter().flatten());
+        self.corners = corners;
+        true
+    }
+
+    // The tree itself, for `CgarMesh::cast_ray`
+    pub fn tree(&self) -> &CgarFaceTree {
+        &self.tree
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x
+    }
+
+    pub fn corners(&self, face: usize) -> Option<[DVec3; 3]> {
+        self.corners.get(face).copied().flatten()
+    }
+
+    // Live faces with their corners, in face id order
+    pub fn faces(&self) -> impl Iterator<Item = (usize, [DVec3; 3])> + '_ {
+        self.corners
+            .iter()
+            .enumerate()
+            .filter_map(|(f, tri)| tri.map(|tri| (f, tri)))
+    }
+
+    // Faces whose bounds overlap the box, as reported by cgar's tree
+    pub fn faces_in_box(&self, min: DVec3, max: DVec3) -> Vec<usize> {
+        let mut hits = Vec::new();
+        self.tree.query(
+            &Aabb::from_points(&cgar_point(min), &cgar_point(max)),
+            &mut hits,
+        );
+        let mut faces: Vec<usize> = hits
+            .into_iter()
+            .copied()
+            .filter(|&f| self.corners(f).is_some())
+            .collect();
+        faces.sort_unstable();
+        faces.dedup();
+        faces
+    }
+
+    // Faces touching `shape`, or only those entirely inside it when
+    // `contained` is set. cgar's tree narrows the candidates to the shape's
+    // bounds; each is then tested exactly.
+    pub fn query_faces(&self, shape: &QueryShape, contained: bool) -> Vec<usize> {
+        let (min, max) = shape.bounds();
+        self.faces_in_box(min, max)
+            .into_iter()
+            .filter(|&face| {
+                self.corners(face)
+                    .is_some_and(|tri| shape.matches(&tri, contained))
+            })
+            .collect()
+    }
+
+    // Same query without the tree, for cross-checking it
+    pub fn query_faces_brute_force(&self, shape: &QueryShape, contained: bool) -> Vec<usize> {
+        self.faces()
+            .filter(|(_, tri)| shape.matches(tri, contained))
+            .map(|(face, _)| face)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.corners.iter().flatten().count()
+    }
+
+    // Approximate heap size: a binary tree over n leaves holds 2n - 1 nodes,
+    // plus the corner copy kept beside it
+    pub fn memory_bytes(&self) -> usize {
+        let nodes = (2 * self.len()).saturating_sub(1);
+        nodes * size_of::<CgarFaceTree>() + size_of_val(self.corners.as_slice())
+    }
+
+    // Nearest surface point to `q`. cgar's tree only answers box overlaps, so
+    // the box around `q` grows until it holds a face; any face closer than
+    // the best one found overlaps a box of that distance, so one more query
+    // at that size makes the answer exact.
+    pub fn closest_point(&self, q: DVec3) -> Option<ClosestPoint> {
+        if self.is_empty() {
+            return None;
+        }
+        let diagonal = (self.max - self.min).length().max(f64::EPSILON);
+        let reach = (q - q.clamp(self.min, self.max)).length();
+        let mut half = reach + diagonal / (self.corners.len() as f64).cbrt().max(1.0);
+        loop {
+            let candidates = self.faces_in_box(q - DVec3::splat(half), q + DVec3::splat(half));
+            let best = self.nearest_of(&candidates, q);
+            match best {
+                Some(best) if best.distance <= half => return Some(best),
+                Some(best) => {
+                    let half = best.distance;
+                    let candidates =
+                        self.faces_in_box(q - DVec3::splat(half), q + DVec3::splat(half));
+                    return self.nearest_of(&candidates, q).or(Some(best));
+                }
+                // The box already covers the whole mesh
+                None if half > reach + diagonal => return None,
+                None => half *= 2.0,
+            }
+        }
+    }
+
+    // Nearest hit along `origin + t * direction` on a face `accept` takes,
+    // found by cgar's `cast_ray` against this tree. A rejected hit is
+    // stepped past and the ray cast again from there. Edge hits can land on
+    // the face on either side of the edge; other hits go to the faces
+    // nearest the hit point.
+    pub fn cast_ray(
+        &self,
+        mesh: &CgarMesh<CgarF64, 3>,
+        origin: DVec3,
+        direction: DVec3,
+        tolerance: Option<f64>,
+        accept: impl Fn(usize) -> bool,
+    ) -> Option<RayHit>
+    where
+        for<'a> &'a CgarF64: Add<&'a CgarF64, Output = CgarF64>
+            + Sub<&'a CgarF64, Output = CgarF64>
+            + Mul<&'a CgarF64, Output = CgarF64>
+            + Div<&'a CgarF64, Output = CgarF64>
+            + Neg<Output = CgarF64>,
+    {
+        let length = direction.length();
+        if self.is_empty() || !length.is_normal() {
+            return None;
+        }
+        let unit = direction / length;
+        let cgar_direction = Vector3::<CgarF64>::from_vals([unit.x, unit.y, unit.z]);
+        let cgar_tolerance = tolerance.map(CgarF64::from);
+        // Far enough to leave a rejected face behind, even with cgar's
+        // tolerance around it
+        let step = tolerance
+            .unwrap_or(0.0)
+            .max((self.max - self.min).length() * 1e-9);
+
+        let mut travelled = 0.0;
+        for _ in 0..MAX_RECASTS {
+            let start = origin + unit * travelled;
+            let IntersectionResult::Hit(hit, distance) = mesh.cast_ray(
+                &cgar_point(start),
+                &cgar_direction,
+                &self.tree,
+                &cgar_tolerance,
+            ) else {
+                return None;
+            };
+            let point = start + unit * distance.0;
+            travelled += distance.0;
+            let candidates: Vec<(usize, DVec3, Option<(usize, usize, f64)>)> = match hit {
+                IntersectionHit::Face(face, (u, v)) => {
+                    vec![(face, DVec3::new(1.0 - u.0 - v.0, u.0, v.0), None)]
+                }
+                IntersectionHit::Edge(v0, v1, u) => [(v0, v1), (v1, v0)]
+                    .iter()
+                    .filter_map(|key| mesh.edge_map.get(key))
+                    .filter_map(|&he| mesh.half_edges[he].face)
+                    .filter(|&face| self.corners(face).is_some())
+                    .map(|face| {
+                        let weights = tri_vertices_of_face(mesh, face).map(|c| {
+                            if c == v0 {
+                                1.0 - u.0
+                            } else if c == v1 {
+                                u.0
+                            } else {
+                                0.0
+                            }
+                        });
+                        (face, DVec3::from_array(weights), Some((v0, v1, u.0)))
+                    })
+                    .collect(),
+                _ => {
+                    let reach = DVec3::splat(step.max(tolerance.unwrap_or(0.0)));
+                    let mut nearest: Vec<ClosestPoint> = self
+                        .faces_in_box(point - reach, point + reach)
+                        .into_iter()
+                        .filter_map(|face| self.nearest_of(&[face], point))
+                        .collect();
+                    nearest.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+                    nearest
+                        .into_iter()
+                        .filter_map(|closest| {
+                            let tri = self.corners(closest.face)?;
+                            Some((closest.face, barycentric(closest.point, &tri), None))
+                        })
+                        .collect()
+                }
+            };
+            if let Some((face, barycentric, edge)) =
+                candidates.into_iter().find(|&(face, ..)| accept(face))
+            {
+                return Some(RayHit {
+                    face,
+                    barycentric,
+                    edge,
+                    t: travelled / length,
+                });
+            }
+            travelled += step;
+        }
+        None
+    }
+
+    fn nearest_of(&self, faces: &[usize], q: DVec3) -> Option<ClosestPoint> {
+        faces
+            .iter()
+            .filter_map(|&face| {
+                let point = closest_point_on_triangle(q, &self.corners(face)?);
+                Some(ClosestPoint {
+                    face,
+                    point,
+                    distance: point.distance(q),
+                })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+}
+
+// cgar's face tree kept alongside each mesh so interactive queries don't rebuild it per frame
+#[derive(Component)]
+pub struct FaceTreeCache(pub FaceSurface);
+
+// Refits the cached tree whenever a mesh's connectivity or geometry changes,
+// rebuilding it when that isn't possible
+pub fn refresh_face_tree_cache(
+    mut commands: Commands,
+    mut mesh_query: Query<
+        (Entity, &CgarMeshData, Option<&mut FaceTreeCache>),
+        Changed<CgarMeshData>,
+    >,
+) {
+    for (entity, cgar_data, cache) in &mut mesh_query {
+        match cache {
+            Some(mut cache) => {
+                if !cache.0.refit(&cgar_data.0) {
+                    cache.0 = FaceSurface::build(&cgar_data.0);
+                }
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(FaceTreeCache(FaceSurface::build(&cgar_data.0)));
+            }
+        }
+    }
+}
+
+// Weights of `p` over the corners of `tri`, for a point in its plane (Ericson 3.4)
+pub fn barycentric(p: DVec3, tri: &[DVec3; 3]) -> DVec3 {
+    let v0 = tri[1] - tri[0];
+    let v1 = tri[2] - tri[0];
+    let v2 = p - tri[0];
+    let d00 = v0.dot(v0);
+    let d01 = v0.dot(v1);
+    let d11 = v1.dot(v1);
+    let d20 = v2.dot(v0);
+    let d21 = v2.dot(v1);
+    let denom = d00 * d11 - d01 * d01;
+    let v = (d11 * d20 - d01 * d21) / denom;
+    let w = (d00 * d21 - d01 * d20) / denom;
+    DVec3::new(1.0 - v - w, v, w)
+}
+
+// Separating-axis triangle/box overlap test (Akenine-Möller)
+fn triangle_intersects_aabb(tri: &[DVec3; 3], min: DVec3, max: DVec3) -> bool {
+    let center = (min + max) * 0.5;
+    let half = (max - min) * 0.5;
+    let v = [tri[0] - center, tri[1] - center, tri[2] - center];
+    let edges = [v[1] - v[0], v[2] - v[1], v[0] - v[2]];
+
+    let separated = |axis: DVec3| -> bool {
+        if axis.length_squared() < 1e-24 {
+            return false;
+        }
+        let p = [axis.dot(v[0]), axis.dot(v[1]), axis.dot(v[2])];
+        let r = half.x * axis.x.abs() + half.y * axis.y.abs() + half.z * axis.z.abs();
+        p[0].min(p[1]).min(p[2]) > r || p[0].max(p[1]).max(p[2]) < -r
+    };
+
+    // Box face normals, triangle normal, then the nine edge cross products
+    for axis in [DVec3::X, DVec3::Y, DVec3::Z] {
+        if separated(axis) {
+            return false;
+        }
+    }
+    if separated(edges[0].cross(edges[1])) {
+        return false;
+    }
+    for box_axis in [DVec3::X, DVec3::Y, DVec3::Z] {
+        for edge in edges {
+            if separated(box_axis.cross(edge)) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+// Closest point on triangle `tri` to `p` (Ericson, Real-Time Collision Detection 5.1.5)
+pub fn closest_point_on_triangle(p: DVec3, tri: &[DVec3; 3]) -> DVec3 {
+    let [a, b, c] = *tri;
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return a + ab * v;
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return a + ac * w;
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return b + (c - b) * w;
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    a + ab * v + ac * w
+}
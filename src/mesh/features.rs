@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use bevy::ecs::component::Component;
+
+use crate::mesh::topology::MeshTopology;
+
+// Edges tagged as sharp features, stored as undirected (min, max) cgar vertex
+// pairs on the mesh entity. Shading splits normals across them and collapses
+// must not drag a feature vertex off its feature line.
+#[derive(Component, Default, Debug, Clone)]
+pub struct FeatureEdges {
+    pub edges: BTreeSet<(usize, usize)>,
+}
+
+impl FeatureEdges {
+    pub fn contains(&self, v0: usize, v1: usize) -> bool {
+        self.edges.contains(&(v0.min(v1), v0.max(v1)))
+    }
+
+    // Adds the edge if untagged, removes it otherwise
+    pub fn toggle(&mut self, v0: usize, v1: usize) {
+        let edge = (v0.min(v1), v0.max(v1));
+        if !self.edges.remove(&edge) {
+            self.edges.insert(edge);
+        }
+    }
+
+    // Feature edges touching `v`
+    pub fn valence(&self, v: usize) -> usize {
+        self.edges
+            .iter()
+            .filter(|&&(a, b)| a == v || b == v)
+            .count()
+    }
+
+    // Follows a vertex renumbering, dropping edges that lost or merged an endpoint
+    pub fn remap(&mut self, vertices: &[Option<usize>]) {
+        let vertex = |v: usize| vertices.get(v).copied().flatten();
+        self.edges = self
+            .edges
+            .iter()
+            .filter_map(|&(v0, v1)| Some((vertex(v0)?, vertex(v1)?)))
+            .filter(|(v0, v1)| v0 != v1)
+            .map(|(v0, v1)| (v0.min(v1), v0.max(v1)))
+            .collect();
+    }
+
+    // Whether collapsing `v0`-`v1` keeps feature lines intact. Either endpoint
+    // may be the one removed, so both must be free to move: no features at
+    // all, or a plain feature line running along the collapsed edge.
+    pub fn allows_collapse(&self, v0: usize, v1: usize) -> bool {
+        let along = self.contains(v0, v1);
+        [v0, v1].into_iter().all(|v| match self.valence(v) {
+            0 => true,
+            2 => along,
+            _ => false,
+        })
+    }
+}
+
+// Manifold edges whose dihedral angle is at least `min_angle` radians, plus
+// boundary edges when `include_boundary` is set
+pub fn detect_feature_edges(
+    topology: &MeshTopology,
+    min_angle: f64,
+    include_boundary: bool,
+) -> BTreeSet<(usize, usize)> {
+    topology
+        .edge_faces
+        .iter()
+        .filter(|(edge, faces)| match topology.dihedral_angle(**edge) {
+            Some(angle) => angle >= min_angle,
+            None => include_boundary && faces.len() == 1,
+        })
+        .map(|(edge, _)| *edge)
+        .collect()
+}
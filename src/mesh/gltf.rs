@@ -0,0 +1,268 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use bevy::{
+    render::mesh::{Mesh, VertexAttributeValues},
+    transform::components::Transform,
+};
+use serde_json::{Value, json};
+
+// glTF enums for accessor component types and buffer view targets
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_JSON_CHUNK: u32 = 0x4E4F_534A;
+const GLB_BIN_CHUNK: u32 = 0x004E_4942;
+
+// Triangle data of one node, as drawn
+pub struct GltfMesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    // Linear RGBA per vertex, written as COLOR_0
+    pub colors: Option<Vec<[f32; 4]>>,
+    pub indices: Vec<u32>,
+}
+
+impl GltfMesh {
+    // Reads a triangle-list render mesh; None if it has no triangles
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION)? {
+            VertexAttributeValues::Float32x3(positions) => positions.clone(),
+            _ => return None,
+        };
+        let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(normals)) => normals.clone(),
+            _ => Vec::new(),
+        };
+        let colors = match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+            Some(VertexAttributeValues::Float32x4(colors)) => Some(colors.clone()),
+            _ => None,
+        };
+        let indices = match mesh.indices() {
+            Some(indices) => indices.iter().map(|i| i as u32).collect(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        if positions.is_empty() || indices.is_empty() {
+            return None;
+        }
+        Some(Self {
+            positions,
+            normals,
+            colors,
+            indices,
+        })
+    }
+}
+
+// A scene node: a group or a mesh, placed relative to its parent
+pub struct GltfNode {
+    pub name: String,
+    pub transform: Transform,
+    pub mesh: Option<GltfMesh>,
+    // Indices into the node list
+    pub children: Vec<usize>,
+}
+
+// Binary buffer and the JSON describing it, built one accessor at a time
+#[derive(Default)]
+struct GltfBuilder {
+    buffer: Vec<u8>,
+    views: Vec<Value>,
+    accessors: Vec<Value>,
+}
+
+impl GltfBuilder {
+    // Appends tightly packed 4-byte components and returns the accessor index
+    fn push(
+        &mut self,
+        bytes: impl IntoIterator<Item = [u8; 4]>,
+        count: usize,
+        kind: &str,
+        component: u32,
+        target: u32,
+        bounds: Option<(Value, Value)>,
+    ) -> usize {
+        let offset = self.buffer.len();
+        self.buffer.extend(bytes.into_iter().flatten());
+        self.views.push(json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": self.buffer.len() - offset,
+            "target": target,
+        }));
+        let mut accessor = json!({
+            "bufferView": self.views.len() - 1,
+            "componentType": component,
+            "count": count,
+            "type": kind,
+        });
+        if let Some((min, max)) = bounds {
+            accessor["min"] = min;
+            accessor["max"] = max;
+        }
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn push_mesh(&mut self, name: &str, mesh: &GltfMesh) -> Value {
+        let floats = |values: &[f32]| values.iter().map(|v| v.to_le_bytes()).collect::<Vec<_>>();
+        let (min, max) = mesh.positions.iter().fold(
+            ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]),
+            |(min, max), p| {
+                (
+                    [min[0].min(p[0]), min[1].min(p[1]), min[2].min(p[2])],
+                    [max[0].max(p[0]), max[1].max(p[1]), max[2].max(p[2])],
+                )
+            },
+        );
+        let position = self.push(
+            floats(mesh.positions.as_flattened()),
+            mesh.positions.len(),
+            "VEC3",
+            FLOAT,
+            ARRAY_BUFFER,
+            Some((json!(min), json!(max))),
+        );
+        let mut attributes = json!({ "POSITION": position });
+        if mesh.normals.len() == mesh.positions.len() {
+            attributes["NORMAL"] = json!(self.push(
+                floats(mesh.normals.as_flattened()),
+                mesh.normals.len(),
+                "VEC3",
+                FLOAT,
+                ARRAY_BUFFER,
+                None,
+            ));
+        }
+        if let Some(colors) = mesh
+            .colors
+            .as_ref()
+            .filter(|colors| colors.len() == mesh.positions.len())
+        {
+            attributes["COLOR_0"] = json!(self.push(
+                floats(colors.as_flattened()),
+                colors.len(),
+                "VEC4",
+                FLOAT,
+                ARRAY_BUFFER,
+                None,
+            ));
+        }
+        let indices = self.push(
+            mesh.indices.iter().map(|i| i.to_le_bytes()),
+            mesh.indices.len(),
+            "SCALAR",
+            UNSIGNED_INT,
+            ELEMENT_ARRAY_BUFFER,
+            None,
+        );
+        json!({
+            "name": name,
+            "primitives": [{ "attributes": attributes, "indices": indices }],
+        })
+    }
+}
+
+fn pad_to_4(bytes: &mut Vec<u8>, fill: u8) {
+    bytes.resize(bytes.len().next_multiple_of(4), fill);
+}
+
+// Writes the nodes as a glTF 2.0 scene whose top level is `roots`. A `.glb`
+// path gets a single binary file; anything else gets JSON with the buffer in
+// a `.bin` file next to it.
+pub fn write_gltf(path: &Path, nodes: &[GltfNode], roots: &[usize]) -> std::io::Result<()> {
+    let mut builder = GltfBuilder::default();
+    let mut meshes = Vec::new();
+    let gltf_nodes: Vec<Value> = nodes
+        .iter()
+        .map(|node| {
+            let t = node.transform;
+            let mut value = json!({
+                "name": node.name,
+                "translation": t.translation.to_array(),
+                "rotation": t.rotation.to_array(),
+                "scale": t.scale.to_array(),
+            });
+            if !node.children.is_empty() {
+                value["children"] = json!(node.children);
+            }
+            if let Some(mesh) = &node.mesh {
+                meshes.push(builder.push_mesh(&node.name, mesh));
+                value["mesh"] = json!(meshes.len() - 1);
+            }
+            value
+        })
+        .collect();
+
+    let binary = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("glb"));
+    let bin_path = path.with_extension("bin");
+    let mut buffer = json!({ "byteLength": builder.buffer.len() });
+    if !binary {
+        let uri = bin_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        buffer["uri"] = json!(uri);
+    }
+    let document = json!({
+        "asset": { "version": "2.0", "generator": "cgar-viewer" },
+        "scene": 0,
+        "scenes": [{ "nodes": roots }],
+        "nodes": gltf_nodes,
+        "meshes": meshes,
+        "buffers": [buffer],
+        "bufferViews": builder.views,
+        "accessors": builder.accessors,
+    });
+
+    if !binary {
+        std::fs::write(&bin_path, &builder.buffer)?;
+        let text = serde_json::to_string_pretty(&document).map_err(std::io::Error::other)?;
+        return std::fs::write(path, text);
+    }
+    let mut json_chunk = serde_json::to_vec(&document).map_err(std::io::Error::other)?;
+    pad_to_4(&mut json_chunk, b' ');
+    let mut bin_chunk = builder.buffer;
+    pad_to_4(&mut bin_chunk, 0);
+    let total = 12 + 8 + json_chunk.len() + 8 + bin_chunk.len();
+
+    let mut out = BufWriter::new(File::create(path)?);
+    for word in [GLB_MAGIC, 2, total as u32] {
+        out.write_all(&word.to_le_bytes())?;
+    }
+    out.write_all(&(json_chunk.len() as u32).to_le_bytes())?;
+    out.write_all(&GLB_JSON_CHUNK.to_le_bytes())?;
+    out.write_all(&json_chunk)?;
+    out.write_all(&(bin_chunk.len() as u32).to_le_bytes())?;
+    out.write_all(&GLB_BIN_CHUNK.to_le_bytes())?;
+    out.write_all(&bin_chunk)?;
+    out.flush()
+}
@@ -0,0 +1,414 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{Assets, Handle},
+    color::{Color, LinearRgba},
+    ecs::{
+        change_detection::DetectChanges,
+        entity::Entity,
+        event::EventReader,
+        query::With,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+        world::{FromWorld, Ref, World},
+    },
+    math::{Vec2, Vec3, primitives::Cylinder},
+    pbr::StandardMaterial,
+    picking::events::{Move, Out, Pointer},
+    render::{camera::Camera, mesh::Mesh},
+    state::state::State,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::{CgarMeshData, OrbitCamera, SceneBounds};
+use crate::mesh::conversion::vertex_position;
+use crate::mesh::edge::{
+    EdgeHighlight, PickSettings, cast_pick_ray, create_edge_cylinder, local_pixel_size,
+};
+use crate::mesh::face_tree::FaceTreeCache;
+use crate::tools::systems::ActiveTool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HighlightKind {
+    Hover,
+    Selection,
+    Boundary,
+    Error,
+    // The picked half-edge's next and previous half-edges
+    Next,
+    Prev,
+}
+
+impl HighlightKind {
+    pub const ALL: [HighlightKind; 6] = [
+        HighlightKind::Hover,
+        HighlightKind::Selection,
+        HighlightKind::Boundary,
+        HighlightKind::Error,
+        HighlightKind::Next,
+        HighlightKind::Prev,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HighlightKind::Hover => "Hover",
+            HighlightKind::Selection => "Selection",
+            HighlightKind::Boundary => "Boundary",
+            HighlightKind::Error => "Error",
+            HighlightKind::Next => "Next half-edge",
+            HighlightKind::Prev => "Previous half-edge",
+        }
+    }
+}
+
+// What highlight radii are measured against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightScaling {
+    // Pixels on screen, so highlights keep their width at any zoom level
+    Screen,
+    // Thousandths of the scene diagonal, so highlights zoom with the mesh
+    Scene,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeStyle {
+    pub color: [f32; 3],
+    // Emission as a multiple of the color
+    pub emissive: f32,
+    // In the units of `HighlightStyle::scaling`
+    pub radius: f32,
+}
+
+impl EdgeStyle {
+    fn color(&self) -> Color {
+        Color::srgb(self.color[0], self.color[1], self.color[2])
+    }
+
+    pub fn material(&self) -> StandardMaterial {
+        let color = self.color();
+        StandardMaterial {
+            base_color: color,
+            emissive: LinearRgba::from(color) * self.emissive,
+            ..Default::default()
+        }
+    }
+}
+
+// Appearance of the cylinders drawn over highlighted edges
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct HighlightStyle {
+    pub scaling: HighlightScaling,
+    // Highlight the edge under the pointer with the Select tool
+    pub hover_enabled: bool,
+    pub hover: EdgeStyle,
+    pub selection: EdgeStyle,
+    pub boundary: EdgeStyle,
+    pub error: EdgeStyle,
+    pub next: EdgeStyle,
+    pub prev: EdgeStyle,
+}
+
+impl Default for HighlightStyle {
+    fn default() -> Self {
+        let style = |color, radius| EdgeStyle {
+            color,
+            emissive: 1.0,
+            radius,
+        };
+        Self {
+            scaling: HighlightScaling::Screen,
+            hover_enabled: true,
+            hover: style([1.0, 0.85, 0.3], 1.5),
+            selection: style([0.2, 1.0, 0.2], 2.5),
+            boundary: style([1.0, 0.55, 0.1], 2.5),
+            error: style([1.0, 0.1, 0.1], 3.5),
+            next: style([1.0, 0.2, 0.2], 2.5),
+            prev: style([0.2, 0.2, 1.0], 2.5),
+        }
+    }
+}
+
+impl HighlightStyle {
+    pub fn get(&self, kind: HighlightKind) -> &EdgeStyle {
+        match kind {
+            HighlightKind::Hover => &self.hover,
+            HighlightKind::Selection => &self.selection,
+            HighlightKind::Boundary => &self.boundary,
+            HighlightKind::Error => &self.error,
+            HighlightKind::Next => &self.next,
+            HighlightKind::Prev => &self.prev,
+        }
+    }
+
+    fn get_mut(&mut self, kind: HighlightKind) -> &mut EdgeStyle {
+        match kind {
+            HighlightKind::Hover => &mut self.hover,
+            HighlightKind::Selection => &mut self.selection,
+            HighlightKind::Boundary => &mut self.boundary,
+            HighlightKind::Error => &mut self.error,
+            HighlightKind::Next => &mut self.next,
+            HighlightKind::Prev => &mut self.prev,
+        }
+    }
+}
+
+// Assets shared by every highlight cylinder: one unit-length cylinder, scaled
+// per edge, and one material per kind, restyled in place when the style
+// changes. Highlights come and go with every click and pointer move.
+#[derive(Resource)]
+pub struct HighlightAssets {
+    pub cylinder: Handle<Mesh>,
+    materials: HashMap<HighlightKind, Handle<StandardMaterial>>,
+}
+
+impl HighlightAssets {
+    pub fn material(&self, kind: HighlightKind) -> Handle<StandardMaterial> {
+        self.materials[&kind].clone()
+    }
+}
+
+impl FromWorld for HighlightAssets {
+    fn from_world(world: &mut World) -> Self {
+        let style = world
+            .get_resource::<HighlightStyle>()
+            .cloned()
+            .unwrap_or_default();
+        let cylinder = world.resource_mut::<Assets<Mesh>>().add(Cylinder {
+            radius: 1.0,
+            half_height: 0.5,
+        });
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let materials = HighlightKind::ALL
+            .into_iter()
+            .map(|kind| (kind, materials.add(style.get(kind).material())))
+            .collect();
+        Self {
+            cylinder,
+            materials,
+        }
+    }
+}
+
+// The edge under the pointer and the cylinder drawn over it
+#[derive(Resource, Default)]
+pub struct HoveredEdge {
+    pub edge: Option<(Entity, (usize, usize))>,
+    pub cylinder: Option<Entity>,
+}
+
+// Edge cgar's ray cast reports under the pointer, the same way a click
+// picks one. Needs the mesh's cached face tree; building one per pointer
+// move would stall on large meshes.
+fn edge_under_pointer(
+    camera: (&Camera, &GlobalTransform),
+    mesh_global: &GlobalTransform,
+    cgar_data: &CgarMeshData,
+    surface: &FaceTreeCache,
+    cursor: Vec2,
+    depth_at: Option<Vec3>,
+    settings: &PickSettings,
+) -> Option<(usize, usize)> {
+    let hit = cast_pick_ray(
+        camera,
+        mesh_global,
+        &cgar_data.0,
+        &surface.0,
+        cursor,
+        depth_at,
+        settings,
+    )?;
+    hit.edge.map(|(a, b, _)| (a.min(b), a.max(b)))
+}
+
+// Keeps a hover highlight on the edge under the pointer while the Select
+// tool is active
+pub fn hover_edge_highlight(
+    mut commands: Commands,
+    assets: Res<HighlightAssets>,
+    mut hovered: ResMut<HoveredEdge>,
+    mut moves: EventReader<Pointer<Move>>,
+    mut outs: EventReader<Pointer<Out>>,
+    (tool, style, pick_settings): (
+        Res<State<ActiveTool>>,
+        Res<HighlightStyle>,
+        Res<PickSettings>,
+    ),
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mesh_query: Query<(&GlobalTransform, Ref<CgarMeshData>, Option<&FaceTreeCache>)>,
+) {
+    let hovered_mesh = hovered.edge.map(|(entity, _)| entity);
+    let left = outs.read().any(|event| Some(event.target) == hovered_mesh);
+    let last_move = moves
+        .read()
+        .filter(|event| mesh_query.contains(event.target))
+        .last()
+        .map(|event| {
+            (
+                event.target,
+                event.pointer_location.position,
+                event.hit.position,
+            )
+        });
+    let target = if !style.hover_enabled || *tool.get() != ActiveTool::Select {
+        None
+    } else {
+        match last_move {
+            Some((entity, cursor, depth_at)) => {
+                let found = match (camera_query.single(), mesh_query.get(entity)) {
+                    (Ok(camera), Ok((mesh_global, cgar_data, Some(surface)))) => {
+                        edge_under_pointer(
+                            camera,
+                            mesh_global,
+                            &cgar_data,
+                            surface,
+                            cursor,
+                            depth_at,
+                            &pick_settings,
+                        )
+                    }
+                    _ => None,
+                };
+                found.map(|edge| (entity, edge))
+            }
+            None if left => None,
+            None => hovered.edge,
+        }
+    };
+    // Recreate the cylinder when the mesh under it was edited
+    let edited = target.is_some_and(|(entity, _)| {
+        mesh_query
+            .get(entity)
+            .is_ok_and(|(_, cgar_data, _)| cgar_data.is_changed())
+    });
+    if target == hovered.edge && !edited {
+        return;
+    }
+
+    let old = hovered.cylinder.take();
+    if let Some(mut cylinder) = old.and_then(|cylinder| commands.get_entity(cylinder).ok()) {
+        cylinder.despawn();
+    }
+    hovered.edge = None;
+    let Some((entity, (v0, v1))) = target else {
+        return;
+    };
+    let Ok((mesh_global, cgar_data, Some(_))) = mesh_query.get(entity) else {
+        return;
+    };
+    if v0.max(v1) >= cgar_data.0.vertices.len() {
+        return;
+    }
+    hovered.edge = target;
+    hovered.cylinder = Some(create_edge_cylinder(
+        &mut commands,
+        vertex_position(&cgar_data.0, v0),
+        vertex_position(&cgar_data.0, v1),
+        mesh_global,
+        (v0, v1),
+        entity,
+        &assets,
+        HighlightKind::Hover,
+    ));
+}
+
+// Sizes highlight cylinders for the current zoom and scene size, and
+// restyles the shared materials after the style changes
+pub fn update_edge_highlights(
+    style: Res<HighlightStyle>,
+    bounds: Res<SceneBounds>,
+    assets: Res<HighlightAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mut highlights: Query<(&EdgeHighlight, &mut Transform)>,
+) {
+    if style.is_changed() {
+        for (kind, handle) in &assets.materials {
+            if let Some(material) = materials.get_mut(handle) {
+                *material = style.get(*kind).material();
+            }
+        }
+    }
+    let Ok((camera, camera_global)) = camera_query.single() else {
+        return;
+    };
+    for (highlight, mut transform) in &mut highlights {
+        let edge_style = style.get(highlight.kind);
+        let radius = match style.scaling {
+            HighlightScaling::Screen => local_pixel_size(
+                camera,
+                camera_global,
+                &GlobalTransform::IDENTITY,
+                transform.translation,
+            )
+            .map_or(0.0, |pixel| pixel * edge_style.radius),
+            HighlightScaling::Scene => bounds.diagonal * edge_style.radius * 1e-3,
+        };
+        // Y holds the edge length
+        let scale = Vec3::new(radius, transform.scale.y, radius);
+        if transform.scale != scale {
+            transform.scale = scale;
+        }
+    }
+}
+
+pub fn highlight_style_panel(mut contexts: EguiContexts, mut style: ResMut<HighlightStyle>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Highlights")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut edited = style.clone();
+            ui.horizontal(|ui| {
+                ui.label("Radius in");
+                ui.radio_value(&mut edited.scaling, HighlightScaling::Screen, "Pixels");
+                ui.radio_value(&mut edited.scaling, HighlightScaling::Scene, "‰ of scene");
+            });
+            ui.checkbox(&mut edited.hover_enabled, "Highlight edge under pointer");
+            egui::Grid::new("highlight_styles").show(ui, |ui| {
+                ui.label("");
+                ui.label("Color");
+                ui.label("Glow");
+                ui.label("Radius");
+                ui.end_row();
+                for kind in HighlightKind::ALL {
+                    let edge_style = edited.get_mut(kind);
+                    ui.label(kind.label());
+                    ui.color_edit_button_rgb(&mut edge_style.color);
+                    ui.add(egui::Slider::new(&mut edge_style.emissive, 0.0..=4.0));
+                    ui.add(egui::Slider::new(&mut edge_style.radius, 0.5..=10.0));
+                    ui.end_row();
+                }
+            });
+            if ui.button("Reset").clicked() {
+                edited = HighlightStyle::default();
+            }
+            if edited != *style {
+                *style = edited;
+            }
+        });
+}
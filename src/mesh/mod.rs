@@ -20,6 +20,22 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub mod attributes;
+pub mod collapse;
+pub mod constraints;
 pub mod conversion;
 pub mod edge;
+#[cfg(feature = "native")]
+pub mod export;
+pub mod face_tree;
+pub mod features;
+#[cfg(feature = "native")]
+pub mod gltf;
+pub mod highlight;
+pub mod normals;
+pub mod obj;
+pub mod off;
 pub mod setup;
+pub mod spatial;
+pub mod topology;
+pub mod triangulate;
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use bevy::{ecs::component::Component, math::Vec3};
+
+// How much each face contributes to the normals of its corners
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NormalWeighting {
+    Uniform,
+    // Raw cross products, so large faces dominate
+    #[default]
+    Area,
+    // Interior angle at the corner, independent of how the surface is tessellated
+    Angle,
+}
+
+impl NormalWeighting {
+    pub const ALL: [NormalWeighting; 3] = [
+        NormalWeighting::Uniform,
+        NormalWeighting::Area,
+        NormalWeighting::Angle,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NormalWeighting::Uniform => "Uniform",
+            NormalWeighting::Area => "Area-weighted",
+            NormalWeighting::Angle => "Angle-weighted",
+        }
+    }
+
+    // Contribution of triangle (`a`, `b`, `c`) to the normal at corner `a`
+    pub fn corner_contribution(self, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+        let n = (b - a).cross(c - a);
+        match self {
+            NormalWeighting::Uniform => n.normalize_or_zero(),
+            NormalWeighting::Area => n,
+            NormalWeighting::Angle => n
+                .try_normalize()
+                .map_or(Vec3::ZERO, |u| u * (b - a).angle_between(c - a)),
+        }
+    }
+}
+
+// How shading normals are generated for a mesh; meshes without this
+// component use the defaults
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct NormalSettings {
+    // Split normals across edges whose dihedral angle reaches `crease_angle_deg`
+    pub split_creases: bool,
+    pub crease_angle_deg: f32,
+    pub weighting: NormalWeighting,
+}
+
+impl Default for NormalSettings {
+    fn default() -> Self {
+        Self {
+            split_creases: false,
+            crease_angle_deg: 30.0,
+            weighting: NormalWeighting::default(),
+        }
+    }
+}
+
+// Normals authored in the source file (OBJ `vn`), kept next to the
+// `CgarMeshData` so shading can follow them instead of recomputed averages.
+// Corners are keyed by their outgoing directed edge (vertex, next vertex),
+// which survives triangulating a polygon; corners on new diagonals fall back
+// to the average over the vertex.
+#[derive(Component, Default, Debug, Clone)]
+pub struct ImportedNormals {
+    pub corners: HashMap<(usize, usize), Vec3>,
+    pub vertices: Vec<Option<Vec3>>,
+    // Shade with these; otherwise normals are recomputed from the geometry
+    pub enabled: bool,
+}
+
+impl ImportedNormals {
+    // Collects corner normals given as (vertex, next vertex, normal)
+    pub fn from_corners(
+        vertex_count: usize,
+        corners: impl IntoIterator<Item = (usize, usize, Vec3)>,
+    ) -> Self {
+        let mut normals = Self {
+            vertices: vec![None; vertex_count],
+            enabled: true,
+            ..Default::default()
+        };
+        let mut sums = vec![Vec3::ZERO; vertex_count];
+        for (v, next, n) in corners {
+            let Some(n) = n.try_normalize() else {
+                continue;
+            };
+            normals.corners.insert((v, next), n);
+            sums[v] += n;
+        }
+        for (v, sum) in sums.into_iter().enumerate() {
+            normals.vertices[v] = sum.try_normalize();
+        }
+        normals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.corners.is_empty()
+    }
+
+    // Normal of the corner at `v` whose face continues to `next`
+    pub fn corner(&self, v: usize, next: usize) -> Option<Vec3> {
+        self.corners
+            .get(&(v, next))
+            .copied()
+            .or_else(|| self.vertices.get(v).copied().flatten())
+    }
+
+    // Follows a vertex renumbering, dropping normals of removed vertices
+    pub fn remap(&mut self, vertices: &[Option<usize>]) {
+        let vertex = |v: usize| vertices.get(v).copied().flatten();
+        self.corners = self
+            .corners
+            .iter()
+            .filter_map(|(&(v, next), &n)| Some(((vertex(v)?, vertex(next)?), n)))
+            .collect();
+        let count = vertices.iter().flatten().map(|&v| v + 1).max().unwrap_or(0);
+        let mut remapped = vec![None; count];
+        for (old, &new) in vertices.iter().enumerate() {
+            if let (Some(new), Some(n)) = (new, self.vertices.get(old).copied().flatten()) {
+                remapped[new] = Some(n);
+            }
+        }
+        self.vertices = remapped;
+    }
+}
@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::fmt::Write;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::Path;
+
+use bevy::math::{DVec3, Vec3};
+use cgar::{io::obj::read_obj, mesh::basic_types::Mesh as CgarMesh, numeric::cgar_f64::CgarF64};
+
+use crate::import::systems::ImportSettings;
+use crate::mesh::conversion::build_cgar_mesh;
+use crate::mesh::normals::ImportedNormals;
+use crate::mesh::topology::MeshTopology;
+use crate::mesh::triangulate::triangulate_polygon;
+use crate::repair::ops::{TriangleSoup, remove_degenerate_faces, split_non_manifold};
+use crate::repair::systems::ImportIssues;
+
+// A mesh fresh from a file, before triangulation checks and spawning
+pub struct LoadedMesh {
+    pub mesh: CgarMesh<CgarF64, 3>,
+    // Polygons the reader already triangulated
+    pub polygons: usize,
+    // Set when the tolerant path had to clean the file up
+    pub issues: Option<ImportIssues>,
+    pub normals: Option<ImportedNormals>,
+}
+
+// Reads `v` and `f` records of OBJ text into a triangle soup. Negative
+// indices count from the end and polygons are triangulated; their count is
+// returned alongside the soup.
+pub fn parse_obj_soup(text: &str) -> Result<(TriangleSoup, usize), String> {
+    let mut soup = TriangleSoup::default();
+    let mut polygons = 0usize;
+    for (line_no, line) in text.lines().enumerate() {
+        let error = |message: String| format!("line {}: {}", line_no + 1, message);
+        let mut tokens = line.split('#').next().unwrap_or("").split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let coords = tokens
+                    .take(3)
+                    .map(str::parse::<f64>)
+                    .collect::<Result<Vec<f64>, _>>()
+                    .map_err(|e| error(e.to_string()))?;
+                if coords.len() < 3 {
+                    return Err(error("vertex needs three coordinates".to_string()));
+                }
+                soup.positions
+                    .push(DVec3::new(coords[0], coords[1], coords[2]));
+            }
+            Some("f") => {
+                let corners = tokens
+                    .map(|token| {
+                        // `v`, `v/vt`, `v//vn` and `v/vt/vn` all start with the position index
+                        let index = token
+                            .split('/')
+                            .next()
+                            .unwrap_or("")
+                            .parse::<i64>()
+                            .map_err(|e| error(e.to_string()))?;
+                        let resolved = if index < 0 {
+                            soup.positions.len() as i64 + index
+                        } else {
+                            index - 1
+                        };
+                        usize::try_from(resolved)
+                            .ok()
+                            .filter(|&v| v < soup.positions.len())
+                            .ok_or_else(|| error(format!("vertex index {} out of range", index)))
+                    })
+                    .collect::<Result<Vec<usize>, String>>()?;
+                if corners.len() < 3 {
+                    return Err(error("face needs at least three vertices".to_string()));
+                }
+                if corners.len() > 3 {
+                    polygons += 1;
+                }
+                let points: Vec<DVec3> = corners.iter().map(|&v| soup.positions[v]).collect();
+                soup.triangles.extend(
+                    triangulate_polygon(&points)
+                        .into_iter()
+                        .map(|tri| tri.map(|k| corners[k])),
+                );
+            }
+            _ => {}
+        }
+    }
+    if soup.positions.is_empty() {
+        return Err("no vertices".to_string());
+    }
+    Ok((soup, polygons))
+}
+
+// Resolves a 1-based OBJ index, negative ones counting back from `len`
+fn obj_index(token: &str, len: usize) -> Option<usize> {
+    let index = token.parse::<i64>().ok()?;
+    let resolved = if index < 0 {
+        len as i64 + index
+    } else {
+        index - 1
+    };
+    usize::try_from(resolved).ok().filter(|&i| i < len)
+}
+
+// Authored normals referenced by `f` records (`v//vn`, `v/vt/vn`). Malformed
+// references are skipped rather than failing the load; `None` when the file
+// has no usable normals.
+pub fn parse_obj_normals(text: &str) -> Option<ImportedNormals> {
+    let mut vertex_count = 0usize;
+    let mut normals: Vec<Vec3> = Vec::new();
+    let mut corners: Vec<(usize, usize, Vec3)> = Vec::new();
+    for line in text.lines() {
+        let mut tokens = line.split('#').next().unwrap_or("").split_whitespace();
+        match tokens.next() {
+            Some("v") => vertex_count += 1,
+            Some("vn") => {
+                let coords: Vec<f32> = tokens.take(3).filter_map(|t| t.parse().ok()).collect();
+                normals.push(match coords[..] {
+                    [x, y, z] => Vec3::new(x, y, z),
+                    _ => Vec3::NAN,
+                });
+            }
+            Some("f") => {
+                let face: Vec<(Option<usize>, Option<Vec3>)> = tokens
+                    .map(|token| {
+                        let mut parts = token.split('/');
+                        let v = parts.next().and_then(|t| obj_index(t, vertex_count));
+                        let n = parts
+                            .nth(1)
+                            .and_then(|t| obj_index(t, normals.len()))
+                            .map(|i| normals[i])
+                            .filter(|n| n.is_finite());
+                        (v, n)
+                    })
+                    .collect();
+                for (k, &(v, n)) in face.iter().enumerate() {
+                    let next = face[(k + 1) % face.len()].0;
+                    if let (Some(v), Some(next), Some(n)) = (v, next, n) {
+                        corners.push((v, next, n));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let normals = ImportedNormals::from_corners(vertex_count, corners);
+    (!normals.is_empty()).then_some(normals)
+}
+
+// Builds a mesh from OBJ text already in memory, for files that don't come
+// from a path (browser uploads, fetched URLs)
+pub fn parse_obj(text: &str) -> Result<(CgarMesh<CgarF64, 3>, usize), String> {
+    let (soup, polygons) = parse_obj_soup(text)?;
+    Ok((
+        build_cgar_mesh(&soup.positions, soup.triangles.iter().copied()),
+        polygons,
+    ))
+}
+
+pub fn parse_obj_tolerant(text: &str) -> Result<LoadedMesh, String> {
+    let (soup, polygons) = parse_obj_soup(text)?;
+    load_soup_tolerant(&soup, polygons, parse_obj_normals(text))
+}
+
+// Loads whatever it can from a soup the half-edge mesh would refuse:
+// degenerate and duplicate faces are dropped and non-manifold edges and
+// vertices are split into separate shells, with markers where that happened
+pub fn load_soup_tolerant(
+    soup: &TriangleSoup,
+    polygons: usize,
+    normals: Option<ImportedNormals>,
+) -> Result<LoadedMesh, String> {
+    let cleaned = remove_degenerate_faces(soup, 0.0);
+    let split = split_non_manifold(&cleaned.soup);
+
+    let mut markers = cleaned.markers;
+    markers.points.extend(split.markers.points);
+    markers.faces.extend(split.markers.faces);
+    markers.loops.extend(split.markers.loops);
+    let clean = markers.points.is_empty() && markers.faces.is_empty() && markers.loops.is_empty();
+
+    // Cleanup compacts vertex ids; vertices split off keep recomputed normals
+    let normals = normals.map(|mut normals| {
+        normals.remap(&cleaned.vertex_map);
+        normals
+    });
+
+    let soup = split.soup;
+    let mesh = catch_unwind(AssertUnwindSafe(|| {
+        build_cgar_mesh(&soup.positions, soup.triangles.iter().copied())
+    }))
+    .map_err(|_| "mesh construction failed even after cleanup".to_string())?;
+    Ok(LoadedMesh {
+        mesh,
+        polygons,
+        issues: (!clean).then(|| ImportIssues {
+            summary: format!("{}; {}", cleaned.summary, split.summary),
+            markers,
+            show: true,
+        }),
+        normals,
+    })
+}
+
+// Strict loads go through the regular reader; if it fails or panics the
+// tolerant path takes over and says so in the issues
+pub fn with_fallback(
+    strict: Option<Result<LoadedMesh, String>>,
+    tolerant: impl FnOnce() -> Result<LoadedMesh, String>,
+) -> Result<LoadedMesh, String> {
+    let strict_error = match strict {
+        Some(Ok(loaded)) => return Ok(loaded),
+        Some(Err(err)) => Some(err),
+        None => None,
+    };
+    let mut loaded = tolerant()?;
+    if let Some(err) = strict_error {
+        let issues = loaded.issues.get_or_insert_with(|| ImportIssues {
+            summary: "no defects found".to_string(),
+            markers: Default::default(),
+            show: true,
+        });
+        issues.summary = format!(
+            "Strict load failed ({}), loaded tolerantly: {}",
+            err, issues.summary
+        );
+    }
+    Ok(loaded)
+}
+
+// Imported normals start out in use unless the settings ask to recompute them
+pub fn apply_settings(mut loaded: LoadedMesh, settings: &ImportSettings) -> LoadedMesh {
+    if let Some(normals) = loaded.normals.as_mut() {
+        normals.enabled = !settings.recompute_normals;
+    }
+    loaded
+}
+
+pub fn load_obj_file(path: &Path, settings: &ImportSettings) -> Result<LoadedMesh, String> {
+    let load = || {
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let strict =
+            (!settings.tolerant).then(|| match catch_unwind(|| read_obj::<CgarF64, _>(path)) {
+                Ok(Ok(mesh)) => Ok(LoadedMesh {
+                    mesh,
+                    polygons: 0,
+                    issues: None,
+                    normals: parse_obj_normals(&text),
+                }),
+                Ok(Err(err)) => Err(format!("{:?}", err)),
+                Err(_) => Err("reader panicked".to_string()),
+            });
+        with_fallback(strict, || parse_obj_tolerant(&text))
+    };
+    load()
+        .map(|loaded| apply_settings(loaded, settings))
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn load_obj_text(text: &str, settings: &ImportSettings) -> Result<LoadedMesh, String> {
+    let strict = (!settings.tolerant).then(|| match catch_unwind(|| parse_obj(text)) {
+        Ok(Ok((mesh, polygons))) => Ok(LoadedMesh {
+            mesh,
+            polygons,
+            issues: None,
+            normals: parse_obj_normals(text),
+        }),
+        Ok(Err(err)) => Err(err),
+        Err(_) => Err("mesh construction panicked".to_string()),
+    });
+    with_fallback(strict, || parse_obj_tolerant(text))
+        .map(|loaded| apply_settings(loaded, settings))
+}
+
+// Selected faces as standalone OBJ text, renumbering the used vertices
+pub fn faces_to_obj(topology: &MeshTopology, faces: &[usize]) -> String {
+    let mut text = String::new();
+    let mut remap: BTreeMap<usize, usize> = BTreeMap::new();
+
+    let triangles: Vec<[usize; 3]> = faces
+        .iter()
+        .filter_map(|&f| topology.triangles.get(f).copied().flatten())
+        .collect();
+    for tri in &triangles {
+        for &v in tri {
+            let next = remap.len() + 1;
+            if let Entry::Vacant(entry) = remap.entry(v) {
+                entry.insert(next);
+                let p = topology.positions[v];
+                let _ = writeln!(text, "v {} {} {}", p.x, p.y, p.z);
+            }
+        }
+    }
+    for tri in &triangles {
+        let _ = writeln!(
+            text,
+            "f {} {} {}",
+            remap[&tri[0]], remap[&tri[1]], remap[&tri[2]]
+        );
+    }
+    text
+}
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::panic::catch_unwind;
+
+use bevy::math::DVec3;
+
+use crate::import::systems::ImportSettings;
+use crate::mesh::conversion::build_cgar_mesh;
+use crate::mesh::obj::{LoadedMesh, apply_settings, load_soup_tolerant, with_fallback};
+use crate::mesh::triangulate::triangulate_polygon;
+use crate::repair::ops::TriangleSoup;
+
+// Lines with their comments stripped, skipping the blank ones
+fn off_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(line_no, line)| (line_no + 1, line.split('#').next().unwrap_or("").trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+// `OFF` and its color/normal variants (`COFF`, `NOFF`, `CNOFF`); the
+// per-vertex extras are skipped when reading
+fn is_off_header(token: &str) -> bool {
+    matches!(token, "OFF" | "COFF" | "NOFF" | "CNOFF")
+}
+
+pub fn is_off_text(text: &str) -> bool {
+    off_lines(text)
+        .next()
+        .and_then(|(_, line)| line.split_whitespace().next())
+        .is_some_and(is_off_header)
+}
+
+// Reads an OFF file into a triangle soup: a header, the vertex and face
+// counts, then one vertex per line and faces as a corner count followed by
+// 0-based indices. Polygons are triangulated; their count is returned
+// alongside the soup.
+pub fn parse_off_soup(text: &str) -> Result<(TriangleSoup, usize), String> {
+    let mut lines = off_lines(text);
+    let (_, header) = lines.next().ok_or("empty file")?;
+    let mut tokens = header.split_whitespace();
+    if !tokens.next().is_some_and(is_off_header) {
+        return Err("missing OFF header".to_string());
+    }
+    // The counts may share the header line or follow on the next one
+    let mut counts: Vec<&str> = tokens.collect();
+    if counts.is_empty() {
+        let (_, line) = lines.next().ok_or("missing vertex and face counts")?;
+        counts = line.split_whitespace().collect();
+    }
+    let counts = counts
+        .iter()
+        .take(2)
+        .map(|n| n.parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|e| format!("counts: {}", e))?;
+    let [vertex_count, face_count] = counts[..] else {
+        return Err("missing vertex and face counts".to_string());
+    };
+
+    let mut soup = TriangleSoup::default();
+    let mut polygons = 0usize;
+    for _ in 0..vertex_count {
+        let (line_no, line) = lines.next().ok_or("fewer vertices than declared")?;
+        let coords = line
+            .split_whitespace()
+            .take(3)
+            .map(str::parse::<f64>)
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|e| format!("line {}: {}", line_no, e))?;
+        if coords.len() < 3 {
+            return Err(format!("line {}: vertex needs three coordinates", line_no));
+        }
+        soup.positions
+            .push(DVec3::new(coords[0], coords[1], coords[2]));
+    }
+    for _ in 0..face_count {
+        let (line_no, line) = lines.next().ok_or("fewer faces than declared")?;
+        let error = |message: String| format!("line {}: {}", line_no, message);
+        let mut tokens = line.split_whitespace();
+        let corner_count = tokens
+            .next()
+            .unwrap_or("")
+            .parse::<usize>()
+            .map_err(|e| error(e.to_string()))?;
+        // Anything after the indices is a face color
+        let corners = tokens
+            .take(corner_count)
+            .map(|token| {
+                let index = token.parse::<usize>().map_err(|e| error(e.to_string()))?;
+                (index < soup.positions.len())
+                    .then_some(index)
+                    .ok_or_else(|| error(format!("vertex index {} out of range", index)))
+            })
+            .collect::<Result<Vec<usize>, String>>()?;
+        if corners.len() < corner_count {
+            return Err(error(format!("face declares {} vertices", corner_count)));
+        }
+        if corners.len() < 3 {
+            return Err(error("face needs at least three vertices".to_string()));
+        }
+        if corners.len() > 3 {
+            polygons += 1;
+        }
+        let points: Vec<DVec3> = corners.iter().map(|&v| soup.positions[v]).collect();
+        soup.triangles.extend(
+            triangulate_polygon(&points)
+                .into_iter()
+                .map(|tri| tri.map(|k| corners[k])),
+        );
+    }
+    if soup.positions.is_empty() {
+        return Err("no vertices".to_string());
+    }
+    Ok((soup, polygons))
+}
+
+// Same strict-then-tolerant loading as OBJ text; OFF carries no normals
+pub fn load_off_text(text: &str, settings: &ImportSettings) -> Result<LoadedMesh, String> {
+    let (soup, polygons) = parse_off_soup(text)?;
+    let strict = (!settings.tolerant).then(|| {
+        catch_unwind(|| build_cgar_mesh(&soup.positions, soup.triangles.iter().copied()))
+            .map(|mesh| LoadedMesh {
+                mesh,
+                polygons,
+                issues: None,
+                normals: None,
+            })
+            .map_err(|_| "mesh construction panicked".to_string())
+    });
+    with_fallback(strict, || load_soup_tolerant(&soup, polygons, None))
+        .map(|loaded| apply_settings(loaded, settings))
+}
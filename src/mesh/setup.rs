@@ -21,24 +21,48 @@
 // SOFTWARE.
 
 use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::path::{Path, PathBuf};
 
 use bevy::{
-    asset::Assets,
+    asset::{Assets, Handle},
     color::Color,
-    ecs::system::{Commands, ResMut},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        name::Name,
+        resource::Resource,
+        system::{Commands, Res, ResMut},
+    },
     pbr::{MeshMaterial3d, StandardMaterial},
     picking::Pickable,
     render::mesh::{Mesh, Mesh3d},
     transform::components::Transform,
     utils::default,
 };
-use cgar::{
-    geometry::spatial_element::SpatialElement, io::obj::read_obj, numeric::cgar_f64::CgarF64,
-};
+use cgar::{geometry::spatial_element::SpatialElement, numeric::cgar_f64::CgarF64};
 
-use crate::{camera::components::CgarMeshData, mesh::conversion::cgar_to_bevy_mesh};
+use crate::{
+    camera::components::CgarMeshData,
+    import::systems::ImportSettings,
+    mesh::conversion::cgar_to_bevy_mesh,
+    mesh::normals::NormalWeighting,
+    mesh::obj::{LoadedMesh, load_obj_file},
+    mesh::triangulate::triangulate_mesh,
+    notifications::systems::{NoticeLevel, Notify},
+    pointcloud::io::is_point_cloud_path,
+    utils::cli::CliOptions,
+};
 use cgar::mesh::basic_types::Mesh as CgarMesh;
 
+// File a mesh was loaded from
+#[derive(Component, Debug, Clone)]
+pub struct MeshSource(pub PathBuf);
+
+// Material shared by every cgar mesh entity
+#[derive(Resource)]
+pub struct DefaultMeshMaterial(pub Handle<StandardMaterial>);
+
 fn create_grid_mesh(grid_size: usize) -> CgarMesh<CgarF64, 3> {
     let mut mesh = CgarMesh::<CgarF64, 3>::new();
 
@@ -71,10 +95,79 @@ fn create_grid_mesh(grid_size: usize) -> CgarMesh<CgarF64, 3> {
     mesh
 }
 
-pub fn setup_cgar_mesh(
+// Sanity checks on a freshly loaded mesh. Errors mean the mesh shouldn't be
+// spawned at all; warnings are worth telling the user about.
+pub fn validate_mesh(name: &str, mesh: &CgarMesh<CgarF64, 3>) -> Option<Notify> {
+    if mesh.vertices.is_empty() {
+        return Some(Notify::error(format!("{} has no vertices", name)));
+    }
+    let non_finite = mesh
+        .vertices
+        .iter()
+        .filter(|vertex| (0..3).any(|i| !vertex.position[i].0.is_finite()))
+        .count();
+    if non_finite > 0 {
+        return Some(Notify::error(format!(
+            "{} has {} vertices with non-finite coordinates",
+            name, non_finite
+        )));
+    }
+    if mesh.faces.iter().all(|face| face.removed) {
+        return Some(Notify::warning(format!("{} has no faces", name)));
+    }
+    None
+}
+
+// Triangulates polygon faces and validates a freshly loaded mesh, reporting
+// both. `triangulated` counts polygons the reader already split. Returns
+// `None` when the mesh can't be used.
+fn prepare_loaded_mesh(
+    name: &str,
+    mut mesh: CgarMesh<CgarF64, 3>,
+    mut triangulated: usize,
+    notices: &mut EventWriter<Notify>,
+) -> Option<CgarMesh<CgarF64, 3>> {
+    if let Some((triangles, polygons)) = triangulate_mesh(&mesh) {
+        mesh = triangles;
+        triangulated += polygons;
+    }
+    if triangulated > 0 {
+        notices.write(Notify::info(format!(
+            "{}: triangulated {} polygon faces",
+            name, triangulated
+        )));
+    }
+    if let Some(problem) = validate_mesh(name, &mesh) {
+        let fatal = problem.level == NoticeLevel::Error;
+        notices.write(problem);
+        if fatal {
+            return None;
+        }
+    }
+    Some(mesh)
+}
+
+// Material shared by every mesh spawned later
+pub fn setup_cgar_mesh(mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>) {
+    commands.insert_resource(DefaultMeshMaterial(materials.add(StandardMaterial {
+        base_color: Color::srgb(0.9, 0.9, 0.95), // Brighter base color
+        perceptual_roughness: 0.3,               // Lower roughness = more reflective
+        metallic: 0.0, // Non-metallic for better visibility with ambient light
+        emissive: Color::srgb(0.5, 0.5, 0.5).into(), // Add slight emission
+        ..default()
+    })));
+}
+
+// Meshes given on the command line, falling back to a demo grid when none
+// of them loads. Point clouds are picked up by `setup_point_clouds`, and
+// URLs arrive later through the import queue once they are downloaded.
+pub fn load_cli_meshes(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut notices: EventWriter<Notify>,
+    material: Res<DefaultMeshMaterial>,
+    cli: Res<CliOptions>,
+    import_settings: Res<ImportSettings>,
 ) where
     for<'a> &'a CgarF64: Add<&'a CgarF64, Output = CgarF64>
         + Sub<&'a CgarF64, Output = CgarF64>
@@ -82,25 +175,101 @@ pub fn setup_cgar_mesh(
         + Div<&'a CgarF64, Output = CgarF64>
         + Neg<Output = CgarF64>,
 {
-    // For now: create a simple cube as a placeholder
-    // let cgar_mesh = read_obj::<CgarF64, _>("/mnt/v/cgar_meshes/cube.obj").unwrap(); // Replace with your actual CGAR mesh
-    let cgar_mesh = create_grid_mesh(16);
-    let bevy_mesh = cgar_to_bevy_mesh(&cgar_mesh);
+    let mut spawned = false;
+    for path in &cli.mesh_paths {
+        let path = Path::new(path);
+        if is_point_cloud_path(path) {
+            continue;
+        }
+        match load_obj_file(path, &import_settings) {
+            Ok(loaded) => {
+                let name = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                if let Some(entity) = spawn_loaded_mesh(
+                    &mut commands,
+                    &mut meshes,
+                    &material,
+                    name,
+                    loaded,
+                    &mut notices,
+                ) {
+                    commands
+                        .entity(entity)
+                        .insert(MeshSource(path.to_path_buf()));
+                    spawned = true;
+                }
+            }
+            Err(err) => {
+                notices.write(Notify::error(format!("Failed to load {}", err)));
+            }
+        }
+    }
 
-    let handle = meshes.add(bevy_mesh);
-    let material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.9, 0.9, 0.95), // Brighter base color
-        perceptual_roughness: 0.3,               // Lower roughness = more reflective
-        metallic: 0.0, // Non-metallic for better visibility with ambient light
-        emissive: Color::srgb(0.5, 0.5, 0.5).into(), // Add slight emission
-        ..default()
-    });
+    let has_point_clouds = cli
+        .mesh_paths
+        .iter()
+        .any(|path| is_point_cloud_path(Path::new(path)));
+    if !spawned && !has_point_clouds && cli.mesh_urls.is_empty() {
+        spawn_cgar_mesh(
+            &mut commands,
+            &mut meshes,
+            &material,
+            "grid".to_string(),
+            create_grid_mesh(16),
+            Transform::default(),
+        );
+    }
+}
 
-    commands.spawn((
-        MeshMaterial3d(material),
-        Mesh3d(handle.clone()),
+// Spawns a mesh read from a file once it passes `prepare_loaded_mesh`,
+// attaching its authored normals and whatever the tolerant importer had to fix
+pub fn spawn_loaded_mesh(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: &DefaultMeshMaterial,
+    name: String,
+    loaded: LoadedMesh,
+    notices: &mut EventWriter<Notify>,
+) -> Option<Entity> {
+    let cgar_mesh = prepare_loaded_mesh(&name, loaded.mesh, loaded.polygons, notices)?;
+    let entity = spawn_cgar_mesh(
+        commands,
+        meshes,
+        material,
+        name.clone(),
+        cgar_mesh,
         Transform::default(),
-        Pickable::default(),
-        CgarMeshData(cgar_mesh),
-    ));
+    );
+    if let Some(issues) = loaded.issues {
+        notices.write(Notify::warning(format!("{}: {}", name, issues.summary)));
+        commands.entity(entity).insert(issues);
+    }
+    if let Some(normals) = loaded.normals {
+        commands.entity(entity).insert(normals);
+    }
+    Some(entity)
+}
+
+// Spawns a pickable mesh entity backed by `cgar_mesh`
+pub fn spawn_cgar_mesh(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: &DefaultMeshMaterial,
+    name: String,
+    cgar_mesh: CgarMesh<CgarF64, 3>,
+    transform: Transform,
+) -> Entity {
+    let handle = meshes.add(cgar_to_bevy_mesh(&cgar_mesh, NormalWeighting::default()));
+    commands
+        .spawn((
+            Name::new(name),
+            MeshMaterial3d(material.0.clone()),
+            Mesh3d(handle),
+            transform,
+            Pickable::default(),
+            CgarMeshData(cgar_mesh),
+        ))
+        .id()
 }
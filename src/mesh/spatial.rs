@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use bevy::math::DVec3;
+
+// Uniform hash grid over points for nearest-neighbor and radius queries
+pub struct PointGrid {
+    cell: f64,
+    cells: HashMap<(i64, i64, i64), Vec<usize>>,
+    points: Vec<(usize, DVec3)>,
+    min: DVec3,
+    max: DVec3,
+}
+
+impl PointGrid {
+    // `points` pairs an external id with its position
+    pub fn new(points: Vec<(usize, DVec3)>, cell: f64) -> Self {
+        let cell = if cell > 0.0 { cell } else { 1.0 };
+        let mut grid = Self {
+            cell,
+            cells: HashMap::new(),
+            points,
+            min: DVec3::splat(f64::INFINITY),
+            max: DVec3::splat(f64::NEG_INFINITY),
+        };
+        for (slot, &(_, p)) in grid.points.iter().enumerate() {
+            grid.min = grid.min.min(p);
+            grid.max = grid.max.max(p);
+            let key = grid.key(p);
+            grid.cells.entry(key).or_default().push(slot);
+        }
+        grid
+    }
+
+    // Picks a cell size giving a few points per occupied cell
+    pub fn with_auto_cell(points: Vec<(usize, DVec3)>) -> Self {
+        let (min, max) = points.iter().fold(
+            (DVec3::splat(f64::INFINITY), DVec3::splat(f64::NEG_INFINITY)),
+            |(lo, hi), &(_, p)| (lo.min(p), hi.max(p)),
+        );
+        let diagonal = if points.is_empty() {
+            1.0
+        } else {
+            (max - min).length()
+        };
+        let cell = diagonal / (points.len().max(1) as f64).cbrt();
+        Self::new(points, cell.max(1e-12))
+    }
+
+    fn key(&self, p: DVec3) -> (i64, i64, i64) {
+        let k = (p / self.cell).floor();
+        (k.x as i64, k.y as i64, k.z as i64)
+    }
+
+    fn visit_shell(&self, center: (i64, i64, i64), ring: i64, mut f: impl FnMut(usize)) {
+        for dx in -ring..=ring {
+            for dy in -ring..=ring {
+                for dz in -ring..=ring {
+                    if dx.abs().max(dy.abs()).max(dz.abs()) != ring {
+                        continue;
+                    }
+                    let key = (center.0 + dx, center.1 + dy, center.2 + dz);
+                    if let Some(slots) = self.cells.get(&key) {
+                        slots.iter().for_each(|&slot| f(slot));
+                    }
+                }
+            }
+        }
+    }
+
+    // Closest point as (external id, distance)
+    pub fn nearest(&self, q: DVec3) -> Option<(usize, f64)> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let center = self.key(q);
+        let outside = (self.min - q).max(q - self.max).max(DVec3::ZERO).length();
+        let max_ring = ((outside + (self.max - self.min).length()) / self.cell).ceil() as i64 + 1;
+
+        let mut best: Option<(usize, f64)> = None;
+        for ring in 0..=max_ring {
+            self.visit_shell(center, ring, |slot| {
+                let (id, p) = self.points[slot];
+                let d = p.distance(q);
+                if best.is_none_or(|(_, bd)| d < bd) {
+                    best = Some((id, d));
+                }
+            });
+            // Points in further shells are at least `ring * cell` away
+            if best.is_some_and(|(_, bd)| bd <= ring as f64 * self.cell) {
+                break;
+            }
+        }
+        best
+    }
+
+    // Points within `radius` of `q` as (external id, distance), unordered
+    pub fn within(&self, q: DVec3, radius: f64) -> Vec<(usize, f64)> {
+        let mut found = Vec::new();
+        let (lo, hi) = (
+            self.key(q - DVec3::splat(radius)),
+            self.key(q + DVec3::splat(radius)),
+        );
+        for x in lo.0..=hi.0 {
+            for y in lo.1..=hi.1 {
+                for z in lo.2..=hi.2 {
+                    let Some(slots) = self.cells.get(&(x, y, z)) else {
+                        continue;
+                    };
+                    for &slot in slots {
+                        let (id, p) = self.points[slot];
+                        let d = p.distance(q);
+                        if d <= radius {
+                            found.push((id, d));
+                        }
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    // The `k` closest points as (external id, distance), nearest first
+    pub fn k_nearest(&self, q: DVec3, k: usize) -> Vec<(usize, f64)> {
+        let mut found: Vec<(usize, f64)> = Vec::new();
+        if self.points.is_empty() || k == 0 {
+            return found;
+        }
+        let center = self.key(q);
+        let outside = (self.min - q).max(q - self.max).max(DVec3::ZERO).length();
+        let max_ring = ((outside + (self.max - self.min).length()) / self.cell).ceil() as i64 + 1;
+
+        for ring in 0..=max_ring {
+            self.visit_shell(center, ring, |slot| {
+                let (id, p) = self.points[slot];
+                found.push((id, p.distance(q)));
+            });
+            if found.len() >= k {
+                found.sort_by(|a, b| a.1.total_cmp(&b.1));
+                found.truncate(k);
+                if found[k - 1].1 <= ring as f64 * self.cell {
+                    break;
+                }
+            }
+        }
+        found.sort_by(|a, b| a.1.total_cmp(&b.1));
+        found
+    }
+}
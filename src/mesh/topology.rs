@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+
+use bevy::math::DVec3;
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::mesh::conversion::tri_vertices_of_face;
+
+// Flattened f64 view of a cgar mesh used by analysis and selection tools.
+// Faces are indexed by cgar face id; removed faces are `None`.
+pub struct MeshTopology {
+    pub positions: Vec<DVec3>,
+    pub triangles: Vec<Option<[usize; 3]>>,
+    // Undirected (min, max) edge -> incident face ids
+    pub edge_faces: BTreeMap<(usize, usize), Vec<usize>>,
+    pub vertex_faces: Vec<Vec<usize>>,
+    pub vertex_neighbors: Vec<Vec<usize>>,
+}
+
+impl MeshTopology {
+    pub fn from_cgar(m: &CgarMesh<CgarF64, 3>) -> Self {
+        let positions: Vec<DVec3> = m
+            .vertices
+            .iter()
+            .map(|v| DVec3::new(v.position[0].0, v.position[1].0, v.position[2].0))
+            .collect();
+
+        let mut triangles = Vec::with_capacity(m.faces.len());
+        for (fi, f) in m.faces.iter().enumerate() {
+            if f.removed {
+                triangles.push(None);
+            } else {
+                triangles.push(Some(tri_vertices_of_face(m, fi)));
+            }
+        }
+
+        Self::from_triangles(positions, triangles)
+    }
+
+    pub fn from_triangles(positions: Vec<DVec3>, triangles: Vec<Option<[usize; 3]>>) -> Self {
+        let mut edge_faces: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+        let mut vertex_faces = vec![Vec::new(); positions.len()];
+        let mut vertex_neighbors: Vec<Vec<usize>> = vec![Vec::new(); positions.len()];
+
+        for (fi, tri) in triangles.iter().enumerate() {
+            let Some(tri) = tri else {
+                continue;
+            };
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                edge_faces.entry((a.min(b), a.max(b))).or_default().push(fi);
+                vertex_faces[a].push(fi);
+            }
+        }
+        for &(a, b) in edge_faces.keys() {
+            vertex_neighbors[a].push(b);
+            vertex_neighbors[b].push(a);
+        }
+
+        Self {
+            positions,
+            triangles,
+            edge_faces,
+            vertex_faces,
+            vertex_neighbors,
+        }
+    }
+
+    // Re-reads `faces` from `m` after an edit that changed no other face,
+    // along with the positions of every vertex they use before and after
+    pub fn refresh_faces(&mut self, m: &CgarMesh<CgarF64, 3>, faces: &[usize]) {
+        self.positions.resize(m.vertices.len(), DVec3::ZERO);
+        self.vertex_faces.resize(m.vertices.len(), Vec::new());
+        self.vertex_neighbors.resize(m.vertices.len(), Vec::new());
+        self.triangles.resize(m.faces.len(), None);
+        let mut moved = Vec::new();
+        for &fi in faces {
+            if let Some(tri) = self.triangles[fi].take() {
+                for k in 0..3 {
+                    let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                    let key = (a.min(b), a.max(b));
+                    if let Some(incident) = self.edge_faces.get_mut(&key) {
+                        incident.retain(|&f| f != fi);
+                        if incident.is_empty() {
+                            self.edge_faces.remove(&key);
+                            unlink(&mut self.vertex_neighbors[a], b);
+                            unlink(&mut self.vertex_neighbors[b], a);
+                        }
+                    }
+                    self.vertex_faces[a].retain(|&f| f != fi);
+                }
+                moved.extend(tri);
+            }
+            if m.faces[fi].removed {
+                continue;
+            }
+            let tri = tri_vertices_of_face(m, fi);
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                let incident = self.edge_faces.entry((a.min(b), a.max(b))).or_default();
+                if incident.is_empty() {
+                    self.vertex_neighbors[a].push(b);
+                    self.vertex_neighbors[b].push(a);
+                }
+                incident.push(fi);
+                self.vertex_faces[a].push(fi);
+            }
+            self.triangles[fi] = Some(tri);
+            moved.extend(tri);
+        }
+        for v in moved {
+            let p = &m.vertices[v].position;
+            self.positions[v] = DVec3::new(p[0].0, p[1].0, p[2].0);
+        }
+    }
+
+    pub fn live_faces(&self) -> impl Iterator<Item = (usize, [usize; 3])> + '_ {
+        self.triangles
+            .iter()
+            .enumerate()
+            .filter_map(|(fi, tri)| tri.map(|t| (fi, t)))
+    }
+
+    // Vertices referenced by at least one live face
+    pub fn used_vertices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.positions.len()).filter(|&v| !self.vertex_faces[v].is_empty())
+    }
+
+    pub fn corners(&self, tri: [usize; 3]) -> [DVec3; 3] {
+        tri.map(|v| self.positions[v])
+    }
+
+    // Unnormalized normal, twice the triangle area in length
+    pub fn face_cross(&self, fi: usize) -> DVec3 {
+        let Some(tri) = self.triangles[fi] else {
+            return DVec3::ZERO;
+        };
+        let [a, b, c] = self.corners(tri);
+        (b - a).cross(c - a)
+    }
+
+    pub fn face_normal(&self, fi: usize) -> DVec3 {
+        self.face_cross(fi).normalize_or_zero()
+    }
+
+    pub fn face_area(&self, fi: usize) -> f64 {
+        0.5 * self.face_cross(fi).length()
+    }
+
+    // Area-weighted average of the incident face normals
+    pub fn vertex_normal(&self, v: usize) -> DVec3 {
+        self.vertex_faces[v]
+            .iter()
+            .map(|&f| self.face_cross(f))
+            .sum::<DVec3>()
+            .normalize_or_zero()
+    }
+
+    // Faces sharing an edge with `fi`
+    pub fn face_neighbors(&self, fi: usize) -> Vec<usize> {
+        let Some(tri) = self.triangles[fi] else {
+            return Vec::new();
+        };
+        let mut neighbors = Vec::new();
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            if let Some(faces) = self.edge_faces.get(&(a.min(b), a.max(b))) {
+                neighbors.extend(faces.iter().copied().filter(|&f| f != fi));
+            }
+        }
+        neighbors
+    }
+
+    pub fn edge_length(&self, edge: (usize, usize)) -> f64 {
+        self.positions[edge.0].distance(self.positions[edge.1])
+    }
+
+    // Angle in radians between the normals of the two faces of an interior edge
+    pub fn dihedral_angle(&self, edge: (usize, usize)) -> Option<f64> {
+        match self.edge_faces.get(&edge).map(Vec::as_slice) {
+            Some(&[f0, f1]) => {
+                let cos = self.face_normal(f0).dot(self.face_normal(f1));
+                Some(cos.clamp(-1.0, 1.0).acos())
+            }
+            _ => None,
+        }
+    }
+}
+
+// Drops one occurrence of `v` from a neighbor list
+fn unlink(neighbors: &mut Vec<usize>, v: usize) {
+    if let Some(i) = neighbors.iter().position(|&n| n == v) {
+        neighbors.swap_remove(i);
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::{DVec2, DVec3};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::mesh::conversion::{build_cgar_mesh, face_vertices};
+
+// Splits a polygon, given as its corner positions in loop order, into
+// triangles of local corner indices. Ears are clipped in the plane of the
+// Newell normal, so concave loops come out right; a fan takes over when no
+// ear is left (self-intersecting or degenerate loops).
+pub fn triangulate_polygon(points: &[DVec3]) -> Vec<[usize; 3]> {
+    let n = points.len();
+    if n < 3 {
+        return Vec::new();
+    }
+    let fan = |corners: &[usize]| -> Vec<[usize; 3]> {
+        (1..corners.len() - 1)
+            .map(|i| [corners[0], corners[i], corners[i + 1]])
+            .collect()
+    };
+    let mut remaining: Vec<usize> = (0..n).collect();
+    if n == 3 {
+        return fan(&remaining);
+    }
+
+    let mut normal = DVec3::ZERO;
+    for i in 0..n {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        normal += DVec3::new(
+            (a.y - b.y) * (a.z + b.z),
+            (a.z - b.z) * (a.x + b.x),
+            (a.x - b.x) * (a.y + b.y),
+        );
+    }
+    let Some(normal) = normal.try_normalize() else {
+        return fan(&remaining);
+    };
+    // (u, v, normal) is right-handed, so the loop runs counter-clockwise in 2D
+    let u = normal.any_orthonormal_vector();
+    let v = normal.cross(u);
+    let flat: Vec<DVec2> = points
+        .iter()
+        .map(|p| DVec2::new(p.dot(u), p.dot(v)))
+        .collect();
+    let turn = |a: usize, b: usize, c: usize| (flat[b] - flat[a]).perp_dot(flat[c] - flat[a]);
+
+    let mut triangles = Vec::with_capacity(n - 2);
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let ear = (0..m).find(|&i| {
+            let (a, b, c) = (
+                remaining[(i + m - 1) % m],
+                remaining[i],
+                remaining[(i + 1) % m],
+            );
+            turn(a, b, c) > 0.0
+                && remaining.iter().all(|&p| {
+                    p == a
+                        || p == b
+                        || p == c
+                        || turn(a, b, p) < 0.0
+                        || turn(b, c, p) < 0.0
+                        || turn(c, a, p) < 0.0
+                })
+        });
+        let Some(i) = ear else {
+            triangles.extend(fan(&remaining));
+            return triangles;
+        };
+        triangles.push([
+            remaining[(i + m - 1) % m],
+            remaining[i],
+            remaining[(i + 1) % m],
+        ]);
+        remaining.remove(i);
+    }
+    triangles.extend(fan(&remaining));
+    triangles
+}
+
+// Rebuilds a mesh whose faces aren't all triangles, keeping vertex ids.
+// Returns the new mesh and how many polygons were split, or `None` if every
+// face already was a triangle.
+pub fn triangulate_mesh(m: &CgarMesh<CgarF64, 3>) -> Option<(CgarMesh<CgarF64, 3>, usize)> {
+    let faces: Vec<Vec<usize>> = m
+        .faces
+        .iter()
+        .enumerate()
+        .filter(|(_, face)| !face.removed)
+        .map(|(fi, _)| face_vertices(m, fi))
+        .collect();
+    let polygons = faces.iter().filter(|face| face.len() > 3).count();
+    if polygons == 0 {
+        return None;
+    }
+
+    let positions: Vec<DVec3> = m
+        .vertices
+        .iter()
+        .map(|v| DVec3::new(v.position[0].0, v.position[1].0, v.position[2].0))
+        .collect();
+    let triangles = faces.iter().flat_map(|face| {
+        let points: Vec<DVec3> = face.iter().map(|&v| positions[v]).collect();
+        triangulate_polygon(&points)
+            .into_iter()
+            .map(|tri| tri.map(|k| face[k]))
+            .collect::<Vec<_>>()
+    });
+    Some((build_cgar_mesh(&positions, triangles), polygons))
+}
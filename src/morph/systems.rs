@@ -0,0 +1,345 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    asset::{Assets, Handle},
+    ecs::{
+        change_detection::DetectChanges,
+        entity::Entity,
+        event::EventWriter,
+        name::Name,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::DVec3,
+    pbr::{MeshMaterial3d, StandardMaterial},
+    render::{
+        mesh::{Mesh, Mesh3d},
+        view::Visibility,
+    },
+    transform::components::Transform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::colormap::heat;
+use crate::analysis::overlay::overlay_color;
+use crate::budget::decimate::soup_to_bevy_mesh;
+use crate::camera::components::CgarMeshData;
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::repair::ops::TriangleSoup;
+use crate::snapshot::systems::SnapshotCompare;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MorphSource {
+    Mesh(Entity),
+    // The pinned snapshot, as the mesh was before later edits
+    Snapshot,
+}
+
+// A morph in progress: both vertex sets in the shared numbering, drawn by a
+// stand-in mesh while the compared meshes are hidden
+pub struct Morph {
+    pub from: Vec<DVec3>,
+    pub to: Vec<DVec3>,
+    pub triangles: Vec<[usize; 3]>,
+    pub max_displacement: f64,
+    pub preview: Entity,
+    preview_mesh: Handle<Mesh>,
+    hidden: Vec<Entity>,
+}
+
+// Blends the vertex positions of two meshes with the same connectivity, e.g.
+// before and after smoothing, to show how far each vertex travelled
+#[derive(Resource)]
+pub struct MorphView {
+    pub from: Option<MorphSource>,
+    pub to: Option<Entity>,
+    // 0 draws `from`, 1 draws `to`
+    pub t: f32,
+    // Tint vertices by how far they have moved so far, relative to the
+    // largest displacement
+    pub color_displacement: bool,
+    pub running: Option<Morph>,
+}
+
+impl Default for MorphView {
+    fn default() -> Self {
+        Self {
+            from: None,
+            to: None,
+            t: 0.5,
+            color_displacement: true,
+            running: None,
+        }
+    }
+}
+
+impl MorphView {
+    fn stop(&mut self, commands: &mut Commands, visibilities: &mut Query<&mut Visibility>) {
+        let Some(morph) = self.running.take() else {
+            return;
+        };
+        for entity in morph.hidden {
+            if let Ok(mut visibility) = visibilities.get_mut(entity) {
+                *visibility = Visibility::Inherited;
+            }
+        }
+        commands.entity(morph.preview).despawn();
+    }
+}
+
+// Live faces over used vertices only, so meshes that differ just in dead
+// slots still line up
+fn compact_soup(topology: &MeshTopology) -> TriangleSoup {
+    TriangleSoup::from_topology(topology).compacted().0
+}
+
+// Interpolated mesh at `t`, with displacement colors when asked for
+fn morph_mesh(morph: &Morph, t: f32, color_displacement: bool) -> Mesh {
+    let soup = TriangleSoup {
+        positions: morph
+            .from
+            .iter()
+            .zip(&morph.to)
+            .map(|(a, b)| a.lerp(*b, t as f64))
+            .collect(),
+        triangles: morph.triangles.clone(),
+    };
+    let mut mesh = soup_to_bevy_mesh(&soup);
+    if color_displacement {
+        let colors: Vec<[f32; 4]> = morph
+            .from
+            .iter()
+            .zip(&soup.positions)
+            .map(|(a, p)| {
+                let moved = if morph.max_displacement > 0.0 {
+                    a.distance(*p) / morph.max_displacement
+                } else {
+                    0.0
+                };
+                overlay_color(heat(moved as f32))
+            })
+            .collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    }
+    mesh
+}
+
+// Redraws the stand-in whenever the blend or coloring changes, and ends the
+// morph if a compared mesh went away
+pub fn update_morph(
+    mut commands: Commands,
+    mut view: ResMut<MorphView>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    let Some(morph) = &view.running else {
+        return;
+    };
+    if morph.hidden.iter().any(|&e| !visibilities.contains(e)) {
+        view.stop(&mut commands, &mut visibilities);
+        return;
+    }
+    if view.is_changed() {
+        meshes.insert(
+            &morph.preview_mesh,
+            morph_mesh(morph, view.t, view.color_displacement),
+        );
+    }
+}
+
+pub fn morph_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut view: ResMut<MorphView>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut notices: EventWriter<Notify>,
+    compare: Res<SnapshotCompare>,
+    mesh_query: Query<(
+        Entity,
+        &CgarMeshData,
+        &Transform,
+        Option<&Name>,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+    )>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let label = |entity: Entity| {
+        let name = mesh_query
+            .get(entity)
+            .ok()
+            .and_then(|(_, _, _, name, _)| name)
+            .map_or_else(|| "unnamed".to_string(), |name| name.to_string());
+        format!("Mesh: {} ({})", name, entity)
+    };
+
+    egui::Window::new("Morph")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let running = view.running.is_some();
+            ui.add_enabled_ui(!running, |ui| {
+                let mut from = view.from;
+                let from_text = match from {
+                    Some(MorphSource::Mesh(entity)) => label(entity),
+                    Some(MorphSource::Snapshot) => "Pinned snapshot".to_string(),
+                    None => "None".to_string(),
+                };
+                egui::ComboBox::from_label("From")
+                    .selected_text(from_text)
+                    .show_ui(ui, |ui| {
+                        for (entity, ..) in &mesh_query {
+                            ui.selectable_value(
+                                &mut from,
+                                Some(MorphSource::Mesh(entity)),
+                                label(entity),
+                            );
+                        }
+                        if compare.pinned.is_some() {
+                            ui.selectable_value(
+                                &mut from,
+                                Some(MorphSource::Snapshot),
+                                "Pinned snapshot",
+                            );
+                        }
+                    });
+                if from != view.from {
+                    view.from = from;
+                }
+
+                let mut to = view.to.filter(|e| mesh_query.contains(*e));
+                egui::ComboBox::from_label("To")
+                    .selected_text(to.map_or_else(|| "None".to_string(), label))
+                    .show_ui(ui, |ui| {
+                        for (entity, ..) in &mesh_query {
+                            ui.selectable_value(&mut to, Some(entity), label(entity));
+                        }
+                    });
+                if to != view.to {
+                    view.to = to;
+                }
+            });
+
+            let mut t = view.t;
+            ui.add(egui::Slider::new(&mut t, 0.0..=1.0).text("Blend"));
+            if t != view.t {
+                view.t = t;
+            }
+            let mut color_displacement = view.color_displacement;
+            ui.checkbox(&mut color_displacement, "Color by displacement");
+            if color_displacement != view.color_displacement {
+                view.color_displacement = color_displacement;
+            }
+
+            if let Some(morph) = &view.running {
+                ui.label(format!(
+                    "{} vertices, max displacement {:.6}",
+                    morph.from.len(),
+                    morph.max_displacement
+                ));
+                if ui.button("Stop").clicked() {
+                    view.stop(&mut commands, &mut visibilities);
+                }
+                return;
+            }
+
+            let to = view.to.filter(|e| mesh_query.contains(*e));
+            let can_start = match (view.from, to) {
+                (Some(MorphSource::Mesh(from)), Some(to)) => {
+                    from != to && mesh_query.contains(from)
+                }
+                (Some(MorphSource::Snapshot), Some(_)) => compare.pinned.is_some(),
+                _ => false,
+            };
+            let start = ui
+                .add_enabled(can_start, egui::Button::new("Start"))
+                .clicked();
+            let Some((to, to_data, transform, _, material)) =
+                to.filter(|_| start).and_then(|e| mesh_query.get(e).ok())
+            else {
+                return;
+            };
+            let (from, mut hidden) = match view.from {
+                Some(MorphSource::Mesh(from)) => match mesh_query.get(from) {
+                    Ok((_, from_data, ..)) => (
+                        compact_soup(&MeshTopology::from_cgar(&from_data.0)),
+                        vec![from],
+                    ),
+                    Err(_) => return,
+                },
+                Some(MorphSource::Snapshot) => match &compare.pinned {
+                    Some(snapshot) => (compact_soup(&snapshot.topology), Vec::new()),
+                    None => return,
+                },
+                None => return,
+            };
+            let target = compact_soup(&MeshTopology::from_cgar(&to_data.0));
+            if from.positions.len() != target.positions.len() || from.triangles != target.triangles
+            {
+                notices.write(Notify::warning(format!(
+                    "The meshes don't share connectivity ({} vertices, {} faces against {} \
+                     vertices, {} faces)",
+                    from.positions.len(),
+                    from.triangles.len(),
+                    target.positions.len(),
+                    target.triangles.len()
+                )));
+                return;
+            }
+            hidden.push(to);
+
+            let max_displacement = from
+                .positions
+                .iter()
+                .zip(&target.positions)
+                .map(|(a, b)| a.distance(*b))
+                .fold(0.0, f64::max);
+            // Drawn in `to`'s place and filled in by `update_morph`
+            let preview_mesh = meshes.reserve_handle();
+            let mut preview = commands.spawn((
+                Name::new("Morph preview"),
+                Mesh3d(preview_mesh.clone()),
+                *transform,
+            ));
+            if let Some(material) = material {
+                preview.insert(material.clone());
+            }
+            let preview = preview.id();
+            for &entity in &hidden {
+                if let Ok(mut visibility) = visibilities.get_mut(entity) {
+                    *visibility = Visibility::Hidden;
+                }
+            }
+            view.running = Some(Morph {
+                from: from.positions,
+                to: target.positions,
+                triangles: from.triangles,
+                max_displacement,
+                preview,
+                preview_mesh,
+                hidden,
+            });
+        });
+}
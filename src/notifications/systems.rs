@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::VecDeque;
+
+use bevy::{
+    ecs::{
+        event::{Event, EventReader},
+        resource::Resource,
+        system::{Res, ResMut},
+    },
+    log::{error, info, warn},
+    time::Time,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+// Oldest entries are dropped past this many
+const MAX_LOG_ENTRIES: usize = 500;
+// Toasts stacked at once; older ones stay in the log panel
+const MAX_TOASTS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NoticeLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl NoticeLevel {
+    pub const ALL: [NoticeLevel; 3] = [NoticeLevel::Info, NoticeLevel::Warning, NoticeLevel::Error];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NoticeLevel::Info => "Info",
+            NoticeLevel::Warning => "Warning",
+            NoticeLevel::Error => "Error",
+        }
+    }
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            NoticeLevel::Info => egui::Color32::LIGHT_GRAY,
+            NoticeLevel::Warning => egui::Color32::from_rgb(240, 190, 60),
+            NoticeLevel::Error => egui::Color32::from_rgb(235, 90, 80),
+        }
+    }
+}
+
+// Something the user should hear about: failed imports, invalid meshes,
+// operations that were refused. Also mirrored to the console log.
+#[derive(Event, Debug, Clone)]
+pub struct Notify {
+    pub level: NoticeLevel,
+    pub message: String,
+}
+
+impl Notify {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            level: NoticeLevel::Info,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            level: NoticeLevel::Warning,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            level: NoticeLevel::Error,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notice {
+    pub level: NoticeLevel,
+    pub message: String,
+    // Seconds since startup
+    pub time: f64,
+    pub dismissed: bool,
+}
+
+#[derive(Resource, Debug)]
+pub struct NotificationLog {
+    pub entries: VecDeque<Notice>,
+    pub toast_secs: f64,
+    pub min_level: NoticeLevel,
+}
+
+impl Default for NotificationLog {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            toast_secs: 6.0,
+            min_level: NoticeLevel::Info,
+        }
+    }
+}
+
+pub fn collect_notifications(
+    mut events: EventReader<Notify>,
+    time: Res<Time>,
+    mut log: ResMut<NotificationLog>,
+) {
+    for event in events.read() {
+        match event.level {
+            NoticeLevel::Info => info!("{}", event.message),
+            NoticeLevel::Warning => warn!("{}", event.message),
+            NoticeLevel::Error => error!("{}", event.message),
+        }
+        log.entries.push_back(Notice {
+            level: event.level,
+            message: event.message.clone(),
+            time: time.elapsed_secs_f64(),
+            dismissed: false,
+        });
+        while log.entries.len() > MAX_LOG_ENTRIES {
+            log.entries.pop_front();
+        }
+    }
+}
+
+// Recent notices stacked in the bottom-right corner; click one to dismiss it
+pub fn notification_toasts(
+    mut contexts: EguiContexts,
+    time: Res<Time>,
+    mut log: ResMut<NotificationLog>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let now = time.elapsed_secs_f64();
+    let toast_secs = log.toast_secs;
+    let visible: Vec<usize> = log
+        .entries
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, notice)| !notice.dismissed && now - notice.time < toast_secs)
+        .map(|(i, _)| i)
+        .take(MAX_TOASTS)
+        .collect();
+    if visible.is_empty() {
+        return;
+    }
+
+    let mut dismissed = Vec::new();
+    egui::Area::new(egui::Id::new("notification_toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+        .order(egui::Order::Foreground)
+        .interactable(true)
+        .show(ctx, |ui| {
+            ui.set_max_width(360.0);
+            for &i in visible.iter().rev() {
+                let notice = &log.entries[i];
+                let response = egui::Frame::popup(ui.style())
+                    .show(ui, |ui| {
+                        ui.colored_label(notice.level.color(), notice.level.label());
+                        ui.label(&notice.message);
+                    })
+                    .response
+                    .interact(egui::Sense::click());
+                if response.clicked() {
+                    dismissed.push(i);
+                }
+            }
+        });
+    for i in dismissed {
+        log.entries[i].dismissed = true;
+    }
+}
+
+pub fn notification_log_panel(mut contexts: EguiContexts, mut log: ResMut<NotificationLog>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Log").show(ctx, |ui| {
+        let mut min_level = log.min_level;
+        let mut clear = false;
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Show")
+                .selected_text(min_level.label())
+                .show_ui(ui, |ui| {
+                    for level in NoticeLevel::ALL {
+                        ui.selectable_value(&mut min_level, level, level.label());
+                    }
+                });
+            clear = ui.button("Clear").clicked();
+        });
+        if min_level != log.min_level {
+            log.min_level = min_level;
+        }
+        if clear {
+            log.entries.clear();
+        }
+
+        egui::ScrollArea::vertical()
+            .max_height(240.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                let shown = log
+                    .entries
+                    .iter()
+                    .filter(|notice| notice.level >= min_level);
+                for notice in shown {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.weak(format!("{:>7.1}s", notice.time));
+                        ui.colored_label(notice.level.color(), notice.level.label());
+                        ui.label(&notice.message);
+                    });
+                }
+            });
+    });
+}
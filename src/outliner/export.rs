@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        event::EventWriter,
+        hierarchy::ChildOf,
+        name::Name,
+        query::{With, Without},
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    render::mesh::Mesh,
+    transform::components::{GlobalTransform, Transform},
+};
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::CgarMeshData;
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::gltf::{GltfMesh, GltfNode, write_gltf};
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::notifications::systems::Notify;
+use crate::outliner::systems::MeshGroup;
+use crate::workspace::systems::Stashed;
+
+// Writes the groups and meshes of the scene to glTF, for handing assembled
+// scenes to artists or web viewers
+#[derive(Resource)]
+pub struct SceneExport {
+    pub path: String,
+    pub requested: Option<PathBuf>,
+}
+
+impl Default for SceneExport {
+    fn default() -> Self {
+        Self {
+            path: "scene.glb".to_string(),
+            requested: None,
+        }
+    }
+}
+
+// Groups become parent nodes of their meshes; meshes are written at full
+// resolution with the colors of their active overlay, whatever the display
+// budget currently draws
+pub fn run_scene_export(
+    mut export: ResMut<SceneExport>,
+    mut notices: EventWriter<Notify>,
+    mesh_query: Query<
+        (
+            Entity,
+            Option<&Name>,
+            &Transform,
+            &GlobalTransform,
+            Option<&ChildOf>,
+            &CgarMeshData,
+            Option<&FaceColorOverlay>,
+            Option<&FeatureEdges>,
+            Option<&ImportedNormals>,
+            Option<&NormalSettings>,
+        ),
+        (Without<MeshGroup>, Without<Stashed>),
+    >,
+    group_query: Query<
+        (Entity, Option<&Name>, &Transform),
+        (With<MeshGroup>, Without<CgarMeshData>, Without<Stashed>),
+    >,
+) {
+    let Some(path) = export.requested.take() else {
+        return;
+    };
+    let label = |entity: Entity, name: Option<&Name>| {
+        name.map_or_else(|| entity.to_string(), |name| name.to_string())
+    };
+
+    let mut nodes: Vec<GltfNode> = Vec::new();
+    let mut roots = Vec::new();
+    let mut group_nodes: HashMap<Entity, usize> = HashMap::new();
+    let mut groups: Vec<_> = group_query.iter().collect();
+    groups.sort_by_key(|(entity, name, _)| (label(*entity, *name), *entity));
+    for (entity, name, transform) in groups {
+        group_nodes.insert(entity, nodes.len());
+        roots.push(nodes.len());
+        nodes.push(GltfNode {
+            name: label(entity, name),
+            transform: *transform,
+            mesh: None,
+            children: Vec::new(),
+        });
+    }
+
+    let mut meshes: Vec<_> = mesh_query.iter().collect();
+    meshes.sort_by_key(|(entity, name, ..)| (label(*entity, *name), *entity));
+    let mut exported = 0;
+    for (
+        entity,
+        name,
+        transform,
+        global,
+        parent,
+        cgar_data,
+        overlay,
+        features,
+        normals,
+        settings,
+    ) in meshes
+    {
+        let mut mesh = render_mesh(&cgar_data.0, overlay, features, normals, settings);
+        // Unshared meshes carry white colors even without an overlay
+        if overlay.is_none() {
+            mesh.remove_attribute(Mesh::ATTRIBUTE_COLOR);
+        }
+        let mesh = GltfMesh::from_mesh(&mesh);
+        exported += mesh.is_some() as usize;
+        let index = nodes.len();
+        let group = parent.and_then(|parent| group_nodes.get(&parent.parent()).copied());
+        match group {
+            Some(group) => nodes[group].children.push(index),
+            None => roots.push(index),
+        }
+        nodes.push(GltfNode {
+            name: label(entity, name),
+            // Meshes outside groups may hang off other entities; flatten them
+            transform: match group {
+                Some(_) => *transform,
+                None => global.compute_transform(),
+            },
+            mesh,
+            children: Vec::new(),
+        });
+    }
+
+    if exported == 0 {
+        notices.write(Notify::warning("No meshes to export"));
+        return;
+    }
+    notices.write(match write_gltf(&path, &nodes, &roots) {
+        Ok(()) => Notify::info(format!(
+            "Exported {} meshes to {}",
+            exported,
+            path.display()
+        )),
+        Err(err) => Notify::error(format!("Failed to export {}: {}", path.display(), err)),
+    });
+}
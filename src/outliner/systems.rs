@@ -0,0 +1,397 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        hierarchy::ChildOf,
+        name::Name,
+        query::{With, Without},
+        resource::Resource,
+        system::{Commands, Query, ResMut},
+    },
+    math::{EulerRot, Quat, Vec3},
+    render::view::Visibility,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::budget::memory::{MeshMemory, format_bytes};
+use crate::camera::components::CgarMeshData;
+#[cfg(feature = "native")]
+use crate::outliner::export::SceneExport;
+use crate::selection::components::SelectionSet;
+use crate::workspace::systems::Stashed;
+
+// Empty node that meshes are parented to, so an assembly can be moved and
+// hidden as one
+#[derive(Component, Debug, Default)]
+pub struct MeshGroup;
+
+#[derive(Resource, Default)]
+pub struct Outliner {
+    // Meshes ticked for the next "Group" action
+    pub checked: BTreeSet<Entity>,
+    pub new_group_name: String,
+}
+
+enum OutlinerAction {
+    Group(Vec<Entity>, String),
+    MoveTo(Entity, Option<Entity>),
+    Ungroup(Entity),
+    SetVisible(Entity, bool),
+    SetTransform(Entity, Transform),
+    Rename(Entity, String),
+    Select(Entity),
+}
+
+// Places `entity` under `parent` (or back at the top level) without moving it
+// in the world
+fn reparent(
+    commands: &mut Commands,
+    entity: Entity,
+    global: &GlobalTransform,
+    parent: Option<(Entity, &GlobalTransform)>,
+) {
+    match parent {
+        Some((parent, parent_global)) => {
+            commands
+                .entity(entity)
+                .insert((ChildOf(parent), global.reparented_to(parent_global)));
+        }
+        None => {
+            commands
+                .entity(entity)
+                .remove::<ChildOf>()
+                .insert(global.compute_transform());
+        }
+    }
+}
+
+fn visibility_toggle(ui: &mut egui::Ui, visibility: &Visibility) -> Option<bool> {
+    let mut visible = *visibility != Visibility::Hidden;
+    ui.checkbox(&mut visible, "")
+        .on_hover_text("Visible")
+        .changed()
+        .then_some(visible)
+}
+
+fn transform_editor(ui: &mut egui::Ui, transform: &Transform) -> Option<Transform> {
+    let mut translation = transform.translation;
+    let (x, y, z) = transform.rotation.to_euler(EulerRot::XYZ);
+    let mut rotation = Vec3::new(x, y, z) * (180.0 / std::f32::consts::PI);
+    let mut scale = transform.scale;
+    ui.horizontal(|ui| {
+        ui.label("Position");
+        for axis in 0..3 {
+            ui.add(egui::DragValue::new(&mut translation[axis]).speed(0.01));
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Rotation");
+        for axis in 0..3 {
+            ui.add(
+                egui::DragValue::new(&mut rotation[axis])
+                    .speed(0.5)
+                    .suffix("°"),
+            );
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Scale");
+        for axis in 0..3 {
+            ui.add(
+                egui::DragValue::new(&mut scale[axis])
+                    .speed(0.01)
+                    .range(1e-4..=1e4),
+            );
+        }
+    });
+    let rotation = rotation * (std::f32::consts::PI / 180.0);
+    let edited = Transform {
+        translation,
+        rotation: Quat::from_euler(EulerRot::XYZ, rotation.x, rotation.y, rotation.z),
+        scale,
+    };
+    let unchanged = translation == transform.translation
+        && rotation.abs_diff_eq(Vec3::new(x, y, z), 1e-5)
+        && scale == transform.scale;
+    (!unchanged).then_some(edited)
+}
+
+fn label_of(entity: Entity, name: Option<&Name>) -> String {
+    name.map_or_else(|| entity.to_string(), |name| name.to_string())
+}
+
+// Lists groups and meshes. Meshes can be ticked and grouped, moved between
+// groups, shown or hidden and selected; groups carry their own transform and
+// visibility, which their meshes inherit.
+pub fn outliner_panel(
+    mut contexts: EguiContexts,
+    mut commands: Commands,
+    mut outliner: ResMut<Outliner>,
+    mut selection: ResMut<SelectionSet>,
+    mut mesh_query: Query<
+        (
+            Entity,
+            Option<&Name>,
+            &mut Visibility,
+            &GlobalTransform,
+            Option<&ChildOf>,
+        ),
+        (With<CgarMeshData>, Without<MeshGroup>, Without<Stashed>),
+    >,
+    mut group_query: Query<
+        (
+            Entity,
+            &mut Name,
+            &mut Transform,
+            &mut Visibility,
+            &GlobalTransform,
+        ),
+        (With<MeshGroup>, Without<CgarMeshData>, Without<Stashed>),
+    >,
+    memory_query: Query<&MeshMemory>,
+    #[cfg(feature = "native")] mut export: ResMut<SceneExport>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut groups: Vec<(Entity, String)> = group_query
+        .iter()
+        .map(|(entity, name, ..)| (entity, name.to_string()))
+        .collect();
+    groups.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+    let mut meshes: Vec<(Entity, String, Option<Entity>)> = mesh_query
+        .iter()
+        .map(|(entity, name, _, _, parent)| {
+            let group = parent
+                .map(|parent| parent.parent())
+                .filter(|parent| group_query.contains(*parent));
+            (entity, label_of(entity, name), group)
+        })
+        .collect();
+    meshes.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+    outliner
+        .checked
+        .retain(|entity| meshes.iter().any(|(mesh, ..)| mesh == entity));
+
+    let outliner = &mut *outliner;
+    let mut actions: Vec<OutlinerAction> = Vec::new();
+    egui::Window::new("Outliner").show(ctx, |ui| {
+        let mesh_row =
+            |ui: &mut egui::Ui,
+             outliner: &mut Outliner,
+             actions: &mut Vec<OutlinerAction>,
+             (entity, label, group): &(Entity, String, Option<Entity>)| {
+                let Ok((_, _, visibility, ..)) = mesh_query.get(*entity) else {
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    let mut checked = outliner.checked.contains(entity);
+                    if ui.checkbox(&mut checked, "").changed() {
+                        if checked {
+                            outliner.checked.insert(*entity);
+                        } else {
+                            outliner.checked.remove(entity);
+                        }
+                    }
+                    if let Some(visible) = visibility_toggle(ui, visibility) {
+                        actions.push(OutlinerAction::SetVisible(*entity, visible));
+                    }
+                    if ui
+                        .selectable_label(selection.mesh == Some(*entity), label.as_str())
+                        .clicked()
+                    {
+                        actions.push(OutlinerAction::Select(*entity));
+                    }
+                    let current = groups
+                        .iter()
+                        .find(|(g, _)| Some(*g) == *group)
+                        .map_or("(no group)", |(_, name)| name.as_str());
+                    let mut target = *group;
+                    egui::ComboBox::from_id_salt(("outliner_group", *entity))
+                        .selected_text(current)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut target, None, "(no group)");
+                            for (g, name) in &groups {
+                                ui.selectable_value(&mut target, Some(*g), name.as_str());
+                            }
+                        });
+                    if target != *group {
+                        actions.push(OutlinerAction::MoveTo(*entity, target));
+                    }
+                    if let Ok(memory) = memory_query.get(*entity) {
+                        ui.weak(format_bytes(memory.total())).on_hover_text(format!(
+                            "cgar {}, GPU {}, face tree {}",
+                            format_bytes(memory.cgar),
+                            format_bytes(memory.gpu),
+                            format_bytes(memory.face_tree)
+                        ));
+                    }
+                });
+            };
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut outliner.new_group_name);
+            let can_group = !outliner.checked.is_empty();
+            if ui
+                .add_enabled(can_group, egui::Button::new("Group checked"))
+                .clicked()
+            {
+                let name = match outliner.new_group_name.trim() {
+                    "" => format!("Group {}", groups.len() + 1),
+                    name => name.to_string(),
+                };
+                let members = std::mem::take(&mut outliner.checked).into_iter().collect();
+                actions.push(OutlinerAction::Group(members, name));
+                outliner.new_group_name.clear();
+            }
+        });
+        ui.separator();
+
+        for (group, name) in &groups {
+            let Ok((_, _, transform, visibility, _)) = group_query.get(*group) else {
+                continue;
+            };
+            let id = ui.make_persistent_id(("outliner_group_node", *group));
+            egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, true)
+                .show_header(ui, |ui| {
+                    if let Some(visible) = visibility_toggle(ui, visibility) {
+                        actions.push(OutlinerAction::SetVisible(*group, visible));
+                    }
+                    let mut edited = name.clone();
+                    ui.add(egui::TextEdit::singleline(&mut edited).desired_width(120.0));
+                    if edited != *name && !edited.trim().is_empty() {
+                        actions.push(OutlinerAction::Rename(*group, edited));
+                    }
+                    if ui.button("Ungroup").clicked() {
+                        actions.push(OutlinerAction::Ungroup(*group));
+                    }
+                })
+                .body(|ui| {
+                    if let Some(edited) = transform_editor(ui, transform) {
+                        actions.push(OutlinerAction::SetTransform(*group, edited));
+                    }
+                    for mesh in meshes.iter().filter(|(.., g)| *g == Some(*group)) {
+                        mesh_row(ui, outliner, &mut actions, mesh);
+                    }
+                });
+        }
+        for mesh in meshes.iter().filter(|(.., g)| g.is_none()) {
+            mesh_row(ui, outliner, &mut actions, mesh);
+        }
+
+        #[cfg(feature = "native")]
+        {
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut export.path);
+                let path = export.path.trim().to_string();
+                if ui
+                    .add_enabled(!path.is_empty(), egui::Button::new("Export scene"))
+                    .on_hover_text("Write every group and mesh to .glb or .gltf")
+                    .clicked()
+                {
+                    export.requested = Some(path.into());
+                }
+            });
+        }
+    });
+
+    for action in actions {
+        match action {
+            OutlinerAction::Group(members, name) => {
+                let group = commands
+                    .spawn((
+                        Name::new(name),
+                        MeshGroup,
+                        Transform::default(),
+                        Visibility::default(),
+                    ))
+                    .id();
+                for entity in members {
+                    if let Ok((_, _, _, global, _)) = mesh_query.get(entity) {
+                        reparent(
+                            &mut commands,
+                            entity,
+                            global,
+                            Some((group, &GlobalTransform::IDENTITY)),
+                        );
+                    }
+                }
+            }
+            OutlinerAction::MoveTo(entity, target) => {
+                let Ok((_, _, _, global, _)) = mesh_query.get(entity) else {
+                    continue;
+                };
+                let parent = target.and_then(|group| {
+                    group_query
+                        .get(group)
+                        .ok()
+                        .map(|(group, _, _, _, group_global)| (group, group_global))
+                });
+                reparent(&mut commands, entity, global, parent);
+            }
+            OutlinerAction::Ungroup(group) => {
+                for (entity, _, _, global, parent) in &mesh_query {
+                    if parent.is_some_and(|parent| parent.parent() == group) {
+                        reparent(&mut commands, entity, global, None);
+                    }
+                }
+                commands.entity(group).despawn();
+            }
+            OutlinerAction::SetVisible(entity, visible) => {
+                let wanted = if visible {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+                if let Ok((_, _, mut visibility, ..)) = mesh_query.get_mut(entity) {
+                    *visibility = wanted;
+                } else if let Ok((_, _, _, mut visibility, _)) = group_query.get_mut(entity) {
+                    *visibility = wanted;
+                }
+            }
+            OutlinerAction::SetTransform(group, edited) => {
+                if let Ok((_, _, mut transform, ..)) = group_query.get_mut(group) {
+                    *transform = edited;
+                }
+            }
+            OutlinerAction::Rename(group, name) => {
+                if let Ok((_, mut group_name, ..)) = group_query.get_mut(group) {
+                    group_name.set(name);
+                }
+            }
+            OutlinerAction::Select(entity) => {
+                if selection.mesh != Some(entity) {
+                    selection.clear();
+                    selection.mesh = Some(entity);
+                }
+            }
+        }
+    }
+}
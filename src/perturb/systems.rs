@@ -0,0 +1,372 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        entity::Entity,
+        event::EventWriter,
+        name::Name,
+        resource::Resource,
+        system::{Commands, Query, ResMut},
+    },
+    math::DVec3,
+    render::mesh::{Mesh, Mesh3d},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::CgarMeshData;
+use crate::edit::ops::flip_edge;
+use crate::mesh::conversion::{build_cgar_mesh, set_vertex_position};
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+use crate::utils::random::SplitMix64;
+
+// Random picks tried per requested operation before giving up
+const ATTEMPTS_PER_OP: usize = 20;
+
+// Developer tool that jitters vertices and applies random collapses and
+// flips to the selected mesh, to shake out robustness problems in cgar.
+// Every run is reproducible from its seed.
+#[derive(Resource)]
+pub struct Perturbation {
+    pub seed: u64,
+    // Noise amplitude as a fraction of the mean edge length
+    pub noise: f32,
+    pub collapses: usize,
+    pub flips: usize,
+    pub requested: bool,
+    pub last: Option<PerturbReport>,
+}
+
+impl Default for Perturbation {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            noise: 0.05,
+            collapses: 10,
+            flips: 10,
+            requested: false,
+            last: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PerturbReport {
+    pub mesh: String,
+    pub seed: u64,
+    pub jittered: usize,
+    pub collapsed: usize,
+    pub collapse_rejected: usize,
+    pub flipped: usize,
+    pub flip_rejected: usize,
+    // Operation that panicked; the mesh is restored to just before it
+    pub panic: Option<String>,
+    // Problems found in the result
+    pub issues: Vec<String>,
+}
+
+impl PerturbReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} (seed {}): {} vertices jittered, {} collapsed ({} rejected), {} flipped ({} rejected)",
+            self.mesh,
+            self.seed,
+            self.jittered,
+            self.collapsed,
+            self.collapse_rejected,
+            self.flipped,
+            self.flip_rejected
+        )
+    }
+}
+
+pub fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+fn random_edge(topology: &MeshTopology, rng: &mut SplitMix64) -> Option<(usize, usize)> {
+    let count = topology.edge_faces.len();
+    if count == 0 {
+        return None;
+    }
+    let index = ((rng.next_f64() * count as f64) as usize).min(count - 1);
+    topology.edge_faces.keys().nth(index).copied()
+}
+
+pub fn restore(topology: &MeshTopology) -> CgarMesh<CgarF64, 3> {
+    let soup = TriangleSoup::from_topology(topology);
+    build_cgar_mesh(&soup.positions, soup.triangles.iter().copied())
+}
+
+// Problems a sequence of valid operations should never produce
+pub fn find_issues(topology: &MeshTopology) -> Vec<String> {
+    let mut issues = Vec::new();
+    let non_finite = topology
+        .used_vertices()
+        .filter(|&v| !topology.positions[v].is_finite())
+        .count();
+    if non_finite > 0 {
+        issues.push(format!("{} non-finite vertices", non_finite));
+    }
+    let non_manifold = topology
+        .edge_faces
+        .values()
+        .filter(|faces| faces.len() > 2)
+        .count();
+    if non_manifold > 0 {
+        issues.push(format!("{} non-manifold edges", non_manifold));
+    }
+    let degenerate = topology
+        .live_faces()
+        .filter(|&(fi, [a, b, c])| a == b || b == c || c == a || topology.face_area(fi) <= 0.0)
+        .count();
+    if degenerate > 0 {
+        issues.push(format!("{} degenerate faces", degenerate));
+    }
+    issues
+}
+
+fn perturb(
+    cgar_mesh: &mut CgarMesh<CgarF64, 3>,
+    settings: &Perturbation,
+    features: Option<&FeatureEdges>,
+    report: &mut PerturbReport,
+) {
+    let mut rng = SplitMix64::new(settings.seed);
+
+    let topology = MeshTopology::from_cgar(cgar_mesh);
+    let mean_edge = topology
+        .edge_faces
+        .keys()
+        .map(|&edge| topology.edge_length(edge))
+        .sum::<f64>()
+        / topology.edge_faces.len().max(1) as f64;
+    let amplitude = settings.noise as f64 * mean_edge;
+    if amplitude > 0.0 {
+        for v in topology.used_vertices() {
+            let offset =
+                DVec3::new(rng.next_f64(), rng.next_f64(), rng.next_f64()) * 2.0 - DVec3::ONE;
+            set_vertex_position(cgar_mesh, v, topology.positions[v] + offset * amplitude);
+            report.jittered += 1;
+        }
+    }
+
+    for _ in 0..settings.collapses * ATTEMPTS_PER_OP {
+        if report.collapsed == settings.collapses {
+            break;
+        }
+        let topology = MeshTopology::from_cgar(cgar_mesh);
+        let Some((v0, v1)) = random_edge(&topology, &mut rng) else {
+            break;
+        };
+        if features.is_some_and(|features| !features.allows_collapse(v0, v1)) {
+            continue;
+        }
+        match catch_unwind(AssertUnwindSafe(|| cgar_mesh.collapse_edge(v0, v1))) {
+            Ok(Ok(_)) => report.collapsed += 1,
+            Ok(Err(_)) => report.collapse_rejected += 1,
+            Err(payload) => {
+                report.panic = Some(format!(
+                    "collapse_edge({}, {}) panicked: {}",
+                    v0,
+                    v1,
+                    panic_message(payload)
+                ));
+                *cgar_mesh = restore(&topology);
+                return;
+            }
+        }
+    }
+
+    for _ in 0..settings.flips * ATTEMPTS_PER_OP {
+        if report.flipped == settings.flips {
+            break;
+        }
+        let topology = MeshTopology::from_cgar(cgar_mesh);
+        let Some(edge) = random_edge(&topology, &mut rng) else {
+            break;
+        };
+        let Ok(soup) = flip_edge(&topology, edge) else {
+            report.flip_rejected += 1;
+            continue;
+        };
+        match catch_unwind(AssertUnwindSafe(|| {
+            build_cgar_mesh(&soup.positions, soup.triangles.iter().copied())
+        })) {
+            Ok(flipped) => {
+                *cgar_mesh = flipped;
+                report.flipped += 1;
+            }
+            Err(payload) => {
+                report.panic = Some(format!(
+                    "rebuilding after flipping ({}, {}) panicked: {}",
+                    edge.0,
+                    edge.1,
+                    panic_message(payload)
+                ));
+                return;
+            }
+        }
+    }
+}
+
+pub fn run_perturbation(
+    mut commands: Commands,
+    mut perturbation: ResMut<Perturbation>,
+    mut selection: ResMut<SelectionSet>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut notices: EventWriter<Notify>,
+    mut mesh_query: Query<(
+        Entity,
+        &Mesh3d,
+        &mut CgarMeshData,
+        Option<&Name>,
+        Option<&FeatureEdges>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    if !perturbation.requested {
+        return;
+    }
+    perturbation.requested = false;
+    let Some(entity) = selection
+        .mesh
+        .filter(|e| mesh_query.contains(*e))
+        .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity))
+    else {
+        notices.write(Notify::warning("Perturb: no mesh loaded"));
+        return;
+    };
+    let Ok((_, mesh_handle, mut cgar_data, name, features, settings)) = mesh_query.get_mut(entity)
+    else {
+        return;
+    };
+
+    let mut report = PerturbReport {
+        mesh: name.map_or_else(|| entity.to_string(), |name| name.to_string()),
+        seed: perturbation.seed,
+        ..Default::default()
+    };
+    perturb(&mut cgar_data.0, &perturbation, features, &mut report);
+    report.issues = find_issues(&MeshTopology::from_cgar(&cgar_data.0));
+
+    // Moved vertices make authored normals meaningless, and face ids change
+    meshes.insert(
+        &mesh_handle.0,
+        render_mesh(&cgar_data.0, None, features, None, settings),
+    );
+    commands
+        .entity(entity)
+        .remove::<(FaceColorOverlay, ImportedNormals)>();
+    if selection.mesh == Some(entity) {
+        selection.clear();
+    }
+
+    notices.write(Notify::info(format!("Perturb: {}", report.summary())));
+    if let Some(panic) = &report.panic {
+        notices.write(Notify::error(format!(
+            "Perturb (seed {}): {}",
+            report.seed, panic
+        )));
+    }
+    if !report.issues.is_empty() {
+        notices.write(Notify::warning(format!(
+            "Perturb (seed {}): {}",
+            report.seed,
+            report.issues.join(", ")
+        )));
+    }
+    // The next run explores a different sequence; the report keeps this seed
+    perturbation.seed = perturbation.seed.wrapping_add(1);
+    perturbation.last = Some(report);
+}
+
+pub fn perturbation_panel(mut contexts: EguiContexts, mut perturbation: ResMut<Perturbation>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Perturb (dev)")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut seed = perturbation.seed;
+            ui.add(egui::DragValue::new(&mut seed).prefix("Seed: "));
+            if seed != perturbation.seed {
+                perturbation.seed = seed;
+            }
+            let mut noise = perturbation.noise;
+            ui.add(egui::Slider::new(&mut noise, 0.0..=1.0).text("Noise (× mean edge)"));
+            if noise != perturbation.noise {
+                perturbation.noise = noise;
+            }
+            let mut collapses = perturbation.collapses;
+            ui.add(egui::Slider::new(&mut collapses, 0..=1000).text("Random collapses"));
+            if collapses != perturbation.collapses {
+                perturbation.collapses = collapses;
+            }
+            let mut flips = perturbation.flips;
+            ui.add(egui::Slider::new(&mut flips, 0..=1000).text("Random flips"));
+            if flips != perturbation.flips {
+                perturbation.flips = flips;
+            }
+            if ui.button("Perturb selected mesh").clicked() {
+                perturbation.requested = true;
+            }
+
+            let Some(report) = &perturbation.last else {
+                return;
+            };
+            ui.separator();
+            ui.label(report.summary());
+            if let Some(panic) = &report.panic {
+                ui.colored_label(egui::Color32::RED, panic);
+            }
+            for issue in &report.issues {
+                ui.colored_label(egui::Color32::YELLOW, issue);
+            }
+            if report.panic.is_none() && report.issues.is_empty() {
+                ui.label("No problems found");
+            }
+            if ui.button("Copy report").clicked() {
+                let mut text = report.summary();
+                for line in report.panic.iter().chain(&report.issues) {
+                    text += &format!("\n{}", line);
+                }
+                ui.ctx().copy_text(text);
+            }
+        });
+}
@@ -0,0 +1,268 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::VecDeque;
+#[cfg(feature = "native")]
+use std::{fs, path::Path};
+
+use bevy::{
+    asset::uuid::Uuid,
+    ecs::{
+        entity::Entity,
+        event::EventWriter,
+        query::With,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::Vec2,
+    picking::pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput},
+    render::camera::NormalizedRenderTarget,
+    time::Time,
+    window::{PrimaryWindow, Window, WindowRef},
+};
+
+use crate::notifications::systems::Notify;
+#[cfg(feature = "native")]
+use crate::picklog::systems::PickRecord;
+
+// Pointer the replayed clicks come from, next to the mouse and touches
+const REPLAY_POINTER: PointerId =
+    PointerId::Custom(Uuid::from_u128(0x7069_636b_2d72_6570_6c61_7900_0000_0001));
+// Delay before the first click, so the pointer exists and the scale factor
+// override has been applied
+const START_DELAY_SECS: f64 = 0.25;
+
+// A logged click: seconds after the first one, position in logical pixels
+// and the window's scale factor at the time
+#[derive(Debug, Clone, Copy)]
+struct ReplayClick {
+    offset: f64,
+    position: Vec2,
+    scale_factor: f32,
+}
+
+// Plays a pick log back as pointer input: each click is a move, press and
+// release of a virtual pointer at the logged position, keeping the logged
+// timing, so it goes through the picking backend and `handle_mesh_click`
+// exactly like the original click. The logged scale factor is applied to the
+// window for the duration, for bugs that depend on DPI.
+#[derive(Resource, Default)]
+pub struct PickReplay {
+    pub path: String,
+    pub total: usize,
+    clicks: VecDeque<ReplayClick>,
+    started_at: Option<f64>,
+    // Click pressed last frame, released this one
+    pressed: Option<Vec2>,
+    // Scale factor override in effect before the replay
+    restore_scale: Option<Option<f32>>,
+}
+
+impl PickReplay {
+    pub fn active(&self) -> bool {
+        !self.clicks.is_empty() || self.pressed.is_some()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.clicks.len()
+    }
+
+    pub fn stop(&mut self) {
+        self.clicks.clear();
+    }
+
+    // Queues the clicks of a CSV or JSON pick log, by extension
+    #[cfg(feature = "native")]
+    pub fn load(&mut self, path: &Path) -> Result<usize, String> {
+        let text = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        let json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let clicks = if json {
+            let records: Vec<PickRecord> =
+                serde_json::from_str(&text).map_err(|err| err.to_string())?;
+            records
+                .iter()
+                .map(|r| (r.time, Vec2::from_array(r.screen), r.scale_factor))
+                .collect()
+        } else {
+            parse_csv(&text)?
+        };
+        let Some(&(first, ..)) = clicks.first() else {
+            return Err("the log has no picks".into());
+        };
+        self.clicks = clicks
+            .into_iter()
+            .map(|(time, position, scale_factor)| ReplayClick {
+                offset: time - first,
+                position,
+                scale_factor,
+            })
+            .collect();
+        self.total = self.clicks.len();
+        self.started_at = None;
+        Ok(self.total)
+    }
+}
+
+// Time, position and scale factor of every row of a CSV pick log
+#[cfg(feature = "native")]
+fn parse_csv(text: &str) -> Result<Vec<(f64, Vec2, f32)>, String> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = split_csv(lines.next().ok_or("the log is empty")?);
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|field| field == name)
+            .ok_or_else(|| format!("missing column {}", name))
+    };
+    let (time, x, y, scale) = (
+        column("time")?,
+        column("screen_x")?,
+        column("screen_y")?,
+        column("scale_factor")?,
+    );
+    lines
+        .enumerate()
+        .map(|(row, line)| {
+            let fields = split_csv(line);
+            let number = |index: usize| {
+                fields
+                    .get(index)
+                    .and_then(|field| field.parse::<f64>().ok())
+                    .ok_or_else(|| {
+                        format!("row {}: bad value in column {}", row + 1, header[index])
+                    })
+            };
+            Ok((
+                number(time)?,
+                Vec2::new(number(x)? as f32, number(y)? as f32),
+                number(scale)? as f32,
+            ))
+        })
+        .collect()
+}
+
+// Fields of one CSV line; quoted fields may hold commas and doubled quotes
+#[cfg(feature = "native")]
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+pub fn replay_picks(
+    mut commands: Commands,
+    mut replay: ResMut<PickReplay>,
+    mut inputs: EventWriter<PointerInput>,
+    mut notices: EventWriter<Notify>,
+    time: Res<Time>,
+    pointer_query: Query<(Entity, &PointerId)>,
+    mut window_query: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+) {
+    let pointer = pointer_query
+        .iter()
+        .find(|(_, id)| **id == REPLAY_POINTER)
+        .map(|(entity, _)| entity);
+    let Ok((window_entity, mut window)) = window_query.single_mut() else {
+        return;
+    };
+    if !replay.active() {
+        if let Some(pointer) = pointer {
+            commands.entity(pointer).despawn();
+        }
+        if let Some(scale) = replay.restore_scale.take() {
+            window.resolution.set_scale_factor_override(scale);
+            notices.write(Notify::info(format!(
+                "Replayed {} picks from {}",
+                replay.total, replay.path
+            )));
+        }
+        return;
+    }
+
+    let now = time.elapsed_secs_f64();
+    let Some(started_at) = replay.started_at else {
+        // The clicks are replayed at the scale factor of the first one
+        let scale = replay.clicks.front().map(|click| click.scale_factor);
+        // Loading a log mid-replay keeps the override from before the first one
+        let previous = window.resolution.scale_factor_override();
+        replay.restore_scale.get_or_insert(previous);
+        if scale.is_some_and(|scale| scale != window.scale_factor()) {
+            window.resolution.set_scale_factor_override(scale);
+        }
+        if pointer.is_none() {
+            commands.spawn(REPLAY_POINTER);
+        }
+        replay.started_at = Some(now + START_DELAY_SECS);
+        return;
+    };
+    let Some(target) = WindowRef::Primary
+        .normalize(Some(window_entity))
+        .map(NormalizedRenderTarget::Window)
+    else {
+        return;
+    };
+    let location = |position| Location {
+        target: target.clone(),
+        position,
+    };
+
+    if let Some(position) = replay.pressed.take() {
+        inputs.write(PointerInput::new(
+            REPLAY_POINTER,
+            location(position),
+            PointerAction::Release(PointerButton::Primary),
+        ));
+        return;
+    }
+    let Some(click) = replay.clicks.front().copied() else {
+        return;
+    };
+    if now < started_at + click.offset {
+        return;
+    }
+    replay.clicks.pop_front();
+    inputs.write(PointerInput::new(
+        REPLAY_POINTER,
+        location(click.position),
+        PointerAction::Move { delta: Vec2::ZERO },
+    ));
+    inputs.write(PointerInput::new(
+        REPLAY_POINTER,
+        location(click.position),
+        PointerAction::Press(PointerButton::Primary),
+    ));
+    replay.pressed = Some(click.position);
+}
@@ -0,0 +1,370 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(feature = "native")]
+use std::{
+    fmt::{Display, Write as _},
+    fs,
+    path::Path,
+};
+
+#[cfg(feature = "native")]
+use bevy::ecs::event::EventWriter;
+
+use bevy::{
+    ecs::{
+        event::EventReader,
+        name::Name,
+        query::With,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    math::Vec2,
+    render::camera::Camera,
+    time::Time,
+    transform::components::GlobalTransform,
+    window::{PrimaryWindow, Window},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use serde::{Deserialize, Serialize};
+
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::context_menu::systems::{MeshElement, pick_element};
+use crate::mesh::edge::{MeshPicked, PickSettings};
+use crate::mesh::face_tree::{FaceSurface, FaceTreeCache};
+#[cfg(feature = "native")]
+use crate::notifications::systems::Notify;
+#[cfg(feature = "native")]
+use crate::picklog::replay::PickReplay;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickLogFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl PickLogFormat {
+    pub const ALL: [PickLogFormat; 2] = [PickLogFormat::Csv, PickLogFormat::Json];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PickLogFormat::Csv => "CSV",
+            PickLogFormat::Json => "JSON",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            PickLogFormat::Csv => "csv",
+            PickLogFormat::Json => "json",
+        }
+    }
+}
+
+// One click on a mesh, with what it resolved to and everything needed to
+// replay it against cgar: the ray in mesh-local coordinates and the pick
+// settings in effect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PickRecord {
+    // Seconds since the viewer started
+    pub time: f64,
+    pub mesh: String,
+    // "vertex", "edge", "face" or "none" when the ray missed every triangle
+    pub element: String,
+    pub ids: Vec<usize>,
+    // Pointer position in logical pixels, and the window's scale factor
+    pub screen: [f32; 2],
+    pub scale_factor: f32,
+    pub hit_world: Option<[f32; 3]>,
+    pub hit_local: Option<[f64; 3]>,
+    pub ray_origin: [f64; 3],
+    pub ray_direction: [f64; 3],
+    pub radius_px: f32,
+    pub ignore_backfaces: bool,
+}
+
+// Logs every pick while enabled and rewrites the log file after each one
+#[derive(Resource)]
+pub struct PickLog {
+    pub enabled: bool,
+    pub format: PickLogFormat,
+    // File name without extension; the format picks the extension
+    pub stem: String,
+    pub records: Vec<PickRecord>,
+}
+
+impl Default for PickLog {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: PickLogFormat::default(),
+            stem: "cgar-viewer.picks".into(),
+            records: Vec::new(),
+        }
+    }
+}
+
+impl PickLog {
+    pub fn path(&self) -> String {
+        format!("{}.{}", self.stem, self.format.extension())
+    }
+
+    #[cfg(feature = "native")]
+    fn contents(&self) -> Result<String, String> {
+        match self.format {
+            PickLogFormat::Json => {
+                serde_json::to_string_pretty(&self.records).map_err(|err| err.to_string())
+            }
+            PickLogFormat::Csv => {
+                let mut out = String::from(
+                    "time,mesh,element,ids,screen_x,screen_y,scale_factor,hit_x,hit_y,hit_z,\
+                     local_x,local_y,local_z,origin_x,origin_y,origin_z,\
+                     direction_x,direction_y,direction_z,radius_px,ignore_backfaces\n",
+                );
+                fn triple<T: Display>(v: Option<[T; 3]>) -> String {
+                    v.map_or_else(
+                        || ",,".to_string(),
+                        |[x, y, z]| format!("{},{},{}", x, y, z),
+                    )
+                }
+                for r in &self.records {
+                    let ids: Vec<String> = r.ids.iter().map(usize::to_string).collect();
+                    let _ = writeln!(
+                        out,
+                        "{},\"{}\",{},{},{},{},{},{},{},{},{},{},{}",
+                        r.time,
+                        r.mesh.replace('"', "\"\""),
+                        r.element,
+                        ids.join(" "),
+                        r.screen[0],
+                        r.screen[1],
+                        r.scale_factor,
+                        triple(r.hit_world),
+                        triple(r.hit_local),
+                        triple(Some(r.ray_origin)),
+                        triple(Some(r.ray_direction)),
+                        r.radius_px,
+                        r.ignore_backfaces,
+                    );
+                }
+                Ok(out)
+            }
+        }
+    }
+
+    #[cfg(feature = "native")]
+    fn write(&self) -> Result<(), String> {
+        fs::write(self.path(), self.contents()?).map_err(|err| err.to_string())
+    }
+}
+
+pub fn log_picks(
+    mut log: ResMut<PickLog>,
+    mut picked: EventReader<MeshPicked>,
+    #[cfg(feature = "native")] mut notices: EventWriter<Notify>,
+    time: Res<Time>,
+    pick_settings: Res<PickSettings>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mesh_query: Query<(
+        &GlobalTransform,
+        &CgarMeshData,
+        Option<&FaceTreeCache>,
+        Option<&Name>,
+    )>,
+) {
+    if !log.enabled {
+        picked.clear();
+        return;
+    }
+    let Ok((camera, camera_global)) = camera_query.single() else {
+        return;
+    };
+    let viewport_min = camera
+        .logical_viewport_rect()
+        .map_or(Vec2::ZERO, |rect| rect.min);
+    let mut logged = false;
+    for pick in picked.read().filter(|pick| !pick.double_click) {
+        let Ok((mesh_global, cgar_data, cache, name)) = mesh_query.get(pick.entity) else {
+            continue;
+        };
+        let Ok(ray) = camera.viewport_to_world(camera_global, pick.screen_position - viewport_min)
+        else {
+            continue;
+        };
+        let to_local = mesh_global.affine().inverse();
+        let origin = to_local.transform_point3(ray.origin).as_dvec3();
+        let direction = to_local
+            .transform_vector3(ray.direction.as_vec3())
+            .as_dvec3();
+
+        let built;
+        let surface = match cache {
+            Some(cache) => &cache.0,
+            None => {
+                built = FaceSurface::build(&cgar_data.0);
+                &built
+            }
+        };
+        let hit = pick_element(
+            (camera, camera_global),
+            mesh_global,
+            &cgar_data.0,
+            surface,
+            pick.screen_position,
+            pick.world_position,
+            &pick_settings,
+        );
+        let (element, ids) = match hit.map(|(element, _)| element) {
+            Some(MeshElement::Vertex(v)) => ("vertex", vec![v]),
+            Some(MeshElement::Edge(v0, v1)) => ("edge", vec![v0, v1]),
+            Some(MeshElement::Face(f)) => ("face", vec![f]),
+            None => ("none", Vec::new()),
+        };
+        let hit = hit.map(|(_, hit)| hit);
+        log.records.push(PickRecord {
+            time: time.elapsed_secs_f64(),
+            mesh: name.map_or_else(|| pick.entity.to_string(), |name| name.to_string()),
+            element: element.into(),
+            ids,
+            screen: pick.screen_position.to_array(),
+            scale_factor: window_query
+                .single()
+                .map_or(1.0, |window| window.scale_factor()),
+            hit_world: hit.map(|hit| hit.world.to_array()),
+            hit_local: hit.map(|hit| hit.local.to_array()),
+            ray_origin: origin.to_array(),
+            ray_direction: direction.to_array(),
+            radius_px: pick_settings.radius_px,
+            ignore_backfaces: pick_settings.ignore_backfaces,
+        });
+        logged = true;
+    }
+
+    if !logged {
+        return;
+    }
+    // The whole file is rewritten so it is complete even if the viewer crashes
+    #[cfg(feature = "native")]
+    if let Err(err) = log.write() {
+        notices.write(Notify::error(format!(
+            "Pick logging stopped, could not write {}: {}",
+            log.path(),
+            err
+        )));
+        log.enabled = false;
+    }
+}
+
+pub fn pick_log_panel(
+    mut contexts: EguiContexts,
+    mut log: ResMut<PickLog>,
+    #[cfg(feature = "native")] mut replay: ResMut<PickReplay>,
+    #[cfg(feature = "native")] mut notices: EventWriter<Notify>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Pick log")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut enabled = log.enabled;
+            ui.checkbox(&mut enabled, "Log every pick");
+            if enabled != log.enabled {
+                log.enabled = enabled;
+            }
+
+            let mut format = log.format;
+            let mut stem = log.stem.clone();
+            ui.horizontal(|ui| {
+                ui.label("File");
+                ui.text_edit_singleline(&mut stem);
+                egui::ComboBox::from_id_salt("pick_log_format")
+                    .selected_text(format.label())
+                    .show_ui(ui, |ui| {
+                        for option in PickLogFormat::ALL {
+                            ui.selectable_value(&mut format, option, option.label());
+                        }
+                    });
+            });
+            if format != log.format || stem != log.stem {
+                log.format = format;
+                log.stem = stem;
+            }
+            ui.label(format!(
+                "{} picks, written to {}",
+                log.records.len(),
+                log.path()
+            ));
+            if ui
+                .add_enabled(!log.records.is_empty(), egui::Button::new("Clear"))
+                .clicked()
+            {
+                log.records.clear();
+            }
+
+            // Clicks come back through the picking backend, so logging while
+            // replaying writes a log to compare with the original
+            #[cfg(feature = "native")]
+            {
+                ui.separator();
+                let mut path = if replay.path.is_empty() {
+                    log.path()
+                } else {
+                    replay.path.clone()
+                };
+                ui.horizontal(|ui| {
+                    ui.label("Replay");
+                    ui.text_edit_singleline(&mut path);
+                });
+                if path != replay.path {
+                    replay.path = path;
+                }
+                if replay.active() {
+                    ui.label(format!(
+                        "{} of {} clicks left",
+                        replay.remaining(),
+                        replay.total
+                    ));
+                    if ui.button("Stop").clicked() {
+                        replay.stop();
+                    }
+                } else if ui.button("Replay clicks").clicked() {
+                    let path = replay.path.clone();
+                    match replay.load(Path::new(&path)) {
+                        Ok(count) => {
+                            notices.write(Notify::info(format!(
+                                "Replaying {} clicks from {}",
+                                count, path
+                            )));
+                        }
+                        Err(err) => {
+                            notices
+                                .write(Notify::error(format!("Failed to load {}: {}", path, err)));
+                        }
+                    }
+                }
+            }
+        });
+}
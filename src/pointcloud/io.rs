@@ -0,0 +1,338 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use bevy::{color::Color, math::DVec3};
+
+use crate::pointcloud::components::PointCloud;
+
+pub fn is_point_cloud_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("xyz") || ext.eq_ignore_ascii_case("ply"))
+}
+
+pub fn read_point_cloud(path: &Path) -> Result<PointCloud, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse_point_cloud(path, BufReader::new(file))
+}
+
+// Parses cloud data already in memory; `path` only picks the format and names errors
+pub fn parse_point_cloud(path: &Path, reader: impl BufRead) -> Result<PointCloud, String> {
+    let cloud = if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ply"))
+    {
+        read_ply(reader)
+    } else {
+        read_xyz(reader)
+    };
+    cloud.map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn srgb_to_linear(rgb: [f64; 3], scale: f64) -> [f32; 4] {
+    Color::srgb(
+        (rgb[0] / scale) as f32,
+        (rgb[1] / scale) as f32,
+        (rgb[2] / scale) as f32,
+    )
+    .to_linear()
+    .to_f32_array()
+}
+
+// Whitespace- or comma-separated `x y z [nx ny nz] [r g b]` rows; `#` starts a comment.
+// Six columns are read as normals when they have unit length, otherwise as colors.
+pub fn read_xyz(reader: impl BufRead) -> Result<PointCloud, String> {
+    let mut rows: Vec<Vec<f64>> = Vec::new();
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        let data = line.split('#').next().unwrap_or("").trim();
+        if data.is_empty() {
+            continue;
+        }
+        let row = data
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(str::parse::<f64>)
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|e| format!("line {}: {}", line_no + 1, e))?;
+        if row.len() < 3 {
+            return Err(format!("line {}: expected at least 3 values", line_no + 1));
+        }
+        rows.push(row);
+    }
+
+    let columns = rows.iter().map(Vec::len).min().unwrap_or(3);
+    let unit_length =
+        |row: &[f64]| (DVec3::new(row[3], row[4], row[5]).length() - 1.0).abs() < 1e-2;
+    let (normal_at, color_at) = match columns {
+        0..=5 => (None, None),
+        6..=8 if rows.iter().all(|row| unit_length(row)) => (Some(3), None),
+        6..=8 => (None, Some(3)),
+        _ => (Some(3), Some(6)),
+    };
+
+    let mut cloud = PointCloud {
+        points: rows
+            .iter()
+            .map(|row| DVec3::new(row[0], row[1], row[2]))
+            .collect(),
+        ..Default::default()
+    };
+    if let Some(i) = normal_at {
+        cloud.normals = Some(
+            rows.iter()
+                .map(|row| DVec3::new(row[i], row[i + 1], row[i + 2]).normalize_or_zero())
+                .collect(),
+        );
+    }
+    if let Some(i) = color_at {
+        // 0..255 integers are far more common than 0..1 floats, but accept both
+        let max = rows
+            .iter()
+            .flat_map(|row| &row[i..i + 3])
+            .fold(0.0f64, |m, &c| m.max(c));
+        let scale = if max > 1.0 { 255.0 } else { 1.0 };
+        cloud.colors = Some(
+            rows.iter()
+                .map(|row| srgb_to_linear([row[i], row[i + 1], row[i + 2]], scale))
+                .collect(),
+        );
+    }
+    Ok(cloud)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlyType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl PlyType {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => PlyType::I8,
+            "uchar" | "uint8" => PlyType::U8,
+            "short" | "int16" => PlyType::I16,
+            "ushort" | "uint16" => PlyType::U16,
+            "int" | "int32" => PlyType::I32,
+            "uint" | "uint32" => PlyType::U32,
+            "float" | "float32" => PlyType::F32,
+            "double" | "float64" => PlyType::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            PlyType::I8 | PlyType::U8 => 1,
+            PlyType::I16 | PlyType::U16 => 2,
+            PlyType::I32 | PlyType::U32 | PlyType::F32 => 4,
+            PlyType::F64 => 8,
+        }
+    }
+
+    fn read(self, bytes: &[u8], big_endian: bool) -> f64 {
+        macro_rules! decode {
+            ($t:ty) => {{
+                let raw = bytes.try_into().expect("property size");
+                (if big_endian {
+                    <$t>::from_be_bytes(raw)
+                } else {
+                    <$t>::from_le_bytes(raw)
+                }) as f64
+            }};
+        }
+        match self {
+            PlyType::I8 => decode!(i8),
+            PlyType::U8 => decode!(u8),
+            PlyType::I16 => decode!(i16),
+            PlyType::U16 => decode!(u16),
+            PlyType::I32 => decode!(i32),
+            PlyType::U32 => decode!(u32),
+            PlyType::F32 => decode!(f32),
+            PlyType::F64 => decode!(f64),
+        }
+    }
+}
+
+// Reads the vertex element of a PLY file; faces and other elements after it are ignored
+pub fn read_ply(mut reader: impl BufRead) -> Result<PointCloud, String> {
+    let mut line = String::new();
+    let mut next_line = |reader: &mut dyn BufRead| -> Result<String, String> {
+        line.clear();
+        if reader.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+            return Err("unexpected end of header".to_string());
+        }
+        Ok(line.trim().to_string())
+    };
+
+    if next_line(&mut reader)? != "ply" {
+        return Err("missing ply magic".to_string());
+    }
+    let mut format = None;
+    let mut vertex_count = 0usize;
+    let mut properties: Vec<(String, PlyType)> = Vec::new();
+    // Only elements before "vertex" matter, since their data has to be skipped
+    let mut in_vertex = false;
+    let mut seen_vertex = false;
+    loop {
+        let header = next_line(&mut reader)?;
+        let tokens: Vec<&str> = header.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["end_header"] => break,
+            ["format", kind, _] => {
+                format = Some(match *kind {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    other => return Err(format!("unsupported format {}", other)),
+                });
+            }
+            ["element", name, count] => {
+                if !seen_vertex && *name != "vertex" {
+                    return Err(format!("element {} before vertex is not supported", name));
+                }
+                in_vertex = *name == "vertex";
+                if in_vertex {
+                    seen_vertex = true;
+                    vertex_count = count
+                        .parse()
+                        .map_err(|_| format!("bad vertex count {}", count))?;
+                }
+            }
+            ["property", "list", ..] if in_vertex => {
+                return Err("list properties on vertices are not supported".to_string());
+            }
+            ["property", ty, name] if in_vertex => {
+                let ty = PlyType::parse(ty).ok_or_else(|| format!("unknown type {}", ty))?;
+                properties.push((name.to_string(), ty));
+            }
+            _ => {}
+        }
+    }
+    let format = format.ok_or("missing format line")?;
+
+    let column = |name: &str| properties.iter().position(|(n, _)| n == name);
+    let (Some(x), Some(y), Some(z)) = (column("x"), column("y"), column("z")) else {
+        return Err("vertex element has no x/y/z".to_string());
+    };
+    let normal_columns = match (column("nx"), column("ny"), column("nz")) {
+        (Some(a), Some(b), Some(c)) => Some([a, b, c]),
+        _ => None,
+    };
+    let color_columns = match (
+        column("red").or(column("r")),
+        column("green").or(column("g")),
+        column("blue").or(column("b")),
+    ) {
+        (Some(a), Some(b), Some(c)) => Some([a, b, c]),
+        _ => None,
+    };
+
+    let mut rows: Vec<Vec<f64>> = Vec::with_capacity(vertex_count);
+    match format {
+        PlyFormat::Ascii => {
+            for line in reader.lines().take(vertex_count) {
+                let line = line.map_err(|e| e.to_string())?;
+                let row = line
+                    .split_whitespace()
+                    .map(str::parse::<f64>)
+                    .collect::<Result<Vec<f64>, _>>()
+                    .map_err(|e| e.to_string())?;
+                if row.len() < properties.len() {
+                    return Err(format!("vertex {} is missing properties", rows.len()));
+                }
+                rows.push(row);
+            }
+        }
+        PlyFormat::BinaryLittleEndian | PlyFormat::BinaryBigEndian => {
+            let big_endian = format == PlyFormat::BinaryBigEndian;
+            let stride: usize = properties.iter().map(|(_, ty)| ty.size()).sum();
+            let mut record = vec![0u8; stride];
+            for _ in 0..vertex_count {
+                reader.read_exact(&mut record).map_err(|e| e.to_string())?;
+                let mut offset = 0;
+                let row = properties
+                    .iter()
+                    .map(|(_, ty)| {
+                        let value = ty.read(&record[offset..offset + ty.size()], big_endian);
+                        offset += ty.size();
+                        value
+                    })
+                    .collect();
+                rows.push(row);
+            }
+        }
+    }
+    if rows.len() < vertex_count {
+        return Err(format!(
+            "expected {} vertices, found {}",
+            vertex_count,
+            rows.len()
+        ));
+    }
+
+    let mut cloud = PointCloud {
+        points: rows
+            .iter()
+            .map(|row| DVec3::new(row[x], row[y], row[z]))
+            .collect(),
+        ..Default::default()
+    };
+    if let Some([a, b, c]) = normal_columns {
+        cloud.normals = Some(
+            rows.iter()
+                .map(|row| DVec3::new(row[a], row[b], row[c]).normalize_or_zero())
+                .collect(),
+        );
+    }
+    if let Some([a, b, c]) = color_columns {
+        // Integer channels are 0..255, float channels 0..1
+        let scale = if properties[a].1 == PlyType::F32 || properties[a].1 == PlyType::F64 {
+            1.0
+        } else {
+            255.0
+        };
+        cloud.colors = Some(
+            rows.iter()
+                .map(|row| srgb_to_linear([row[a], row[b], row[c]], scale))
+                .collect(),
+        );
+    }
+    Ok(cloud)
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Round point sprites for `PointSpriteMaterial`. Every point is a quad whose
+// four corners share its position; the vertex shader spreads them apart in
+// view space so the quad always faces the camera, and the fragment shader
+// cuts it down to a disc.
+
+#import bevy_pbr::{
+    mesh_functions::get_world_from_local,
+    mesh_view_bindings::view,
+}
+
+struct PointSpriteParams {
+    // Disc radius in the cloud's local units
+    radius: f32,
+}
+
+@group(2) @binding(0) var<uniform> params: PointSpriteParams;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(1) corner: vec2<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) corner: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    let world_from_local = get_world_from_local(vertex.instance_index);
+    let world = world_from_local * vec4<f32>(vertex.position, 1.0);
+    let scale = length(world_from_local[0].xyz);
+    var center = view.view_from_world * world;
+    center = vec4<f32>(center.xy + vertex.corner * params.radius * scale, center.zw);
+
+    var out: VertexOutput;
+    out.clip_position = view.clip_from_view * center;
+    out.corner = vertex.corner;
+    out.color = vertex.color;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    if dot(in.corner, in.corner) > 1.0 {
+        discard;
+    }
+    return in.color;
+}
@@ -0,0 +1,327 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use bevy::math::DVec3;
+
+use crate::analysis::fitting::{covariance, symmetric_eigen};
+use crate::mesh::spatial::PointGrid;
+
+// Shared between the UI and the background reconstruction task
+#[derive(Default)]
+pub struct ReconstructionProgress {
+    pub processed: AtomicUsize,
+    pub total: AtomicUsize,
+    pub cancel: AtomicBool,
+}
+
+impl ReconstructionProgress {
+    pub fn fraction(&self) -> f32 {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            0.0
+        } else {
+            self.processed.load(Ordering::Relaxed) as f32 / total as f32
+        }
+    }
+
+    fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+fn indexed(points: &[DVec3]) -> Vec<(usize, DVec3)> {
+    points.iter().copied().enumerate().collect()
+}
+
+// Mean nearest-neighbor distance over an even subsample of the cloud
+pub fn estimate_spacing(points: &[DVec3]) -> f64 {
+    if points.len() < 2 {
+        return 0.0;
+    }
+    let grid = PointGrid::with_auto_cell(indexed(points));
+    let stride = points.len().div_ceil(1000);
+    let distances: Vec<f64> = points
+        .iter()
+        .step_by(stride)
+        .filter_map(|&p| grid.k_nearest(p, 2).get(1).map(|&(_, d)| d))
+        .collect();
+    if distances.is_empty() {
+        0.0
+    } else {
+        distances.iter().sum::<f64>() / distances.len() as f64
+    }
+}
+
+// PCA normals over the `k` nearest neighbors. Without a scanner position to orient
+// against, normals are flipped to point away from the cloud's centroid.
+pub fn estimate_normals(points: &[DVec3], k: usize) -> Vec<DVec3> {
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let grid = PointGrid::with_auto_cell(indexed(points));
+    let centroid = points.iter().sum::<DVec3>() / points.len() as f64;
+    points
+        .iter()
+        .map(|&p| {
+            let neighbors: Vec<DVec3> = grid
+                .k_nearest(p, k)
+                .iter()
+                .map(|&(id, _)| points[id])
+                .collect();
+            if neighbors.len() < 3 {
+                return DVec3::ZERO;
+            }
+            let mean = neighbors.iter().sum::<DVec3>() / neighbors.len() as f64;
+            let (_, vectors) = symmetric_eigen(covariance(neighbors.iter().map(|&q| q - mean)));
+            let normal = vectors[0].normalize_or_zero();
+            if normal.dot(p - centroid) < 0.0 {
+                -normal
+            } else {
+                normal
+            }
+        })
+        .collect()
+}
+
+// Center of the ball of `radius` touching a, b and c, on the side of the
+// triangle's counter-clockwise normal
+fn ball_center(a: DVec3, b: DVec3, c: DVec3, radius: f64) -> Option<DVec3> {
+    let ab = b - a;
+    let ac = c - a;
+    let n = ab.cross(ac);
+    let n_sq = n.length_squared();
+    if n_sq < 1e-24 {
+        return None;
+    }
+    let circumcenter =
+        a + (n.cross(ab) * ac.length_squared() + ac.cross(n) * ab.length_squared()) / (2.0 * n_sq);
+    let h_sq = radius * radius - circumcenter.distance_squared(a);
+    if h_sq < 0.0 {
+        return None;
+    }
+    Some(circumcenter + n / n_sq.sqrt() * h_sq.sqrt())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EdgeInfo {
+    center: DVec3,
+    opposite: usize,
+    on_front: bool,
+}
+
+struct BallPivot<'a> {
+    points: &'a [DVec3],
+    normals: &'a [DVec3],
+    radius: f64,
+    grid: PointGrid,
+    // Every directed edge of every emitted triangle
+    edges: HashMap<(usize, usize), EdgeInfo>,
+    queue: VecDeque<(usize, usize)>,
+    used: Vec<bool>,
+    front_degree: Vec<u32>,
+    used_count: usize,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl BallPivot<'_> {
+    fn agrees_with_normals(&self, tri: [usize; 3]) -> bool {
+        let [a, b, c] = tri.map(|v| self.points[v]);
+        let n = (b - a).cross(c - a);
+        tri.iter().all(|&v| n.dot(self.normals[v]) > 0.0)
+    }
+
+    fn ball_is_empty(&self, center: DVec3, tri: [usize; 3]) -> bool {
+        // Tolerance keeps the triangle's own corners, which sit exactly on the sphere, out
+        self.grid
+            .within(center, self.radius * (1.0 - 1e-7))
+            .iter()
+            .all(|(id, _)| tri.contains(id))
+    }
+
+    fn can_add(&self, tri: [usize; 3]) -> bool {
+        let [a, b, c] = tri;
+        [(a, b), (b, c), (c, a)]
+            .iter()
+            .all(|edge| !self.edges.contains_key(edge))
+    }
+
+    fn add_triangle(&mut self, tri: [usize; 3], center: DVec3) {
+        let [a, b, c] = tri;
+        for (p, q, opposite) in [(a, b, c), (b, c, a), (c, a, b)] {
+            let glued = match self.edges.get_mut(&(q, p)) {
+                Some(reverse) => {
+                    if reverse.on_front {
+                        reverse.on_front = false;
+                        self.front_degree[p] -= 1;
+                        self.front_degree[q] -= 1;
+                    }
+                    true
+                }
+                None => false,
+            };
+            self.edges.insert(
+                (p, q),
+                EdgeInfo {
+                    center,
+                    opposite,
+                    on_front: !glued,
+                },
+            );
+            if !glued {
+                self.front_degree[p] += 1;
+                self.front_degree[q] += 1;
+                self.queue.push_back((p, q));
+            }
+        }
+        for v in tri {
+            if !self.used[v] {
+                self.used[v] = true;
+                self.used_count += 1;
+            }
+        }
+        self.triangles.push(tri);
+    }
+
+    fn retire(&mut self, edge: (usize, usize)) {
+        if let Some(info) = self.edges.get_mut(&edge).filter(|info| info.on_front) {
+            info.on_front = false;
+            self.front_degree[edge.0] -= 1;
+            self.front_degree[edge.1] -= 1;
+        }
+    }
+
+    // Rolls the ball over front edge a->b; the new triangle is (b, a, x)
+    fn pivot(&self, a: usize, b: usize) -> Option<([usize; 3], DVec3)> {
+        let info = self.edges[&(a, b)];
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let mid = (pa + pb) * 0.5;
+        let axis = (pb - pa).normalize();
+        let start = info.center - mid;
+
+        let mut candidates: Vec<(f64, usize, DVec3)> = self
+            .grid
+            .within(mid, 2.0 * self.radius)
+            .into_iter()
+            .filter(|&(x, _)| x != a && x != b && x != info.opposite)
+            .filter(|&(x, _)| !self.used[x] || self.front_degree[x] > 0)
+            .filter_map(|(x, _)| {
+                let center = ball_center(pb, pa, self.points[x], self.radius)?;
+                let end = center - mid;
+                let angle = axis.dot(start.cross(end)).atan2(start.dot(end));
+                let angle = if angle < 0.0 {
+                    angle + std::f64::consts::TAU
+                } else {
+                    angle
+                };
+                Some((angle, x, center))
+            })
+            .collect();
+        candidates.sort_by(|p, q| p.0.total_cmp(&q.0));
+
+        candidates.into_iter().find_map(|(_, x, center)| {
+            let tri = [b, a, x];
+            (self.agrees_with_normals(tri) && self.can_add(tri) && self.ball_is_empty(center, tri))
+                .then_some((tri, center))
+        })
+    }
+
+    fn find_seed(&self, cursor: &mut usize) -> Option<([usize; 3], DVec3)> {
+        while *cursor < self.points.len() {
+            let i = *cursor;
+            *cursor += 1;
+            if self.used[i] {
+                continue;
+            }
+            let mut neighbors = self.grid.within(self.points[i], 2.0 * self.radius);
+            neighbors.retain(|&(id, _)| id != i && !self.used[id]);
+            neighbors.sort_by(|p, q| p.1.total_cmp(&q.1));
+            neighbors.truncate(16);
+
+            for (n, &(j, _)) in neighbors.iter().enumerate() {
+                for &(k, _) in &neighbors[n + 1..] {
+                    for tri in [[i, j, k], [i, k, j]] {
+                        if !self.agrees_with_normals(tri) {
+                            continue;
+                        }
+                        let [a, b, c] = tri.map(|v| self.points[v]);
+                        let Some(center) = ball_center(a, b, c, self.radius) else {
+                            continue;
+                        };
+                        if self.ball_is_empty(center, tri) {
+                            return Some((tri, center));
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+// Ball-pivoting surface reconstruction (Bernardini et al. 1999) for a single radius.
+// Returns counter-clockwise triangles indexing `points`, or `None` when cancelled.
+pub fn ball_pivot(
+    points: &[DVec3],
+    normals: &[DVec3],
+    radius: f64,
+    progress: &ReconstructionProgress,
+) -> Option<Vec<[usize; 3]>> {
+    progress.total.store(points.len(), Ordering::Relaxed);
+    let mut state = BallPivot {
+        points,
+        normals,
+        radius,
+        grid: PointGrid::new(indexed(points), 2.0 * radius),
+        edges: HashMap::new(),
+        queue: VecDeque::new(),
+        used: vec![false; points.len()],
+        front_degree: vec![0; points.len()],
+        used_count: 0,
+        triangles: Vec::new(),
+    };
+
+    let mut seed_cursor = 0;
+    while let Some((tri, center)) = state.find_seed(&mut seed_cursor) {
+        state.add_triangle(tri, center);
+        while let Some((a, b)) = state.queue.pop_front() {
+            if progress.cancelled() {
+                return None;
+            }
+            if !state.edges[&(a, b)].on_front {
+                continue;
+            }
+            match state.pivot(a, b) {
+                Some((tri, center)) => state.add_triangle(tri, center),
+                // Nothing to pivot onto: the edge stays on the mesh boundary
+                None => state.retire((a, b)),
+            }
+            progress
+                .processed
+                .store(state.used_count, Ordering::Relaxed);
+        }
+    }
+    progress.processed.store(points.len(), Ordering::Relaxed);
+    Some(state.triangles)
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::mesh::topology::MeshTopology;
+use crate::pointcloud::components::PointCloud;
+use crate::utils::random::SplitMix64;
+
+// Area-weighted uniform samples over the live faces, with face normals
+pub fn sample_surface(topology: &MeshTopology, count: usize, seed: u64) -> PointCloud {
+    let faces: Vec<(usize, [usize; 3])> = topology.live_faces().collect();
+    let mut cumulative = Vec::with_capacity(faces.len());
+    let mut total = 0.0;
+    for &(f, _) in &faces {
+        total += topology.face_area(f);
+        cumulative.push(total);
+    }
+    let mut cloud = PointCloud::default();
+    if faces.is_empty() || total <= 0.0 {
+        return cloud;
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut normals = Vec::with_capacity(count);
+    cloud.points.reserve(count);
+    for _ in 0..count {
+        let target = rng.next_f64() * total;
+        let index = cumulative
+            .partition_point(|&c| c <= target)
+            .min(faces.len() - 1);
+        let (f, tri) = faces[index];
+        let [a, b, c] = topology.corners(tri);
+
+        // Square-root warp keeps the barycentric samples uniform over the triangle
+        let r1 = rng.next_f64().sqrt();
+        let r2 = rng.next_f64();
+        cloud
+            .points
+            .push(a * (1.0 - r1) + b * (r1 * (1.0 - r2)) + c * (r1 * r2));
+        normals.push(topology.face_normal(f));
+    }
+    cloud.normals = Some(normals);
+    cloud
+}
@@ -0,0 +1,707 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use bevy::{
+    asset::{Asset, Assets, Handle, RenderAssetUsages, weak_handle},
+    color::Color,
+    ecs::{
+        change_detection::DetectChanges,
+        entity::Entity,
+        event::EventWriter,
+        name::Name,
+        query::{Changed, With},
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+    },
+    log::{info, warn},
+    math::DVec3,
+    pbr::{Material, MaterialPipeline, MaterialPipelineKey, MeshMaterial3d},
+    picking::Pickable,
+    reflect::TypePath,
+    render::{
+        mesh::{
+            Indices, Mesh, Mesh3d, MeshVertexAttribute, MeshVertexBufferLayoutRef,
+            PrimitiveTopology,
+        },
+        render_resource::{
+            AsBindGroup, RenderPipelineDescriptor, Shader, ShaderRef, ShaderType,
+            SpecializedMeshPipelineError, VertexFormat,
+        },
+        view::NoFrustumCulling,
+    },
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future},
+    transform::components::Transform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::colormap::ColorRamp;
+use crate::analysis::overlay::overlay_color;
+use crate::analysis::ramps::Colormaps;
+use crate::camera::components::CgarMeshData;
+use crate::mesh::obj::{LoadedMesh, load_soup_tolerant};
+use crate::mesh::setup::{DefaultMeshMaterial, spawn_cgar_mesh};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::pointcloud::components::PointCloud;
+use crate::pointcloud::io::{is_point_cloud_path, read_point_cloud};
+use crate::pointcloud::reconstruction::{
+    ReconstructionProgress, ball_pivot, estimate_normals, estimate_spacing,
+};
+use crate::pointcloud::sampling::sample_surface;
+use crate::repair::ops::TriangleSoup;
+use crate::utils::cli::CliOptions;
+
+const POINT_SPRITE_SHADER: Handle<Shader> = weak_handle!("7e4d2b91-c58a-4f36-9d0e-3a6b1f8c52e7");
+
+// Which corner of its point's quad a vertex is, in [-1, 1]
+const ATTRIBUTE_SPRITE_CORNER: MeshVertexAttribute =
+    MeshVertexAttribute::new("SpriteCorner", 988_540_918, VertexFormat::Float32x2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointColorMode {
+    // Per-point colors from the file, uniform where the file has none
+    File,
+    Uniform,
+    Height,
+}
+
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct PointCloudDisplay {
+    // Splat radius as a fraction of each cloud's bounding-box diagonal
+    pub size: f32,
+    pub color_mode: PointColorMode,
+    pub color: [f32; 3],
+}
+
+impl Default for PointCloudDisplay {
+    fn default() -> Self {
+        Self {
+            size: 0.003,
+            color_mode: PointColorMode::File,
+            color: [0.2, 0.6, 1.0],
+        }
+    }
+}
+
+#[derive(ShaderType, Debug, Clone, Copy)]
+pub struct PointSpriteParams {
+    pub radius: f32,
+}
+
+// Camera-facing discs drawn from one quad per point. The radius lives in
+// the material, so resizing never touches the mesh.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct PointSpriteMaterial {
+    #[uniform(0)]
+    pub params: PointSpriteParams,
+}
+
+impl Material for PointSpriteMaterial {
+    fn vertex_shader() -> ShaderRef {
+        POINT_SPRITE_SHADER.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        POINT_SPRITE_SHADER.into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        descriptor.vertex.buffers = vec![layout.0.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            ATTRIBUTE_SPRITE_CORNER.at_shader_location(1),
+            Mesh::ATTRIBUTE_COLOR.at_shader_location(2),
+        ])?];
+        // Both sides of the quad face the camera by construction
+        descriptor.primitive.cull_mode = None;
+        Ok(())
+    }
+}
+
+pub fn setup_point_sprite_shader(mut shaders: ResMut<Assets<Shader>>) {
+    shaders.insert(
+        &POINT_SPRITE_SHADER,
+        Shader::from_wgsl(include_str!("point_sprites.wgsl"), file!()),
+    );
+}
+
+const SPRITE_CORNERS: [[f32; 2]; 4] = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+
+// Splat radius in the cloud's own units
+fn sprite_radius(cloud: &PointCloud, display: &PointCloudDisplay) -> f32 {
+    let (min, max) = cloud.bounds().unwrap_or_default();
+    (max - min).length() as f32 * display.size
+}
+
+// One color per sprite corner, in mesh vertex order
+fn sprite_colors(
+    cloud: &PointCloud,
+    display: &PointCloudDisplay,
+    ramp: &ColorRamp,
+) -> Vec<[f32; 4]> {
+    let (min, max) = cloud.bounds().unwrap_or_default();
+    let uniform = overlay_color(Color::srgb(
+        display.color[0],
+        display.color[1],
+        display.color[2],
+    ));
+    let height_range = (max.y - min.y).max(f64::EPSILON);
+
+    let mut colors = Vec::with_capacity(cloud.len() * SPRITE_CORNERS.len());
+    for (i, p) in cloud.points.iter().enumerate() {
+        let color = match display.color_mode {
+            PointColorMode::File => cloud
+                .colors
+                .as_ref()
+                .and_then(|c| c.get(i).copied())
+                .unwrap_or(uniform),
+            PointColorMode::Uniform => uniform,
+            PointColorMode::Height => {
+                overlay_color(ramp.sample(((p.y - min.y) / height_range) as f32))
+            }
+        };
+        colors.extend([color; SPRITE_CORNERS.len()]);
+    }
+    colors
+}
+
+// Four corners sharing the point's position, merged into one mesh so a
+// whole scan is a single draw
+fn sprite_mesh(cloud: &PointCloud, colors: Vec<[f32; 4]>) -> Mesh {
+    let n = cloud.len();
+    let mut positions = Vec::with_capacity(n * SPRITE_CORNERS.len());
+    let mut corners = Vec::with_capacity(n * SPRITE_CORNERS.len());
+    let mut indices = Vec::with_capacity(n * 6);
+    for p in &cloud.points {
+        let base = positions.len() as u32;
+        let center = p.as_vec3().to_array();
+        for corner in SPRITE_CORNERS {
+            positions.push(center);
+            corners.push(corner);
+        }
+        indices.extend([0, 1, 2, 0, 2, 3].map(|k| base + k));
+    }
+
+    // Kept in the main world too, so a recolor can swap one attribute
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(ATTRIBUTE_SPRITE_CORNER, corners);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+pub fn spawn_point_cloud(
+    commands: &mut Commands,
+    name: String,
+    cloud: PointCloud,
+    transform: Transform,
+) {
+    if cloud.is_empty() {
+        warn!("Point cloud {} has no points", name);
+    } else {
+        info!("Point cloud {}: {} points", name, cloud.len());
+    }
+    commands.spawn((
+        Name::new(name),
+        cloud,
+        transform,
+        // Clouds shouldn't swallow clicks meant for the meshes behind them
+        Pickable::IGNORE,
+    ));
+}
+
+pub fn setup_point_clouds(
+    mut commands: Commands,
+    mut notices: EventWriter<Notify>,
+    cli: Res<CliOptions>,
+) {
+    for path in cli.mesh_paths.iter().map(Path::new) {
+        if !is_point_cloud_path(path) {
+            continue;
+        }
+        match read_point_cloud(path) {
+            Ok(cloud) => {
+                let name = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                spawn_point_cloud(&mut commands, name, cloud, Transform::default());
+            }
+            Err(err) => {
+                notices.write(Notify::error(format!("Failed to load {}", err)));
+            }
+        }
+    }
+}
+
+// Builds sprite meshes for new or edited clouds. Display changes only
+// recolor the existing meshes or resize their materials.
+#[allow(clippy::too_many_arguments)]
+pub fn render_point_clouds(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<PointSpriteMaterial>>,
+    display: Res<PointCloudDisplay>,
+    mut applied: Local<PointCloudDisplay>,
+    colormaps: Res<Colormaps>,
+    changed: Query<Entity, Changed<PointCloud>>,
+    cloud_query: Query<(
+        Entity,
+        &PointCloud,
+        Option<&Mesh3d>,
+        Option<&MeshMaterial3d<PointSpriteMaterial>>,
+    )>,
+) {
+    let recolor = display.color_mode != applied.color_mode
+        || display.color != applied.color
+        || (colormaps.is_changed() && display.color_mode == PointColorMode::Height);
+    let resize = display.size != applied.size;
+    *applied = display.clone();
+    if !recolor && !resize && changed.is_empty() {
+        return;
+    }
+    let ramp = colormaps.ramp(false);
+    for (entity, cloud, mesh_handle, material_handle) in &cloud_query {
+        let rebuild = changed.contains(entity);
+        if rebuild || recolor {
+            let colors = sprite_colors(cloud, &display, ramp);
+            match mesh_handle {
+                Some(handle) if rebuild => {
+                    meshes.insert(&handle.0, sprite_mesh(cloud, colors));
+                }
+                Some(handle) => {
+                    if let Some(mesh) = meshes.get_mut(&handle.0) {
+                        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+                    }
+                }
+                None => {
+                    commands
+                        .entity(entity)
+                        .insert(Mesh3d(meshes.add(sprite_mesh(cloud, colors))));
+                }
+            }
+        }
+        if rebuild || resize {
+            let params = PointSpriteParams {
+                radius: sprite_radius(cloud, &display),
+            };
+            match material_handle.and_then(|handle| materials.get_mut(&handle.0)) {
+                Some(material) => material.params = params,
+                None => {
+                    commands.entity(entity).insert((
+                        MeshMaterial3d(materials.add(PointSpriteMaterial { params })),
+                        // Sprites reach past the bounds of their centers
+                        NoFrustumCulling,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+pub struct PointCloudPanelState {
+    #[cfg(feature = "native")]
+    path: String,
+    sample_mesh: Option<Entity>,
+    sample_count: usize,
+    status: String,
+}
+
+impl Default for PointCloudPanelState {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "native")]
+            path: String::new(),
+            sample_mesh: None,
+            sample_count: 50_000,
+            status: String::new(),
+        }
+    }
+}
+
+pub fn point_cloud_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut display: ResMut<PointCloudDisplay>,
+    mut state: Local<PointCloudPanelState>,
+    cloud_query: Query<(Entity, &PointCloud, Option<&Name>)>,
+    mesh_query: Query<(Entity, &CgarMeshData, &Transform, Option<&Name>)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mesh_label = |entity: Option<Entity>| -> String {
+        match entity.and_then(|e| mesh_query.get(e).ok()) {
+            Some((e, _, _, Some(name))) => format!("{} ({})", name, e),
+            Some((e, ..)) => format!("{}", e),
+            None => "None".to_string(),
+        }
+    };
+
+    egui::Window::new("Point Clouds").show(ctx, |ui| {
+        #[cfg(feature = "native")]
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut state.path);
+            if ui.button("Load XYZ/PLY").clicked() {
+                let path = Path::new(state.path.trim()).to_path_buf();
+                match read_point_cloud(&path) {
+                    Ok(cloud) => {
+                        let name = path
+                            .file_stem()
+                            .map(|stem| stem.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| "cloud".to_string());
+                        state.status = format!("Loaded {} points", cloud.len());
+                        spawn_point_cloud(&mut commands, name, cloud, Transform::default());
+                    }
+                    Err(err) => state.status = err,
+                }
+            }
+        });
+
+        for (entity, cloud, name) in &cloud_query {
+            ui.horizontal(|ui| {
+                let name = name.map_or_else(|| format!("{}", entity), |n| n.to_string());
+                ui.label(format!(
+                    "{}: {} points{}{}",
+                    name,
+                    cloud.len(),
+                    if cloud.normals.is_some() {
+                        ", normals"
+                    } else {
+                        ""
+                    },
+                    if cloud.colors.is_some() {
+                        ", colors"
+                    } else {
+                        ""
+                    },
+                ));
+                if ui.small_button("Remove").clicked() {
+                    commands.entity(entity).despawn();
+                }
+            });
+        }
+
+        ui.separator();
+        let mut edited = display.clone();
+        ui.add(
+            egui::Slider::new(&mut edited.size, 0.0002..=0.05)
+                .logarithmic(true)
+                .text("Point size"),
+        );
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut edited.color_mode, PointColorMode::File, "File");
+            ui.radio_value(&mut edited.color_mode, PointColorMode::Uniform, "Uniform");
+            ui.radio_value(&mut edited.color_mode, PointColorMode::Height, "Height");
+            ui.color_edit_button_rgb(&mut edited.color);
+        });
+        if edited != *display {
+            *display = edited;
+        }
+
+        ui.separator();
+        ui.label("Sample points on mesh surface");
+        egui::ComboBox::from_label("Mesh")
+            .selected_text(mesh_label(state.sample_mesh))
+            .show_ui(ui, |ui| {
+                for (entity, ..) in &mesh_query {
+                    ui.selectable_value(
+                        &mut state.sample_mesh,
+                        Some(entity),
+                        mesh_label(Some(entity)),
+                    );
+                }
+            });
+        ui.add(
+            egui::DragValue::new(&mut state.sample_count)
+                .range(1..=5_000_000)
+                .prefix("Count: "),
+        );
+        let source = state.sample_mesh.and_then(|e| mesh_query.get(e).ok());
+        let sample = ui
+            .add_enabled(source.is_some(), egui::Button::new("Sample"))
+            .clicked();
+        if let Some((entity, cgar_data, transform, name)) = source.filter(|_| sample) {
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            let cloud = sample_surface(&topology, state.sample_count, entity.to_bits());
+            let name = format!(
+                "{} samples",
+                name.map_or_else(|| format!("{}", entity), |n| n.to_string())
+            );
+            state.status = format!("Sampled {} points", cloud.len());
+            spawn_point_cloud(&mut commands, name, cloud, *transform);
+        }
+
+        if !state.status.is_empty() {
+            ui.label(&state.status);
+        }
+    });
+}
+
+pub struct ReconstructionJob {
+    // `None` when cancelled; the error is the status line to show
+    task: Task<Option<Result<(LoadedMesh, usize), String>>>,
+    progress: Arc<ReconstructionProgress>,
+    name: String,
+    transform: Transform,
+}
+
+#[derive(Resource)]
+pub struct SurfaceReconstruction {
+    pub source: Option<Entity>,
+    pub radius: f64,
+    // Used when the cloud carries no normals of its own
+    pub normal_neighbors: usize,
+    pub job: Option<ReconstructionJob>,
+    // Point spacing being estimated for a cloud, to default the radius
+    pub spacing: Option<(Entity, Task<f64>)>,
+    pub status: String,
+}
+
+impl Default for SurfaceReconstruction {
+    fn default() -> Self {
+        Self {
+            source: None,
+            radius: 0.0,
+            normal_neighbors: 12,
+            job: None,
+            spacing: None,
+            status: String::new(),
+        }
+    }
+}
+
+// Estimates `cloud`'s point spacing on the task pool; a large scan takes a
+// while to grid
+fn estimate_spacing_task(cloud: &PointCloud) -> Task<f64> {
+    let points = cloud.points.clone();
+    AsyncComputeTaskPool::get().spawn(async move { estimate_spacing(&points) })
+}
+
+// Drops points no triangle references and renumbers the triangles to match
+fn compact(points: &[DVec3], triangles: &[[usize; 3]]) -> (Vec<DVec3>, Vec<[usize; 3]>) {
+    let mut remap = vec![usize::MAX; points.len()];
+    let mut positions = Vec::new();
+    let triangles = triangles
+        .iter()
+        .map(|tri| {
+            tri.map(|v| {
+                if remap[v] == usize::MAX {
+                    remap[v] = positions.len();
+                    positions.push(points[v]);
+                }
+                remap[v]
+            })
+        })
+        .collect();
+    (positions, triangles)
+}
+
+pub fn reconstruction_panel(
+    mut contexts: EguiContexts,
+    mut reconstruction: ResMut<SurfaceReconstruction>,
+    cloud_query: Query<(Entity, &PointCloud, &Transform, Option<&Name>)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let cloud_label = |entity: Option<Entity>| -> String {
+        match entity.and_then(|e| cloud_query.get(e).ok()) {
+            Some((e, _, _, Some(name))) => format!("{} ({})", name, e),
+            Some((e, ..)) => format!("{}", e),
+            None => "None".to_string(),
+        }
+    };
+
+    egui::Window::new("Surface Reconstruction").show(ctx, |ui| {
+        if let Some(job) = &reconstruction.job {
+            ui.label(format!("Reconstructing {}...", job.name));
+            ui.add(egui::ProgressBar::new(job.progress.fraction()).show_percentage());
+            if ui.button("Cancel").clicked() {
+                job.progress.cancel.store(true, Ordering::Relaxed);
+            }
+            // Keep repainting so the bar advances without input events
+            ui.ctx().request_repaint();
+            return;
+        }
+
+        let mut source = reconstruction.source;
+        egui::ComboBox::from_label("Point cloud")
+            .selected_text(cloud_label(source))
+            .show_ui(ui, |ui| {
+                for (entity, ..) in &cloud_query {
+                    ui.selectable_value(&mut source, Some(entity), cloud_label(Some(entity)));
+                }
+            });
+        let changed = source != reconstruction.source;
+        if changed {
+            reconstruction.source = source;
+            reconstruction.radius = 0.0;
+            // Dropping the task cancels an estimate for the previous cloud
+            reconstruction.spacing = None;
+        }
+        let Some((entity, cloud, transform, name)) = source.and_then(|e| cloud_query.get(e).ok())
+        else {
+            return;
+        };
+
+        // Ball radius defaults to twice the average point spacing
+        if changed {
+            reconstruction.spacing = Some((entity, estimate_spacing_task(cloud)));
+        }
+        let estimating = reconstruction.spacing.is_some();
+        ui.horizontal(|ui| {
+            ui.label("Ball radius");
+            let speed = reconstruction.radius * 0.01;
+            ui.add(
+                egui::DragValue::new(&mut reconstruction.radius)
+                    .speed(speed)
+                    .range(0.0..=f64::MAX),
+            );
+            if ui
+                .add_enabled(!estimating, egui::Button::new("Estimate"))
+                .clicked()
+            {
+                reconstruction.spacing = Some((entity, estimate_spacing_task(cloud)));
+            }
+            if estimating {
+                ui.spinner();
+            }
+        });
+        if cloud.normals.is_none() {
+            ui.add(
+                egui::Slider::new(&mut reconstruction.normal_neighbors, 4..=64)
+                    .text("Normal estimation neighbors"),
+            );
+        }
+
+        if ui
+            .add_enabled(
+                reconstruction.radius > 0.0 && !estimating && cloud.len() >= 3,
+                egui::Button::new("Reconstruct (ball pivoting)"),
+            )
+            .clicked()
+        {
+            let points = cloud.points.clone();
+            let normals = cloud.normals.clone();
+            let radius = reconstruction.radius;
+            let neighbors = reconstruction.normal_neighbors;
+            let progress = Arc::new(ReconstructionProgress::default());
+            let task_progress = progress.clone();
+            let task = AsyncComputeTaskPool::get().spawn(async move {
+                let normals = normals.unwrap_or_else(|| estimate_normals(&points, neighbors));
+                let triangles = ball_pivot(&points, &normals, radius, &task_progress)?;
+                let (positions, triangles) = compact(&points, &triangles);
+                if triangles.is_empty() {
+                    return Some(Err("No triangles produced; try a larger radius".to_string()));
+                }
+                let count = triangles.len();
+                // Ball pivoting can leave non-manifold fans cgar would panic on
+                let soup = TriangleSoup {
+                    positions,
+                    triangles,
+                };
+                Some(
+                    load_soup_tolerant(&soup, 0, None)
+                        .map(|loaded| (loaded, count))
+                        .map_err(|err| format!("Reconstruction failed: {}", err)),
+                )
+            });
+            reconstruction.job = Some(ReconstructionJob {
+                task,
+                progress,
+                name: format!(
+                    "{} surface",
+                    name.map_or_else(|| "cloud".to_string(), |n| n.to_string())
+                ),
+                transform: *transform,
+            });
+            reconstruction.status.clear();
+        }
+
+        if !reconstruction.status.is_empty() {
+            ui.label(&reconstruction.status);
+        }
+    });
+}
+
+// Applies a finished spacing estimate, and spawns the reconstructed mesh once
+// the background task finishes
+pub fn poll_reconstruction(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Res<DefaultMeshMaterial>,
+    mut reconstruction: ResMut<SurfaceReconstruction>,
+) {
+    let estimated = reconstruction.spacing.as_mut().and_then(|(source, task)| {
+        block_on(future::poll_once(task)).map(|spacing| (*source, spacing))
+    });
+    if let Some((source, spacing)) = estimated {
+        reconstruction.spacing = None;
+        if reconstruction.source == Some(source) {
+            reconstruction.radius = 2.0 * spacing;
+        }
+    }
+
+    let Some(job) = reconstruction.job.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(future::poll_once(&mut job.task)) else {
+        return;
+    };
+    let job = reconstruction.job.take().expect("job checked above");
+
+    reconstruction.status = match result {
+        None => "Reconstruction cancelled".to_string(),
+        Some(Err(status)) => status,
+        Some(Ok((loaded, count))) => {
+            let entity = spawn_cgar_mesh(
+                &mut commands,
+                &mut meshes,
+                &material,
+                job.name.clone(),
+                loaded.mesh,
+                job.transform,
+            );
+            match loaded.issues {
+                Some(issues) => {
+                    let status = format!("{}: {} triangles; {}", job.name, count, issues.summary);
+                    commands.entity(entity).insert(issues);
+                    status
+                }
+                None => format!("{}: {} triangles", job.name, count),
+            }
+        }
+    };
+    info!("{}", reconstruction.status);
+}
@@ -0,0 +1,740 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::time::Duration;
+
+use bevy::{
+    color::Color,
+    ecs::{
+        change_detection::{DetectChanges, Ref},
+        entity::Entity,
+        name::Name,
+        query::With,
+        resource::Resource,
+        system::{Local, Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    input::{ButtonInput, keyboard::KeyCode, mouse::MouseButton},
+    log::{info, warn},
+    math::{Isometry3d, Vec3, primitives::InfinitePlane3d},
+    platform::time::Instant,
+    render::camera::Camera,
+    transform::components::{GlobalTransform, Transform},
+    window::{PrimaryWindow, Window},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::layers::{AnalysisLayerKind, AnalysisLayers};
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::mesh::conversion::{tri_vertices_of_face, vertex_position};
+use crate::mesh::edge::{PickSettings, cast_pick_ray};
+use crate::mesh::face_tree::{FaceHit, FaceSurface, FaceTreeCache, QueryShape};
+use crate::mesh::topology::MeshTopology;
+use crate::selection::components::SelectionSet;
+
+// Upper bound on vertices tested per direction in mesh-to-mesh mode
+const MAX_PROBE_SAMPLES: usize = 20000;
+// Outlines drawn for query results; the count in the panel stays exact
+const MAX_DRAWN_QUERY_FACES: usize = 20000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeMode {
+    // Closest point to the 3D cursor, which sits on the view plane through the orbit focus
+    Cursor,
+    // Closest pair of points between two mesh surfaces
+    Mesh,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProbeReading {
+    pub mesh: Entity,
+    pub face: usize,
+    pub from: Vec3,
+    pub to: Vec3,
+    pub distance: f64,
+}
+
+#[derive(Resource)]
+pub struct Probe {
+    pub enabled: bool,
+    pub mode: ProbeMode,
+    // Mesh whose surface is probed; `None` probes every mesh in cursor mode
+    pub target: Option<Entity>,
+    // Second mesh for mesh-to-mesh mode
+    pub other: Option<Entity>,
+    pub reading: Option<ProbeReading>,
+}
+
+impl Default for Probe {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: ProbeMode::Cursor,
+            target: None,
+            other: None,
+            reading: None,
+        }
+    }
+}
+
+pub fn toggle_probe(kb: Res<ButtonInput<KeyCode>>, mut probe: ResMut<Probe>) {
+    if kb.just_pressed(KeyCode::KeyP) {
+        probe.enabled = !probe.enabled;
+        info!("Probe: {}", probe.enabled);
+    }
+}
+
+// Mouse cursor projected onto the plane through `anchor` facing the camera
+pub fn cursor_on_view_plane(
+    window: &Window,
+    camera: &Camera,
+    camera_global: &GlobalTransform,
+    anchor: Vec3,
+) -> Option<Vec3> {
+    let ray = camera
+        .viewport_to_world(camera_global, window.cursor_position()?)
+        .ok()?;
+    let plane = InfinitePlane3d::new(camera_global.forward());
+    ray.intersect_plane(anchor, plane).map(|t| ray.get_point(t))
+}
+
+// Closest point on a mesh's surface to a world-space point, measured in world space
+fn closest_on_mesh(
+    surface: &FaceSurface,
+    global: &GlobalTransform,
+    world: Vec3,
+) -> Option<(usize, Vec3, f64)> {
+    let local = global.affine().inverse().transform_point3(world);
+    let hit = surface.closest_point(local.as_dvec3())?;
+    let to = global.transform_point(hit.point.as_vec3());
+    Some((hit.face, to, world.distance(to) as f64))
+}
+
+// Closest surface point on `target` over a subsample of `source`'s vertices
+fn closest_between(
+    source: &MeshTopology,
+    source_global: &GlobalTransform,
+    target: &FaceSurface,
+    target_global: &GlobalTransform,
+) -> Option<(usize, Vec3, Vec3, f64)> {
+    let used: Vec<usize> = source.used_vertices().collect();
+    let stride = used.len().div_ceil(MAX_PROBE_SAMPLES).max(1);
+    used.iter()
+        .step_by(stride)
+        .filter_map(|&v| {
+            let from = source_global.transform_point(source.positions[v].as_vec3());
+            let (face, to, distance) = closest_on_mesh(target, target_global, from)?;
+            Some((face, from, to, distance))
+        })
+        .min_by(|a, b| a.3.total_cmp(&b.3))
+}
+
+pub fn update_probe(
+    mut probe: ResMut<Probe>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform, &OrbitCamera), With<OrbitCamera>>,
+    mesh_query: Query<(
+        Entity,
+        Ref<GlobalTransform>,
+        &CgarMeshData,
+        Ref<FaceTreeCache>,
+    )>,
+) {
+    if !probe.enabled {
+        if probe.reading.is_some() {
+            probe.reading = None;
+        }
+        return;
+    }
+
+    match probe.mode {
+        ProbeMode::Cursor => {
+            let Ok(window) = windows.single() else {
+                return;
+            };
+            let Ok((camera, camera_global, orbit)) = camera_query.single() else {
+                return;
+            };
+            let cursor = cursor_on_view_plane(window, camera, camera_global, orbit.focus);
+            let Some(cursor) = cursor else {
+                probe.reading = None;
+                return;
+            };
+
+            let target = probe.target;
+            probe.reading = mesh_query
+                .iter()
+                .filter(|(entity, ..)| target.is_none_or(|t| t == *entity))
+                .filter_map(|(entity, global, _, cache)| {
+                    let (face, to, distance) = closest_on_mesh(&cache.0, &global, cursor)?;
+                    Some(ProbeReading {
+                        mesh: entity,
+                        face,
+                        from: cursor,
+                        to,
+                        distance,
+                    })
+                })
+                .min_by(|a, b| a.distance.total_cmp(&b.distance));
+        }
+        ProbeMode::Mesh => {
+            let (Some(target), Some(other)) = (probe.target, probe.other) else {
+                probe.reading = None;
+                return;
+            };
+            let (Ok(a), Ok(b)) = (mesh_query.get(target), mesh_query.get(other)) else {
+                probe.reading = None;
+                return;
+            };
+            if target == other {
+                probe.reading = None;
+                return;
+            }
+            let (_, a_global, a_data, a_cache) = a;
+            let (_, b_global, b_data, b_cache) = b;
+            // Only re-run the sweep when either mesh or the probe settings moved
+            let stale = probe.is_changed()
+                || a_global.is_changed()
+                || b_global.is_changed()
+                || a_cache.is_changed()
+                || b_cache.is_changed();
+            if !stale && probe.reading.is_some() {
+                return;
+            }
+
+            let a_topology = MeshTopology::from_cgar(&a_data.0);
+            let b_topology = MeshTopology::from_cgar(&b_data.0);
+            let forward = closest_between(&b_topology, &b_global, &a_cache.0, &a_global).map(
+                |(face, from, to, distance)| ProbeReading {
+                    mesh: target,
+                    face,
+                    from,
+                    to,
+                    distance,
+                },
+            );
+            let backward = closest_between(&a_topology, &a_global, &b_cache.0, &b_global).map(
+                |(face, from, to, distance)| ProbeReading {
+                    mesh: other,
+                    face,
+                    from,
+                    to,
+                    distance,
+                },
+            );
+            probe.reading = match (forward, backward) {
+                (Some(f), Some(b)) => Some(if f.distance <= b.distance { f } else { b }),
+                (f, b) => f.or(b),
+            };
+        }
+    }
+}
+
+pub fn probe_panel(
+    mut contexts: EguiContexts,
+    mut probe: ResMut<Probe>,
+    mesh_query: Query<(Entity, Option<&Name>), With<CgarMeshData>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let label = |entity: Option<Entity>, none: &str| -> String {
+        match entity.and_then(|e| mesh_query.get(e).ok()) {
+            Some((e, Some(name))) => format!("{} ({})", name, e),
+            Some((e, None)) => format!("{}", e),
+            None => none.to_string(),
+        }
+    };
+
+    egui::Window::new("Probe").show(ctx, |ui| {
+        let mut enabled = probe.enabled;
+        if ui.checkbox(&mut enabled, "Enabled (P)").changed() {
+            probe.enabled = enabled;
+        }
+
+        let mut mode = probe.mode;
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut mode, ProbeMode::Cursor, "3D cursor");
+            ui.radio_value(&mut mode, ProbeMode::Mesh, "Second mesh");
+        });
+        if mode != probe.mode {
+            probe.mode = mode;
+            probe.reading = None;
+        }
+
+        let none = if probe.mode == ProbeMode::Cursor {
+            "All meshes"
+        } else {
+            "None"
+        };
+        let mut target = probe.target;
+        egui::ComboBox::from_label("Mesh")
+            .selected_text(label(target, none))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut target, None, none);
+                for (entity, _) in &mesh_query {
+                    ui.selectable_value(&mut target, Some(entity), label(Some(entity), none));
+                }
+            });
+        if target != probe.target {
+            probe.target = target;
+        }
+
+        if probe.mode == ProbeMode::Mesh {
+            let mut other = probe.other;
+            egui::ComboBox::from_label("Against")
+                .selected_text(label(other, "None"))
+                .show_ui(ui, |ui| {
+                    for (entity, _) in &mesh_query {
+                        ui.selectable_value(&mut other, Some(entity), label(Some(entity), "None"));
+                    }
+                });
+            if other != probe.other {
+                probe.other = other;
+            }
+        }
+
+        ui.separator();
+        match probe.reading {
+            Some(reading) => {
+                ui.label(format!("Distance: {:.6}", reading.distance));
+                ui.label(format!(
+                    "Closest point: ({:.4}, {:.4}, {:.4}) on face {} of {}",
+                    reading.to.x,
+                    reading.to.y,
+                    reading.to.z,
+                    reading.face,
+                    label(Some(reading.mesh), "None")
+                ));
+            }
+            None if probe.enabled => {
+                ui.label("No reading");
+            }
+            None => {}
+        }
+    });
+}
+
+pub fn draw_probe(mut gizmos: Gizmos, probe: Res<Probe>) {
+    let Some(reading) = probe.reading else {
+        return;
+    };
+    let color = Color::srgb(0.2, 0.9, 1.0);
+    gizmos.line(reading.from, reading.to, color);
+    gizmos.sphere(Isometry3d::from_translation(reading.to), 0.015, color);
+    gizmos.sphere(
+        Isometry3d::from_translation(reading.from),
+        0.01,
+        Color::WHITE,
+    );
+}
+
+// Value of the topmost scalar layer at the surface point under the mouse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScalarReading {
+    pub kind: AnalysisLayerKind,
+    pub value: f64,
+    pub position: Vec3,
+}
+
+#[derive(Resource)]
+pub struct ScalarProbe {
+    pub enabled: bool,
+    pub reading: Option<ScalarReading>,
+}
+
+impl Default for ScalarProbe {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reading: None,
+        }
+    }
+}
+
+// Reads the scalar field on the nearest surface under the mouse, at the
+// weights of the same cgar ray cast clicks use
+pub fn update_scalar_probe(
+    mut scalar_probe: ResMut<ScalarProbe>,
+    pick_settings: Res<PickSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mesh_query: Query<(
+        &GlobalTransform,
+        &CgarMeshData,
+        &FaceTreeCache,
+        Option<&AnalysisLayers>,
+    )>,
+) {
+    let pointer = match (windows.single(), camera_query.single()) {
+        (Ok(window), Ok(camera)) if scalar_probe.enabled => {
+            window.cursor_position().map(|cursor| (camera, cursor))
+        }
+        _ => None,
+    };
+    let hit = pointer.and_then(|(camera, cursor)| {
+        let eye = camera.1.translation();
+        mesh_query
+            .iter()
+            .filter_map(|(global, cgar_data, cache, layers)| {
+                let hit = cast_pick_ray(
+                    camera,
+                    global,
+                    &cgar_data.0,
+                    &cache.0,
+                    cursor,
+                    None,
+                    &pick_settings,
+                )?;
+                let world =
+                    FaceHit::from_barycentric(&cgar_data.0, hit.face, hit.barycentric, global)?
+                        .world;
+                Some((world.distance(eye), hit, world, cgar_data, layers))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+    });
+    // The nearest surface may be a mesh without a field; it still hides
+    // the ones behind it
+    let reading = hit.and_then(|(_, hit, world, cgar_data, layers)| {
+        let (kind, field) = layers?.top_scalar()?;
+        let tri = tri_vertices_of_face(&cgar_data.0, hit.face);
+        let value = field.value_at(hit.face, tri, hit.barycentric.to_array())?;
+        Some(ScalarReading {
+            kind,
+            value,
+            position: world,
+        })
+    });
+    if scalar_probe.reading != reading {
+        scalar_probe.reading = reading;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryShapeKind {
+    Box,
+    Sphere,
+}
+
+// Box/sphere region query against a mesh's cgar face tree
+#[derive(Resource)]
+pub struct SpatialQueryTool {
+    pub enabled: bool,
+    pub target: Option<Entity>,
+    pub kind: QueryShapeKind,
+    // Shape placement in the target mesh's local space
+    pub center: Vec3,
+    pub half_extents: Vec3,
+    pub radius: f32,
+    pub contained: bool,
+    pub faces: Vec<usize>,
+    pub query_time: Option<Duration>,
+    pub status: String,
+}
+
+impl Default for SpatialQueryTool {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target: None,
+            kind: QueryShapeKind::Box,
+            center: Vec3::ZERO,
+            half_extents: Vec3::splat(0.25),
+            radius: 0.25,
+            contained: false,
+            faces: Vec::new(),
+            query_time: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl SpatialQueryTool {
+    fn shape(&self) -> QueryShape {
+        let center = self.center.as_dvec3();
+        match self.kind {
+            QueryShapeKind::Box => {
+                let half = self.half_extents.as_dvec3();
+                QueryShape::Aabb {
+                    min: center - half,
+                    max: center + half,
+                }
+            }
+            QueryShapeKind::Sphere => QueryShape::Sphere {
+                center,
+                radius: self.radius as f64,
+            },
+        }
+    }
+}
+
+// Middle-mouse drag moves the query shape across the view plane
+pub fn drag_query_shape(
+    mut tool: ResMut<SpatialQueryTool>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mesh_query: Query<&GlobalTransform, With<CgarMeshData>>,
+    mut grab: Local<Option<Vec3>>,
+) {
+    if !tool.enabled || !mouse_buttons.pressed(MouseButton::Middle) {
+        *grab = None;
+        return;
+    }
+    let Some(mesh_global) = tool.target.and_then(|e| mesh_query.get(e).ok()) else {
+        return;
+    };
+    let (Ok(window), Ok((camera, camera_global))) = (windows.single(), camera_query.single())
+    else {
+        return;
+    };
+
+    let world_center = mesh_global.transform_point(tool.center);
+    let Some(cursor) = cursor_on_view_plane(window, camera, camera_global, world_center) else {
+        return;
+    };
+    // Keep the offset between cursor and center from the moment the drag started
+    let offset = *grab.get_or_insert(world_center - cursor);
+    let local = mesh_global
+        .affine()
+        .inverse()
+        .transform_point3(cursor + offset);
+    if local != tool.center {
+        tool.center = local;
+    }
+}
+
+pub fn update_spatial_query(
+    mut tool: ResMut<SpatialQueryTool>,
+    mesh_query: Query<Ref<FaceTreeCache>>,
+) {
+    if !tool.enabled {
+        if !tool.faces.is_empty() {
+            tool.faces.clear();
+        }
+        return;
+    }
+    let Some(cache) = tool.target.and_then(|e| mesh_query.get(e).ok()) else {
+        return;
+    };
+    if !tool.is_changed() && !cache.is_changed() {
+        return;
+    }
+
+    let shape = tool.shape();
+    let start = Instant::now();
+    let faces = cache.0.query_faces(&shape, tool.contained);
+    tool.query_time = Some(start.elapsed());
+    tool.faces = faces;
+}
+
+pub fn spatial_query_panel(
+    mut contexts: EguiContexts,
+    mut tool: ResMut<SpatialQueryTool>,
+    mut selection: ResMut<SelectionSet>,
+    mesh_query: Query<(Entity, Option<&Name>, Option<&FaceTreeCache>), With<CgarMeshData>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let label = |entity: Option<Entity>| -> String {
+        match entity.and_then(|e| mesh_query.get(e).ok()) {
+            Some((e, Some(name), _)) => format!("{} ({})", name, e),
+            Some((e, None, _)) => format!("{}", e),
+            None => "None".to_string(),
+        }
+    };
+
+    egui::Window::new("Spatial Query").show(ctx, |ui| {
+        let mut enabled = tool.enabled;
+        if ui.checkbox(&mut enabled, "Enabled").changed() {
+            tool.enabled = enabled;
+        }
+
+        let mut target = tool.target;
+        egui::ComboBox::from_label("Mesh")
+            .selected_text(label(target))
+            .show_ui(ui, |ui| {
+                for (entity, ..) in &mesh_query {
+                    ui.selectable_value(&mut target, Some(entity), label(Some(entity)));
+                }
+            });
+        if target != tool.target {
+            tool.target = target;
+        }
+
+        let mut kind = tool.kind;
+        let mut contained = tool.contained;
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut kind, QueryShapeKind::Box, "Box");
+            ui.radio_value(&mut kind, QueryShapeKind::Sphere, "Sphere");
+            ui.checkbox(&mut contained, "Fully contained only");
+        });
+        if kind != tool.kind {
+            tool.kind = kind;
+        }
+        if contained != tool.contained {
+            tool.contained = contained;
+        }
+
+        let mut center = tool.center;
+        ui.horizontal(|ui| {
+            ui.label("Center");
+            ui.add(egui::DragValue::new(&mut center.x).speed(0.01));
+            ui.add(egui::DragValue::new(&mut center.y).speed(0.01));
+            ui.add(egui::DragValue::new(&mut center.z).speed(0.01));
+        });
+        if center != tool.center {
+            tool.center = center;
+        }
+        match tool.kind {
+            QueryShapeKind::Box => {
+                let mut half = tool.half_extents;
+                ui.horizontal(|ui| {
+                    ui.label("Half extents");
+                    for value in [&mut half.x, &mut half.y, &mut half.z] {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .speed(0.01)
+                                .range(0.0..=f32::MAX),
+                        );
+                    }
+                });
+                if half != tool.half_extents {
+                    tool.half_extents = half;
+                }
+            }
+            QueryShapeKind::Sphere => {
+                let mut radius = tool.radius;
+                ui.horizontal(|ui| {
+                    ui.label("Radius");
+                    ui.add(
+                        egui::DragValue::new(&mut radius)
+                            .speed(0.01)
+                            .range(0.0..=f32::MAX),
+                    );
+                });
+                if radius != tool.radius {
+                    tool.radius = radius;
+                }
+            }
+        }
+        ui.label("Middle-drag in the viewport to move the shape");
+
+        ui.separator();
+        ui.label(format!("Faces: {}", tool.faces.len()));
+        if let Some(time) = tool.query_time {
+            ui.label(format!("Query time: {:.1} µs", time.as_secs_f64() * 1e6));
+        }
+
+        let cache = tool
+            .target
+            .and_then(|e| mesh_query.get(e).ok())
+            .and_then(|(.., cache)| cache);
+        ui.horizontal(|ui| {
+            let verify = ui
+                .add_enabled(cache.is_some(), egui::Button::new("Verify (brute force)"))
+                .clicked();
+            if let Some(cache) = cache.filter(|_| verify) {
+                let shape = tool.shape();
+                let start = Instant::now();
+                let expected = cache.0.query_faces_brute_force(&shape, tool.contained);
+                let brute_time = start.elapsed();
+                tool.status = if expected == tool.faces {
+                    format!(
+                        "Tree matches brute force over {} faces ({:.1} µs)",
+                        cache.0.len(),
+                        brute_time.as_secs_f64() * 1e6
+                    )
+                } else {
+                    warn!(
+                        "Face tree query mismatch: tree {} faces, brute force {}",
+                        tool.faces.len(),
+                        expected.len()
+                    );
+                    format!(
+                        "MISMATCH: tree found {}, brute force found {}",
+                        tool.faces.len(),
+                        expected.len()
+                    )
+                };
+            }
+            if ui
+                .add_enabled(!tool.faces.is_empty(), egui::Button::new("Select faces"))
+                .clicked()
+            {
+                selection.clear();
+                selection.mesh = tool.target;
+                selection.faces = tool.faces.iter().copied().collect();
+            }
+        });
+        if !tool.status.is_empty() {
+            ui.label(&tool.status);
+        }
+    });
+}
+
+pub fn draw_spatial_query(
+    mut gizmos: Gizmos,
+    tool: Res<SpatialQueryTool>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+) {
+    if !tool.enabled {
+        return;
+    }
+    let Some((mesh_global, cgar_data)) = tool.target.and_then(|e| mesh_query.get(e).ok()) else {
+        return;
+    };
+    let shape_color = Color::srgb(0.9, 0.3, 1.0);
+    let (scale, rotation, _) = mesh_global.to_scale_rotation_translation();
+    let world_center = mesh_global.transform_point(tool.center);
+    match tool.kind {
+        QueryShapeKind::Box => {
+            gizmos.cuboid(
+                Transform::from_translation(world_center)
+                    .with_rotation(rotation)
+                    .with_scale(tool.half_extents * 2.0 * scale),
+                shape_color,
+            );
+        }
+        QueryShapeKind::Sphere => {
+            gizmos.sphere(
+                Isometry3d::new(world_center, rotation),
+                tool.radius * scale.max_element(),
+                shape_color,
+            );
+        }
+    }
+
+    let cgar_mesh = &cgar_data.0;
+    let face_color = Color::srgb(1.0, 0.9, 0.2);
+    let world = |v: usize| mesh_global.transform_point(vertex_position(cgar_mesh, v));
+    for &face in tool.faces.iter().take(MAX_DRAWN_QUERY_FACES) {
+        if cgar_mesh.faces.get(face).is_none_or(|f| f.removed) {
+            continue;
+        }
+        let [a, b, c] = tri_vertices_of_face(cgar_mesh, face);
+        gizmos.linestrip([world(a), world(b), world(c), world(a)], face_color);
+    }
+}
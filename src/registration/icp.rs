@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::{DAffine3, DQuat, DVec3};
+
+use crate::analysis::fitting::jacobi_eigen;
+use crate::mesh::face_tree::FaceSurface;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RigidTransform {
+    pub rotation: DQuat,
+    pub translation: DVec3,
+}
+
+impl RigidTransform {
+    pub const IDENTITY: Self = Self {
+        rotation: DQuat::IDENTITY,
+        translation: DVec3::ZERO,
+    };
+
+    pub fn apply(&self, p: DVec3) -> DVec3 {
+        self.rotation * p + self.translation
+    }
+
+    // `self` applied after `first`
+    pub fn after(&self, first: &RigidTransform) -> RigidTransform {
+        RigidTransform {
+            rotation: (self.rotation * first.rotation).normalize(),
+            translation: self.rotation * first.translation + self.translation,
+        }
+    }
+}
+
+// Least-squares rigid transform mapping each `pair.0` onto `pair.1`
+// (Horn's closed-form quaternion method).
+pub fn best_fit_rigid(pairs: &[(DVec3, DVec3)]) -> Option<RigidTransform> {
+    if pairs.len() < 3 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let src_center = pairs.iter().map(|&(p, _)| p).sum::<DVec3>() / n;
+    let dst_center = pairs.iter().map(|&(_, q)| q).sum::<DVec3>() / n;
+
+    let mut s = [[0.0; 3]; 3];
+    for &(p, q) in pairs {
+        let a = (p - src_center).to_array();
+        let b = (q - dst_center).to_array();
+        for (row, ai) in s.iter_mut().zip(a) {
+            for (cell, bj) in row.iter_mut().zip(b) {
+                *cell += ai * bj;
+            }
+        }
+    }
+    let [[sxx, sxy, sxz], [syx, syy, syz], [szx, szy, szz]] = s;
+    let n_matrix = [
+        [sxx + syy + szz, syz - szy, szx - sxz, sxy - syx],
+        [syz - szy, sxx - syy - szz, sxy + syx, szx + sxz],
+        [szx - sxz, sxy + syx, -sxx + syy - szz, syz + szy],
+        [sxy - syx, szx + sxz, syz + szy, -sxx - syy + szz],
+    ];
+    let (_, vectors) = jacobi_eigen(n_matrix);
+    // Largest eigenvalue is last; its eigenvector is the quaternion (w, x, y, z)
+    let rotation =
+        DQuat::from_xyzw(vectors[1][3], vectors[2][3], vectors[3][3], vectors[0][3]).normalize();
+    if !rotation.is_finite() {
+        return None;
+    }
+    Some(RigidTransform {
+        rotation,
+        translation: dst_center - rotation * src_center,
+    })
+}
+
+pub struct IcpResult {
+    // World-space correction to apply to the moving mesh
+    pub transform: RigidTransform,
+    pub rms: f64,
+    pub iterations: usize,
+}
+
+// Point-to-surface ICP. `moving` points are in world space; the fixed surface is
+// queried through cgar's face tree in its own local space.
+pub fn run_icp(
+    moving: &[DVec3],
+    fixed: &FaceSurface,
+    fixed_world_to_local: &DAffine3,
+    fixed_local_to_world: &DAffine3,
+    max_iterations: usize,
+) -> IcpResult {
+    let mut total = RigidTransform::IDENTITY;
+    let mut current: Vec<DVec3> = moving.to_vec();
+    let mut rms = f64::INFINITY;
+    let mut iterations = 0;
+
+    let correspondences = |points: &[DVec3]| -> Vec<(DVec3, DVec3, f64)> {
+        points
+            .iter()
+            .filter_map(|&p| {
+                let hit = fixed.closest_point(fixed_world_to_local.transform_point3(p))?;
+                let q = fixed_local_to_world.transform_point3(hit.point);
+                Some((p, q, p.distance(q)))
+            })
+            .collect()
+    };
+
+    while iterations < max_iterations {
+        let mut pairs = correspondences(&current);
+        if pairs.len() < 3 {
+            break;
+        }
+
+        // Reject pairs much farther apart than the typical match
+        let mut distances: Vec<f64> = pairs.iter().map(|&(_, _, d)| d).collect();
+        distances.sort_by(f64::total_cmp);
+        let median = distances[distances.len() / 2];
+        if median > 0.0 {
+            pairs.retain(|&(_, _, d)| d <= 3.0 * median);
+        }
+
+        let matched: Vec<(DVec3, DVec3)> = pairs.iter().map(|&(p, q, _)| (p, q)).collect();
+        let Some(step) = best_fit_rigid(&matched) else {
+            break;
+        };
+        iterations += 1;
+        for p in &mut current {
+            *p = step.apply(*p);
+        }
+        total = step.after(&total);
+
+        let new_rms = (matched
+            .iter()
+            .map(|&(p, q)| step.apply(p).distance_squared(q))
+            .sum::<f64>()
+            / matched.len() as f64)
+            .sqrt();
+        let converged = (rms - new_rms).abs() <= 1e-9 * rms.max(1.0);
+        rms = new_rms;
+        if converged {
+            break;
+        }
+    }
+
+    // Residual against fresh correspondences after the final step
+    let final_pairs = correspondences(&current);
+    if !final_pairs.is_empty() {
+        rms = (final_pairs.iter().map(|&(_, _, d)| d * d).sum::<f64>() / final_pairs.len() as f64)
+            .sqrt();
+    }
+
+    IcpResult {
+        transform: total,
+        rms,
+        iterations,
+    }
+}
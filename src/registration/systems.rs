@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    color::Color,
+    ecs::{
+        entity::Entity,
+        event::EventReader,
+        hierarchy::ChildOf,
+        name::Name,
+        query::With,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    log::info,
+    math::{DAffine3, DVec3, Isometry3d, Vec3},
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::CgarMeshData;
+use crate::mesh::edge::MeshPicked;
+use crate::mesh::face_tree::FaceSurface;
+use crate::mesh::topology::MeshTopology;
+use crate::registration::icp::{RigidTransform, best_fit_rigid, run_icp};
+
+// Upper bound on moving-mesh samples per ICP iteration
+const MAX_ICP_SAMPLES: usize = 5000;
+
+#[derive(Resource)]
+pub struct Registration {
+    pub fixed: Option<Entity>,
+    pub moving: Option<Entity>,
+    pub picking: bool,
+    // Correspondence picks in mesh-local space, so they follow the mesh as it moves
+    pub fixed_picks: Vec<Vec3>,
+    pub moving_picks: Vec<Vec3>,
+    pub max_iterations: usize,
+    pub status: String,
+}
+
+impl Default for Registration {
+    fn default() -> Self {
+        Self {
+            fixed: None,
+            moving: None,
+            picking: false,
+            fixed_picks: Vec::new(),
+            moving_picks: Vec::new(),
+            max_iterations: 30,
+            status: String::new(),
+        }
+    }
+}
+
+fn to_daffine(global: &GlobalTransform) -> DAffine3 {
+    let (scale, rotation, translation) = global.to_scale_rotation_translation();
+    DAffine3::from_scale_rotation_translation(
+        scale.as_dvec3(),
+        rotation.as_dquat(),
+        translation.as_dvec3(),
+    )
+}
+
+// Applies a world-space rigid correction to an entity at `global`, going
+// back through its parent's transform when it has one
+fn apply_rigid(
+    transform: &mut Transform,
+    global: &GlobalTransform,
+    parent: Option<&GlobalTransform>,
+    rigid: &RigidTransform,
+) {
+    let delta = Transform {
+        translation: rigid.translation.as_vec3(),
+        rotation: rigid.rotation.as_quat(),
+        ..Transform::IDENTITY
+    };
+    let moved = GlobalTransform::from(delta) * *global;
+    *transform = match parent {
+        Some(parent) => moved.reparented_to(parent),
+        None => moved.compute_transform(),
+    };
+}
+
+pub fn record_registration_picks(
+    mut registration: ResMut<Registration>,
+    mut picked: EventReader<MeshPicked>,
+    mesh_query: Query<&GlobalTransform, With<CgarMeshData>>,
+) {
+    for event in picked.read() {
+        if !registration.picking || event.double_click {
+            continue;
+        }
+        let (Some(world), Ok(global)) = (event.world_position, mesh_query.get(event.entity)) else {
+            continue;
+        };
+        let local = global.affine().inverse().transform_point3(world);
+        if Some(event.entity) == registration.fixed {
+            registration.fixed_picks.push(local);
+        } else if Some(event.entity) == registration.moving {
+            registration.moving_picks.push(local);
+        }
+    }
+}
+
+pub fn registration_panel(
+    mut contexts: EguiContexts,
+    mut registration: ResMut<Registration>,
+    mesh_query: Query<(Entity, &GlobalTransform, &CgarMeshData, Option<&Name>)>,
+    mut transform_query: Query<(&mut Transform, Option<&ChildOf>), With<CgarMeshData>>,
+    parent_query: Query<&GlobalTransform>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let label = |entity: Option<Entity>| -> String {
+        match entity.and_then(|e| mesh_query.get(e).ok()) {
+            Some((e, _, _, Some(name))) => format!("{} ({})", name, e),
+            Some((e, _, _, None)) => format!("{}", e),
+            None => "None".to_string(),
+        }
+    };
+
+    egui::Window::new("Registration").show(ctx, |ui| {
+        for (title, is_fixed) in [("Fixed", true), ("Moving", false)] {
+            let mut choice = if is_fixed {
+                registration.fixed
+            } else {
+                registration.moving
+            };
+            egui::ComboBox::from_label(title)
+                .selected_text(label(choice))
+                .show_ui(ui, |ui| {
+                    for (entity, ..) in &mesh_query {
+                        ui.selectable_value(&mut choice, Some(entity), label(Some(entity)));
+                    }
+                });
+            let current = if is_fixed {
+                registration.fixed
+            } else {
+                registration.moving
+            };
+            if choice != current {
+                if is_fixed {
+                    registration.fixed = choice;
+                    registration.fixed_picks.clear();
+                } else {
+                    registration.moving = choice;
+                    registration.moving_picks.clear();
+                }
+            }
+        }
+
+        let (Some(fixed), Some(moving)) = (registration.fixed, registration.moving) else {
+            ui.label("Choose a fixed and a moving mesh");
+            return;
+        };
+        if fixed == moving {
+            ui.label("Fixed and moving meshes must differ");
+            return;
+        }
+        let (Ok((_, fixed_global, fixed_data, _)), Ok((_, moving_global, moving_data, _))) =
+            (mesh_query.get(fixed), mesh_query.get(moving))
+        else {
+            return;
+        };
+
+        ui.separator();
+        let mut picking = registration.picking;
+        if ui
+            .checkbox(&mut picking, "Pick correspondences (click matching points)")
+            .changed()
+        {
+            registration.picking = picking;
+        }
+        ui.label(format!(
+            "Picks: {} fixed, {} moving",
+            registration.fixed_picks.len(),
+            registration.moving_picks.len()
+        ));
+
+        ui.horizontal(|ui| {
+            let pair_count = registration
+                .fixed_picks
+                .len()
+                .min(registration.moving_picks.len());
+            if ui
+                .add_enabled(pair_count >= 3, egui::Button::new("Rough align"))
+                .clicked()
+            {
+                let pairs: Vec<(DVec3, DVec3)> = registration
+                    .moving_picks
+                    .iter()
+                    .zip(&registration.fixed_picks)
+                    .map(|(&m, &f)| {
+                        (
+                            moving_global.transform_point(m).as_dvec3(),
+                            fixed_global.transform_point(f).as_dvec3(),
+                        )
+                    })
+                    .collect();
+                match best_fit_rigid(&pairs) {
+                    Some(rigid) => {
+                        if let Ok((mut transform, child_of)) = transform_query.get_mut(moving) {
+                            let parent = child_of.and_then(|c| parent_query.get(c.parent()).ok());
+                            apply_rigid(&mut transform, moving_global, parent, &rigid);
+                        }
+                        let residual = (pairs
+                            .iter()
+                            .map(|&(m, f)| rigid.apply(m).distance_squared(f))
+                            .sum::<f64>()
+                            / pairs.len() as f64)
+                            .sqrt();
+                        registration.status = format!("Rough alignment residual: {:.6}", residual);
+                    }
+                    None => registration.status = "Degenerate picks".to_string(),
+                }
+            }
+            if ui.button("Reset picks").clicked() {
+                registration.fixed_picks.clear();
+                registration.moving_picks.clear();
+            }
+        });
+
+        let mut max_iterations = registration.max_iterations;
+        if ui
+            .add(egui::Slider::new(&mut max_iterations, 1..=200).text("Max iterations"))
+            .changed()
+        {
+            registration.max_iterations = max_iterations;
+        }
+
+        if ui.button("Run ICP").clicked() {
+            let surface = FaceSurface::build(&fixed_data.0);
+            let fixed_to_world = to_daffine(fixed_global);
+            let moving_to_world = to_daffine(moving_global);
+
+            let moving_topology = MeshTopology::from_cgar(&moving_data.0);
+            let used: Vec<usize> = moving_topology.used_vertices().collect();
+            let stride = used.len().div_ceil(MAX_ICP_SAMPLES).max(1);
+            let samples: Vec<DVec3> = used
+                .iter()
+                .step_by(stride)
+                .map(|&v| moving_to_world.transform_point3(moving_topology.positions[v]))
+                .collect();
+
+            let result = run_icp(
+                &samples,
+                &surface,
+                &fixed_to_world.inverse(),
+                &fixed_to_world,
+                registration.max_iterations,
+            );
+            if let Ok((mut transform, child_of)) = transform_query.get_mut(moving) {
+                let parent = child_of.and_then(|c| parent_query.get(c.parent()).ok());
+                apply_rigid(&mut transform, moving_global, parent, &result.transform);
+            }
+            registration.status = format!(
+                "ICP: {} iterations, RMS residual {:.6}",
+                result.iterations, result.rms
+            );
+            info!("{}", registration.status);
+        }
+
+        if !registration.status.is_empty() {
+            ui.label(&registration.status);
+        }
+    });
+}
+
+pub fn draw_registration_picks(
+    mut gizmos: Gizmos,
+    registration: Res<Registration>,
+    mesh_query: Query<&GlobalTransform, With<CgarMeshData>>,
+) {
+    let world_picks = |entity: Option<Entity>, picks: &[Vec3]| -> Vec<Vec3> {
+        match entity.and_then(|e| mesh_query.get(e).ok()) {
+            Some(global) => picks.iter().map(|&p| global.transform_point(p)).collect(),
+            None => Vec::new(),
+        }
+    };
+    let fixed = world_picks(registration.fixed, &registration.fixed_picks);
+    let moving = world_picks(registration.moving, &registration.moving_picks);
+
+    for &p in &fixed {
+        gizmos.sphere(
+            Isometry3d::from_translation(p),
+            0.02,
+            Color::srgb(0.2, 1.0, 0.4),
+        );
+    }
+    for &p in &moving {
+        gizmos.sphere(
+            Isometry3d::from_translation(p),
+            0.02,
+            Color::srgb(1.0, 0.6, 0.1),
+        );
+    }
+    for (&m, &f) in moving.iter().zip(&fixed) {
+        gizmos.line(m, f, Color::srgba(1.0, 1.0, 1.0, 0.5));
+    }
+}
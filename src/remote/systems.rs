@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::app::{App, Plugin};
+#[cfg(not(feature = "remote"))]
+use bevy::log::warn;
+
+use crate::utils::cli::CliOptions;
+
+// `--remote` serves viewer commands as JSON-RPC over HTTP on localhost,
+// through Bevy's remote protocol, for end-to-end tests and remote demos:
+//
+//   cgar/command    {"line": "<command line>"}
+//   cgar/load       {"path": "<file.obj>"}
+//   cgar/run        {"operation": "decimate" | "fill-holes", "limit": <n>}
+//   cgar/screenshot {"path": "<file.png>"}
+//   cgar/pick       {"x": <px>, "y": <px>}
+//   cgar/stats
+pub struct RemoteControlPlugin {
+    pub port: Option<u16>,
+}
+
+impl RemoteControlPlugin {
+    pub fn from_options(options: &CliOptions) -> Self {
+        Self {
+            port: options.remote_port,
+        }
+    }
+}
+
+impl Plugin for RemoteControlPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(port) = self.port else {
+            return;
+        };
+        #[cfg(feature = "remote")]
+        methods::install(app, port);
+        #[cfg(not(feature = "remote"))]
+        {
+            let _ = (app, port);
+            warn!("--remote needs a build with the `remote` feature");
+        }
+    }
+}
+
+#[cfg(feature = "remote")]
+mod methods {
+    use std::sync::atomic::Ordering;
+
+    use bevy::{
+        app::App,
+        ecs::{
+            entity::Entity,
+            name::Name,
+            query::With,
+            system::{In, Query, Res},
+            world::World,
+        },
+        log::info,
+        math::Vec2,
+        remote::{BrpError, BrpResult, RemotePlugin, error_codes, http::RemoteHttpPlugin},
+        render::{camera::Camera, view::InheritedVisibility},
+        transform::components::GlobalTransform,
+    };
+    use serde_json::{Value, json};
+
+    use crate::camera::components::{CgarMeshData, OrbitCamera};
+    use crate::command::systems::{CommandLine, run_commands};
+    use crate::context_menu::systems::{MeshElement, pick_element};
+    use crate::mesh::edge::PickSettings;
+    use crate::mesh::face_tree::{FaceSurface, FaceTreeCache};
+    use crate::mesh::topology::MeshTopology;
+    use crate::selection::components::SelectionSet;
+    use crate::stats::systems::MeshFingerprints;
+
+    pub fn install(app: &mut App, port: u16) {
+        app.add_plugins((
+            RemotePlugin::default()
+                .with_method("cgar/command", command)
+                .with_method("cgar/load", load)
+                .with_method("cgar/run", run)
+                .with_method("cgar/screenshot", screenshot)
+                .with_method("cgar/pick", pick)
+                .with_method("cgar/stats", stats),
+            RemoteHttpPlugin::default().with_port(port),
+        ));
+        info!("Remote control listening on 127.0.0.1:{}", port);
+    }
+
+    fn error(code: i16, message: impl Into<String>) -> BrpError {
+        BrpError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn param<'a>(params: &'a Option<Value>, key: &str) -> Result<&'a Value, BrpError> {
+        params
+            .as_ref()
+            .and_then(|params| params.get(key))
+            .ok_or_else(|| error(error_codes::INVALID_PARAMS, format!("missing `{}`", key)))
+    }
+
+    fn str_param<'a>(params: &'a Option<Value>, key: &str) -> Result<&'a str, BrpError> {
+        param(params, key)?.as_str().ok_or_else(|| {
+            error(
+                error_codes::INVALID_PARAMS,
+                format!("`{}` must be a string", key),
+            )
+        })
+    }
+
+    // Runs one line through the command line right away and returns its message
+    fn run_line(world: &mut World, line: String) -> BrpResult {
+        world.resource_mut::<CommandLine>().pending = Some(line);
+        world
+            .run_system_cached(run_commands)
+            .map_err(|err| error(error_codes::INTERNAL_ERROR, err.to_string()))?;
+        match world.resource::<CommandLine>().history.last() {
+            Some((_, Ok(message))) => Ok(json!({ "message": message })),
+            Some((_, Err(err))) => Err(error(error_codes::INTERNAL_ERROR, err.clone())),
+            None => Err(error(
+                error_codes::INTERNAL_ERROR,
+                "the command left no result",
+            )),
+        }
+    }
+
+    fn command(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+        let line = str_param(&params, "line")?.to_string();
+        run_line(world, line)
+    }
+
+    fn load(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+        let path = str_param(&params, "path")?;
+        run_line(world, format!("load {}", path))
+    }
+
+    fn run(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+        let operation = str_param(&params, "operation")?;
+        if !matches!(operation, "decimate" | "fill-holes") {
+            return Err(error(
+                error_codes::INVALID_PARAMS,
+                "`operation` must be decimate or fill-holes",
+            ));
+        }
+        let limit = param(&params, "limit")?.as_u64().ok_or_else(|| {
+            error(
+                error_codes::INVALID_PARAMS,
+                "`limit` must be a whole number",
+            )
+        })?;
+        run_line(world, format!("{} {}", operation, limit))
+    }
+
+    // The image is written a few frames later; `cgar/stats` reports pending captures
+    fn screenshot(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+        let path = str_param(&params, "path")?;
+        run_line(world, format!("screenshot {}", path))
+    }
+
+    // Nearest element under a window position in logical pixels, across
+    // every visible mesh
+    fn pick(
+        In(params): In<Option<Value>>,
+        pick_settings: Res<PickSettings>,
+        camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+        mesh_query: Query<(
+            Entity,
+            &GlobalTransform,
+            &CgarMeshData,
+            Option<&FaceTreeCache>,
+            &InheritedVisibility,
+        )>,
+    ) -> BrpResult {
+        let coordinate = |key| {
+            param(&params, key)?.as_f64().ok_or_else(|| {
+                error(
+                    error_codes::INVALID_PARAMS,
+                    format!("`{}` must be a number", key),
+                )
+            })
+        };
+        let cursor = Vec2::new(coordinate("x")? as f32, coordinate("y")? as f32);
+        let (camera, camera_global) = camera_query
+            .single()
+            .map_err(|err| error(error_codes::INTERNAL_ERROR, err.to_string()))?;
+
+        let mut best = None;
+        for (entity, mesh_global, cgar_data, cache, visibility) in &mesh_query {
+            if !visibility.get() {
+                continue;
+            }
+            let built;
+            let surface = match cache {
+                Some(cache) => &cache.0,
+                None => {
+                    built = FaceSurface::build(&cgar_data.0);
+                    &built
+                }
+            };
+            let Some((element, hit)) = pick_element(
+                (camera, camera_global),
+                mesh_global,
+                &cgar_data.0,
+                surface,
+                cursor,
+                None,
+                &pick_settings,
+            ) else {
+                continue;
+            };
+            let distance = camera_global.translation().distance(hit.world);
+            if best.is_none_or(|(nearest, ..)| distance < nearest) {
+                best = Some((distance, entity, element, hit));
+            }
+        }
+
+        let Some((distance, entity, element, hit)) = best else {
+            return Ok(Value::Null);
+        };
+        let (kind, ids) = match element {
+            MeshElement::Vertex(v) => ("vertex", vec![v]),
+            MeshElement::Edge(v0, v1) => ("edge", vec![v0, v1]),
+            MeshElement::Face(f) => ("face", vec![f]),
+        };
+        Ok(json!({
+            "entity": entity.to_bits(),
+            "element": kind,
+            "ids": ids,
+            "point": hit.world.to_array(),
+            "barycentric": hit.barycentric.to_array(),
+            "distance": distance,
+        }))
+    }
+
+    fn stats(
+        In(_): In<Option<Value>>,
+        selection: Res<SelectionSet>,
+        command_line: Res<CommandLine>,
+        fingerprints: Res<MeshFingerprints>,
+        mesh_query: Query<(Entity, Option<&Name>, &CgarMeshData)>,
+    ) -> BrpResult {
+        let meshes: Vec<Value> = mesh_query
+            .iter()
+            .map(|(entity, name, cgar_data)| {
+                let topology = MeshTopology::from_cgar(&cgar_data.0);
+                json!({
+                    "entity": entity.to_bits(),
+                    "name": name.map(|name| name.to_string()),
+                    "vertices": topology.used_vertices().count(),
+                    "faces": topology.live_faces().count(),
+                    "edges": topology.edge_faces.len(),
+                    // Only when computed since the mesh last changed
+                    "fingerprint": fingerprints.0.get(&entity).map(|fingerprint| json!({
+                        "geometry": format!("{:016x}", fingerprint.geometry),
+                        "connectivity": format!("{:016x}", fingerprint.connectivity),
+                    })),
+                })
+            })
+            .collect();
+        Ok(json!({
+            "meshes": meshes,
+            "selection": {
+                "mesh": selection.mesh.map(Entity::to_bits),
+                "vertices": selection.vertices.len(),
+                "edges": selection.edges.len(),
+                "faces": selection.faces.len(),
+            },
+            "pending_screenshots": command_line.captures.load(Ordering::Relaxed),
+        }))
+    }
+}
@@ -0,0 +1,260 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    color::Color,
+    ecs::{
+        change_detection::DetectChanges,
+        entity::Entity,
+        name::Name,
+        query::With,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+        world::Ref,
+    },
+    gizmos::gizmos::Gizmos,
+    input::{ButtonInput, keyboard::KeyCode},
+    math::DVec3,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::mesh::conversion::vertex_position;
+use crate::mesh::highlight::{HighlightKind, HighlightStyle};
+use crate::mesh::topology::MeshTopology;
+use crate::repair::ops::{TriangleSoup, boundary_loops};
+use crate::selection::components::SelectionSet;
+
+// Loops are framed this many times their radius away, so the rim around
+// the hole is in view too
+const FRAME_MARGIN: f64 = 3.0;
+
+pub struct HoleLoop {
+    pub vertices: Vec<usize>,
+    // Perimeter, in mesh units
+    pub length: f64,
+    pub center: DVec3,
+    // Farthest loop vertex from `center`
+    pub radius: f64,
+}
+
+impl HoleLoop {
+    fn new(topology: &MeshTopology, vertices: Vec<usize>) -> Self {
+        let points: Vec<DVec3> = vertices.iter().map(|&v| topology.positions[v]).collect();
+        let length = (0..points.len())
+            .map(|k| points[k].distance(points[(k + 1) % points.len()]))
+            .sum();
+        let center = points.iter().sum::<DVec3>() / points.len() as f64;
+        let radius = points
+            .iter()
+            .map(|p| p.distance(center))
+            .fold(0.0, f64::max);
+        Self {
+            vertices,
+            length,
+            center,
+            radius,
+        }
+    }
+}
+
+// Steps through a mesh's boundary loops one at a time, framing each, so every
+// hole in a scan gets looked at. Loops are found again whenever the mesh
+// changes and are ordered longest first.
+#[derive(Resource, Default)]
+pub struct HoleNavigator {
+    pub mesh: Option<Entity>,
+    pub loops: Vec<HoleLoop>,
+    pub current: Option<usize>,
+    // Set by the panel; applied with the camera in `navigate_holes`
+    pub detect: Option<Entity>,
+    pub step: isize,
+}
+
+impl HoleNavigator {
+    fn refresh(&mut self, topology: &MeshTopology) {
+        let soup = TriangleSoup::from_topology(topology);
+        self.loops = boundary_loops(&soup)
+            .into_iter()
+            .map(|chain| HoleLoop::new(topology, chain))
+            .collect();
+        self.loops.sort_by(|a, b| b.length.total_cmp(&a.length));
+        self.current = self
+            .current
+            .filter(|_| !self.loops.is_empty())
+            .map(|i| i.min(self.loops.len() - 1));
+    }
+}
+
+// `[` and `]` step to the previous and next loop
+pub fn navigate_holes(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut navigator: ResMut<HoleNavigator>,
+    mesh_query: Query<(Ref<CgarMeshData>, &GlobalTransform)>,
+    mut camera_query: Query<(&mut Transform, &mut OrbitCamera), With<OrbitCamera>>,
+) {
+    let detect = navigator.detect.take();
+    if let Some(mesh) = detect {
+        navigator.mesh = Some(mesh);
+        navigator.current = None;
+    }
+    let Some(mesh) = navigator.mesh else {
+        return;
+    };
+    let Ok((cgar_data, mesh_global)) = mesh_query.get(mesh) else {
+        navigator.mesh = None;
+        navigator.loops.clear();
+        navigator.current = None;
+        return;
+    };
+    if detect.is_some() || cgar_data.is_changed() {
+        navigator.refresh(&MeshTopology::from_cgar(&cgar_data.0));
+    }
+
+    let mut step = std::mem::take(&mut navigator.step);
+    if kb.just_pressed(KeyCode::BracketLeft) {
+        step -= 1;
+    }
+    if kb.just_pressed(KeyCode::BracketRight) {
+        step += 1;
+    }
+    let count = navigator.loops.len() as isize;
+    if step == 0 || count == 0 {
+        return;
+    }
+    // Wraps around; the first step forward lands on the first loop
+    let current = match navigator.current {
+        Some(current) => (current as isize + step).rem_euclid(count),
+        None if step > 0 => (step - 1).rem_euclid(count),
+        None => step.rem_euclid(count),
+    } as usize;
+    navigator.current = Some(current);
+
+    let Ok((mut transform, mut orbit)) = camera_query.single_mut() else {
+        return;
+    };
+    let hole = &navigator.loops[current];
+    let center = mesh_global.transform_point(hole.center.as_vec3());
+    let rim = mesh_global.transform_point((hole.center + DVec3::X * hole.radius).as_vec3());
+    let radius = (center.distance(rim) * FRAME_MARGIN as f32).max(1e-3);
+    let back = transform.back();
+    transform.translation = center + back * radius;
+    orbit.focus = center;
+    orbit.radius = radius;
+    orbit.focus_target = None;
+}
+
+pub fn draw_hole_loops(
+    mut gizmos: Gizmos,
+    navigator: Res<HoleNavigator>,
+    style: Res<HighlightStyle>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+) {
+    let Some((mesh_global, cgar_data)) = navigator.mesh.and_then(|mesh| mesh_query.get(mesh).ok())
+    else {
+        return;
+    };
+    let Some(hole) = navigator.current.and_then(|i| navigator.loops.get(i)) else {
+        return;
+    };
+    // Loops go stale for a frame after an edit
+    if hole
+        .vertices
+        .iter()
+        .any(|&v| v >= cgar_data.0.vertices.len())
+    {
+        return;
+    }
+    let [r, g, b] = style.get(HighlightKind::Boundary).color;
+    let world: Vec<_> = hole
+        .vertices
+        .iter()
+        .map(|&v| mesh_global.transform_point(vertex_position(&cgar_data.0, v)))
+        .collect();
+    gizmos.linestrip(
+        world.iter().copied().chain(world.first().copied()),
+        Color::srgb(r, g, b),
+    );
+}
+
+pub fn hole_navigator_panel(
+    mut contexts: EguiContexts,
+    mut navigator: ResMut<HoleNavigator>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<(Entity, Option<&Name>), With<CgarMeshData>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Holes")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let target = selection
+                .mesh
+                .filter(|e| mesh_query.contains(*e))
+                .or_else(|| mesh_query.iter().next().map(|(entity, _)| entity));
+            let Some(target) = target else {
+                ui.label("No mesh loaded");
+                return;
+            };
+            if navigator.mesh != Some(target) {
+                if ui.button("Find holes").clicked() {
+                    navigator.detect = Some(target);
+                }
+                return;
+            }
+            if let Ok((entity, name)) = mesh_query.get(target) {
+                match name {
+                    Some(name) => ui.label(format!("Mesh: {} ({})", name, entity)),
+                    None => ui.label(format!("Mesh: {}", entity)),
+                };
+            }
+            let count = navigator.loops.len();
+            if count == 0 {
+                ui.label("No holes: the mesh is closed");
+                return;
+            }
+            ui.horizontal(|ui| {
+                if ui.button("< Previous").on_hover_text("[").clicked() {
+                    navigator.step -= 1;
+                }
+                if ui.button("Next >").on_hover_text("]").clicked() {
+                    navigator.step += 1;
+                }
+            });
+            match navigator
+                .current
+                .and_then(|i| Some((i, navigator.loops.get(i)?)))
+            {
+                Some((i, hole)) => {
+                    ui.label(format!("Hole {} of {}", i + 1, count));
+                    ui.label(format!("{} vertices", hole.vertices.len()));
+                    ui.label(format!("Length {:.6}", hole.length));
+                }
+                None => {
+                    ui.label(format!("{} holes; press ] to visit the first", count));
+                }
+            }
+        });
+}
@@ -0,0 +1,611 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bevy::math::DVec3;
+
+use crate::mesh::spatial::PointGrid;
+use crate::mesh::topology::MeshTopology;
+
+// Plain positions + triangles that repair steps rewrite freely before the
+// result is turned back into a cgar mesh
+#[derive(Debug, Clone, Default)]
+pub struct TriangleSoup {
+    pub positions: Vec<DVec3>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl TriangleSoup {
+    pub fn from_topology(topology: &MeshTopology) -> Self {
+        Self {
+            positions: topology.positions.clone(),
+            triangles: topology.live_faces().map(|(_, tri)| tri).collect(),
+        }
+    }
+
+    fn corners(&self, tri: [usize; 3]) -> [DVec3; 3] {
+        tri.map(|v| self.positions[v])
+    }
+
+    // Drops vertices no triangle uses and renumbers the rest, also returning
+    // each old vertex's new id
+    pub fn compacted(&self) -> (Self, Vec<Option<usize>>) {
+        let mut remap: Vec<Option<usize>> = vec![None; self.positions.len()];
+        let mut positions = Vec::new();
+        let triangles = self
+            .triangles
+            .iter()
+            .map(|tri| {
+                tri.map(|v| {
+                    *remap[v].get_or_insert_with(|| {
+                        positions.push(self.positions[v]);
+                        positions.len() - 1
+                    })
+                })
+            })
+            .collect();
+        (
+            Self {
+                positions,
+                triangles,
+            },
+            remap,
+        )
+    }
+
+    // One compacted soup per edge-connected component, largest first
+    pub fn split_components(&self) -> Vec<Self> {
+        let mut components = self.components();
+        components.sort_by_key(|faces| std::cmp::Reverse(faces.len()));
+        components
+            .into_iter()
+            .map(|faces| {
+                let part = Self {
+                    positions: self.positions.clone(),
+                    triangles: faces.into_iter().map(|f| self.triangles[f]).collect(),
+                };
+                part.compacted().0
+            })
+            .collect()
+    }
+
+    // Face indices grouped into edge-connected components
+    fn components(&self) -> Vec<Vec<usize>> {
+        let adjacency = self.face_adjacency();
+        let mut component = vec![usize::MAX; self.triangles.len()];
+        let mut components = Vec::new();
+        for seed in 0..self.triangles.len() {
+            if component[seed] != usize::MAX {
+                continue;
+            }
+            let id = components.len();
+            let mut faces = vec![seed];
+            component[seed] = id;
+            let mut queue = VecDeque::from([seed]);
+            while let Some(f) = queue.pop_front() {
+                for &(g, _) in &adjacency[f] {
+                    if component[g] == usize::MAX {
+                        component[g] = id;
+                        faces.push(g);
+                        queue.push_back(g);
+                    }
+                }
+            }
+            components.push(faces);
+        }
+        components
+    }
+
+    // For each face, its neighbors across shared edges and whether the shared
+    // edge runs the same direction in both (an orientation conflict)
+    fn face_adjacency(&self) -> Vec<Vec<(usize, bool)>> {
+        let mut edge_faces: HashMap<(usize, usize), Vec<(usize, bool)>> = HashMap::new();
+        for (f, tri) in self.triangles.iter().enumerate() {
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                edge_faces
+                    .entry((a.min(b), a.max(b)))
+                    .or_default()
+                    .push((f, a < b));
+            }
+        }
+        let mut adjacency = vec![Vec::new(); self.triangles.len()];
+        for faces in edge_faces.values() {
+            for (i, &(f, f_forward)) in faces.iter().enumerate() {
+                for &(g, g_forward) in &faces[i + 1..] {
+                    adjacency[f].push((g, f_forward == g_forward));
+                    adjacency[g].push((f, f_forward == g_forward));
+                }
+            }
+        }
+        adjacency
+    }
+}
+
+// What a step would change, for previewing before it is accepted
+#[derive(Debug, Clone, Default)]
+pub struct RepairMarkers {
+    pub points: Vec<DVec3>,
+    pub faces: Vec<[DVec3; 3]>,
+    pub loops: Vec<Vec<DVec3>>,
+}
+
+pub struct RepairOutcome {
+    pub soup: TriangleSoup,
+    // New id of each input vertex, `None` where it was dropped
+    pub vertex_map: Vec<Option<usize>>,
+    pub summary: String,
+    pub markers: RepairMarkers,
+}
+
+// Union-find root of `v`, halving the path on the way
+pub fn find(parent: &mut [usize], mut v: usize) -> usize {
+    while parent[v] != v {
+        parent[v] = parent[parent[v]];
+        v = parent[v];
+    }
+    v
+}
+
+// Representative vertex for every vertex, merging those closer than `tolerance`
+fn weld_roots(positions: &[DVec3], tolerance: f64) -> Vec<usize> {
+    let mut parent: Vec<usize> = (0..positions.len()).collect();
+    let grid = PointGrid::new(
+        positions.iter().copied().enumerate().collect(),
+        tolerance.max(f64::EPSILON) * 2.0,
+    );
+    for (v, &p) in positions.iter().enumerate() {
+        for (u, _) in grid.within(p, tolerance) {
+            let (rv, ru) = (find(&mut parent, v), find(&mut parent, u));
+            if rv != ru {
+                parent[rv.max(ru)] = rv.min(ru);
+            }
+        }
+    }
+    (0..positions.len()).map(|v| find(&mut parent, v)).collect()
+}
+
+fn merged_markers(positions: &[DVec3], roots: &[usize]) -> RepairMarkers {
+    RepairMarkers {
+        points: roots
+            .iter()
+            .enumerate()
+            .filter(|&(v, &root)| root != v)
+            .map(|(v, _)| positions[v])
+            .collect(),
+        ..Default::default()
+    }
+}
+
+// Merges vertices closer than `tolerance`; faces that collapse as a result are dropped
+pub fn weld_vertices(soup: &TriangleSoup, tolerance: f64) -> RepairOutcome {
+    let roots = weld_roots(&soup.positions, tolerance);
+    let markers = merged_markers(&soup.positions, &roots);
+    let mut dropped = 0;
+    let triangles: Vec<[usize; 3]> = soup
+        .triangles
+        .iter()
+        .filter_map(|tri| {
+            let [a, b, c] = tri.map(|v| roots[v]);
+            if a == b || b == c || c == a {
+                dropped += 1;
+                None
+            } else {
+                Some([a, b, c])
+            }
+        })
+        .collect();
+
+    let (soup, remap) = TriangleSoup {
+        positions: soup.positions.clone(),
+        triangles,
+    }
+    .compacted();
+    RepairOutcome {
+        soup,
+        vertex_map: roots.iter().map(|&root| remap[root]).collect(),
+        summary: format!(
+            "Merge {} vertices; {} faces collapse and are dropped",
+            markers.points.len(),
+            dropped
+        ),
+        markers,
+    }
+}
+
+// `weld_vertices` on a whole mesh, also returning the new id of each cgar face
+// (`None` if it collapsed) so selections can follow the weld
+pub fn merge_by_distance(
+    topology: &MeshTopology,
+    tolerance: f64,
+) -> (RepairOutcome, Vec<Option<usize>>) {
+    let roots = weld_roots(&topology.positions, tolerance);
+    let markers = merged_markers(&topology.positions, &roots);
+    let mut faces = vec![None; topology.triangles.len()];
+    let mut triangles = Vec::new();
+    let mut dropped = 0;
+    for (f, tri) in topology.live_faces() {
+        let [a, b, c] = tri.map(|v| roots[v]);
+        if a == b || b == c || c == a {
+            dropped += 1;
+        } else {
+            faces[f] = Some(triangles.len());
+            triangles.push([a, b, c]);
+        }
+    }
+    let (soup, remap) = TriangleSoup {
+        positions: topology.positions.clone(),
+        triangles,
+    }
+    .compacted();
+    let outcome = RepairOutcome {
+        soup,
+        vertex_map: roots.iter().map(|&root| remap[root]).collect(),
+        summary: format!(
+            "Merge {} vertices; {} faces collapse and are dropped",
+            markers.points.len(),
+            dropped
+        ),
+        markers,
+    };
+    (outcome, faces)
+}
+
+// Drops faces with repeated corners or (near-)zero area, and faces using the
+// same three vertices as an earlier face regardless of winding
+pub fn remove_degenerate_faces(soup: &TriangleSoup, min_area: f64) -> RepairOutcome {
+    let mut seen: HashSet<[usize; 3]> = HashSet::new();
+    let mut markers = RepairMarkers::default();
+    let (mut degenerate, mut duplicate) = (0, 0);
+    let triangles: Vec<[usize; 3]> = soup
+        .triangles
+        .iter()
+        .copied()
+        .filter(|&tri| {
+            let [a, b, c] = soup.corners(tri);
+            if tri[0] == tri[1]
+                || tri[1] == tri[2]
+                || tri[2] == tri[0]
+                || (b - a).cross(c - a).length() * 0.5 <= min_area
+            {
+                degenerate += 1;
+                markers.faces.push([a, b, c]);
+                return false;
+            }
+            let mut key = tri;
+            key.sort_unstable();
+            if !seen.insert(key) {
+                duplicate += 1;
+                markers.faces.push([a, b, c]);
+                return false;
+            }
+            true
+        })
+        .collect();
+
+    let (soup, vertex_map) = TriangleSoup {
+        positions: soup.positions.clone(),
+        triangles,
+    }
+    .compacted();
+    RepairOutcome {
+        soup,
+        vertex_map,
+        summary: format!(
+            "Remove {} degenerate and {} duplicate faces",
+            degenerate, duplicate
+        ),
+        markers,
+    }
+}
+
+// Flips faces so neighbors agree on winding, then turns each closed
+// component outward (positive signed volume)
+pub fn unify_orientation(soup: &TriangleSoup) -> RepairOutcome {
+    let adjacency = soup.face_adjacency();
+    let mut flip: Vec<Option<bool>> = vec![None; soup.triangles.len()];
+    let mut conflicts = 0;
+
+    for component in soup.components() {
+        let seed = component[0];
+        flip[seed] = Some(false);
+        let mut queue = VecDeque::from([seed]);
+        while let Some(f) = queue.pop_front() {
+            let f_flip = flip[f].unwrap_or(false);
+            for &(g, same_direction) in &adjacency[f] {
+                // Neighbors must traverse the shared edge in opposite directions
+                let wanted = f_flip ^ same_direction;
+                match flip[g] {
+                    None => {
+                        flip[g] = Some(wanted);
+                        queue.push_back(g);
+                    }
+                    Some(existing) if existing != wanted => conflicts += 1,
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let volume: f64 = component
+            .iter()
+            .map(|&f| {
+                let [a, b, c] = soup.corners(soup.triangles[f]);
+                let v = a.dot(b.cross(c)) / 6.0;
+                if flip[f] == Some(true) { -v } else { v }
+            })
+            .sum();
+        if volume < 0.0 {
+            for &f in &component {
+                flip[f] = flip[f].map(|x| !x);
+            }
+        }
+    }
+
+    let mut markers = RepairMarkers::default();
+    let triangles = soup
+        .triangles
+        .iter()
+        .zip(&flip)
+        .map(|(&[a, b, c], flip)| {
+            if *flip == Some(true) {
+                markers.faces.push(soup.corners([a, b, c]));
+                [a, c, b]
+            } else {
+                [a, b, c]
+            }
+        })
+        .collect();
+
+    let mut summary = format!("Flip {} faces", markers.faces.len());
+    if conflicts > 0 {
+        // Non-orientable or non-manifold spots can't be made consistent
+        summary += &format!(" ({} edges still inconsistent)", conflicts / 2);
+    }
+    RepairOutcome {
+        soup: TriangleSoup {
+            positions: soup.positions.clone(),
+            triangles,
+        },
+        vertex_map: (0..soup.positions.len()).map(Some).collect(),
+        summary,
+        markers,
+    }
+}
+
+// Boundary loops as vertex chains, following each face's winding
+pub fn boundary_loops(soup: &TriangleSoup) -> Vec<Vec<usize>> {
+    let mut directed: HashSet<(usize, usize)> = HashSet::new();
+    for tri in &soup.triangles {
+        for k in 0..3 {
+            directed.insert((tri[k], tri[(k + 1) % 3]));
+        }
+    }
+    let mut next: HashMap<usize, usize> = HashMap::new();
+    let mut branching = HashSet::new();
+    for &(a, b) in &directed {
+        if !directed.contains(&(b, a)) && next.insert(a, b).is_some() {
+            branching.insert(a);
+        }
+    }
+
+    let mut starts: Vec<usize> = next.keys().copied().collect();
+    starts.sort_unstable();
+    let mut visited = HashSet::new();
+    let mut loops = Vec::new();
+    for start in starts {
+        if visited.contains(&start) || branching.contains(&start) {
+            continue;
+        }
+        let mut chain = vec![start];
+        let mut current = start;
+        visited.insert(start);
+        let mut closed = false;
+        while let Some(&to) = next.get(&current) {
+            if to == start {
+                closed = true;
+                break;
+            }
+            if !visited.insert(to) || branching.contains(&to) {
+                break;
+            }
+            chain.push(to);
+            current = to;
+        }
+        if closed && chain.len() >= 3 {
+            loops.push(chain);
+        }
+    }
+    loops
+}
+
+// Closes one boundary loop with a fan around its centroid. The center is
+// appended, so existing vertices keep their ids.
+pub fn fill_loop(soup: &mut TriangleSoup, chain: &[usize]) {
+    if chain.len() == 3 {
+        // Boundary runs a->b inside the mesh, so the patch must run b->a
+        soup.triangles.push([chain[2], chain[1], chain[0]]);
+        return;
+    }
+    let center = soup.positions.len();
+    let centroid = chain.iter().map(|&v| soup.positions[v]).sum::<DVec3>() / chain.len() as f64;
+    soup.positions.push(centroid);
+    for (i, &a) in chain.iter().enumerate() {
+        let b = chain[(i + 1) % chain.len()];
+        soup.triangles.push([b, a, center]);
+    }
+}
+
+// Closes boundary loops of at most `max_edges` edges with a fan around their centroid
+pub fn fill_holes(soup: &TriangleSoup, max_edges: usize) -> RepairOutcome {
+    let mut result = soup.clone();
+    let mut markers = RepairMarkers::default();
+    let loops = boundary_loops(soup);
+    let mut filled = 0;
+    for chain in loops.iter().filter(|chain| chain.len() <= max_edges) {
+        markers
+            .loops
+            .push(chain.iter().map(|&v| soup.positions[v]).collect());
+        filled += 1;
+        fill_loop(&mut result, chain);
+    }
+
+    RepairOutcome {
+        soup: result,
+        // Fan centers are appended, so existing vertices keep their ids
+        vertex_map: (0..soup.positions.len()).map(Some).collect(),
+        summary: format!(
+            "Fill {} of {} holes (up to {} edges)",
+            filled,
+            loops.len(),
+            max_edges
+        ),
+        markers,
+    }
+}
+
+// Deletes connected pieces with fewer than `min_faces` faces
+pub fn remove_small_components(soup: &TriangleSoup, min_faces: usize) -> RepairOutcome {
+    let components = soup.components();
+    let mut keep = vec![true; soup.triangles.len()];
+    let mut markers = RepairMarkers::default();
+    let mut removed = 0;
+    for component in components.iter().filter(|c| c.len() < min_faces) {
+        removed += 1;
+        for &f in component {
+            keep[f] = false;
+            markers.faces.push(soup.corners(soup.triangles[f]));
+        }
+    }
+    let triangles = soup
+        .triangles
+        .iter()
+        .zip(&keep)
+        .filter(|(_, keep)| **keep)
+        .map(|(&tri, _)| tri)
+        .collect();
+
+    let (soup, vertex_map) = TriangleSoup {
+        positions: soup.positions.clone(),
+        triangles,
+    }
+    .compacted();
+    RepairOutcome {
+        soup,
+        vertex_map,
+        summary: format!(
+            "Delete {} of {} components ({} faces)",
+            removed,
+            components.len(),
+            markers.faces.len()
+        ),
+        markers,
+    }
+}
+
+// Makes a soup safe for the half-edge mesh. Each edge keeps at most one pair
+// of oppositely wound faces; any other faces on it are cut loose by giving
+// them their own copies of the edge's vertices, and fans that only touch at a
+// vertex get separate vertices too. Faces still repeating a directed edge
+// afterwards are dropped. Copies are appended, so existing ids stay valid.
+pub fn split_non_manifold(soup: &TriangleSoup) -> RepairOutcome {
+    let corner_count = soup.triangles.len() * 3;
+    // Undirected edge -> corners (at min vertex, at max vertex), split by winding
+    let mut edge_uses: HashMap<(usize, usize), [Vec<(usize, usize)>; 2]> = HashMap::new();
+    for (t, tri) in soup.triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (ca, cb) = (t * 3 + k, t * 3 + (k + 1) % 3);
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            let (uses, corners) = if a < b { (0, (ca, cb)) } else { (1, (cb, ca)) };
+            edge_uses.entry((a.min(b), a.max(b))).or_default()[uses].push(corners);
+        }
+    }
+
+    let mut markers = RepairMarkers::default();
+    let mut parent: Vec<usize> = (0..corner_count).collect();
+    for (&(a, b), [forward, backward]) in &edge_uses {
+        // More than one face winding the same way means more than one sheet
+        if forward.len() > 1 || backward.len() > 1 {
+            markers
+                .loops
+                .push(vec![soup.positions[a], soup.positions[b]]);
+        }
+        if let (Some(f), Some(g)) = (forward.first(), backward.first()) {
+            for (c0, c1) in [(f.0, g.0), (f.1, g.1)] {
+                let (r0, r1) = (find(&mut parent, c0), find(&mut parent, c1));
+                parent[r0] = r1;
+            }
+        }
+    }
+
+    // One vertex per connected corner group; the first group keeps the id
+    let mut positions = soup.positions.clone();
+    let mut group_vertex: HashMap<usize, usize> = HashMap::new();
+    let mut claimed: HashSet<usize> = HashSet::new();
+    let mut split = 0;
+    let mut triangles: Vec<[usize; 3]> = Vec::with_capacity(soup.triangles.len());
+    for (t, tri) in soup.triangles.iter().enumerate() {
+        let mut new_tri = *tri;
+        for (k, v) in new_tri.iter_mut().enumerate() {
+            let root = find(&mut parent, t * 3 + k);
+            *v = *group_vertex.entry(root).or_insert_with(|| {
+                if claimed.insert(*v) {
+                    *v
+                } else {
+                    split += 1;
+                    markers.points.push(soup.positions[*v]);
+                    positions.push(soup.positions[*v]);
+                    positions.len() - 1
+                }
+            });
+        }
+        triangles.push(new_tri);
+    }
+
+    let mut directed: HashSet<(usize, usize)> = HashSet::new();
+    let mut dropped = 0;
+    triangles.retain(|tri| {
+        let edges = [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])];
+        if edges.iter().any(|edge| directed.contains(edge)) {
+            dropped += 1;
+            markers.faces.push(tri.map(|v| positions[v]));
+            return false;
+        }
+        directed.extend(edges);
+        true
+    });
+
+    RepairOutcome {
+        vertex_map: (0..soup.positions.len()).map(Some).collect(),
+        soup: TriangleSoup {
+            positions,
+            triangles,
+        },
+        summary: format!(
+            "Split {} non-manifold edges and {} vertices; drop {} faces that still overlap",
+            markers.loops.len(),
+            split,
+            dropped
+        ),
+        markers,
+    }
+}
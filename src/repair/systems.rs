@@ -0,0 +1,549 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    asset::Assets,
+    color::Color,
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        name::Name,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    log::info,
+    math::{DVec3, Isometry3d},
+    render::mesh::{Mesh, Mesh3d},
+    transform::components::GlobalTransform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::CgarMeshData;
+use crate::mesh::conversion::build_cgar_mesh;
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::repair::ops::{
+    RepairMarkers, RepairOutcome, TriangleSoup, fill_holes, merge_by_distance,
+    remove_degenerate_faces, remove_small_components, unify_orientation, weld_vertices,
+};
+use crate::selection::components::SelectionSet;
+use crate::session::presets::{OperationPresets, PresetParams, preset_section};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairStep {
+    Weld,
+    Degenerate,
+    Orientation,
+    Holes,
+    Components,
+}
+
+impl RepairStep {
+    pub const ALL: [RepairStep; 5] = [
+        RepairStep::Weld,
+        RepairStep::Degenerate,
+        RepairStep::Orientation,
+        RepairStep::Holes,
+        RepairStep::Components,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RepairStep::Weld => "Weld duplicate vertices",
+            RepairStep::Degenerate => "Remove degenerate/duplicate faces",
+            RepairStep::Orientation => "Unify orientation",
+            RepairStep::Holes => "Fill small holes",
+            RepairStep::Components => "Delete tiny components",
+        }
+    }
+
+    fn next(self) -> Option<RepairStep> {
+        let index = RepairStep::ALL.iter().position(|&s| s == self)?;
+        RepairStep::ALL.get(index + 1).copied()
+    }
+}
+
+#[derive(Resource)]
+pub struct RepairWizard {
+    pub mesh: Option<Entity>,
+    // Current step; `None` before starting and after the last step
+    pub step: Option<RepairStep>,
+    pub weld_tolerance: f64,
+    pub min_face_area: f64,
+    pub max_hole_edges: usize,
+    pub min_component_faces: usize,
+    pub preview: Option<RepairOutcome>,
+    pub log: Vec<String>,
+}
+
+impl Default for RepairWizard {
+    fn default() -> Self {
+        Self {
+            mesh: None,
+            step: None,
+            weld_tolerance: 1e-6,
+            min_face_area: 1e-12,
+            max_hole_edges: 16,
+            min_component_faces: 10,
+            preview: None,
+            log: Vec::new(),
+        }
+    }
+}
+
+impl RepairWizard {
+    fn run_step(&self, step: RepairStep, soup: &TriangleSoup) -> RepairOutcome {
+        match step {
+            RepairStep::Weld => weld_vertices(soup, self.weld_tolerance),
+            RepairStep::Degenerate => remove_degenerate_faces(soup, self.min_face_area),
+            RepairStep::Orientation => unify_orientation(soup),
+            RepairStep::Holes => fill_holes(soup, self.max_hole_edges),
+            RepairStep::Components => remove_small_components(soup, self.min_component_faces),
+        }
+    }
+
+    fn advance(&mut self) {
+        self.preview = None;
+        self.step = self.step.and_then(RepairStep::next);
+        if self.step.is_none() {
+            self.log.push("Repair finished".to_string());
+        }
+    }
+}
+
+fn drag_value(ui: &mut egui::Ui, label: &str, value: &mut f64) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.add(
+            egui::DragValue::new(value)
+                .speed(*value * 0.1 + f64::EPSILON)
+                .range(0.0..=f64::MAX),
+        );
+    });
+}
+
+pub fn repair_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut wizard: ResMut<RepairWizard>,
+    mut selection: ResMut<SelectionSet>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh_query: Query<(
+        Entity,
+        &Mesh3d,
+        &mut CgarMeshData,
+        Option<&mut FeatureEdges>,
+        Option<&mut ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Repair").show(ctx, |ui| {
+        let Some(step) = wizard.step else {
+            if ui.button("Start repair").clicked() {
+                wizard.mesh = selection
+                    .mesh
+                    .filter(|e| mesh_query.contains(*e))
+                    .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+                if wizard.mesh.is_some() {
+                    wizard.step = Some(RepairStep::ALL[0]);
+                    wizard.preview = None;
+                    wizard.log.clear();
+                }
+            }
+            for line in &wizard.log {
+                ui.label(line);
+            }
+            return;
+        };
+        let Some(entity) = wizard.mesh.filter(|e| mesh_query.contains(*e)) else {
+            wizard.step = None;
+            return;
+        };
+
+        let index = RepairStep::ALL.iter().position(|&s| s == step).unwrap_or(0);
+        ui.heading(format!(
+            "Step {}/{}: {}",
+            index + 1,
+            RepairStep::ALL.len(),
+            step.label()
+        ));
+
+        let before = (
+            wizard.weld_tolerance,
+            wizard.min_face_area,
+            wizard.max_hole_edges,
+            wizard.min_component_faces,
+        );
+        match step {
+            RepairStep::Weld => drag_value(ui, "Tolerance", &mut wizard.weld_tolerance),
+            RepairStep::Degenerate => {
+                drag_value(ui, "Minimum face area", &mut wizard.min_face_area)
+            }
+            RepairStep::Orientation => {
+                ui.label("Makes neighboring faces agree and closed parts face outward");
+            }
+            RepairStep::Holes => {
+                ui.add(
+                    egui::Slider::new(&mut wizard.max_hole_edges, 3..=256).text("Max hole edges"),
+                );
+            }
+            RepairStep::Components => {
+                ui.add(
+                    egui::Slider::new(&mut wizard.min_component_faces, 1..=10_000)
+                        .logarithmic(true)
+                        .text("Min component faces"),
+                );
+            }
+        }
+        let after = (
+            wizard.weld_tolerance,
+            wizard.min_face_area,
+            wizard.max_hole_edges,
+            wizard.min_component_faces,
+        );
+        // A stale preview would accept results for the old parameters
+        if before != after {
+            wizard.preview = None;
+        }
+
+        let mut accept = false;
+        ui.horizontal(|ui| {
+            let preview = ui.button("Preview").clicked();
+            if let Some((_, _, cgar_data, ..)) = mesh_query.get(entity).ok().filter(|_| preview) {
+                let soup = TriangleSoup::from_topology(&MeshTopology::from_cgar(&cgar_data.0));
+                wizard.preview = Some(wizard.run_step(step, &soup));
+            }
+            accept = ui
+                .add_enabled(wizard.preview.is_some(), egui::Button::new("Accept"))
+                .clicked();
+            if ui.button("Skip").clicked() {
+                wizard.log.push(format!("Skipped: {}", step.label()));
+                wizard.advance();
+            }
+            if ui.button("Stop").clicked() {
+                wizard.step = None;
+                wizard.preview = None;
+            }
+        });
+
+        if let Some(preview) = &wizard.preview {
+            ui.label(&preview.summary);
+        }
+
+        let accepted = if accept { wizard.preview.take() } else { None };
+        if let (
+            Some(preview),
+            Ok((_, mesh_handle, mut cgar_data, mut features, mut normals, settings)),
+        ) = (accepted, mesh_query.get_mut(entity))
+        {
+            cgar_data.0 = build_cgar_mesh(
+                &preview.soup.positions,
+                preview.soup.triangles.iter().copied(),
+            );
+            if let Some(features) = features.as_mut() {
+                features.remap(&preview.vertex_map);
+            }
+            if let Some(normals) = normals.as_mut() {
+                normals.remap(&preview.vertex_map);
+            }
+            meshes.insert(
+                &mesh_handle.0,
+                render_mesh(
+                    &cgar_data.0,
+                    None,
+                    features.as_deref(),
+                    normals.as_deref(),
+                    settings,
+                ),
+            );
+            commands.entity(entity).remove::<FaceColorOverlay>();
+            // Face and vertex ids no longer mean the same thing
+            if selection.mesh == Some(entity) {
+                selection.clear();
+            }
+            info!("Repair: {}", preview.summary);
+            wizard
+                .log
+                .push(format!("{}: {}", step.label(), preview.summary));
+            wizard.advance();
+        }
+
+        if !wizard.log.is_empty() {
+            ui.separator();
+            for line in &wizard.log {
+                ui.label(line);
+            }
+        }
+    });
+}
+
+fn draw_markers(gizmos: &mut Gizmos, markers: &RepairMarkers, mesh_global: &GlobalTransform) {
+    let world = |p: DVec3| mesh_global.transform_point(p.as_vec3());
+    let color = Color::srgb(1.0, 0.2, 0.6);
+
+    for &p in &markers.points {
+        gizmos.sphere(Isometry3d::from_translation(world(p)), 0.01, color);
+    }
+    for [a, b, c] in &markers.faces {
+        gizmos.linestrip([world(*a), world(*b), world(*c), world(*a)], color);
+    }
+    for points in &markers.loops {
+        let mut strip: Vec<_> = points.iter().map(|&p| world(p)).collect();
+        if let Some(&first) = strip.first() {
+            strip.push(first);
+        }
+        gizmos.linestrip(strip, color);
+    }
+}
+
+// Defects the tolerant importer cleaned up, kept on the mesh so they can be
+// inspected after loading
+#[derive(Component, Debug, Clone)]
+pub struct ImportIssues {
+    pub summary: String,
+    pub markers: RepairMarkers,
+    pub show: bool,
+}
+
+pub fn import_issues_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut issues_query: Query<(Entity, &mut ImportIssues, Option<&Name>)>,
+) {
+    if issues_query.is_empty() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Import Issues").show(ctx, |ui| {
+        for (entity, mut issues, name) in &mut issues_query {
+            let label = match name {
+                Some(name) => format!("{} ({})", name, entity),
+                None => format!("{}", entity),
+            };
+            ui.heading(label);
+            ui.label(&issues.summary);
+            ui.horizontal(|ui| {
+                let mut show = issues.show;
+                if ui.checkbox(&mut show, "Show markers").changed() {
+                    issues.show = show;
+                }
+                if ui.button("Dismiss").clicked() {
+                    commands.entity(entity).remove::<ImportIssues>();
+                }
+            });
+            ui.separator();
+        }
+    });
+}
+
+pub fn draw_import_issues(
+    mut gizmos: Gizmos,
+    issues_query: Query<(&ImportIssues, &GlobalTransform)>,
+) {
+    for (issues, mesh_global) in &issues_query {
+        if issues.show {
+            draw_markers(&mut gizmos, &issues.markers, mesh_global);
+        }
+    }
+}
+
+pub fn draw_repair_preview(
+    mut gizmos: Gizmos,
+    wizard: Res<RepairWizard>,
+    merge: Res<MergeByDistance>,
+    mesh_query: Query<&GlobalTransform>,
+) {
+    let global = |entity: Option<Entity>| entity.and_then(|e| mesh_query.get(e).ok());
+    if let Some((preview, mesh_global)) = wizard.preview.as_ref().zip(global(wizard.mesh)) {
+        draw_markers(&mut gizmos, &preview.markers, mesh_global);
+    }
+    let merge_mesh = merge
+        .key
+        .map(|(entity, _)| entity)
+        .filter(|_| merge.show_preview);
+    if let Some(((preview, _), mesh_global)) = merge.preview.as_ref().zip(global(merge_mesh)) {
+        draw_markers(&mut gizmos, &preview.markers, mesh_global);
+    }
+}
+
+// Standalone weld of vertices closer than a tolerance, with a live count of
+// what would be merged
+#[derive(Resource)]
+pub struct MergeByDistance {
+    pub tolerance: f64,
+    pub show_preview: bool,
+    // Mesh and tolerance the preview was computed for
+    pub key: Option<(Entity, f64)>,
+    // Outcome plus the new id of each cgar face
+    pub preview: Option<(RepairOutcome, Vec<Option<usize>>)>,
+}
+
+impl Default for MergeByDistance {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-5,
+            show_preview: true,
+            key: None,
+            preview: None,
+        }
+    }
+}
+
+// Merge by Distance settings saved as a named preset
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct MergePreset {
+    tolerance: f64,
+}
+
+impl Default for MergePreset {
+    fn default() -> Self {
+        Self {
+            tolerance: MergeByDistance::default().tolerance,
+        }
+    }
+}
+
+impl PresetParams for MergePreset {
+    const OPERATION: &'static str = "merge_by_distance";
+}
+
+pub fn merge_by_distance_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut merge: ResMut<MergeByDistance>,
+    mut presets: ResMut<OperationPresets>,
+    mut selection: ResMut<SelectionSet>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh_query: Query<(
+        Entity,
+        &Mesh3d,
+        &mut CgarMeshData,
+        Option<&mut FeatureEdges>,
+        Option<&mut ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let target = selection
+        .mesh
+        .filter(|e| mesh_query.contains(*e))
+        .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+
+    egui::Window::new("Merge by Distance").show(ctx, |ui| {
+        let Some((entity, mesh_handle, mut cgar_data, mut features, mut normals, settings)) =
+            target.and_then(|e| mesh_query.get_mut(e).ok())
+        else {
+            ui.label("No mesh loaded");
+            return;
+        };
+
+        let mut tolerance = merge.tolerance;
+        ui.add(
+            egui::Slider::new(&mut tolerance, 1e-8..=1.0)
+                .logarithmic(true)
+                .text("Tolerance"),
+        );
+        if tolerance != merge.tolerance {
+            merge.tolerance = tolerance;
+        }
+        let current = MergePreset {
+            tolerance: merge.tolerance,
+        };
+        if let Some(preset) = preset_section(ui, &mut presets, &current) {
+            merge.tolerance = preset.tolerance;
+        }
+        let mut show_preview = merge.show_preview;
+        ui.checkbox(&mut show_preview, "Show merged vertices");
+        if show_preview != merge.show_preview {
+            merge.show_preview = show_preview;
+        }
+
+        let key = Some((entity, merge.tolerance));
+        if merge.key != key || cgar_data.is_changed() || merge.preview.is_none() {
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            merge.preview = Some(merge_by_distance(&topology, merge.tolerance));
+            merge.key = key;
+        }
+
+        let mut apply = false;
+        if let Some((preview, _)) = &merge.preview {
+            ui.label(format!(
+                "{} -> {} vertices",
+                cgar_data.0.vertices.len(),
+                preview.soup.positions.len()
+            ));
+            ui.label(&preview.summary);
+            apply = ui
+                .add_enabled(
+                    !preview.markers.points.is_empty(),
+                    egui::Button::new("Merge"),
+                )
+                .clicked();
+        }
+
+        let applied = if apply { merge.preview.take() } else { None };
+        if let Some((preview, face_map)) = applied {
+            cgar_data.0 = build_cgar_mesh(
+                &preview.soup.positions,
+                preview.soup.triangles.iter().copied(),
+            );
+            if let Some(features) = features.as_mut() {
+                features.remap(&preview.vertex_map);
+            }
+            if let Some(normals) = normals.as_mut() {
+                normals.remap(&preview.vertex_map);
+            }
+            meshes.insert(
+                &mesh_handle.0,
+                render_mesh(
+                    &cgar_data.0,
+                    None,
+                    features.as_deref(),
+                    normals.as_deref(),
+                    settings,
+                ),
+            );
+            commands.entity(entity).remove::<FaceColorOverlay>();
+            if selection.mesh == Some(entity) {
+                selection.remap(&preview.vertex_map, &face_map);
+            }
+            info!("Merge by distance: {}", preview.summary);
+            merge.key = None;
+        }
+    });
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use bevy::math::DVec3;
+
+use crate::mesh::topology::MeshTopology;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrushKind {
+    // Pulls vertices toward the average of their neighbors
+    #[default]
+    Smooth,
+    // Pushes vertices out along their normals
+    Inflate,
+    // Projects vertices onto the average plane under the brush
+    Flatten,
+}
+
+impl BrushKind {
+    pub const ALL: [BrushKind; 3] = [BrushKind::Smooth, BrushKind::Inflate, BrushKind::Flatten];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BrushKind::Smooth => "Smooth",
+            BrushKind::Inflate => "Inflate",
+            BrushKind::Flatten => "Flatten",
+        }
+    }
+}
+
+// How an effect fades from the center of a brush to its rim
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Falloff {
+    #[default]
+    Smooth,
+    Linear,
+    Sharp,
+}
+
+impl Falloff {
+    pub const ALL: [Falloff; 3] = [Falloff::Smooth, Falloff::Linear, Falloff::Sharp];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Falloff::Smooth => "Smooth",
+            Falloff::Linear => "Linear",
+            Falloff::Sharp => "Sharp",
+        }
+    }
+
+    // Weight at `t` = distance / radius: 1 at the center, 0 at the rim and beyond
+    pub fn weight(self, t: f64) -> f64 {
+        let s = 1.0 - t.clamp(0.0, 1.0);
+        match self {
+            Falloff::Smooth => s * s * (3.0 - 2.0 * s),
+            Falloff::Linear => s,
+            Falloff::Sharp => s * s,
+        }
+    }
+}
+
+// One application of `kind` to the vertices of `topology` within `radius` of
+// `center`, scaled by `amount` in [0, 1]. `locked` vertices stay put.
+// Returns the vertices that moved.
+pub fn apply_brush(
+    topology: &mut MeshTopology,
+    kind: BrushKind,
+    falloff: Falloff,
+    center: DVec3,
+    radius: f64,
+    amount: f64,
+    locked: &BTreeSet<usize>,
+) -> BTreeSet<usize> {
+    let weights: Vec<(usize, f64)> = topology
+        .used_vertices()
+        .filter(|v| !locked.contains(v))
+        .filter_map(|v| {
+            let distance = topology.positions[v].distance(center);
+            let weight = amount * falloff.weight(distance / radius);
+            (distance < radius && weight > 0.0).then_some((v, weight))
+        })
+        .collect();
+    if weights.is_empty() {
+        return BTreeSet::new();
+    }
+
+    // Targets are computed from the positions before this dab so the result
+    // doesn't depend on vertex order
+    let plane = match kind {
+        BrushKind::Flatten => {
+            let total: f64 = weights.iter().map(|(_, w)| w).sum();
+            let origin = weights
+                .iter()
+                .map(|&(v, w)| topology.positions[v] * w)
+                .sum::<DVec3>()
+                / total;
+            let normal = weights
+                .iter()
+                .map(|&(v, w)| topology.vertex_normal(v) * w)
+                .sum::<DVec3>()
+                .normalize_or_zero();
+            Some((origin, normal))
+        }
+        _ => None,
+    };
+    let moves: Vec<(usize, DVec3)> = weights
+        .iter()
+        .map(|&(v, w)| {
+            let p = topology.positions[v];
+            let delta = match (kind, plane) {
+                (BrushKind::Smooth, _) => {
+                    let neighbors = &topology.vertex_neighbors[v];
+                    if neighbors.is_empty() {
+                        DVec3::ZERO
+                    } else {
+                        let average = neighbors
+                            .iter()
+                            .map(|&n| topology.positions[n])
+                            .sum::<DVec3>()
+                            / neighbors.len() as f64;
+                        (average - p) * w
+                    }
+                }
+                // A full-strength dab moves the center by a tenth of the radius
+                (BrushKind::Inflate, _) => topology.vertex_normal(v) * (0.1 * radius * w),
+                (BrushKind::Flatten, Some((origin, normal))) => {
+                    -normal * (p - origin).dot(normal) * w
+                }
+                (BrushKind::Flatten, None) => DVec3::ZERO,
+            };
+            (v, p + delta)
+        })
+        .collect();
+
+    let mut moved = BTreeSet::new();
+    for (v, p) in moves {
+        if p != topology.positions[v] && p.is_finite() {
+            topology.positions[v] = p;
+            moved.insert(v);
+        }
+    }
+    moved
+}
+
+// One Laplacian pass: each of `vertices` moves `factor` times its weight of
+// the way toward the average of its neighbors, all computed from the
+// positions before the pass. `locked` vertices stay put. Returns the
+// vertices that moved.
+pub fn smooth_pass(
+    topology: &mut MeshTopology,
+    vertices: &[(usize, f64)],
+    factor: f64,
+    locked: &BTreeSet<usize>,
+) -> BTreeSet<usize> {
+    let moves: Vec<(usize, DVec3)> = vertices
+        .iter()
+        .filter(|(v, _)| !locked.contains(v))
+        .filter_map(|&(v, weight)| {
+            let neighbors = &topology.vertex_neighbors[v];
+            if neighbors.is_empty() {
+                return None;
+            }
+            let average = neighbors
+                .iter()
+                .map(|&n| topology.positions[n])
+                .sum::<DVec3>()
+                / neighbors.len() as f64;
+            let p = topology.positions[v];
+            Some((v, p + (average - p) * (factor * weight)))
+        })
+        .collect();
+
+    let mut moved = BTreeSet::new();
+    for (v, p) in moves {
+        if p != topology.positions[v] && p.is_finite() {
+            topology.positions[v] = p;
+            moved.insert(v);
+        }
+    }
+    moved
+}
@@ -0,0 +1,544 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use bevy::{
+    asset::Assets,
+    color::Color,
+    ecs::{
+        change_detection::DetectChangesMut,
+        entity::Entity,
+        query::With,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    input::{ButtonInput, mouse::MouseButton},
+    math::{DVec3, Isometry3d, Quat, Vec3},
+    render::{
+        camera::Camera,
+        mesh::{Mesh, Mesh3d, VertexAttributeValues},
+    },
+    state::state::State,
+    time::Time,
+    transform::components::GlobalTransform,
+    window::{PrimaryWindow, Window},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::mesh::constraints::EditConstraints;
+use crate::mesh::conversion::{
+    set_vertex_position, tri_vertices_of_face, unshared_corner_vertices, vertex_position,
+};
+use crate::mesh::edge::local_pixel_size;
+use crate::mesh::face_tree::{FaceTreeCache, RayHit};
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::sculpt::brush::{BrushKind, Falloff, apply_brush, smooth_pass};
+use crate::selection::components::SelectionSet;
+use crate::selection::region::EditRegion;
+use crate::session::presets::{OperationPresets, PresetParams, preset_section};
+use crate::snapshot::systems::SnapshotCompare;
+use crate::tools::systems::ActiveTool;
+use crate::trajectory::systems::TrajectoryRecorder;
+
+// Dabs per second at full strength while the button is held
+const DAB_RATE: f32 = 10.0;
+
+pub struct SculptStroke {
+    pub mesh: Entity,
+    // Snapshot taken when the stroke starts; brushes edit its positions and
+    // moved vertices are copied into the cgar mesh
+    pub topology: MeshTopology,
+    // Drawn vertices of every cgar vertex, or `None` if the drawn mesh has a
+    // layout we can't patch and must be rebuilt per dab
+    pub render_vertices: Option<Vec<Vec<u32>>>,
+    // Vertices the edit constraints keep in place, fixed for the stroke
+    pub locked: BTreeSet<usize>,
+}
+
+// Brush under the cursor in world space, for drawing
+#[derive(Debug, Clone, Copy)]
+pub struct BrushHover {
+    pub center: Vec3,
+    pub normal: Vec3,
+    pub radius: f32,
+}
+
+#[derive(Resource)]
+pub struct SculptBrush {
+    pub kind: BrushKind,
+    pub falloff: Falloff,
+    pub radius_px: f32,
+    pub strength: f32,
+    pub stroke: Option<SculptStroke>,
+    pub hover: Option<BrushHover>,
+    // Laplacian smoothing of the whole mesh (or its selected vertices)
+    pub smooth_iterations: usize,
+    pub smooth_factor: f64,
+}
+
+impl Default for SculptBrush {
+    fn default() -> Self {
+        Self {
+            kind: BrushKind::default(),
+            falloff: Falloff::default(),
+            radius_px: 40.0,
+            strength: 0.5,
+            stroke: None,
+            hover: None,
+            smooth_iterations: 10,
+            smooth_factor: 0.5,
+        }
+    }
+}
+
+// Smoothing settings saved as a named preset
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct SmoothingPreset {
+    iterations: usize,
+    factor: f64,
+}
+
+impl Default for SmoothingPreset {
+    fn default() -> Self {
+        Self::from(&SculptBrush::default())
+    }
+}
+
+impl From<&SculptBrush> for SmoothingPreset {
+    fn from(brush: &SculptBrush) -> Self {
+        Self {
+            iterations: brush.smooth_iterations,
+            factor: brush.smooth_factor,
+        }
+    }
+}
+
+impl PresetParams for SmoothingPreset {
+    const OPERATION: &'static str = "smoothing";
+}
+
+// Drawn vertices of every cgar vertex, matching the layout `render_mesh` chose
+fn render_vertex_map(cgar_data: &CgarMeshData, mesh: &Mesh) -> Option<Vec<Vec<u32>>> {
+    let vertex_count = cgar_data.0.vertices.len();
+    let mut map = vec![Vec::new(); vertex_count];
+    if mesh.contains_attribute(Mesh::ATTRIBUTE_COLOR) {
+        let corners = unshared_corner_vertices(&cgar_data.0);
+        if corners.len() != mesh.count_vertices() {
+            return None;
+        }
+        for (corner, &v) in corners.iter().enumerate() {
+            map[v as usize].push(corner as u32);
+        }
+    } else {
+        if mesh.count_vertices() != vertex_count {
+            return None;
+        }
+        for (v, drawn) in map.iter_mut().enumerate() {
+            drawn.push(v as u32);
+        }
+    }
+    Some(map)
+}
+
+// Patches positions and normals around the moved vertices in place instead of
+// rebuilding the mesh. Normals are plain smooth ones here; crease splits and
+// authored normals come back with the full rebuild when the stroke ends.
+fn patch_render_mesh(
+    mesh: &mut Mesh,
+    topology: &MeshTopology,
+    render_vertices: &[Vec<u32>],
+    moved: &BTreeSet<usize>,
+) {
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        for &v in moved {
+            let p = topology.positions[v].as_vec3().to_array();
+            for &i in &render_vertices[v] {
+                positions[i as usize] = p;
+            }
+        }
+    }
+    let mut shaded = moved.clone();
+    for &v in moved {
+        shaded.extend(topology.vertex_neighbors[v].iter().copied());
+    }
+    if let Some(VertexAttributeValues::Float32x3(normals)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+    {
+        for v in shaded {
+            let n = topology.vertex_normal(v).as_vec3();
+            let n = if n == Vec3::ZERO { Vec3::Y } else { n }.to_array();
+            for &i in &render_vertices[v] {
+                normals[i as usize] = n;
+            }
+        }
+    }
+}
+
+// Left-drag over a mesh with the Sculpt tool to apply the active brush.
+// Edits bypass change detection until the stroke ends so the face tree and
+// other dependents rebuild once per stroke; hits during a stroke use the tree
+// from its start.
+pub fn sculpt_stroke(
+    mut brush: ResMut<SculptBrush>,
+    mut recorder: ResMut<TrajectoryRecorder>,
+    constraints: Res<EditConstraints>,
+    selection: Res<SelectionSet>,
+    tool: Res<State<ActiveTool>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    time: Res<Time>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mut mesh_query: Query<(
+        Entity,
+        &Mesh3d,
+        &GlobalTransform,
+        &mut CgarMeshData,
+        Option<&FaceTreeCache>,
+        Option<&FaceColorOverlay>,
+        Option<&FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    let active = *tool.get() == ActiveTool::Sculpt;
+    if !active || !mouse_buttons.pressed(MouseButton::Left) {
+        if let Some(stroke) = brush.stroke.take() {
+            // Full rebuild restores creases and overlays; marking the mesh
+            // changed refreshes everything that caches its geometry
+            if let Ok((_, mesh_handle, _, mut cgar_data, _, overlay, features, normals, settings)) =
+                mesh_query.get_mut(stroke.mesh)
+            {
+                cgar_data.set_changed();
+                meshes.insert(
+                    &mesh_handle.0,
+                    render_mesh(&cgar_data.0, overlay, features, normals, settings),
+                );
+            }
+        }
+    }
+    if !active {
+        brush.hover = None;
+        return;
+    }
+
+    let (Ok(window), Ok((camera, camera_global))) = (windows.single(), camera_query.single())
+    else {
+        return;
+    };
+    let Some(ray) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(camera_global, cursor).ok())
+    else {
+        brush.hover = None;
+        return;
+    };
+
+    // Nearest hit along the cursor ray; a stroke stays on the mesh it started on
+    let stroke_mesh = brush.stroke.as_ref().map(|stroke| stroke.mesh);
+    let mut hit: Option<(f64, Entity, DVec3, usize)> = None;
+    for (entity, _, mesh_global, cgar_data, cache, ..) in &mesh_query {
+        let Some(cache) = cache.filter(|_| stroke_mesh.is_none_or(|mesh| mesh == entity)) else {
+            continue;
+        };
+        let to_local = mesh_global.affine().inverse();
+        let origin = to_local.transform_point3(ray.origin).as_dvec3();
+        let direction = to_local
+            .transform_vector3(ray.direction.as_vec3())
+            .as_dvec3();
+        // `t` is along the world ray for every mesh, so hits compare across them
+        if let Some(RayHit { face, t, .. }) = cache
+            .0
+            .cast_ray(&cgar_data.0, origin, direction, None, |_| true)
+            .filter(|cast| hit.is_none_or(|(best, ..)| cast.t < best))
+        {
+            hit = Some((t, entity, origin + direction * t, face));
+        }
+    }
+    let Some((_, entity, center, face)) = hit else {
+        brush.hover = None;
+        return;
+    };
+    let Ok((_, mesh_handle, mesh_global, mut cgar_data, _, overlay, features, normals, settings)) =
+        mesh_query.get_mut(entity)
+    else {
+        return;
+    };
+    let world_center = mesh_global.transform_point(center.as_vec3());
+    let Some(pixel) = local_pixel_size(camera, camera_global, mesh_global, world_center) else {
+        return;
+    };
+    let radius = (pixel * brush.radius_px) as f64;
+
+    if brush.stroke.is_none() && mouse_buttons.just_pressed(MouseButton::Left) {
+        let render_vertices = meshes
+            .get(&mesh_handle.0)
+            .and_then(|mesh| render_vertex_map(&cgar_data, mesh));
+        let topology = MeshTopology::from_cgar(&cgar_data.0);
+        let locked = constraints.locked(
+            &topology,
+            features,
+            Some(&*selection).filter(|s| s.mesh == Some(entity)),
+        );
+        recorder.begin(
+            entity,
+            format!("{} stroke", brush.kind.label()),
+            &topology.positions,
+        );
+        brush.stroke = Some(SculptStroke {
+            mesh: entity,
+            topology,
+            render_vertices,
+            locked,
+        });
+    }
+
+    let (kind, falloff) = (brush.kind, brush.falloff);
+    let amount = (brush.strength * (time.delta_secs() * DAB_RATE).min(1.0)) as f64;
+    if let Some(stroke) = brush.stroke.as_mut() {
+        let moved = apply_brush(
+            &mut stroke.topology,
+            kind,
+            falloff,
+            center,
+            radius,
+            amount,
+            &stroke.locked,
+        );
+        recorder.record(
+            stroke.mesh,
+            moved.iter().map(|&v| (v, stroke.topology.positions[v])),
+        );
+        if !moved.is_empty() {
+            let cgar = &mut cgar_data.bypass_change_detection().0;
+            for &v in &moved {
+                set_vertex_position(cgar, v, stroke.topology.positions[v]);
+            }
+            match (&stroke.render_vertices, meshes.get_mut(&mesh_handle.0)) {
+                (Some(render_vertices), Some(mesh)) => {
+                    patch_render_mesh(mesh, &stroke.topology, render_vertices, &moved);
+                }
+                _ => {
+                    meshes.insert(
+                        &mesh_handle.0,
+                        render_mesh(&cgar_data.0, overlay, features, normals, settings),
+                    );
+                }
+            }
+        }
+    }
+
+    let [a, b, c] = tri_vertices_of_face(&cgar_data.0, face)
+        .map(|v| mesh_global.transform_point(vertex_position(&cgar_data.0, v)));
+    // Same footprint in world units, for the outline
+    let world_pixel = local_pixel_size(
+        camera,
+        camera_global,
+        &GlobalTransform::IDENTITY,
+        world_center,
+    )
+    .unwrap_or(pixel);
+    brush.hover = Some(BrushHover {
+        center: world_center,
+        normal: (b - a).cross(c - a).normalize_or(Vec3::Z),
+        radius: world_pixel * brush.radius_px,
+    });
+}
+
+pub fn draw_sculpt_brush(mut gizmos: Gizmos, brush: Res<SculptBrush>) {
+    let Some(hover) = brush.hover else {
+        return;
+    };
+    let color = if brush.stroke.is_some() {
+        Color::srgb(1.0, 0.6, 0.2)
+    } else {
+        Color::srgb(0.9, 0.9, 0.9)
+    };
+    let facing = Isometry3d::new(hover.center, Quat::from_rotation_arc(Vec3::Z, hover.normal));
+    gizmos.circle(facing, hover.radius, color);
+    gizmos.line(
+        hover.center,
+        hover.center + hover.normal * hover.radius * 0.5,
+        color,
+    );
+}
+
+pub fn sculpt_panel(
+    mut contexts: EguiContexts,
+    mut brush: ResMut<SculptBrush>,
+    mut recorder: ResMut<TrajectoryRecorder>,
+    mut presets: ResMut<OperationPresets>,
+    constraints: Res<EditConstraints>,
+    selection: Res<SelectionSet>,
+    compare: Res<SnapshotCompare>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh_query: Query<(
+        Entity,
+        &Mesh3d,
+        &mut CgarMeshData,
+        Option<&FaceColorOverlay>,
+        Option<&FeatureEdges>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Sculpt").show(ctx, |ui| {
+        let mut kind = brush.kind;
+        egui::ComboBox::from_label("Brush")
+            .selected_text(kind.label())
+            .show_ui(ui, |ui| {
+                for option in BrushKind::ALL {
+                    ui.selectable_value(&mut kind, option, option.label());
+                }
+            });
+        if kind != brush.kind {
+            brush.kind = kind;
+        }
+
+        let mut falloff = brush.falloff;
+        egui::ComboBox::from_label("Falloff")
+            .selected_text(falloff.label())
+            .show_ui(ui, |ui| {
+                for option in Falloff::ALL {
+                    ui.selectable_value(&mut falloff, option, option.label());
+                }
+            });
+        if falloff != brush.falloff {
+            brush.falloff = falloff;
+        }
+
+        let mut radius = brush.radius_px;
+        ui.add(egui::Slider::new(&mut radius, 5.0..=300.0).text("Radius (px)"));
+        if radius != brush.radius_px {
+            brush.radius_px = radius;
+        }
+        let mut strength = brush.strength;
+        ui.add(egui::Slider::new(&mut strength, 0.0..=1.0).text("Strength"));
+        if strength != brush.strength {
+            brush.strength = strength;
+        }
+        ui.label("Press B, then left-drag over a mesh");
+
+        ui.separator();
+        let mut iterations = brush.smooth_iterations;
+        ui.add(egui::Slider::new(&mut iterations, 1..=200).text("Iterations"));
+        if iterations != brush.smooth_iterations {
+            brush.smooth_iterations = iterations;
+        }
+        let mut factor = brush.smooth_factor;
+        ui.add(egui::Slider::new(&mut factor, 0.01..=1.0).text("Factor"));
+        if factor != brush.smooth_factor {
+            brush.smooth_factor = factor;
+        }
+        let current = SmoothingPreset::from(&*brush);
+        if let Some(preset) = preset_section(ui, &mut presets, &current) {
+            brush.smooth_iterations = preset.iterations;
+            brush.smooth_factor = preset.factor;
+        }
+        let target = selection
+            .mesh
+            .filter(|e| mesh_query.contains(*e))
+            .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+        // The selection only counts on the mesh it was picked on
+        let selected = target.filter(|&e| selection.mesh == Some(e) && !selection.is_empty());
+        let label = if selected.is_some() {
+            "Smooth selection"
+        } else {
+            "Smooth mesh"
+        };
+        let reference = target.and_then(|entity| compare.reference(entity));
+        if reference.is_some() {
+            ui.label("Passes are re-projected onto the pinned snapshot");
+        }
+        if !ui
+            .add_enabled(target.is_some(), egui::Button::new(label))
+            .clicked()
+        {
+            return;
+        }
+        let Some(Ok((entity, mesh_handle, mut cgar_data, overlay, features, normals, settings))) =
+            target.map(|entity| mesh_query.get_mut(entity))
+        else {
+            return;
+        };
+
+        let mut topology = MeshTopology::from_cgar(&cgar_data.0);
+        // A selection smooths its region, fading out on the transition ring
+        let region = selected.and_then(|_| EditRegion::from_selection(&selection, &topology));
+        let vertices: Vec<(usize, f64)> = match &region {
+            Some(region) => region
+                .vertices
+                .iter()
+                .map(|&v| (v, region.weight(v)))
+                .collect(),
+            None => topology.used_vertices().map(|v| (v, 1.0)).collect(),
+        };
+        // Smoothing the selection itself ignores the selection lock
+        let locked = constraints.locked(
+            &topology,
+            features,
+            Some(&*selection).filter(|s| s.mesh == Some(entity) && selected.is_none()),
+        );
+        recorder.begin(
+            entity,
+            format!("Smoothing x{}", brush.smooth_iterations),
+            &topology.positions,
+        );
+        let mut moved = BTreeSet::new();
+        for _ in 0..brush.smooth_iterations {
+            let pass = smooth_pass(&mut topology, &vertices, brush.smooth_factor, &locked);
+            if pass.is_empty() {
+                break;
+            }
+            if let Some(surface) = &reference {
+                for &v in &pass {
+                    let p = topology.positions[v];
+                    topology.positions[v] =
+                        surface.closest_point(p).map_or(p, |closest| closest.point);
+                }
+            }
+            recorder.record(entity, pass.iter().map(|&v| (v, topology.positions[v])));
+            moved.extend(pass);
+        }
+        for &v in &moved {
+            set_vertex_position(&mut cgar_data.0, v, topology.positions[v]);
+        }
+        meshes.insert(
+            &mesh_handle.0,
+            render_mesh(&cgar_data.0, overlay, features, normals, settings),
+        );
+    });
+}
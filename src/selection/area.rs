@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::math::{DVec3, Vec2};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::mesh::face_tree::FaceSurface;
+use crate::selection::components::AreaShape;
+
+// Even-odd test against a closed polygon
+fn polygon_contains(polygon: &[Vec2], p: Vec2) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[j];
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+// Whether a screen point falls inside the dragged box or lasso
+pub fn area_contains(shape: AreaShape, path: &[Vec2], p: Vec2) -> bool {
+    match (shape, path.first(), path.last()) {
+        (AreaShape::Box, Some(&a), Some(&b)) => {
+            let (min, max) = (a.min(b), a.max(b));
+            p.cmpge(min).all() && p.cmple(max).all()
+        }
+        (AreaShape::Lasso, ..) if path.len() >= 3 => polygon_contains(path, p),
+        _ => false,
+    }
+}
+
+// Whether nothing blocks the view ray `origin + t * direction` before it
+// reaches `point`. The last sliver of the ray is left out so the faces the
+// point lies on don't count as blocking it.
+pub fn unoccluded(
+    (mesh, surface): (&CgarMesh<CgarF64, 3>, &FaceSurface),
+    origin: DVec3,
+    direction: DVec3,
+    point: DVec3,
+) -> bool {
+    let t = (point - origin).dot(direction) / direction.length_squared().max(f64::MIN_POSITIVE);
+    t <= 0.0
+        || surface
+            .cast_ray(mesh, origin, direction, None, |_| true)
+            .is_none_or(|hit| hit.t >= t * (1.0 - 1e-4))
+}
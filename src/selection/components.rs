@@ -0,0 +1,283 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use bevy::ecs::{entity::Entity, event::Event, resource::Resource};
+use bevy::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::mesh::topology::MeshTopology;
+use crate::stats::fingerprint::MeshFingerprint;
+
+// Set of picked mesh elements, stored as cgar vertex/face ids and
+// undirected (min, max) vertex pairs for edges.
+#[derive(Resource, Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectionSet {
+    #[serde(skip)]
+    pub mesh: Option<Entity>,
+    pub vertices: BTreeSet<usize>,
+    pub edges: BTreeSet<(usize, usize)>,
+    pub faces: BTreeSet<usize>,
+}
+
+impl SelectionSet {
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty() && self.edges.is_empty() && self.faces.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.vertices.len() + self.edges.len() + self.faces.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+        self.faces.clear();
+    }
+
+    pub fn insert_edge(&mut self, v0: usize, v1: usize) {
+        self.edges.insert((v0.min(v1), v0.max(v1)));
+    }
+
+    pub fn contains_edge(&self, v0: usize, v1: usize) -> bool {
+        self.edges.contains(&(v0.min(v1), v0.max(v1)))
+    }
+
+    // Vertices of every selected element, skipping ids no longer in the mesh
+    pub fn touched_vertices(&self, topology: &MeshTopology) -> BTreeSet<usize> {
+        let mut vertices: BTreeSet<usize> = self.vertices.clone();
+        for &(v0, v1) in &self.edges {
+            vertices.insert(v0);
+            vertices.insert(v1);
+        }
+        for &face in &self.faces {
+            if let Some(Some(tri)) = topology.triangles.get(face) {
+                vertices.extend(tri.iter().copied());
+            }
+        }
+        vertices.retain(|&v| v < topology.positions.len());
+        vertices
+    }
+
+    // Copies the elements of `other` into `self`, keeping the current mesh target
+    pub fn assign(&mut self, other: &SelectionSet) {
+        self.vertices = other.vertices.clone();
+        self.edges = other.edges.clone();
+        self.faces = other.faces.clone();
+    }
+
+    // Renumbers elements after the mesh was rebuilt, dropping those that no
+    // longer exist and edges whose endpoints were merged
+    pub fn remap(&mut self, vertices: &[Option<usize>], faces: &[Option<usize>]) {
+        let vertex = |v: usize| vertices.get(v).copied().flatten();
+        self.vertices = self.vertices.iter().filter_map(|&v| vertex(v)).collect();
+        self.edges = self
+            .edges
+            .iter()
+            .filter_map(|&(v0, v1)| Some((vertex(v0)?, vertex(v1)?)))
+            .filter(|(v0, v1)| v0 != v1)
+            .map(|(v0, v1)| (v0.min(v1), v0.max(v1)))
+            .collect();
+        self.faces = self
+            .faces
+            .iter()
+            .filter_map(|&f| faces.get(f).copied().flatten())
+            .collect();
+    }
+
+    pub fn combine(&mut self, other: &SelectionSet, mode: SelectionCombine) {
+        match mode {
+            SelectionCombine::Replace => self.assign(other),
+            SelectionCombine::Union => {
+                self.vertices.extend(other.vertices.iter().copied());
+                self.edges.extend(other.edges.iter().copied());
+                self.faces.extend(other.faces.iter().copied());
+            }
+            SelectionCombine::Intersect => {
+                self.vertices.retain(|v| other.vertices.contains(v));
+                self.edges.retain(|e| other.edges.contains(e));
+                self.faces.retain(|f| other.faces.contains(f));
+            }
+            SelectionCombine::Subtract => {
+                self.vertices.retain(|v| !other.vertices.contains(v));
+                self.edges.retain(|e| !other.edges.contains(e));
+                self.faces.retain(|f| !other.faces.contains(f));
+            }
+        }
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SelectionCombine {
+    #[default]
+    Replace,
+    Union,
+    Intersect,
+    Subtract,
+}
+
+impl SelectionCombine {
+    pub const ALL: [SelectionCombine; 4] = [
+        SelectionCombine::Replace,
+        SelectionCombine::Union,
+        SelectionCombine::Intersect,
+        SelectionCombine::Subtract,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SelectionCombine::Replace => "Replace",
+            SelectionCombine::Union => "Union",
+            SelectionCombine::Intersect => "Intersect",
+            SelectionCombine::Subtract => "Subtract",
+        }
+    }
+}
+
+// Mesh a saved set was taken on. Element ids only mean something on that
+// mesh, so a set is recalled only onto one from the same file with the
+// same content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectionSource {
+    // File the mesh was loaded from, if any
+    pub path: Option<PathBuf>,
+    pub geometry: u64,
+    pub connectivity: u64,
+}
+
+impl SelectionSource {
+    pub fn new(path: Option<&Path>, fingerprint: &MeshFingerprint) -> Self {
+        Self {
+            path: path.map(Path::to_path_buf),
+            geometry: fingerprint.geometry,
+            connectivity: fingerprint.connectivity,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let file = self.path.as_ref().map_or_else(
+            || "unsaved mesh".to_string(),
+            |path| path.display().to_string(),
+        );
+        format!("{} ({:016x})", file, self.geometry)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSelection {
+    pub source: SelectionSource,
+    pub set: SelectionSet,
+}
+
+// Named selection sets, persisted in the session file
+#[derive(Resource, Default, Debug)]
+pub struct SavedSelections {
+    pub sets: BTreeMap<String, SavedSelection>,
+}
+
+// Click-to-grow selection of connected faces with similar normals
+#[derive(Resource, Debug)]
+pub struct RegionGrowSettings {
+    pub enabled: bool,
+    pub max_angle_deg: f32,
+    // Compare against the seed face instead of the neighboring face
+    pub relative_to_seed: bool,
+    pub seed: Option<(Entity, usize)>,
+}
+
+// Sent whenever a click assigns `RegionGrowSettings::seed`, so the region
+// is grown again even when the same face is picked twice
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RegionGrowSeeded;
+
+impl Default for RegionGrowSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_angle_deg: 10.0,
+            relative_to_seed: false,
+            seed: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AreaShape {
+    #[default]
+    Box,
+    Lasso,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AreaElement {
+    #[default]
+    Vertices,
+    Edges,
+    Faces,
+}
+
+impl AreaElement {
+    pub const ALL: [AreaElement; 3] = [
+        AreaElement::Vertices,
+        AreaElement::Edges,
+        AreaElement::Faces,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AreaElement::Vertices => "Vertices",
+            AreaElement::Edges => "Edges",
+            AreaElement::Faces => "Faces",
+        }
+    }
+}
+
+// First face picked with the Face path tool, waiting for the second
+#[derive(Resource, Default, Debug)]
+pub struct FacePathPick {
+    pub start: Option<(Entity, usize)>,
+}
+
+// Box or lasso selection with the Area select tool
+#[derive(Resource, Debug)]
+pub struct AreaSelect {
+    pub shape: AreaShape,
+    pub element: AreaElement,
+    // Leave out elements hidden behind other geometry
+    pub visible_only: bool,
+    // Screen path of the drag in progress, in logical pixels: the two
+    // corners for a box, every sampled point for a lasso
+    pub drag: Option<Vec<Vec2>>,
+}
+
+impl Default for AreaSelect {
+    fn default() -> Self {
+        Self {
+            shape: AreaShape::default(),
+            element: AreaElement::default(),
+            visible_only: true,
+            drag: None,
+        }
+    }
+}
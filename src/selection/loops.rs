@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::mesh::topology::MeshTopology;
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+// Neighbors of `v` in fan order, following the winding of the faces around
+// it. `None` at boundary and non-manifold vertices, whose fans don't close.
+fn vertex_ring(topology: &MeshTopology, v: usize) -> Option<Vec<usize>> {
+    let mut next = BTreeMap::new();
+    for &face in topology.vertex_faces.get(v)? {
+        let Some(tri) = topology.triangles[face] else {
+            continue;
+        };
+        let i = tri.iter().position(|&corner| corner == v)?;
+        if next.insert(tri[(i + 1) % 3], tri[(i + 2) % 3]).is_some() {
+            return None;
+        }
+    }
+
+    let &start = next.keys().next()?;
+    let mut ring = vec![start];
+    let mut current = start;
+    loop {
+        let &n = next.get(&current)?;
+        if n == start {
+            break;
+        }
+        if ring.len() == next.len() {
+            return None;
+        }
+        ring.push(n);
+        current = n;
+    }
+    (ring.len() == next.len()).then_some(ring)
+}
+
+// The neighbor of `at` straight across from `from`: as many edges lie on
+// either side of the continuation, so it only exists at even valence
+fn loop_continuation(topology: &MeshTopology, from: usize, at: usize) -> Option<usize> {
+    let ring = vertex_ring(topology, at)?;
+    if ring.len() % 2 != 0 {
+        return None;
+    }
+    let i = ring.iter().position(|&n| n == from)?;
+    Some(ring[(i + ring.len() / 2) % ring.len()])
+}
+
+// Edges continuing `edge` straight through regular vertices in both
+// directions. The walk stops at odd-valence and boundary vertices, or when it
+// closes back on itself.
+pub fn edge_loop(topology: &MeshTopology, edge: (usize, usize)) -> BTreeSet<(usize, usize)> {
+    let mut edges = BTreeSet::new();
+    if !topology.edge_faces.contains_key(&edge_key(edge.0, edge.1)) {
+        return edges;
+    }
+    edges.insert(edge_key(edge.0, edge.1));
+
+    for (mut from, mut at) in [(edge.0, edge.1), (edge.1, edge.0)] {
+        while let Some(next) = loop_continuation(topology, from, at) {
+            if !edges.insert(edge_key(at, next)) {
+                break;
+            }
+            from = at;
+            at = next;
+        }
+    }
+    edges
+}
+
+// The face's longest edge, read as the diagonal splitting a quad in two
+fn quad_diagonal(topology: &MeshTopology, face: usize) -> Option<(usize, usize)> {
+    let tri = topology.triangles.get(face).copied().flatten()?;
+    (0..3)
+        .map(|i| edge_key(tri[i], tri[(i + 1) % 3]))
+        .max_by(|&x, &y| {
+            topology
+                .edge_length(x)
+                .total_cmp(&topology.edge_length(y))
+                .then(y.cmp(&x))
+        })
+}
+
+// The other face on a manifold edge
+fn face_across(topology: &MeshTopology, face: usize, edge: (usize, usize)) -> Option<usize> {
+    match topology.edge_faces.get(&edge)?.as_slice() {
+        &[f, g] => Some(if f == face { g } else { f }),
+        _ => None,
+    }
+}
+
+// Side opposite `edge` in the quad `face` forms with the neighbor across its
+// diagonal, together with that neighbor. Both triangles must agree on the
+// diagonal; otherwise the faces don't pair up into a quad.
+fn ring_step(
+    topology: &MeshTopology,
+    face: usize,
+    edge: (usize, usize),
+) -> Option<(usize, (usize, usize))> {
+    let diagonal = quad_diagonal(topology, face)?;
+    if diagonal == edge {
+        return None;
+    }
+    let partner = face_across(topology, face, diagonal)?;
+    if quad_diagonal(topology, partner)? != diagonal {
+        return None;
+    }
+
+    // `edge` shares one corner with the diagonal; the opposite side avoids it
+    let shared = if diagonal.0 == edge.0 || diagonal.0 == edge.1 {
+        diagonal.0
+    } else {
+        diagonal.1
+    };
+    let tri = topology.triangles[partner]?;
+    let i = tri.iter().position(|&v| v == shared)?;
+    Some((partner, edge_key(tri[(i + 1) % 3], tri[(i + 2) % 3])))
+}
+
+// Opposite sides of the quads `edge` borders, repeated across the strip of
+// quads in both directions. Triangles pair into quads across their longest
+// edge; the walk stops at boundaries, where faces don't pair and when the
+// ring closes.
+pub fn edge_ring(topology: &MeshTopology, edge: (usize, usize)) -> BTreeSet<(usize, usize)> {
+    let mut edges = BTreeSet::new();
+    let start = edge_key(edge.0, edge.1);
+    let Some(faces) = topology.edge_faces.get(&start) else {
+        return edges;
+    };
+    edges.insert(start);
+
+    for &first in faces {
+        let (mut face, mut edge) = (first, start);
+        while let Some((partner, opposite)) = ring_step(topology, face, edge) {
+            if !edges.insert(opposite) {
+                break;
+            }
+            let Some(next) = face_across(topology, partner, opposite) else {
+                break;
+            };
+            face = next;
+            edge = opposite;
+        }
+    }
+    edges
+}
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use bevy::math::DVec3;
+
+use crate::mesh::topology::MeshTopology;
+
+// Shortest walk from face `from` to face `to` over edge-adjacent faces,
+// measured between face centroids, endpoints included. `None` when either
+// face is gone or they lie on different components.
+pub fn face_path(topology: &MeshTopology, from: usize, to: usize) -> Option<Vec<usize>> {
+    let centroid = |face: usize| -> Option<DVec3> {
+        let tri = topology.triangles.get(face).copied().flatten()?;
+        let [a, b, c] = topology.corners(tri);
+        Some((a + b + c) / 3.0)
+    };
+    centroid(from)?;
+    centroid(to)?;
+
+    let mut distance = vec![f64::INFINITY; topology.triangles.len()];
+    let mut previous = vec![usize::MAX; topology.triangles.len()];
+    // Distances are never negative, so their bit patterns sort like the values
+    let mut queue = BinaryHeap::from([Reverse((0f64.to_bits(), from))]);
+    distance[from] = 0.0;
+
+    while let Some(Reverse((bits, face))) = queue.pop() {
+        let d = f64::from_bits(bits);
+        if face == to {
+            break;
+        }
+        if d > distance[face] {
+            continue;
+        }
+        let Some(here) = centroid(face) else {
+            continue;
+        };
+        for neighbor in topology.face_neighbors(face) {
+            let Some(there) = centroid(neighbor) else {
+                continue;
+            };
+            let candidate = d + here.distance(there);
+            if candidate < distance[neighbor] {
+                distance[neighbor] = candidate;
+                previous[neighbor] = face;
+                queue.push(Reverse((candidate.to_bits(), neighbor)));
+            }
+        }
+    }
+
+    if !distance[to].is_finite() {
+        return None;
+    }
+    let mut path = vec![to];
+    while let Some(&face) = path.last().filter(|&&face| face != from) {
+        path.push(previous[face]);
+    }
+    path.reverse();
+    Some(path)
+}
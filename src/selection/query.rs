@@ -0,0 +1,664 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use crate::mesh::topology::MeshTopology;
+use crate::selection::components::SelectionSet;
+
+// Expression-based selection over mesh attributes, e.g.
+// `face.area < 0.001`, `vertex.degree > 8`, `edge.length > 2*mean`.
+// Bare statistics (`mean`, `median`, `min`, `max`, `std`) refer to the
+// attribute used in the same comparison.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    Vertex,
+    Edge,
+    Face,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Attribute {
+    VertexDegree,
+    VertexX,
+    VertexY,
+    VertexZ,
+    EdgeLength,
+    EdgeDihedral,
+    EdgeFaces,
+    FaceArea,
+    FacePerimeter,
+    FaceAspect,
+    FaceNormalX,
+    FaceNormalY,
+    FaceNormalZ,
+}
+
+impl Attribute {
+    pub const ALL: [Attribute; 13] = [
+        Attribute::VertexDegree,
+        Attribute::VertexX,
+        Attribute::VertexY,
+        Attribute::VertexZ,
+        Attribute::EdgeLength,
+        Attribute::EdgeDihedral,
+        Attribute::EdgeFaces,
+        Attribute::FaceArea,
+        Attribute::FacePerimeter,
+        Attribute::FaceAspect,
+        Attribute::FaceNormalX,
+        Attribute::FaceNormalY,
+        Attribute::FaceNormalZ,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Attribute::VertexDegree => "vertex.degree",
+            Attribute::VertexX => "vertex.x",
+            Attribute::VertexY => "vertex.y",
+            Attribute::VertexZ => "vertex.z",
+            Attribute::EdgeLength => "edge.length",
+            Attribute::EdgeDihedral => "edge.dihedral",
+            Attribute::EdgeFaces => "edge.faces",
+            Attribute::FaceArea => "face.area",
+            Attribute::FacePerimeter => "face.perimeter",
+            Attribute::FaceAspect => "face.aspect",
+            Attribute::FaceNormalX => "face.nx",
+            Attribute::FaceNormalY => "face.ny",
+            Attribute::FaceNormalZ => "face.nz",
+        }
+    }
+
+    pub fn kind(self) -> ElementKind {
+        match self {
+            Attribute::VertexDegree
+            | Attribute::VertexX
+            | Attribute::VertexY
+            | Attribute::VertexZ => ElementKind::Vertex,
+            Attribute::EdgeLength | Attribute::EdgeDihedral | Attribute::EdgeFaces => {
+                ElementKind::Edge
+            }
+            _ => ElementKind::Face,
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stat {
+    Mean,
+    Median,
+    Min,
+    Max,
+    Std,
+}
+
+impl Stat {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "mean" | "avg" => Some(Stat::Mean),
+            "median" => Some(Stat::Median),
+            "min" => Some(Stat::Min),
+            "max" => Some(Stat::Max),
+            "std" | "stddev" => Some(Stat::Std),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(f64),
+    Attr(Attribute),
+    // Resolved to an attribute once the enclosing comparison is parsed
+    Stat(Stat, Option<Attribute>),
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    Compare(Expr, CmpOp, Expr),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectionQuery {
+    pub kind: ElementKind,
+    condition: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit()))
+        {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                i += 1;
+                if i < chars.len() && (chars[i] == '+' || chars[i] == '-') {
+                    i += 1;
+                }
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number `{}`", text))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            let ident: String = chars[start..i].iter().collect();
+            match ident.as_str() {
+                "and" => tokens.push(Token::Op("&&")),
+                "or" => tokens.push(Token::Op("||")),
+                "not" => tokens.push(Token::Op("!")),
+                _ => tokens.push(Token::Ident(ident)),
+            }
+        } else {
+            let next = chars.get(i + 1).copied();
+            let (op, len): (&'static str, usize) = match (c, next) {
+                ('<', Some('=')) => ("<=", 2),
+                ('>', Some('=')) => (">=", 2),
+                ('=', Some('=')) => ("==", 2),
+                ('!', Some('=')) => ("!=", 2),
+                ('&', Some('&')) => ("&&", 2),
+                ('|', Some('|')) => ("||", 2),
+                ('<', _) => ("<", 1),
+                ('>', _) => (">", 1),
+                ('=', _) => ("==", 1),
+                ('!', _) => ("!", 1),
+                ('+', _) => ("+", 1),
+                ('-', _) => ("-", 1),
+                ('*', _) => ("*", 1),
+                ('/', _) => ("/", 1),
+                ('(', _) => {
+                    tokens.push(Token::LParen);
+                    i += 1;
+                    continue;
+                }
+                (')', _) => {
+                    tokens.push(Token::RParen);
+                    i += 1;
+                    continue;
+                }
+                _ => return Err(format!("unexpected character `{}`", c)),
+            };
+            tokens.push(Token::Op(op));
+            i += len;
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    kind: Option<ElementKind>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat_op(&mut self, op: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Op(o)) if *o == op) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn condition(&mut self) -> Result<Condition, String> {
+        let mut lhs = self.and_condition()?;
+        while self.eat_op("||") {
+            let rhs = self.and_condition()?;
+            lhs = Condition::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn and_condition(&mut self) -> Result<Condition, String> {
+        let mut lhs = self.unary_condition()?;
+        while self.eat_op("&&") {
+            let rhs = self.unary_condition()?;
+            lhs = Condition::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn unary_condition(&mut self) -> Result<Condition, String> {
+        if self.eat_op("!") {
+            return Ok(Condition::Not(Box::new(self.unary_condition()?)));
+        }
+
+        // `(` may open either a nested condition or an arithmetic group
+        if self.peek() == Some(&Token::LParen) {
+            let start = self.pos;
+            self.pos += 1;
+            if let Ok(inner) = self.condition() {
+                if self.peek() == Some(&Token::RParen) {
+                    self.pos += 1;
+                    return Ok(inner);
+                }
+            }
+            self.pos = start;
+        }
+
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Condition, String> {
+        let mut lhs = self.expr()?;
+        let op = match self.peek() {
+            Some(Token::Op("<")) => CmpOp::Lt,
+            Some(Token::Op("<=")) => CmpOp::Le,
+            Some(Token::Op(">")) => CmpOp::Gt,
+            Some(Token::Op(">=")) => CmpOp::Ge,
+            Some(Token::Op("==")) => CmpOp::Eq,
+            Some(Token::Op("!=")) => CmpOp::Ne,
+            _ => return Err("expected a comparison operator".to_string()),
+        };
+        self.pos += 1;
+        let mut rhs = self.expr()?;
+
+        let Some(attribute) = first_attribute(&lhs).or_else(|| first_attribute(&rhs)) else {
+            return Err("comparison does not reference any attribute".to_string());
+        };
+        bind_stats(&mut lhs, attribute);
+        bind_stats(&mut rhs, attribute);
+        Ok(Condition::Compare(lhs, op, rhs))
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.term()?;
+        loop {
+            let op = if self.eat_op("+") {
+                BinOp::Add
+            } else if self.eat_op("-") {
+                BinOp::Sub
+            } else {
+                return Ok(lhs);
+            };
+            let rhs = self.term()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.factor()?;
+        loop {
+            let op = if self.eat_op("*") {
+                BinOp::Mul
+            } else if self.eat_op("/") {
+                BinOp::Div
+            } else {
+                return Ok(lhs);
+            };
+            let rhs = self.factor()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expr, String> {
+        if self.eat_op("-") {
+            return Ok(Expr::Neg(Box::new(self.factor()?)));
+        }
+        match self.tokens.get(self.pos).cloned() {
+            Some(Token::Number(value)) => {
+                self.pos += 1;
+                Ok(Expr::Number(value))
+            }
+            Some(Token::Ident(name)) => {
+                self.pos += 1;
+                if let Some(stat) = Stat::parse(&name) {
+                    return Ok(Expr::Stat(stat, None));
+                }
+                let attribute = Attribute::parse(&name)
+                    .ok_or_else(|| format!("unknown attribute `{}`", name))?;
+                match self.kind {
+                    Some(kind) if kind != attribute.kind() => {
+                        Err("a query can only reference one element type".to_string())
+                    }
+                    _ => {
+                        self.kind = Some(attribute.kind());
+                        Ok(Expr::Attr(attribute))
+                    }
+                }
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let inner = self.expr()?;
+                if self.peek() != Some(&Token::RParen) {
+                    return Err("expected `)`".to_string());
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            _ => Err("expected a number, attribute or `(`".to_string()),
+        }
+    }
+}
+
+fn first_attribute(expr: &Expr) -> Option<Attribute> {
+    match expr {
+        Expr::Attr(attribute) => Some(*attribute),
+        Expr::Neg(inner) => first_attribute(inner),
+        Expr::Binary(_, lhs, rhs) => first_attribute(lhs).or_else(|| first_attribute(rhs)),
+        _ => None,
+    }
+}
+
+fn bind_stats(expr: &mut Expr, attribute: Attribute) {
+    match expr {
+        Expr::Stat(_, bound) => *bound = Some(attribute),
+        Expr::Neg(inner) => bind_stats(inner, attribute),
+        Expr::Binary(_, lhs, rhs) => {
+            bind_stats(lhs, attribute);
+            bind_stats(rhs, attribute);
+        }
+        _ => {}
+    }
+}
+
+pub fn parse_query(src: &str) -> Result<SelectionQuery, String> {
+    let mut parser = Parser {
+        tokens: tokenize(src)?,
+        pos: 0,
+        kind: None,
+    };
+    if parser.tokens.is_empty() {
+        return Err("empty query".to_string());
+    }
+    let condition = parser.condition()?;
+    if parser.pos != parser.tokens.len() {
+        return Err("unexpected trailing input".to_string());
+    }
+    let kind = parser
+        .kind
+        .ok_or_else(|| "query does not reference any attribute".to_string())?;
+    Ok(SelectionQuery { kind, condition })
+}
+
+// Element ids of one kind in a fixed order, with attribute columns aligned to it
+enum Elements {
+    Vertices(Vec<usize>),
+    Edges(Vec<(usize, usize)>),
+    Faces(Vec<usize>),
+}
+
+impl Elements {
+    fn len(&self) -> usize {
+        match self {
+            Elements::Vertices(v) => v.len(),
+            Elements::Edges(e) => e.len(),
+            Elements::Faces(f) => f.len(),
+        }
+    }
+}
+
+pub fn attribute_values(topology: &MeshTopology, attribute: Attribute) -> Vec<f64> {
+    match collect_elements(topology, attribute.kind()) {
+        Elements::Vertices(vertices) => vertices
+            .iter()
+            .map(|&v| match attribute {
+                Attribute::VertexDegree => topology.vertex_neighbors[v].len() as f64,
+                Attribute::VertexX => topology.positions[v].x,
+                Attribute::VertexY => topology.positions[v].y,
+                _ => topology.positions[v].z,
+            })
+            .collect(),
+        Elements::Edges(edges) => edges
+            .iter()
+            .map(|&e| match attribute {
+                Attribute::EdgeLength => topology.edge_length(e),
+                Attribute::EdgeDihedral => topology
+                    .dihedral_angle(e)
+                    .map(f64::to_degrees)
+                    .unwrap_or(f64::NAN),
+                _ => topology.edge_faces[&e].len() as f64,
+            })
+            .collect(),
+        Elements::Faces(faces) => faces
+            .iter()
+            .map(|&f| {
+                let tri = topology.triangles[f].unwrap_or_default();
+                let [a, b, c] = topology.corners(tri);
+                let lengths = [a.distance(b), b.distance(c), c.distance(a)];
+                match attribute {
+                    Attribute::FaceArea => topology.face_area(f),
+                    Attribute::FacePerimeter => lengths.iter().sum(),
+                    Attribute::FaceAspect => {
+                        // Longest edge over shortest altitude, 1.0 for equilateral
+                        let longest = lengths.iter().copied().fold(0.0, f64::max);
+                        let area = topology.face_area(f);
+                        if area > 0.0 {
+                            longest * longest * 3f64.sqrt() / (4.0 * area)
+                        } else {
+                            f64::INFINITY
+                        }
+                    }
+                    Attribute::FaceNormalX => topology.face_normal(f).x,
+                    Attribute::FaceNormalY => topology.face_normal(f).y,
+                    _ => topology.face_normal(f).z,
+                }
+            })
+            .collect(),
+    }
+}
+
+fn collect_elements(topology: &MeshTopology, kind: ElementKind) -> Elements {
+    match kind {
+        ElementKind::Vertex => Elements::Vertices(topology.used_vertices().collect()),
+        ElementKind::Edge => Elements::Edges(topology.edge_faces.keys().copied().collect()),
+        ElementKind::Face => Elements::Faces(topology.live_faces().map(|(f, _)| f).collect()),
+    }
+}
+
+pub fn statistic(values: &[f64], stat: Stat) -> f64 {
+    let mut finite: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.is_empty() {
+        return f64::NAN;
+    }
+    let n = finite.len() as f64;
+    let mean = finite.iter().sum::<f64>() / n;
+    match stat {
+        Stat::Mean => mean,
+        Stat::Min => finite.iter().copied().fold(f64::INFINITY, f64::min),
+        Stat::Max => finite.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        Stat::Std => (finite.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n).sqrt(),
+        Stat::Median => {
+            finite.sort_by(f64::total_cmp);
+            let mid = finite.len() / 2;
+            if finite.len() % 2 == 0 {
+                0.5 * (finite[mid - 1] + finite[mid])
+            } else {
+                finite[mid]
+            }
+        }
+    }
+}
+
+struct Columns {
+    values: HashMap<Attribute, Vec<f64>>,
+    stats: HashMap<(Stat, Attribute), f64>,
+}
+
+impl Columns {
+    fn eval(&self, expr: &Expr, row: usize) -> f64 {
+        match expr {
+            Expr::Number(value) => *value,
+            Expr::Attr(attribute) => self.values[attribute][row],
+            Expr::Stat(stat, attribute) => attribute
+                .and_then(|a| self.stats.get(&(*stat, a)).copied())
+                .unwrap_or(f64::NAN),
+            Expr::Neg(inner) => -self.eval(inner, row),
+            Expr::Binary(op, lhs, rhs) => {
+                let (l, r) = (self.eval(lhs, row), self.eval(rhs, row));
+                match op {
+                    BinOp::Add => l + r,
+                    BinOp::Sub => l - r,
+                    BinOp::Mul => l * r,
+                    BinOp::Div => l / r,
+                }
+            }
+        }
+    }
+
+    fn test(&self, condition: &Condition, row: usize) -> bool {
+        match condition {
+            Condition::Compare(lhs, op, rhs) => {
+                let (l, r) = (self.eval(lhs, row), self.eval(rhs, row));
+                match op {
+                    CmpOp::Lt => l < r,
+                    CmpOp::Le => l <= r,
+                    CmpOp::Gt => l > r,
+                    CmpOp::Ge => l >= r,
+                    CmpOp::Eq => l == r,
+                    CmpOp::Ne => l != r,
+                }
+            }
+            Condition::And(lhs, rhs) => self.test(lhs, row) && self.test(rhs, row),
+            Condition::Or(lhs, rhs) => self.test(lhs, row) || self.test(rhs, row),
+            Condition::Not(inner) => !self.test(inner, row),
+        }
+    }
+}
+
+fn collect_refs(condition: &Condition, out: &mut Vec<(Option<Stat>, Attribute)>) {
+    fn walk(expr: &Expr, out: &mut Vec<(Option<Stat>, Attribute)>) {
+        match expr {
+            Expr::Attr(attribute) => out.push((None, *attribute)),
+            Expr::Stat(stat, Some(attribute)) => out.push((Some(*stat), *attribute)),
+            Expr::Neg(inner) => walk(inner, out),
+            Expr::Binary(_, lhs, rhs) => {
+                walk(lhs, out);
+                walk(rhs, out);
+            }
+            _ => {}
+        }
+    }
+    match condition {
+        Condition::Compare(lhs, _, rhs) => {
+            walk(lhs, out);
+            walk(rhs, out);
+        }
+        Condition::And(lhs, rhs) | Condition::Or(lhs, rhs) => {
+            collect_refs(lhs, out);
+            collect_refs(rhs, out);
+        }
+        Condition::Not(inner) => collect_refs(inner, out),
+    }
+}
+
+impl SelectionQuery {
+    pub fn evaluate(&self, topology: &MeshTopology) -> SelectionSet {
+        let elements = collect_elements(topology, self.kind);
+
+        let mut refs = Vec::new();
+        collect_refs(&self.condition, &mut refs);
+        let mut columns = Columns {
+            values: HashMap::new(),
+            stats: HashMap::new(),
+        };
+        for (stat, attribute) in refs {
+            let values = columns
+                .values
+                .entry(attribute)
+                .or_insert_with(|| attribute_values(topology, attribute));
+            if let Some(stat) = stat {
+                let value = statistic(values, stat);
+                columns.stats.insert((stat, attribute), value);
+            }
+        }
+
+        let mut selection = SelectionSet::default();
+        for row in 0..elements.len() {
+            if !columns.test(&self.condition, row) {
+                continue;
+            }
+            match &elements {
+                Elements::Vertices(vertices) => {
+                    selection.vertices.insert(vertices[row]);
+                }
+                Elements::Edges(edges) => {
+                    selection.edges.insert(edges[row]);
+                }
+                Elements::Faces(faces) => {
+                    selection.faces.insert(faces[row]);
+                }
+            }
+        }
+        selection
+    }
+}
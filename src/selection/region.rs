@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::mesh::topology::MeshTopology;
+use crate::selection::components::SelectionSet;
+
+// Breadth-first growth over edge-adjacent faces whose normals deviate less
+// than `max_angle` (radians) from the neighbor they were reached from, or
+// from the seed face when `relative_to_seed` is set.
+pub fn grow_region(
+    topology: &MeshTopology,
+    seed: usize,
+    max_angle: f64,
+    relative_to_seed: bool,
+) -> BTreeSet<usize> {
+    let mut region = BTreeSet::new();
+    if topology.triangles.get(seed).is_none_or(Option::is_none) {
+        return region;
+    }
+
+    let cos_limit = max_angle.cos();
+    let seed_normal = topology.face_normal(seed);
+    let mut queue = VecDeque::from([seed]);
+    region.insert(seed);
+
+    while let Some(face) = queue.pop_front() {
+        let reference = if relative_to_seed {
+            seed_normal
+        } else {
+            topology.face_normal(face)
+        };
+        for neighbor in topology.face_neighbors(face) {
+            if region.contains(&neighbor) {
+                continue;
+            }
+            if topology.face_normal(neighbor).dot(reference) >= cos_limit {
+                region.insert(neighbor);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    region
+}
+
+// Part of a mesh an operation is confined to, taken from a selection
+pub struct EditRegion {
+    // Vertices of every selected element; operations may move or remove them
+    pub vertices: BTreeSet<usize>,
+    // Region vertices with a neighbor outside the region. They form the
+    // transition ring: smoothing fades out across them and decimation keeps
+    // them in place so the surrounding faces don't distort.
+    pub border: BTreeSet<usize>,
+    // Selected faces plus faces whose corners are all region vertices
+    pub faces: BTreeSet<usize>,
+}
+
+impl EditRegion {
+    // `None` when nothing of the mesh is selected
+    pub fn from_selection(selection: &SelectionSet, topology: &MeshTopology) -> Option<Self> {
+        let vertices = selection.touched_vertices(topology);
+        if vertices.is_empty() {
+            return None;
+        }
+        let border = vertices
+            .iter()
+            .copied()
+            .filter(|&v| {
+                topology.vertex_neighbors[v]
+                    .iter()
+                    .any(|n| !vertices.contains(n))
+            })
+            .collect();
+        let faces = topology
+            .live_faces()
+            .filter(|(f, tri)| {
+                selection.faces.contains(f) || tri.iter().all(|v| vertices.contains(v))
+            })
+            .map(|(f, _)| f)
+            .collect();
+        Some(Self {
+            vertices,
+            border,
+            faces,
+        })
+    }
+
+    // How strongly an operation acts on `v`: fully inside, half on the
+    // transition ring and not at all outside
+    pub fn weight(&self, v: usize) -> f64 {
+        if !self.vertices.contains(&v) {
+            0.0
+        } else if self.border.contains(&v) {
+            0.5
+        } else {
+            1.0
+        }
+    }
+}
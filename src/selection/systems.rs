@@ -0,0 +1,780 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use bevy::{
+    asset::{Assets, Handle, RenderAssetUsages},
+    color::Color,
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        hierarchy::ChildOf,
+        query::With,
+        system::{Commands, Local, Query, Res, ResMut},
+        world::Ref,
+    },
+    gizmos::gizmos::Gizmos,
+    input::mouse::MouseButton,
+    input::{ButtonInput, keyboard::KeyCode},
+    log::info,
+    math::{DVec3, Isometry3d, Vec3},
+    pbr::{MeshMaterial3d, NotShadowCaster, StandardMaterial},
+    picking::Pickable,
+    render::{
+        camera::Camera,
+        mesh::{Mesh, Mesh3d, PrimitiveTopology},
+    },
+    state::state::State,
+    transform::components::{GlobalTransform, Transform},
+    utils::default,
+    window::{PrimaryWindow, Window},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::budget::systems::MeshBudget;
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::mesh::conversion::{tri_vertices_of_face, vertex_position};
+use crate::mesh::edge::MeshPicked;
+use crate::mesh::face_tree::{FaceSurface, FaceTreeCache};
+use crate::mesh::setup::MeshSource;
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::selection::area::{area_contains, unoccluded};
+use crate::selection::components::{
+    AreaElement, AreaSelect, AreaShape, FacePathPick, RegionGrowSeeded, RegionGrowSettings,
+    SavedSelection, SavedSelections, SelectionCombine, SelectionSet, SelectionSource,
+};
+use crate::selection::path::face_path;
+use crate::selection::query::parse_query;
+use crate::selection::region::grow_region;
+use crate::stats::systems::MeshFingerprints;
+use crate::tools::systems::ActiveTool;
+
+// Draws the active selection on top of its mesh
+pub fn draw_selection(
+    mut gizmos: Gizmos,
+    selection: Res<SelectionSet>,
+    budget: Res<MeshBudget>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+) {
+    if budget.batched() {
+        return;
+    }
+    // Recalled sets carry no mesh entity; fall back to the only mesh in the scene
+    let Some((mesh_global, cgar_data)) = selection
+        .mesh
+        .and_then(|entity| mesh_query.get(entity).ok())
+        .or_else(|| mesh_query.single().ok())
+    else {
+        return;
+    };
+    let cgar_mesh = &cgar_data.0;
+    let vertex_count = cgar_mesh.vertices.len();
+    let color = Color::srgb(1.0, 0.6, 0.1);
+    let world = |v: usize| mesh_global.transform_point(vertex_position(cgar_mesh, v));
+
+    for &(v0, v1) in &selection.edges {
+        if v0 < vertex_count && v1 < vertex_count {
+            gizmos.line(world(v0), world(v1), color);
+        }
+    }
+
+    for &face in &selection.faces {
+        if cgar_mesh.faces.get(face).is_none_or(|f| f.removed) {
+            continue;
+        }
+        let [a, b, c] = tri_vertices_of_face(cgar_mesh, face);
+        gizmos.linestrip([world(a), world(b), world(c), world(a)], color);
+    }
+
+    for &v in &selection.vertices {
+        if v < vertex_count {
+            gizmos.sphere(Isometry3d::from_translation(world(v)), 0.01, color);
+        }
+    }
+}
+
+// Marks the line mesh drawing the selection while highlights are batched
+#[derive(Component)]
+pub struct SelectionBatch;
+
+// Line list with the selected edges, selected face outlines and a small cross
+// per selected vertex, in the mesh's local space
+fn selection_lines(cgar_mesh: &CgarMesh<CgarF64, 3>, selection: &SelectionSet) -> Vec<[f32; 3]> {
+    let vertex_count = cgar_mesh.vertices.len();
+    let mut edges: BTreeSet<(usize, usize)> = selection
+        .edges
+        .iter()
+        .filter(|&&(v0, v1)| v0 < vertex_count && v1 < vertex_count)
+        .map(|&(v0, v1)| (v0.min(v1), v0.max(v1)))
+        .collect();
+    for &face in &selection.faces {
+        if cgar_mesh.faces.get(face).is_none_or(|f| f.removed) {
+            continue;
+        }
+        let [a, b, c] = tri_vertices_of_face(cgar_mesh, face);
+        for (v0, v1) in [(a, b), (b, c), (c, a)] {
+            edges.insert((v0.min(v1), v0.max(v1)));
+        }
+    }
+    let mut lines: Vec<[f32; 3]> = edges
+        .into_iter()
+        .flat_map(|(v0, v1)| [v0, v1].map(|v| vertex_position(cgar_mesh, v).to_array()))
+        .collect();
+    for &v in &selection.vertices {
+        if v >= vertex_count {
+            continue;
+        }
+        let p = vertex_position(cgar_mesh, v);
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            let arm = axis * 0.01;
+            lines.push((p - arm).to_array());
+            lines.push((p + arm).to_array());
+        }
+    }
+    lines
+}
+
+// Batched highlight path: the selection becomes one line mesh parented to its
+// mesh, rebuilt only when the selection or the mesh changes, instead of
+// thousands of gizmo calls every frame
+pub fn sync_selection_batch(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    (mut material, mut was_batched): (Local<Option<Handle<StandardMaterial>>>, Local<bool>),
+    selection: Res<SelectionSet>,
+    budget: Res<MeshBudget>,
+    mesh_query: Query<(Entity, Ref<CgarMeshData>)>,
+    batch_query: Query<Entity, With<SelectionBatch>>,
+) {
+    let target = selection
+        .mesh
+        .and_then(|entity| mesh_query.get(entity).ok())
+        .or_else(|| mesh_query.single().ok());
+    let mesh_changed = target
+        .as_ref()
+        .is_some_and(|(_, cgar_data)| cgar_data.is_changed());
+    let batched = budget.batched();
+    let stale = selection.is_changed() || batched != *was_batched || mesh_changed;
+    *was_batched = batched;
+    if !stale {
+        return;
+    }
+    for entity in &batch_query {
+        commands.entity(entity).despawn();
+    }
+    let Some((entity, cgar_data)) = target.filter(|_| batched) else {
+        return;
+    };
+    let lines = selection_lines(&cgar_data.0, &selection);
+    if lines.is_empty() {
+        return;
+    }
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::all());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, lines);
+    let material = material
+        .get_or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color: Color::srgb(1.0, 0.6, 0.1),
+                unlit: true,
+                depth_bias: 1.0,
+                ..default()
+            })
+        })
+        .clone();
+    commands.spawn((
+        SelectionBatch,
+        Mesh3d(meshes.add(mesh)),
+        MeshMaterial3d(material),
+        Transform::default(),
+        Pickable::IGNORE,
+        NotShadowCaster,
+        ChildOf(entity),
+    ));
+}
+
+// Source of a mesh for saved selection sets, hashing it on first use
+fn selection_source(
+    fingerprints: &mut MeshFingerprints,
+    entity: Entity,
+    cgar_data: &CgarMeshData,
+    source: Option<&MeshSource>,
+) -> SelectionSource {
+    let fingerprint = match fingerprints.0.get(&entity) {
+        Some(fingerprint) => *fingerprint,
+        None => fingerprints.compute(entity, cgar_data),
+    };
+    SelectionSource::new(source.map(|source| source.0.as_path()), &fingerprint)
+}
+
+pub fn selection_sets_panel(
+    mut contexts: EguiContexts,
+    mut selection: ResMut<SelectionSet>,
+    mut saved: ResMut<SavedSelections>,
+    mut new_name: Local<String>,
+    mut fingerprints: ResMut<MeshFingerprints>,
+    mut notices: EventWriter<Notify>,
+    mesh_query: Query<(Entity, &CgarMeshData, Option<&MeshSource>)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut pending: Option<(String, SelectionCombine)> = None;
+    let mut removed: Option<String> = None;
+    let mut save_as: Option<String> = None;
+
+    egui::Window::new("Selection Sets").show(ctx, |ui| {
+        ui.label(format!(
+            "Active: {} vertices, {} edges, {} faces",
+            selection.vertices.len(),
+            selection.edges.len(),
+            selection.faces.len()
+        ));
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut *new_name);
+            let name = new_name.trim().to_string();
+            let can_save = !name.is_empty()
+                && !selection.is_empty()
+                && selection.mesh.is_some_and(|mesh| mesh_query.contains(mesh));
+            if ui
+                .add_enabled(can_save, egui::Button::new("Save"))
+                .clicked()
+            {
+                save_as = Some(name);
+                new_name.clear();
+            }
+        });
+        if ui.button("Clear active").clicked() {
+            selection.clear();
+        }
+
+        ui.separator();
+        for (name, saved_set) in &saved.sets {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} ({})", name, saved_set.set.len()))
+                    .on_hover_text(saved_set.source.describe());
+                for (label, mode) in [
+                    ("Recall", SelectionCombine::Replace),
+                    ("Union", SelectionCombine::Union),
+                    ("Intersect", SelectionCombine::Intersect),
+                    ("Subtract", SelectionCombine::Subtract),
+                ] {
+                    if ui.small_button(label).clicked() {
+                        pending = Some((name.clone(), mode));
+                    }
+                }
+                if ui.small_button("Delete").clicked() {
+                    removed = Some(name.clone());
+                }
+            });
+        }
+    });
+
+    if let Some(name) = save_as {
+        let target = selection.mesh.and_then(|mesh| mesh_query.get(mesh).ok());
+        if let Some((entity, cgar_data, source)) = target {
+            let source = selection_source(&mut fingerprints, entity, cgar_data, source);
+            saved.sets.insert(
+                name,
+                SavedSelection {
+                    source,
+                    set: selection.clone(),
+                },
+            );
+        }
+    }
+    if let Some((name, mode)) = pending {
+        if let Some(saved_set) = saved.sets.get(&name) {
+            // Replacing may switch to any mesh the set was taken on, the
+            // active one first; combining needs the active mesh to match
+            let active = selection.mesh.and_then(|mesh| mesh_query.get(mesh).ok());
+            let candidates: Vec<_> = if mode == SelectionCombine::Replace {
+                active
+                    .into_iter()
+                    .chain(
+                        mesh_query
+                            .iter()
+                            .filter(|(entity, ..)| Some(*entity) != selection.mesh),
+                    )
+                    .collect()
+            } else {
+                active.into_iter().collect()
+            };
+            let matching = candidates
+                .into_iter()
+                .filter(|(.., source)| {
+                    source.map(|source| source.0.as_path()) == saved_set.source.path.as_deref()
+                })
+                .find(|&(entity, cgar_data, source)| {
+                    selection_source(&mut fingerprints, entity, cgar_data, source)
+                        == saved_set.source
+                });
+            match matching {
+                Some((entity, ..)) => {
+                    if mode == SelectionCombine::Replace {
+                        selection.mesh = Some(entity);
+                    }
+                    selection.combine(&saved_set.set, mode);
+                }
+                None => {
+                    notices.write(Notify::warning(format!(
+                        "Selection set \"{}\" was saved on {}, which doesn't match the {}",
+                        name,
+                        saved_set.source.describe(),
+                        if mode == SelectionCombine::Replace {
+                            "open meshes"
+                        } else {
+                            "active mesh"
+                        }
+                    )));
+                }
+            }
+        }
+    }
+    if let Some(name) = removed {
+        saved.sets.remove(&name);
+    }
+}
+
+#[derive(Default)]
+pub struct QueryPanelState {
+    text: String,
+    mode: SelectionCombine,
+    status: String,
+}
+
+pub fn query_selection_panel(
+    mut contexts: EguiContexts,
+    mut selection: ResMut<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData)>,
+    mut state: Local<QueryPanelState>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Query Selection").show(ctx, |ui| {
+        ui.label("e.g. face.area < 0.001, vertex.degree > 8, edge.length > 2*mean");
+        let response = ui.text_edit_singleline(&mut state.text);
+        ui.horizontal(|ui| {
+            for mode in SelectionCombine::ALL {
+                ui.radio_value(&mut state.mode, mode, mode.label());
+            }
+        });
+
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if ui.button("Select").clicked() || submitted {
+            let target = selection
+                .mesh
+                .and_then(|entity| mesh_query.get(entity).ok())
+                .or_else(|| mesh_query.iter().next());
+            state.status = match (parse_query(&state.text), target) {
+                (Ok(query), Some((entity, cgar_data))) => {
+                    let topology = MeshTopology::from_cgar(&cgar_data.0);
+                    let matched = query.evaluate(&topology);
+                    if selection.mesh != Some(entity) {
+                        selection.clear();
+                        selection.mesh = Some(entity);
+                    }
+                    selection.combine(&matched, state.mode);
+                    format!("{} elements matched", matched.len())
+                }
+                (Ok(_), None) => "No mesh loaded".to_string(),
+                (Err(err), _) => format!("Error: {}", err),
+            };
+        }
+
+        if !state.status.is_empty() {
+            ui.label(&state.status);
+        }
+    });
+}
+
+// Turning the tool off forgets its seed, so turning it back on doesn't grow
+// over whatever has been selected since
+fn set_region_grow(settings: &mut RegionGrowSettings, enabled: bool) {
+    settings.enabled = enabled;
+    if !enabled {
+        settings.seed = None;
+    }
+}
+
+pub fn toggle_region_grow(kb: Res<ButtonInput<KeyCode>>, mut settings: ResMut<RegionGrowSettings>) {
+    if kb.just_pressed(KeyCode::KeyG) {
+        let enabled = !settings.enabled;
+        set_region_grow(&mut settings, enabled);
+        info!("Region grow: {}", settings.enabled);
+    }
+}
+
+// Recomputes the grown region while the tool is on whenever a seed is
+// picked, even the same face again, the angle or the mode changes, or the
+// seed's mesh is edited. Other changes to the settings leave the selection
+// alone.
+pub fn update_region_grow(
+    settings: Res<RegionGrowSettings>,
+    mut seeded: EventReader<RegionGrowSeeded>,
+    mut grown: Local<Option<((Entity, usize), f32, bool)>>,
+    mut selection: ResMut<SelectionSet>,
+    mesh_query: Query<Ref<CgarMeshData>>,
+) {
+    let reseeded = seeded.read().count() > 0;
+    let Some((entity, seed)) = settings.seed.filter(|_| settings.enabled) else {
+        *grown = None;
+        return;
+    };
+    let Ok(cgar_data) = mesh_query.get(entity) else {
+        return;
+    };
+    let params = Some((
+        (entity, seed),
+        settings.max_angle_deg,
+        settings.relative_to_seed,
+    ));
+    if !reseeded && !cgar_data.is_changed() && *grown == params {
+        return;
+    }
+    *grown = params;
+
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    let region = grow_region(
+        &topology,
+        seed,
+        (settings.max_angle_deg as f64).to_radians(),
+        settings.relative_to_seed,
+    );
+
+    selection.clear();
+    selection.mesh = Some(entity);
+    selection.faces = region;
+}
+
+pub fn region_grow_panel(mut contexts: EguiContexts, mut settings: ResMut<RegionGrowSettings>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Region Grow").show(ctx, |ui| {
+        let mut enabled = settings.enabled;
+        if ui
+            .checkbox(&mut enabled, "Click face to grow (G)")
+            .changed()
+        {
+            set_region_grow(&mut settings, enabled);
+        }
+
+        let mut angle = settings.max_angle_deg;
+        if ui
+            .add(egui::Slider::new(&mut angle, 0.0..=90.0).text("Max angle (deg)"))
+            .changed()
+        {
+            settings.max_angle_deg = angle;
+        }
+
+        let mut relative_to_seed = settings.relative_to_seed;
+        if ui
+            .checkbox(&mut relative_to_seed, "Compare to seed normal (planar)")
+            .changed()
+        {
+            settings.relative_to_seed = relative_to_seed;
+        }
+    });
+}
+
+// Minimum cursor travel, in logical pixels, between sampled lasso points
+const LASSO_STEP_PX: f32 = 3.0;
+
+// Drag a box or lasso with the Area select tool. On release, elements of the
+// selected mesh whose screen position lies inside are selected: Shift adds to
+// the selection and Ctrl removes from it. With `visible_only`, each element
+// is also ray cast from the camera and skipped when something is in front.
+pub fn area_select(
+    tool: Res<State<ActiveTool>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    kb: Res<ButtonInput<KeyCode>>,
+    mut area: ResMut<AreaSelect>,
+    mut selection: ResMut<SelectionSet>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mesh_query: Query<(
+        Entity,
+        &GlobalTransform,
+        &CgarMeshData,
+        Option<&FaceTreeCache>,
+    )>,
+) {
+    if *tool.get() != ActiveTool::AreaSelect {
+        if area.drag.is_some() {
+            area.drag = None;
+        }
+        return;
+    }
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        area.drag = cursor.map(|cursor| vec![cursor, cursor]);
+        return;
+    }
+    if mouse_buttons.pressed(MouseButton::Left) {
+        let shape = area.shape;
+        if let (Some(path), Some(cursor)) = (area.drag.as_mut(), cursor) {
+            match shape {
+                AreaShape::Box => path[1] = cursor,
+                AreaShape::Lasso => {
+                    if path
+                        .last()
+                        .is_none_or(|last| last.distance(cursor) >= LASSO_STEP_PX)
+                    {
+                        path.push(cursor);
+                    }
+                }
+            }
+        }
+        return;
+    }
+    let Some(path) = area.drag.take() else {
+        return;
+    };
+
+    let Some((entity, mesh_global, cgar_data, cache)) = selection
+        .mesh
+        .and_then(|entity| mesh_query.get(entity).ok())
+        .or_else(|| mesh_query.iter().next())
+    else {
+        return;
+    };
+    let Ok((camera, camera_global)) = camera_query.single() else {
+        return;
+    };
+    let topology = MeshTopology::from_cgar(&cgar_data.0);
+    // The cached tree lags a frame behind mesh edits
+    let built;
+    let surface = match cache {
+        Some(cache) => &cache.0,
+        None => {
+            built = FaceSurface::build(&cgar_data.0);
+            &built
+        }
+    };
+    let to_local = mesh_global.affine().inverse();
+    let (shape, visible_only) = (area.shape, area.visible_only);
+    // Screen position of a mesh-local point, if it is inside the area and
+    // not hidden
+    let picked = |local: DVec3| {
+        let world = mesh_global.transform_point(local.as_vec3());
+        let Ok(screen) = camera.world_to_viewport(camera_global, world) else {
+            return false;
+        };
+        if !area_contains(shape, &path, screen) {
+            return false;
+        }
+        if !visible_only {
+            return true;
+        }
+        let Ok(ray) = camera.viewport_to_world(camera_global, screen) else {
+            return false;
+        };
+        let origin = to_local.transform_point3(ray.origin).as_dvec3();
+        let direction = to_local
+            .transform_vector3(ray.direction.as_vec3())
+            .as_dvec3();
+        unoccluded((&cgar_data.0, surface), origin, direction, local)
+    };
+
+    let mut found = SelectionSet::default();
+    match area.element {
+        AreaElement::Vertices => {
+            found.vertices = topology
+                .used_vertices()
+                .filter(|&v| picked(topology.positions[v]))
+                .collect();
+        }
+        AreaElement::Edges => {
+            found.edges = topology
+                .edge_faces
+                .keys()
+                .filter(|&&(a, b)| picked((topology.positions[a] + topology.positions[b]) * 0.5))
+                .copied()
+                .collect();
+        }
+        AreaElement::Faces => {
+            found.faces = topology
+                .live_faces()
+                .filter(|(_, [a, b, c])| {
+                    picked(
+                        (topology.positions[*a] + topology.positions[*b] + topology.positions[*c])
+                            / 3.0,
+                    )
+                })
+                .map(|(face, _)| face)
+                .collect();
+        }
+    }
+
+    let shift = kb.pressed(KeyCode::ShiftLeft) || kb.pressed(KeyCode::ShiftRight);
+    let ctrl = kb.pressed(KeyCode::ControlLeft) || kb.pressed(KeyCode::ControlRight);
+    if selection.mesh != Some(entity) || !(shift || ctrl) {
+        selection.clear();
+        selection.mesh = Some(entity);
+    }
+    let combine = if ctrl {
+        SelectionCombine::Subtract
+    } else {
+        SelectionCombine::Union
+    };
+    selection.combine(&found, combine);
+    info!(
+        "Area select: {} vertices, {} edges, {} faces",
+        found.vertices.len(),
+        found.edges.len(),
+        found.faces.len()
+    );
+}
+
+// Area select options while the tool is active, and the drag outline
+pub fn area_select_panel(
+    mut contexts: EguiContexts,
+    tool: Res<State<ActiveTool>>,
+    mut area: ResMut<AreaSelect>,
+) {
+    if *tool.get() != ActiveTool::AreaSelect {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Area Select").show(ctx, |ui| {
+        let mut shape = area.shape;
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut shape, AreaShape::Box, "Box");
+            ui.radio_value(&mut shape, AreaShape::Lasso, "Lasso");
+        });
+        if shape != area.shape {
+            area.shape = shape;
+        }
+        let mut element = area.element;
+        ui.horizontal(|ui| {
+            for option in AreaElement::ALL {
+                ui.radio_value(&mut element, option, option.label());
+            }
+        });
+        if element != area.element {
+            area.element = element;
+        }
+        let mut visible_only = area.visible_only;
+        ui.checkbox(&mut visible_only, "Visible only")
+            .on_hover_text("Skip elements hidden behind other geometry");
+        if visible_only != area.visible_only {
+            area.visible_only = visible_only;
+        }
+    });
+
+    let Some(path) = &area.drag else {
+        return;
+    };
+    let stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 153, 26));
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("area_select_outline"),
+    ));
+    let points: Vec<egui::Pos2> = path.iter().map(|p| egui::pos2(p.x, p.y)).collect();
+    match area.shape {
+        AreaShape::Box => {
+            painter.rect_stroke(
+                egui::Rect::from_two_pos(points[0], points[points.len() - 1]),
+                0.0,
+                stroke,
+                egui::StrokeKind::Middle,
+            );
+        }
+        AreaShape::Lasso => {
+            painter.add(egui::Shape::closed_line(points, stroke));
+        }
+    }
+}
+
+// Face path tool: the first click marks a face, the second selects the
+// shortest chain of adjacent faces between the two. Shift keeps the current
+// selection, so several paths can be strung together.
+pub fn face_path_on_pick(
+    mut picked: EventReader<MeshPicked>,
+    mut path: ResMut<FacePathPick>,
+    mut selection: ResMut<SelectionSet>,
+    mut notices: EventWriter<Notify>,
+    kb: Res<ButtonInput<KeyCode>>,
+    mesh_query: Query<&CgarMeshData>,
+) {
+    for pick in picked.read().filter(|pick| !pick.double_click) {
+        let Some(hit) = pick.face_hit else {
+            notices.write(Notify::warning("The mesh isn't ready for picking yet"));
+            continue;
+        };
+        let extend = kb.pressed(KeyCode::ShiftLeft) || kb.pressed(KeyCode::ShiftRight);
+        if !extend || selection.mesh != Some(pick.entity) {
+            selection.clear();
+        }
+        selection.mesh = Some(pick.entity);
+
+        let Some((_, from)) = path.start.filter(|(mesh, _)| *mesh == pick.entity) else {
+            path.start = Some((pick.entity, hit.face));
+            selection.faces.insert(hit.face);
+            continue;
+        };
+        path.start = None;
+        let Ok(cgar_data) = mesh_query.get(pick.entity) else {
+            continue;
+        };
+        let topology = MeshTopology::from_cgar(&cgar_data.0);
+        match face_path(&topology, from, hit.face) {
+            Some(faces) => {
+                notices.write(Notify::info(format!(
+                    "Selected a path of {} faces from face {} to face {}",
+                    faces.len(),
+                    from,
+                    hit.face
+                )));
+                selection.faces.extend(faces);
+            }
+            None => {
+                notices.write(Notify::warning(format!(
+                    "Faces {} and {} aren't connected",
+                    from, hit.face
+                )));
+                selection.faces.insert(hit.face);
+            }
+        }
+    }
+}
+
+pub fn exit_face_path(mut path: ResMut<FacePathPick>) {
+    path.start = None;
+}
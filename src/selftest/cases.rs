@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::f64::consts::{PI, TAU};
+
+use bevy::math::DVec3;
+
+use crate::repair::ops::TriangleSoup;
+
+pub struct TestCase {
+    pub name: &'static str,
+    pub soup: TriangleSoup,
+    // Non-manifold cases only have to survive the pipeline without panicking
+    pub manifold: bool,
+}
+
+// Unit square split into (n - 1)^2 quads of two triangles each
+pub fn grid(n: usize) -> TriangleSoup {
+    let n = n.max(2);
+    let step = 1.0 / (n - 1) as f64;
+    let positions = (0..n * n)
+        .map(|i| DVec3::new((i % n) as f64 * step, (i / n) as f64 * step, 0.0))
+        .collect();
+    let id = |x: usize, y: usize| y * n + x;
+    let mut triangles = Vec::with_capacity((n - 1) * (n - 1) * 2);
+    for y in 0..n - 1 {
+        for x in 0..n - 1 {
+            triangles.push([id(x, y), id(x + 1, y), id(x + 1, y + 1)]);
+            triangles.push([id(x, y), id(x + 1, y + 1), id(x, y + 1)]);
+        }
+    }
+    TriangleSoup {
+        positions,
+        triangles,
+    }
+}
+
+// Closed unit sphere with outward-facing triangles
+pub fn uv_sphere(rings: usize, segments: usize) -> TriangleSoup {
+    let (rings, segments) = (rings.max(2), segments.max(3));
+    let mut positions = vec![DVec3::Z];
+    for i in 1..rings {
+        let theta = PI * i as f64 / rings as f64;
+        for j in 0..segments {
+            let phi = TAU * j as f64 / segments as f64;
+            positions.push(DVec3::new(
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            ));
+        }
+    }
+    let bottom = positions.len();
+    positions.push(-DVec3::Z);
+
+    let ring = |i: usize, j: usize| 1 + (i - 1) * segments + j % segments;
+    let mut triangles = Vec::new();
+    for j in 0..segments {
+        triangles.push([0, ring(1, j), ring(1, j + 1)]);
+        for i in 1..rings - 1 {
+            let (a, b) = (ring(i, j), ring(i + 1, j));
+            let (c, d) = (ring(i + 1, j + 1), ring(i, j + 1));
+            triangles.push([a, b, c]);
+            triangles.push([a, c, d]);
+        }
+        triangles.push([bottom, ring(rings - 1, j + 1), ring(rings - 1, j)]);
+    }
+    TriangleSoup {
+        positions,
+        triangles,
+    }
+}
+
+// Three triangles sharing one edge
+pub fn fin() -> TriangleSoup {
+    TriangleSoup {
+        positions: vec![
+            DVec3::new(0.0, 0.0, 0.0),
+            DVec3::new(1.0, 0.0, 0.0),
+            DVec3::new(0.5, 1.0, 0.0),
+            DVec3::new(0.5, -1.0, 0.0),
+            DVec3::new(0.5, 0.0, 1.0),
+        ],
+        triangles: vec![[0, 1, 2], [1, 0, 3], [0, 1, 4]],
+    }
+}
+
+// Two triangles touching at a single vertex
+pub fn bowtie() -> TriangleSoup {
+    TriangleSoup {
+        positions: vec![
+            DVec3::new(0.0, 0.0, 0.0),
+            DVec3::new(1.0, -0.5, 0.0),
+            DVec3::new(1.0, 0.5, 0.0),
+            DVec3::new(-1.0, 0.5, 0.0),
+            DVec3::new(-1.0, -0.5, 0.0),
+        ],
+        triangles: vec![[0, 1, 2], [0, 3, 4]],
+    }
+}
+
+pub fn suite() -> Vec<TestCase> {
+    vec![
+        TestCase {
+            name: "grid 8x8",
+            soup: grid(8),
+            manifold: true,
+        },
+        TestCase {
+            name: "sphere 8x16",
+            soup: uv_sphere(8, 16),
+            manifold: true,
+        },
+        TestCase {
+            name: "fin",
+            soup: fin(),
+            manifold: false,
+        },
+        TestCase {
+            name: "bowtie",
+            soup: bowtie(),
+            manifold: false,
+        },
+    ]
+}
@@ -0,0 +1,370 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use bevy::{
+    app::AppExit,
+    ecs::{
+        event::EventWriter,
+        query::With,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    log::{error, info},
+    math::{DVec3, Quat, Vec3},
+    render::{
+        camera::Camera,
+        mesh::{Mesh, VertexAttributeValues},
+    },
+    transform::components::{GlobalTransform, Transform},
+};
+use cgar::geometry::{Point3, Vector3};
+use cgar::mesh::basic_types::{IntersectionHit, IntersectionResult, Mesh as CgarMesh};
+use cgar::numeric::cgar_f64::CgarF64;
+
+use crate::camera::components::OrbitCamera;
+use crate::mesh::conversion::{build_cgar_mesh, cgar_to_bevy_mesh, cgar_to_bevy_mesh_unshared};
+use crate::mesh::edge::{PickSettings, local_pixel_size};
+use crate::mesh::normals::NormalWeighting;
+use crate::mesh::topology::MeshTopology;
+use crate::perturb::systems::find_issues;
+use crate::selftest::cases::{TestCase, suite};
+use crate::utils::cli::CliOptions;
+
+// Faces picked per case, spread evenly over the face list
+const PICKS_PER_CASE: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub case: &'static str,
+    pub check: &'static str,
+    pub passed: bool,
+    // Expected versus actual when the check failed, otherwise a short note
+    pub detail: String,
+}
+
+// Regression suite over generated meshes: conversion, picking through the
+// live camera and edge collapse. Runs once the scene is up, prints a report
+// and exits with a failure code if any check failed.
+#[derive(Resource)]
+pub struct SelfTest {
+    pub requested: bool,
+    pub results: Vec<CheckResult>,
+}
+
+impl SelfTest {
+    pub fn from_options(options: &CliOptions) -> Self {
+        Self {
+            requested: options.selftest,
+            results: Vec::new(),
+        }
+    }
+
+    pub fn failures(&self) -> usize {
+        self.results.iter().filter(|result| !result.passed).count()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut out = String::new();
+        for result in &self.results {
+            out += &format!(
+                "{:<4} {:<12} {:<12} {}\n",
+                if result.passed { "ok" } else { "FAIL" },
+                result.case,
+                result.check,
+                result.detail
+            );
+        }
+        out += &format!(
+            "{} checks, {} failed\n",
+            self.results.len(),
+            self.failures()
+        );
+        out
+    }
+}
+
+fn check(case: &'static str, check: &'static str, outcome: Result<String, String>) -> CheckResult {
+    let passed = outcome.is_ok();
+    CheckResult {
+        case,
+        check,
+        passed,
+        detail: outcome.unwrap_or_else(|err| err),
+    }
+}
+
+fn expect_eq(what: &str, expected: usize, actual: usize) -> Result<(), String> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!("{}: expected {}, got {}", what, expected, actual))
+    }
+}
+
+fn check_conversion(
+    mesh: &CgarMesh<CgarF64, 3>,
+    topology: &MeshTopology,
+) -> Result<String, String> {
+    let corners = topology.live_faces().count() * 3;
+    let shared = cgar_to_bevy_mesh(mesh, NormalWeighting::default());
+    expect_eq(
+        "shared vertices",
+        mesh.vertices.len(),
+        shared.count_vertices(),
+    )?;
+    expect_eq(
+        "shared indices",
+        corners,
+        shared.indices().map_or(0, |indices| indices.len()),
+    )?;
+    let unshared = cgar_to_bevy_mesh_unshared(mesh, &[], None, None, NormalWeighting::default());
+    expect_eq("unshared vertices", corners, unshared.count_vertices())?;
+
+    for (label, rendered) in [("shared", &shared), ("unshared", &unshared)] {
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            rendered.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            return Err(format!("{} mesh has no normals", label));
+        };
+        if let Some((i, n)) = normals
+            .iter()
+            .enumerate()
+            .find(|(_, n)| (Vec3::from(**n).length() - 1.0).abs() > 1e-3)
+        {
+            return Err(format!(
+                "{} normal {}: expected unit length, got {:?}",
+                label, i, n
+            ));
+        }
+    }
+    Ok(format!("{} corners", corners))
+}
+
+// Places the case in front of the camera, facing it with a slight tilt so
+// no face is seen edge-on
+fn framing(topology: &MeshTopology, camera_global: &GlobalTransform) -> GlobalTransform {
+    let (min, max) = topology.positions.iter().fold(
+        (DVec3::splat(f64::INFINITY), DVec3::splat(f64::NEG_INFINITY)),
+        |(min, max), p| (min.min(*p), max.max(*p)),
+    );
+    let center = ((min + max) * 0.5).as_vec3();
+    let scale = 1.0 / (max - min).length().max(f64::EPSILON) as f32;
+    let rotation = camera_global.rotation() * Quat::from_rotation_x(0.3);
+    let target = camera_global.translation() + camera_global.forward() * 2.0;
+    GlobalTransform::from(
+        Transform::from_translation(target - rotation * (center * scale))
+            .with_rotation(rotation)
+            .with_scale(Vec3::splat(scale)),
+    )
+}
+
+// Projects face centroids to the screen and casts them back through the
+// camera with cgar's `cast_ray`, using the click tolerance `handle_mesh_click`
+// derives from the pick radius. The ray has to land on the same face, or on
+// one of its sides or corners within that tolerance, unless something
+// nearer hides it.
+fn check_picking(
+    mesh: &CgarMesh<CgarF64, 3>,
+    topology: &MeshTopology,
+    camera: &Camera,
+    camera_global: &GlobalTransform,
+    pick_settings: &PickSettings,
+) -> Result<String, String> {
+    let mesh_global = framing(topology, camera_global);
+    let to_local = mesh_global.affine().inverse();
+    let tree = mesh.build_face_tree();
+    let faces: Vec<(usize, [usize; 3])> = topology.live_faces().collect();
+    let step = (faces.len() / PICKS_PER_CASE).max(1);
+    let (mut hits, mut hidden) = (0, 0);
+    for &(face, tri) in faces.iter().step_by(step) {
+        let [a, b, c] = topology.corners(tri);
+        let centroid = (a + b + c) / 3.0;
+        let world = mesh_global.transform_point(centroid.as_vec3());
+        let screen = camera
+            .world_to_viewport(camera_global, world)
+            .map_err(|err| format!("face {} centroid off screen: {:?}", face, err))?;
+        let ray = camera
+            .viewport_to_world(camera_global, screen)
+            .map_err(|err| format!("no ray through {}: {:?}", screen, err))?;
+        let origin = to_local.transform_point3(ray.origin).as_dvec3();
+        let direction = to_local
+            .transform_vector3(ray.direction.as_vec3())
+            .as_dvec3()
+            .normalize();
+        let tolerance = local_pixel_size(camera, camera_global, &mesh_global, world)
+            .map_or(0.05, |pixel| (pixel * pick_settings.radius_px) as f64);
+
+        let result = mesh.cast_ray(
+            &Point3::<CgarF64>::from_vals([origin.x, origin.y, origin.z]),
+            &Vector3::<CgarF64>::from_vals([direction.x, direction.y, direction.z]),
+            &tree,
+            &Some(CgarF64::from(tolerance)),
+        );
+        let IntersectionResult::Hit(hit, distance) = result else {
+            return Err(format!(
+                "face {} at {}: expected a hit, got none",
+                face, screen
+            ));
+        };
+        let point = origin + direction * distance.0;
+        let (on_face, got) = match hit {
+            IntersectionHit::Face(hit_face, _) => (hit_face == face, format!("face {}", hit_face)),
+            IntersectionHit::Edge(v0, v1, _) => (
+                tri.contains(&v0) && tri.contains(&v1),
+                format!("edge ({}, {})", v0, v1),
+            ),
+            _ => (false, "a vertex".to_string()),
+        };
+        if on_face || point.distance(centroid) <= tolerance {
+            hits += 1;
+        } else if (centroid - origin).length() - (point - origin).length() > tolerance {
+            hidden += 1;
+        } else {
+            return Err(format!(
+                "pixel {}: expected face {}, got {}",
+                screen, face, got
+            ));
+        }
+    }
+    Ok(format!("{} picked, {} hidden", hits, hidden))
+}
+
+// Manifold cases must collapse an edge cleanly; the rest only must not panic
+fn check_collapse(case: &TestCase, topology: &MeshTopology) -> Result<String, String> {
+    let mut mesh = build_cgar_mesh(&case.soup.positions, case.soup.triangles.iter().copied());
+    let before = topology.live_faces().count();
+    for &(v0, v1) in topology.edge_faces.keys() {
+        let collapsed = catch_unwind(AssertUnwindSafe(|| mesh.collapse_edge(v0, v1)))
+            .map_err(|_| format!("collapse_edge({}, {}) panicked", v0, v1))?;
+        if collapsed.is_err() {
+            continue;
+        }
+        let after = MeshTopology::from_cgar(&mesh);
+        let issues = find_issues(&after);
+        if !case.manifold {
+            return Ok(format!("collapsed ({}, {})", v0, v1));
+        }
+        let removed = before - after.live_faces().count();
+        if removed != 1 && removed != 2 {
+            return Err(format!(
+                "collapse ({}, {}): expected 1 or 2 faces removed, got {}",
+                v0, v1, removed
+            ));
+        }
+        if !issues.is_empty() {
+            return Err(format!("collapse ({}, {}): {}", v0, v1, issues.join(", ")));
+        }
+        return Ok(format!("collapsed ({}, {})", v0, v1));
+    }
+    if case.manifold {
+        Err("expected a collapsible edge, every collapse was rejected".to_string())
+    } else {
+        Ok("every collapse rejected".to_string())
+    }
+}
+
+fn run_case(
+    case: &TestCase,
+    camera: &Camera,
+    camera_global: &GlobalTransform,
+    pick_settings: &PickSettings,
+    results: &mut Vec<CheckResult>,
+) {
+    let built = catch_unwind(AssertUnwindSafe(|| {
+        build_cgar_mesh(&case.soup.positions, case.soup.triangles.iter().copied())
+    }));
+    let Ok(mesh) = built else {
+        results.push(check(
+            case.name,
+            "build",
+            Err("construction panicked".to_string()),
+        ));
+        return;
+    };
+    let topology = MeshTopology::from_cgar(&mesh);
+    results.push(check(
+        case.name,
+        "build",
+        expect_eq(
+            "faces",
+            case.soup.triangles.len(),
+            topology.live_faces().count(),
+        )
+        .map(|_| format!("{} faces", case.soup.triangles.len())),
+    ));
+    results.push(check(
+        case.name,
+        "conversion",
+        catch_unwind(AssertUnwindSafe(|| check_conversion(&mesh, &topology)))
+            .unwrap_or_else(|_| Err("conversion panicked".to_string())),
+    ));
+    results.push(check(
+        case.name,
+        "picking",
+        catch_unwind(AssertUnwindSafe(|| {
+            check_picking(&mesh, &topology, camera, camera_global, pick_settings)
+        }))
+        .unwrap_or_else(|_| Err("cast_ray panicked".to_string())),
+    ));
+    results.push(check(
+        case.name,
+        "collapse",
+        check_collapse(case, &topology),
+    ));
+}
+
+pub fn run_selftest(
+    mut selftest: ResMut<SelfTest>,
+    pick_settings: Res<PickSettings>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if !selftest.requested {
+        return;
+    }
+    let Ok((camera, camera_global)) = camera_query.single() else {
+        return;
+    };
+    // Picking needs the viewport, which is only known once the render target is set up
+    if camera.logical_viewport_size().is_none() {
+        return;
+    }
+    selftest.requested = false;
+
+    let mut results = Vec::new();
+    for case in suite() {
+        run_case(&case, camera, camera_global, &pick_settings, &mut results);
+    }
+    selftest.results = results;
+
+    let summary = selftest.summary();
+    println!("{}", summary);
+    if selftest.failures() == 0 {
+        info!("Self-test passed");
+        exit.write(AppExit::Success);
+    } else {
+        error!("Self-test failed: {} checks", selftest.failures());
+        exit.write(AppExit::error());
+    }
+}
@@ -0,0 +1,359 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::path::PathBuf;
+
+use bevy::{
+    app::AppExit,
+    asset::Assets,
+    ecs::{
+        change_detection::DetectChanges,
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        name::Name,
+        query::With,
+        removal_detection::RemovedComponents,
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+        world::Ref,
+    },
+    log::info,
+    math::{DVec3, Quat, Vec3},
+    render::mesh::Mesh,
+    tasks::{IoTaskPool, Task, block_on, futures_lite::future},
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use serde::{Deserialize, Serialize};
+
+use crate::camera::components::CgarMeshData;
+use crate::mesh::conversion::build_cgar_mesh;
+use crate::mesh::setup::{DefaultMeshMaterial, MeshSource, spawn_cgar_mesh};
+use crate::mesh::topology::MeshTopology;
+use crate::notifications::systems::Notify;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+use crate::session::storage::{
+    read_orphaned_autosave, remove_autosave, remove_orphaned_autosave, write_autosave_text,
+};
+
+// Geometry and placement of one mesh at the time of the snapshot. The viewer
+// keeps no operation log, so edits are captured as the resulting geometry.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AutosavedMesh {
+    pub name: String,
+    #[serde(default)]
+    pub source: Option<PathBuf>,
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+    pub positions: Vec<[f64; 3]>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+// Crash-recovery snapshot, serialized as RON. It is removed on a clean exit,
+// so finding one whose process is gone means that run did not shut down.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct AutosaveFile {
+    pub meshes: Vec<AutosavedMesh>,
+}
+
+#[derive(Resource)]
+pub struct Autosave {
+    pub enabled: bool,
+    pub interval_secs: f32,
+    // Snapshot left behind by a previous run, waiting for restore or discard
+    pub recovered: Option<AutosaveFile>,
+    pub restore_requested: bool,
+    pub status: String,
+    elapsed: f32,
+    dirty: bool,
+    // Storage id of `recovered`, to remove it once dealt with
+    recovered_id: Option<String>,
+    // Snapshot being serialized and written, with its mesh count
+    writing: Option<Task<Result<usize, String>>>,
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 60.0,
+            recovered: None,
+            restore_requested: false,
+            status: String::new(),
+            elapsed: 0.0,
+            dirty: false,
+            recovered_id: None,
+            writing: None,
+        }
+    }
+}
+
+fn snapshot_mesh(
+    name: Option<&Name>,
+    entity: Entity,
+    cgar_data: &CgarMeshData,
+    global: &GlobalTransform,
+    source: Option<&MeshSource>,
+) -> AutosavedMesh {
+    // Groups aren't saved, so meshes are restored where they sat in the world
+    let transform = global.compute_transform();
+    let soup = TriangleSoup::from_topology(&MeshTopology::from_cgar(&cgar_data.0));
+    AutosavedMesh {
+        name: name.map_or_else(|| entity.to_string(), |name| name.to_string()),
+        source: source.map(|source| source.0.clone()),
+        translation: transform.translation.to_array(),
+        rotation: transform.rotation.to_array(),
+        scale: transform.scale.to_array(),
+        positions: soup.positions.iter().map(|p| p.to_array()).collect(),
+        triangles: soup.triangles,
+    }
+}
+
+// Offers the autosave of a run that crashed. Snapshots of viewers that are
+// still open are left to them.
+pub fn check_recovery(mut autosave: ResMut<Autosave>, mut notices: EventWriter<Notify>) {
+    let Some((id, text)) = read_orphaned_autosave() else {
+        return;
+    };
+    match ron::from_str::<AutosaveFile>(&text) {
+        Ok(file) if !file.meshes.is_empty() => {
+            info!("Found autosave with {} meshes", file.meshes.len());
+            autosave.recovered = Some(file);
+            autosave.recovered_id = Some(id);
+        }
+        Ok(_) => remove_orphaned_autosave(&id),
+        Err(err) => {
+            notices.write(Notify::warning(format!(
+                "Discarding unreadable autosave: {}",
+                err
+            )));
+            remove_orphaned_autosave(&id);
+        }
+    }
+}
+
+// Takes the recovered snapshot and removes it from storage
+fn take_recovered(autosave: &mut Autosave) -> Option<AutosaveFile> {
+    if let Some(id) = autosave.recovered_id.take() {
+        remove_orphaned_autosave(&id);
+    }
+    autosave.recovered.take()
+}
+
+// Writes a snapshot every `interval_secs` while there are unsaved edits.
+// The meshes are copied here and serialized and written on the IO task
+// pool, one snapshot at a time.
+pub fn autosave_session(
+    mut autosave: ResMut<Autosave>,
+    mut primed: Local<bool>,
+    time: Res<Time>,
+    mut removed: RemovedComponents<CgarMeshData>,
+    mut notices: EventWriter<Notify>,
+    mesh_query: Query<(
+        Entity,
+        Ref<CgarMeshData>,
+        Ref<Transform>,
+        &GlobalTransform,
+        Option<&Name>,
+        Option<&MeshSource>,
+    )>,
+) {
+    let removed_any = removed.read().count() > 0;
+    let written = autosave
+        .writing
+        .as_mut()
+        .and_then(|task| block_on(future::poll_once(task)));
+    if let Some(written) = written {
+        autosave.writing = None;
+        match written {
+            Ok(count) => autosave.status = format!("Autosaved {} meshes", count),
+            Err(err) => {
+                autosave.status = "Autosave failed".to_string();
+                notices.write(Notify::error(format!("Autosave failed: {}", err)));
+            }
+        }
+    }
+    // Meshes spawned at startup are the files as loaded, not edits
+    if !*primed {
+        *primed = true;
+        return;
+    }
+    let changed = mesh_query
+        .iter()
+        .any(|(_, cgar_data, transform, ..)| cgar_data.is_changed() || transform.is_changed());
+    if changed || removed_any {
+        autosave.dirty = true;
+    }
+    if !autosave.enabled || autosave.writing.is_some() {
+        return;
+    }
+    autosave.elapsed += time.delta_secs();
+    if !autosave.dirty || autosave.elapsed < autosave.interval_secs {
+        return;
+    }
+    autosave.elapsed = 0.0;
+    autosave.dirty = false;
+
+    let file = AutosaveFile {
+        meshes: mesh_query
+            .iter()
+            .map(|(entity, cgar_data, _, global, name, source)| {
+                snapshot_mesh(name, entity, &cgar_data, global, source)
+            })
+            .collect(),
+    };
+    autosave.writing = Some(IoTaskPool::get().spawn(async move {
+        let text = ron::ser::to_string(&file).map_err(|e| e.to_string())?;
+        write_autosave_text(&text)?;
+        Ok(file.meshes.len())
+    }));
+}
+
+// Replaces the meshes loaded at startup with the recovered snapshot
+pub fn restore_autosave(
+    mut commands: Commands,
+    mut autosave: ResMut<Autosave>,
+    mut selection: ResMut<SelectionSet>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Res<DefaultMeshMaterial>,
+    mut notices: EventWriter<Notify>,
+    mesh_query: Query<Entity, With<CgarMeshData>>,
+) {
+    if !autosave.restore_requested {
+        return;
+    }
+    autosave.restore_requested = false;
+    let Some(file) = take_recovered(&mut autosave) else {
+        return;
+    };
+    for entity in &mesh_query {
+        commands.entity(entity).despawn();
+    }
+    selection.clear();
+    for saved in &file.meshes {
+        let positions: Vec<DVec3> = saved.positions.iter().map(|&p| DVec3::from(p)).collect();
+        let transform = Transform {
+            translation: Vec3::from(saved.translation),
+            rotation: Quat::from_array(saved.rotation),
+            scale: Vec3::from(saved.scale),
+        };
+        let entity = spawn_cgar_mesh(
+            &mut commands,
+            &mut meshes,
+            &material,
+            saved.name.clone(),
+            build_cgar_mesh(&positions, saved.triangles.iter().copied()),
+            transform,
+        );
+        if let Some(source) = &saved.source {
+            commands.entity(entity).insert(MeshSource(source.clone()));
+        }
+    }
+    // The restored state is only on disk until the next snapshot is written
+    autosave.dirty = true;
+    autosave.elapsed = autosave.interval_secs;
+    notices.write(Notify::info(format!(
+        "Restored {} meshes from autosave",
+        file.meshes.len()
+    )));
+}
+
+// A clean shutdown leaves nothing to recover
+pub fn clear_autosave_on_exit(mut exits: EventReader<AppExit>, mut autosave: ResMut<Autosave>) {
+    if exits.read().count() == 0 {
+        return;
+    }
+    // A write still in flight would put the snapshot back after removal
+    if let Some(task) = autosave.writing.take() {
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        let _ = block_on(task);
+        // The browser runs tasks on this thread, so waiting would never return
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        drop(task);
+    }
+    remove_autosave();
+}
+
+pub fn autosave_panel(mut contexts: EguiContexts, mut autosave: ResMut<Autosave>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    if let Some(file) = &autosave.recovered {
+        let mut restore = false;
+        let mut discard = false;
+        egui::Window::new("Recover session")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("The previous session did not exit cleanly. Restore its autosave?");
+                ui.separator();
+                for saved in &file.meshes {
+                    let source = saved
+                        .source
+                        .as_ref()
+                        .map_or_else(|| "unsaved".to_string(), |p| p.display().to_string());
+                    ui.label(format!(
+                        "{} ({} faces, from {})",
+                        saved.name,
+                        saved.triangles.len(),
+                        source
+                    ));
+                }
+                ui.horizontal(|ui| {
+                    restore = ui.button("Restore").clicked();
+                    discard = ui.button("Discard").clicked();
+                });
+            });
+        if restore {
+            autosave.restore_requested = true;
+        } else if discard {
+            take_recovered(&mut autosave);
+        }
+    }
+
+    egui::Window::new("Autosave")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut enabled = autosave.enabled;
+            ui.checkbox(&mut enabled, "Autosave edits");
+            if enabled != autosave.enabled {
+                autosave.enabled = enabled;
+            }
+            let mut interval = autosave.interval_secs;
+            ui.add(
+                egui::Slider::new(&mut interval, 10.0..=600.0)
+                    .text("Interval (s)")
+                    .logarithmic(true),
+            );
+            if interval != autosave.interval_secs {
+                autosave.interval_secs = interval;
+            }
+            if !autosave.status.is_empty() {
+                ui.label(&autosave.status);
+            }
+        });
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+
+use bevy::ecs::{change_detection::ResMut, resource::Resource};
+use bevy_inspector_egui::egui;
+use serde::{Serialize, de::DeserializeOwned};
+
+// Named parameter sets for operation dialogs, persisted in the session file
+#[derive(Resource, Default, Debug)]
+pub struct OperationPresets {
+    // Operation -> preset name -> parameters as RON
+    pub presets: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+// Parameters of one operation dialog that can be saved as a preset. Fields
+// missing from an older preset should fall back through `#[serde(default)]`.
+pub trait PresetParams: Serialize + DeserializeOwned {
+    // Key the presets are stored under
+    const OPERATION: &'static str;
+}
+
+// Collapsible list of `P`'s presets with a field to save `current` as a new
+// one. Returns the parameters of the preset the user applied, if any.
+pub fn preset_section<P: PresetParams>(
+    ui: &mut egui::Ui,
+    presets: &mut ResMut<OperationPresets>,
+    current: &P,
+) -> Option<P> {
+    let mut applied = None;
+    let mut removed = None;
+    ui.collapsing("Presets", |ui| {
+        if let Some(saved) = presets.presets.get(P::OPERATION) {
+            for (name, params) in saved {
+                ui.horizontal(|ui| {
+                    ui.label(name);
+                    if ui.small_button("Apply").clicked() {
+                        applied = ron::from_str::<P>(params).ok();
+                    }
+                    if ui.small_button("Delete").clicked() {
+                        removed = Some(name.clone());
+                    }
+                });
+            }
+        }
+        // The name being typed lives in egui memory, one per operation
+        let id = ui.make_persistent_id(("preset_name", P::OPERATION));
+        let mut new_name = ui.data(|d| d.get_temp::<String>(id).unwrap_or_default());
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut new_name);
+            let name = new_name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new("Save current"))
+                .clicked()
+            {
+                if let Ok(params) = ron::to_string(current) {
+                    presets
+                        .presets
+                        .entry(P::OPERATION.to_string())
+                        .or_default()
+                        .insert(name, params);
+                }
+                new_name.clear();
+            }
+        });
+        ui.data_mut(|d| d.insert_temp(id, new_name));
+    });
+
+    if let Some(name) = removed {
+        presets
+            .presets
+            .entry(P::OPERATION.to_string())
+            .or_default()
+            .remove(&name);
+    }
+    applied
+}
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Where the session text lives: a file in the per-user config directory on
+// desktop, localStorage (keyed by the file name) in the browser build. The
+// autosave snapshot goes to the temp directory instead, one file per process
+// next to a lock file that process holds until it exits. A snapshot is only
+// offered for recovery once nothing holds its lock.
+
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+mod imp {
+    use std::fs::{File, TryLockError};
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+
+    use crate::utils::constants::{AUTOSAVE_FILE_STEM, CONFIG_DIR_NAME, SESSION_FILE_NAME};
+
+    // This process's lock file, kept open and locked until it exits
+    static OWNER_LOCK: OnceLock<Option<File>> = OnceLock::new();
+
+    // %APPDATA% on Windows, ~/Library/Application Support on macOS and
+    // $XDG_CONFIG_HOME or ~/.config elsewhere; the working directory when
+    // none of these is set
+    fn session_path() -> PathBuf {
+        let var = |name: &str| {
+            std::env::var_os(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+        let config = if cfg!(windows) {
+            var("APPDATA")
+        } else if cfg!(target_os = "macos") {
+            var("HOME").map(|home| home.join("Library").join("Application Support"))
+        } else {
+            var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))
+        };
+        match config {
+            Some(dir) => dir.join(CONFIG_DIR_NAME).join(SESSION_FILE_NAME),
+            None => PathBuf::from(SESSION_FILE_NAME),
+        }
+    }
+
+    pub fn session_location() -> String {
+        session_path().display().to_string()
+    }
+
+    pub fn read_session_text() -> Option<String> {
+        std::fs::read_to_string(session_path()).ok()
+    }
+
+    pub fn write_session_text(text: &str) -> Result<(), String> {
+        let path = session_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(path, text).map_err(|e| e.to_string())
+    }
+
+    fn autosave_path(pid: u32) -> PathBuf {
+        std::env::temp_dir().join(format!("{}.{}.ron", AUTOSAVE_FILE_STEM, pid))
+    }
+
+    fn lock_path(pid: u32) -> PathBuf {
+        std::env::temp_dir().join(format!("{}.{}.lock", AUTOSAVE_FILE_STEM, pid))
+    }
+
+    fn claim_lock() {
+        OWNER_LOCK.get_or_init(|| {
+            let file = File::create(lock_path(std::process::id())).ok()?;
+            file.try_lock().ok()?;
+            Some(file)
+        });
+    }
+
+    // A missing lock file or one nobody holds means the writer is gone
+    fn owner_running(pid: u32) -> bool {
+        let Ok(file) = File::open(lock_path(pid)) else {
+            return false;
+        };
+        matches!(file.try_lock(), Err(TryLockError::WouldBlock))
+    }
+
+    // Newest autosave whose process is no longer running, with the id to
+    // remove it by
+    pub fn read_orphaned_autosave() -> Option<(String, String)> {
+        claim_lock();
+        let own = std::process::id();
+        std::fs::read_dir(std::env::temp_dir())
+            .ok()?
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let pid: u32 = name
+                    .strip_prefix(AUTOSAVE_FILE_STEM)?
+                    .strip_prefix('.')?
+                    .strip_suffix(".ron")?
+                    .parse()
+                    .ok()?;
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                (pid != own && !owner_running(pid)).then_some((modified, pid))
+            })
+            .max()
+            .and_then(|(_, pid)| {
+                let text = std::fs::read_to_string(autosave_path(pid)).ok()?;
+                Some((pid.to_string(), text))
+            })
+    }
+
+    pub fn remove_orphaned_autosave(id: &str) {
+        if let Ok(pid) = id.parse() {
+            let _ = std::fs::remove_file(autosave_path(pid));
+            let _ = std::fs::remove_file(lock_path(pid));
+        }
+    }
+
+    // Written beside the target and renamed over it, so a crash mid-write
+    // never leaves a truncated snapshot behind
+    pub fn write_autosave_text(text: &str) -> Result<(), String> {
+        claim_lock();
+        let path = autosave_path(std::process::id());
+        let partial = path.with_extension("ron.partial");
+        std::fs::write(&partial, text).map_err(|e| e.to_string())?;
+        std::fs::rename(&partial, &path).map_err(|e| e.to_string())
+    }
+
+    pub fn remove_autosave() {
+        let pid = std::process::id();
+        let _ = std::fs::remove_file(autosave_path(pid));
+        let _ = std::fs::remove_file(lock_path(pid));
+    }
+}
+
+// localStorage is shared by every tab of the origin and has no locks, so the
+// browser build keeps a single snapshot
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod imp {
+    use crate::utils::constants::{AUTOSAVE_FILE_STEM, SESSION_FILE_NAME};
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    fn autosave_key() -> String {
+        format!("{}.ron", AUTOSAVE_FILE_STEM)
+    }
+
+    pub fn session_location() -> String {
+        format!("localStorage[{}]", SESSION_FILE_NAME)
+    }
+
+    pub fn read_session_text() -> Option<String> {
+        local_storage()?.get_item(SESSION_FILE_NAME).ok()?
+    }
+
+    pub fn write_session_text(text: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or_else(|| "localStorage is unavailable".to_string())?
+            .set_item(SESSION_FILE_NAME, text)
+            .map_err(|e| format!("{:?}", e))
+    }
+
+    pub fn read_orphaned_autosave() -> Option<(String, String)> {
+        let key = autosave_key();
+        let text = local_storage()?.get_item(&key).ok()??;
+        Some((key, text))
+    }
+
+    pub fn remove_orphaned_autosave(key: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+
+    pub fn write_autosave_text(text: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or_else(|| "localStorage is unavailable".to_string())?
+            .set_item(&autosave_key(), text)
+            .map_err(|e| format!("{:?}", e))
+    }
+
+    pub fn remove_autosave() {
+        remove_orphaned_autosave(&autosave_key());
+    }
+}
+
+pub use imp::{
+    read_orphaned_autosave, read_session_text, remove_autosave, remove_orphaned_autosave,
+    session_location, write_autosave_text, write_session_text,
+};
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+
+use bevy::{
+    ecs::{
+        event::EventWriter,
+        system::{Res, ResMut},
+    },
+    log::info,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::colormap::ColorRamp;
+use crate::analysis::ramps::Colormaps;
+use crate::notifications::systems::Notify;
+use crate::selection::components::{SavedSelection, SavedSelections};
+use crate::session::presets::OperationPresets;
+use crate::session::storage::{read_session_text, session_location, write_session_text};
+
+// On-disk session state, serialized as RON
+#[derive(Serialize, Deserialize, Default)]
+pub struct SessionFile {
+    #[serde(default)]
+    pub selection_sets: BTreeMap<String, SavedSelection>,
+    #[serde(default)]
+    pub color_ramps: Vec<ColorRamp>,
+    #[serde(default)]
+    pub sequential_ramp: Option<String>,
+    #[serde(default)]
+    pub diverging_ramp: Option<String>,
+    #[serde(default)]
+    pub presets: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+pub fn load_session(
+    mut saved: ResMut<SavedSelections>,
+    mut colormaps: ResMut<Colormaps>,
+    mut presets: ResMut<OperationPresets>,
+    mut notices: EventWriter<Notify>,
+) {
+    let Some(text) = read_session_text() else {
+        return;
+    };
+    match ron::from_str::<SessionFile>(&text) {
+        Ok(session) => {
+            saved.sets = session.selection_sets;
+            colormaps.custom = session.color_ramps;
+            if let Some(name) = session.sequential_ramp {
+                colormaps.sequential = name;
+            }
+            if let Some(name) = session.diverging_ramp {
+                colormaps.diverging = name;
+            }
+            presets.presets = session.presets;
+            info!("Loaded session from {}", session_location());
+        }
+        Err(err) => {
+            notices.write(Notify::error(format!(
+                "Failed to parse {}: {}",
+                session_location(),
+                err
+            )));
+        }
+    }
+}
+
+// Writes the session back whenever persisted state changes
+pub fn save_session(
+    saved: Res<SavedSelections>,
+    colormaps: Res<Colormaps>,
+    presets: Res<OperationPresets>,
+    mut notices: EventWriter<Notify>,
+) {
+    let dirty = (saved.is_changed() && !saved.is_added())
+        || (colormaps.is_changed() && !colormaps.is_added())
+        || (presets.is_changed() && !presets.is_added());
+    if !dirty {
+        return;
+    }
+    let session = SessionFile {
+        selection_sets: saved.sets.clone(),
+        color_ramps: colormaps.custom.clone(),
+        sequential_ramp: Some(colormaps.sequential.clone()),
+        diverging_ramp: Some(colormaps.diverging.clone()),
+        presets: presets.presets.clone(),
+    };
+    let text = match ron::ser::to_string_pretty(&session, ron::ser::PrettyConfig::default()) {
+        Ok(text) => text,
+        Err(err) => {
+            notices.write(Notify::error(format!(
+                "Failed to serialize session: {}",
+                err
+            )));
+            return;
+        }
+    };
+    if let Err(err) = write_session_text(&text) {
+        notices.write(Notify::error(format!(
+            "Failed to write {}: {}",
+            session_location(),
+            err
+        )));
+    }
+}
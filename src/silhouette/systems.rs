@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    color::Color,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::{Changed, With},
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    input::{ButtonInput, keyboard::KeyCode},
+    log::info,
+    math::{DVec3, Vec3},
+    render::{camera::Projection, view::InheritedVisibility},
+    transform::components::GlobalTransform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::mesh::topology::MeshTopology;
+
+// View-dependent outline: edges between a face turned toward the camera and
+// one turned away, recomputed every frame. Press O to toggle.
+#[derive(Resource)]
+pub struct SilhouetteSettings {
+    pub enabled: bool,
+    pub include_boundary: bool,
+    // Edges whose two faces disagree on winding; these also show up as
+    // silhouettes across what should be smooth surface
+    pub show_orientation_errors: bool,
+}
+
+impl Default for SilhouetteSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            include_boundary: true,
+            show_orientation_errors: true,
+        }
+    }
+}
+
+pub struct SilhouetteEdge {
+    pub endpoints: (usize, usize),
+    // Incident faces; one for boundary edges, more than two when non-manifold
+    pub faces: Vec<usize>,
+    // Manifold edge whose faces traverse it in the same direction
+    pub flipped: bool,
+}
+
+// Per-mesh data the per-frame pass needs, rebuilt when the mesh changes
+#[derive(Component)]
+pub struct SilhouetteCache {
+    pub positions: Vec<Vec3>,
+    // A corner and the unit normal of every cgar face; `None` if removed
+    pub faces: Vec<Option<(DVec3, DVec3)>>,
+    pub edges: Vec<SilhouetteEdge>,
+}
+
+impl SilhouetteCache {
+    pub fn build(topology: &MeshTopology) -> Self {
+        let directed = |f: usize, a: usize, b: usize| {
+            topology.triangles[f]
+                .is_some_and(|tri| (0..3).any(|k| tri[k] == a && tri[(k + 1) % 3] == b))
+        };
+        let edges = topology
+            .edge_faces
+            .iter()
+            .map(|(&(a, b), faces)| SilhouetteEdge {
+                endpoints: (a, b),
+                faces: faces.clone(),
+                flipped: faces.len() == 2 && directed(faces[0], a, b) == directed(faces[1], a, b),
+            })
+            .collect();
+        Self {
+            positions: topology.positions.iter().map(|p| p.as_vec3()).collect(),
+            faces: topology
+                .triangles
+                .iter()
+                .enumerate()
+                .map(|(f, tri)| {
+                    tri.map(|tri| (topology.positions[tri[0]], topology.face_normal(f)))
+                })
+                .collect(),
+            edges,
+        }
+    }
+}
+
+pub fn toggle_silhouettes(kb: Res<ButtonInput<KeyCode>>, mut settings: ResMut<SilhouetteSettings>) {
+    if kb.just_pressed(KeyCode::KeyO) {
+        settings.enabled = !settings.enabled;
+        info!("Silhouettes: {}", settings.enabled);
+    }
+}
+
+pub fn refresh_silhouette_cache(
+    mut commands: Commands,
+    settings: Res<SilhouetteSettings>,
+    changed: Query<(Entity, &CgarMeshData), Changed<CgarMeshData>>,
+    all: Query<(Entity, &CgarMeshData)>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    // Caches aren't kept while the mode is off, so turning it on builds them all
+    let meshes = if settings.is_changed() {
+        all.iter().collect::<Vec<_>>()
+    } else {
+        changed.iter().collect()
+    };
+    for (entity, cgar_data) in meshes {
+        let topology = MeshTopology::from_cgar(&cgar_data.0);
+        commands
+            .entity(entity)
+            .insert(SilhouetteCache::build(&topology));
+    }
+}
+
+pub fn draw_silhouettes(
+    mut gizmos: Gizmos,
+    settings: Res<SilhouetteSettings>,
+    camera_query: Query<(&GlobalTransform, &Projection), With<OrbitCamera>>,
+    mesh_query: Query<(&GlobalTransform, &SilhouetteCache, &InheritedVisibility)>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let Ok((camera_global, projection)) = camera_query.single() else {
+        return;
+    };
+    let silhouette_color = Color::srgb(0.05, 0.05, 0.05);
+    let boundary_color = Color::srgb(0.2, 0.5, 1.0);
+    let flipped_color = Color::srgb(1.0, 0.1, 0.1);
+    for (mesh_global, cache, visibility) in &mesh_query {
+        if !visibility.get() {
+            continue;
+        }
+        let to_local = mesh_global.affine().inverse();
+        let eye = to_local
+            .transform_point3(camera_global.translation())
+            .as_dvec3();
+        let view = to_local
+            .transform_vector3(camera_global.forward().as_vec3())
+            .as_dvec3();
+        let orthographic = matches!(projection, Projection::Orthographic(_));
+        let facing = |f: usize| {
+            cache
+                .faces
+                .get(f)
+                .copied()
+                .flatten()
+                .map(|(point, normal)| {
+                    if orthographic {
+                        view.dot(normal) < 0.0
+                    } else {
+                        (eye - point).dot(normal) > 0.0
+                    }
+                })
+        };
+        for edge in &cache.edges {
+            let color = if edge.flipped && settings.show_orientation_errors {
+                Some(flipped_color)
+            } else {
+                match edge.faces.as_slice() {
+                    [f] if settings.include_boundary && facing(*f).is_some() => {
+                        Some(boundary_color)
+                    }
+                    faces if faces.len() >= 2 => {
+                        let mut sides = faces.iter().filter_map(|&f| facing(f));
+                        let first = sides.next();
+                        sides
+                            .any(|side| Some(side) != first)
+                            .then_some(silhouette_color)
+                    }
+                    _ => None,
+                }
+            };
+            if let Some(color) = color {
+                let (a, b) = edge.endpoints;
+                gizmos.line(
+                    mesh_global.transform_point(cache.positions[a]),
+                    mesh_global.transform_point(cache.positions[b]),
+                    color,
+                );
+            }
+        }
+    }
+}
+
+pub fn silhouette_panel(mut contexts: EguiContexts, mut settings: ResMut<SilhouetteSettings>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Silhouettes")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut enabled = settings.enabled;
+            ui.checkbox(&mut enabled, "Draw silhouettes (O)");
+            let mut include_boundary = settings.include_boundary;
+            ui.checkbox(&mut include_boundary, "Include boundary edges");
+            let mut show_orientation_errors = settings.show_orientation_errors;
+            ui.checkbox(
+                &mut show_orientation_errors,
+                "Highlight edges with inconsistent winding",
+            );
+            if enabled != settings.enabled
+                || include_boundary != settings.include_boundary
+                || show_orientation_errors != settings.show_orientation_errors
+            {
+                settings.enabled = enabled;
+                settings.include_boundary = include_boundary;
+                settings.show_orientation_errors = show_orientation_errors;
+            }
+            ui.label("Silhouettes across smooth areas point at flipped normals");
+        });
+}
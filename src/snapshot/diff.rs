@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashSet;
+
+use bevy::math::DVec3;
+
+use crate::mesh::topology::MeshTopology;
+
+// Differences between a pinned copy of a mesh and its live state. Vertices
+// are matched by id, which in-place edits and soup rebuilds preserve;
+// operations that renumber vertices show up as removals plus additions.
+#[derive(Debug, Clone, Default)]
+pub struct MeshDiff {
+    pub moved_vertices: Vec<usize>,
+    pub added_vertices: Vec<usize>,
+    pub removed_vertices: Vec<usize>,
+    // Live face ids
+    pub added_faces: Vec<usize>,
+    // Live faces with the same corners as before, at least one of them moved
+    pub moved_faces: Vec<usize>,
+    // Snapshot triangles missing from the live mesh
+    pub removed_faces: Vec<[usize; 3]>,
+    pub max_displacement: f64,
+}
+
+impl MeshDiff {
+    pub fn is_empty(&self) -> bool {
+        self.moved_vertices.is_empty()
+            && self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_faces.is_empty()
+            && self.removed_faces.is_empty()
+    }
+}
+
+// Rotation of `tri` starting at its smallest id, so equal faces compare equal
+// while opposite windings stay distinct
+fn face_key(tri: [usize; 3]) -> [usize; 3] {
+    let k = (0..3).min_by_key(|&k| tri[k]).unwrap_or(0);
+    [tri[k], tri[(k + 1) % 3], tri[(k + 2) % 3]]
+}
+
+fn is_used(topology: &MeshTopology, v: usize) -> bool {
+    topology
+        .vertex_faces
+        .get(v)
+        .is_some_and(|faces| !faces.is_empty())
+}
+
+pub fn diff_meshes(before: &MeshTopology, after: &MeshTopology) -> MeshDiff {
+    let (min, max) = before.positions.iter().fold(
+        (DVec3::splat(f64::INFINITY), DVec3::splat(f64::NEG_INFINITY)),
+        |(min, max), p| (min.min(*p), max.max(*p)),
+    );
+    let tolerance = 1e-9 * (max - min).length().max(1.0);
+
+    let mut diff = MeshDiff::default();
+    let mut moved = vec![false; after.positions.len()];
+    for v in after.used_vertices() {
+        if !is_used(before, v) {
+            diff.added_vertices.push(v);
+            continue;
+        }
+        let displacement = before.positions[v].distance(after.positions[v]);
+        if displacement > tolerance {
+            moved[v] = true;
+            diff.moved_vertices.push(v);
+            diff.max_displacement = diff.max_displacement.max(displacement);
+        }
+    }
+    diff.removed_vertices = before
+        .used_vertices()
+        .filter(|&v| !is_used(after, v))
+        .collect();
+
+    let before_faces: HashSet<[usize; 3]> =
+        before.live_faces().map(|(_, tri)| face_key(tri)).collect();
+    let mut after_faces = HashSet::new();
+    for (fi, tri) in after.live_faces() {
+        let key = face_key(tri);
+        after_faces.insert(key);
+        if !before_faces.contains(&key) {
+            diff.added_faces.push(fi);
+        } else if tri.iter().any(|&v| moved[v]) {
+            diff.moved_faces.push(fi);
+        }
+    }
+    diff.removed_faces = before
+        .live_faces()
+        .map(|(_, tri)| tri)
+        .filter(|tri| !after_faces.contains(&face_key(*tri)))
+        .collect();
+    diff
+}
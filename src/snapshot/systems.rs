@@ -0,0 +1,261 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::Arc;
+
+use bevy::{
+    color::Color,
+    ecs::{
+        entity::Entity,
+        name::Name,
+        query::Changed,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    math::DVec3,
+    transform::components::GlobalTransform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::overlay::{FaceColorOverlay, overlay_color};
+use crate::camera::components::CgarMeshData;
+use crate::mesh::face_tree::FaceSurface;
+use crate::mesh::topology::MeshTopology;
+use crate::selection::components::SelectionSet;
+use crate::snapshot::diff::{MeshDiff, diff_meshes};
+
+const UNCHANGED_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+const ADDED_COLOR: Color = Color::srgb(0.3, 0.85, 0.3);
+const MOVED_COLOR: Color = Color::srgb(1.0, 0.7, 0.2);
+const REMOVED_COLOR: Color = Color::srgb(1.0, 0.25, 0.25);
+
+pub struct Snapshot {
+    pub mesh: Entity,
+    pub name: String,
+    pub topology: MeshTopology,
+    // Pinned faces for closest-point queries when re-projecting edits
+    pub surface: Arc<FaceSurface>,
+    pub diff: MeshDiff,
+}
+
+// A pinned copy of one mesh, compared against its live state after edits
+#[derive(Resource)]
+pub struct SnapshotCompare {
+    pub pinned: Option<Snapshot>,
+    // Tint live faces by how they changed
+    pub color_faces: bool,
+    // Outline removed faces and draw vertex displacements
+    pub show_changes: bool,
+    // Pull smoothed and decimated vertices of the pinned mesh back onto the
+    // snapshot surface, keeping its shape while the mesh changes
+    pub reproject: bool,
+}
+
+impl SnapshotCompare {
+    // Surface edits of `mesh` are re-projected onto, when that is on
+    pub fn reference(&self, mesh: Entity) -> Option<Arc<FaceSurface>> {
+        self.pinned
+            .as_ref()
+            .filter(|snapshot| self.reproject && snapshot.mesh == mesh)
+            .map(|snapshot| snapshot.surface.clone())
+    }
+}
+
+impl Default for SnapshotCompare {
+    fn default() -> Self {
+        Self {
+            pinned: None,
+            color_faces: true,
+            show_changes: true,
+            reproject: false,
+        }
+    }
+}
+
+fn diff_overlay(diff: &MeshDiff, face_count: usize) -> FaceColorOverlay {
+    let mut colors = vec![overlay_color(UNCHANGED_COLOR); face_count];
+    for &f in &diff.moved_faces {
+        colors[f] = overlay_color(MOVED_COLOR);
+    }
+    for &f in &diff.added_faces {
+        colors[f] = overlay_color(ADDED_COLOR);
+    }
+    FaceColorOverlay { colors }
+}
+
+pub fn update_snapshot_diff(
+    mut commands: Commands,
+    mut compare: ResMut<SnapshotCompare>,
+    changed: Query<&CgarMeshData, Changed<CgarMeshData>>,
+) {
+    let color_faces = compare.color_faces;
+    let Some(snapshot) = compare.pinned.as_mut() else {
+        return;
+    };
+    let Ok(cgar_data) = changed.get(snapshot.mesh) else {
+        return;
+    };
+    let live = MeshTopology::from_cgar(&cgar_data.0);
+    snapshot.diff = diff_meshes(&snapshot.topology, &live);
+    if color_faces {
+        commands
+            .entity(snapshot.mesh)
+            .insert(diff_overlay(&snapshot.diff, live.triangles.len()));
+    }
+}
+
+pub fn draw_snapshot_diff(
+    mut gizmos: Gizmos,
+    compare: Res<SnapshotCompare>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+) {
+    let Some(snapshot) = compare.pinned.as_ref().filter(|_| compare.show_changes) else {
+        return;
+    };
+    let Ok((mesh_global, cgar_data)) = mesh_query.get(snapshot.mesh) else {
+        return;
+    };
+    let before = |v: usize| mesh_global.transform_point(snapshot.topology.positions[v].as_vec3());
+    for tri in &snapshot.diff.removed_faces {
+        let [a, b, c] = tri.map(before);
+        gizmos.linestrip([a, b, c, a], REMOVED_COLOR);
+    }
+    for &v in &snapshot.diff.moved_vertices {
+        let p = &cgar_data.0.vertices[v].position;
+        let now =
+            mesh_global.transform_point(bevy::math::DVec3::new(p[0].0, p[1].0, p[2].0).as_vec3());
+        gizmos.line(before(v), now, MOVED_COLOR);
+    }
+}
+
+pub fn snapshot_panel(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut compare: ResMut<SnapshotCompare>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData, Option<&Name>)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Snapshot")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let target = selection
+                .mesh
+                .filter(|e| mesh_query.contains(*e))
+                .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+            ui.horizontal(|ui| {
+                let pin = ui.add_enabled(target.is_some(), egui::Button::new("Pin snapshot"));
+                if let Some((entity, cgar_data, name)) = target
+                    .filter(|_| pin.clicked())
+                    .and_then(|e| mesh_query.get(e).ok())
+                {
+                    if let Some(previous) = compare.pinned.take() {
+                        commands.entity(previous.mesh).remove::<FaceColorOverlay>();
+                    }
+                    let topology = MeshTopology::from_cgar(&cgar_data.0);
+                    compare.pinned = Some(Snapshot {
+                        mesh: entity,
+                        name: name.map_or_else(|| "unnamed".to_string(), |n| n.to_string()),
+                        surface: Arc::new(FaceSurface::build(&cgar_data.0)),
+                        topology,
+                        diff: MeshDiff::default(),
+                    });
+                }
+                let unpin = ui
+                    .add_enabled(compare.pinned.is_some(), egui::Button::new("Unpin"))
+                    .clicked();
+                if let Some(previous) = compare.pinned.take_if(|_| unpin) {
+                    commands.entity(previous.mesh).remove::<FaceColorOverlay>();
+                }
+            });
+
+            let mut color_faces = compare.color_faces;
+            ui.checkbox(&mut color_faces, "Color changed faces");
+            let mut show_changes = compare.show_changes;
+            ui.checkbox(&mut show_changes, "Show removed faces and displacements");
+            if color_faces != compare.color_faces {
+                compare.color_faces = color_faces;
+                if let Some(snapshot) = &compare.pinned {
+                    let live = mesh_query
+                        .get(snapshot.mesh)
+                        .map(|(_, cgar_data, _)| cgar_data.0.faces.len());
+                    match (color_faces, live) {
+                        (true, Ok(face_count)) => {
+                            commands
+                                .entity(snapshot.mesh)
+                                .insert(diff_overlay(&snapshot.diff, face_count));
+                        }
+                        _ => {
+                            commands.entity(snapshot.mesh).remove::<FaceColorOverlay>();
+                        }
+                    }
+                }
+            }
+            if show_changes != compare.show_changes {
+                compare.show_changes = show_changes;
+            }
+            let mut reproject = compare.reproject;
+            ui.checkbox(&mut reproject, "Re-project smoothing and decimation")
+                .on_hover_text(
+                    "Moves each smoothed vertex and collapse survivor to the closest point \
+                     of the snapshot surface",
+                );
+            if reproject != compare.reproject {
+                compare.reproject = reproject;
+            }
+
+            let Some(snapshot) = &compare.pinned else {
+                ui.label("Pin a snapshot, edit the mesh, then compare");
+                return;
+            };
+            ui.separator();
+            ui.label(format!("Mesh: {} ({})", snapshot.name, snapshot.mesh));
+            let diff = &snapshot.diff;
+            if diff.is_empty() {
+                ui.label("No changes since the snapshot");
+                return;
+            }
+            egui::Grid::new("snapshot_diff")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in ["", "Added", "Removed", "Moved"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    ui.label("Vertices");
+                    ui.label(diff.added_vertices.len().to_string());
+                    ui.label(diff.removed_vertices.len().to_string());
+                    ui.label(diff.moved_vertices.len().to_string());
+                    ui.end_row();
+                    ui.label("Faces");
+                    ui.label(diff.added_faces.len().to_string());
+                    ui.label(diff.removed_faces.len().to_string());
+                    ui.label(diff.moved_faces.len().to_string());
+                    ui.end_row();
+                });
+            ui.label(format!("Max displacement: {:.6}", diff.max_displacement));
+        });
+}
@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::mesh::topology::MeshTopology;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// FNV-1a, spelled out so hashes stay the same across builds and platforms
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(FNV_OFFSET)
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+// Content hashes of a mesh, for telling at a glance whether two files or two
+// runs of a pipeline produced the same thing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshFingerprint {
+    // Used vertex positions, bit for bit, in sorted order: renumbering
+    // vertices or retriangulating the same points leaves it alone
+    pub geometry: u64,
+    // Triangles over the used vertices numbered in their original order, each
+    // rotated to start at its lowest index and sorted: face order and unused
+    // vertex slots don't matter, winding does
+    pub connectivity: u64,
+    pub vertices: usize,
+    pub faces: usize,
+}
+
+impl MeshFingerprint {
+    pub fn of(topology: &MeshTopology) -> Self {
+        let used: Vec<usize> = topology.used_vertices().collect();
+        let mut rank = vec![usize::MAX; topology.positions.len()];
+        for (i, &v) in used.iter().enumerate() {
+            rank[v] = i;
+        }
+
+        // -0.0 and 0.0 compare equal, so they hash the same too
+        let mut positions: Vec<[u64; 3]> = used
+            .iter()
+            .map(|&v| {
+                topology.positions[v]
+                    .to_array()
+                    .map(|c| (c + 0.0).to_bits())
+            })
+            .collect();
+        positions.sort_unstable();
+        let mut geometry = Fnv::new();
+        geometry.write_u64(positions.len() as u64);
+        for coordinate in positions.iter().flatten() {
+            geometry.write_u64(*coordinate);
+        }
+
+        let mut triangles: Vec<[usize; 3]> = topology
+            .live_faces()
+            .map(|(_, tri)| {
+                let [a, b, c] = tri.map(|v| rank[v]);
+                if a <= b && a <= c {
+                    [a, b, c]
+                } else if b <= c {
+                    [b, c, a]
+                } else {
+                    [c, a, b]
+                }
+            })
+            .collect();
+        triangles.sort_unstable();
+        let mut connectivity = Fnv::new();
+        connectivity.write_u64(used.len() as u64);
+        connectivity.write_u64(triangles.len() as u64);
+        for &v in triangles.iter().flatten() {
+            connectivity.write_u64(v as u64);
+        }
+
+        Self {
+            geometry: geometry.0,
+            connectivity: connectivity.0,
+            vertices: used.len(),
+            faces: triangles.len(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "geometry {:016x}, connectivity {:016x} ({} vertices, {} faces)",
+            self.geometry, self.connectivity, self.vertices, self.faces
+        )
+    }
+}
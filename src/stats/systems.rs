@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use bevy::ecs::{
+    entity::Entity,
+    name::Name,
+    query::{Changed, With},
+    resource::Resource,
+    system::{Query, Res, ResMut},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::CgarMeshData;
+use crate::mesh::topology::MeshTopology;
+use crate::selection::components::SelectionSet;
+use crate::stats::fingerprint::MeshFingerprint;
+
+// Fingerprints computed on request, per mesh. Hashing a large mesh takes a
+// while, so they aren't kept up to date; an edit drops the mesh's entry.
+#[derive(Resource, Default)]
+pub struct MeshFingerprints(pub HashMap<Entity, MeshFingerprint>);
+
+impl MeshFingerprints {
+    pub fn compute(&mut self, entity: Entity, cgar_data: &CgarMeshData) -> MeshFingerprint {
+        let fingerprint = MeshFingerprint::of(&MeshTopology::from_cgar(&cgar_data.0));
+        self.0.insert(entity, fingerprint);
+        fingerprint
+    }
+}
+
+// Runs before anything that computes fingerprints in the frame, so only
+// edits made since are caught
+pub fn drop_stale_fingerprints(
+    mut fingerprints: ResMut<MeshFingerprints>,
+    changed: Query<Entity, Changed<CgarMeshData>>,
+    meshes: Query<(), With<CgarMeshData>>,
+) {
+    if fingerprints.0.is_empty() {
+        return;
+    }
+    let stale = fingerprints
+        .0
+        .keys()
+        .any(|&entity| changed.contains(entity) || !meshes.contains(entity));
+    if stale {
+        fingerprints
+            .0
+            .retain(|&entity, _| !changed.contains(entity) && meshes.contains(entity));
+    }
+}
+
+pub fn mesh_stats_panel(
+    mut contexts: EguiContexts,
+    mut fingerprints: ResMut<MeshFingerprints>,
+    selection: Res<SelectionSet>,
+    mesh_query: Query<(Entity, &CgarMeshData, Option<&Name>)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Mesh Stats")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let target = selection
+                .mesh
+                .filter(|e| mesh_query.contains(*e))
+                .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+            let Some((entity, cgar_data, name)) = target.and_then(|e| mesh_query.get(e).ok())
+            else {
+                ui.label("No mesh loaded");
+                return;
+            };
+            let name = name.map_or_else(|| "unnamed".to_string(), |n| n.to_string());
+            ui.label(format!("Mesh: {} ({})", name, entity));
+            ui.label(format!(
+                "{} faces",
+                cgar_data.0.faces.iter().filter(|f| !f.removed).count()
+            ));
+
+            ui.separator();
+            match fingerprints.0.get(&entity) {
+                Some(fingerprint) => {
+                    egui::Grid::new("mesh_fingerprint").show(ui, |ui| {
+                        ui.label("Geometry");
+                        ui.monospace(format!("{:016x}", fingerprint.geometry));
+                        ui.end_row();
+                        ui.label("Connectivity");
+                        ui.monospace(format!("{:016x}", fingerprint.connectivity));
+                        ui.end_row();
+                        ui.label("Hashed");
+                        ui.label(format!(
+                            "{} vertices, {} faces",
+                            fingerprint.vertices, fingerprint.faces
+                        ));
+                        ui.end_row();
+                    });
+                    if ui.button("Copy").clicked() {
+                        ui.ctx().copy_text(fingerprint.describe());
+                    }
+                }
+                None => {
+                    ui.label("Fingerprint not computed since the last edit");
+                }
+            }
+            if ui
+                .button("Compute fingerprint")
+                .on_hover_text("Same as the fingerprint command")
+                .clicked()
+            {
+                fingerprints.compute(entity, cgar_data);
+            }
+        });
+}
@@ -0,0 +1,551 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::Ordering;
+
+use bevy::math::DVec3;
+use cgar::mesh::basic_types::Mesh as CgarMesh;
+use cgar::numeric::cgar_f64::CgarF64;
+use serde::{Deserialize, Serialize};
+
+use crate::background::systems::{JobOutcome, OperationProgress};
+use crate::mesh::collapse::collapse_edge_around;
+use crate::mesh::conversion::build_cgar_mesh;
+use crate::mesh::face_tree::FaceSurface;
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::topology::MeshTopology;
+use crate::perturb::systems::{panic_message, restore};
+use crate::repair::ops::{TriangleSoup, boundary_loops, fill_loop};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepOperation {
+    // Collapses the shortest edge per step until a face budget is met
+    #[default]
+    Decimate,
+    // Fans one small boundary loop per step
+    FillHoles,
+}
+
+impl StepOperation {
+    pub const ALL: [StepOperation; 2] = [StepOperation::Decimate, StepOperation::FillHoles];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StepOperation::Decimate => "Decimation",
+            StepOperation::FillHoles => "Hole filling",
+        }
+    }
+}
+
+// An edge collapse the decimator may try next
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollapseCandidate {
+    pub edge: (usize, usize),
+    pub cost: f64,
+    // Where the surviving vertex goes
+    pub position: DVec3,
+}
+
+// Edges of `topology` by collapse cost, cheapest first. Cost is the edge
+// length and the survivor goes to the midpoint, or stays on a `locked`
+// endpoint. Feature lines, edges with both ends locked and edges the mesh
+// already rejected are left out.
+pub fn collapse_candidates(
+    topology: &MeshTopology,
+    features: Option<&FeatureEdges>,
+    rejected: &BTreeSet<(usize, usize)>,
+    locked: &BTreeSet<usize>,
+) -> Vec<CollapseCandidate> {
+    let mut candidates: Vec<CollapseCandidate> = topology
+        .edge_faces
+        .keys()
+        .filter(|&&edge| collapsible(edge, features, rejected))
+        .filter_map(|&edge| candidate(topology, edge, locked))
+        .collect();
+    candidates.sort_by(|a, b| a.cost.total_cmp(&b.cost));
+    candidates
+}
+
+fn collapsible(
+    (v0, v1): (usize, usize),
+    features: Option<&FeatureEdges>,
+    rejected: &BTreeSet<(usize, usize)>,
+) -> bool {
+    !rejected.contains(&(v0, v1))
+        && features.is_none_or(|features| features.allows_collapse(v0, v1))
+}
+
+fn candidate(
+    topology: &MeshTopology,
+    (v0, v1): (usize, usize),
+    locked: &BTreeSet<usize>,
+) -> Option<CollapseCandidate> {
+    let (p0, p1) = (topology.positions[v0], topology.positions[v1]);
+    let position = match (locked.contains(&v0), locked.contains(&v1)) {
+        (true, true) => return None,
+        (true, false) => p0,
+        (false, true) => p1,
+        (false, false) => p0.lerp(p1, 0.5),
+    };
+    let cost = p0.distance(p1);
+    cost.is_finite().then_some(CollapseCandidate {
+        edge: (v0, v1),
+        cost,
+        position,
+    })
+}
+
+// Collapse cost ordered with `total_cmp`, so it can key the decimation queue
+#[derive(Debug, Clone, Copy)]
+struct Cost(f64);
+
+impl PartialEq for Cost {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Cost {}
+
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cost {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// Decimation state kept across steps: a copy of the mesh's topology and its
+// collapsible edges ordered by cost. A collapse re-reads only the faces
+// around it and re-queues the edges they use. It has to see every edit, so
+// a new one is needed when the mesh, its feature lines or the locked
+// vertices change some other way, and after a failed step.
+pub struct Decimator {
+    topology: MeshTopology,
+    locked: BTreeSet<usize>,
+    queue: BTreeSet<(Cost, (usize, usize))>,
+    // Cost each queued edge is filed under
+    queued: BTreeMap<(usize, usize), Cost>,
+    faces: usize,
+}
+
+impl Decimator {
+    pub fn new(
+        topology: MeshTopology,
+        features: Option<&FeatureEdges>,
+        rejected: &BTreeSet<(usize, usize)>,
+        locked: BTreeSet<usize>,
+    ) -> Self {
+        let candidates = collapse_candidates(&topology, features, rejected, &locked);
+        let mut decimator = Self {
+            faces: topology.live_faces().count(),
+            topology,
+            locked,
+            queue: BTreeSet::new(),
+            queued: BTreeMap::new(),
+        };
+        for candidate in candidates {
+            decimator.push(candidate);
+        }
+        decimator
+    }
+
+    pub fn faces(&self) -> usize {
+        self.faces
+    }
+
+    // The `count` cheapest collapses, as the next steps would try them
+    pub fn cheapest(&self, count: usize) -> Vec<CollapseCandidate> {
+        self.queue
+            .iter()
+            .take(count)
+            .filter_map(|&(_, edge)| candidate(&self.topology, edge, &self.locked))
+            .collect()
+    }
+
+    fn push(&mut self, candidate: CollapseCandidate) {
+        let cost = Cost(candidate.cost);
+        self.queue.insert((cost, candidate.edge));
+        self.queued.insert(candidate.edge, cost);
+    }
+
+    fn remove(&mut self, edge: (usize, usize)) {
+        if let Some(cost) = self.queued.remove(&edge) {
+            self.queue.remove(&(cost, edge));
+        }
+    }
+
+    // Re-reads `around` after a collapse and re-queues every edge those
+    // faces used before or use now
+    fn update(
+        &mut self,
+        mesh: &CgarMesh<CgarF64, 3>,
+        around: &[usize],
+        features: Option<&FeatureEdges>,
+        rejected: &BTreeSet<(usize, usize)>,
+    ) {
+        let edges_of = |topology: &MeshTopology, edges: &mut BTreeSet<(usize, usize)>| {
+            for tri in around.iter().filter_map(|&f| topology.triangles[f]) {
+                for k in 0..3 {
+                    let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                    edges.insert((a.min(b), a.max(b)));
+                }
+            }
+        };
+        let live = |topology: &MeshTopology| {
+            around
+                .iter()
+                .filter(|&&f| topology.triangles[f].is_some())
+                .count()
+        };
+        let mut edges = BTreeSet::new();
+        edges_of(&self.topology, &mut edges);
+        let before = live(&self.topology);
+        self.topology.refresh_faces(mesh, around);
+        edges_of(&self.topology, &mut edges);
+        self.faces = self.faces + live(&self.topology) - before;
+        for edge in edges {
+            self.remove(edge);
+            if self.topology.edge_faces.contains_key(&edge) && collapsible(edge, features, rejected)
+            {
+                if let Some(candidate) = candidate(&self.topology, edge, &self.locked) {
+                    self.push(candidate);
+                }
+            }
+        }
+    }
+
+    // Tries the cheapest collapse through cgar. A rejected edge is remembered
+    // and skipped from then on, and counts as a step of its own. With a
+    // `reference` surface the survivor lands on its closest point instead of
+    // the midpoint.
+    pub fn step(
+        &mut self,
+        mesh: &mut CgarMesh<CgarF64, 3>,
+        features: Option<&FeatureEdges>,
+        rejected: &mut BTreeSet<(usize, usize)>,
+        target_faces: usize,
+        reference: Option<&FaceSurface>,
+    ) -> StepEvent {
+        if self.faces <= target_faces {
+            return StepEvent::finished(format!("Reached {} faces", self.faces));
+        }
+        let Some(candidate) = self
+            .queue
+            .first()
+            .and_then(|&(_, edge)| candidate(&self.topology, edge, &self.locked))
+        else {
+            return StepEvent::finished(format!(
+                "No collapsible edges left at {} faces",
+                self.faces
+            ));
+        };
+        let (v0, v1) = candidate.edge;
+        let locked = &self.locked;
+        // A locked endpoint stays exactly where it is
+        let position = match reference.filter(|_| !locked.contains(&v0) && !locked.contains(&v1)) {
+            Some(surface) => surface
+                .closest_point(candidate.position)
+                .map_or(candidate.position, |closest| closest.point),
+            None => candidate.position,
+        };
+        let outline = vec![self.topology.positions[v0], self.topology.positions[v1]];
+        let event = |kind: StepEventKind, message: String, worst| StepEvent {
+            kind,
+            message,
+            outline: outline.clone(),
+            closed: false,
+            position: (kind == StepEventKind::Collapsed).then_some(position),
+            worst,
+            error: (kind == StepEventKind::Collapsed).then_some(candidate.cost),
+        };
+        // A locked endpoint is the one kept
+        let (removed, kept) = if locked.contains(&v0) {
+            (v1, v0)
+        } else {
+            (v0, v1)
+        };
+        let mut around: Vec<usize> = [v0, v1]
+            .iter()
+            .flat_map(|&v| self.topology.vertex_faces[v].iter().copied())
+            .collect();
+        around.sort_unstable();
+        around.dedup();
+        match catch_unwind(AssertUnwindSafe(|| {
+            collapse_edge_around(mesh, removed, kept, position, &around)
+        })) {
+            Ok(Ok(survivors)) => {
+                self.update(mesh, &around, features, rejected);
+                event(
+                    StepEventKind::Collapsed,
+                    format!("Collapsed ({}, {}), length {:.4}", v0, v1, candidate.cost),
+                    worst_face(&self.topology, &survivors),
+                )
+            }
+            Ok(Err(_)) => {
+                rejected.insert(candidate.edge);
+                self.remove(candidate.edge);
+                event(
+                    StepEventKind::Rejected,
+                    format!("Collapse of ({}, {}) rejected", v0, v1),
+                    None,
+                )
+            }
+            Err(payload) => {
+                *mesh = restore(&self.topology);
+                event(
+                    StepEventKind::Failed,
+                    format!(
+                        "collapse_edge({}, {}) panicked: {}",
+                        v0,
+                        v1,
+                        panic_message(payload)
+                    ),
+                    None,
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepEventKind {
+    Collapsed,
+    Rejected,
+    Filled,
+    // cgar panicked; the mesh was restored to its state before the step
+    Failed,
+    // Nothing left to do
+    Finished,
+}
+
+// What one atomic step did
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepEvent {
+    pub kind: StepEventKind,
+    pub message: String,
+    // Element acted on, in the mesh's local space: an edge's two ends or a
+    // boundary loop
+    pub outline: Vec<DVec3>,
+    pub closed: bool,
+    // Where a collapse put the surviving vertex
+    pub position: Option<DVec3>,
+    // Lowest quality triangle around the step after it ran, with its corners
+    pub worst: Option<(f64, [DVec3; 3])>,
+    // Cost of the collapse a decimation step made
+    pub error: Option<f64>,
+}
+
+impl StepEvent {
+    fn finished(message: impl Into<String>) -> Self {
+        Self {
+            kind: StepEventKind::Finished,
+            message: message.into(),
+            outline: Vec::new(),
+            closed: false,
+            position: None,
+            worst: None,
+            error: None,
+        }
+    }
+
+    // Stops playback; nothing after it would make progress
+    pub fn ends_run(&self) -> bool {
+        matches!(self.kind, StepEventKind::Failed | StepEventKind::Finished)
+    }
+}
+
+// 1 for an equilateral triangle, falling to 0 as it degenerates
+pub fn triangle_quality([a, b, c]: [DVec3; 3]) -> f64 {
+    let squares = a.distance_squared(b) + b.distance_squared(c) + c.distance_squared(a);
+    if squares <= 0.0 {
+        return 0.0;
+    }
+    2.0 * 3f64.sqrt() * (b - a).cross(c - a).length() / squares
+}
+
+// Lowest quality face touching any of `vertices`
+fn worst_face(topology: &MeshTopology, vertices: &[usize]) -> Option<(f64, [DVec3; 3])> {
+    let faces: BTreeSet<usize> = vertices
+        .iter()
+        .filter_map(|&v| topology.vertex_faces.get(v))
+        .flatten()
+        .copied()
+        .collect();
+    faces
+        .into_iter()
+        .filter_map(|f| topology.triangles[f])
+        .map(|tri| {
+            let corners = topology.corners(tri);
+            (triangle_quality(corners), corners)
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+}
+
+// Fans the first boundary loop of at most `max_edges` edges
+pub fn fill_hole_step(
+    mesh: &mut CgarMesh<CgarF64, 3>,
+    max_edges: usize,
+    within: Option<&BTreeSet<usize>>,
+) -> StepEvent {
+    let topology = MeshTopology::from_cgar(mesh);
+    let mut soup = TriangleSoup::from_topology(&topology);
+    // Holes outside `within` are left alone entirely
+    let loops = loops_within(&soup, within);
+    let Some(chain) = loops.iter().find(|chain| chain.len() <= max_edges) else {
+        return StepEvent::finished(format!(
+            "No holes of at most {} edges left ({} larger)",
+            max_edges,
+            loops.len()
+        ));
+    };
+    let outline = chain.iter().map(|&v| soup.positions[v]).collect();
+    // The loop's vertices and the fan center, if one was added
+    let touched: Vec<usize> = chain
+        .iter()
+        .copied()
+        .chain((chain.len() > 3).then_some(soup.positions.len()))
+        .collect();
+    fill_loop(&mut soup, chain);
+    let (kind, message, worst) = match catch_unwind(AssertUnwindSafe(|| {
+        build_cgar_mesh(&soup.positions, soup.triangles.iter().copied())
+    })) {
+        Ok(filled) => {
+            *mesh = filled;
+            (
+                StepEventKind::Filled,
+                format!("Filled a hole of {} edges", chain.len()),
+                worst_face(&MeshTopology::from_cgar(mesh), &touched),
+            )
+        }
+        Err(payload) => (
+            StepEventKind::Failed,
+            format!(
+                "rebuilding after filling a hole panicked: {}",
+                panic_message(payload)
+            ),
+            None,
+        ),
+    };
+    StepEvent {
+        kind,
+        message,
+        outline,
+        closed: true,
+        position: None,
+        worst,
+        error: None,
+    }
+}
+
+// Boundary loops with every vertex in `within`, or all of them
+fn loops_within(soup: &TriangleSoup, within: Option<&BTreeSet<usize>>) -> Vec<Vec<usize>> {
+    boundary_loops(soup)
+        .into_iter()
+        .filter(|chain| within.is_none_or(|within| chain.iter().all(|v| within.contains(v))))
+        .collect()
+}
+
+// Runs `operation` to completion on a copy of a mesh, for a background task.
+// `limit` is the face target when decimating and the largest hole when
+// filling. Stops early once `progress` is cancelled.
+#[allow(clippy::too_many_arguments)]
+pub fn run_to_end(
+    soup: &TriangleSoup,
+    operation: StepOperation,
+    features: Option<&FeatureEdges>,
+    locked: &BTreeSet<usize>,
+    within: Option<&BTreeSet<usize>>,
+    limit: usize,
+    reference: Option<&FaceSurface>,
+    progress: &OperationProgress,
+) -> JobOutcome {
+    let built = catch_unwind(AssertUnwindSafe(|| {
+        build_cgar_mesh(&soup.positions, soup.triangles.iter().copied())
+    }));
+    let mut mesh = match built {
+        Ok(mesh) => mesh,
+        Err(payload) => return JobOutcome::Failed(panic_message(payload)),
+    };
+    let live_faces = |mesh: &CgarMesh<CgarF64, 3>| mesh.faces.iter().filter(|f| !f.removed).count();
+    let start = live_faces(&mesh);
+    let total = match operation {
+        StepOperation::Decimate => start.saturating_sub(limit),
+        StepOperation::FillHoles => loops_within(soup, within)
+            .iter()
+            .filter(|chain| chain.len() <= limit)
+            .count(),
+    };
+    progress.total.store(total, Ordering::Relaxed);
+    progress.faces.store(start, Ordering::Relaxed);
+
+    let mut rejected = BTreeSet::new();
+    let mut decimator = (operation == StepOperation::Decimate).then(|| {
+        Decimator::new(
+            MeshTopology::from_cgar(&mesh),
+            features,
+            &rejected,
+            locked.clone(),
+        )
+    });
+    loop {
+        if progress.cancelled() {
+            return JobOutcome::Cancelled;
+        }
+        let (event, faces) = match decimator.as_mut() {
+            Some(decimator) => {
+                let event = decimator.step(&mut mesh, features, &mut rejected, limit, reference);
+                (event, decimator.faces())
+            }
+            None => {
+                let event = fill_hole_step(&mut mesh, limit, within);
+                (event, live_faces(&mesh))
+            }
+        };
+        progress.faces.store(faces, Ordering::Relaxed);
+        match (operation, event.kind) {
+            (_, StepEventKind::Finished) => {
+                let soup = TriangleSoup::from_topology(&MeshTopology::from_cgar(&mesh));
+                return JobOutcome::Done(soup, event.message);
+            }
+            (_, StepEventKind::Failed) => return JobOutcome::Failed(event.message),
+            (StepOperation::Decimate, _) => {
+                progress
+                    .done
+                    .store(start.saturating_sub(faces), Ordering::Relaxed);
+            }
+            (StepOperation::FillHoles, _) => {
+                progress.done.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        progress.steps.fetch_add(1, Ordering::Relaxed);
+        if let Some(error) = event.error {
+            progress.set_error(error);
+        }
+    }
+}
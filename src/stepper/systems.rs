@@ -0,0 +1,819 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::Arc;
+
+use bevy::{
+    asset::Assets,
+    color::Color,
+    ecs::{
+        change_detection::{DetectChanges, Ref},
+        entity::Entity,
+        query::Without,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    log::info,
+    math::{DVec3, Isometry3d},
+    render::mesh::{Mesh, Mesh3d},
+    tasks::AsyncComputeTaskPool,
+    time::Time,
+    transform::components::GlobalTransform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::overlay::{FaceColorOverlay, render_mesh};
+use crate::background::systems::{BackgroundOperation, OperationProgress};
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::mesh::constraints::EditConstraints;
+use crate::mesh::conversion::vertex_position;
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::highlight::{HighlightKind, HighlightStyle};
+use crate::mesh::normals::{ImportedNormals, NormalSettings};
+use crate::mesh::topology::MeshTopology;
+use crate::repair::ops::TriangleSoup;
+use crate::selection::components::SelectionSet;
+use crate::selection::region::EditRegion;
+use crate::session::presets::{OperationPresets, PresetParams, preset_section};
+use crate::snapshot::systems::SnapshotCompare;
+use crate::stepper::ops::{
+    CollapseCandidate, Decimator, StepEvent, StepEventKind, StepOperation, fill_hole_step,
+    run_to_end,
+};
+use crate::workspace::systems::Stashed;
+
+// Steps run in one frame at most while playing fast; the mesh is redrawn
+// once per frame either way
+const MAX_STEPS_PER_FRAME: usize = 64;
+// Log lines kept in the panel
+const LOG_LINES: usize = 200;
+
+// Runs a supported operation one atomic step at a time on one mesh, drawing
+// the element each step acted on
+#[derive(Resource)]
+pub struct StepMode {
+    pub operation: StepOperation,
+    // Mesh being stepped; `None` when no run is active
+    pub mesh: Option<Entity>,
+    pub playing: bool,
+    pub steps_per_second: f32,
+    pub target_faces: usize,
+    pub max_hole_edges: usize,
+    // Confine the run to the selection taken when it starts
+    pub within_selection: bool,
+    pub region: Option<EditRegion>,
+    pub steps: usize,
+    pub last: Option<StepEvent>,
+    pub log: VecDeque<String>,
+    // Edges cgar refused to collapse during this run
+    pub rejected: BTreeSet<(usize, usize)>,
+    // Steps asked for with Next, not yet run
+    pub pending: usize,
+    // Conditions that pause playback and move the camera to the element
+    // that triggered them
+    pub break_on_reject: bool,
+    pub break_on_quality: bool,
+    pub min_quality: f64,
+    pub focus_on_break: bool,
+    pub breakpoint: Option<Breakpoint>,
+    // Cheapest collapses while decimating, as the next steps would try them
+    pub queue: Vec<CollapseCandidate>,
+    pub queue_size: usize,
+    pub queue_sort: QueueSort,
+    pub queue_descending: bool,
+    // Queue row under the mouse, drawn in the viewport
+    pub hovered: Option<(usize, usize)>,
+    queue_stale: bool,
+    // Kept across frames while decimating, so a step doesn't rescan the mesh
+    decimator: Option<Decimator>,
+    progress: f32,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueSort {
+    #[default]
+    Cost,
+    Edge,
+}
+
+// A condition that halted playback, after the step that met it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakpoint {
+    pub reason: String,
+    // Point to look at, in the mesh's local space
+    pub focus: DVec3,
+    // Triangle that failed the quality condition
+    pub face: Option<[DVec3; 3]>,
+}
+
+impl Default for StepMode {
+    fn default() -> Self {
+        Self {
+            operation: StepOperation::default(),
+            mesh: None,
+            playing: false,
+            steps_per_second: 5.0,
+            target_faces: 1000,
+            max_hole_edges: 32,
+            within_selection: false,
+            region: None,
+            steps: 0,
+            last: None,
+            log: VecDeque::new(),
+            rejected: BTreeSet::new(),
+            pending: 0,
+            break_on_reject: true,
+            break_on_quality: false,
+            min_quality: 0.1,
+            focus_on_break: true,
+            breakpoint: None,
+            queue: Vec::new(),
+            queue_size: 20,
+            queue_sort: QueueSort::default(),
+            queue_descending: false,
+            hovered: None,
+            queue_stale: true,
+            decimator: None,
+            progress: 0.0,
+        }
+    }
+}
+
+// Step Mode settings saved as a named preset
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct StepPreset {
+    operation: StepOperation,
+    target_faces: usize,
+    max_hole_edges: usize,
+    within_selection: bool,
+}
+
+impl Default for StepPreset {
+    fn default() -> Self {
+        Self::from(&StepMode::default())
+    }
+}
+
+impl From<&StepMode> for StepPreset {
+    fn from(step_mode: &StepMode) -> Self {
+        Self {
+            operation: step_mode.operation,
+            target_faces: step_mode.target_faces,
+            max_hole_edges: step_mode.max_hole_edges,
+            within_selection: step_mode.within_selection,
+        }
+    }
+}
+
+impl PresetParams for StepPreset {
+    const OPERATION: &'static str = "step_mode";
+}
+
+impl StepMode {
+    fn start(&mut self, mesh: Entity, region: Option<EditRegion>) {
+        self.mesh = Some(mesh);
+        self.region = region;
+        self.playing = false;
+        self.steps = 0;
+        self.last = None;
+        self.log.clear();
+        self.rejected.clear();
+        self.pending = 0;
+        self.breakpoint = None;
+        self.queue_stale = true;
+        self.decimator = None;
+        self.progress = 0.0;
+    }
+
+    // First enabled condition `event` meets. Failed steps always break.
+    fn check_breakpoints(&self, event: &StepEvent) -> Option<Breakpoint> {
+        let center = |points: &[DVec3]| points.iter().sum::<DVec3>() / points.len().max(1) as f64;
+        match (event.kind, event.worst) {
+            (StepEventKind::Failed, _) => Some(Breakpoint {
+                reason: "Step failed".to_string(),
+                focus: center(&event.outline),
+                face: None,
+            }),
+            (StepEventKind::Rejected, _) if self.break_on_reject => Some(Breakpoint {
+                reason: "Collapse rejected".to_string(),
+                focus: center(&event.outline),
+                face: None,
+            }),
+            (_, Some((quality, corners)))
+                if self.break_on_quality && quality < self.min_quality =>
+            {
+                Some(Breakpoint {
+                    reason: format!(
+                        "Triangle quality {:.4} below {:.4}",
+                        quality, self.min_quality
+                    ),
+                    focus: center(&corners),
+                    face: Some(corners),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn stop(&mut self) {
+        self.mesh = None;
+        self.playing = false;
+        self.pending = 0;
+    }
+}
+
+// Vertices a collapse must keep. Inside a region that is everything but its
+// interior, so the transition ring holds the surrounding faces in place. The
+// selection lock would freeze the region itself and is ignored there.
+fn locked_vertices(
+    region: Option<&EditRegion>,
+    constraints: &EditConstraints,
+    topology: &MeshTopology,
+    features: Option<&FeatureEdges>,
+    picked: Option<&SelectionSet>,
+) -> BTreeSet<usize> {
+    let Some(region) = region else {
+        return constraints.locked(topology, features, picked);
+    };
+    let mut locked = constraints.locked(topology, features, None);
+    locked.extend(
+        topology
+            .used_vertices()
+            .filter(|v| !region.vertices.contains(v) || region.border.contains(v)),
+    );
+    locked
+}
+
+pub fn run_step_mode(
+    time: Res<Time>,
+    mut step_mode: ResMut<StepMode>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut camera_query: Query<&mut OrbitCamera>,
+    constraints: Res<EditConstraints>,
+    selection: Res<SelectionSet>,
+    compare: Res<SnapshotCompare>,
+    mut mesh_query: Query<(
+        &Mesh3d,
+        &GlobalTransform,
+        &mut CgarMeshData,
+        Option<&FaceColorOverlay>,
+        Option<Ref<FeatureEdges>>,
+        Option<&ImportedNormals>,
+        Option<&NormalSettings>,
+    )>,
+) {
+    let Some(entity) = step_mode.mesh else {
+        return;
+    };
+    let Ok((mesh_handle, mesh_global, mut cgar_data, overlay, features, normals, settings)) =
+        mesh_query.get_mut(entity)
+    else {
+        // The mesh was deleted mid-run
+        step_mode.stop();
+        return;
+    };
+
+    let picked = Some(&*selection).filter(|s| s.mesh == Some(entity));
+    // Edits made outside Step Mode, including to what locks vertices, leave
+    // the decimator behind the mesh
+    let outdated = cgar_data.is_changed()
+        || features
+            .as_ref()
+            .is_some_and(|features| features.is_changed())
+        || constraints.is_changed()
+        || (constraints.any() && selection.is_changed());
+    if step_mode.operation == StepOperation::Decimate && (step_mode.decimator.is_none() || outdated)
+    {
+        let topology = MeshTopology::from_cgar(&cgar_data.0);
+        let locked = if constraints.any() || step_mode.region.is_some() {
+            locked_vertices(
+                step_mode.region.as_ref(),
+                &constraints,
+                &topology,
+                features.as_deref(),
+                picked,
+            )
+        } else {
+            BTreeSet::new()
+        };
+        step_mode.decimator = Some(Decimator::new(
+            topology,
+            features.as_deref(),
+            &step_mode.rejected,
+            locked,
+        ));
+        step_mode.queue_stale = true;
+    }
+    if step_mode.queue_stale {
+        step_mode.queue_stale = false;
+        step_mode.queue = match (step_mode.operation, &step_mode.decimator) {
+            (StepOperation::Decimate, Some(decimator)) => decimator.cheapest(step_mode.queue_size),
+            _ => Vec::new(),
+        };
+    }
+
+    let mut count = std::mem::take(&mut step_mode.pending);
+    if step_mode.playing {
+        let progress = step_mode.progress + time.delta_secs() * step_mode.steps_per_second;
+        let whole = progress.floor();
+        step_mode.progress = progress - whole;
+        count += whole as usize;
+    }
+    let count = count.min(MAX_STEPS_PER_FRAME);
+    if count == 0 {
+        return;
+    }
+
+    let step_mode = &mut *step_mode;
+    step_mode.breakpoint = None;
+    let reference = compare.reference(entity);
+    let cgar = &mut cgar_data.0;
+    for _ in 0..count {
+        let event = match step_mode.operation {
+            StepOperation::Decimate => {
+                let Some(decimator) = step_mode.decimator.as_mut() else {
+                    break;
+                };
+                decimator.step(
+                    cgar,
+                    features.as_deref(),
+                    &mut step_mode.rejected,
+                    step_mode.target_faces,
+                    reference.as_deref(),
+                )
+            }
+            StepOperation::FillHoles => fill_hole_step(
+                cgar,
+                step_mode.max_hole_edges,
+                step_mode.region.as_ref().map(|region| &region.vertices),
+            ),
+        };
+        if event.kind != StepEventKind::Finished {
+            step_mode.steps += 1;
+        }
+        if step_mode.log.len() == LOG_LINES {
+            step_mode.log.pop_front();
+        }
+        step_mode
+            .log
+            .push_back(format!("{}: {}", step_mode.steps, event.message));
+        let ends_run = event.ends_run();
+        if ends_run {
+            info!("Step mode: {}", event.message);
+            step_mode.playing = false;
+        }
+        // A restored or refilled mesh no longer matches the decimator
+        if event.kind == StepEventKind::Failed || step_mode.operation == StepOperation::FillHoles {
+            step_mode.decimator = None;
+        }
+        let breakpoint = step_mode.check_breakpoints(&event);
+        step_mode.last = Some(event);
+        if let Some(breakpoint) = breakpoint {
+            step_mode
+                .log
+                .push_back(format!("Breakpoint: {}", breakpoint.reason));
+            step_mode.playing = false;
+            step_mode.pending = 0;
+            if step_mode.focus_on_break {
+                let focus = mesh_global.transform_point(breakpoint.focus.as_vec3());
+                for mut orbit in &mut camera_query {
+                    orbit.focus_target = Some(focus);
+                }
+            }
+            step_mode.breakpoint = Some(breakpoint);
+            break;
+        }
+        if ends_run {
+            break;
+        }
+    }
+    step_mode.queue_stale = true;
+    meshes.insert(
+        &mesh_handle.0,
+        render_mesh(
+            &cgar_data.0,
+            overlay,
+            features.as_deref(),
+            normals,
+            settings,
+        ),
+    );
+}
+
+fn highlight_color(style: &HighlightStyle, kind: HighlightKind) -> Color {
+    let [r, g, b] = style.get(kind).color;
+    Color::srgb(r, g, b)
+}
+
+pub fn draw_step_mode(
+    mut gizmos: Gizmos,
+    step_mode: Res<StepMode>,
+    style: Res<HighlightStyle>,
+    mesh_query: Query<(&GlobalTransform, &CgarMeshData)>,
+) {
+    let Some(entity) = step_mode.mesh else {
+        return;
+    };
+    let Ok((mesh_global, cgar_data)) = mesh_query.get(entity) else {
+        return;
+    };
+    let hovered = step_mode
+        .hovered
+        .and_then(|edge| step_mode.queue.iter().find(|c| c.edge == edge));
+    if let Some(candidate) = hovered {
+        let color = highlight_color(&style, HighlightKind::Hover);
+        let (v0, v1) = candidate.edge;
+        let [a, b] =
+            [v0, v1].map(|v| mesh_global.transform_point(vertex_position(&cgar_data.0, v)));
+        gizmos.line(a, b, color);
+        gizmos.sphere(
+            Isometry3d::from_translation(mesh_global.transform_point(candidate.position.as_vec3())),
+            a.distance(b) * 0.15,
+            color,
+        );
+    }
+    let Some(event) = step_mode.last.as_ref() else {
+        return;
+    };
+    let kind = match event.kind {
+        StepEventKind::Collapsed => HighlightKind::Selection,
+        StepEventKind::Filled => HighlightKind::Boundary,
+        StepEventKind::Rejected | StepEventKind::Failed => HighlightKind::Error,
+        StepEventKind::Finished => return,
+    };
+    let color = highlight_color(&style, kind);
+    let world: Vec<_> = event
+        .outline
+        .iter()
+        .map(|p| mesh_global.transform_point(p.as_vec3()))
+        .collect();
+    let closing = world.first().copied().filter(|_| event.closed);
+    gizmos.linestrip(world.iter().copied().chain(closing), color);
+    if let Some(face) = step_mode.breakpoint.as_ref().and_then(|b| b.face) {
+        let [a, b, c] = face.map(|p| mesh_global.transform_point(p.as_vec3()));
+        gizmos.linestrip([a, b, c, a], highlight_color(&style, HighlightKind::Error));
+    }
+    if let (Some(position), [a, b]) = (event.position, &world[..]) {
+        let center = mesh_global.transform_point(position.as_vec3());
+        gizmos.sphere(
+            Isometry3d::from_translation(center),
+            a.distance(*b) * 0.15,
+            color,
+        );
+    }
+}
+
+pub fn step_mode_panel(
+    mut contexts: EguiContexts,
+    mut step_mode: ResMut<StepMode>,
+    mut background: ResMut<BackgroundOperation>,
+    mut presets: ResMut<OperationPresets>,
+    selection: Res<SelectionSet>,
+    constraints: Res<EditConstraints>,
+    compare: Res<SnapshotCompare>,
+    // Meshes of other workspaces can't be stepped
+    mesh_query: Query<(Entity, Ref<CgarMeshData>, Option<&FeatureEdges>), Without<Stashed>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Step Mode").show(ctx, |ui| {
+        let running = step_mode.mesh.is_some();
+        ui.add_enabled_ui(!running, |ui| {
+            let mut operation = step_mode.operation;
+            egui::ComboBox::from_label("Operation")
+                .selected_text(operation.label())
+                .show_ui(ui, |ui| {
+                    for option in StepOperation::ALL {
+                        ui.selectable_value(&mut operation, option, option.label());
+                    }
+                });
+            if operation != step_mode.operation {
+                step_mode.operation = operation;
+            }
+            let mut within = step_mode.within_selection;
+            ui.checkbox(&mut within, "Only within the selection");
+            if within != step_mode.within_selection {
+                step_mode.within_selection = within;
+            }
+        });
+        match step_mode.operation {
+            StepOperation::Decimate => {
+                let mut target = step_mode.target_faces;
+                ui.add(
+                    egui::DragValue::new(&mut target)
+                        .range(1..=usize::MAX)
+                        .prefix("Stop at faces: "),
+                );
+                if target != step_mode.target_faces {
+                    step_mode.target_faces = target;
+                }
+            }
+            StepOperation::FillHoles => {
+                let mut max_edges = step_mode.max_hole_edges;
+                ui.add(egui::Slider::new(&mut max_edges, 3..=512).text("Max hole edges"));
+                if max_edges != step_mode.max_hole_edges {
+                    step_mode.max_hole_edges = max_edges;
+                }
+            }
+        }
+        ui.add_enabled_ui(step_mode.mesh.is_none(), |ui| {
+            let current = StepPreset::from(&*step_mode);
+            if let Some(preset) = preset_section(ui, &mut presets, &current) {
+                step_mode.operation = preset.operation;
+                step_mode.target_faces = preset.target_faces;
+                step_mode.max_hole_edges = preset.max_hole_edges;
+                step_mode.within_selection = preset.within_selection;
+            }
+        });
+
+        let Some(entity) = step_mode.mesh else {
+            let target = selection
+                .mesh
+                .filter(|e| mesh_query.contains(*e))
+                .or_else(|| mesh_query.iter().next().map(|(entity, ..)| entity));
+            // The selection only counts on the mesh it was picked on
+            let has_selection =
+                target.is_some() && selection.mesh == target && !selection.is_empty();
+            let ready = target.is_some()
+                && (has_selection || !step_mode.within_selection)
+                && !background.busy();
+            let (start, run) = ui
+                .horizontal(|ui| {
+                    (
+                        ui.add_enabled(ready, egui::Button::new("Start")).clicked(),
+                        ui.add_enabled(ready, egui::Button::new("Run to end in background"))
+                            .clicked(),
+                    )
+                })
+                .inner;
+            if step_mode.within_selection && !has_selection {
+                ui.label("Select part of the mesh first");
+            } else {
+                ui.label("Edits the selected mesh in place");
+            }
+            let Some((target, cgar_data, features)) = target
+                .filter(|_| start || run)
+                .and_then(|e| mesh_query.get(e).ok())
+            else {
+                return;
+            };
+            let topology = MeshTopology::from_cgar(&cgar_data.0);
+            let region = if step_mode.within_selection {
+                EditRegion::from_selection(&selection, &topology)
+            } else {
+                None
+            };
+            if start {
+                step_mode.start(target, region);
+                return;
+            }
+            let operation = step_mode.operation;
+            let (locked, limit) = match operation {
+                StepOperation::Decimate => {
+                    let picked = Some(&*selection).filter(|s| s.mesh == Some(target));
+                    let locked =
+                        locked_vertices(region.as_ref(), &constraints, &topology, features, picked);
+                    (locked, step_mode.target_faces)
+                }
+                StepOperation::FillHoles => (BTreeSet::new(), step_mode.max_hole_edges),
+            };
+            let soup = TriangleSoup::from_topology(&topology);
+            let features = features.cloned();
+            let within = region.map(|region| region.vertices);
+            let reference = compare.reference(target);
+            let progress = Arc::new(OperationProgress::default());
+            let task_progress = progress.clone();
+            let task = AsyncComputeTaskPool::get().spawn(async move {
+                run_to_end(
+                    &soup,
+                    operation,
+                    features.as_ref(),
+                    &locked,
+                    within.as_ref(),
+                    limit,
+                    reference.as_deref(),
+                    &task_progress,
+                )
+            });
+            background.spawn(
+                target,
+                cgar_data.last_changed(),
+                operation.label(),
+                progress,
+                task,
+            );
+            return;
+        };
+
+        if let Ok((_, cgar_data, _)) = mesh_query.get(entity) {
+            ui.label(format!(
+                "{} steps, {} faces",
+                step_mode.steps,
+                cgar_data.0.faces.iter().filter(|f| !f.removed).count()
+            ));
+        }
+        let finished = step_mode.last.as_ref().is_some_and(StepEvent::ends_run);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!finished, egui::Button::new("Next"))
+                .clicked()
+            {
+                step_mode.playing = false;
+                step_mode.pending += 1;
+            }
+            let playing = step_mode.playing;
+            if ui
+                .add_enabled(
+                    !finished,
+                    egui::Button::new(if playing { "Pause" } else { "Play" }),
+                )
+                .clicked()
+            {
+                step_mode.playing = !playing;
+            }
+            if ui.button("Stop").clicked() {
+                step_mode.stop();
+            }
+        });
+        let mut rate = step_mode.steps_per_second;
+        ui.add(
+            egui::Slider::new(&mut rate, 0.5..=1000.0)
+                .logarithmic(true)
+                .text("Steps per second"),
+        );
+        if rate != step_mode.steps_per_second {
+            step_mode.steps_per_second = rate;
+        }
+
+        ui.collapsing("Breakpoints", |ui| {
+            let mut on_reject = step_mode.break_on_reject;
+            ui.checkbox(&mut on_reject, "Pause when a collapse is rejected");
+            if on_reject != step_mode.break_on_reject {
+                step_mode.break_on_reject = on_reject;
+            }
+            ui.horizontal(|ui| {
+                let mut on_quality = step_mode.break_on_quality;
+                ui.checkbox(&mut on_quality, "Pause when triangle quality drops below");
+                if on_quality != step_mode.break_on_quality {
+                    step_mode.break_on_quality = on_quality;
+                }
+                let mut min_quality = step_mode.min_quality;
+                ui.add(
+                    egui::DragValue::new(&mut min_quality)
+                        .speed(0.005)
+                        .range(0.0..=1.0),
+                );
+                if min_quality != step_mode.min_quality {
+                    step_mode.min_quality = min_quality;
+                }
+            });
+            let mut focus = step_mode.focus_on_break;
+            ui.checkbox(&mut focus, "Focus the camera on the triggering element");
+            if focus != step_mode.focus_on_break {
+                step_mode.focus_on_break = focus;
+            }
+            ui.label("Quality is 1 for equilateral triangles and 0 for degenerate ones");
+        });
+
+        if let Some(breakpoint) = &step_mode.breakpoint {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("Breakpoint: {}", breakpoint.reason),
+            );
+        }
+        if let Some(event) = &step_mode.last {
+            ui.separator();
+            ui.label(&event.message);
+        }
+        if !step_mode.log.is_empty() {
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for line in &step_mode.log {
+                        ui.label(line);
+                    }
+                });
+        }
+    });
+}
+
+// Top of the decimation queue during a step mode run. Hovering a row
+// highlights its edge and where the collapse would put the vertex.
+pub fn collapse_queue_panel(mut contexts: EguiContexts, mut step_mode: ResMut<StepMode>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if step_mode.mesh.is_none() || step_mode.operation != StepOperation::Decimate {
+        if step_mode.hovered.is_some() {
+            step_mode.hovered = None;
+        }
+        return;
+    }
+
+    egui::Window::new("Collapse Queue").show(ctx, |ui| {
+        let mut size = step_mode.queue_size;
+        ui.add(egui::Slider::new(&mut size, 1..=200).text("Candidates"));
+        if size != step_mode.queue_size {
+            step_mode.queue_size = size;
+            step_mode.queue_stale = true;
+        }
+        ui.label(format!(
+            "{} edges rejected so far are left out",
+            step_mode.rejected.len()
+        ));
+
+        let mut rows: Vec<(usize, CollapseCandidate)> =
+            step_mode.queue.iter().copied().enumerate().collect();
+        match step_mode.queue_sort {
+            QueueSort::Cost => rows.sort_by(|a, b| a.1.cost.total_cmp(&b.1.cost)),
+            QueueSort::Edge => rows.sort_by_key(|(_, candidate)| candidate.edge),
+        }
+        if step_mode.queue_descending {
+            rows.reverse();
+        }
+
+        let (sort, descending) = (step_mode.queue_sort, step_mode.queue_descending);
+        let mut clicked_sort = None;
+        let mut hovered = None;
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("collapse_queue")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("#");
+                        for (column, label) in
+                            [(QueueSort::Edge, "Edge"), (QueueSort::Cost, "Cost")]
+                        {
+                            let arrow = match (column == sort, descending) {
+                                (true, false) => " ^",
+                                (true, true) => " v",
+                                (false, _) => "",
+                            };
+                            if ui
+                                .selectable_label(column == sort, format!("{}{}", label, arrow))
+                                .clicked()
+                            {
+                                clicked_sort = Some(column);
+                            }
+                        }
+                        ui.label("Predicted position");
+                        ui.end_row();
+
+                        for (rank, candidate) in &rows {
+                            let (v0, v1) = candidate.edge;
+                            let p = candidate.position;
+                            let responses = [
+                                ui.label((rank + 1).to_string()),
+                                ui.label(format!("({}, {})", v0, v1)),
+                                ui.label(format!("{:.6}", candidate.cost)),
+                                ui.label(format!("({:.4}, {:.4}, {:.4})", p.x, p.y, p.z)),
+                            ];
+                            ui.end_row();
+                            if responses.iter().any(|response| response.hovered()) {
+                                hovered = Some(candidate.edge);
+                            }
+                        }
+                    });
+            });
+        ui.label("Ranks follow cost; the first row is the next collapse");
+
+        if let Some(column) = clicked_sort {
+            if column == sort {
+                step_mode.queue_descending = !descending;
+            } else {
+                step_mode.queue_sort = column;
+                step_mode.queue_descending = false;
+            }
+        }
+        if hovered != step_mode.hovered {
+            step_mode.hovered = hovered;
+        }
+    });
+}
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Red-cyan anaglyph composite of the two stereo eye renders. Each eye is
+// reduced to luminance before splitting channels ("half-color"), which keeps
+// colored overlays from bleeding into the wrong eye.
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(2) @binding(0) var left_texture: texture_2d<f32>;
+@group(2) @binding(1) var left_sampler: sampler;
+@group(2) @binding(2) var right_texture: texture_2d<f32>;
+@group(2) @binding(3) var right_sampler: sampler;
+
+const LUMA: vec3<f32> = vec3<f32>(0.299, 0.587, 0.114);
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let left = textureSample(left_texture, left_sampler, mesh.uv).rgb;
+    let right = textureSample(right_texture, right_sampler, mesh.uv).rgb;
+    return vec4<f32>(dot(left, LUMA), right.g, right.b, 1.0);
+}
@@ -0,0 +1,351 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    asset::{Asset, Assets, Handle, RenderAssetUsages, weak_handle},
+    core_pipeline::{core_2d::Camera2d, core_3d::Camera3d, tonemapping::Tonemapping},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        hierarchy::ChildOf,
+        query::{Or, With, Without},
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+    },
+    image::Image,
+    math::{UVec2, Vec3, primitives::Rectangle},
+    picking::Pickable,
+    reflect::TypePath,
+    render::{
+        camera::{Camera, ClearColorConfig, Projection, RenderTarget, Viewport},
+        mesh::{Mesh, Mesh2d},
+        render_resource::{
+            AsBindGroup, Extent3d, Shader, ShaderRef, TextureDimension, TextureFormat,
+            TextureUsages,
+        },
+        view::RenderLayers,
+    },
+    sprite::{Material2d, MeshMaterial2d},
+    transform::components::Transform,
+    utils::default,
+    window::{PrimaryWindow, Window},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::OrbitCamera;
+
+const ANAGLYPH_SHADER: Handle<Shader> = weak_handle!("5b0f7a52-3d1e-4c9a-9a43-8e2f61c4d7b1");
+
+// Layer nothing is spawned on; the main camera switches to it while the eye
+// cameras draw the scene, so it only contributes the egui pass
+const HIDDEN_LAYER: usize = 30;
+// Layer holding the anaglyph composite quad and its 2D camera
+const COMPOSITE_LAYER: usize = 31;
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StereoMode {
+    #[default]
+    Off,
+    SideBySide,
+    Anaglyph,
+}
+
+impl StereoMode {
+    pub const ALL: [StereoMode; 3] = [
+        StereoMode::Off,
+        StereoMode::SideBySide,
+        StereoMode::Anaglyph,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StereoMode::Off => "Off",
+            StereoMode::SideBySide => "Side-by-side",
+            StereoMode::Anaglyph => "Red-cyan anaglyph",
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct StereoSettings {
+    pub mode: StereoMode,
+    // Distance between the eyes as a fraction of the distance to the orbit focus
+    pub eye_separation: f32,
+    // Cross-eyed viewing for side-by-side, blue-red glasses for anaglyph
+    pub swap_eyes: bool,
+}
+
+impl Default for StereoSettings {
+    fn default() -> Self {
+        Self {
+            mode: StereoMode::Off,
+            eye_separation: 0.03,
+            swap_eyes: false,
+        }
+    }
+}
+
+// Eye camera parented to the orbit camera; `side` is -1 for left, 1 for right
+#[derive(Component)]
+pub struct StereoEye {
+    pub side: f32,
+    pub target: Option<Handle<Image>>,
+}
+
+// Marks the anaglyph composite camera and its fullscreen quad
+#[derive(Component)]
+pub struct StereoComposite;
+
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+pub struct AnaglyphMaterial {
+    #[texture(0)]
+    #[sampler(1)]
+    pub left: Handle<Image>,
+    #[texture(2)]
+    #[sampler(3)]
+    pub right: Handle<Image>,
+}
+
+impl Material2d for AnaglyphMaterial {
+    fn fragment_shader() -> ShaderRef {
+        ANAGLYPH_SHADER.into()
+    }
+}
+
+pub fn setup_stereo_shader(mut shaders: ResMut<Assets<Shader>>) {
+    shaders.insert(
+        &ANAGLYPH_SHADER,
+        Shader::from_wgsl(include_str!("anaglyph.wgsl"), file!()),
+    );
+}
+
+fn eye_target(size: UVec2) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x.max(1),
+            height: size.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+// Rebuilds the eye cameras whenever the stereo mode changes
+#[allow(clippy::too_many_arguments)]
+pub fn apply_stereo_mode(
+    mut commands: Commands,
+    settings: Res<StereoSettings>,
+    mut applied: Local<StereoMode>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut main_query: Query<(Entity, &mut Camera), With<OrbitCamera>>,
+    rig_query: Query<Entity, Or<(With<StereoEye>, With<StereoComposite>)>>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<AnaglyphMaterial>>,
+) {
+    if settings.mode == *applied {
+        return;
+    }
+    let (Ok(window), Ok((main_entity, mut main_camera))) =
+        (windows.single(), main_query.single_mut())
+    else {
+        return;
+    };
+    *applied = settings.mode;
+
+    for entity in &rig_query {
+        commands.entity(entity).despawn();
+    }
+
+    if settings.mode == StereoMode::Off {
+        main_camera.clear_color = ClearColorConfig::Default;
+        commands.entity(main_entity).insert(RenderLayers::default());
+        return;
+    }
+
+    // The main camera keeps driving navigation and hosts egui, drawn last
+    // over whatever the eyes produced
+    main_camera.clear_color = ClearColorConfig::None;
+    commands
+        .entity(main_entity)
+        .insert(RenderLayers::layer(HIDDEN_LAYER));
+
+    let size = window.physical_size();
+    let mut eye_targets = Vec::new();
+    for (order, side) in [(-3, -1.0), (-2, 1.0)] {
+        let target = (settings.mode == StereoMode::Anaglyph).then(|| images.add(eye_target(size)));
+        let camera = Camera {
+            order,
+            target: target
+                .clone()
+                .map(|image| RenderTarget::Image(image.into()))
+                .unwrap_or_default(),
+            ..default()
+        };
+        eye_targets.extend(target.clone());
+        commands.spawn((
+            Camera3d::default(),
+            camera,
+            Transform::default(),
+            StereoEye { side, target },
+            ChildOf(main_entity),
+        ));
+    }
+
+    if let [left, right] = eye_targets.as_slice() {
+        let material = materials.add(AnaglyphMaterial {
+            left: left.clone(),
+            right: right.clone(),
+        });
+        commands.spawn((
+            Camera2d,
+            Camera {
+                order: -1,
+                ..default()
+            },
+            Tonemapping::None,
+            RenderLayers::layer(COMPOSITE_LAYER),
+            StereoComposite,
+        ));
+        commands.spawn((
+            Mesh2d(meshes.add(Rectangle::new(1.0, 1.0))),
+            MeshMaterial2d(material),
+            Transform::from_scale(window.size().extend(1.0)),
+            RenderLayers::layer(COMPOSITE_LAYER),
+            Pickable::IGNORE,
+            StereoComposite,
+        ));
+    }
+}
+
+// Keeps the eyes converged on the orbit focus and sized to the window
+pub fn sync_stereo_eyes(
+    settings: Res<StereoSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    main_query: Query<(&Transform, &OrbitCamera, &Projection), Without<StereoEye>>,
+    mut eye_query: Query<(&StereoEye, &mut Transform, &mut Projection, &mut Camera)>,
+    mut quad_query: Query<
+        &mut Transform,
+        (
+            With<StereoComposite>,
+            With<Mesh2d>,
+            Without<StereoEye>,
+            Without<OrbitCamera>,
+        ),
+    >,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let (Ok(window), Ok((main_transform, orbit, main_projection))) =
+        (windows.single(), main_query.single())
+    else {
+        return;
+    };
+    let size = window.physical_size();
+    let distance = (orbit.focus - main_transform.translation)
+        .length()
+        .max(f32::EPSILON);
+    let swap = if settings.swap_eyes { -1.0 } else { 1.0 };
+
+    for (eye, mut transform, mut projection, mut camera) in &mut eye_query {
+        let offset = eye.side * swap * settings.eye_separation * distance * 0.5;
+        *transform = Transform::from_xyz(offset, 0.0, 0.0)
+            .looking_at(Vec3::new(0.0, 0.0, -distance), Vec3::Y);
+        *projection = main_projection.clone();
+
+        match &eye.target {
+            Some(image) => {
+                let extent = Extent3d {
+                    width: size.x.max(1),
+                    height: size.y.max(1),
+                    depth_or_array_layers: 1,
+                };
+                if let Some(image) = images
+                    .get_mut(image)
+                    .filter(|image| image.texture_descriptor.size != extent)
+                {
+                    image.resize(extent);
+                }
+            }
+            None => {
+                // Left eye on the left half, right eye on the right half
+                let half = size.x / 2;
+                let viewport = Viewport {
+                    physical_position: UVec2::new(if eye.side < 0.0 { 0 } else { half }, 0),
+                    physical_size: UVec2::new(half.max(1), size.y.max(1)),
+                    ..default()
+                };
+                if camera.viewport.as_ref() != Some(&viewport) {
+                    camera.viewport = Some(viewport);
+                }
+            }
+        }
+    }
+
+    for mut transform in &mut quad_query {
+        transform.scale = window.size().extend(1.0);
+    }
+}
+
+pub fn stereo_panel(mut contexts: EguiContexts, mut settings: ResMut<StereoSettings>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Stereo").show(ctx, |ui| {
+        let mut mode = settings.mode;
+        egui::ComboBox::from_label("Mode")
+            .selected_text(mode.label())
+            .show_ui(ui, |ui| {
+                for candidate in StereoMode::ALL {
+                    ui.selectable_value(&mut mode, candidate, candidate.label());
+                }
+            });
+
+        let mut eye_separation = settings.eye_separation;
+        let mut swap_eyes = settings.swap_eyes;
+        ui.add_enabled_ui(mode != StereoMode::Off, |ui| {
+            ui.add(
+                egui::Slider::new(&mut eye_separation, 0.0..=0.2)
+                    .text("Eye separation")
+                    .custom_formatter(|v, _| format!("{:.1}%", v * 100.0)),
+            );
+            ui.checkbox(&mut swap_eyes, "Swap eyes");
+        });
+        if mode != StereoMode::Off {
+            ui.label("Separation is relative to the distance to the orbit focus");
+        }
+
+        if (mode, eye_separation, swap_eyes)
+            != (settings.mode, settings.eye_separation, settings.swap_eyes)
+        {
+            settings.mode = mode;
+            settings.eye_separation = eye_separation;
+            settings.swap_eyes = swap_eyes;
+        }
+    });
+}
@@ -0,0 +1,413 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::extension::{ToolPick, ViewerTools};
+use bevy::{
+    color::Color,
+    ecs::{
+        entity::Entity,
+        event::{EventCursor, EventReader, EventWriter, Events},
+        query::With,
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+        world::{Mut, World},
+    },
+    gizmos::gizmos::Gizmos,
+    input::{ButtonInput, keyboard::KeyCode},
+    log::info,
+    math::{Isometry3d, Vec3},
+    state::state::{NextState, State, States},
+    window::{PrimaryWindow, SystemCursorIcon, Window},
+    winit::cursor::CursorIcon,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::edit::systems::VertexEdit;
+use crate::mesh::collapse::{CollapseOptions, CollapsePlacement};
+use crate::mesh::edge::MeshPicked;
+use crate::notifications::systems::Notify;
+use crate::probe::systems::ScalarProbe;
+
+// The interactive tool that owns left clicks on meshes. Every tool has a
+// shortcut, a cursor and a status-bar hint; tools with setup or teardown add
+// systems to `OnEnter`/`OnExit` of their state. `Custom` tools come from
+// `ViewerTools`, by registration order, and bring their own hooks.
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActiveTool {
+    #[default]
+    Select,
+    Measure,
+    Collapse,
+    Flip,
+    Split,
+    TagFeature,
+    VertexMove,
+    Sculpt,
+    AreaSelect,
+    Weld,
+    FacePath,
+    Custom(usize),
+}
+
+impl ActiveTool {
+    pub const ALL: [ActiveTool; 11] = [
+        ActiveTool::Select,
+        ActiveTool::Measure,
+        ActiveTool::Collapse,
+        ActiveTool::Flip,
+        ActiveTool::Split,
+        ActiveTool::TagFeature,
+        ActiveTool::VertexMove,
+        ActiveTool::Sculpt,
+        ActiveTool::AreaSelect,
+        ActiveTool::Weld,
+        ActiveTool::FacePath,
+    ];
+
+    // Built-in tools followed by the registered custom ones
+    pub fn all(tools: &ViewerTools) -> impl Iterator<Item = ActiveTool> {
+        ActiveTool::ALL
+            .into_iter()
+            .chain((0..tools.len()).map(ActiveTool::Custom))
+    }
+
+    pub fn label(self, tools: &ViewerTools) -> &str {
+        match self {
+            ActiveTool::Select => "Select",
+            ActiveTool::Measure => "Measure",
+            ActiveTool::Collapse => "Collapse",
+            ActiveTool::Flip => "Flip",
+            ActiveTool::Split => "Split",
+            ActiveTool::TagFeature => "Tag feature",
+            ActiveTool::VertexMove => "Move vertex",
+            ActiveTool::Sculpt => "Sculpt",
+            ActiveTool::AreaSelect => "Area select",
+            ActiveTool::Weld => "Weld",
+            ActiveTool::FacePath => "Face path",
+            ActiveTool::Custom(id) => tools.get(id).map_or("", |tool| tool.label()),
+        }
+    }
+
+    // Pressing it again returns to Select
+    pub fn shortcut(self, tools: &ViewerTools) -> Option<KeyCode> {
+        match self {
+            ActiveTool::Select => Some(KeyCode::Escape),
+            ActiveTool::Measure => Some(KeyCode::KeyM),
+            ActiveTool::Collapse => Some(KeyCode::KeyE),
+            ActiveTool::Flip => Some(KeyCode::KeyR),
+            ActiveTool::Split => Some(KeyCode::KeyS),
+            ActiveTool::TagFeature => Some(KeyCode::KeyF),
+            ActiveTool::VertexMove => Some(KeyCode::KeyV),
+            ActiveTool::Sculpt => Some(KeyCode::KeyB),
+            ActiveTool::AreaSelect => Some(KeyCode::KeyL),
+            ActiveTool::Weld => Some(KeyCode::KeyJ),
+            ActiveTool::FacePath => Some(KeyCode::KeyK),
+            ActiveTool::Custom(id) => tools
+                .get(id)
+                .and_then(|tool| tool.shortcut())
+                .map(|(key, _)| key),
+        }
+    }
+
+    fn shortcut_label(self, tools: &ViewerTools) -> Option<&str> {
+        match self {
+            ActiveTool::Select => Some("Esc"),
+            ActiveTool::Measure => Some("M"),
+            ActiveTool::Collapse => Some("E"),
+            ActiveTool::Flip => Some("R"),
+            ActiveTool::Split => Some("S"),
+            ActiveTool::TagFeature => Some("F"),
+            ActiveTool::VertexMove => Some("V"),
+            ActiveTool::Sculpt => Some("B"),
+            ActiveTool::AreaSelect => Some("L"),
+            ActiveTool::Weld => Some("J"),
+            ActiveTool::FacePath => Some("K"),
+            ActiveTool::Custom(id) => tools
+                .get(id)
+                .and_then(|tool| tool.shortcut())
+                .map(|(_, label)| label),
+        }
+    }
+
+    pub fn hint(self, tools: &ViewerTools) -> &str {
+        match self {
+            ActiveTool::Select => {
+                "Click to select faces and edges; Shift extends, Alt picks an edge loop, Ctrl+Alt a ring"
+            }
+            ActiveTool::Measure => "Click two points on a mesh to measure the distance",
+            ActiveTool::Collapse => "Click an edge to collapse it",
+            ActiveTool::Flip => "Click an edge shared by two faces to flip it",
+            ActiveTool::Split => "Click an edge or a face to split it where you click",
+            ActiveTool::TagFeature => "Click an edge to toggle its feature tag",
+            ActiveTool::VertexMove => "Drag a vertex to move it; hold Ctrl to snap",
+            ActiveTool::Sculpt => "Drag over a mesh to apply the brush",
+            ActiveTool::AreaSelect => "Drag a box or lasso to select; Shift adds, Ctrl removes",
+            ActiveTool::Weld => "Click two vertices to weld them into one; Ctrl+Z undoes",
+            ActiveTool::FacePath => {
+                "Click two faces to select the shortest face path between them; Shift extends"
+            }
+            ActiveTool::Custom(id) => tools.get(id).map_or("", |tool| tool.hint()),
+        }
+    }
+
+    fn cursor(self, tools: &ViewerTools) -> SystemCursorIcon {
+        match self {
+            ActiveTool::Select => SystemCursorIcon::Default,
+            ActiveTool::Measure
+            | ActiveTool::Sculpt
+            | ActiveTool::AreaSelect
+            | ActiveTool::Weld
+            | ActiveTool::FacePath => SystemCursorIcon::Crosshair,
+            ActiveTool::Collapse | ActiveTool::Flip | ActiveTool::Split => {
+                SystemCursorIcon::Pointer
+            }
+            ActiveTool::TagFeature => SystemCursorIcon::Cell,
+            ActiveTool::VertexMove => SystemCursorIcon::Move,
+            ActiveTool::Custom(id) => tools
+                .get(id)
+                .map_or(SystemCursorIcon::Default, |tool| tool.cursor()),
+        }
+    }
+}
+
+// Two world-space points picked with the Measure tool or the context menu
+#[derive(Resource, Default, Debug)]
+pub struct Measurement {
+    pub from: Option<Vec3>,
+    pub to: Option<Vec3>,
+}
+
+impl Measurement {
+    // Starts a new measurement, or finishes the pending one returning its length
+    pub fn record(&mut self, point: Vec3) -> Option<f32> {
+        match (self.from, self.to) {
+            (Some(from), None) => {
+                self.to = Some(point);
+                Some(from.distance(point))
+            }
+            _ => {
+                self.from = Some(point);
+                self.to = None;
+                None
+            }
+        }
+    }
+}
+
+pub fn tool_shortcuts(
+    kb: Res<ButtonInput<KeyCode>>,
+    tool: Res<State<ActiveTool>>,
+    mut next_tool: ResMut<NextState<ActiveTool>>,
+    tools: Res<ViewerTools>,
+) {
+    let current = *tool.get();
+    let Some(pressed) = ActiveTool::all(&tools).find(|tool| {
+        tool.shortcut(&tools)
+            .is_some_and(|key| kb.just_pressed(key))
+    }) else {
+        return;
+    };
+    let wanted = if pressed == current {
+        ActiveTool::Select
+    } else {
+        pressed
+    };
+    if wanted != current {
+        next_tool.set(wanted);
+        info!("Tool: {}", wanted.label(&tools));
+    }
+}
+
+pub fn enter_vertex_move(mut edit: ResMut<VertexEdit>) {
+    edit.enabled = true;
+}
+
+pub fn exit_vertex_move(mut edit: ResMut<VertexEdit>) {
+    edit.enabled = false;
+    edit.drag = None;
+    edit.snap = None;
+}
+
+// Leaving Measure drops a half-finished measurement
+pub fn exit_measure(mut measurement: ResMut<Measurement>) {
+    if measurement.to.is_none() {
+        measurement.from = None;
+    }
+}
+
+pub fn update_tool_cursor(
+    mut commands: Commands,
+    tool: Res<State<ActiveTool>>,
+    tools: Res<ViewerTools>,
+    window_query: Query<Entity, With<PrimaryWindow>>,
+) {
+    if !tool.is_changed() {
+        return;
+    }
+    if let Ok(window) = window_query.single() {
+        commands
+            .entity(window)
+            .insert(CursorIcon::from(tool.get().cursor(&tools)));
+    }
+}
+
+pub fn measure_on_pick(
+    mut picked: EventReader<MeshPicked>,
+    mut measurement: ResMut<Measurement>,
+    mut notices: EventWriter<Notify>,
+) {
+    for pick in picked.read().filter(|pick| !pick.double_click) {
+        // Exact hit on the full-resolution mesh when there is one
+        let Some(point) = pick.face_hit.map(|hit| hit.world).or(pick.world_position) else {
+            continue;
+        };
+        if let Some(distance) = measurement.record(point) {
+            notices.write(Notify::info(format!("Distance: {:.6}", distance)));
+        }
+    }
+}
+
+pub fn draw_measurement(mut gizmos: Gizmos, measurement: Res<Measurement>) {
+    let color = Color::srgb(0.3, 0.8, 1.0);
+    if let Some(from) = measurement.from {
+        gizmos.sphere(Isometry3d::from_translation(from), 0.01, color);
+        if let Some(to) = measurement.to {
+            gizmos.sphere(Isometry3d::from_translation(to), 0.01, color);
+            gizmos.line(from, to, color);
+        }
+    }
+}
+
+// Tool buttons and the active tool's hint along the bottom of the window
+pub fn status_bar(
+    mut contexts: EguiContexts,
+    tool: Res<State<ActiveTool>>,
+    mut next_tool: ResMut<NextState<ActiveTool>>,
+    measurement: Res<Measurement>,
+    scalar_probe: Res<ScalarProbe>,
+    mut collapse_options: ResMut<CollapseOptions>,
+    mut tools: ResMut<ViewerTools>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let current = *tool.get();
+    egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            for tool in ActiveTool::all(&tools) {
+                let label = match tool.shortcut_label(&tools) {
+                    Some(key) => format!("{} ({})", tool.label(&tools), key),
+                    None => tool.label(&tools).to_string(),
+                };
+                if ui.selectable_label(tool == current, label).clicked() && tool != current {
+                    next_tool.set(tool);
+                }
+            }
+            ui.separator();
+            ui.label(current.hint(&tools));
+            if current == ActiveTool::Collapse {
+                let mut placement = collapse_options.placement;
+                egui::ComboBox::from_id_salt("collapse_placement")
+                    .selected_text(placement.label())
+                    .show_ui(ui, |ui| {
+                        for option in CollapsePlacement::ALL {
+                            ui.selectable_value(&mut placement, option, option.label());
+                        }
+                    });
+                if placement != collapse_options.placement {
+                    collapse_options.placement = placement;
+                }
+            }
+            let custom = match current {
+                ActiveTool::Custom(id) => tools.get_mut(id),
+                _ => None,
+            };
+            if let Some(custom) = custom {
+                ui.separator();
+                custom.options_ui(ui);
+            }
+            if let (ActiveTool::Measure, Some(from), Some(to)) =
+                (current, measurement.from, measurement.to)
+            {
+                ui.separator();
+                ui.label(format!("Distance: {:.6}", from.distance(to)));
+            }
+            if let Some(reading) = scalar_probe.reading {
+                ui.separator();
+                ui.label(format!("{}: {:.6}", reading.kind.label(), reading.value));
+            }
+        });
+    });
+}
+
+// Drives the active custom tool: activation hooks when the tool changes,
+// then its mesh clicks and per-frame update
+pub fn run_custom_tools(
+    world: &mut World,
+    mut picks: Local<EventCursor<MeshPicked>>,
+    mut previous: Local<Option<usize>>,
+) {
+    let current = match world.resource::<State<ActiveTool>>().get() {
+        ActiveTool::Custom(id) => Some(*id),
+        _ => None,
+    };
+    // Read every frame so clicks made under other tools don't pile up
+    let clicks: Vec<ToolPick> = picks
+        .read(world.resource::<Events<MeshPicked>>())
+        .filter(|pick| !pick.double_click)
+        .map(|pick| ToolPick {
+            entity: pick.entity,
+            world_position: pick.face_hit.map(|hit| hit.world).or(pick.world_position),
+            face: pick.face_hit.map(|hit| (hit.face, hit.barycentric)),
+        })
+        .collect();
+    if current.is_none() && previous.is_none() {
+        return;
+    }
+    world.resource_scope(|world, mut tools: Mut<ViewerTools>| {
+        if *previous != current {
+            if let Some(tool) = previous.and_then(|id| tools.get_mut(id)) {
+                tool.deactivate(world);
+            }
+            if let Some(tool) = current.and_then(|id| tools.get_mut(id)) {
+                tool.activate(world);
+            }
+            *previous = current;
+        }
+        let Some(tool) = current.and_then(|id| tools.get_mut(id)) else {
+            return;
+        };
+        for click in clicks {
+            tool.on_pick(click, world);
+        }
+        tool.update(world);
+    });
+}
+
+pub fn draw_custom_tool(mut gizmos: Gizmos, tool: Res<State<ActiveTool>>, tools: Res<ViewerTools>) {
+    let ActiveTool::Custom(id) = *tool.get() else {
+        return;
+    };
+    if let Some(custom) = tools.get(id) {
+        custom.draw_gizmos(&mut gizmos);
+    }
+}
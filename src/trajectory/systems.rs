@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    gizmos::gizmos::Gizmos,
+    math::{DVec3, Vec3},
+    time::Time,
+    transform::components::GlobalTransform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::analysis::colormap::heat;
+
+// Samples kept per recording; later steps are dropped so long strokes or
+// many iterations over large meshes don't exhaust memory
+const MAX_SAMPLES: usize = 4_000_000;
+// Vertices drawn at most; the rest are skipped evenly
+const MAX_DRAWN: usize = 20_000;
+
+// Positions one operation moved vertices through, in the mesh's local space
+pub struct Trajectory {
+    pub mesh: Entity,
+    pub label: String,
+    // Number of recorded steps; step 0 is the state before the operation
+    pub steps: usize,
+    // Per moved vertex, its position at step 0 and after every step that
+    // moved it
+    pub tracks: BTreeMap<usize, Vec<(usize, Vec3)>>,
+    // Positions at step 0, for vertices that haven't moved yet
+    start: Vec<Vec3>,
+    samples: usize,
+    pub truncated: bool,
+}
+
+impl Trajectory {
+    fn new(mesh: Entity, label: String, positions: &[DVec3]) -> Self {
+        Self {
+            mesh,
+            label,
+            steps: 0,
+            tracks: BTreeMap::new(),
+            start: positions.iter().map(|p| p.as_vec3()).collect(),
+            samples: 0,
+            truncated: false,
+        }
+    }
+
+    // Position of `track` after `step`
+    fn position_at(track: &[(usize, Vec3)], step: usize) -> Vec3 {
+        let index = track.partition_point(|&(s, _)| s <= step);
+        track[index.saturating_sub(1)].1
+    }
+
+    fn displacement(track: &[(usize, Vec3)], step: usize) -> f32 {
+        track[0].1.distance(Trajectory::position_at(track, step))
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrajectoryView {
+    // Arrow from each vertex's start to its position at the current step
+    #[default]
+    Vectors,
+    // The path each vertex took up to the current step
+    Paths,
+}
+
+impl TrajectoryView {
+    pub const ALL: [TrajectoryView; 2] = [TrajectoryView::Vectors, TrajectoryView::Paths];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TrajectoryView::Vectors => "Displacement vectors",
+            TrajectoryView::Paths => "Paths",
+        }
+    }
+}
+
+// Records how iterative operations (sculpt strokes, smoothing passes) move
+// vertices, and plays the recording back over the mesh
+#[derive(Resource)]
+pub struct TrajectoryRecorder {
+    pub enabled: bool,
+    pub trajectory: Option<Trajectory>,
+    pub view: TrajectoryView,
+    pub step: usize,
+    pub playing: bool,
+    pub steps_per_second: f32,
+    // Drawn displacements are scaled by this, for motions too small to see
+    pub scale: f32,
+    // Vertices that moved less than this in total are not drawn
+    pub min_displacement: f32,
+    // Fractional step while playing
+    progress: f32,
+}
+
+impl Default for TrajectoryRecorder {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trajectory: None,
+            view: TrajectoryView::default(),
+            step: 0,
+            playing: false,
+            steps_per_second: 10.0,
+            scale: 1.0,
+            min_displacement: 0.0,
+            progress: 0.0,
+        }
+    }
+}
+
+impl TrajectoryRecorder {
+    // Starts a new recording of `mesh` from `positions`, if recording is on
+    pub fn begin(&mut self, mesh: Entity, label: impl Into<String>, positions: &[DVec3]) {
+        if !self.enabled {
+            return;
+        }
+        self.trajectory = Some(Trajectory::new(mesh, label.into(), positions));
+        self.step = 0;
+        self.playing = false;
+        self.progress = 0.0;
+    }
+
+    // Adds one step moving vertices to the given positions. Ignored unless a
+    // recording of `mesh` is in progress.
+    pub fn record(&mut self, mesh: Entity, moves: impl IntoIterator<Item = (usize, DVec3)>) {
+        let Some(trajectory) = self
+            .trajectory
+            .as_mut()
+            .filter(|t| self.enabled && t.mesh == mesh && !t.truncated)
+        else {
+            return;
+        };
+        let step = trajectory.steps + 1;
+        let mut any = false;
+        for (v, p) in moves {
+            let Some(&start) = trajectory.start.get(v) else {
+                continue;
+            };
+            if trajectory.samples >= MAX_SAMPLES {
+                trajectory.truncated = true;
+                break;
+            }
+            let track = trajectory
+                .tracks
+                .entry(v)
+                .or_insert_with(|| vec![(0, start)]);
+            track.push((step, p.as_vec3()));
+            trajectory.samples += 1;
+            any = true;
+        }
+        if any {
+            trajectory.steps = step;
+            self.step = step;
+        }
+    }
+}
+
+pub fn play_trajectory(time: Res<Time>, mut recorder: ResMut<TrajectoryRecorder>) {
+    let Some(steps) = recorder.trajectory.as_ref().map(|t| t.steps) else {
+        return;
+    };
+    if !recorder.playing || steps == 0 {
+        return;
+    }
+    let progress = recorder.progress + time.delta_secs() * recorder.steps_per_second;
+    let advance = progress.floor();
+    recorder.progress = progress - advance;
+    if advance >= 1.0 {
+        // Loops back to the start after the last step
+        recorder.step = (recorder.step + advance as usize) % (steps + 1);
+    }
+}
+
+pub fn draw_trajectory(
+    mut gizmos: Gizmos,
+    recorder: Res<TrajectoryRecorder>,
+    mesh_query: Query<&GlobalTransform>,
+) {
+    let Some(trajectory) = recorder.trajectory.as_ref() else {
+        return;
+    };
+    let Ok(mesh_global) = mesh_query.get(trajectory.mesh) else {
+        return;
+    };
+    let step = recorder.step;
+    let shown: Vec<(&Vec<(usize, Vec3)>, f32)> = trajectory
+        .tracks
+        .values()
+        .map(|track| (track, Trajectory::displacement(track, trajectory.steps)))
+        .filter(|&(_, total)| total > 0.0 && total >= recorder.min_displacement)
+        .collect();
+    let Some(longest) = shown.iter().map(|&(_, total)| total).reduce(f32::max) else {
+        return;
+    };
+    let stride = shown.len().div_ceil(MAX_DRAWN).max(1);
+
+    let scale = recorder.scale;
+    // Displacements are exaggerated around each vertex's start
+    let world = |start: Vec3, p: Vec3| mesh_global.transform_point(start + (p - start) * scale);
+    for &(track, total) in shown.iter().step_by(stride) {
+        let color = heat(total / longest);
+        let start = track[0].1;
+        match recorder.view {
+            TrajectoryView::Vectors => {
+                let now = Trajectory::position_at(track, step);
+                if now != start {
+                    gizmos.arrow(world(start, start), world(start, now), color);
+                }
+            }
+            TrajectoryView::Paths => {
+                let end = track.partition_point(|&(s, _)| s <= step);
+                gizmos.linestrip(track[..end].iter().map(|&(_, p)| world(start, p)), color);
+            }
+        }
+    }
+}
+
+pub fn trajectory_panel(mut contexts: EguiContexts, mut recorder: ResMut<TrajectoryRecorder>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Trajectories").show(ctx, |ui| {
+        let mut enabled = recorder.enabled;
+        ui.checkbox(&mut enabled, "Record vertex motion");
+        if enabled != recorder.enabled {
+            recorder.enabled = enabled;
+        }
+        ui.label("Sculpt strokes and mesh smoothing are recorded while this is on");
+
+        let Some((label, steps, moved, truncated)) = recorder
+            .trajectory
+            .as_ref()
+            .map(|t| (t.label.clone(), t.steps, t.tracks.len(), t.truncated))
+        else {
+            return;
+        };
+        ui.separator();
+        ui.label(format!(
+            "{}: {} steps, {} vertices moved",
+            label, steps, moved
+        ));
+        if truncated {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "Recording stopped early to limit memory",
+            );
+        }
+
+        let mut view = recorder.view;
+        egui::ComboBox::from_label("Show")
+            .selected_text(view.label())
+            .show_ui(ui, |ui| {
+                for option in TrajectoryView::ALL {
+                    ui.selectable_value(&mut view, option, option.label());
+                }
+            });
+        if view != recorder.view {
+            recorder.view = view;
+        }
+
+        let mut step = recorder.step.min(steps);
+        ui.add(egui::Slider::new(&mut step, 0..=steps).text("Step"));
+        if step != recorder.step {
+            recorder.step = step;
+        }
+        ui.horizontal(|ui| {
+            let playing = recorder.playing;
+            if ui.button(if playing { "Pause" } else { "Play" }).clicked() {
+                recorder.playing = !playing;
+            }
+            if ui.button("Last step").clicked() {
+                recorder.playing = false;
+                recorder.step = steps;
+            }
+        });
+        let mut rate = recorder.steps_per_second;
+        ui.add(egui::Slider::new(&mut rate, 1.0..=120.0).text("Steps per second"));
+        if rate != recorder.steps_per_second {
+            recorder.steps_per_second = rate;
+        }
+        let mut scale = recorder.scale;
+        ui.add(
+            egui::Slider::new(&mut scale, 1.0..=100.0)
+                .logarithmic(true)
+                .text("Exaggerate"),
+        );
+        if scale != recorder.scale {
+            recorder.scale = scale;
+        }
+        let mut min_displacement = recorder.min_displacement;
+        ui.add(
+            egui::DragValue::new(&mut min_displacement)
+                .speed(0.0001)
+                .range(0.0..=f32::MAX)
+                .prefix("Hide moves under "),
+        );
+        if min_displacement != recorder.min_displacement {
+            recorder.min_displacement = min_displacement;
+        }
+        ui.label("Colors run from blue (moved least) to red (moved most)");
+
+        if ui.button("Clear recording").clicked() {
+            recorder.trajectory = None;
+            recorder.playing = false;
+        }
+    });
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::ecs::resource::Resource;
+
+use crate::import::download::is_url;
+use crate::utils::constants::DEFAULT_REMOTE_PORT;
+
+// Command-line options; anything that isn't a flag is treated as a mesh path or URL
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CliOptions {
+    pub mesh_paths: Vec<String>,
+    // http(s) URLs, downloaded and imported once the app is running
+    pub mesh_urls: Vec<String>,
+    // Run the ray-cast benchmark once the scene is up, print the summary and exit
+    pub bench: bool,
+    pub bench_grid: Option<usize>,
+    // Run the generated-mesh regression suite, print the report and exit
+    pub selftest: bool,
+    // Load meshes through the tolerant importer (see `parse_obj_tolerant`)
+    pub tolerant: bool,
+    // Shade with recomputed normals instead of the ones stored in the file
+    pub recompute_normals: bool,
+    // Command script to run once the scene is up, exiting when it ends
+    pub run_script: Option<String>,
+    // Localhost port of the JSON-RPC control endpoint, when enabled
+    pub remote_port: Option<u16>,
+}
+
+const USAGE: &str = "usage: cgar-viewer [--bench] [--bench-grid N] [--selftest] [--tolerant] \
+[--recompute-normals] [--run SCRIPT] [--remote | --remote-port PORT] [MESH | URL]...";
+
+impl CliOptions {
+    // Usage errors go to stderr and exit with status 2; this runs before the
+    // app and its logger exist, so `warn!` would print nothing
+    pub fn from_args() -> Self {
+        Self::parse(std::env::args().skip(1)).unwrap_or_else(|err| {
+            eprintln!("cgar-viewer: {}", err);
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        })
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--bench" => options.bench = true,
+                "--selftest" => options.selftest = true,
+                "--tolerant" => options.tolerant = true,
+                "--recompute-normals" => options.recompute_normals = true,
+                "--run" => match args.next() {
+                    Some(path) => options.run_script = Some(path),
+                    None => return Err("--run expects a script path".to_string()),
+                },
+                "--remote" => options.remote_port = Some(DEFAULT_REMOTE_PORT),
+                "--remote-port" => match args.next().map(|n| n.parse::<u16>()) {
+                    Some(Ok(port)) if port > 0 => options.remote_port = Some(port),
+                    _ => return Err("--remote-port expects a port number".to_string()),
+                },
+                "--bench-grid" => match args.next().map(|n| n.parse::<usize>()) {
+                    Some(Ok(n)) if n > 0 => options.bench_grid = Some(n),
+                    _ => return Err("--bench-grid expects a positive integer".to_string()),
+                },
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unknown option {}", flag));
+                }
+                url if is_url(url) => options.mesh_urls.push(arg),
+                _ => options.mesh_paths.push(arg),
+            }
+        }
+        Ok(options)
+    }
+}
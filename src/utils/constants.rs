@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Session file, kept in a `CONFIG_DIR_NAME` folder under the per-user config
+// directory (the localStorage key in the browser build)
+pub const SESSION_FILE_NAME: &str = "cgar-viewer.session.ron";
+pub const CONFIG_DIR_NAME: &str = "cgar-viewer";
+
+// Crash-recovery snapshots, kept in the system temp directory as
+// `<stem>.<pid>.ron` beside a `<stem>.<pid>.lock`
+pub const AUTOSAVE_FILE_STEM: &str = "cgar-viewer.autosave";
+// Directory under the temp dir holding meshes downloaded from URLs
+pub const DOWNLOAD_CACHE_DIR: &str = "cgar-viewer-downloads";
+
+// Mesh sizes past which the viewer warns and starts degrading gracefully
+pub const DEFAULT_FACE_BUDGET: usize = 2_000_000;
+pub const DEFAULT_VERTEX_BUDGET: usize = 1_000_000;
+// Face count aimed for when a mesh is decimated for display
+pub const DEFAULT_DISPLAY_FACES: usize = 250_000;
+// Memory the meshes may use together before the viewer warns, in MiB
+pub const DEFAULT_MEMORY_CEILING_MIB: usize = 8192;
+// Face count of the proxies drawn while the camera moves
+pub const DEFAULT_PROXY_FACES: usize = 100_000;
+
+// Localhost port of the JSON-RPC control endpoint (`--remote`)
+pub const DEFAULT_REMOTE_PORT: u16 = 15702;
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Screen-space edge lines for `EdgeLineMaterial`. Every triangle carries
+// barycentric coordinates; dividing them by their screen-space derivatives
+// gives the distance in pixels to each side, so lines keep a constant width
+// and an antialiased falloff at any zoom. Hidden sides have their coordinate
+// offset so it never reaches zero.
+
+#import bevy_pbr::mesh_functions::{get_world_from_local, mesh_position_local_to_clip}
+
+struct EdgeLineParams {
+    color: vec4<f32>,
+    width: f32,
+    // Triangles smaller than this many pixels fade their lines out
+    fade: f32,
+}
+
+@group(2) @binding(0) var<uniform> params: EdgeLineParams;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(1) barycentric: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) barycentric: vec3<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = mesh_position_local_to_clip(
+        get_world_from_local(vertex.instance_index),
+        vec4<f32>(vertex.position, 1.0),
+    );
+    out.barycentric = vertex.barycentric;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let derivative = max(fwidth(in.barycentric), vec3<f32>(1e-6));
+    let pixels = in.barycentric / derivative;
+    let distance = min(min(pixels.x, pixels.y), pixels.z);
+    let half_width = params.width * 0.5;
+    var alpha = 1.0 - smoothstep(half_width - 0.5, half_width + 0.5, distance);
+
+    // Roughly the triangle's smallest height on screen
+    let size = 1.0 / max(max(derivative.x, derivative.y), derivative.z);
+    if params.fade > 0.0 {
+        alpha *= smoothstep(params.fade * 0.5, params.fade, size);
+    }
+    if alpha <= 0.0 {
+        discard;
+    }
+    return vec4<f32>(params.color.rgb, params.color.a * alpha);
+}
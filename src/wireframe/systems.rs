@@ -0,0 +1,389 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    asset::{Asset, Assets, Handle, RenderAssetUsages, weak_handle},
+    color::{Color, ColorToComponents, LinearRgba},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        hierarchy::ChildOf,
+        query::{Changed, Has, Or, With},
+        removal_detection::RemovedComponents,
+        resource::Resource,
+        system::{Commands, Local, Query, Res, ResMut},
+    },
+    math::Vec4,
+    pbr::{
+        Material, MaterialPipeline, MaterialPipelineKey, MeshMaterial3d, NotShadowCaster,
+        wireframe::NoWireframe,
+    },
+    picking::Pickable,
+    reflect::TypePath,
+    render::{
+        alpha::AlphaMode,
+        mesh::{Mesh, Mesh3d, MeshVertexAttribute, MeshVertexBufferLayoutRef, PrimitiveTopology},
+        render_resource::{
+            AsBindGroup, RenderPipelineDescriptor, Shader, ShaderRef, ShaderType,
+            SpecializedMeshPipelineError, VertexFormat,
+        },
+    },
+    transform::components::Transform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::budget::systems::DisplayDecimation;
+use crate::camera::components::CgarMeshData;
+use crate::mesh::features::FeatureEdges;
+use crate::mesh::topology::MeshTopology;
+
+const EDGE_LINE_SHADER: Handle<Shader> = weak_handle!("a3c1e9d4-6b27-4f80-b5e2-91d7c04f3a68");
+
+pub const ATTRIBUTE_BARYCENTRIC: MeshVertexAttribute =
+    MeshVertexAttribute::new("Barycentric", 988_540_917, VertexFormat::Float32x3);
+
+// Added to the barycentric coordinate of a hidden side so the shader never
+// finds a pixel near it
+const HIDDEN_SIDE: f32 = 1.0e4;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeLineMode {
+    #[default]
+    Off,
+    All,
+    // Tagged feature edges and open boundaries only
+    FeaturesAndBoundary,
+    Boundary,
+}
+
+impl EdgeLineMode {
+    pub const ALL: [EdgeLineMode; 4] = [
+        EdgeLineMode::Off,
+        EdgeLineMode::All,
+        EdgeLineMode::FeaturesAndBoundary,
+        EdgeLineMode::Boundary,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EdgeLineMode::Off => "Off",
+            EdgeLineMode::All => "All edges",
+            EdgeLineMode::FeaturesAndBoundary => "Feature and boundary edges",
+            EdgeLineMode::Boundary => "Boundary edges",
+        }
+    }
+}
+
+// Antialiased edges drawn in screen space over the surface, readable on
+// dense meshes where the global wireframe (W) turns into noise. Lines keep
+// their pixel width at any zoom; with every edge shown, they fade out where
+// triangles get too small on screen to tell apart.
+#[derive(Resource)]
+pub struct EdgeLines {
+    pub mode: EdgeLineMode,
+    pub width_px: f32,
+    pub color: [f32; 3],
+    pub opacity: f32,
+    // Screen size in pixels below which triangles fade their lines; 0 keeps
+    // every line
+    pub fade_px: f32,
+    material: Option<Handle<EdgeLineMaterial>>,
+}
+
+impl Default for EdgeLines {
+    fn default() -> Self {
+        Self {
+            mode: EdgeLineMode::Off,
+            width_px: 1.0,
+            color: [0.05, 0.05, 0.05],
+            opacity: 0.8,
+            fade_px: 6.0,
+            material: None,
+        }
+    }
+}
+
+impl EdgeLines {
+    fn params(&self) -> EdgeLineParams {
+        let mut color = LinearRgba::from(Color::srgb_from_array(self.color));
+        color.alpha = self.opacity;
+        EdgeLineParams {
+            color: color.to_vec4(),
+            width: self.width_px,
+            fade: if self.mode == EdgeLineMode::All {
+                self.fade_px
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+#[derive(ShaderType, Debug, Clone, Copy)]
+pub struct EdgeLineParams {
+    pub color: Vec4,
+    pub width: f32,
+    pub fade: f32,
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct EdgeLineMaterial {
+    #[uniform(0)]
+    pub params: EdgeLineParams,
+}
+
+impl Material for EdgeLineMaterial {
+    fn vertex_shader() -> ShaderRef {
+        EDGE_LINE_SHADER.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        EDGE_LINE_SHADER.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        descriptor.vertex.buffers = vec![layout.0.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            ATTRIBUTE_BARYCENTRIC.at_shader_location(1),
+        ])?];
+        // Same surface as the mesh; pulled toward the camera to win the
+        // depth test, like the built-in wireframe
+        if let Some(depth_stencil) = descriptor.depth_stencil.as_mut() {
+            depth_stencil.bias.constant = 4;
+            depth_stencil.bias.slope_scale = 1.0;
+        }
+        Ok(())
+    }
+}
+
+// Overlay mesh parented to the mesh it outlines
+#[derive(Component)]
+pub struct EdgeLineOverlay;
+
+pub fn setup_edge_line_shader(mut shaders: ResMut<Assets<Shader>>) {
+    shaders.insert(
+        &EDGE_LINE_SHADER,
+        Shader::from_wgsl(include_str!("edge_lines.wgsl"), file!()),
+    );
+}
+
+// Unindexed copy of the live faces with barycentric coordinates; faces with
+// no side to draw are left out
+fn overlay_mesh(
+    topology: &MeshTopology,
+    mode: EdgeLineMode,
+    features: Option<&FeatureEdges>,
+) -> Option<Mesh> {
+    let shown = |v0: usize, v1: usize| match mode {
+        EdgeLineMode::Off => false,
+        EdgeLineMode::All => true,
+        EdgeLineMode::FeaturesAndBoundary | EdgeLineMode::Boundary => {
+            let boundary = topology
+                .edge_faces
+                .get(&(v0.min(v1), v0.max(v1)))
+                .is_some_and(|faces| faces.len() == 1);
+            boundary
+                || (mode == EdgeLineMode::FeaturesAndBoundary
+                    && features.is_some_and(|features| features.contains(v0, v1)))
+        }
+    };
+
+    let mut positions = Vec::new();
+    let mut barycentrics = Vec::new();
+    for (_, tri) in topology.live_faces() {
+        // Side `i` is opposite corner `i`, where coordinate `i` is zero
+        let hidden = [0, 1, 2].map(|i| {
+            if shown(tri[(i + 1) % 3], tri[(i + 2) % 3]) {
+                0.0
+            } else {
+                HIDDEN_SIDE
+            }
+        });
+        if hidden.iter().all(|&offset| offset > 0.0) {
+            continue;
+        }
+        for (corner, &v) in tri.iter().enumerate() {
+            positions.push(topology.positions[v].as_vec3().to_array());
+            let mut barycentric = hidden;
+            barycentric[corner] += 1.0;
+            barycentrics.push(barycentric);
+        }
+    }
+    if positions.is_empty() {
+        return None;
+    }
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(ATTRIBUTE_BARYCENTRIC, barycentrics);
+    Some(mesh)
+}
+
+// Rebuilds a mesh's overlay when the mesh, its feature tags or the mode
+// change. Meshes drawn decimated get none, since their surface no longer
+// matches the cgar faces.
+#[allow(clippy::too_many_arguments)]
+pub fn sync_edge_lines(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<EdgeLineMaterial>>,
+    mut settings: ResMut<EdgeLines>,
+    mut applied: Local<EdgeLineMode>,
+    changed: Query<
+        Entity,
+        (
+            With<CgarMeshData>,
+            Or<(
+                Changed<CgarMeshData>,
+                Changed<FeatureEdges>,
+                Changed<DisplayDecimation>,
+            )>,
+        ),
+    >,
+    (mut removed_features, mut removed_decimation): (
+        RemovedComponents<FeatureEdges>,
+        RemovedComponents<DisplayDecimation>,
+    ),
+    mesh_query: Query<(
+        Entity,
+        &CgarMeshData,
+        Option<&FeatureEdges>,
+        Has<DisplayDecimation>,
+    )>,
+    overlay_query: Query<(Entity, &ChildOf), With<EdgeLineOverlay>>,
+) {
+    if settings.is_changed() {
+        let params = settings.params();
+        if let Some(material) = settings
+            .material
+            .as_ref()
+            .and_then(|handle| materials.get_mut(handle))
+        {
+            material.params = params;
+        }
+    }
+
+    let stale: Vec<Entity> = if settings.mode != *applied {
+        *applied = settings.mode;
+        mesh_query.iter().map(|(entity, ..)| entity).collect()
+    } else {
+        changed
+            .iter()
+            .chain(removed_features.read())
+            .chain(removed_decimation.read())
+            .collect()
+    };
+    if stale.is_empty() {
+        return;
+    }
+
+    let params = settings.params();
+    let material = settings
+        .bypass_change_detection()
+        .material
+        .get_or_insert_with(|| materials.add(EdgeLineMaterial { params }))
+        .clone();
+    for entity in stale {
+        for (overlay, parent) in &overlay_query {
+            if parent.parent() == entity {
+                commands.entity(overlay).despawn();
+            }
+        }
+        let Ok((_, cgar_data, features, decimated)) = mesh_query.get(entity) else {
+            continue;
+        };
+        if decimated || settings.mode == EdgeLineMode::Off {
+            continue;
+        }
+        let topology = MeshTopology::from_cgar(&cgar_data.0);
+        let Some(mesh) = overlay_mesh(&topology, settings.mode, features) else {
+            continue;
+        };
+        commands.spawn((
+            EdgeLineOverlay,
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(material.clone()),
+            Transform::default(),
+            Pickable::IGNORE,
+            NotShadowCaster,
+            NoWireframe,
+            ChildOf(entity),
+        ));
+    }
+}
+
+pub fn edge_lines_panel(mut contexts: EguiContexts, mut settings: ResMut<EdgeLines>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Edge Lines")
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut mode = settings.mode;
+            let mut width = settings.width_px;
+            let mut color = settings.color;
+            let mut opacity = settings.opacity;
+            let mut fade = settings.fade_px;
+            egui::ComboBox::from_label("Show")
+                .selected_text(mode.label())
+                .show_ui(ui, |ui| {
+                    for option in EdgeLineMode::ALL {
+                        ui.selectable_value(&mut mode, option, option.label());
+                    }
+                });
+            ui.add(egui::Slider::new(&mut width, 0.5..=6.0).text("Width (px)"));
+            ui.horizontal(|ui| {
+                ui.color_edit_button_rgb(&mut color);
+                ui.add(egui::Slider::new(&mut opacity, 0.05..=1.0).text("Opacity"));
+            });
+            ui.add_enabled(
+                mode == EdgeLineMode::All,
+                egui::Slider::new(&mut fade, 0.0..=20.0).text("Fade below (px)"),
+            );
+            ui.label("Meshes decimated for display are not outlined");
+
+            if mode != settings.mode
+                || width != settings.width_px
+                || color != settings.color
+                || opacity != settings.opacity
+                || fade != settings.fade_px
+            {
+                settings.mode = mode;
+                settings.width_px = width;
+                settings.color = color;
+                settings.opacity = opacity;
+                settings.fade_px = fade;
+            }
+        });
+}
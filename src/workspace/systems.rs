@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: MIT
+//
+// Copyright (c) 2025 Alexandre Severino
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::{Or, With, Without},
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::Vec3,
+    render::view::Visibility,
+    transform::components::Transform,
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::camera::components::{CgarMeshData, OrbitCamera};
+use crate::outliner::systems::MeshGroup;
+use crate::pointcloud::components::PointCloud;
+use crate::selection::components::SelectionSet;
+
+// Workspace a mesh, point cloud or group was loaded into
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkspaceMember(pub usize);
+
+// Hides a member of an inactive workspace, keeping the visibility it had
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Stashed(pub Visibility);
+
+#[derive(Debug, Clone, Copy)]
+struct CameraState {
+    transform: Transform,
+    focus: Vec3,
+    radius: f32,
+    upside_down: bool,
+}
+
+impl CameraState {
+    fn capture(transform: &Transform, orbit: &OrbitCamera) -> Self {
+        Self {
+            transform: *transform,
+            focus: orbit.focus,
+            radius: orbit.radius,
+            upside_down: orbit.upside_down,
+        }
+    }
+
+    fn restore(self, transform: &mut Transform, orbit: &mut OrbitCamera) {
+        *transform = self.transform;
+        orbit.focus = self.focus;
+        orbit.radius = self.radius;
+        orbit.upside_down = self.upside_down;
+        orbit.focus_target = None;
+    }
+}
+
+struct Workspace {
+    id: usize,
+    name: String,
+    // Saved when the workspace is left; `None` keeps the camera as it is
+    camera: Option<CameraState>,
+    selection: SelectionSet,
+}
+
+impl Workspace {
+    fn new(id: usize) -> Self {
+        Self {
+            id,
+            name: format!("Workspace {}", id + 1),
+            camera: None,
+            selection: SelectionSet::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TabRequest {
+    Switch(usize),
+    Close(usize),
+}
+
+// Independent sets of loaded meshes, each with its own camera and
+// selection. Only the active one is shown; the others' entities stay
+// loaded but stashed.
+#[derive(Resource)]
+pub struct Workspaces {
+    tabs: Vec<Workspace>,
+    // Id of the shown workspace
+    active: usize,
+    next_id: usize,
+    request: Option<TabRequest>,
+}
+
+impl Default for Workspaces {
+    fn default() -> Self {
+        Self {
+            tabs: vec![Workspace::new(0)],
+            active: 0,
+            next_id: 1,
+            request: None,
+        }
+    }
+}
+
+// Puts newly spawned meshes, clouds and groups in the active workspace
+pub fn tag_workspace_members(
+    mut commands: Commands,
+    workspaces: Res<Workspaces>,
+    new_query: Query<
+        Entity,
+        (
+            Or<(With<CgarMeshData>, With<PointCloud>, With<MeshGroup>)>,
+            Without<WorkspaceMember>,
+        ),
+    >,
+) {
+    for entity in &new_query {
+        commands
+            .entity(entity)
+            .insert(WorkspaceMember(workspaces.active));
+    }
+}
+
+// Tab bar along the top of the window
+pub fn workspace_tabs(mut contexts: EguiContexts, mut workspaces: ResMut<Workspaces>) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut request = None;
+    let mut added = false;
+    let mut renamed = None;
+    egui::TopBottomPanel::top("workspace_tabs").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            for tab in &workspaces.tabs {
+                let active = tab.id == workspaces.active;
+                if active {
+                    let mut name = tab.name.clone();
+                    ui.add(egui::TextEdit::singleline(&mut name).desired_width(120.0));
+                    if name != tab.name {
+                        renamed = Some(name);
+                    }
+                } else if ui.selectable_label(false, &tab.name).clicked() {
+                    request = Some(TabRequest::Switch(tab.id));
+                }
+                if workspaces.tabs.len() > 1 && ui.small_button("x").clicked() {
+                    request = Some(TabRequest::Close(tab.id));
+                }
+                ui.separator();
+            }
+            added = ui.button("+").on_hover_text("New workspace").clicked();
+        });
+    });
+
+    if let Some(name) = renamed {
+        let active = workspaces.active;
+        if let Some(tab) = workspaces.tabs.iter_mut().find(|tab| tab.id == active) {
+            tab.name = name;
+        }
+    }
+    if added {
+        let id = workspaces.next_id;
+        workspaces.next_id += 1;
+        workspaces.tabs.push(Workspace::new(id));
+        request = Some(TabRequest::Switch(id));
+    }
+    if request.is_some() {
+        workspaces.request = request;
+    }
+}
+
+// Shows `tab`'s entities, camera and selection and stashes every other
+// workspace's entities
+fn activate(
+    tab: &Workspace,
+    commands: &mut Commands,
+    selection: &mut SelectionSet,
+    camera_query: &mut Query<(&mut Transform, &mut OrbitCamera)>,
+    member_query: &mut Query<(Entity, &WorkspaceMember, &mut Visibility, Option<&Stashed>)>,
+) {
+    for (entity, member, mut visibility, stashed) in member_query {
+        match (member.0 == tab.id, stashed) {
+            (true, Some(stashed)) => {
+                *visibility = stashed.0;
+                commands.entity(entity).remove::<Stashed>();
+            }
+            (false, None) => {
+                commands.entity(entity).insert(Stashed(*visibility));
+                *visibility = Visibility::Hidden;
+            }
+            _ => {}
+        }
+    }
+    if let Some(camera) = tab.camera {
+        for (mut transform, mut orbit) in camera_query {
+            camera.restore(&mut transform, &mut orbit);
+        }
+    }
+    *selection = tab.selection.clone();
+}
+
+// Applies a tab switch or close. The workspace being left keeps its camera
+// and selection; a closed one despawns its entities.
+pub fn switch_workspace(
+    mut commands: Commands,
+    mut workspaces: ResMut<Workspaces>,
+    mut selection: ResMut<SelectionSet>,
+    mut camera_query: Query<(&mut Transform, &mut OrbitCamera)>,
+    mut member_query: Query<(Entity, &WorkspaceMember, &mut Visibility, Option<&Stashed>)>,
+) {
+    let Some(request) = workspaces.request.take() else {
+        return;
+    };
+    let workspaces = &mut *workspaces;
+    match request {
+        TabRequest::Switch(id) => {
+            if id == workspaces.active {
+                return;
+            }
+            let Some(next) = workspaces.tabs.iter().position(|tab| tab.id == id) else {
+                return;
+            };
+            let camera = camera_query
+                .iter()
+                .next()
+                .map(|(transform, orbit)| CameraState::capture(transform, orbit));
+            let active = workspaces.active;
+            if let Some(tab) = workspaces.tabs.iter_mut().find(|tab| tab.id == active) {
+                tab.camera = camera;
+                tab.selection = std::mem::take(&mut *selection);
+            }
+            workspaces.active = id;
+            activate(
+                &workspaces.tabs[next],
+                &mut commands,
+                &mut selection,
+                &mut camera_query,
+                &mut member_query,
+            );
+        }
+        TabRequest::Close(id) => {
+            let Some(index) = workspaces.tabs.iter().position(|tab| tab.id == id) else {
+                return;
+            };
+            if workspaces.tabs.len() < 2 {
+                return;
+            }
+            workspaces.tabs.remove(index);
+            // Closing the shown tab moves to its neighbor
+            if id == workspaces.active {
+                let next = &workspaces.tabs[index.min(workspaces.tabs.len() - 1)];
+                workspaces.active = next.id;
+                activate(
+                    next,
+                    &mut commands,
+                    &mut selection,
+                    &mut camera_query,
+                    &mut member_query,
+                );
+            }
+            for (entity, member, ..) in &member_query {
+                if member.0 == id {
+                    commands.entity(entity).despawn();
+                }
+            }
+        }
+    }
+}